@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TIMESTAMP_SHIM: &str = "#[serde(with = \"crate::codec::timestamp\")]";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
@@ -7,8 +10,66 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("cargo:rerun-if-changed={}", proto_file.display());
 
+    let lock_file = manifest_dir.join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_file.display());
+    let (servo_version, servo_commit) = servo_lock_info(&lock_file);
+    println!("cargo:rustc-env=BROWSERD_SERVO_VERSION={servo_version}");
+    println!("cargo:rustc-env=BROWSERD_SERVO_COMMIT={servo_commit}");
+
+    // Serde derives back the `--protocol json` codec. Well-known types have
+    // no serde impls in prost-types, so their fields go through shims in
+    // `codec.rs`.
     prost_build::Config::new()
+        .message_attribute(
+            ".",
+            "#[derive(serde::Serialize, serde::Deserialize)] #[serde(default)]",
+        )
+        .enum_attribute(
+            ".",
+            "#[derive(serde::Serialize, serde::Deserialize)] #[serde(rename_all = \"snake_case\")]",
+        )
+        .field_attribute("Observation.timestamp", TIMESTAMP_SHIM)
+        .field_attribute("Frame.timestamp", TIMESTAMP_SHIM)
+        .field_attribute("Frame.presentation_timestamp", TIMESTAMP_SHIM)
+        .field_attribute("StreamEvent.timestamp", TIMESTAMP_SHIM)
+        .field_attribute("ConsoleMessage.timestamp", TIMESTAMP_SHIM)
+        .field_attribute(
+            "Effect.metadata",
+            "#[serde(with = \"crate::codec::json_struct\")]",
+        )
         .compile_protos(&[proto_file], &[proto_dir])?;
 
     Ok(())
 }
+
+/// Read the pinned libservo version and git commit from Cargo.lock so
+/// responses can report exactly which engine build produced them.
+fn servo_lock_info(lock_file: &Path) -> (String, String) {
+    let unknown = || ("unknown".to_string(), "unknown".to_string());
+    let Ok(contents) = fs::read_to_string(lock_file) else {
+        return unknown();
+    };
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() != "name = \"libservo\"" {
+            continue;
+        }
+        let mut version = "unknown".to_string();
+        let mut commit = "unknown".to_string();
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("[[") {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("version = ") {
+                version = value.trim_matches('"').to_string();
+            } else if let Some(value) = line.strip_prefix("source = ") {
+                if let Some((_, rev)) = value.trim_matches('"').rsplit_once('#') {
+                    commit = rev.to_string();
+                }
+            }
+        }
+        return (version, commit);
+    }
+    unknown()
+}
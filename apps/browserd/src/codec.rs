@@ -0,0 +1,275 @@
+//! Wire framing for the daemon socket.
+//!
+//! The default protocol is length-prefixed protobuf. `--protocol json`
+//! switches a daemon to newline-delimited JSON so clients without a protobuf
+//! library (shell scripts, small Python tools) can drive it. JSON messages use
+//! the prost structs' serde derives: proto field names, enums as their numeric
+//! values, oneofs as `{"<field_name>": {...}}` and bytes as number arrays.
+
+use std::io::{self, BufRead, Read, Write};
+
+use crate::proto as pb;
+
+/// Framing used for every message on a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Protocol {
+    #[default]
+    Protobuf,
+    Json,
+}
+
+impl Protocol {
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "protobuf" | "proto" => Ok(Self::Protobuf),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown protocol: {other} (expected protobuf or json)"
+            )),
+        }
+    }
+
+    pub(crate) fn read_envelope<R: BufRead>(
+        self,
+        reader: &mut R,
+    ) -> io::Result<Option<pb::Envelope>> {
+        match self {
+            Self::Protobuf => crate::read_envelope(reader),
+            Self::Json => read_json_envelope(reader),
+        }
+    }
+
+    /// Write one envelope. `compression` only applies to protobuf frames;
+    /// JSON lines are always sent as plain text.
+    pub(crate) fn write_envelope<W: Write>(
+        self,
+        writer: &mut W,
+        envelope: pb::Envelope,
+        compression: pb::StreamCompression,
+    ) -> io::Result<()> {
+        let frame = self.encode_envelope(envelope, compression)?;
+        writer.write_all(&frame)?;
+        writer.flush()
+    }
+
+    /// Encode one envelope into the exact bytes `write_envelope` would send.
+    pub(crate) fn encode_envelope(
+        self,
+        envelope: pb::Envelope,
+        compression: pb::StreamCompression,
+    ) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Protobuf => crate::encode_frame(envelope, compression),
+            Self::Json => encode_json_envelope(&envelope),
+        }
+    }
+}
+
+/// Read one JSON envelope per line, skipping blank lines. Lines are capped at
+/// the message limit like protobuf frames.
+fn read_json_envelope<R: BufRead>(reader: &mut R) -> io::Result<Option<pb::Envelope>> {
+    let max = crate::max_message_bytes();
+    let limit = max as u64 + 1;
+    loop {
+        let mut line = Vec::new();
+        let read = Read::take(&mut *reader, limit).read_until(b'\n', &mut line)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if line.len() > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("message too large: more than {max} bytes"),
+            ));
+        }
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let envelope = serde_json::from_slice(&line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        return Ok(Some(envelope));
+    }
+}
+
+fn encode_json_envelope(envelope: &pb::Envelope) -> io::Result<Vec<u8>> {
+    // serde_json never emits raw newlines, so one envelope is one line.
+    let mut buf = serde_json::to_vec(envelope)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    crate::check_message_size(buf.len(), crate::max_message_bytes())?;
+    buf.push(b'\n');
+    Ok(buf)
+}
+
+/// Serde shim for `google.protobuf.Timestamp` fields, written as
+/// `{"seconds": .., "nanos": ..}`.
+pub(crate) mod timestamp {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct TimestampJson {
+        seconds: i64,
+        #[serde(default)]
+        nanos: i32,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<prost_types::Timestamp>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .as_ref()
+            .map(|ts| TimestampJson {
+                seconds: ts.seconds,
+                nanos: ts.nanos,
+            })
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<prost_types::Timestamp>, D::Error> {
+        let value = Option::<TimestampJson>::deserialize(deserializer)?;
+        Ok(value.map(|ts| prost_types::Timestamp {
+            seconds: ts.seconds,
+            nanos: ts.nanos,
+        }))
+    }
+}
+
+/// Serde shim for `google.protobuf.Struct` fields, written as a plain JSON
+/// object.
+pub(crate) mod json_struct {
+    use prost_types::value::Kind;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value as Json;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<prost_types::Struct>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(struct_to_json).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<prost_types::Struct>, D::Error> {
+        match Option::<Json>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(Json::Object(map)) => Ok(Some(prost_types::Struct {
+                fields: map
+                    .into_iter()
+                    .map(|(k, v)| (k, json_to_value(v)))
+                    .collect(),
+            })),
+            Some(_) => Err(D::Error::custom("expected a JSON object")),
+        }
+    }
+
+    fn struct_to_json(value: &prost_types::Struct) -> Json {
+        Json::Object(
+            value
+                .fields
+                .iter()
+                .map(|(k, v)| (k.clone(), value_to_json(v)))
+                .collect(),
+        )
+    }
+
+    fn value_to_json(value: &prost_types::Value) -> Json {
+        match &value.kind {
+            None | Some(Kind::NullValue(_)) => Json::Null,
+            Some(Kind::NumberValue(n)) => serde_json::Number::from_f64(*n)
+                .map(Json::Number)
+                .unwrap_or(Json::Null),
+            Some(Kind::StringValue(s)) => Json::String(s.clone()),
+            Some(Kind::BoolValue(b)) => Json::Bool(*b),
+            Some(Kind::StructValue(s)) => struct_to_json(s),
+            Some(Kind::ListValue(list)) => {
+                Json::Array(list.values.iter().map(value_to_json).collect())
+            }
+        }
+    }
+
+    fn json_to_value(value: Json) -> prost_types::Value {
+        let kind = match value {
+            Json::Null => Kind::NullValue(0),
+            Json::Bool(b) => Kind::BoolValue(b),
+            Json::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+            Json::String(s) => Kind::StringValue(s),
+            Json::Array(items) => Kind::ListValue(prost_types::ListValue {
+                values: items.into_iter().map(json_to_value).collect(),
+            }),
+            Json::Object(map) => Kind::StructValue(prost_types::Struct {
+                fields: map
+                    .into_iter()
+                    .map(|(k, v)| (k, json_to_value(v)))
+                    .collect(),
+            }),
+        };
+        prost_types::Value { kind: Some(kind) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_session() -> pb::Envelope {
+        pb::Envelope {
+            message: Some(pb::envelope::Message::Request(pb::Request {
+                request_id: "req-1".to_string(),
+                session_id: "json-session".to_string(),
+                payload: Some(pb::request::Payload::CreateSession(
+                    pb::CreateSessionRequest {
+                        config: Some(pb::SessionConfig {
+                            session_id: "json-session".to_string(),
+                            initial_url: "https://example.com".to_string(),
+                            ..Default::default()
+                        }),
+                    },
+                )),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_parse_protocol() {
+        assert_eq!(Protocol::parse("json"), Ok(Protocol::Json));
+        assert_eq!(Protocol::parse(" Protobuf "), Ok(Protocol::Protobuf));
+        assert!(Protocol::parse("xml").is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip_create_session() {
+        let mut pipe = Vec::new();
+        for _ in 0..2 {
+            Protocol::Json
+                .write_envelope(&mut pipe, create_session(), pb::StreamCompression::Gzip)
+                .expect("write");
+        }
+        assert_eq!(pipe.iter().filter(|b| **b == b'\n').count(), 2);
+        assert_eq!(pipe.last(), Some(&b'\n'));
+
+        let mut reader = &pipe[..];
+        for _ in 0..2 {
+            let envelope = Protocol::Json
+                .read_envelope(&mut reader)
+                .expect("read")
+                .expect("envelope");
+            assert_eq!(envelope, create_session());
+        }
+        assert!(Protocol::Json
+            .read_envelope(&mut reader)
+            .expect("eof")
+            .is_none());
+    }
+
+    #[test]
+    fn test_json_rejects_malformed_line() {
+        let mut reader = &b"{not json\n"[..];
+        let err = Protocol::Json
+            .read_envelope(&mut reader)
+            .expect_err("malformed");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
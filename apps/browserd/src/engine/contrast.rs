@@ -0,0 +1,147 @@
+//! WCAG 2.x color contrast helpers shared by the engines.
+
+use crate::proto as pb;
+
+/// Minimum contrast ratios from WCAG 2.1 success criteria 1.4.3 and 1.4.6.
+const AA_NORMAL: f64 = 4.5;
+const AA_LARGE: f64 = 3.0;
+const AAA_NORMAL: f64 = 7.0;
+const AAA_LARGE: f64 = 4.5;
+
+/// Large text is at least 18pt (24px), or 14pt (~18.66px) when bold.
+const LARGE_TEXT_PX: f64 = 24.0;
+const LARGE_BOLD_TEXT_PX: f64 = 18.66;
+const BOLD_WEIGHT: u32 = 700;
+
+/// An sRGB color with channels in 0..=255 and alpha in 0.0..=1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Rgba {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+impl Rgba {
+    pub fn opaque(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r as f64,
+            g: g as f64,
+            b: b as f64,
+            a: 1.0,
+        }
+    }
+
+    /// Composite this color over an opaque `background`.
+    fn over(self, background: Rgba) -> Rgba {
+        let alpha = self.a.clamp(0.0, 1.0);
+        let mix = |fg: f64, bg: f64| fg * alpha + bg * (1.0 - alpha);
+        Rgba {
+            r: mix(self.r, background.r),
+            g: mix(self.g, background.g),
+            b: mix(self.b, background.b),
+            a: 1.0,
+        }
+    }
+
+    fn relative_luminance(self) -> f64 {
+        let channel = |value: f64| {
+            let c = (value / 255.0).clamp(0.0, 1.0);
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(self.r) + 0.7152 * channel(self.g) + 0.0722 * channel(self.b)
+    }
+
+    pub fn css(self) -> String {
+        if self.a >= 1.0 {
+            format!(
+                "rgb({}, {}, {})",
+                self.r.round(),
+                self.g.round(),
+                self.b.round()
+            )
+        } else {
+            format!(
+                "rgba({}, {}, {}, {})",
+                self.r.round(),
+                self.g.round(),
+                self.b.round(),
+                self.a
+            )
+        }
+    }
+}
+
+/// WCAG contrast ratio between a foreground and background color. A
+/// translucent background is treated as sitting on white.
+pub(crate) fn contrast_ratio(foreground: Rgba, background: Rgba) -> f64 {
+    let background = background.over(Rgba::opaque(255, 255, 255));
+    let foreground = foreground.over(background);
+    let l1 = foreground.relative_luminance();
+    let l2 = background.relative_luminance();
+    let (lighter, darker) = if l1 >= l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+pub(crate) fn is_large_text(font_size_px: f64, font_weight: u32) -> bool {
+    font_size_px >= LARGE_TEXT_PX
+        || (font_weight >= BOLD_WEIGHT && font_size_px >= LARGE_BOLD_TEXT_PX)
+}
+
+/// Build a contrast result, grading the ratio against the AA and AAA levels.
+pub(crate) fn contrast_result(
+    node_id: u64,
+    text: String,
+    foreground: Rgba,
+    background: Rgba,
+    font_size_px: f64,
+    font_weight: u32,
+) -> pb::ContrastResult {
+    let ratio = contrast_ratio(foreground, background);
+    let large_text = is_large_text(font_size_px, font_weight);
+    let (aa, aaa) = if large_text {
+        (AA_LARGE, AAA_LARGE)
+    } else {
+        (AA_NORMAL, AAA_NORMAL)
+    };
+    pb::ContrastResult {
+        node_id,
+        text,
+        color: foreground.css(),
+        background_color: background.css(),
+        contrast_ratio: (ratio * 100.0).round() / 100.0,
+        large_text,
+        passes_aa: ratio >= aa,
+        passes_aaa: ratio >= aaa,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio_extremes() {
+        let black = Rgba::opaque(0, 0, 0);
+        let white = Rgba::opaque(255, 255, 255);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_contrast_result_grading() {
+        // #777 on white is ~4.48:1: fails AA for body text, passes for large text.
+        let gray = Rgba::opaque(0x77, 0x77, 0x77);
+        let white = Rgba::opaque(255, 255, 255);
+        let normal = contrast_result(1, String::new(), gray, white, 16.0, 400);
+        assert!(!normal.passes_aa);
+        let large = contrast_result(1, String::new(), gray, white, 24.0, 400);
+        assert!(large.large_text);
+        assert!(large.passes_aa);
+        assert!(!large.passes_aaa);
+    }
+}
@@ -0,0 +1,289 @@
+//! Per-session download storage shared by the engines.
+//!
+//! Files live in a private temp dir, created on the first download and removed
+//! with the store. The total size of
+//! kept files is capped; a download that doesn't fit is dropped rather than
+//! evicting older ones, so ids handed to the client stay valid.
+
+use super::{session_temp_dir, EngineError};
+use crate::proto as pb;
+use prost_types::{value, Struct, Value};
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Cap used when neither the session nor the daemon sets one.
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+pub(crate) struct DownloadStore {
+    session_id: String,
+    dir: Option<tempfile::TempDir>,
+    enabled: bool,
+    max_bytes: u64,
+    total_bytes: u64,
+    next_id: u64,
+    downloads: Vec<pb::Download>,
+}
+
+impl DownloadStore {
+    pub fn new(config: &pb::SessionConfig) -> Self {
+        let max_bytes = if config.max_download_bytes > 0 {
+            config.max_download_bytes
+        } else {
+            DEFAULT_MAX_DOWNLOAD_BYTES
+        };
+        Self {
+            session_id: config.session_id.clone(),
+            dir: None,
+            enabled: config.downloads_enabled,
+            max_bytes,
+            total_bytes: 0,
+            next_id: 1,
+            downloads: Vec::new(),
+        }
+    }
+
+    /// Bytes left under the cap.
+    pub fn remaining(&self) -> u64 {
+        self.max_bytes.saturating_sub(self.total_bytes)
+    }
+
+    /// Check a download of `bytes` against the policy before reading it.
+    pub fn admit(&self, filename: &str, bytes: u64) -> Result<(), EngineError> {
+        if !self.enabled {
+            return Err(EngineError::new(
+                "downloads_disabled",
+                format!("download of {filename:?} cancelled; downloads are disabled"),
+            ));
+        }
+        if bytes > self.remaining() {
+            return Err(EngineError::new(
+                "download_limit",
+                format!(
+                    "download of {filename:?} ({bytes} bytes) exceeds the {} bytes left for this session",
+                    self.remaining()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Keep a finished download and return its entry.
+    pub fn save(
+        &mut self,
+        url: &str,
+        filename: &str,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Result<pb::Download, EngineError> {
+        let filename = download_filename(filename, url);
+        self.admit(&filename, data.len() as u64)?;
+        let id = format!("download-{}", self.next_id);
+        let dir = match &mut self.dir {
+            Some(dir) => dir,
+            dir => dir.insert(session_temp_dir(&self.session_id, "downloads")?),
+        };
+        fs::write(dir.path().join(&id), data).map_err(|e| {
+            EngineError::new("unavailable", format!("failed to store download: {e}"))
+        })?;
+        self.next_id += 1;
+        self.total_bytes += data.len() as u64;
+        let download = pb::Download {
+            id,
+            filename,
+            url: url.to_string(),
+            mime_type: mime_type.to_string(),
+            bytes: data.len() as u64,
+        };
+        self.downloads.push(download.clone());
+        Ok(download)
+    }
+
+    pub fn list(&self) -> pb::ListDownloadsResponse {
+        pb::ListDownloadsResponse {
+            downloads: self.downloads.clone(),
+            total_bytes: self.total_bytes,
+            max_bytes: self.max_bytes,
+            enabled: self.enabled,
+        }
+    }
+
+    pub fn get(&self, download_id: &str) -> Result<pb::GetDownloadResponse, EngineError> {
+        let download = self
+            .downloads
+            .iter()
+            .find(|download| download.id == download_id)
+            .ok_or_else(|| {
+                EngineError::new(
+                    "download_not_found",
+                    format!("no download with id {download_id:?}"),
+                )
+            })?;
+        // A listed download was saved, so the dir exists.
+        let path = self
+            .dir
+            .as_ref()
+            .map(|dir| dir.path().join(&download.id))
+            .unwrap_or_default();
+        let data = fs::read(path).map_err(|e| {
+            EngineError::new("unavailable", format!("failed to read download: {e}"))
+        })?;
+        Ok(pb::GetDownloadResponse {
+            download: Some(download.clone()),
+            data,
+        })
+    }
+}
+
+/// Effect reported for a download started during an action. Rejected
+/// downloads use the error code as the kind (`downloads_disabled`,
+/// `download_limit`).
+pub(crate) fn download_effect(
+    filename: &str,
+    outcome: &Result<pb::Download, EngineError>,
+) -> pb::Effect {
+    let mut fields = BTreeMap::new();
+    let mut insert = |key: &str, kind: value::Kind| {
+        fields.insert(key.to_string(), Value { kind: Some(kind) });
+    };
+    match outcome {
+        Ok(download) => {
+            insert("download_id", value::Kind::StringValue(download.id.clone()));
+            insert(
+                "filename",
+                value::Kind::StringValue(download.filename.clone()),
+            );
+            insert("bytes", value::Kind::NumberValue(download.bytes as f64));
+            pb::Effect {
+                kind: "download".to_string(),
+                summary: format!(
+                    "downloaded {:?} ({} bytes)",
+                    download.filename, download.bytes
+                ),
+                metadata: Some(Struct { fields }),
+            }
+        }
+        Err(err) => {
+            insert("filename", value::Kind::StringValue(filename.to_string()));
+            pb::Effect {
+                kind: err.code.to_string(),
+                summary: err.message.clone(),
+                metadata: Some(Struct { fields }),
+            }
+        }
+    }
+}
+
+/// The name a download is listed under: the suggested name reduced to a base
+/// name, falling back to the URL's last path segment.
+pub(crate) fn download_filename(suggested: &str, url: &str) -> String {
+    let from_url = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default();
+    [suggested, from_url]
+        .into_iter()
+        .map(|name| {
+            name.rsplit(['/', '\\'])
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect::<String>()
+        })
+        .find(|name| !name.is_empty() && name != "." && name != "..")
+        .unwrap_or_else(|| "download".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(max_download_bytes: u64) -> DownloadStore {
+        DownloadStore::new(&pb::SessionConfig {
+            session_id: format!("downloads-test-{max_download_bytes}"),
+            downloads_enabled: true,
+            max_download_bytes,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_list_and_get_downloads() {
+        let mut store = store(0);
+        let saved = store
+            .save(
+                "https://example.com/files/report.csv?v=2",
+                "",
+                "text/csv",
+                b"a,b\n1,2\n",
+            )
+            .ok()
+            .expect("save");
+        assert_eq!(saved.filename, "report.csv");
+
+        let list = store.list();
+        assert_eq!(list.downloads, vec![saved.clone()]);
+        assert_eq!(list.total_bytes, 8);
+        assert_eq!(list.max_bytes, DEFAULT_MAX_DOWNLOAD_BYTES);
+
+        let got = store.get(&saved.id).ok().expect("get");
+        assert_eq!(got.data, b"a,b\n1,2\n");
+        let err = store.get("download-99").expect_err("unknown id");
+        assert_eq!(err.code, "download_not_found");
+    }
+
+    #[test]
+    fn test_download_size_cap() {
+        let mut store = store(10);
+        assert!(store
+            .save("https://example.com/a", "a.bin", "", &[0; 6])
+            .is_ok());
+        let err = store
+            .save("https://example.com/b", "b.bin", "", &[0; 6])
+            .expect_err("over cap");
+        assert_eq!(err.code, "download_limit");
+        assert_eq!(store.remaining(), 4);
+        assert!(store
+            .save("https://example.com/c", "c.bin", "", &[0; 4])
+            .is_ok());
+        assert_eq!(store.list().downloads.len(), 2);
+    }
+
+    #[test]
+    fn test_dropping_a_store_keeps_other_sessions_files() {
+        // Both ids reduce to the same filename-safe name.
+        let session = |session_id: &str| {
+            DownloadStore::new(&pb::SessionConfig {
+                session_id: session_id.to_string(),
+                downloads_enabled: true,
+                ..Default::default()
+            })
+        };
+        let mut first = session("shared.id");
+        let mut second = session("shared_id");
+        first
+            .save("https://example.com/a", "a.txt", "", b"a")
+            .ok()
+            .expect("save");
+        let kept = second
+            .save("https://example.com/b", "b.txt", "", b"b")
+            .ok()
+            .expect("save");
+        drop(first);
+        assert_eq!(second.get(&kept.id).ok().expect("get").data, b"b");
+    }
+
+    #[test]
+    fn test_disabled_store_rejects_downloads() {
+        let mut store = DownloadStore::new(&pb::SessionConfig {
+            session_id: "downloads-disabled".to_string(),
+            ..Default::default()
+        });
+        let outcome = store.save("https://example.com/a", "../a.txt", "", b"x");
+        let effect = download_effect("a.txt", &outcome);
+        assert_eq!(effect.kind, "downloads_disabled");
+        assert!(store.list().downloads.is_empty());
+        assert_eq!(download_filename("../a.txt", ""), "a.txt");
+    }
+}
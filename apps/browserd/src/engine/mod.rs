@@ -1,9 +1,89 @@
 use crate::proto as pb;
+use ipnet::IpNet;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::os::unix::fs::PermissionsExt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
 use url::Url;
 
-mod stub;
+mod contrast;
+mod downloads;
+mod pdf;
+mod profiles;
 #[cfg(feature = "servo")]
 mod servo;
+mod stub;
+
+pub use downloads::DEFAULT_MAX_DOWNLOAD_BYTES;
+
+pub(crate) const DEFAULT_NAVIGATION_TIMEOUT_MS: u32 = 30_000;
+const DEFAULT_DRAG_STEPS: u32 = 10;
+const MAX_DRAG_STEPS: u32 = 100;
+const MAX_CLICK_COUNT: u32 = 3;
+const MAX_KEY_REPEAT: u32 = 100;
+const MAX_ACTION_TIMEOUT_MS: u32 = 300_000;
+const MAX_VIEWPORT_DIMENSION: u32 = 16_384;
+const DEFAULT_VIEWPORT_WIDTH: u32 = 1280;
+const DEFAULT_VIEWPORT_HEIGHT: u32 = 720;
+const MAX_DEVICE_SCALE_FACTOR: f64 = 8.0;
+/// Default pixel ceiling for captured frames, a 4K screen.
+const DEFAULT_MAX_FRAME_PIXELS: u64 = 3840 * 2160;
+/// Default cap on the text one type action may carry. Engines type one key
+/// or composition per character, blocking the session while they do.
+const DEFAULT_MAX_TYPE_CHARS: usize = 10_000;
+/// Largest `BROWSERD_SERVO_STACK_MB` accepted.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+const MAX_RUNTIME_STACK_MB: usize = 1024;
+const DEFAULT_POST_LOAD_SETTLE_MS: u32 = 100;
+const MAX_POST_LOAD_SETTLE_MS: u32 = 10_000;
+const MAX_FORM_FIELDS: usize = 100;
+const MAX_STORAGE_ENTRIES: usize = 100;
+const MAX_QUERY_PROPERTIES: usize = 32;
+const DEFAULT_MAX_HTML_BYTES: u32 = 256 * 1024;
+const MAX_HTML_BYTES: u32 = 4 * 1024 * 1024;
+/// SHA-256 bytes kept in snapshot content hashes: 64 bits is plenty to tell
+/// consecutive snapshots apart.
+const CONTENT_HASH_BYTES: usize = 8;
+/// Unread console messages kept per session; older ones are dropped.
+pub(crate) const MAX_CONSOLE_MESSAGES: usize = 200;
+pub(crate) const MAX_CONSOLE_MESSAGE_CHARS: usize = 2000;
+/// Unreported page errors kept per session; older ones are dropped.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+pub(crate) const MAX_PAGE_ERRORS: usize = 50;
+const MAX_STYLE_NODES: usize = 32;
+const MAX_STYLE_PROPERTIES: usize = 32;
+const DEFAULT_HIT_TEST_REGIONS: u32 = 250;
+const MAX_HIT_TEST_REGIONS: u32 = 2000;
+const MAX_HIT_TEST_SELECTORS: usize = 32;
+const DEFAULT_STYLE_PROPERTIES: &[&str] = &[
+    "display",
+    "visibility",
+    "position",
+    "width",
+    "height",
+    "color",
+    "background-color",
+    "font-size",
+    "font-family",
+];
+/// Headers the network stack must own; letting callers set them would
+/// allow request smuggling or break framing.
+const FORBIDDEN_REQUEST_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "keep-alive",
+    "transfer-encoding",
+    "te",
+    "trailer",
+    "upgrade",
+];
+pub const MAX_NAVIGATION_TIMEOUT_MS: u32 = 300_000;
 
 pub struct EngineError {
     pub code: &'static str,
@@ -19,38 +99,1097 @@ impl EngineError {
     }
 }
 
+/// Map the string codes used by `EngineError` and `error_response` onto the
+/// typed protocol enum. Unknown codes map to `Unspecified`; the string code is
+/// always sent alongside for clients that predate the enum.
+impl From<&str> for pb::ErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "invalid_request" => pb::ErrorCode::InvalidRequest,
+            "invalid_session" => pb::ErrorCode::InvalidSession,
+            "stale_state" => pb::ErrorCode::StaleState,
+            "load_timeout" => pb::ErrorCode::LoadTimeout,
+            "script_error" => pb::ErrorCode::ScriptError,
+            "script_timeout" => pb::ErrorCode::ScriptTimeout,
+            "unavailable" => pb::ErrorCode::Unavailable,
+            "invalid_url" => pb::ErrorCode::InvalidUrl,
+            "invalid_target" => pb::ErrorCode::InvalidTarget,
+            "no_webview" => pb::ErrorCode::NoWebview,
+            "render_failed" => pb::ErrorCode::RenderFailed,
+            "rendering_init" => pb::ErrorCode::RenderingInit,
+            "clipboard_denied" => pb::ErrorCode::ClipboardDenied,
+            "clipboard_limit" => pb::ErrorCode::ClipboardLimit,
+            "cookie_denied" => pb::ErrorCode::CookieDenied,
+            "wait_timeout" => pb::ErrorCode::WaitTimeout,
+            "session_limit" => pb::ErrorCode::SessionLimit,
+            "unsupported" => pb::ErrorCode::Unsupported,
+            "rate_limited" => pb::ErrorCode::RateLimited,
+            "script_budget_exceeded" => pb::ErrorCode::ScriptBudgetExceeded,
+            "load_failed" => pb::ErrorCode::LoadFailed,
+            "unauthenticated" => pb::ErrorCode::Unauthenticated,
+            "host_denied" => pb::ErrorCode::HostDenied,
+            "action_timeout" => pb::ErrorCode::ActionTimeout,
+            "tab_not_found" => pb::ErrorCode::TabNotFound,
+            "uploads_disabled" => pb::ErrorCode::UploadsDisabled,
+            "download_not_found" => pb::ErrorCode::DownloadNotFound,
+            "storage_denied" => pb::ErrorCode::StorageDenied,
+            "engine_panic" => pb::ErrorCode::EnginePanic,
+            "response_too_large" => pb::ErrorCode::ResponseTooLarge,
+            _ => pb::ErrorCode::Unspecified,
+        }
+    }
+}
+
+/// A pending network-idle wait that doesn't borrow the engine, so the
+/// daemon can run it without holding the sessions lock. Yields whether the
+/// network went quiet before the navigation timeout.
+pub type NetworkIdleWait = Box<dyn FnOnce() -> bool + Send>;
+
+/// Cuts short a navigate that is still waiting for its page. Raising it
+/// doesn't touch the engine, so a Stop can reach a navigate that holds the
+/// sessions lock; the engine clears it once it has stopped the load.
+#[derive(Clone, Default)]
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the signal was raised since the last call, clearing it.
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::SeqCst)
+    }
+}
+
 pub trait BrowserEngine: Send {
     fn state_version(&self) -> u64;
     fn frame_rate(&self) -> u32;
-    fn navigate(&mut self, url: &str) -> Result<pb::Observation, EngineError>;
+    /// False once the engine can no longer serve requests, e.g. because its
+    /// runtime thread panicked or exited. Every call on a dead engine fails
+    /// with `unavailable`, so the daemon drops its session.
+    fn is_alive(&self) -> bool;
+    /// Load `url`, waiting at most `timeout_ms` for it to finish; 0 uses the
+    /// session's navigation timeout.
+    fn navigate(&mut self, url: &str, timeout_ms: u32) -> Result<pb::Observation, EngineError>;
     fn observe(&mut self, opts: &pb::ObserveOptions) -> Result<pb::Observation, EngineError>;
+    /// Wait for the active tab to load and then for `quiet` to pass with no
+    /// requests, as `ObserveOptions.until_network_idle_ms` does.
+    fn network_idle_wait(&self, quiet: Duration) -> NetworkIdleWait;
+    /// Stop loading the active tab and observe the partially loaded page.
+    /// Runs after any in-flight call on the session; a navigate still waiting
+    /// for its page is cut short through `stop_signal` first.
+    fn stop(&mut self) -> Result<pb::Observation, EngineError>;
+    /// The signal `navigate` watches while it waits for the page. Engines
+    /// whose navigate never waits keep this default, which nothing watches.
+    fn stop_signal(&self) -> StopSignal {
+        StopSignal::default()
+    }
     fn act(&mut self, action: &pb::Action) -> Result<pb::ActionResult, EngineError>;
-    fn stream_event(&mut self, event_type: pb::StreamEventType) -> Result<pb::StreamEvent, EngineError>;
+    /// Build one stream event. `hit_test` shapes `HitTest` events and is
+    /// ignored for the other types. Delta frames are diffed against `base`,
+    /// which is updated to the frame returned.
+    fn stream_event(
+        &mut self,
+        event_type: pb::StreamEventType,
+        hit_test: &HitTestQuery,
+        base: &mut DeltaBase,
+    ) -> Result<pb::StreamEvent, EngineError>;
+    /// Compute foreground/background contrast for `node_ids`, or for all
+    /// visible text when empty.
+    fn check_contrast(&mut self, node_ids: &[u64]) -> Result<Vec<pb::ContrastResult>, EngineError>;
+    /// Render the current page as a single-page PDF. `page_size` overrides
+    /// the page dimensions in CSS pixels; unset fields fall back to the
+    /// session viewport.
+    fn export_pdf(&mut self, page_size: Option<&pb::Viewport>) -> Result<Vec<u8>, EngineError>;
+    fn get_cookies(&mut self) -> Result<Vec<pb::Cookie>, EngineError>;
+    fn set_cookie(&mut self, cookie: &pb::Cookie) -> Result<(), EngineError>;
+    /// Remove all cookies visible to the session, returning how many were cleared.
+    fn clear_cookies(&mut self) -> Result<u32, EngineError>;
+    /// Write `entries` into the active page's `scope` storage, returning how
+    /// many were set. `entries` has passed `validate_storage_entries`.
+    fn set_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        entries: &[pb::StorageEntry],
+    ) -> Result<u32, EngineError>;
+    /// Read `keys` (every key when empty) from the active page's `scope`
+    /// storage.
+    fn get_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        keys: &[String],
+    ) -> Result<pb::GetStorageResponse, EngineError>;
+    /// Empty the active page's `scope` storage, returning how many keys were
+    /// removed.
+    fn clear_storage(&mut self, scope: pb::StorageScope) -> Result<u32, EngineError>;
+    /// Drop the kinds of browsing data flagged in `kinds`, which has passed
+    /// through `browsing_data_kinds`. Reports the kinds cleared and which of
+    /// them were only cleared in part.
+    fn clear_browsing_data(
+        &mut self,
+        kinds: &pb::ClearBrowsingDataRequest,
+    ) -> Result<pb::ClearBrowsingDataResponse, EngineError>;
+    /// Block until `selector` matches an element or `timeout` elapses, in
+    /// which case a `wait_timeout` error is returned.
+    fn wait_for(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<pb::Observation, EngineError>;
+    /// Open a tab at `url` and make it the active tab.
+    fn open_tab(&mut self, url: &str) -> Result<pb::Tab, EngineError>;
+    /// Close `tab_id`, returning the id of the tab that is active afterwards.
+    fn close_tab(&mut self, tab_id: u64) -> Result<u64, EngineError>;
+    fn switch_tab(&mut self, tab_id: u64) -> Result<pb::Observation, EngineError>;
+    fn list_tabs(&mut self) -> Result<Vec<pb::Tab>, EngineError>;
+    /// Visible text of the page, normalized and capped at `max_bytes`.
+    fn extract_text(&mut self, max_bytes: usize) -> Result<pb::ExtractTextResponse, EngineError>;
+    /// Read the session clipboard, subject to its `ClipboardPolicy`.
+    fn clipboard_get(&mut self) -> Result<pb::ClipboardData, EngineError>;
+    /// Replace the session clipboard, subject to its `ClipboardPolicy`.
+    fn clipboard_set(&mut self, text: &str) -> Result<pb::ClipboardData, EngineError>;
+    /// Fill form fields in one batch. `fields` has passed `validate_form_fields`.
+    fn fill_form(&mut self, fields: &[pb::FormField]) -> Result<pb::FillFormResponse, EngineError>;
+    /// Read properties of the first element matching the query's selector.
+    /// `query` has passed `validate_query_element`.
+    fn query_element(
+        &mut self,
+        query: &pb::QueryElementRequest,
+    ) -> Result<pb::QueryElementResponse, EngineError>;
+    /// Attach a file to the file input matching the request's selector.
+    /// `upload` has passed `validate_file_upload`; the daemon has already
+    /// checked that uploads are enabled.
+    fn set_file_input(
+        &mut self,
+        upload: &pb::SetFileInputRequest,
+    ) -> Result<pb::SetFileInputResponse, EngineError>;
+    /// Downloads kept for the session, oldest first.
+    fn list_downloads(&mut self) -> Result<pb::ListDownloadsResponse, EngineError>;
+    /// One download's entry and bytes; `download_not_found` for unknown ids.
+    fn get_download(&mut self, download_id: &str) -> Result<pb::GetDownloadResponse, EngineError>;
+    /// Current scroll offset of the page in CSS pixels.
+    fn scroll_position(&mut self) -> Result<pb::Point, EngineError>;
+    /// Resize the session's viewport. `viewport` has passed `validate_viewport`.
+    fn set_viewport(&mut self, viewport: &pb::Viewport) -> Result<pb::Observation, EngineError>;
 }
 
 pub fn new_engine(config: &pb::SessionConfig) -> Result<Box<dyn BrowserEngine>, EngineError> {
     #[cfg(feature = "servo")]
-    {
-        let engine = servo::ServoEngine::new(config)?;
-        return Ok(Box::new(engine));
-    }
+    let engine = servo::ServoEngine::new(config)?;
+    #[cfg(not(feature = "servo"))]
+    let engine = stub::StubEngine::new(config)?;
+    Ok(Box::new(engine))
+}
+
+/// Describe the engine compiled into this binary.
+pub fn engine_info() -> pb::EngineInfo {
+    #[cfg(feature = "servo")]
+    let info = servo::engine_info();
     #[cfg(not(feature = "servo"))]
-    {
-        let engine = stub::StubEngine::new(config)?;
-        return Ok(Box::new(engine));
+    let info = stub::engine_info();
+    info
+}
+
+/// Cargo features this binary was built with, as reported in `EngineInfo`.
+pub fn compiled_features() -> Vec<String> {
+    [("servo", cfg!(feature = "servo"))]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Reject navigation timeouts above `MAX_NAVIGATION_TIMEOUT_MS`. Zero is
+/// valid and means "use the default".
+pub fn validate_navigation_timeout(timeout_ms: u32) -> Result<(), EngineError> {
+    if timeout_ms > MAX_NAVIGATION_TIMEOUT_MS {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("navigation timeout must be at most {MAX_NAVIGATION_TIMEOUT_MS}ms"),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject custom request headers with forbidden or malformed names, or with
+/// values containing line breaks.
+pub fn validate_request_headers(headers: &HashMap<String, String>) -> Result<(), EngineError> {
+    for (name, value) in headers {
+        let valid_name = !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+        if !valid_name {
+            return Err(EngineError::new(
+                "invalid_request",
+                format!("invalid header name {name:?}"),
+            ));
+        }
+        if FORBIDDEN_REQUEST_HEADERS
+            .iter()
+            .any(|forbidden| name.eq_ignore_ascii_case(forbidden))
+        {
+            return Err(EngineError::new(
+                "invalid_request",
+                format!("header {name} may not be overridden"),
+            ));
+        }
+        if value.contains(['\r', '\n']) {
+            return Err(EngineError::new(
+                "invalid_request",
+                format!("header {name} value contains a line break"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject cookies whose fields would break out of a `Set-Cookie` string:
+/// `;` or control characters anywhere, or `=` in the name, would let the
+/// caller add attributes of its own.
+pub fn validate_cookie(cookie: &pb::Cookie) -> Result<(), EngineError> {
+    let fields = [
+        ("name", &cookie.name),
+        ("value", &cookie.value),
+        ("domain", &cookie.domain),
+        ("path", &cookie.path),
+    ];
+    for (field, text) in fields {
+        if text.contains(|c: char| c == ';' || c.is_control())
+            || (field == "name" && text.contains('='))
+        {
+            return Err(EngineError::new(
+                "invalid_request",
+                format!("cookie {field} contains a reserved character"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a navigation timeout, treating 0 as "use `fallback`".
+pub(crate) fn navigation_timeout(timeout_ms: u32, fallback: Duration) -> Duration {
+    if timeout_ms == 0 {
+        fallback
+    } else {
+        Duration::from_millis(u64::from(timeout_ms))
+    }
+}
+
+/// Resolve the frame clip requested in `opts`, clamped to the viewport.
+/// `clip_node_id` takes precedence over `clip_rect`; `node_bounds` looks up a
+/// node's hit-test bounds. Returns `None` when no clip was requested.
+pub(crate) fn resolve_clip_rect(
+    opts: &pb::ObserveOptions,
+    node_bounds: impl FnOnce(u64) -> Option<pb::Rect>,
+    viewport_width: u32,
+    viewport_height: u32,
+) -> Result<Option<pb::Rect>, EngineError> {
+    let rect = if opts.clip_node_id != 0 {
+        node_bounds(opts.clip_node_id)
+            .ok_or_else(|| EngineError::new("invalid_target", "unknown clip node"))?
+    } else if let Some(rect) = opts.clip_rect.as_ref() {
+        rect.clone()
+    } else {
+        return Ok(None);
+    };
+    clamp_rect(&rect, viewport_width, viewport_height)
+        .map(Some)
+        .ok_or_else(|| EngineError::new("invalid_target", "clip rect is outside the viewport"))
+}
+
+/// Intersect `rect` with the viewport, returning `None` if nothing remains.
+fn clamp_rect(rect: &pb::Rect, viewport_width: u32, viewport_height: u32) -> Option<pb::Rect> {
+    let x0 = i64::from(rect.x).max(0);
+    let y0 = i64::from(rect.y).max(0);
+    let x1 = (i64::from(rect.x) + i64::from(rect.width)).min(i64::from(viewport_width));
+    let y1 = (i64::from(rect.y) + i64::from(rect.height)).min(i64::from(viewport_height));
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    Some(pb::Rect {
+        x: x0 as i32,
+        y: y0 as i32,
+        width: (x1 - x0) as i32,
+        height: (y1 - y0) as i32,
+    })
+}
+
+/// Number of intermediate drag moves for a requested step count: 0 uses
+/// `DEFAULT_DRAG_STEPS` and larger values are capped at `MAX_DRAG_STEPS`.
+pub(crate) fn drag_steps(requested: u32) -> u32 {
+    match requested {
+        0 => DEFAULT_DRAG_STEPS,
+        n => n.min(MAX_DRAG_STEPS),
+    }
+}
+
+/// Decode the click button and count for an action, defaulting to a single
+/// left click. Counts above `MAX_CLICK_COUNT` (a triple click) are capped.
+pub(crate) fn click_params(action: &pb::Action) -> (pb::MouseButton, u32) {
+    let button = match pb::MouseButton::try_from(action.button) {
+        Ok(pb::MouseButton::Unspecified) | Err(_) => pb::MouseButton::Left,
+        Ok(button) => button,
+    };
+    (button, action.click_count.clamp(1, MAX_CLICK_COUNT))
+}
+
+/// The viewport a session starts with. Fields the config leaves unset (zero)
+/// come from the daemon-wide default.
+pub(crate) fn session_viewport(config: &pb::SessionConfig) -> pb::Viewport {
+    let defaults = default_viewport();
+    let requested = config.viewport.clone().unwrap_or_default();
+    pb::Viewport {
+        width: if requested.width > 0 {
+            requested.width
+        } else {
+            defaults.width
+        },
+        height: if requested.height > 0 {
+            requested.height
+        } else {
+            defaults.height
+        },
+        device_scale_factor: if requested.device_scale_factor > 0.0 {
+            requested.device_scale_factor
+        } else {
+            defaults.device_scale_factor
+        },
+    }
+}
+
+/// The fallback viewport, read once from `BROWSERD_DEFAULT_VIEWPORT_WIDTH`,
+/// `_HEIGHT` and `_SCALE`.
+fn default_viewport() -> &'static pb::Viewport {
+    static DEFAULT: OnceLock<pb::Viewport> = OnceLock::new();
+    DEFAULT.get_or_init(|| viewport_defaults(|key| std::env::var(key).ok()))
+}
+
+/// Build the fallback viewport from `lookup`ed variables. Values that don't
+/// parse or are out of range are ignored with a warning.
+fn viewport_defaults(lookup: impl Fn(&str) -> Option<String>) -> pb::Viewport {
+    let read = |key: &str, valid: &dyn Fn(f64) -> bool| -> Option<f64> {
+        let raw = lookup(key)?;
+        match raw.trim().parse::<f64>() {
+            Ok(value) if valid(value) => Some(value),
+            _ => {
+                log::warn!("ignoring invalid {key}={raw:?}");
+                None
+            }
+        }
+    };
+    let dimension = |value: f64| {
+        value.fract() == 0.0 && (1.0..=f64::from(MAX_VIEWPORT_DIMENSION)).contains(&value)
+    };
+    let scale = |value: f64| value > 0.0 && value <= MAX_DEVICE_SCALE_FACTOR;
+    let width = read("BROWSERD_DEFAULT_VIEWPORT_WIDTH", &dimension);
+    let height = read("BROWSERD_DEFAULT_VIEWPORT_HEIGHT", &dimension);
+    pb::Viewport {
+        width: width.map_or(DEFAULT_VIEWPORT_WIDTH, |value| value as u32),
+        height: height.map_or(DEFAULT_VIEWPORT_HEIGHT, |value| value as u32),
+        device_scale_factor: read("BROWSERD_DEFAULT_VIEWPORT_SCALE", &scale).unwrap_or(1.0),
+    }
+}
+
+/// The clipboard policy a session starts with: its own when the config sets
+/// one, otherwise `default`. A per-session policy replaces the default
+/// entirely rather than merging with it.
+pub(crate) fn session_clipboard_policy(
+    config: &pb::SessionConfig,
+    default: &pb::ClipboardPolicy,
+) -> pb::ClipboardPolicy {
+    config.clipboard.clone().unwrap_or_else(|| default.clone())
+}
+
+/// The fallback clipboard policy, read once from `BROWSERD_CLIPBOARD_MODE`,
+/// `_ALLOW_READ`, `_ALLOW_WRITE` and `_MAX_BYTES`.
+pub(crate) fn default_clipboard_policy() -> &'static pb::ClipboardPolicy {
+    static DEFAULT: OnceLock<pb::ClipboardPolicy> = OnceLock::new();
+    DEFAULT.get_or_init(|| clipboard_policy_defaults(|key| std::env::var(key).ok()))
+}
+
+/// Build the fallback clipboard policy from `lookup`ed variables. Unset or
+/// invalid values keep the built-in default: virtual, write-only, engine
+/// size limit.
+fn clipboard_policy_defaults(lookup: impl Fn(&str) -> Option<String>) -> pb::ClipboardPolicy {
+    let read_bool = |key: &str, default: bool| -> bool {
+        let Some(raw) = lookup(key) else {
+            return default;
+        };
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => {
+                log::warn!("ignoring invalid {key}={raw:?}");
+                default
+            }
+        }
+    };
+    let mode = match lookup("BROWSERD_CLIPBOARD_MODE") {
+        None => pb::ClipboardMode::Unspecified,
+        Some(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "" => pb::ClipboardMode::Unspecified,
+            "virtual" => pb::ClipboardMode::Virtual,
+            "host" => pb::ClipboardMode::Host,
+            _ => {
+                log::warn!("ignoring invalid BROWSERD_CLIPBOARD_MODE={raw:?}");
+                pb::ClipboardMode::Unspecified
+            }
+        },
+    };
+    let max_bytes =
+        lookup("BROWSERD_CLIPBOARD_MAX_BYTES").map_or(0, |raw| match raw.trim().parse::<u32>() {
+            Ok(value) if value > 0 => value,
+            _ => {
+                log::warn!("ignoring invalid BROWSERD_CLIPBOARD_MAX_BYTES={raw:?}");
+                0
+            }
+        });
+    pb::ClipboardPolicy {
+        mode: mode as i32,
+        allow_read: read_bool("BROWSERD_CLIPBOARD_ALLOW_READ", false),
+        allow_write: read_bool("BROWSERD_CLIPBOARD_ALLOW_WRITE", true),
+        max_bytes,
+        read_allowlist: Vec::new(),
+    }
+}
+
+/// Pixel ceiling for captured frames, read once from
+/// `BROWSERD_MAX_FRAME_PIXELS`.
+pub(crate) fn max_frame_pixels() -> u64 {
+    static MAX: OnceLock<u64> = OnceLock::new();
+    *MAX.get_or_init(|| frame_pixel_limit(|key| std::env::var(key).ok()))
+}
+
+fn frame_pixel_limit(lookup: impl Fn(&str) -> Option<String>) -> u64 {
+    let Some(raw) = lookup("BROWSERD_MAX_FRAME_PIXELS") else {
+        return DEFAULT_MAX_FRAME_PIXELS;
+    };
+    match raw.trim().parse::<u64>() {
+        Ok(value) if value > 0 => value,
+        _ => {
+            log::warn!("ignoring invalid BROWSERD_MAX_FRAME_PIXELS={raw:?}");
+            DEFAULT_MAX_FRAME_PIXELS
+        }
+    }
+}
+
+/// Most characters a type action may carry, read once from
+/// `BROWSERD_MAX_TYPE_CHARS`.
+pub(crate) fn max_type_chars() -> usize {
+    static MAX: OnceLock<usize> = OnceLock::new();
+    *MAX.get_or_init(|| type_char_limit(|key| std::env::var(key).ok()))
+}
+
+fn type_char_limit(lookup: impl Fn(&str) -> Option<String>) -> usize {
+    let Some(raw) = lookup("BROWSERD_MAX_TYPE_CHARS") else {
+        return DEFAULT_MAX_TYPE_CHARS;
+    };
+    match raw.trim().parse::<usize>() {
+        Ok(value) if value > 0 => value,
+        _ => {
+            log::warn!("ignoring invalid BROWSERD_MAX_TYPE_CHARS={raw:?}");
+            DEFAULT_MAX_TYPE_CHARS
+        }
+    }
+}
+
+/// Reject type actions whose text is longer than `max_chars`, before the
+/// engine starts typing it.
+pub(crate) fn check_type_length(
+    action_type: pb::ActionType,
+    action: &pb::Action,
+    max_chars: usize,
+) -> Result<(), EngineError> {
+    if !matches!(action_type, pb::ActionType::Type | pb::ActionType::TypeInto) {
+        return Ok(());
+    }
+    let chars = action.text.chars().count();
+    if chars > max_chars {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("type text is {chars} chars, over the limit of {max_chars}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Stack size for engine runtime threads, read once from
+/// `BROWSERD_SERVO_STACK_MB`. `None` keeps the standard library default,
+/// which deeply nested pages can overflow.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+pub(crate) fn runtime_stack_size() -> Option<usize> {
+    static SIZE: OnceLock<Option<usize>> = OnceLock::new();
+    *SIZE.get_or_init(|| runtime_stack_bytes(|key| std::env::var(key).ok()))
+}
+
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+fn runtime_stack_bytes(lookup: impl Fn(&str) -> Option<String>) -> Option<usize> {
+    let raw = lookup("BROWSERD_SERVO_STACK_MB")?;
+    match raw.trim().parse::<usize>() {
+        Ok(mb) if (1..=MAX_RUNTIME_STACK_MB).contains(&mb) => Some(mb * 1024 * 1024),
+        _ => {
+            log::warn!("ignoring invalid BROWSERD_SERVO_STACK_MB={raw:?}");
+            None
+        }
+    }
+}
+
+/// How Servo rasterizes pages, chosen daemon-wide with
+/// `BROWSERD_RENDER_BACKEND`.
+///
+/// `Software` renders on the CPU: it needs no GPU or driver in the sandbox and
+/// gives the same pixels on every host, but painting and frame reads cost CPU
+/// time that grows with the viewport. `Gl` renders on the GPU, which makes
+/// frame capture cheaper, at the price of needing a working driver, pixels
+/// that can differ between drivers, and a larger attack surface. A `Gl`
+/// context that fails to initialize falls back to `Software`.
+///
+/// libservo can't yet build a GL context without a native window, so for now
+/// `Gl` always takes that fallback: the session logs "GL rendering
+/// unavailable, falling back to software" and renders on the CPU. Setting it
+/// today costs nothing and picks up GPU rendering once libservo can provide
+/// a headless hardware context.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RenderBackend {
+    Software,
+    Gl,
+}
+
+/// The configured render backend, read once from `BROWSERD_RENDER_BACKEND`.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+pub(crate) fn render_backend() -> RenderBackend {
+    static BACKEND: OnceLock<RenderBackend> = OnceLock::new();
+    *BACKEND.get_or_init(|| render_backend_from(|key| std::env::var(key).ok()))
+}
+
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+fn render_backend_from(lookup: impl Fn(&str) -> Option<String>) -> RenderBackend {
+    let Some(raw) = lookup("BROWSERD_RENDER_BACKEND") else {
+        return RenderBackend::Software;
+    };
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "" | "software" => RenderBackend::Software,
+        "gl" => RenderBackend::Gl,
+        _ => {
+            log::warn!("ignoring invalid BROWSERD_RENDER_BACKEND={raw:?}, using software");
+            RenderBackend::Software
+        }
+    }
+}
+
+/// Run one engine command, turning a panic into an `engine_panic` error so
+/// the caller still gets a reply and the runtime keeps serving later
+/// commands. Whatever the command changed before panicking stays changed.
+#[cfg_attr(not(feature = "servo"), allow(dead_code))]
+pub(crate) fn catch_panic<T>(
+    command: impl FnOnce() -> Result<T, EngineError>,
+) -> Result<T, EngineError> {
+    panic::catch_unwind(AssertUnwindSafe(command)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        log::error!("engine command panicked: {message}");
+        Err(EngineError::new(
+            "engine_panic",
+            format!("engine panicked: {message}"),
+        ))
+    })
+}
+
+/// The largest size with the aspect ratio of `width`x`height` that fits in
+/// `max_pixels`, or `None` when the frame already fits. Large viewports and
+/// scale factors trade frame detail for a bounded encode and message size.
+pub(crate) fn scaled_frame_size(width: u32, height: u32, max_pixels: u64) -> Option<(u32, u32)> {
+    let pixels = u64::from(width) * u64::from(height);
+    if pixels <= max_pixels {
+        return None;
+    }
+    let scale = (max_pixels as f64 / pixels as f64).sqrt();
+    let scaled = |dimension: u32| ((f64::from(dimension) * scale).floor() as u32).max(1);
+    Some((scaled(width), scaled(height)))
+}
+
+/// Reject viewport sizes no engine can render.
+pub fn validate_viewport(viewport: &pb::Viewport) -> Result<(), EngineError> {
+    let valid = |dimension: u32| (1..=MAX_VIEWPORT_DIMENSION).contains(&dimension);
+    if !valid(viewport.width) || !valid(viewport.height) {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("viewport width and height must be between 1 and {MAX_VIEWPORT_DIMENSION}"),
+        ));
+    }
+    if !viewport.device_scale_factor.is_finite() || viewport.device_scale_factor < 0.0 {
+        return Err(EngineError::new(
+            "invalid_request",
+            "device_scale_factor must be a non-negative number",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject empty or oversized FillForm batches and fields without a selector.
+pub fn validate_form_fields(fields: &[pb::FormField]) -> Result<(), EngineError> {
+    if fields.is_empty() {
+        return Err(EngineError::new("invalid_request", "fields is required"));
+    }
+    if fields.len() > MAX_FORM_FIELDS {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("fields is limited to {MAX_FORM_FIELDS} entries"),
+        ));
+    }
+    if fields.iter().any(|field| field.selector.trim().is_empty()) {
+        return Err(EngineError::new(
+            "invalid_request",
+            "every field needs a selector",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject QueryElement requests without a selector or with too many or
+/// empty property names.
+pub fn validate_query_element(query: &pb::QueryElementRequest) -> Result<(), EngineError> {
+    if query.selector.trim().is_empty() {
+        return Err(EngineError::new("invalid_request", "selector is required"));
+    }
+    if query.properties.len() > MAX_QUERY_PROPERTIES {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("properties is limited to {MAX_QUERY_PROPERTIES} names"),
+        ));
+    }
+    if query.properties.iter().any(|name| name.trim().is_empty()) {
+        return Err(EngineError::new(
+            "invalid_request",
+            "property names can't be empty",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject empty or oversized SetStorage batches and entries without a key.
+pub fn validate_storage_entries(entries: &[pb::StorageEntry]) -> Result<(), EngineError> {
+    if entries.is_empty() {
+        return Err(EngineError::new("invalid_request", "entries is required"));
+    }
+    if entries.len() > MAX_STORAGE_ENTRIES {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("entries is limited to {MAX_STORAGE_ENTRIES} items"),
+        ));
+    }
+    if entries.iter().any(|entry| entry.key.is_empty()) {
+        return Err(EngineError::new(
+            "invalid_request",
+            "every entry needs a key",
+        ));
+    }
+    Ok(())
+}
+
+/// The kinds a ClearBrowsingData request asks for; no flags means all of them.
+pub fn browsing_data_kinds(request: &pb::ClearBrowsingDataRequest) -> pb::ClearBrowsingDataRequest {
+    if request.cookies || request.storage || request.cache || request.clipboard {
+        return request.clone();
+    }
+    pb::ClearBrowsingDataRequest {
+        cookies: true,
+        storage: true,
+        cache: true,
+        clipboard: true,
+    }
+}
+
+/// Origin whose storage a page at `url` uses, as `location.origin` reports
+/// it. File pages share the opaque "null" origin; other opaque origins
+/// (about:blank, data: URLs) have no storage and get `invalid_request`.
+pub(crate) fn storage_origin(url: &str) -> Result<String, EngineError> {
+    match Url::parse(url) {
+        Ok(url) if url.scheme() == "file" => Ok("null".to_string()),
+        Ok(url) if url.origin().is_tuple() => Ok(url.origin().ascii_serialization()),
+        _ => Err(EngineError::new(
+            "invalid_request",
+            "the active page has no origin to store data for",
+        )),
+    }
+}
+
+/// Reject uploads without a selector or with a filename that isn't a plain
+/// base name, so the engine can't be made to write outside its upload dir.
+pub fn validate_file_upload(upload: &pb::SetFileInputRequest) -> Result<(), EngineError> {
+    if upload.selector.trim().is_empty() {
+        return Err(EngineError::new("invalid_request", "selector is required"));
+    }
+    let name = upload.filename.as_str();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', '\0']) {
+        return Err(EngineError::new(
+            "invalid_request",
+            "filename must be a base name without path separators",
+        ));
+    }
+    Ok(())
+}
+
+/// A new private (0700) temp directory for one session's files of `kind`
+/// (uploads, downloads), removed when the handle drops. The random suffix
+/// keeps sessions whose ids reduce to the same filename-safe name, and
+/// concurrent daemons, apart.
+pub(crate) fn session_temp_dir(
+    session_id: &str,
+    kind: &str,
+) -> Result<tempfile::TempDir, EngineError> {
+    let session: String = session_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    tempfile::Builder::new()
+        .prefix(&format!("browserd-{session}-{kind}-"))
+        .permissions(std::fs::Permissions::from_mode(0o700))
+        .tempdir()
+        .map_err(|e| EngineError::new("unavailable", format!("failed to create {kind} dir: {e}")))
+}
+
+/// Reject computed-style requests over the node and property caps.
+pub fn validate_style_request(opts: &pb::ObserveOptions) -> Result<(), EngineError> {
+    if opts.style_node_ids.len() > MAX_STYLE_NODES {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("style_node_ids is limited to {MAX_STYLE_NODES} nodes"),
+        ));
+    }
+    if opts.style_properties.len() > MAX_STYLE_PROPERTIES {
+        return Err(EngineError::new(
+            "invalid_request",
+            format!("style_properties is limited to {MAX_STYLE_PROPERTIES} properties"),
+        ));
+    }
+    Ok(())
+}
+
+/// Node ids and CSS properties to report computed styles for, capped even
+/// when the request skipped `validate_style_request`.
+pub(crate) fn style_query(opts: &pb::ObserveOptions) -> (&[u64], Vec<String>) {
+    let nodes = &opts.style_node_ids[..opts.style_node_ids.len().min(MAX_STYLE_NODES)];
+    let properties = if opts.style_properties.is_empty() {
+        DEFAULT_STYLE_PROPERTIES
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    } else {
+        opts.style_properties
+            .iter()
+            .take(MAX_STYLE_PROPERTIES)
+            .map(|name| name.trim().to_ascii_lowercase())
+            .collect()
+    };
+    (nodes, properties)
+}
+
+/// How many regions a hit-test map may hold and which selectors, beyond the
+/// engine's built-in interactive ones, contribute regions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitTestQuery {
+    pub max_regions: usize,
+    pub selectors: Vec<String>,
+}
+
+impl HitTestQuery {
+    /// A `max_regions` of 0 uses `DEFAULT_HIT_TEST_REGIONS`; larger values
+    /// are capped at `MAX_HIT_TEST_REGIONS`. Blank selectors are dropped and
+    /// the rest capped at `MAX_HIT_TEST_SELECTORS`.
+    pub fn new(max_regions: u32, selectors: &[String]) -> Self {
+        let max_regions = match max_regions {
+            0 => DEFAULT_HIT_TEST_REGIONS,
+            n => n.min(MAX_HIT_TEST_REGIONS),
+        };
+        Self {
+            max_regions: max_regions as usize,
+            selectors: selectors
+                .iter()
+                .map(|selector| selector.trim())
+                .filter(|selector| !selector.is_empty())
+                .take(MAX_HIT_TEST_SELECTORS)
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    pub fn from_observe(opts: &pb::ObserveOptions) -> Self {
+        Self::new(opts.max_hit_regions, &opts.hit_test_selectors)
+    }
+
+    /// The query an engine builds its cached hit-test map with: this one
+    /// with at least the default region cap, so a small `max_regions` only
+    /// trims the response and later lookups and diffs see the whole page.
+    #[cfg_attr(not(feature = "servo"), allow(dead_code))]
+    pub(crate) fn for_cache(&self) -> Self {
+        Self {
+            max_regions: self.max_regions.max(DEFAULT_HIT_TEST_REGIONS as usize),
+            selectors: self.selectors.clone(),
+        }
+    }
+
+    /// Trim a map built with `for_cache` to this query's region cap.
+    #[cfg_attr(not(feature = "servo"), allow(dead_code))]
+    pub(crate) fn truncate(&self, mut map: pb::HitTestMap) -> pb::HitTestMap {
+        map.regions.truncate(self.max_regions);
+        map
+    }
+}
+
+impl Default for HitTestQuery {
+    fn default() -> Self {
+        Self::new(0, &[])
+    }
+}
+
+/// The frame a stream last delivered, which the engine diffs the next delta
+/// frame against. Each stream owns one: a base shared by every subscriber of
+/// a session would hand each of them deltas against frames it never got.
+#[derive(Default)]
+pub struct DeltaBase {
+    #[cfg(feature = "servo")]
+    frame: Option<image::RgbaImage>,
+}
+
+impl DeltaBase {
+    /// Forget the base so the next frame is sent whole.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// How long to keep the page running after load before observing it.
+pub(crate) fn post_load_settle(config: &pb::SessionConfig) -> Duration {
+    let ms = config
+        .post_load_settle_ms
+        .unwrap_or(DEFAULT_POST_LOAD_SETTLE_MS)
+        .min(MAX_POST_LOAD_SETTLE_MS);
+    Duration::from_millis(u64::from(ms))
+}
+
+/// How many times a key action presses its key: at least once, at most
+/// `MAX_KEY_REPEAT`.
+pub(crate) fn key_repeat(action: &pb::Action) -> u32 {
+    action.repeat.clamp(1, MAX_KEY_REPEAT)
+}
+
+/// The action's overall time bound, capped at `MAX_ACTION_TIMEOUT_MS`.
+/// `None` when the action doesn't set one.
+pub(crate) fn action_timeout(action: &pb::Action) -> Option<Duration> {
+    (action.action_timeout_ms > 0).then(|| {
+        Duration::from_millis(u64::from(
+            action.action_timeout_ms.min(MAX_ACTION_TIMEOUT_MS),
+        ))
+    })
+}
+
+/// Whether `action` was built against a state version other than `current`.
+/// Actions without an expected version are never stale.
+pub(crate) fn is_stale(action: &pb::Action, current: u64) -> bool {
+    action.expected_state_version != 0 && action.expected_state_version != current
+}
+
+/// Whether a stale action should be re-resolved against the current state
+/// and retried once instead of failing with `stale_state`.
+pub(crate) fn retries_on_stale(action: &pb::Action) -> bool {
+    action.on_stale == pb::StalePolicy::Retry as i32
+}
+
+pub(crate) fn stale_state_error(expected: u64, current: u64) -> EngineError {
+    EngineError::new(
+        "stale_state",
+        format!("expected state version {expected} but current is {current}"),
+    )
+}
+
+/// Result of a dry-run action: the resolved target and the unchanged state
+/// version, with no observation.
+pub(crate) fn dry_run_result(
+    state_version: u64,
+    resolved_node_id: u64,
+    resolved_point: Option<pb::Point>,
+) -> pb::ActionResult {
+    let summary = match resolved_point.as_ref() {
+        Some(point) => format!(
+            "target resolved to node {resolved_node_id} at ({}, {})",
+            point.x, point.y
+        ),
+        None => format!("target resolved to node {resolved_node_id}"),
+    };
+    pb::ActionResult {
+        state_version,
+        observation: None,
+        effects: vec![pb::Effect {
+            kind: "dry_run".to_string(),
+            summary,
+            metadata: None,
+        }],
+        resolved_node_id,
+        resolved_point,
+        caused_navigation: false,
+    }
+}
+
+/// Collapse whitespace within each line of `raw`, drop blank lines and cut
+/// the result at `max_bytes` on a character boundary.
+pub(crate) fn page_text(raw: &str, max_bytes: usize) -> pb::ExtractTextResponse {
+    let mut text = raw
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let truncated = truncate_to_bytes(&mut text, max_bytes);
+    pb::ExtractTextResponse {
+        word_count: text.split_whitespace().count() as u32,
+        text,
+        truncated,
+    }
+}
+
+/// Cut `text` to at most `max_bytes` on a character boundary, returning
+/// whether anything was removed.
+fn truncate_to_bytes(text: &mut String, max_bytes: usize) -> bool {
+    if text.len() <= max_bytes {
+        return false;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    true
+}
+
+/// Byte cap for Observation.html from `ObserveOptions.max_html_bytes`.
+pub(crate) fn html_byte_cap(opts: &pb::ObserveOptions) -> usize {
+    let bytes = match opts.max_html_bytes {
+        0 => DEFAULT_MAX_HTML_BYTES,
+        bytes => bytes.min(MAX_HTML_BYTES),
+    };
+    bytes as usize
+}
+
+/// Fill in Observation.html and html_truncated from the serialized document.
+pub(crate) fn set_observation_html(
+    obs: &mut pb::Observation,
+    mut html: String,
+    opts: &pb::ObserveOptions,
+) {
+    obs.html_truncated = truncate_to_bytes(&mut html, html_byte_cap(opts));
+    obs.html = html;
+}
+
+/// Short hex digest of a serialized snapshot, empty for an empty one.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return String::new();
+    }
+    Sha256::digest(bytes)[..CONTENT_HASH_BYTES]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Fill in Observation's snapshot hashes from the snapshots it carries.
+pub(crate) fn set_content_hashes(obs: &mut pb::Observation) {
+    obs.dom_snapshot_hash = content_hash(&obs.dom_snapshot);
+    obs.accessibility_tree_hash = content_hash(&obs.accessibility_tree);
+}
+
+/// Whether `target` names a node that should be scrolled into view before a
+/// pointer action. Explicit points and selectors are used as given.
+pub(crate) fn scrolls_into_view(action_type: pb::ActionType, target: &pb::ActionTarget) -> bool {
+    let pointer_action = matches!(
+        action_type,
+        pb::ActionType::Click
+            | pb::ActionType::Hover
+            | pb::ActionType::Focus
+            | pb::ActionType::Type
+    );
+    pointer_action
+        && target.node_id != 0
+        && target.point.is_none()
+        && target.selector.trim().is_empty()
+        && target.scroll_into_view.unwrap_or(true)
+}
+
+/// Length of typed text as a user sees it: user-perceived characters
+/// (grapheme clusters), so an emoji with a skin tone counts once.
+pub(crate) fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Whether the action carries the Shift modifier.
+pub(crate) fn has_shift(action: &pb::Action) -> bool {
+    action.modifiers.contains(&(pb::KeyModifier::Shift as i32))
+}
+
+/// Resolve the PDF page size, preferring non-zero dimensions from `page_size`.
+pub(crate) fn pdf_page_size(
+    page_size: Option<&pb::Viewport>,
+    viewport_width: u32,
+    viewport_height: u32,
+) -> (u32, u32) {
+    let width = page_size
+        .map(|p| p.width)
+        .filter(|w| *w > 0)
+        .unwrap_or(viewport_width);
+    let height = page_size
+        .map(|p| p.height)
+        .filter(|h| *h > 0)
+        .unwrap_or(viewport_height);
+    (width, height)
+}
+
+/// Decode the action type, rejecting unspecified or unknown values the same
+/// way in every engine.
+pub(crate) fn parse_action_type(raw: i32) -> Result<pb::ActionType, EngineError> {
+    match pb::ActionType::try_from(raw) {
+        Ok(pb::ActionType::Unspecified) | Err(_) => Err(EngineError::new(
+            "invalid_request",
+            "unsupported action type",
+        )),
+        Ok(action_type) => Ok(action_type),
     }
 }
 
-/// Check whether `host` (with optional `port`) matches any entry in `allowlist`.
-pub(crate) fn allowlist_allows(host: &str, port: Option<u16>, allowlist: &[String]) -> bool {
-    let host = host.to_ascii_lowercase();
-    for entry in allowlist {
+/// Check whether `host` (with optional `port`) matches any entry in `list`.
+/// Allowlists and denylists share this matcher.
+pub(crate) fn host_list_matches(host: &str, port: Option<u16>, list: &[String]) -> bool {
+    let host = normalize_host(host);
+    // URL hosts wrap IPv6 literals in brackets.
+    let host_ip = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .ok();
+    for entry in list {
         let entry = entry.trim();
         if entry.is_empty() {
             continue;
         }
+        if let Some(net) = parse_cidr_entry(entry) {
+            if host_ip.is_some_and(|ip| net.contains(&ip)) {
+                return true;
+            }
+            continue;
+        }
         if let Some(suffix) = entry.strip_prefix("*.") {
-            let suffix = suffix.to_ascii_lowercase();
+            let suffix = normalize_host(suffix);
             if host == suffix || host.ends_with(&format!(".{suffix}")) {
                 return true;
             }
@@ -73,21 +1212,1447 @@ pub(crate) fn allowlist_allows(host: &str, port: Option<u16>, allowlist: &[Strin
     false
 }
 
-/// Parse an allowlist entry into a `(host, optional_port)` pair.
+/// Parse a CIDR allowlist entry such as `10.0.0.0/8` or `fd00::/8`. CIDR
+/// entries only match hosts given as literal IPs, on any port.
+fn parse_cidr_entry(entry: &str) -> Option<IpNet> {
+    if !entry.contains('/') || entry.contains("://") {
+        return None;
+    }
+    entry.parse::<IpNet>().ok()
+}
+
+/// Parse an allowlist entry into a `(host, optional_port)` pair. The host is
+/// normalized like `normalize_host`.
 pub(crate) fn parse_allowlist_entry(entry: &str) -> (String, Option<u16>) {
     if entry.contains("://") {
         if let Ok(url) = Url::parse(entry) {
             if let Some(host) = url.host_str() {
-                return (host.to_ascii_lowercase(), url.port());
+                return (normalize_host(host), url.port());
             }
         }
     }
     if let Some((host, port_str)) = entry.rsplit_once(':') {
         if port_str.chars().all(|c| c.is_ascii_digit()) && !host.contains(']') {
             if let Ok(port) = port_str.parse::<u16>() {
-                return (host.to_ascii_lowercase(), Some(port));
+                return (normalize_host(host), Some(port));
+            }
+        }
+    }
+    (normalize_host(entry), None)
+}
+
+/// Lowercase a host and convert internationalized names to their `xn--`
+/// form, so Unicode and punycode spellings of one name compare equal. Hosts
+/// IDNA rejects (IPv6 literals, odd entries) are only lowercased.
+pub(crate) fn normalize_host(host: &str) -> String {
+    idna::domain_to_ascii(host).unwrap_or_else(|_| host.to_ascii_lowercase())
+}
+
+/// Whether any label of `host` mixes Latin, Greek and Cyrillic letters, the
+/// scripts whose lookalikes are used for homograph attacks (`pаypal` with a
+/// Cyrillic `а`). `xn--` labels are decoded first.
+pub(crate) fn has_mixed_script_label(host: &str) -> bool {
+    #[derive(PartialEq)]
+    enum Script {
+        Latin,
+        Greek,
+        Cyrillic,
+    }
+
+    fn script(c: char) -> Option<Script> {
+        match c {
+            'a'..='z' | 'A'..='Z' | '\u{00c0}'..='\u{024f}' | '\u{1e00}'..='\u{1eff}' => {
+                Some(Script::Latin)
+            }
+            '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => Some(Script::Greek),
+            '\u{0400}'..='\u{052f}' => Some(Script::Cyrillic),
+            _ => None,
+        }
+    }
+
+    let (unicode, _) = idna::domain_to_unicode(host);
+    unicode.split('.').any(|label| {
+        let mut scripts = label.chars().filter_map(script);
+        let Some(first) = scripts.next() else {
+            return false;
+        };
+        scripts.any(|other| other != first)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Start the engine for `config`; engines aren't `Debug`, so tests can't
+    /// `unwrap` the result directly.
+    fn start_engine(config: &pb::SessionConfig) -> Box<dyn BrowserEngine> {
+        new_engine(config).ok().expect("engine init")
+    }
+
+    /// An engine for a session with the default config.
+    fn stub_engine(session_id: &str) -> Box<dyn BrowserEngine> {
+        start_engine(&pb::SessionConfig {
+            session_id: session_id.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn unspecified_action(raw: i32) -> pb::Action {
+        pb::Action {
+            r#type: raw,
+            ..Default::default()
+        }
+    }
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> pb::Rect {
+        pb::Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_allowlist_cidr_entries() {
+        let allowlist = vec![
+            "10.0.0.0/8".to_string(),
+            "fd00::/8".to_string(),
+            "example.com".to_string(),
+        ];
+        assert!(host_list_matches("10.1.2.3", Some(80), &allowlist));
+        assert!(host_list_matches("[fd12::1]", Some(443), &allowlist));
+        assert!(!host_list_matches("11.0.0.1", Some(80), &allowlist));
+        assert!(!host_list_matches(
+            "internal.example.org",
+            Some(80),
+            &allowlist
+        ));
+        assert!(host_list_matches("example.com", Some(443), &allowlist));
+
+        let narrow = vec!["192.168.1.0/24".to_string()];
+        assert!(host_list_matches("192.168.1.254", None, &narrow));
+        assert!(!host_list_matches("192.168.2.1", None, &narrow));
+    }
+
+    #[test]
+    fn test_allowlist_idn_entries() {
+        let punycode = vec!["xn--bcher-kva.example".to_string()];
+        let unicode = vec![
+            "Bücher.example:8443".to_string(),
+            "*.müller.example".to_string(),
+        ];
+        assert!(host_list_matches("bücher.example", None, &punycode));
+        assert!(host_list_matches("BÜCHER.example", None, &punycode));
+        assert!(host_list_matches(
+            "xn--bcher-kva.example",
+            Some(8443),
+            &unicode
+        ));
+        assert!(!host_list_matches(
+            "xn--bcher-kva.example",
+            Some(443),
+            &unicode
+        ));
+        assert!(host_list_matches(
+            "shop.xn--mller-kva.example",
+            None,
+            &unicode
+        ));
+        assert!(!host_list_matches("bucher.example", None, &punycode));
+        assert_eq!(
+            parse_allowlist_entry("https://Bücher.example/"),
+            ("xn--bcher-kva.example".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_mixed_script_labels() {
+        assert!(has_mixed_script_label("p\u{0430}ypal.com"));
+        assert!(has_mixed_script_label(&normalize_host("p\u{0430}ypal.com")));
+        assert!(!has_mixed_script_label("paypal.com"));
+        assert!(!has_mixed_script_label("bücher.example"));
+        assert!(!has_mixed_script_label(
+            "\u{043f}\u{0440}\u{0438}\u{043c}\u{0435}\u{0440}.com"
+        ));
+    }
+
+    #[test]
+    fn test_act_reports_resolved_target() {
+        let mut engine = stub_engine("resolved");
+        let observation = engine
+            .observe(&pb::ObserveOptions {
+                include_hit_test: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let button = observation
+            .hit_test
+            .expect("hit test")
+            .regions
+            .into_iter()
+            .find(|region| region.node_id == 2)
+            .and_then(|region| region.bounds)
+            .expect("button bounds");
+        let inside = pb::Point {
+            x: button.x + 1,
+            y: button.y + 1,
+        };
+        let click = pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            target: Some(pb::ActionTarget {
+                point: Some(inside.clone()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = engine.act(&click).ok().expect("click");
+        assert_eq!(result.resolved_node_id, 2);
+        assert_eq!(result.resolved_point, Some(inside));
+
+        let by_selector = pb::Action {
+            r#type: pb::ActionType::Hover as i32,
+            target: Some(pb::ActionTarget {
+                selector: "#stub-input".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = engine.act(&by_selector).ok().expect("hover");
+        assert_eq!(result.resolved_node_id, 3);
+        assert!(result.resolved_point.is_some());
+    }
+
+    #[test]
+    fn test_action_timeout_echoed_by_stub() {
+        let mut engine = stub_engine("timeouts");
+        let action = pb::Action {
+            r#type: pb::ActionType::Hover as i32,
+            action_timeout_ms: 750,
+            ..Default::default()
+        };
+        let result = engine.act(&action).ok().expect("act");
+        let metadata = result.effects[0].metadata.as_ref().expect("metadata");
+        assert_eq!(
+            metadata.fields["action_timeout_ms"].kind,
+            Some(prost_types::value::Kind::NumberValue(750.0))
+        );
+
+        let capped = pb::Action {
+            action_timeout_ms: u32::MAX,
+            ..action
+        };
+        assert_eq!(
+            action_timeout(&capped),
+            Some(Duration::from_millis(u64::from(MAX_ACTION_TIMEOUT_MS)))
+        );
+        assert_eq!(action_timeout(&pb::Action::default()), None);
+    }
+
+    #[test]
+    fn test_click_summary_distinguishes_buttons() {
+        let mut engine = stub_engine("clicks");
+        let mut summary_for = |button: pb::MouseButton, click_count: u32| {
+            let action = pb::Action {
+                r#type: pb::ActionType::Click as i32,
+                button: button as i32,
+                click_count,
+                ..Default::default()
+            };
+            let result = engine.act(&action).ok().expect("click");
+            result.effects[0].summary.clone()
+        };
+        let left = summary_for(pb::MouseButton::Unspecified, 0);
+        let right = summary_for(pb::MouseButton::Right, 1);
+        let double = summary_for(pb::MouseButton::Left, 2);
+        assert!(left.starts_with("clicked"), "{left}");
+        assert!(right.starts_with("right-clicked"), "{right}");
+        assert!(double.starts_with("double-clicked"), "{double}");
+    }
+
+    #[test]
+    fn test_selector_targets_in_stub() {
+        let mut engine = stub_engine("selectors");
+        let click = |selector: &str, selector_type: pb::SelectorType| pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            target: Some(pb::ActionTarget {
+                selector: selector.to_string(),
+                selector_type: selector_type as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = engine
+            .act(&click("#stub-button", pb::SelectorType::Css))
+            .ok()
+            .expect("css selector");
+        assert_eq!(result.effects[0].summary, "clicked node 2");
+        let result = engine
+            .act(&click("//input", pb::SelectorType::Xpath))
+            .ok()
+            .expect("xpath selector");
+        assert_eq!(result.effects[0].summary, "clicked node 3");
+        let err = engine
+            .act(&click("#missing", pb::SelectorType::Unspecified))
+            .expect_err("unknown selector");
+        assert_eq!(err.code, "invalid_target");
+    }
+
+    #[test]
+    fn test_select_option_recorded_by_stub() {
+        let mut engine = stub_engine("select");
+        let select = |text: &str| pb::Action {
+            r#type: pb::ActionType::SelectOption as i32,
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let result = engine.act(&select("Medium")).ok().expect("select");
+        assert_eq!(result.effects[0].kind, "select_option");
+        let dom = result.observation.expect("observation").dom_snapshot;
+        let dom: serde_json::Value = serde_json::from_slice(&dom).expect("dom json");
+        assert_eq!(dom["selected_option"], "Medium");
+
+        let err = engine.act(&select("")).expect_err("empty text");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    #[test]
+    fn test_set_checked_in_stub() {
+        let mut engine = stub_engine("checked");
+        let mut set_checked = |checked: bool| {
+            let action = pb::Action {
+                r#type: pb::ActionType::SetChecked as i32,
+                target: Some(pb::ActionTarget {
+                    node_id: 2,
+                    ..Default::default()
+                }),
+                checked,
+                ..Default::default()
+            };
+            let result = engine.act(&action).ok().expect("set checked");
+            let metadata = result.effects[0].metadata.clone().expect("metadata");
+            metadata.fields["changed"].kind.clone()
+        };
+        let changed = Some(prost_types::value::Kind::BoolValue(true));
+        let unchanged = Some(prost_types::value::Kind::BoolValue(false));
+        assert_eq!(set_checked(true), changed);
+        assert_eq!(set_checked(true), unchanged);
+        assert_eq!(set_checked(false), changed);
+    }
+
+    #[test]
+    fn test_type_into_in_stub() {
+        let mut engine = stub_engine("type-into");
+        let type_into = |selector: &str| pb::Action {
+            r#type: pb::ActionType::TypeInto as i32,
+            target: Some(pb::ActionTarget {
+                selector: selector.to_string(),
+                ..Default::default()
+            }),
+            text: "hello".to_string(),
+            ..Default::default()
+        };
+        let before = engine.state_version();
+        let result = engine
+            .act(&type_into("#stub-input"))
+            .ok()
+            .expect("type into");
+        assert_eq!(result.state_version, before + 1);
+        assert_eq!(result.resolved_node_id, 3);
+        let obs = result.observation.expect("observation");
+        assert_eq!(obs.focused_node_id, 3);
+        let dom: serde_json::Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(dom["last_text_len"], 5);
+
+        let err = engine
+            .act(&type_into("#stub-button"))
+            .expect_err("not editable");
+        assert_eq!(err.code, "invalid_target");
+        let err = engine
+            .act(&pb::Action {
+                target: None,
+                ..type_into("")
+            })
+            .expect_err("no selector");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    #[test]
+    fn test_stub_counts_typed_graphemes() {
+        let mut engine = stub_engine("graphemes");
+        let text = "hi \u{1f44d}\u{1f3fd}\u{1f469}\u{200d}\u{1f467}";
+        assert_eq!(text.len(), 22);
+        let result = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Type as i32,
+                text: text.to_string(),
+                ..Default::default()
+            })
+            .ok()
+            .expect("type");
+        let obs = result.observation.expect("observation");
+        let dom: serde_json::Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(dom["last_text_len"], 5);
+        assert_eq!(result.effects[0].summary, "typed 5 chars into node 3");
+    }
+
+    #[test]
+    fn test_query_element_in_stub() {
+        let query = |selector: &str, properties: &[&str]| pb::QueryElementRequest {
+            selector: selector.to_string(),
+            properties: properties.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        };
+        assert!(validate_query_element(&query(" ", &[])).is_err());
+        assert!(validate_query_element(&query("a", &[""])).is_err());
+        assert!(validate_query_element(&query("a", &["x"; 33])).is_err());
+
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "query".to_string(),
+            ..Default::default()
+        });
+        let found = engine
+            .query_element(&query("#stub-input", &["id", "aria-label", "missing"]))
+            .ok()
+            .expect("query");
+        assert!(found.found);
+        assert_eq!(found.properties.len(), 2);
+        assert_eq!(found.properties["aria-label"], "Stub Input");
+
+        let missing = engine
+            .query_element(&query("#nope", &["id"]))
+            .ok()
+            .expect("query");
+        assert!(!missing.found);
+        assert!(missing.properties.is_empty());
+    }
+
+    #[test]
+    fn test_validate_file_upload() {
+        let upload = |selector: &str, filename: &str| pb::SetFileInputRequest {
+            selector: selector.to_string(),
+            filename: filename.to_string(),
+            data: b"hello".to_vec(),
+        };
+        assert!(validate_file_upload(&upload("#file", "report.pdf")).is_ok());
+        for (selector, filename) in [
+            (" ", "a.txt"),
+            ("#file", ""),
+            ("#file", "../etc/passwd"),
+            ("#file", ".."),
+        ] {
+            let err = validate_file_upload(&upload(selector, filename)).expect_err(filename);
+            assert_eq!(err.code, "invalid_request");
+        }
+
+        let mut engine = stub_engine("upload");
+        let response = engine
+            .set_file_input(&upload("#file", "a.txt"))
+            .ok()
+            .expect("upload");
+        assert_eq!((response.filename.as_str(), response.bytes), ("a.txt", 5));
+        assert_eq!(response.state_version, engine.state_version());
+    }
+
+    #[test]
+    fn test_session_temp_dirs_are_private_and_unique() {
+        let first = session_temp_dir("a.b", "uploads").ok().expect("dir");
+        let second = session_temp_dir("a_b", "uploads").ok().expect("dir");
+        assert_ne!(first.path(), second.path());
+        let mode = std::fs::metadata(first.path())
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+        assert!(second.path().exists());
+    }
+
+    #[test]
+    fn test_dry_run_click_leaves_state_alone() {
+        let mut engine = stub_engine("dry-run");
+        let before = engine.state_version();
+        let mut click = pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            target: Some(pb::ActionTarget {
+                selector: "#stub-button".to_string(),
+                ..Default::default()
+            }),
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = engine.act(&click).ok().expect("dry run");
+        assert_eq!(result.state_version, before);
+        assert_eq!(engine.state_version(), before);
+        assert_eq!(result.resolved_node_id, 2);
+        assert!(result.resolved_point.is_some());
+        assert!(result.observation.is_none());
+        assert_eq!(result.effects[0].kind, "dry_run");
+
+        click.target.as_mut().unwrap().selector = "#missing".to_string();
+        let err = engine.act(&click).expect_err("unresolvable target");
+        assert_eq!(err.code, "invalid_target");
+    }
+
+    #[test]
+    fn test_stub_enforces_scheme_policy() {
+        let mut config = pb::SessionConfig {
+            session_id: "schemes".to_string(),
+            ..Default::default()
+        };
+        let mut engine = start_engine(&config);
+        let err = engine
+            .navigate("file:///etc/passwd", 0)
+            .expect_err("file blocked");
+        assert_eq!(
+            (err.code, err.message.as_str()),
+            ("invalid_request", "blocked scheme: file")
+        );
+        assert!(engine.open_tab("data:text/html,hi").is_err());
+        assert!(engine.navigate("https://example.com", 0).is_ok());
+
+        config.allowed_schemes = vec!["file".to_string()];
+        let mut engine = start_engine(&config);
+        assert!(engine.navigate("file:///tmp/page.html", 0).is_ok());
+        assert!(engine.navigate("https://example.com", 0).is_err());
+    }
+
+    #[test]
+    fn test_stub_download_link() {
+        let click = pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            target: Some(pb::ActionTarget {
+                selector: "a[download]".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut config = pb::SessionConfig {
+            session_id: "downloads-off".to_string(),
+            ..Default::default()
+        };
+        let mut engine = start_engine(&config);
+        let result = engine.act(&click).ok().expect("click");
+        assert_eq!(result.effects[1].kind, "downloads_disabled");
+        assert!(engine
+            .list_downloads()
+            .ok()
+            .expect("list")
+            .downloads
+            .is_empty());
+
+        config.session_id = "downloads-on".to_string();
+        config.downloads_enabled = true;
+        let mut engine = start_engine(&config);
+        let result = engine.act(&click).ok().expect("click");
+        assert_eq!(result.effects[1].kind, "download");
+        let list = engine.list_downloads().ok().expect("list");
+        assert_eq!(list.downloads.len(), 1);
+        let download = engine
+            .get_download(&list.downloads[0].id)
+            .ok()
+            .expect("get");
+        assert_eq!(download.data.len() as u64, list.total_bytes);
+    }
+
+    #[test]
+    fn test_validate_request_headers() {
+        let headers =
+            |name: &str, value: &str| HashMap::from([(name.to_string(), value.to_string())]);
+        assert!(validate_request_headers(&HashMap::new()).is_ok());
+        assert!(validate_request_headers(&headers("Authorization", "Bearer abc")).is_ok());
+        assert!(validate_request_headers(&headers("X-Trace-Id", "1")).is_ok());
+        for (name, value) in [
+            ("Host", "evil.test"),
+            ("content-length", "0"),
+            ("Bad Header", "1"),
+            ("X-Inject", "a\r\nHost: evil.test"),
+        ] {
+            let err = validate_request_headers(&headers(name, value)).expect_err(name);
+            assert_eq!(err.code, "invalid_request");
+        }
+    }
+
+    #[test]
+    fn test_stub_request_headers() {
+        let config = |name: &str| pb::SessionConfig {
+            session_id: "headers".to_string(),
+            request_headers: HashMap::from([(name.to_string(), "1".to_string())]),
+            ..Default::default()
+        };
+        for name in ["Host", "Content-Length"] {
+            let err = new_engine(&config(name)).err().expect(name);
+            assert_eq!(err.code, "invalid_request");
+        }
+
+        let mut engine = start_engine(&config("X-Trace-Id"));
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let dom: serde_json::Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(dom["request_headers"], 1);
+    }
+
+    #[test]
+    fn test_validate_cookie() {
+        let cookie = |name: &str, value: &str, path: &str| pb::Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        };
+        assert!(validate_cookie(&cookie("sid", "a=b", "/app")).is_ok());
+        for (name, value, path) in [
+            ("sid", "abc; domain=evil.test", ""),
+            ("sid=x", "abc", ""),
+            ("sid", "abc\n", ""),
+            ("sid", "abc", "/; secure"),
+        ] {
+            let err = validate_cookie(&cookie(name, value, path)).expect_err(name);
+            assert_eq!(err.code, "invalid_request");
+        }
+    }
+
+    #[test]
+    fn test_tab_cycles_focus_in_stub() {
+        let mut engine = stub_engine("tabs");
+        let mut tab = |repeat: u32, reverse: bool| {
+            let action = pb::Action {
+                r#type: pb::ActionType::Tab as i32,
+                repeat,
+                modifiers: if reverse {
+                    vec![pb::KeyModifier::Shift as i32]
+                } else {
+                    Vec::new()
+                },
+                ..Default::default()
+            };
+            let result = engine.act(&action).ok().expect("tab");
+            result.observation.expect("observation").focused_node_id
+        };
+        // The stub starts focused on the input, the last focusable node.
+        assert_eq!(tab(0, false), 2);
+        assert_eq!(tab(1, false), 3);
+        assert_eq!(tab(2, false), 3);
+        assert_eq!(tab(1, true), 2);
+        assert_eq!(tab(3, true), 3);
+    }
+
+    #[test]
+    fn test_page_text_normalizes_and_truncates() {
+        let text = page_text("  Hello \t Servo \n\n\n  second   line\n", 1024);
+        assert_eq!(text.text, "Hello Servo\nsecond line");
+        assert_eq!(text.word_count, 4);
+        assert!(!text.truncated);
+
+        // The cut never splits a multi-byte character.
+        let text = page_text("caf\u{e9} au lait", 4);
+        assert_eq!(text.text, "caf");
+        assert_eq!(text.word_count, 1);
+        assert!(text.truncated);
+    }
+
+    #[test]
+    fn test_extract_text_in_stub() {
+        let mut engine = stub_engine("text");
+        engine
+            .navigate("https://example.com/article", 0)
+            .ok()
+            .expect("navigate");
+        let text = engine.extract_text(1024).ok().expect("extract");
+        assert!(text.text.contains("Stub Page"));
+        assert!(text
+            .text
+            .contains("navigate to https://example.com/article"));
+        assert_eq!(text.word_count, 5);
+    }
+
+    #[test]
+    fn test_scrolls_into_view() {
+        let node = pb::ActionTarget {
+            node_id: 7,
+            ..Default::default()
+        };
+        assert!(scrolls_into_view(pb::ActionType::Click, &node));
+        assert!(scrolls_into_view(pb::ActionType::Focus, &node));
+        assert!(!scrolls_into_view(pb::ActionType::Scroll, &node));
+
+        let opted_out = pb::ActionTarget {
+            scroll_into_view: Some(false),
+            ..node.clone()
+        };
+        assert!(!scrolls_into_view(pb::ActionType::Click, &opted_out));
+
+        let point = pb::ActionTarget {
+            point: Some(pb::Point { x: 5, y: 5 }),
+            ..node.clone()
+        };
+        assert!(!scrolls_into_view(pb::ActionType::Click, &point));
+
+        let mut engine = stub_engine("scroll");
+        let click = pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            target: Some(node),
+            ..Default::default()
+        };
+        let result = engine.act(&click).ok().expect("click");
+        let metadata = result.effects[0].metadata.as_ref().expect("metadata");
+        assert_eq!(
+            metadata.fields["scrolled_into_view"].kind,
+            Some(prost_types::value::Kind::BoolValue(true))
+        );
+    }
+
+    #[test]
+    fn test_navigation_reports_render_state_in_stub() {
+        let mut engine = stub_engine("paint");
+        let first = engine
+            .navigate("https://example.com", 0)
+            .ok()
+            .expect("navigate");
+        assert!(first.render_complete);
+        assert!(first.paint_epoch > 0);
+        let second = engine
+            .navigate("https://example.com/next", 0)
+            .ok()
+            .expect("navigate");
+        assert!(second.paint_epoch > first.paint_epoch);
+    }
+
+    #[test]
+    fn test_computed_styles_in_stub() {
+        let mut engine = stub_engine("styles");
+        let opts = pb::ObserveOptions {
+            style_node_ids: vec![2, 404],
+            style_properties: vec!["Display".to_string(), "color".to_string()],
+            ..Default::default()
+        };
+        assert!(validate_style_request(&opts).is_ok());
+        let obs = engine.observe(&opts).ok().expect("observe");
+        let styles = obs.computed_styles.expect("computed styles");
+        assert_eq!(styles.nodes.len(), 1);
+        assert_eq!(styles.nodes[&2].properties["display"], "inline-block");
+        assert_eq!(styles.nodes[&2].properties["color"], "rgb(0, 0, 0)");
+
+        let plain = engine
+            .observe(&pb::ObserveOptions::default())
+            .ok()
+            .expect("observe");
+        assert!(plain.computed_styles.is_none());
+
+        let too_many = pb::ObserveOptions {
+            style_node_ids: (1..=33).collect(),
+            ..Default::default()
+        };
+        let err = validate_style_request(&too_many).expect_err("over the node cap");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    #[test]
+    fn test_navigation_timing_in_stub() {
+        let mut engine = stub_engine("timing");
+        let opts = pb::ObserveOptions {
+            include_timing: true,
+            ..Default::default()
+        };
+        let obs = engine
+            .navigate("https://example.com", 0)
+            .ok()
+            .expect("navigate");
+        assert!(obs.navigation_timing.is_none());
+        let obs = engine.observe(&opts).ok().expect("observe");
+        assert_eq!(obs.navigation_timing, Some(pb::NavigationTiming::default()));
+    }
+
+    #[test]
+    fn test_snapshot_content_hashes() {
+        let mut engine = stub_engine("content-hash");
+        let opts = pb::ObserveOptions {
+            include_dom_snapshot: true,
+            include_accessibility: true,
+            ..Default::default()
+        };
+        let first = engine.observe(&opts).ok().expect("observe");
+        let second = engine.observe(&opts).ok().expect("observe");
+        assert_eq!(first.dom_snapshot_hash.len(), CONTENT_HASH_BYTES * 2);
+        assert_eq!(first.dom_snapshot_hash, second.dom_snapshot_hash);
+        assert_eq!(
+            first.accessibility_tree_hash,
+            second.accessibility_tree_hash
+        );
+        assert_ne!(first.dom_snapshot_hash, first.accessibility_tree_hash);
+
+        let event = engine
+            .stream_event(
+                pb::StreamEventType::DomDiff,
+                &HitTestQuery::default(),
+                &mut DeltaBase::default(),
+            )
+            .ok()
+            .expect("event");
+        assert_eq!(event.content_hash, first.dom_snapshot_hash);
+
+        engine
+            .navigate("https://example.com/changed", 0)
+            .ok()
+            .expect("navigate");
+        let changed = engine.observe(&opts).ok().expect("observe");
+        assert_ne!(changed.dom_snapshot_hash, first.dom_snapshot_hash);
+
+        let bare = engine
+            .observe(&pb::ObserveOptions::default())
+            .ok()
+            .expect("observe");
+        assert!(bare.dom_snapshot_hash.is_empty());
+    }
+
+    #[test]
+    fn test_stub_flat_dom_snapshot() {
+        let mut engine = stub_engine("flat-dom");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                dom_flat: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let dom: serde_json::Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        let nodes = dom["nodes"].as_array().expect("nodes");
+        let root = nodes[0]["node_id"].clone();
+        assert_eq!(nodes[0]["parent_id"], 0);
+        assert!(nodes[1..].iter().all(|node| node["parent_id"] == root));
+        assert_eq!(nodes[1]["text"], "Stub Button");
+    }
+
+    #[test]
+    fn test_viewport_defaults_from_env() {
+        let env = |width: &'static str, scale: &'static str| {
+            move |key: &str| match key {
+                "BROWSERD_DEFAULT_VIEWPORT_WIDTH" => Some(width.to_string()),
+                "BROWSERD_DEFAULT_VIEWPORT_HEIGHT" => Some("844".to_string()),
+                "BROWSERD_DEFAULT_VIEWPORT_SCALE" => Some(scale.to_string()),
+                _ => None,
             }
+        };
+        let phone = viewport_defaults(env("390", "3"));
+        assert_eq!(
+            (phone.width, phone.height, phone.device_scale_factor),
+            (390, 844, 3.0)
+        );
+
+        // Invalid values fall back one by one.
+        let bad = viewport_defaults(env("wide", "-2"));
+        assert_eq!(
+            (bad.width, bad.height, bad.device_scale_factor),
+            (1280, 844, 1.0)
+        );
+        let unset = viewport_defaults(|_| None);
+        assert_eq!((unset.width, unset.height), (1280, 720));
+    }
+
+    #[test]
+    fn test_clipboard_policy_defaults_from_env() {
+        let unset = clipboard_policy_defaults(|_| None);
+        assert_eq!(unset.mode, pb::ClipboardMode::Unspecified as i32);
+        assert!(!unset.allow_read && unset.allow_write);
+        assert_eq!(unset.max_bytes, 0);
+
+        let defaults = clipboard_policy_defaults(|key| match key {
+            "BROWSERD_CLIPBOARD_ALLOW_WRITE" => Some("false".to_string()),
+            "BROWSERD_CLIPBOARD_ALLOW_READ" => Some("maybe".to_string()),
+            "BROWSERD_CLIPBOARD_MAX_BYTES" => Some("16".to_string()),
+            "BROWSERD_CLIPBOARD_MODE" => Some("virtual".to_string()),
+            _ => None,
+        });
+        assert_eq!(defaults.mode, pb::ClipboardMode::Virtual as i32);
+        assert!(!defaults.allow_read && !defaults.allow_write);
+        assert_eq!(defaults.max_bytes, 16);
+
+        let config = pb::SessionConfig {
+            session_id: "clipboard-default".to_string(),
+            ..Default::default()
+        };
+        let mut engine = stub::StubEngine::with_clipboard_default(&config, &defaults)
+            .ok()
+            .expect("engine");
+        let err = engine.clipboard_set("x").expect_err("write denied");
+        assert_eq!(err.code, "clipboard_denied");
+
+        // A per-session policy still wins over the default.
+        let config = pb::SessionConfig {
+            clipboard: Some(pb::ClipboardPolicy {
+                allow_write: true,
+                ..Default::default()
+            }),
+            ..config
+        };
+        let mut engine = stub::StubEngine::with_clipboard_default(&config, &defaults)
+            .ok()
+            .expect("engine");
+        assert_eq!(engine.clipboard_set("x").ok().expect("write").bytes, 1);
+    }
+
+    #[test]
+    fn test_stub_uses_default_viewport() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "default-viewport".to_string(),
+            ..Default::default()
+        });
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_frame: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let defaults = default_viewport();
+        let frame = obs.frame.expect("frame");
+        assert_eq!(
+            (frame.width, frame.height),
+            (defaults.width, defaults.height)
+        );
+
+        // Fields the session sets win over the default.
+        let config = pb::SessionConfig {
+            viewport: Some(pb::Viewport {
+                width: 640,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let viewport = session_viewport(&config);
+        assert_eq!((viewport.width, viewport.height), (640, defaults.height));
+    }
+
+    #[test]
+    fn test_stub_device_profile() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "iphone".to_string(),
+            device_profile: "iphone".to_string(),
+            ..Default::default()
+        });
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_frame: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let frame = obs.frame.expect("frame");
+        assert_eq!((frame.width, frame.height), (390, 844));
+    }
+
+    #[test]
+    fn test_stub_console_messages() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "console".to_string(),
+            ..Default::default()
+        });
+        engine
+            .navigate("https://example.com/", 0)
+            .ok()
+            .expect("navigate");
+        let console = pb::ObserveOptions {
+            include_console: true,
+            ..Default::default()
+        };
+        let obs = engine.observe(&console).ok().expect("observe");
+        assert_eq!(obs.console_messages.len(), 1);
+        assert_eq!(obs.console_messages[0].level, "info");
+        assert!(obs.console_messages[0]
+            .text
+            .contains("https://example.com/"));
+        // Messages are handed out once.
+        let obs = engine.observe(&console).ok().expect("observe");
+        assert!(obs.console_messages.is_empty());
+
+        engine
+            .navigate("https://example.com/next", 0)
+            .ok()
+            .expect("navigate");
+        let event = engine
+            .stream_event(
+                pb::StreamEventType::Console,
+                &HitTestQuery::default(),
+                &mut DeltaBase::default(),
+            )
+            .ok()
+            .expect("console event");
+        assert_eq!(event.console_messages.len(), 1);
+    }
+
+    #[test]
+    fn test_stub_network_is_always_idle() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "network-idle".to_string(),
+            ..Default::default()
+        });
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                until_network_idle_ms: 60_000,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        assert!(obs.network_idle);
+    }
+
+    #[test]
+    fn test_frame_pixel_limit() {
+        assert_eq!(frame_pixel_limit(|_| None), 3840 * 2160);
+        assert_eq!(
+            frame_pixel_limit(|_| Some(" 1000000 ".to_string())),
+            1_000_000
+        );
+        assert_eq!(frame_pixel_limit(|_| Some("0".to_string())), 3840 * 2160);
+        assert_eq!(frame_pixel_limit(|_| Some("lots".to_string())), 3840 * 2160);
+
+        assert_eq!(scaled_frame_size(1280, 720, 1280 * 720), None);
+        assert_eq!(
+            scaled_frame_size(16_384, 16_384, 1_048_576),
+            Some((1024, 1024))
+        );
+        let (width, height) = scaled_frame_size(16_384, 9_000, 3840 * 2160).expect("scaled");
+        assert!(u64::from(width) * u64::from(height) <= 3840 * 2160);
+        assert!(width > height);
+    }
+
+    #[test]
+    fn test_runtime_stack_size() {
+        assert_eq!(runtime_stack_bytes(|_| None), None);
+        assert_eq!(
+            runtime_stack_bytes(|_| Some(" 64 ".to_string())),
+            Some(64 * 1024 * 1024)
+        );
+        assert_eq!(runtime_stack_bytes(|_| Some("0".to_string())), None);
+        assert_eq!(runtime_stack_bytes(|_| Some("4096".to_string())), None);
+        assert_eq!(runtime_stack_bytes(|_| Some("big".to_string())), None);
+    }
+
+    #[test]
+    fn test_type_length_limit() {
+        assert_eq!(type_char_limit(|_| None), DEFAULT_MAX_TYPE_CHARS);
+        assert_eq!(type_char_limit(|_| Some("32".to_string())), 32);
+        assert_eq!(
+            type_char_limit(|_| Some("0".to_string())),
+            DEFAULT_MAX_TYPE_CHARS
+        );
+
+        let typing = |text: &str| pb::Action {
+            r#type: pb::ActionType::Type as i32,
+            text: text.to_string(),
+            ..Default::default()
+        };
+        assert!(check_type_length(pb::ActionType::Type, &typing("four"), 4).is_ok());
+        let err =
+            check_type_length(pb::ActionType::Type, &typing("fives"), 4).expect_err("over limit");
+        assert_eq!(err.code, "invalid_request");
+        assert!(check_type_length(pb::ActionType::TypeInto, &typing("fives"), 4).is_err());
+        // Other actions' text isn't typed key by key.
+        assert!(check_type_length(pb::ActionType::ClipboardWrite, &typing("fives"), 4).is_ok());
+
+        let mut engine = stub_engine("type-limit");
+        let version = engine.state_version();
+        let err = engine
+            .act(&typing(&"x".repeat(DEFAULT_MAX_TYPE_CHARS + 1)))
+            .expect_err("over limit");
+        assert_eq!(err.code, "invalid_request");
+        assert_eq!(engine.state_version(), version);
+        assert!(engine
+            .act(&typing(&"x".repeat(DEFAULT_MAX_TYPE_CHARS)))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_render_backend() {
+        assert_eq!(render_backend_from(|_| None), RenderBackend::Software);
+        assert_eq!(
+            render_backend_from(|_| Some(" GL ".to_string())),
+            RenderBackend::Gl
+        );
+        assert_eq!(
+            render_backend_from(|_| Some("software".to_string())),
+            RenderBackend::Software
+        );
+        assert_eq!(
+            render_backend_from(|_| Some("vulkan".to_string())),
+            RenderBackend::Software
+        );
+    }
+
+    #[test]
+    fn test_catch_panic_reports_engine_panic() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "panic".to_string(),
+            ..Default::default()
+        });
+        let err = catch_panic(|| -> Result<pb::Observation, EngineError> {
+            engine.observe(&pb::ObserveOptions::default())?;
+            panic!("layout blew up");
+        })
+        .expect_err("panic becomes an error");
+        assert_eq!(err.code, "engine_panic");
+        assert!(err.message.contains("layout blew up"), "{}", err.message);
+        assert_eq!(pb::ErrorCode::from(err.code), pb::ErrorCode::EnginePanic);
+
+        // The engine is still usable after the panic.
+        let obs = catch_panic(|| engine.observe(&pb::ObserveOptions::default()))
+            .ok()
+            .expect("observe");
+        assert_eq!(obs.state_version, engine.state_version());
+    }
+
+    #[test]
+    fn test_stub_frame_is_downscaled() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "frame-cap".to_string(),
+            ..Default::default()
+        });
+        let frame_opts = pb::ObserveOptions {
+            include_frame: true,
+            ..Default::default()
+        };
+        let frame = engine
+            .observe(&frame_opts)
+            .ok()
+            .expect("observe")
+            .frame
+            .expect("frame");
+        assert!(!frame.scaled);
+
+        let huge = pb::Viewport {
+            width: 4_000,
+            height: 4_000,
+            device_scale_factor: 1.0,
+        };
+        engine.set_viewport(&huge).ok().expect("set viewport");
+        let frame = engine
+            .observe(&frame_opts)
+            .ok()
+            .expect("observe")
+            .frame
+            .expect("frame");
+        assert!(frame.scaled);
+        assert!(u64::from(frame.width) * u64::from(frame.height) <= max_frame_pixels());
+        assert_eq!(frame.width, frame.height);
+    }
+
+    #[test]
+    fn test_stub_stop() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "stop".to_string(),
+            ..Default::default()
+        });
+        engine
+            .navigate("https://example.com/slow", 0)
+            .ok()
+            .expect("navigate");
+        let before = engine.state_version();
+        let obs = engine.stop().ok().expect("stop");
+        assert_eq!(obs.url, "https://example.com/slow");
+        assert!(obs.state_version > before);
+        let dom: serde_json::Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(dom["last_action"], "stop");
+    }
+
+    #[test]
+    fn test_stub_page_metadata() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "metadata".to_string(),
+            ..Default::default()
+        });
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .ok()
+            .expect("observe");
+        assert!(obs.page_metadata.is_none());
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_metadata: true,
+                ..Default::default()
+            })
+            .ok()
+            .expect("observe");
+        let metadata = obs.page_metadata.expect("page metadata");
+        assert!(!metadata.favicon_url.is_empty());
+        assert!(!metadata.description.is_empty());
+        assert_eq!(metadata.canonical_url, obs.url);
+    }
+
+    #[test]
+    fn test_stub_observe_html() {
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "html".to_string(),
+            ..Default::default()
+        });
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .ok()
+            .expect("observe");
+        assert!(obs.html.is_empty());
+
+        let opts = |max_html_bytes| pb::ObserveOptions {
+            include_html: true,
+            max_html_bytes,
+            ..Default::default()
+        };
+        let obs = engine.observe(&opts(0)).ok().expect("observe");
+        assert!(obs.html.contains(r#"id="stub-button""#));
+        assert!(!obs.html_truncated);
+        let obs = engine.observe(&opts(6)).ok().expect("observe");
+        assert_eq!(obs.html, "<html>");
+        assert!(obs.html_truncated);
+
+        assert_eq!(html_byte_cap(&opts(0)), 256 * 1024);
+        assert_eq!(html_byte_cap(&opts(u32::MAX)), 4 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_post_load_settle_in_stub() {
+        let config = |settle_ms| pb::SessionConfig {
+            session_id: "settle".to_string(),
+            post_load_settle_ms: settle_ms,
+            ..Default::default()
+        };
+        assert_eq!(post_load_settle(&config(None)), Duration::from_millis(100));
+        assert_eq!(post_load_settle(&config(Some(0))), Duration::ZERO);
+
+        let mut engine = start_engine(&config(Some(80)));
+        let start = std::time::Instant::now();
+        engine
+            .navigate("https://example.com", 0)
+            .ok()
+            .expect("navigate");
+        assert!(start.elapsed() >= Duration::from_millis(80));
+
+        // The settle is cut short by the navigation timeout.
+        let start = std::time::Instant::now();
+        engine
+            .navigate("https://example.com", 40)
+            .ok()
+            .expect("navigate");
+        assert!(start.elapsed() < Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_set_viewport_moves_hit_regions_in_stub() {
+        let viewport = |width, height| pb::Viewport {
+            width,
+            height,
+            device_scale_factor: 0.0,
+        };
+        assert!(validate_viewport(&viewport(800, 600)).is_ok());
+        for bad in [viewport(0, 600), viewport(800, 0), viewport(16_385, 600)] {
+            let err = validate_viewport(&bad).expect_err("invalid viewport");
+            assert_eq!(err.code, "invalid_request");
+        }
+
+        let mut engine = stub_engine("resize");
+        let opts = pb::ObserveOptions {
+            include_hit_test: true,
+            ..Default::default()
+        };
+        let before = engine
+            .observe(&opts)
+            .ok()
+            .expect("observe")
+            .hit_test
+            .expect("hit test");
+        let version = engine.state_version();
+        engine
+            .set_viewport(&viewport(640, 480))
+            .ok()
+            .expect("resize");
+        assert!(engine.state_version() > version);
+        let after = engine
+            .observe(&opts)
+            .ok()
+            .expect("observe")
+            .hit_test
+            .expect("hit test");
+        assert_eq!((after.width, after.height), (640, 480));
+        assert_ne!(before.regions, after.regions);
+    }
+
+    #[test]
+    fn test_hit_test_query_limits() {
+        let query = HitTestQuery::default();
+        assert_eq!(query.max_regions, DEFAULT_HIT_TEST_REGIONS as usize);
+        assert!(query.selectors.is_empty());
+
+        let selectors = vec![" .card ".to_string(), "  ".to_string(), "li".to_string()];
+        let query = HitTestQuery::new(u32::MAX, &selectors);
+        assert_eq!(query.max_regions, MAX_HIT_TEST_REGIONS as usize);
+        assert_eq!(query.selectors, vec![".card".to_string(), "li".to_string()]);
+
+        // A small cap only trims the response; the cached map keeps the rest.
+        let small = HitTestQuery::new(1, &selectors);
+        let cached = small.for_cache();
+        assert_eq!(cached.max_regions, DEFAULT_HIT_TEST_REGIONS as usize);
+        assert_eq!(cached.selectors, small.selectors);
+        assert_eq!(query.for_cache(), query);
+        let map = pb::HitTestMap {
+            regions: vec![pb::HitRegion::default(); 3],
+            ..Default::default()
+        };
+        assert_eq!(small.truncate(map).regions.len(), 1);
+
+        let mut engine = stub_engine("regions");
+        let opts = |max_hit_regions| pb::ObserveOptions {
+            include_hit_test: true,
+            max_hit_regions,
+            ..Default::default()
+        };
+        let full = engine
+            .observe(&opts(0))
+            .ok()
+            .expect("observe")
+            .hit_test
+            .expect("hit test");
+        let capped = engine
+            .observe(&opts(2))
+            .ok()
+            .expect("observe")
+            .hit_test
+            .expect("hit test");
+        assert_eq!(capped.regions.len(), 2);
+        assert!(full.regions.len() > capped.regions.len());
+        let event = engine
+            .stream_event(
+                pb::StreamEventType::HitTest,
+                &HitTestQuery::new(1, &[]),
+                &mut DeltaBase::default(),
+            )
+            .ok()
+            .expect("stream event");
+        assert_eq!(event.hit_test.expect("hit test").regions.len(), 1);
+    }
+
+    #[test]
+    fn test_drag_steps() {
+        assert_eq!(drag_steps(0), DEFAULT_DRAG_STEPS);
+        assert_eq!(drag_steps(4), 4);
+        assert_eq!(drag_steps(u32::MAX), MAX_DRAG_STEPS);
+    }
+
+    #[test]
+    fn test_resolve_clip_rect() {
+        let none = pb::ObserveOptions::default();
+        assert!(resolve_clip_rect(&none, |_| None, 100, 100)
+            .ok()
+            .expect("no clip")
+            .is_none());
+
+        let by_rect = pb::ObserveOptions {
+            clip_rect: Some(rect(-10, 90, 50, 50)),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_clip_rect(&by_rect, |_| None, 100, 100)
+                .ok()
+                .expect("clip"),
+            Some(rect(0, 90, 40, 10))
+        );
+
+        let by_node = pb::ObserveOptions {
+            clip_node_id: 7,
+            clip_rect: Some(rect(0, 0, 1, 1)),
+            ..Default::default()
+        };
+        let found = resolve_clip_rect(
+            &by_node,
+            |id| (id == 7).then(|| rect(10, 10, 20, 20)),
+            100,
+            100,
+        );
+        assert_eq!(found.ok().expect("clip"), Some(rect(10, 10, 20, 20)));
+        let err = resolve_clip_rect(&by_node, |_| None, 100, 100).expect_err("unknown node");
+        assert_eq!(err.code, "invalid_target");
+
+        let outside = pb::ObserveOptions {
+            clip_rect: Some(rect(200, 200, 10, 10)),
+            ..Default::default()
+        };
+        let err = resolve_clip_rect(&outside, |_| None, 100, 100).expect_err("outside viewport");
+        assert_eq!(err.code, "invalid_target");
+    }
+
+    #[test]
+    fn test_error_code_from_str() {
+        assert_eq!(
+            pb::ErrorCode::from("invalid_request"),
+            pb::ErrorCode::InvalidRequest
+        );
+        assert_eq!(
+            pb::ErrorCode::from("stale_state"),
+            pb::ErrorCode::StaleState
+        );
+        assert_eq!(
+            pb::ErrorCode::from("wait_timeout"),
+            pb::ErrorCode::WaitTimeout
+        );
+        assert_eq!(
+            pb::ErrorCode::from("no_such_code"),
+            pb::ErrorCode::Unspecified
+        );
+    }
+
+    #[test]
+    fn test_navigation_timeout_override() {
+        let config = pb::SessionConfig {
+            session_id: "timeout".to_string(),
+            navigation_timeout_ms: 5,
+            ..Default::default()
+        };
+        let mut engine = start_engine(&config);
+        let err = engine
+            .navigate("https://example.com", 0)
+            .expect_err("session default should time out");
+        assert_eq!(err.code, "load_timeout");
+        assert!(engine.navigate("https://example.com", 1_000).is_ok());
+        let err = engine
+            .navigate("https://example.com", 1)
+            .expect_err("override should time out");
+        assert_eq!(err.code, "load_timeout");
+
+        let mut engine = start_engine(&pb::SessionConfig {
+            session_id: "default".to_string(),
+            ..Default::default()
+        });
+        assert!(engine.navigate("https://example.com", 0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_navigation_timeout() {
+        assert!(validate_navigation_timeout(0).is_ok());
+        assert!(validate_navigation_timeout(MAX_NAVIGATION_TIMEOUT_MS).is_ok());
+        let err =
+            validate_navigation_timeout(MAX_NAVIGATION_TIMEOUT_MS + 1).expect_err("too large");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    #[test]
+    fn test_unsupported_action_type_parity() {
+        let mut engine = stub_engine("parity");
+        for raw in [pb::ActionType::Unspecified as i32, 999] {
+            let expected = parse_action_type(raw).expect_err("unsupported type");
+            let err = engine
+                .act(&unspecified_action(raw))
+                .expect_err("act should fail");
+            assert_eq!(err.code, expected.code);
+            assert_eq!(err.message, expected.message);
+            assert_eq!(err.code, "invalid_request");
+            assert_eq!(err.message, "unsupported action type");
         }
     }
-    (entry.to_ascii_lowercase(), None)
 }
@@ -0,0 +1,130 @@
+//! Minimal single-page PDF writer used for page export.
+//!
+//! The page content is a single uncompressed RGB image scaled to fill the
+//! page, which keeps the output valid without pulling in a PDF crate.
+
+/// CSS pixels are 1/96 inch; PDF user space units are 1/72 inch.
+const POINTS_PER_CSS_PIXEL: f64 = 72.0 / 96.0;
+
+/// A raw 8-bit RGB image placed on the page.
+pub(crate) struct PdfImage<'a> {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: &'a [u8],
+}
+
+/// Build a one-page PDF whose page is `page_width` x `page_height` CSS
+/// pixels. When `image` is provided it is stretched to cover the page.
+pub(crate) fn single_page_pdf(
+    page_width: u32,
+    page_height: u32,
+    image: Option<PdfImage<'_>>,
+) -> Vec<u8> {
+    let width_pt = page_width.max(1) as f64 * POINTS_PER_CSS_PIXEL;
+    let height_pt = page_height.max(1) as f64 * POINTS_PER_CSS_PIXEL;
+
+    let mut out: Vec<u8> = Vec::new();
+    let mut offsets: Vec<usize> = Vec::new();
+    out.extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+    let mut object = |out: &mut Vec<u8>, body: &[u8]| {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(b"\nendobj\n");
+    };
+
+    object(&mut out, b"<< /Type /Catalog /Pages 2 0 R >>");
+    object(&mut out, b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>");
+    let resources = if image.is_some() {
+        "/Resources << /XObject << /Im0 5 0 R >> >> "
+    } else {
+        "/Resources << >> "
+    };
+    object(
+        &mut out,
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width_pt:.2} {height_pt:.2}] {resources}/Contents 4 0 R >>"
+        )
+        .as_bytes(),
+    );
+    let content = if image.is_some() {
+        format!("q {width_pt:.2} 0 0 {height_pt:.2} 0 0 cm /Im0 Do Q")
+    } else {
+        String::new()
+    };
+    object(&mut out, &stream_object("", content.as_bytes()));
+    if let Some(image) = image {
+        let dict = format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 ",
+            image.width, image.height
+        );
+        object(&mut out, &stream_object(&dict, image.rgb));
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n", offsets.len() + 1).as_bytes());
+    out.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in &offsets {
+        out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            offsets.len() + 1
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+fn stream_object(dict: &str, data: &[u8]) -> Vec<u8> {
+    let mut body = format!("<< {dict}/Length {} >>\nstream\n", data.len()).into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(b"\nendstream");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn startxref(pdf: &[u8]) -> usize {
+        let text = String::from_utf8_lossy(pdf);
+        let tail = text.rsplit("startxref\n").next().expect("startxref");
+        tail.lines().next().unwrap().parse().expect("offset")
+    }
+
+    #[test]
+    fn test_single_page_pdf_structure() {
+        let rgb = vec![255u8; 2 * 2 * 3];
+        let pdf = single_page_pdf(
+            96,
+            96,
+            Some(PdfImage {
+                width: 2,
+                height: 2,
+                rgb: &rgb,
+            }),
+        );
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let xref = startxref(&pdf);
+        assert!(pdf[xref..].starts_with(b"xref\n0 6\n"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/MediaBox [0 0 72.00 72.00]"));
+    }
+
+    #[test]
+    fn test_blank_pdf_has_valid_xref() {
+        let pdf = single_page_pdf(0, 0, None);
+        let xref = startxref(&pdf);
+        assert!(pdf[xref..].starts_with(b"xref\n0 5\n"));
+        // Every xref entry must point at the start of its object.
+        let text = String::from_utf8_lossy(&pdf[xref..]);
+        for (index, line) in text.lines().skip(3).take(4).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Device presets a session can name with `SessionConfig.device_profile`.
+//!
+//! A profile only fills in what the session leaves unset: an explicit
+//! viewport dimension, scale factor or user agent always wins. New devices
+//! are added as rows in `PROFILES`.
+
+use super::EngineError;
+use crate::proto as pb;
+
+pub(crate) struct DeviceProfile {
+    pub name: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub user_agent: &'static str,
+}
+
+const PROFILES: &[DeviceProfile] = &[
+    DeviceProfile {
+        name: "desktop",
+        width: 1920,
+        height: 1080,
+        device_scale_factor: 1.0,
+        user_agent: "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Servo/1.0 Firefox/128.0",
+    },
+    DeviceProfile {
+        name: "iphone",
+        width: 390,
+        height: 844,
+        device_scale_factor: 3.0,
+        user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1",
+    },
+    DeviceProfile {
+        name: "pixel",
+        width: 412,
+        height: 915,
+        device_scale_factor: 2.625,
+        user_agent: "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0.0.0 Mobile Safari/537.36",
+    },
+    DeviceProfile {
+        name: "ipad",
+        width: 820,
+        height: 1180,
+        device_scale_factor: 2.0,
+        user_agent: "Mozilla/5.0 (iPad; CPU OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1",
+    },
+];
+
+/// Look up a profile by name, ignoring case and surrounding whitespace.
+pub(crate) fn find_profile(name: &str) -> Option<&'static DeviceProfile> {
+    let name = name.trim();
+    PROFILES
+        .iter()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))
+}
+
+/// The config with its device profile, if any, expanded into the viewport
+/// and user agent fields it leaves unset. Unknown profile names are rejected.
+pub(crate) fn apply_device_profile(
+    config: &pb::SessionConfig,
+) -> Result<pb::SessionConfig, EngineError> {
+    let mut config = config.clone();
+    if config.device_profile.trim().is_empty() {
+        return Ok(config);
+    }
+    let profile = find_profile(&config.device_profile).ok_or_else(|| {
+        let known: Vec<&str> = PROFILES.iter().map(|profile| profile.name).collect();
+        EngineError::new(
+            "invalid_request",
+            format!(
+                "unknown device_profile {:?} (expected one of: {})",
+                config.device_profile,
+                known.join(", ")
+            ),
+        )
+    })?;
+    let viewport = config.viewport.get_or_insert_with(Default::default);
+    if viewport.width == 0 {
+        viewport.width = profile.width;
+    }
+    if viewport.height == 0 {
+        viewport.height = profile.height;
+    }
+    if viewport.device_scale_factor <= 0.0 {
+        viewport.device_scale_factor = profile.device_scale_factor;
+    }
+    if config.user_agent.trim().is_empty() {
+        config.user_agent = profile.user_agent.to_string();
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_fields_override_profile() {
+        let config = apply_device_profile(&pb::SessionConfig {
+            device_profile: " Pixel ".to_string(),
+            viewport: Some(pb::Viewport {
+                height: 600,
+                ..Default::default()
+            }),
+            user_agent: "Custom/1.0".to_string(),
+            ..Default::default()
+        })
+        .ok()
+        .expect("known profile");
+        let viewport = config.viewport.expect("viewport");
+        assert_eq!((viewport.width, viewport.height), (412, 600));
+        assert_eq!(viewport.device_scale_factor, 2.625);
+        assert_eq!(config.user_agent, "Custom/1.0");
+
+        let err = apply_device_profile(&pb::SessionConfig {
+            device_profile: "fridge".to_string(),
+            ..Default::default()
+        })
+        .expect_err("unknown profile");
+        assert_eq!(err.code, "invalid_request");
+        assert!(err.message.contains("iphone"), "{}", err.message);
+    }
+}
@@ -3,45 +3,97 @@
 //! Implements the BrowserEngine trait using the Servo web engine for real
 //! browser functionality including navigation, DOM access, and rendering.
 
-use super::{allowlist_allows, BrowserEngine, EngineError};
+use super::contrast::{contrast_result, Rgba};
+use super::downloads::{download_effect, download_filename, DownloadStore};
+use super::pdf::{single_page_pdf, PdfImage};
+use super::profiles::apply_device_profile;
+use super::{
+    action_timeout, catch_panic, check_type_length, click_params, compiled_features, content_hash,
+    default_clipboard_policy, drag_steps, dry_run_result, grapheme_count, has_shift,
+    host_list_matches, html_byte_cap, is_stale, key_repeat, max_frame_pixels, max_type_chars,
+    navigation_timeout, page_text, parse_action_type, pdf_page_size, post_load_settle,
+    render_backend, resolve_clip_rect, retries_on_stale, runtime_stack_size, scaled_frame_size,
+    scrolls_into_view, session_clipboard_policy, session_temp_dir, session_viewport,
+    set_content_hashes, set_observation_html, stale_state_error, storage_origin, style_query,
+    validate_cookie, validate_request_headers, BrowserEngine, DeltaBase, EngineError, HitTestQuery,
+    NetworkIdleWait, RenderBackend, StopSignal, DEFAULT_NAVIGATION_TIMEOUT_MS,
+    MAX_CONSOLE_MESSAGES, MAX_CONSOLE_MESSAGE_CHARS, MAX_PAGE_ERRORS,
+};
 use crate::proto as pb;
-use std::cell::RefCell;
+use crate::url_policy::check_scheme;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use base64::prelude::{Engine as _, BASE64_STANDARD};
 use dpi::PhysicalSize;
 use euclid::Point2D;
+use prost_types::{value, Struct, Value};
 use servo::{
-    CSSPixel, Code, EventLoopWaker, InputEvent, JSValue, JavaScriptEvaluationError, Key, KeyState,
-    KeyboardEvent, LoadStatus, Location, Modifiers, MouseButton, MouseButtonAction,
-    MouseButtonEvent, MouseMoveEvent, NamedKey, RenderingContext, Servo, ServoBuilder,
-    SoftwareRenderingContext, WebView, WebViewBuilder, WebViewPoint, WheelDelta, WheelEvent,
-    WheelMode,
+    CSSPixel, Code, CompositionEvent, CompositionState, EmbedderControl, EventLoopWaker, ImeEvent,
+    InputEvent, JSValue, JavaScriptEvaluationError, Key, KeyState, KeyboardEvent, LoadStatus,
+    Location, Modifiers, MouseButton, MouseButtonAction, MouseButtonEvent, MouseMoveEvent,
+    NamedKey, Preferences, RenderingContext, Servo, ServoBuilder, SoftwareRenderingContext,
+    UserContentManager, UserScript, WebResourceLoad, WebView, WebViewBuilder, WebViewDelegate,
+    WebViewPoint, WheelDelta, WheelEvent, WheelMode,
 };
-use prost_types::{value, Struct, Value};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use unicode_segmentation::UnicodeSegmentation;
 use url::Url;
 
 const DEFAULT_FRAME_RATE: u32 = 12;
-const DEFAULT_VIEWPORT_WIDTH: u32 = 1280;
-const DEFAULT_VIEWPORT_HEIGHT: u32 = 720;
-const NAVIGATION_TIMEOUT_SECS: u64 = 30;
 const JS_EVALUATION_TIMEOUT_MS: u64 = 3000;
 const SPIN_POLL_INTERVAL_MS: u64 = 10;
+/// `poll_until` starts checking this often and backs off to
+/// `SPIN_POLL_INTERVAL_MS`.
+const SPIN_POLL_MIN_INTERVAL_MS: u64 = 1;
 const DOM_MAX_DEPTH: usize = 5;
 const DOM_MAX_CHILDREN: usize = 50;
 const DOM_MAX_TEXT_CHARS: usize = 200;
 const A11Y_MAX_DEPTH: usize = 5;
 const A11Y_MAX_CHILDREN: usize = 50;
 const A11Y_MAX_NAME_CHARS: usize = 120;
-const HIT_TEST_MAX_REGIONS: usize = 250;
+const HIT_TEST_MAX_LABEL_CHARS: usize = 120;
+const TEXT_RUNS_MAX_RUNS: usize = 500;
+const TEXT_RUNS_MAX_RUN_CHARS: usize = 200;
+const TEXT_RUNS_MAX_TOTAL_CHARS: usize = 20_000;
+const CONTRAST_MAX_NODES: usize = 200;
+const CONTRAST_MAX_TEXT_CHARS: usize = 120;
 const DEFAULT_CLIPBOARD_MAX_BYTES: usize = 64 * 1024;
+/// Modifier keys in the order a chord presses them; they come up in reverse.
+const CHORD_MODIFIERS: [(Modifiers, NamedKey, Code); 4] = [
+    (Modifiers::CONTROL, NamedKey::Control, Code::ControlLeft),
+    (Modifiers::ALT, NamedKey::Alt, Code::AltLeft),
+    (Modifiers::SHIFT, NamedKey::Shift, Code::ShiftLeft),
+    (Modifiers::META, NamedKey::Meta, Code::MetaLeft),
+];
+/// After a navigation or action, how long an observation waits for the first
+/// new frame before assuming there is nothing to paint.
+const RENDER_QUIET_MS: u64 = 50;
+/// Once a click or key press has started a load, how long to wait for the
+/// webview's URL or load status to show it.
+const NAVIGATION_DETECT_MS: u64 = 100;
+/// Above this fraction of changed pixels a delta frame costs about as much as
+/// a full frame, so the full frame is sent instead.
+const FRAME_DELTA_MAX_CHANGED_RATIO: f64 = 0.6;
+
+pub fn engine_info() -> pb::EngineInfo {
+    pb::EngineInfo {
+        name: "servo".to_string(),
+        version: env!("BROWSERD_SERVO_VERSION").to_string(),
+        commit: env!("BROWSERD_SERVO_COMMIT").to_string(),
+        features: compiled_features(),
+    }
+}
 
 pub struct ServoEngine {
     frame_rate: u32,
     runtime: ServoRuntime,
+    stop: StopSignal,
 }
 
 impl ServoEngine {
@@ -52,19 +104,37 @@ impl ServoEngine {
                 "session_id is required",
             ));
         }
+        validate_request_headers(&config.request_headers)?;
+        let config = &apply_device_profile(config)?;
+        check_supported_config(config)?;
         let frame_rate = if config.frame_rate > 0 {
             config.frame_rate
         } else {
             DEFAULT_FRAME_RATE
         };
-        let runtime = ServoRuntime::spawn(config)?;
+        let stop = StopSignal::default();
+        let runtime = ServoRuntime::spawn(config, stop.clone())?;
         Ok(Self {
             frame_rate,
             runtime,
+            stop,
         })
     }
 }
 
+/// Reject session settings Servo would otherwise accept and then ignore.
+/// libservo's embedding API has no hook for adding headers to outgoing
+/// requests, so `request_headers` can't be sent.
+fn check_supported_config(config: &pb::SessionConfig) -> Result<(), EngineError> {
+    if !config.request_headers.is_empty() {
+        return Err(EngineError::new(
+            "unsupported",
+            "request_headers are not supported by the servo engine",
+        ));
+    }
+    Ok(())
+}
+
 impl BrowserEngine for ServoEngine {
     fn state_version(&self) -> u64 {
         self.runtime.state_version()
@@ -74,8 +144,24 @@ impl BrowserEngine for ServoEngine {
         self.frame_rate
     }
 
-    fn navigate(&mut self, url: &str) -> Result<pb::Observation, EngineError> {
-        self.runtime.navigate(url.to_string())
+    fn is_alive(&self) -> bool {
+        self.runtime.is_alive()
+    }
+
+    fn navigate(&mut self, url: &str, timeout_ms: u32) -> Result<pb::Observation, EngineError> {
+        self.runtime.navigate(url.to_string(), timeout_ms)
+    }
+
+    fn network_idle_wait(&self, quiet: Duration) -> NetworkIdleWait {
+        self.runtime.network_idle_wait(quiet)
+    }
+
+    fn stop(&mut self) -> Result<pb::Observation, EngineError> {
+        self.runtime.stop()
+    }
+
+    fn stop_signal(&self) -> StopSignal {
+        self.stop.clone()
     }
 
     fn observe(&mut self, opts: &pb::ObserveOptions) -> Result<pb::Observation, EngineError> {
@@ -89,8 +175,131 @@ impl BrowserEngine for ServoEngine {
     fn stream_event(
         &mut self,
         event_type: pb::StreamEventType,
+        hit_test: &HitTestQuery,
+        base: &mut DeltaBase,
     ) -> Result<pb::StreamEvent, EngineError> {
-        self.runtime.stream_event(event_type)
+        let (result, next_base) =
+            self.runtime
+                .stream_event(event_type, hit_test.clone(), std::mem::take(base));
+        *base = next_base;
+        result
+    }
+
+    fn check_contrast(&mut self, node_ids: &[u64]) -> Result<Vec<pb::ContrastResult>, EngineError> {
+        self.runtime.check_contrast(node_ids.to_vec())
+    }
+
+    fn export_pdf(&mut self, page_size: Option<&pb::Viewport>) -> Result<Vec<u8>, EngineError> {
+        self.runtime.export_pdf(page_size.cloned())
+    }
+
+    fn get_cookies(&mut self) -> Result<Vec<pb::Cookie>, EngineError> {
+        self.runtime.get_cookies()
+    }
+
+    fn set_cookie(&mut self, cookie: &pb::Cookie) -> Result<(), EngineError> {
+        self.runtime.set_cookie(cookie.clone())
+    }
+
+    fn clear_cookies(&mut self) -> Result<u32, EngineError> {
+        self.runtime.clear_cookies()
+    }
+
+    fn set_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        entries: &[pb::StorageEntry],
+    ) -> Result<u32, EngineError> {
+        self.runtime.set_storage(scope, entries.to_vec())
+    }
+
+    fn get_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        keys: &[String],
+    ) -> Result<pb::GetStorageResponse, EngineError> {
+        self.runtime.get_storage(scope, keys.to_vec())
+    }
+
+    fn clear_storage(&mut self, scope: pb::StorageScope) -> Result<u32, EngineError> {
+        self.runtime.clear_storage(scope)
+    }
+
+    fn clear_browsing_data(
+        &mut self,
+        kinds: &pb::ClearBrowsingDataRequest,
+    ) -> Result<pb::ClearBrowsingDataResponse, EngineError> {
+        self.runtime.clear_browsing_data(kinds.clone())
+    }
+
+    fn wait_for(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<pb::Observation, EngineError> {
+        self.runtime.wait_for(selector.to_string(), timeout)
+    }
+
+    fn open_tab(&mut self, url: &str) -> Result<pb::Tab, EngineError> {
+        self.runtime.open_tab(url.to_string())
+    }
+
+    fn close_tab(&mut self, tab_id: u64) -> Result<u64, EngineError> {
+        self.runtime.close_tab(tab_id)
+    }
+
+    fn switch_tab(&mut self, tab_id: u64) -> Result<pb::Observation, EngineError> {
+        self.runtime.switch_tab(tab_id)
+    }
+
+    fn list_tabs(&mut self) -> Result<Vec<pb::Tab>, EngineError> {
+        self.runtime.list_tabs()
+    }
+
+    fn extract_text(&mut self, max_bytes: usize) -> Result<pb::ExtractTextResponse, EngineError> {
+        self.runtime.extract_text(max_bytes)
+    }
+
+    fn clipboard_get(&mut self) -> Result<pb::ClipboardData, EngineError> {
+        self.runtime.clipboard_get()
+    }
+
+    fn clipboard_set(&mut self, text: &str) -> Result<pb::ClipboardData, EngineError> {
+        self.runtime.clipboard_set(text.to_string())
+    }
+
+    fn fill_form(&mut self, fields: &[pb::FormField]) -> Result<pb::FillFormResponse, EngineError> {
+        self.runtime.fill_form(fields.to_vec())
+    }
+
+    fn query_element(
+        &mut self,
+        query: &pb::QueryElementRequest,
+    ) -> Result<pb::QueryElementResponse, EngineError> {
+        self.runtime.query_element(query.clone())
+    }
+
+    fn set_file_input(
+        &mut self,
+        upload: &pb::SetFileInputRequest,
+    ) -> Result<pb::SetFileInputResponse, EngineError> {
+        self.runtime.set_file_input(upload.clone())
+    }
+
+    fn list_downloads(&mut self) -> Result<pb::ListDownloadsResponse, EngineError> {
+        self.runtime.list_downloads()
+    }
+
+    fn get_download(&mut self, download_id: &str) -> Result<pb::GetDownloadResponse, EngineError> {
+        self.runtime.get_download(download_id.to_string())
+    }
+
+    fn scroll_position(&mut self) -> Result<pb::Point, EngineError> {
+        self.runtime.scroll_position()
+    }
+
+    fn set_viewport(&mut self, viewport: &pb::Viewport) -> Result<pb::Observation, EngineError> {
+        self.runtime.set_viewport(viewport.clone())
     }
 }
 
@@ -104,6 +313,7 @@ impl Drop for ServoEngine {
 enum ServoCommand {
     Navigate {
         url: String,
+        timeout_ms: u32,
         respond_to: mpsc::Sender<Result<pb::Observation, EngineError>>,
     },
     Observe {
@@ -116,7 +326,109 @@ enum ServoCommand {
     },
     StreamEvent {
         event_type: pb::StreamEventType,
-        respond_to: mpsc::Sender<Result<pb::StreamEvent, EngineError>>,
+        hit_test: HitTestQuery,
+        base: DeltaBase,
+        respond_to: mpsc::Sender<(Result<pb::StreamEvent, EngineError>, DeltaBase)>,
+    },
+    CheckContrast {
+        node_ids: Vec<u64>,
+        respond_to: mpsc::Sender<Result<Vec<pb::ContrastResult>, EngineError>>,
+    },
+    ExportPdf {
+        page_size: Option<pb::Viewport>,
+        respond_to: mpsc::Sender<Result<Vec<u8>, EngineError>>,
+    },
+    GetCookies {
+        respond_to: mpsc::Sender<Result<Vec<pb::Cookie>, EngineError>>,
+    },
+    SetCookie {
+        cookie: pb::Cookie,
+        respond_to: mpsc::Sender<Result<(), EngineError>>,
+    },
+    ClearCookies {
+        respond_to: mpsc::Sender<Result<u32, EngineError>>,
+    },
+    SetStorage {
+        scope: pb::StorageScope,
+        entries: Vec<pb::StorageEntry>,
+        respond_to: mpsc::Sender<Result<u32, EngineError>>,
+    },
+    GetStorage {
+        scope: pb::StorageScope,
+        keys: Vec<String>,
+        respond_to: mpsc::Sender<Result<pb::GetStorageResponse, EngineError>>,
+    },
+    ClearStorage {
+        scope: pb::StorageScope,
+        respond_to: mpsc::Sender<Result<u32, EngineError>>,
+    },
+    ClearBrowsingData {
+        kinds: pb::ClearBrowsingDataRequest,
+        respond_to: mpsc::Sender<Result<pb::ClearBrowsingDataResponse, EngineError>>,
+    },
+    WaitFor {
+        selector: String,
+        timeout: Duration,
+        respond_to: mpsc::Sender<Result<pb::Observation, EngineError>>,
+    },
+    OpenTab {
+        url: String,
+        respond_to: mpsc::Sender<Result<pb::Tab, EngineError>>,
+    },
+    CloseTab {
+        tab_id: u64,
+        respond_to: mpsc::Sender<Result<u64, EngineError>>,
+    },
+    SwitchTab {
+        tab_id: u64,
+        respond_to: mpsc::Sender<Result<pb::Observation, EngineError>>,
+    },
+    ListTabs {
+        respond_to: mpsc::Sender<Result<Vec<pb::Tab>, EngineError>>,
+    },
+    ExtractText {
+        max_bytes: usize,
+        respond_to: mpsc::Sender<Result<pb::ExtractTextResponse, EngineError>>,
+    },
+    ClipboardGet {
+        respond_to: mpsc::Sender<Result<pb::ClipboardData, EngineError>>,
+    },
+    FillForm {
+        fields: Vec<pb::FormField>,
+        respond_to: mpsc::Sender<Result<pb::FillFormResponse, EngineError>>,
+    },
+    QueryElement {
+        query: pb::QueryElementRequest,
+        respond_to: mpsc::Sender<Result<pb::QueryElementResponse, EngineError>>,
+    },
+    SetFileInput {
+        upload: pb::SetFileInputRequest,
+        respond_to: mpsc::Sender<Result<pb::SetFileInputResponse, EngineError>>,
+    },
+    ListDownloads {
+        respond_to: mpsc::Sender<Result<pb::ListDownloadsResponse, EngineError>>,
+    },
+    GetDownload {
+        download_id: String,
+        respond_to: mpsc::Sender<Result<pb::GetDownloadResponse, EngineError>>,
+    },
+    ClipboardSet {
+        text: String,
+        respond_to: mpsc::Sender<Result<pb::ClipboardData, EngineError>>,
+    },
+    ScrollPosition {
+        respond_to: mpsc::Sender<Result<pb::Point, EngineError>>,
+    },
+    SetViewport {
+        viewport: pb::Viewport,
+        respond_to: mpsc::Sender<Result<pb::Observation, EngineError>>,
+    },
+    WaitForNetworkIdle {
+        quiet: Duration,
+        respond_to: mpsc::Sender<bool>,
+    },
+    Stop {
+        respond_to: mpsc::Sender<Result<pb::Observation, EngineError>>,
     },
     GetStateVersion {
         respond_to: mpsc::Sender<u64>,
@@ -126,20 +438,35 @@ enum ServoCommand {
 
 struct ServoRuntime {
     tx: mpsc::Sender<ServoCommand>,
+    thread: thread::JoinHandle<()>,
 }
 
 impl ServoRuntime {
-    fn spawn(config: &pb::SessionConfig) -> Result<Self, EngineError> {
+    fn spawn(config: &pb::SessionConfig, stop: StopSignal) -> Result<Self, EngineError> {
         let (tx, rx) = mpsc::channel();
         let config = config.clone();
 
-        thread::spawn(move || {
-            if let Err(e) = run_servo_runtime(config, rx) {
-                log::error!("Servo runtime error: {}", e.message);
-            }
-        });
+        let mut builder = thread::Builder::new().name(format!("servo-{}", config.session_id));
+        if let Some(size) = runtime_stack_size() {
+            builder = builder.stack_size(size);
+        }
+        let thread = builder
+            .spawn(move || {
+                if let Err(e) = run_servo_runtime(config, rx, stop) {
+                    log::error!("Servo runtime error: {}", e.message);
+                }
+            })
+            .map_err(|e| {
+                EngineError::new("unavailable", format!("failed to spawn servo runtime: {e}"))
+            })?;
+
+        Ok(Self { tx, thread })
+    }
 
-        Ok(Self { tx })
+    /// The runtime thread only finishes on Shutdown, an init error or a
+    /// panic; after that every command fails with `unavailable`.
+    fn is_alive(&self) -> bool {
+        !self.thread.is_finished()
     }
 
     fn state_version(&self) -> u64 {
@@ -150,10 +477,11 @@ impl ServoRuntime {
         rx.recv().unwrap_or(0)
     }
 
-    fn navigate(&self, url: String) -> Result<pb::Observation, EngineError> {
+    fn navigate(&self, url: String, timeout_ms: u32) -> Result<pb::Observation, EngineError> {
         let (tx, rx) = mpsc::channel();
         let _ = self.tx.send(ServoCommand::Navigate {
             url,
+            timeout_ms,
             respond_to: tx,
         });
         rx.recv()
@@ -180,363 +508,2383 @@ impl ServoRuntime {
             .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
 
+    /// Hands `base` to the runtime and back; it is lost (so the next frame
+    /// is sent whole) if the runtime is gone.
     fn stream_event(
         &self,
         event_type: pb::StreamEventType,
-    ) -> Result<pb::StreamEvent, EngineError> {
+        hit_test: HitTestQuery,
+        base: DeltaBase,
+    ) -> (Result<pb::StreamEvent, EngineError>, DeltaBase) {
         let (tx, rx) = mpsc::channel();
         let _ = self.tx.send(ServoCommand::StreamEvent {
             event_type,
+            hit_test,
+            base,
+            respond_to: tx,
+        });
+        rx.recv().unwrap_or_else(|_| {
+            (
+                Err(EngineError::new("unavailable", "servo runtime unavailable")),
+                DeltaBase::default(),
+            )
+        })
+    }
+
+    fn check_contrast(&self, node_ids: Vec<u64>) -> Result<Vec<pb::ContrastResult>, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::CheckContrast {
+            node_ids,
             respond_to: tx,
         });
         rx.recv()
             .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
 
-    fn shutdown(&self) {
-        let _ = self.tx.send(ServoCommand::Shutdown);
+    fn export_pdf(&self, page_size: Option<pb::Viewport>) -> Result<Vec<u8>, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ExportPdf {
+            page_size,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
-}
 
-/// Dummy event loop waker for headless operation
-struct HeadlessEventLoopWaker;
+    fn get_cookies(&self) -> Result<Vec<pb::Cookie>, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::GetCookies { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-impl EventLoopWaker for HeadlessEventLoopWaker {
-    fn wake(&self) {
-        // No-op for headless - we poll manually
+    fn set_cookie(&self, cookie: pb::Cookie) -> Result<(), EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::SetCookie {
+            cookie,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
 
-    fn clone_box(&self) -> Box<dyn EventLoopWaker> {
-        Box::new(HeadlessEventLoopWaker)
+    fn clear_cookies(&self) -> Result<u32, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ClearCookies { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
-}
 
-/// State maintained by the Servo runtime thread
-struct ServoState {
-    servo: Servo,
-    webview: Option<WebView>,
-    rendering_context: Rc<dyn RenderingContext>,
-    state_version: u64,
-    current_url: String,
-    current_title: String,
-    viewport_width: u32,
-    viewport_height: u32,
-    device_scale_factor: f32,
-    last_hit_test: Option<pb::HitTestMap>,
-    clipboard_text: String,
-    clipboard_mode: pb::ClipboardMode,
-    clipboard_allow_read: bool,
-    clipboard_allow_write: bool,
-    clipboard_max_bytes: usize,
-    clipboard_read_allowlist: Vec<String>,
-}
+    fn set_storage(
+        &self,
+        scope: pb::StorageScope,
+        entries: Vec<pb::StorageEntry>,
+    ) -> Result<u32, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::SetStorage {
+            scope,
+            entries,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-fn run_servo_runtime(
-    config: pb::SessionConfig,
-    rx: mpsc::Receiver<ServoCommand>,
-) -> Result<(), EngineError> {
-    // Get viewport dimensions
-    let (width, height, device_scale_factor) = if let Some(ref viewport) = config.viewport {
-        let width = if viewport.width > 0 {
-            viewport.width
-        } else {
-            DEFAULT_VIEWPORT_WIDTH
-        };
-        let height = if viewport.height > 0 {
-            viewport.height
-        } else {
-            DEFAULT_VIEWPORT_HEIGHT
-        };
-        let scale = if viewport.device_scale_factor > 0.0 {
-            viewport.device_scale_factor as f32
-        } else {
-            1.0
-        };
-        (width, height, scale)
-    } else {
-        (DEFAULT_VIEWPORT_WIDTH, DEFAULT_VIEWPORT_HEIGHT, 1.0)
-    };
-    let size = PhysicalSize::new(width, height);
+    fn get_storage(
+        &self,
+        scope: pb::StorageScope,
+        keys: Vec<String>,
+    ) -> Result<pb::GetStorageResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::GetStorage {
+            scope,
+            keys,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-    // Initialize rendering context
-    let rendering_context: Rc<dyn RenderingContext> =
-        Rc::new(SoftwareRenderingContext::new(size).map_err(|e| {
-            EngineError::new(
-                "rendering_init",
-                format!("failed to create rendering context: {:?}", e),
-            )
-        })?);
+    fn clear_storage(&self, scope: pb::StorageScope) -> Result<u32, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ClearStorage {
+            scope,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-    // Build Servo instance
-    let servo = ServoBuilder::default()
-        .event_loop_waker(Box::new(HeadlessEventLoopWaker))
-        .build();
+    fn clear_browsing_data(
+        &self,
+        kinds: pb::ClearBrowsingDataRequest,
+    ) -> Result<pb::ClearBrowsingDataResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ClearBrowsingData {
+            kinds,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-    let mut clipboard_mode = pb::ClipboardMode::Virtual;
-    let mut clipboard_allow_read = false;
-    let mut clipboard_allow_write = true;
-    let mut clipboard_max_bytes = DEFAULT_CLIPBOARD_MAX_BYTES;
-    let mut clipboard_read_allowlist = Vec::new();
-    if let Some(ref policy) = config.clipboard {
-        let mode = pb::ClipboardMode::try_from(policy.mode).unwrap_or(pb::ClipboardMode::Unspecified);
-        if mode != pb::ClipboardMode::Unspecified {
-            clipboard_mode = mode;
-        }
-        clipboard_allow_read = policy.allow_read;
-        clipboard_allow_write = policy.allow_write;
-        if policy.max_bytes > 0 {
-            clipboard_max_bytes = policy.max_bytes as usize;
-        }
-        if !policy.read_allowlist.is_empty() {
-            clipboard_read_allowlist = policy.read_allowlist.clone();
-        }
+    fn wait_for(
+        &self,
+        selector: String,
+        timeout: Duration,
+    ) -> Result<pb::Observation, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::WaitFor {
+            selector,
+            timeout,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
     }
 
-    let mut state = ServoState {
-        servo,
-        webview: None,
-        rendering_context,
-        state_version: 0,
-        current_url: String::new(),
-        current_title: String::new(),
-        viewport_width: width,
-        viewport_height: height,
-        device_scale_factor,
-        last_hit_test: None,
-        clipboard_text: String::new(),
-        clipboard_mode,
-        clipboard_allow_read,
-        clipboard_allow_write,
-        clipboard_max_bytes,
-        clipboard_read_allowlist,
-    };
+    fn open_tab(&self, url: String) -> Result<pb::Tab, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::OpenTab {
+            url,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-    // Command loop
-    while let Ok(cmd) = rx.recv() {
-        // Process pending Servo events
-        state.servo.spin_event_loop();
+    fn close_tab(&self, tab_id: u64) -> Result<u64, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::CloseTab {
+            tab_id,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
 
-        match cmd {
-            ServoCommand::Navigate { url, respond_to } => {
-                let result = handle_navigate(&mut state, &url);
+    fn switch_tab(&self, tab_id: u64) -> Result<pb::Observation, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::SwitchTab {
+            tab_id,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn list_tabs(&self) -> Result<Vec<pb::Tab>, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ListTabs { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn extract_text(&self, max_bytes: usize) -> Result<pb::ExtractTextResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ExtractText {
+            max_bytes,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn clipboard_get(&self) -> Result<pb::ClipboardData, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ClipboardGet { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn clipboard_set(&self, text: String) -> Result<pb::ClipboardData, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ClipboardSet {
+            text,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn fill_form(&self, fields: Vec<pb::FormField>) -> Result<pb::FillFormResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::FillForm {
+            fields,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn query_element(
+        &self,
+        query: pb::QueryElementRequest,
+    ) -> Result<pb::QueryElementResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::QueryElement {
+            query,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn set_file_input(
+        &self,
+        upload: pb::SetFileInputRequest,
+    ) -> Result<pb::SetFileInputResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::SetFileInput {
+            upload,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn list_downloads(&self) -> Result<pb::ListDownloadsResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::ListDownloads { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn get_download(&self, download_id: String) -> Result<pb::GetDownloadResponse, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::GetDownload {
+            download_id,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn scroll_position(&self) -> Result<pb::Point, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self
+            .tx
+            .send(ServoCommand::ScrollPosition { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn set_viewport(&self, viewport: pb::Viewport) -> Result<pb::Observation, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::SetViewport {
+            viewport,
+            respond_to: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    /// The wait runs on the runtime thread like any other command; only the
+    /// caller's side of it is moved out of the engine.
+    fn network_idle_wait(&self, quiet: Duration) -> NetworkIdleWait {
+        let runtime = self.tx.clone();
+        Box::new(move || {
+            let (tx, rx) = mpsc::channel();
+            let _ = runtime.send(ServoCommand::WaitForNetworkIdle {
+                quiet,
+                respond_to: tx,
+            });
+            rx.recv().unwrap_or(false)
+        })
+    }
+
+    fn stop(&self) -> Result<pb::Observation, EngineError> {
+        let (tx, rx) = mpsc::channel();
+        let _ = self.tx.send(ServoCommand::Stop { respond_to: tx });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::new("unavailable", "servo runtime unavailable")))
+    }
+
+    fn shutdown(&self) {
+        let _ = self.tx.send(ServoCommand::Shutdown);
+    }
+}
+
+/// Flags frames Servo has ready so the runtime thread can paint them, and
+/// loads starting so actions know to look for a navigation, and answers file
+/// pickers with the file staged by SetFileInput.
+struct FrameReadyDelegate {
+    frame_ready: Rc<Cell<bool>>,
+    load_started: Rc<Cell<bool>>,
+    pending_upload: Rc<RefCell<Option<PathBuf>>>,
+    last_request: Rc<Cell<Instant>>,
+}
+
+impl WebViewDelegate for FrameReadyDelegate {
+    fn notify_new_frame_ready(&self, _webview: WebView) {
+        self.frame_ready.set(true);
+    }
+
+    fn notify_load_status_changed(&self, _webview: WebView, status: LoadStatus) {
+        if status == LoadStatus::Started {
+            self.load_started.set(true);
+        }
+    }
+
+    fn load_web_resource(&self, _webview: WebView, _load: WebResourceLoad) {
+        // Only noted for network-idle waits; dropping `load` without
+        // intercepting it lets the request continue.
+        self.last_request.set(Instant::now());
+    }
+
+    fn show_embedder_control(&self, _webview: WebView, control: EmbedderControl) {
+        // Pages can't open a picker on their own: without a staged upload
+        // the picker is dismissed as if the user cancelled it.
+        if let EmbedderControl::FilePicker(mut picker) = control {
+            match self.pending_upload.borrow_mut().take() {
+                Some(path) => {
+                    picker.select(&[path]);
+                    picker.submit();
+                }
+                None => picker.dismiss(),
+            }
+        }
+    }
+}
+
+/// Dummy event loop waker for headless operation
+struct HeadlessEventLoopWaker;
+
+impl EventLoopWaker for HeadlessEventLoopWaker {
+    fn wake(&self) {
+        // No-op for headless - we poll manually
+    }
+
+    fn clone_box(&self) -> Box<dyn EventLoopWaker> {
+        Box::new(HeadlessEventLoopWaker)
+    }
+}
+
+/// One webview in the session. `url` and `title` are only kept up to date
+/// while the tab is in the background; the active tab's live values are on
+/// `ServoState`.
+struct ServoTab {
+    id: u64,
+    webview: WebView,
+    url: String,
+    title: String,
+    /// When the tab's webview last started a request, for network-idle waits.
+    last_request: Rc<Cell<Instant>>,
+}
+
+/// State maintained by the Servo runtime thread
+struct ServoState {
+    servo: Servo,
+    /// Scripts run at document start in every tab.
+    user_content: Rc<UserContentManager>,
+    tabs: Vec<ServoTab>,
+    active_tab: usize,
+    next_tab_id: u64,
+    rendering_context: Rc<dyn RenderingContext>,
+    state_version: u64,
+    current_url: String,
+    current_title: String,
+    viewport_width: u32,
+    viewport_height: u32,
+    device_scale_factor: f32,
+    last_hit_test: Option<pb::HitTestMap>,
+    clipboard_text: String,
+    /// OS clipboard for `ClipboardMode::Host`; `clipboard_text` is unused then.
+    host_clipboard: Option<arboard::Clipboard>,
+    clipboard_mode: pb::ClipboardMode,
+    clipboard_allow_read: bool,
+    clipboard_allow_write: bool,
+    clipboard_max_bytes: usize,
+    clipboard_read_allowlist: Vec<String>,
+    frame_delta_encoding: bool,
+    navigation_timeout: Duration,
+    post_load_settle: Duration,
+    js_budget: Option<Duration>,
+    /// Set for the duration of an act call with `action_timeout_ms`; script
+    /// evaluations stop at this deadline.
+    action_deadline: Option<Instant>,
+    /// Raised by a Stop to cut short the navigate waiting in `handle_navigate`.
+    stop: StopSignal,
+    request_headers: Vec<(String, String)>,
+    http_status: u32,
+    /// Set by `FrameReadyDelegate`, cleared once the frame is painted.
+    frame_ready: Rc<Cell<bool>>,
+    /// Set by `FrameReadyDelegate` when any tab starts a load; cleared
+    /// before each action.
+    load_started: Rc<Cell<bool>>,
+    /// File SetFileInput staged for the next file picker; taken by
+    /// `FrameReadyDelegate`.
+    pending_upload: Rc<RefCell<Option<PathBuf>>>,
+    /// Per-session directory uploaded files are written to, removed when the
+    /// runtime shuts down.
+    upload_dir: tempfile::TempDir,
+    /// Files captured from `<a download>` clicks; see `download_hook_script`.
+    downloads: DownloadStore,
+    allowed_schemes: Vec<String>,
+    paint_epoch: u64,
+    /// `state_version` as of the last paint; when it still matches, no
+    /// change of ours is waiting to show up in a frame.
+    painted_version: u64,
+}
+
+impl ServoState {
+    fn active_webview(&self) -> Option<&WebView> {
+        self.tabs.get(self.active_tab).map(|tab| &tab.webview)
+    }
+}
+
+fn run_servo_runtime(
+    config: pb::SessionConfig,
+    rx: mpsc::Receiver<ServoCommand>,
+    stop: StopSignal,
+) -> Result<(), EngineError> {
+    // Get viewport dimensions
+    let viewport = session_viewport(&config);
+    let (width, height) = (viewport.width, viewport.height);
+    let device_scale_factor = viewport.device_scale_factor as f32;
+    let size = PhysicalSize::new(width, height);
+
+    // Initialize rendering context
+    let rendering_context = create_rendering_context(render_backend(), size)?;
+
+    // Build Servo instance. No config dir is set, so cookies and storage stay
+    // in memory and every session is already ephemeral as far as Servo goes.
+    let servo = ServoBuilder::default()
+        .preferences(session_preferences(&config))
+        .event_loop_waker(Box::new(HeadlessEventLoopWaker))
+        .build();
+    let user_content = Rc::new(UserContentManager::new(&servo));
+    user_content.add_script(Rc::new(UserScript::new(console_capture_script(), None)));
+    user_content.add_script(Rc::new(UserScript::new(page_error_capture_script(), None)));
+    user_content.add_script(Rc::new(UserScript::new(
+        NETWORK_TRACKING_SCRIPT.to_string(),
+        None,
+    )));
+    user_content.add_script(Rc::new(UserScript::new(
+        download_hook_script(config.downloads_enabled),
+        None,
+    )));
+    if !config.timezone.is_empty() {
+        // Servo reads the timezone from the process environment, which is
+        // shared by every session, so a per-session override can't be honored.
+        log::warn!(
+            "servo: timezone override {:?} not supported, using host timezone",
+            config.timezone
+        );
+    }
+
+    let policy = session_clipboard_policy(config, default_clipboard_policy());
+    let clipboard_mode = match pb::ClipboardMode::try_from(policy.mode) {
+        Ok(pb::ClipboardMode::Unspecified) | Err(_) => pb::ClipboardMode::Virtual,
+        Ok(mode) => mode,
+    };
+    let clipboard_allow_read = policy.allow_read;
+    let clipboard_allow_write = policy.allow_write;
+    let clipboard_max_bytes = if policy.max_bytes > 0 {
+        policy.max_bytes as usize
+    } else {
+        DEFAULT_CLIPBOARD_MAX_BYTES
+    };
+    let clipboard_read_allowlist = policy.read_allowlist;
+
+    // Host mode is gated by the daemon (BROWSERD_SECURITY_HOST_CLIPBOARD), so
+    // by the time a session gets here it is allowed.
+    let host_clipboard = if clipboard_mode == pb::ClipboardMode::Host {
+        Some(arboard::Clipboard::new().map_err(host_clipboard_error)?)
+    } else {
+        None
+    };
+
+    let mut state = ServoState {
+        servo,
+        user_content,
+        tabs: Vec::new(),
+        active_tab: 0,
+        next_tab_id: 1,
+        rendering_context,
+        state_version: 0,
+        current_url: String::new(),
+        current_title: String::new(),
+        viewport_width: width,
+        viewport_height: height,
+        device_scale_factor,
+        last_hit_test: None,
+        clipboard_text: String::new(),
+        host_clipboard,
+        clipboard_mode,
+        clipboard_allow_read,
+        clipboard_allow_write,
+        clipboard_max_bytes,
+        clipboard_read_allowlist,
+        frame_delta_encoding: config.frame_delta_encoding,
+        navigation_timeout: navigation_timeout(
+            config.navigation_timeout_ms,
+            Duration::from_millis(u64::from(DEFAULT_NAVIGATION_TIMEOUT_MS)),
+        ),
+        post_load_settle: post_load_settle(&config),
+        js_budget: (config.js_budget_ms > 0)
+            .then(|| Duration::from_millis(u64::from(config.js_budget_ms))),
+        action_deadline: None,
+        stop,
+        request_headers: sorted_request_headers(&session_request_headers(&config)),
+        http_status: 0,
+        frame_ready: Rc::new(Cell::new(false)),
+        load_started: Rc::new(Cell::new(false)),
+        pending_upload: Rc::new(RefCell::new(None)),
+        upload_dir: session_temp_dir(&config.session_id, "uploads")?,
+        downloads: DownloadStore::new(&config),
+        allowed_schemes: config.allowed_schemes.clone(),
+        paint_epoch: 0,
+        painted_version: 0,
+    };
+
+    // Command loop. Handlers run under `catch_panic`, so a panicking
+    // command answers `engine_panic` instead of taking the session down.
+    while let Ok(cmd) = rx.recv() {
+        // Process pending Servo events
+        state.servo.spin_event_loop();
+
+        match cmd {
+            ServoCommand::Navigate {
+                url,
+                timeout_ms,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_navigate(&mut state, &url, timeout_ms));
                 let _ = respond_to.send(result);
             }
             ServoCommand::Observe { opts, respond_to } => {
-                let result = handle_observe(&mut state, &opts);
+                let result = catch_panic(|| handle_observe(&mut state, &opts));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::Act { action, respond_to } => {
+                let result = catch_panic(|| handle_act(&mut state, &action));
+                // `handle_act` clears the deadline itself unless it panicked;
+                // a stale one would cap every later eval in the session.
+                state.action_deadline = None;
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::StreamEvent {
+                event_type,
+                hit_test,
+                mut base,
+                respond_to,
+            } => {
+                let result = catch_panic(|| {
+                    handle_stream_event(&mut state, event_type, &hit_test, &mut base)
+                });
+                let _ = respond_to.send((result, base));
+            }
+            ServoCommand::CheckContrast {
+                node_ids,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_check_contrast(&mut state, &node_ids));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ExportPdf {
+                page_size,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_export_pdf(&mut state, page_size.as_ref()));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::GetCookies { respond_to } => {
+                let result = catch_panic(|| handle_get_cookies(&mut state));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::SetCookie { cookie, respond_to } => {
+                let result = catch_panic(|| handle_set_cookie(&mut state, &cookie));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ClearCookies { respond_to } => {
+                let result = catch_panic(|| handle_clear_cookies(&mut state));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::SetStorage {
+                scope,
+                entries,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_set_storage(&mut state, scope, &entries));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::GetStorage {
+                scope,
+                keys,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_get_storage(&mut state, scope, &keys));
                 let _ = respond_to.send(result);
             }
-            ServoCommand::Act { action, respond_to } => {
-                let result = handle_act(&mut state, &action);
-                let _ = respond_to.send(result);
+            ServoCommand::ClearStorage { scope, respond_to } => {
+                let result = catch_panic(|| handle_clear_storage(&mut state, scope));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ClearBrowsingData { kinds, respond_to } => {
+                let result = catch_panic(|| handle_clear_browsing_data(&mut state, &kinds));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::WaitFor {
+                selector,
+                timeout,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_wait_for(&mut state, &selector, timeout));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::OpenTab { url, respond_to } => {
+                let result = catch_panic(|| handle_open_tab(&mut state, &url));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::CloseTab { tab_id, respond_to } => {
+                let result = catch_panic(|| handle_close_tab(&mut state, tab_id));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::SwitchTab { tab_id, respond_to } => {
+                let result = catch_panic(|| handle_switch_tab(&mut state, tab_id));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ListTabs { respond_to } => {
+                let _ = respond_to.send(Ok(tab_list(&state)));
+            }
+            ServoCommand::ExtractText {
+                max_bytes,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_extract_text(&mut state, max_bytes));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ClipboardGet { respond_to } => {
+                let result = catch_panic(|| handle_clipboard_get(&mut state));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ClipboardSet { text, respond_to } => {
+                let result = catch_panic(|| handle_clipboard_set(&mut state, &text));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::FillForm { fields, respond_to } => {
+                let result = catch_panic(|| handle_fill_form(&mut state, &fields));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::QueryElement { query, respond_to } => {
+                let result = catch_panic(|| handle_query_element(&mut state, &query));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::SetFileInput { upload, respond_to } => {
+                let result = catch_panic(|| handle_set_file_input(&mut state, &upload));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::ListDownloads { respond_to } => {
+                let _ = respond_to.send(Ok(state.downloads.list()));
+            }
+            ServoCommand::GetDownload {
+                download_id,
+                respond_to,
+            } => {
+                let _ = respond_to.send(state.downloads.get(&download_id));
+            }
+            ServoCommand::ScrollPosition { respond_to } => {
+                let result = catch_panic(|| handle_scroll_position(&mut state));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::SetViewport {
+                viewport,
+                respond_to,
+            } => {
+                let result = catch_panic(|| handle_set_viewport(&mut state, &viewport));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::WaitForNetworkIdle { quiet, respond_to } => {
+                let idle = catch_panic(|| Ok(wait_for_network_idle(&mut state, quiet)));
+                let _ = respond_to.send(idle.unwrap_or(false));
+            }
+            ServoCommand::Stop { respond_to } => {
+                let result = catch_panic(|| handle_stop(&mut state));
+                let _ = respond_to.send(result);
+            }
+            ServoCommand::GetStateVersion { respond_to } => {
+                let _ = respond_to.send(state.state_version);
+            }
+            ServoCommand::Shutdown => {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the rendering context for `backend`. A GL context that can't be
+/// created falls back to software with a warning, so a bad backend setting
+/// never stops a session from starting.
+fn create_rendering_context(
+    backend: RenderBackend,
+    size: PhysicalSize<u32>,
+) -> Result<Rc<dyn RenderingContext>, EngineError> {
+    if backend == RenderBackend::Gl {
+        match gl_rendering_context(size) {
+            Ok(context) => return Ok(context),
+            Err(err) => log::warn!(
+                "GL rendering unavailable, falling back to software: {}",
+                err.message
+            ),
+        }
+    }
+    let context = SoftwareRenderingContext::new(size).map_err(|e| {
+        EngineError::new(
+            "rendering_init",
+            format!("failed to create rendering context: {:?}", e),
+        )
+    })?;
+    Ok(Rc::new(context))
+}
+
+/// A GPU-backed context for headless use. libservo only builds GL contexts
+/// on top of a native window (`WindowRenderingContext` and the offscreen
+/// contexts derived from it), and the daemon has no window to give it, so
+/// this reports the backend as unavailable until libservo grows a headless
+/// hardware context.
+fn gl_rendering_context(_size: PhysicalSize<u32>) -> Result<Rc<dyn RenderingContext>, EngineError> {
+    Err(EngineError::new(
+        "rendering_init",
+        "libservo has no headless GL rendering context",
+    ))
+}
+
+fn handle_navigate(
+    state: &mut ServoState,
+    url_str: &str,
+    timeout_ms: u32,
+) -> Result<pb::Observation, EngineError> {
+    let url = Url::parse(url_str)
+        .map_err(|e| EngineError::new("invalid_url", format!("failed to parse URL: {}", e)))?;
+    check_scheme(&url, &state.allowed_schemes)?;
+    // Servo renders an internal error page for unreadable files and reports
+    // the load as complete, so check local paths up front.
+    if url.scheme() == "file" {
+        let exists = url
+            .to_file_path()
+            .map(|path| path.exists())
+            .unwrap_or(false);
+        if !exists {
+            return Err(EngineError::new(
+                "load_failed",
+                format!("file not found: {}", url.path()),
+            ));
+        }
+    }
+
+    // Load into the active tab, creating the first tab on first navigation
+    if let Some(webview) = state.active_webview() {
+        webview.load(url.clone());
+    } else {
+        add_tab(state, url.clone());
+    }
+    if !state.request_headers.is_empty() {
+        // libservo's embedding API has no hook for adding headers to
+        // outgoing requests yet, so the Accept-Language derived from the
+        // locale is kept but not sent. Warn so callers don't assume it was.
+        let names: Vec<&str> = state
+            .request_headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        log::warn!(
+            "servo: custom request headers not sent (no embedder hook): {}",
+            names.join(", ")
+        );
+    }
+
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "failed to create webview"))?;
+    let timeout = navigation_timeout(timeout_ms, state.navigation_timeout);
+    let deadline = Instant::now() + timeout;
+    let stopped = wait_for_load_or_stop(state, &webview, timeout)?;
+    if stopped {
+        // A Stop arrived while the page was loading: end the load with what
+        // has arrived and observe that, as Stop would once this returned.
+        evaluate_javascript_sync(state, &webview, "window.stop(); true")?;
+        state.servo.spin_event_loop();
+    }
+    let settle_until = deadline.min(Instant::now() + state.post_load_settle);
+    while !stopped && Instant::now() < settle_until {
+        state.servo.spin_event_loop();
+        thread::sleep(Duration::from_millis(SPIN_POLL_INTERVAL_MS));
+    }
+
+    state.state_version += 1;
+    state.last_hit_test = None;
+    state.current_url = url_str.to_string();
+    state.current_title.clear();
+    refresh_page_metadata(state, &webview);
+    state.http_status = navigation_http_status(state, &webview);
+    if !stopped {
+        check_http_status(&url, state.http_status)?;
+    }
+
+    build_observation(state, &pb::ObserveOptions::default())
+}
+
+/// Create a tab loading `url` and make it active.
+fn add_tab(state: &mut ServoState, url: Url) {
+    park_active_tab(state);
+    let last_request = Rc::new(Cell::new(Instant::now()));
+    let webview = WebViewBuilder::new(&state.servo, state.rendering_context.clone())
+        .url(url)
+        .user_content_manager(state.user_content.clone())
+        .delegate(Rc::new(FrameReadyDelegate {
+            frame_ready: state.frame_ready.clone(),
+            load_started: state.load_started.clone(),
+            pending_upload: state.pending_upload.clone(),
+            last_request: last_request.clone(),
+        }))
+        .build();
+    state.tabs.push(ServoTab {
+        id: state.next_tab_id,
+        webview,
+        url: String::new(),
+        title: String::new(),
+        last_request,
+    });
+    state.next_tab_id += 1;
+    state.active_tab = state.tabs.len() - 1;
+    raise_active_tab(state);
+}
+
+/// Save the active tab's URL and title before another tab takes over.
+fn park_active_tab(state: &mut ServoState) {
+    let url = state.current_url.clone();
+    let title = state.current_title.clone();
+    if let Some(tab) = state.tabs.get_mut(state.active_tab) {
+        tab.url = url;
+        tab.title = title;
+    }
+}
+
+/// Show and focus the active tab's webview and drop state cached for the
+/// previous tab.
+fn raise_active_tab(state: &mut ServoState) {
+    if let Some(webview) = state.active_webview() {
+        webview.show(true);
+        webview.focus();
+    }
+    state.state_version += 1;
+    state.last_hit_test = None;
+}
+
+fn activate_tab(state: &mut ServoState, index: usize) {
+    park_active_tab(state);
+    state.active_tab = index;
+    state.current_url = std::mem::take(&mut state.tabs[index].url);
+    state.current_title = std::mem::take(&mut state.tabs[index].title);
+    raise_active_tab(state);
+    let webview = state.tabs[index].webview.clone();
+    refresh_page_metadata(state, &webview);
+    state.http_status = navigation_http_status(state, &webview);
+}
+
+fn tab_index(state: &ServoState, tab_id: u64) -> Result<usize, EngineError> {
+    state
+        .tabs
+        .iter()
+        .position(|tab| tab.id == tab_id)
+        .ok_or_else(|| EngineError::new("tab_not_found", format!("no tab with id {tab_id}")))
+}
+
+fn tab_list(state: &ServoState) -> Vec<pb::Tab> {
+    state
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(index, tab)| {
+            let active = index == state.active_tab;
+            pb::Tab {
+                tab_id: tab.id,
+                url: if active {
+                    state.current_url.clone()
+                } else {
+                    tab.url.clone()
+                },
+                title: if active {
+                    state.current_title.clone()
+                } else {
+                    tab.title.clone()
+                },
+                active,
+            }
+        })
+        .collect()
+}
+
+fn handle_open_tab(state: &mut ServoState, url_str: &str) -> Result<pb::Tab, EngineError> {
+    let url_str = if url_str.is_empty() {
+        "about:blank"
+    } else {
+        url_str
+    };
+    let url = Url::parse(url_str)
+        .map_err(|e| EngineError::new("invalid_url", format!("failed to parse URL: {}", e)))?;
+    check_scheme(&url, &state.allowed_schemes)?;
+    add_tab(state, url);
+    state.current_url = url_str.to_string();
+    state.current_title.clear();
+
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "failed to create webview"))?;
+    let timeout = state.navigation_timeout;
+    wait_for_load(state, &webview, timeout)?;
+    refresh_page_metadata(state, &webview);
+    state.http_status = navigation_http_status(state, &webview);
+    Ok(tab_list(state).swap_remove(state.active_tab))
+}
+
+fn handle_close_tab(state: &mut ServoState, tab_id: u64) -> Result<u64, EngineError> {
+    let index = tab_index(state, tab_id)?;
+    if state.tabs.len() == 1 {
+        return Err(EngineError::new(
+            "invalid_request",
+            "cannot close the last tab",
+        ));
+    }
+    if index == state.active_tab {
+        let next = if index + 1 < state.tabs.len() {
+            index + 1
+        } else {
+            index - 1
+        };
+        activate_tab(state, next);
+    }
+    // Dropping the last handle closes the webview.
+    state.tabs.remove(index);
+    if index < state.active_tab {
+        state.active_tab -= 1;
+    }
+    Ok(state.tabs[state.active_tab].id)
+}
+
+fn handle_switch_tab(state: &mut ServoState, tab_id: u64) -> Result<pb::Observation, EngineError> {
+    let index = tab_index(state, tab_id)?;
+    if index != state.active_tab {
+        activate_tab(state, index);
+    }
+    build_observation(state, &pb::ObserveOptions::default())
+}
+
+/// HTTP status of the current document from the Navigation Timing entry, or
+/// 0 when the page wasn't loaded over HTTP or the engine doesn't report it.
+fn navigation_http_status(state: &mut ServoState, webview: &WebView) -> u32 {
+    let script = r#"(function() {
+        const entries = performance.getEntriesByType
+            ? performance.getEntriesByType("navigation")
+            : [];
+        const entry = entries[0];
+        return String((entry && entry.responseStatus) || 0);
+    })()"#;
+    match evaluate_javascript_sync(state, webview, script) {
+        Ok(JSValue::String(status)) => status.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Fail a navigation that ended on an HTTP error, or on no HTTP response at
+/// all: an http(s) load reporting status 0 failed in the network.
+fn check_http_status(url: &Url, status: u32) -> Result<(), EngineError> {
+    if status == 0 && matches!(url.scheme(), "http" | "https") {
+        return Err(EngineError::new(
+            "load_failed",
+            format!("navigation to {url} got no HTTP response"),
+        ));
+    }
+    if status >= 400 {
+        return Err(EngineError::new(
+            "load_failed",
+            format!("navigation failed with HTTP status {status}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Servo preferences for a session; empty config fields keep Servo defaults.
+fn session_preferences(config: &pb::SessionConfig) -> Preferences {
+    let mut prefs = Preferences::default();
+    if !config.user_agent.trim().is_empty() {
+        prefs.user_agent = config.user_agent.trim().to_string();
+    }
+    prefs
+}
+
+/// The session's custom headers plus `Accept-Language` from `locale`, unless
+/// the caller already set that header explicitly.
+fn session_request_headers(config: &pb::SessionConfig) -> HashMap<String, String> {
+    let mut headers = config.request_headers.clone();
+    let locale = config.locale.trim();
+    let has_accept_language = headers
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("accept-language"));
+    if !locale.is_empty() && !has_accept_language {
+        headers.insert("Accept-Language".to_string(), locale.to_string());
+    }
+    headers
+}
+
+fn sorted_request_headers(headers: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    headers.sort();
+    headers
+}
+
+fn handle_observe(
+    state: &mut ServoState,
+    opts: &pb::ObserveOptions,
+) -> Result<pb::Observation, EngineError> {
+    // Pump event loop
+    state.servo.spin_event_loop();
+
+    let network_idle = opts.until_network_idle_ms > 0
+        && wait_for_network_idle(
+            state,
+            Duration::from_millis(u64::from(opts.until_network_idle_ms)),
+        );
+    let mut obs = build_observation(state, opts)?;
+    obs.network_idle = network_idle;
+    Ok(obs)
+}
+
+/// Wait for the active tab to finish loading and then for `quiet` to pass
+/// with no request starting in that tab (as seen by its webview delegate)
+/// and no fetch/XHR in flight (as counted by `network_tracking_script`). Returns
+/// false if the navigation timeout passes first.
+fn wait_for_network_idle(state: &mut ServoState, quiet: Duration) -> bool {
+    let Some(tab) = state.tabs.get(state.active_tab) else {
+        return true;
+    };
+    let webview = tab.webview.clone();
+    let last_request = tab.last_request.clone();
+    let timeout = state.navigation_timeout;
+    let deadline = Instant::now() + timeout;
+    if wait_for_load(state, &webview, timeout).is_err() {
+        return false;
+    }
+    let mut quiet_since = last_request.get();
+    let mut attempt = 0;
+    loop {
+        state.servo.spin_event_loop();
+        let in_flight =
+            match evaluate_javascript_sync(state, &webview, "window.__buckleyInflight || 0") {
+                Ok(JSValue::Number(count)) => count,
+                _ => 0.0,
+            };
+        let now = Instant::now();
+        if in_flight > 0.0 {
+            quiet_since = now;
+        }
+        quiet_since = quiet_since.max(last_request.get());
+        if now.duration_since(quiet_since) >= quiet {
+            return true;
+        }
+        if now >= deadline {
+            return false;
+        }
+        thread::sleep(poll_interval(attempt).min(deadline - now));
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+fn handle_act(
+    state: &mut ServoState,
+    action: &pb::Action,
+) -> Result<pb::ActionResult, EngineError> {
+    state.action_deadline = action_timeout(action).map(|timeout| Instant::now() + timeout);
+    let result = match dispatch_act(state, action) {
+        Err(err) if err.code == "stale_state" && retries_on_stale(action) => {
+            // Node ids and points came from an older observation, so refresh
+            // the hit-test map they resolve against and try once more at the
+            // current version.
+            state.last_hit_test = build_hit_test_map(state, &HitTestQuery::default());
+            let retry = pb::Action {
+                expected_state_version: state.state_version,
+                ..action.clone()
+            };
+            dispatch_act(state, &retry)
+        }
+        result => result,
+    };
+    state.action_deadline = None;
+    result
+}
+
+fn dispatch_act(
+    state: &mut ServoState,
+    action: &pb::Action,
+) -> Result<pb::ActionResult, EngineError> {
+    // Reject unsupported types before touching the webview so errors match
+    // the stub engine exactly.
+    let action_type = parse_action_type(action.r#type)?;
+    check_type_length(action_type, action, max_type_chars())?;
+    if state.active_webview().is_none() {
+        return Err(EngineError::new(
+            "no_webview",
+            "no webview active - navigate first",
+        ));
+    }
+    // Form-control actions work on the element itself, so they keep the
+    // target as given rather than the point a selector resolves to.
+    let element_target = action.target.clone();
+    let action = &resolve_selector_targets(state, action)?;
+    if action.dry_run {
+        // Scrolling into view would change the page, so a dry run reports
+        // the target where it is now.
+        if is_stale(action, state.state_version) {
+            return Err(stale_state_error(
+                action.expected_state_version,
+                state.state_version,
+            ));
+        }
+        let (resolved_node_id, resolved_point) = resolved_target(state, action.target.as_ref());
+        return Ok(dry_run_result(
+            state.state_version,
+            resolved_node_id,
+            resolved_point,
+        ));
+    }
+    let action = &scroll_target_into_view(state, action_type, action)?;
+    let (resolved_node_id, resolved_point) = resolved_target(state, action.target.as_ref());
+
+    let webview = &state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+
+    // Check state version if provided
+    if is_stale(action, state.state_version) {
+        return Err(stale_state_error(
+            action.expected_state_version,
+            state.state_version,
+        ));
+    }
+
+    // Clicks and Enter on a focused link are what start downloads and
+    // navigations.
+    let captures_downloads = matches!(action_type, pb::ActionType::Click | pb::ActionType::Key);
+    let url_before = state.current_url.clone();
+    state.load_started.set(false);
+
+    // Dispatch action based on type
+    let summary = match action_type {
+        pb::ActionType::Click => {
+            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
+                EngineError::new("invalid_target", "click requires a target point")
+            })?;
+            let (pb_button, count) = click_params(action);
+            let button = match pb_button {
+                pb::MouseButton::Middle => MouseButton::Middle,
+                pb::MouseButton::Right => MouseButton::Right,
+                pb::MouseButton::Left | pb::MouseButton::Unspecified => MouseButton::Left,
+            };
+            send_mouse_move(webview, point);
+            // Repeated down/up pairs within one act call are close enough in
+            // time for the page to see a double or triple click.
+            for _ in 0..count {
+                send_mouse_button(webview, point, MouseButtonAction::Down, button);
+                send_mouse_button(webview, point, MouseButtonAction::Up, button);
+            }
+            action_point_summary(
+                &click_verb(pb_button, count),
+                resolved_node_id,
+                resolved_point.as_ref(),
+            )
+        }
+        pb::ActionType::Type => {
+            if action.text.is_empty() {
+                return Err(EngineError::new(
+                    "invalid_request",
+                    "type action requires text",
+                ));
+            }
+            if let Some(point) = action_point(state, action.target.as_ref()) {
+                send_mouse_move(webview, point);
+                send_mouse_button(webview, point, MouseButtonAction::Down, MouseButton::Left);
+                send_mouse_button(webview, point, MouseButtonAction::Up, MouseButton::Left);
+            }
+            let modifiers = modifiers_from_action(action);
+            send_text(webview, &action.text, modifiers);
+            format!(
+                "typed {} chars into node {}",
+                grapheme_count(&action.text),
+                resolved_node_id
+            )
+        }
+        pb::ActionType::TypeInto => {
+            if action.text.is_empty() {
+                return Err(EngineError::new(
+                    "invalid_request",
+                    "type into action requires text",
+                ));
+            }
+            let selector = element_target
+                .as_ref()
+                .map_or("", |target| target.selector.trim());
+            if selector.is_empty() {
+                return Err(EngineError::new(
+                    "invalid_request",
+                    "type into action requires a target selector",
+                ));
+            }
+            let script = editable_check_script(state, element_target.as_ref())?;
+            let value = evaluate_javascript_sync(state, webview, &script)?;
+            if js_value_to_string(value)? != "ok" {
+                return Err(EngineError::new(
+                    "invalid_target",
+                    "target is not an editable element",
+                ));
+            }
+            // Clicking the centre focuses the element and puts the caret
+            // where a user would, so typing goes through the normal key path.
+            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
+                EngineError::new("invalid_target", "type into requires a target point")
+            })?;
+            send_mouse_move(webview, point);
+            send_mouse_button(webview, point, MouseButtonAction::Down, MouseButton::Left);
+            send_mouse_button(webview, point, MouseButtonAction::Up, MouseButton::Left);
+            send_text(webview, &action.text, modifiers_from_action(action));
+            format!(
+                "typed {} chars into {}",
+                grapheme_count(&action.text),
+                selector
+            )
+        }
+        pb::ActionType::Scroll => {
+            let scroll = action.scroll.as_ref().ok_or_else(|| {
+                EngineError::new("invalid_request", "scroll action requires delta")
+            })?;
+            let point =
+                action_point(state, action.target.as_ref()).unwrap_or_else(|| default_point(state));
+            send_scroll(webview, point, scroll);
+            format!("scrolled {} {}", scroll.y, scroll_unit_label(scroll.unit))
+        }
+        pb::ActionType::Hover => {
+            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
+                EngineError::new("invalid_target", "hover requires a target point")
+            })?;
+            send_mouse_move(webview, point);
+            action_point_summary("hovered", resolved_node_id, resolved_point.as_ref())
+        }
+        pb::ActionType::Drag => {
+            let start = action_coords(state, action.target.as_ref())
+                .ok_or_else(|| EngineError::new("invalid_target", "drag requires a start point"))?;
+            let end = action_coords(state, action.drag_to.as_ref())
+                .ok_or_else(|| EngineError::new("invalid_target", "drag requires an end target"))?;
+            let start_point = webview_point(state, start.0, start.1);
+            send_mouse_move(webview, start_point);
+            send_mouse_button(
+                webview,
+                start_point,
+                MouseButtonAction::Down,
+                MouseButton::Left,
+            );
+            let mut last_point = start_point;
+            for (x, y) in drag_path(start, end, action.drag_steps) {
+                last_point = webview_point(state, x, y);
+                send_mouse_move(webview, last_point);
+            }
+            send_mouse_button(
+                webview,
+                last_point,
+                MouseButtonAction::Up,
+                MouseButton::Left,
+            );
+            format!(
+                "dragged node {} to ({}, {}) in {} moves",
+                resolved_node_id,
+                end.0,
+                end.1,
+                drag_steps(action.drag_steps) + 1
+            )
+        }
+        pb::ActionType::Key => {
+            if action.key.is_empty() {
+                return Err(EngineError::new(
+                    "invalid_request",
+                    "key action requires key",
+                ));
+            }
+            let modifiers = modifiers_from_action(action);
+            let repeat = key_repeat(action);
+            for _ in 0..repeat {
+                send_key(webview, &action.key, modifiers);
+            }
+            if repeat > 1 {
+                format!("pressed key {} {repeat} times", action.key)
+            } else {
+                format!("pressed key {}", action.key)
+            }
+        }
+        pb::ActionType::Tab => {
+            // Shift in the modifiers turns this into Shift+Tab.
+            let modifiers = modifiers_from_action(action);
+            for _ in 0..key_repeat(action) {
+                send_key(webview, "Tab", modifiers);
+            }
+            let key = if has_shift(action) {
+                "Shift+Tab"
+            } else {
+                "Tab"
+            };
+            format!("moved focus via {key}")
+        }
+        pb::ActionType::Focus => {
+            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
+                EngineError::new("invalid_target", "focus requires a target point")
+            })?;
+            send_mouse_move(webview, point);
+            send_mouse_button(webview, point, MouseButtonAction::Down, MouseButton::Left);
+            send_mouse_button(webview, point, MouseButtonAction::Up, MouseButton::Left);
+            format!("focused node {}", resolved_node_id)
+        }
+        pb::ActionType::ClipboardRead => {
+            let data = handle_clipboard_get(state)?;
+            let observation = build_observation(state, &pb::ObserveOptions::default())?;
+            state.state_version += 1;
+            return Ok(pb::ActionResult {
+                state_version: state.state_version,
+                observation: Some(observation),
+                effects: vec![pb::Effect {
+                    kind: "clipboard_read".to_string(),
+                    summary: format!("clipboard read {} bytes", data.bytes),
+                    metadata: clipboard_metadata(
+                        Some(&data.text),
+                        data.bytes as usize,
+                        clipboard_mode_label(state.clipboard_mode),
+                        clipboard_source(state),
+                    ),
+                }],
+                resolved_node_id,
+                resolved_point,
+                caused_navigation: false,
+            });
+        }
+        pb::ActionType::ClipboardWrite => {
+            let data = handle_clipboard_set(state, &action.text)?;
+            let observation = build_observation(state, &pb::ObserveOptions::default())?;
+            state.state_version += 1;
+            return Ok(pb::ActionResult {
+                state_version: state.state_version,
+                observation: Some(observation),
+                effects: vec![pb::Effect {
+                    kind: "clipboard_write".to_string(),
+                    summary: format!("clipboard wrote {} bytes", data.bytes),
+                    metadata: clipboard_metadata(
+                        None,
+                        data.bytes as usize,
+                        clipboard_mode_label(state.clipboard_mode),
+                        clipboard_source(state),
+                    ),
+                }],
+                resolved_node_id,
+                resolved_point,
+                caused_navigation: false,
+            });
+        }
+        pb::ActionType::SelectOption => {
+            if action.text.is_empty() {
+                return Err(EngineError::new(
+                    "invalid_request",
+                    "select option action requires text",
+                ));
+            }
+            let script = select_option_script(state, element_target.as_ref(), &action.text)?;
+            let value = evaluate_javascript_sync(state, webview, &script)?;
+            let json = js_value_to_string(value)?;
+
+            #[derive(serde::Deserialize)]
+            struct SelectedJson {
+                #[serde(default)]
+                error: String,
+                #[serde(default)]
+                value: String,
+                #[serde(default)]
+                label: String,
             }
-            ServoCommand::StreamEvent {
-                event_type,
-                respond_to,
-            } => {
-                let result = handle_stream_event(&mut state, event_type);
-                let _ = respond_to.send(result);
+
+            let selected: SelectedJson = serde_json::from_str(&json).map_err(|e| {
+                EngineError::new(
+                    "script_error",
+                    format!("select option JSON parse error: {}", e),
+                )
+            })?;
+            match selected.error.as_str() {
+                "" => {}
+                "not_select" => {
+                    return Err(EngineError::new(
+                        "invalid_target",
+                        "target is not a select element",
+                    ));
+                }
+                _ => {
+                    return Err(EngineError::new(
+                        "invalid_target",
+                        format!("no option matches {:?}", action.text),
+                    ));
+                }
             }
-            ServoCommand::GetStateVersion { respond_to } => {
-                let _ = respond_to.send(state.state_version);
+            // Let change handlers run before observing.
+            state.servo.spin_event_loop();
+            state.state_version += 1;
+            state.last_hit_test = None;
+            let observation = build_observation(state, &pb::ObserveOptions::default())?;
+            return Ok(pb::ActionResult {
+                state_version: state.state_version,
+                observation: Some(observation),
+                effects: vec![pb::Effect {
+                    kind: "select_option".to_string(),
+                    summary: format!("selected option {:?}", selected.value),
+                    metadata: selection_metadata(&selected.value, &selected.label),
+                }],
+                resolved_node_id,
+                resolved_point,
+                caused_navigation: false,
+            });
+        }
+        pb::ActionType::SetChecked => {
+            let script = set_checked_script(state, element_target.as_ref(), action.checked)?;
+            let value = evaluate_javascript_sync(state, webview, &script)?;
+            let json = js_value_to_string(value)?;
+
+            #[derive(serde::Deserialize)]
+            struct CheckedJson {
+                #[serde(default)]
+                error: String,
+                #[serde(default)]
+                checked: bool,
+                #[serde(default)]
+                changed: bool,
             }
-            ServoCommand::Shutdown => {
-                break;
+
+            let outcome: CheckedJson = serde_json::from_str(&json).map_err(|e| {
+                EngineError::new(
+                    "script_error",
+                    format!("set checked JSON parse error: {}", e),
+                )
+            })?;
+            match outcome.error.as_str() {
+                "" => {}
+                "not_checkable" => {
+                    return Err(EngineError::new(
+                        "invalid_target",
+                        "target is not a checkbox or radio button",
+                    ));
+                }
+                _ => {
+                    return Err(EngineError::new(
+                        "invalid_request",
+                        "a radio button can't be unchecked directly",
+                    ));
+                }
             }
+            state.servo.spin_event_loop();
+            state.state_version += 1;
+            state.last_hit_test = None;
+            let observation = build_observation(state, &pb::ObserveOptions::default())?;
+            return Ok(pb::ActionResult {
+                state_version: state.state_version,
+                observation: Some(observation),
+                effects: vec![pb::Effect {
+                    kind: "set_checked".to_string(),
+                    summary: checked_summary(outcome.checked, outcome.changed),
+                    metadata: checked_metadata(outcome.checked, outcome.changed),
+                }],
+                resolved_node_id,
+                resolved_point,
+                caused_navigation: false,
+            });
+        }
+        pb::ActionType::Unspecified => unreachable!("rejected by parse_action_type"),
+    };
+
+    // Pump events after action
+    state.servo.spin_event_loop();
+    let mut effects = vec![pb::Effect {
+        kind: action_type_label(action_type).to_string(),
+        summary,
+        metadata: action_metadata(action_type, action, resolved_point.as_ref()),
+    }];
+    let mut caused_navigation = false;
+    if captures_downloads {
+        effects.extend(collect_downloads(state, webview)?);
+        // Wait out any load the action started so the observation below
+        // shows the new page rather than one mid-navigation.
+        if let Some(effect) = navigation_effect(state, webview, &url_before) {
+            caused_navigation = true;
+            effects.push(effect);
         }
     }
+    state.state_version += 1;
 
-    Ok(())
+    // Build observation for result
+    let observation = build_observation(state, &pb::ObserveOptions::default())?;
+
+    Ok(pb::ActionResult {
+        state_version: state.state_version,
+        observation: Some(observation),
+        effects,
+        resolved_node_id,
+        resolved_point,
+        caused_navigation,
+    })
 }
 
-fn handle_navigate(state: &mut ServoState, url_str: &str) -> Result<pb::Observation, EngineError> {
-    let url = Url::parse(url_str)
-        .map_err(|e| EngineError::new("invalid_url", format!("failed to parse URL: {}", e)))?;
+/// Wait for a navigation the action started and describe it as an effect.
+/// Actions that started no load return at once. Otherwise the new URL can
+/// lag the load status, so this waits up to `NAVIGATION_DETECT_MS` for the
+/// URL or load status to change; a page that then doesn't finish loading
+/// within the navigation timeout is still reported, with
+/// `load_complete: false`. A load of the same URL is reported as a reload.
+fn navigation_effect(
+    state: &mut ServoState,
+    webview: &WebView,
+    url_before: &str,
+) -> Option<pb::Effect> {
+    if !state.load_started.get() {
+        return None;
+    }
+    let detect_until = Instant::now() + Duration::from_millis(NAVIGATION_DETECT_MS);
+    poll_until(state, detect_until, || {
+        let url_changed = webview.url().is_some_and(|url| url.as_str() != url_before);
+        (url_changed || webview.load_status() != LoadStatus::Complete).then_some(())
+    })?;
+    let timeout = state.navigation_timeout;
+    let load_complete = wait_for_load(state, webview, timeout).is_ok();
+    refresh_page_metadata(state, webview);
+    state.last_hit_test = None;
+    state.http_status = navigation_http_status(state, webview);
 
-    // Create or reuse webview
-    if state.webview.is_none() {
-        let webview = WebViewBuilder::new(&state.servo, state.rendering_context.clone())
-            .url(url.clone())
-            .build();
-        state.webview = Some(webview);
-    } else if let Some(ref webview) = state.webview {
-        webview.load(url.clone());
+    let mut fields = BTreeMap::new();
+    for (key, text) in [("from", url_before), ("to", state.current_url.as_str())] {
+        fields.insert(
+            key.to_string(),
+            Value {
+                kind: Some(value::Kind::StringValue(text.to_string())),
+            },
+        );
+    }
+    fields.insert(
+        "load_complete".to_string(),
+        Value {
+            kind: Some(value::Kind::BoolValue(load_complete)),
+        },
+    );
+    Some(pb::Effect {
+        kind: "navigation".to_string(),
+        summary: if state.current_url == url_before {
+            format!("reloaded {}", state.current_url)
+        } else {
+            format!("navigated to {}", state.current_url)
+        },
+        metadata: Some(Struct { fields }),
+    })
+}
+
+/// Captures `<a download>` clicks the page didn't cancel into
+/// `window.__buckleyDownloads`, fetching the file from inside the page so
+/// blob URLs revoked right after the click still resolve. Installed at
+/// document start; when downloads are disabled, clicks are recorded but
+/// nothing is fetched. libservo has no download hook, so downloads started
+/// any other way (attachment responses, navigations) are not captured.
+fn download_hook_script(enabled: bool) -> String {
+    format!(
+        r#"(function() {{
+    if (window.__buckleyDownloads) {{
+        return;
+    }}
+    window.__buckleyDownloads = [];
+    window.addEventListener("click", (event) => {{
+        const link = event.target instanceof Element ? event.target.closest("a[download]") : null;
+        if (event.defaultPrevented || !link || !link.href) {{
+            return;
+        }}
+        event.preventDefault();
+        const entry = {{ url: link.href, filename: link.getAttribute("download") || "", done: false }};
+        window.__buckleyDownloads.push(entry);
+        if (!{enabled}) {{
+            entry.done = true;
+            return;
+        }}
+        fetch(entry.url).then(async (response) => {{
+            if (!response.ok) {{
+                throw new Error(`HTTP ${{response.status}}`);
+            }}
+            entry.buffer = await response.arrayBuffer();
+            entry.mime = response.headers.get("content-type") || "";
+            entry.size = entry.buffer.byteLength;
+        }}).catch((e) => {{
+            entry.error = String(e);
+        }}).finally(() => {{
+            entry.done = true;
+        }});
+    }});
+}})();"#
+    )
+}
+
+/// Wait for the downloads the last action started and turn each into an
+/// effect. Fetches still running at the navigation timeout are reported as
+/// failed.
+fn collect_downloads(
+    state: &mut ServoState,
+    webview: &WebView,
+) -> Result<Vec<pb::Effect>, EngineError> {
+    #[derive(serde::Deserialize)]
+    struct QueuedDownload {
+        url: String,
+        #[serde(default)]
+        filename: String,
+        #[serde(default)]
+        done: bool,
+        #[serde(default)]
+        mime: String,
+        #[serde(default)]
+        size: u64,
+        #[serde(default)]
+        data: Option<String>,
+        #[serde(default)]
+        error: String,
+    }
+
+    // Bytes only cross into Rust, base64-encoded, for files that fit the
+    // session's remaining budget; larger ones are reported by size alone.
+    let budget = state.downloads.remaining().min(i64::MAX as u64) as i64;
+    let deadline = Instant::now() + state.navigation_timeout;
+    let mut attempt = 0;
+    let queued: Vec<QueuedDownload> = loop {
+        let force = Instant::now() >= deadline;
+        let script = format!(
+            r#"(function() {{
+                const queued = window.__buckleyDownloads || [];
+                if (!{force} && queued.some((entry) => !entry.done)) {{
+                    return "pending";
+                }}
+                let budget = {budget};
+                return JSON.stringify(queued.splice(0).map((entry) => {{
+                    const {{ buffer, ...item }} = entry;
+                    if (buffer && buffer.byteLength <= budget) {{
+                        budget -= buffer.byteLength;
+                        const bytes = new Uint8Array(buffer);
+                        let binary = "";
+                        for (let i = 0; i < bytes.length; i += 0x8000) {{
+                            binary += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+                        }}
+                        item.data = btoa(binary);
+                    }}
+                    return item;
+                }}));
+            }})()"#
+        );
+        let value = evaluate_javascript_sync(state, webview, &script)?;
+        let json = js_value_to_string(value)?;
+        if json != "pending" {
+            break serde_json::from_str(&json).map_err(|e| {
+                EngineError::new("script_error", format!("download JSON parse error: {}", e))
+            })?;
+        }
+        thread::sleep(poll_interval(attempt));
+        attempt += 1;
+    };
+
+    let mut effects = Vec::new();
+    for item in queued {
+        let filename = download_filename(&item.filename, &item.url);
+        let outcome = if !item.error.is_empty() {
+            Err(EngineError::new(
+                "download_failed",
+                format!("download of {filename:?} failed: {}", item.error),
+            ))
+        } else if !item.done {
+            Err(EngineError::new(
+                "download_failed",
+                format!("download of {filename:?} did not finish in time"),
+            ))
+        } else {
+            state.downloads.admit(&filename, item.size).and_then(|_| {
+                let data = item
+                    .data
+                    .as_deref()
+                    .and_then(|data| BASE64_STANDARD.decode(data).ok())
+                    .ok_or_else(|| {
+                        EngineError::new("script_error", "download bytes are not valid base64")
+                    })?;
+                state
+                    .downloads
+                    .save(&item.url, &filename, &item.mime, &data)
+            })
+        };
+        effects.push(download_effect(&filename, &outcome));
     }
+    Ok(effects)
+}
 
+fn handle_check_contrast(
+    state: &mut ServoState,
+    node_ids: &[u64],
+) -> Result<Vec<pb::ContrastResult>, EngineError> {
     let webview = state
-        .webview
-        .clone()
-        .ok_or_else(|| EngineError::new("no_webview", "failed to create webview"))?;
-    wait_for_load(
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let ids_json = serde_json::to_string(node_ids)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let script = contrast_script(&ids_json);
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct ContrastJson {
+        id: u64,
+        text: String,
+        color: [f64; 4],
+        background: [f64; 4],
+        font_size: f64,
+        font_weight: u32,
+    }
+
+    let entries: Vec<ContrastJson> = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new("script_error", format!("contrast JSON parse error: {}", e))
+    })?;
+    if !node_ids.is_empty() && entries.is_empty() {
+        return Err(EngineError::new(
+            "invalid_target",
+            "no matching nodes found",
+        ));
+    }
+
+    let to_rgba = |c: [f64; 4]| Rgba {
+        r: c[0],
+        g: c[1],
+        b: c[2],
+        a: c[3],
+    };
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            contrast_result(
+                entry.id,
+                entry.text,
+                to_rgba(entry.color),
+                to_rgba(entry.background),
+                entry.font_size,
+                entry.font_weight,
+            )
+        })
+        .collect())
+}
+
+fn handle_export_pdf(
+    state: &mut ServoState,
+    page_size: Option<&pb::Viewport>,
+) -> Result<Vec<u8>, EngineError> {
+    if state.active_webview().is_none() {
+        return Err(EngineError::new(
+            "no_webview",
+            "no webview active - navigate first",
+        ));
+    }
+    state.servo.spin_event_loop();
+    let image = read_viewport_image(state)
+        .ok_or_else(|| EngineError::new("render_failed", "failed to read rendered page"))?;
+    let rgb = image::DynamicImage::ImageRgba8(image).into_rgb8();
+    let (width, height) = pdf_page_size(page_size, state.viewport_width, state.viewport_height);
+    Ok(single_page_pdf(
+        width,
+        height,
+        Some(PdfImage {
+            width: rgb.width(),
+            height: rgb.height(),
+            rgb: rgb.as_raw(),
+        }),
+    ))
+}
+
+// Cookies are accessed through `document.cookie`, so HttpOnly cookies are
+// neither visible nor settable and attributes other than name/value are not
+// reported back by the page.
+fn handle_get_cookies(state: &mut ServoState) -> Result<Vec<pb::Cookie>, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let value = evaluate_javascript_sync(state, &webview, "document.cookie")?;
+    let raw = match value {
+        JSValue::String(raw) => raw,
+        _ => String::new(),
+    };
+    let domain = Url::parse(&state.current_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+    Ok(parse_cookie_header(&raw, &domain))
+}
+
+fn handle_scroll_position(state: &mut ServoState) -> Result<pb::Point, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let script = "Math.round(window.scrollX) + \",\" + Math.round(window.scrollY)";
+    let value = evaluate_javascript_sync(state, &webview, script)?;
+    let raw = js_value_to_string(value)?;
+    let (x, y) = raw
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+        .ok_or_else(|| {
+            EngineError::new("script_error", format!("unexpected scroll offset: {raw}"))
+        })?;
+    Ok(pb::Point { x, y })
+}
+
+fn handle_set_viewport(
+    state: &mut ServoState,
+    viewport: &pb::Viewport,
+) -> Result<pb::Observation, EngineError> {
+    let size = PhysicalSize::new(viewport.width, viewport.height);
+    state.rendering_context.resize(size);
+    for tab in &state.tabs {
+        tab.webview.resize(size);
+    }
+    state.viewport_width = viewport.width;
+    state.viewport_height = viewport.height;
+    if viewport.device_scale_factor > 0.0 {
+        state.device_scale_factor = viewport.device_scale_factor as f32;
+    }
+    // Cached bounds have the old dimensions.
+    state.last_hit_test = None;
+    state.state_version += 1;
+    state.servo.spin_event_loop();
+    build_observation(state, &pb::ObserveOptions::default())
+}
+
+/// Abort the active tab's load. `WebView` has no stop call, so this runs
+/// `window.stop()` in the page, which cancels the document's pending fetches
+/// and ends parsing with what has arrived. Commands are serial, so a navigate
+/// still waiting has already been cut short by the stop signal; this stops
+/// what it, or a timed-out load, left going.
+fn handle_stop(state: &mut ServoState) -> Result<pb::Observation, EngineError> {
+    // The signal has done its job; don't let it cut the next navigate short.
+    state.stop.take();
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    evaluate_javascript_sync(state, &webview, "window.stop(); true")?;
+    state.servo.spin_event_loop();
+    state.last_hit_test = None;
+    state.state_version += 1;
+    build_observation(state, &pb::ObserveOptions::default())
+}
+
+fn handle_extract_text(
+    state: &mut ServoState,
+    max_bytes: usize,
+) -> Result<pb::ExtractTextResponse, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let value = evaluate_javascript_sync(
         state,
         &webview,
-        Duration::from_secs(NAVIGATION_TIMEOUT_SECS),
+        "document.body ? document.body.innerText : \"\"",
     )?;
+    let raw = match value {
+        JSValue::String(raw) => raw,
+        _ => String::new(),
+    };
+    Ok(page_text(&raw, max_bytes))
+}
 
+/// Fill every field in a single script so the JS budget bounds the batch
+/// rather than each field.
+fn handle_fill_form(
+    state: &mut ServoState,
+    fields: &[pb::FormField],
+) -> Result<pb::FillFormResponse, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let script = fill_form_script(fields)?;
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct FieldJson {
+        filled: bool,
+        #[serde(default)]
+        error: String,
+    }
+
+    let outcomes: Vec<FieldJson> = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new("script_error", format!("fill form JSON parse error: {}", e))
+    })?;
+    if outcomes.len() != fields.len() {
+        return Err(EngineError::new(
+            "script_error",
+            "fill form returned the wrong number of results",
+        ));
+    }
+    // Let input/change handlers run before the caller observes.
+    state.servo.spin_event_loop();
+    state.last_hit_test = None;
     state.state_version += 1;
+    Ok(pb::FillFormResponse {
+        results: fields
+            .iter()
+            .zip(outcomes)
+            .map(|(field, outcome)| pb::FieldResult {
+                selector: field.selector.clone(),
+                filled: outcome.filled,
+                error: outcome.error,
+            })
+            .collect(),
+        state_version: state.state_version,
+    })
+}
+
+/// Write the upload to the session's upload dir, then click the file input
+/// so the page opens its picker, which `FrameReadyDelegate` answers with the
+/// staged file. Going through a real click keeps the page's user-activation
+/// checks and change events intact.
+fn handle_set_file_input(
+    state: &mut ServoState,
+    upload: &pb::SetFileInputRequest,
+) -> Result<pb::SetFileInputResponse, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let selector_json = serde_json::to_string(upload.selector.trim())
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let script = format!(
+        r#"(function() {{
+            let el = null;
+            try {{
+                el = document.querySelector({selector_json});
+            }} catch (e) {{
+                return JSON.stringify({{ error: "invalid_selector" }});
+            }}
+            if (!el) {{
+                return JSON.stringify({{ error: "not_found" }});
+            }}
+            if (el.tagName !== "INPUT" || el.type !== "file") {{
+                return JSON.stringify({{ error: "not_file_input" }});
+            }}
+            el.scrollIntoView({{ block: "center", inline: "center" }});
+            const rect = el.getBoundingClientRect();
+            return JSON.stringify({{ x: rect.left + rect.width / 2, y: rect.top + rect.height / 2 }});
+        }})()"#,
+    );
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct FileInputJson {
+        #[serde(default)]
+        error: String,
+        #[serde(default)]
+        x: f64,
+        #[serde(default)]
+        y: f64,
+    }
+
+    let input: FileInputJson = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new(
+            "script_error",
+            format!("file input JSON parse error: {}", e),
+        )
+    })?;
+    match input.error.as_str() {
+        "" => {}
+        "invalid_selector" => return Err(EngineError::new("invalid_request", "invalid selector")),
+        "not_found" => {
+            return Err(EngineError::new(
+                "invalid_target",
+                "selector matched no element",
+            ))
+        }
+        _ => {
+            return Err(EngineError::new(
+                "invalid_target",
+                "target is not a file input",
+            ))
+        }
+    }
+
+    let path = state.upload_dir.path().join(&upload.filename);
+    fs::write(&path, &upload.data)
+        .map_err(|e| EngineError::new("unavailable", format!("failed to stage upload: {e}")))?;
+    *state.pending_upload.borrow_mut() = Some(path);
+
+    let point = device_point(state, input.x, input.y);
+    let point = webview_point(state, point.x, point.y);
+    send_mouse_move(&webview, point);
+    send_mouse_button(&webview, point, MouseButtonAction::Down, MouseButton::Left);
+    send_mouse_button(&webview, point, MouseButtonAction::Up, MouseButton::Left);
+
+    let pending = state.pending_upload.clone();
+    let deadline = Instant::now() + Duration::from_millis(JS_EVALUATION_TIMEOUT_MS);
+    if poll_until(state, deadline, || pending.borrow().is_none().then_some(())).is_none() {
+        pending.borrow_mut().take();
+        return Err(EngineError::new(
+            "invalid_target",
+            "file input did not open a file picker",
+        ));
+    }
+    // Let the input and change events run before the caller observes.
+    state.servo.spin_event_loop();
     state.last_hit_test = None;
-    state.current_url = url_str.to_string();
-    state.current_title.clear();
-    refresh_page_metadata(state, &webview);
+    state.state_version += 1;
+    Ok(pb::SetFileInputResponse {
+        filename: upload.filename.clone(),
+        bytes: upload.data.len() as u64,
+        state_version: state.state_version,
+    })
+}
+
+fn handle_query_element(
+    state: &mut ServoState,
+    query: &pb::QueryElementRequest,
+) -> Result<pb::QueryElementResponse, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let target = pb::ActionTarget {
+        selector: query.selector.clone(),
+        selector_type: query.selector_type,
+        ..Default::default()
+    };
+    let element = target_element_js(state, Some(&target))?;
+    let names_json = serde_json::to_string(&query.properties)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let script = format!(
+        r#"(function() {{
+            let el = null;
+            try {{
+                el = {element};
+            }} catch (e) {{
+                return JSON.stringify({{ invalid: true }});
+            }}
+            if (!el) return JSON.stringify({{ found: false }});
+            const properties = {{}};
+            for (const name of {names_json}) {{
+                const value = el[name];
+                const kind = typeof value;
+                if (kind === "string" || kind === "number" || kind === "boolean") {{
+                    properties[name] = String(value);
+                }} else if (el.hasAttribute && el.hasAttribute(name)) {{
+                    properties[name] = el.getAttribute(name);
+                }}
+            }}
+            return JSON.stringify({{ found: true, properties: properties }});
+        }})()"#,
+    );
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct QueryJson {
+        #[serde(default)]
+        invalid: bool,
+        #[serde(default)]
+        found: bool,
+        #[serde(default)]
+        properties: HashMap<String, String>,
+    }
+
+    let outcome: QueryJson = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new(
+            "script_error",
+            format!("query element JSON parse error: {}", e),
+        )
+    })?;
+    if outcome.invalid {
+        return Err(EngineError::new("invalid_request", "invalid selector"));
+    }
+    Ok(pb::QueryElementResponse {
+        found: outcome.found,
+        properties: outcome.properties,
+    })
+}
+
+fn fill_form_script(fields: &[pb::FormField]) -> Result<String, EngineError> {
+    let pairs: Vec<(&str, &str)> = fields
+        .iter()
+        .map(|field| (field.selector.as_str(), field.value.as_str()))
+        .collect();
+    let fields_json = serde_json::to_string(&pairs)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    Ok(format!(
+        r#"(function() {{
+            const fields = {fields_json};
+            return JSON.stringify(fields.map(([selector, value]) => {{
+                let el = null;
+                try {{
+                    el = document.querySelector(selector);
+                }} catch (e) {{
+                    return {{ filled: false, error: "invalid_selector" }};
+                }}
+                if (!el) {{
+                    return {{ filled: false, error: "not_found" }};
+                }}
+                if (el.type === "checkbox" || el.type === "radio") {{
+                    el.checked = ["true", "on", "1"].includes(value.toLowerCase());
+                }} else if ("value" in el) {{
+                    el.value = value;
+                }} else {{
+                    return {{ filled: false, error: "not_fillable" }};
+                }}
+                el.dispatchEvent(new Event("input", {{ bubbles: true }}));
+                el.dispatchEvent(new Event("change", {{ bubbles: true }}));
+                return {{ filled: true }};
+            }}));
+        }})()"#,
+    ))
+}
+
+fn handle_set_cookie(state: &mut ServoState, cookie: &pb::Cookie) -> Result<(), EngineError> {
+    if cookie.http_only {
+        return Err(EngineError::new(
+            "unsupported",
+            "http_only cookies cannot be set from the page context",
+        ));
+    }
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    validate_cookie(cookie)?;
+    let cookie_json = serde_json::to_string(&cookie_string(cookie))
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let script = format!("document.cookie = {cookie_json}; true");
+    evaluate_javascript_sync(state, &webview, &script)?;
+    Ok(())
+}
 
-    build_observation(state, &pb::ObserveOptions::default())
+fn handle_clear_cookies(state: &mut ServoState) -> Result<u32, EngineError> {
+    let cookies = handle_get_cookies(state)?;
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    for cookie in &cookies {
+        let expired = pb::Cookie {
+            name: cookie.name.clone(),
+            path: "/".to_string(),
+            expires_unix: 1,
+            ..Default::default()
+        };
+        let cookie_json = serde_json::to_string(&cookie_string(&expired))
+            .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+        evaluate_javascript_sync(
+            state,
+            &webview,
+            &format!("document.cookie = {cookie_json}; true"),
+        )?;
+    }
+    Ok(cookies.len() as u32)
 }
 
-fn handle_observe(
+/// Run `body` with `storage` bound to the page's `scope` storage area. The
+/// body returns a JSON-serializable value; exceptions (quota, storage
+/// disabled) come back as `script_error`.
+fn run_storage_script(
     state: &mut ServoState,
-    opts: &pb::ObserveOptions,
-) -> Result<pb::Observation, EngineError> {
-    // Pump event loop
-    state.servo.spin_event_loop();
+    scope: pb::StorageScope,
+    body: &str,
+) -> Result<serde_json::Value, EngineError> {
+    storage_origin(&state.current_url)?;
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let area = match scope {
+        pb::StorageScope::Session => "sessionStorage",
+        _ => "localStorage",
+    };
+    let script = format!(
+        r#"(function() {{
+            try {{
+                const storage = window.{area};
+                return JSON.stringify({{ value: (function() {{ {body} }})() }});
+            }} catch (e) {{
+                return JSON.stringify({{ error: String(e) }});
+            }}
+        }})()"#,
+    );
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+    let mut result: serde_json::Value = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new("script_error", format!("storage JSON parse error: {}", e))
+    })?;
+    if let Some(error) = result.get("error").and_then(|error| error.as_str()) {
+        return Err(EngineError::new("script_error", format!("{area}: {error}")));
+    }
+    Ok(result["value"].take())
+}
 
-    build_observation(state, opts)
+fn handle_set_storage(
+    state: &mut ServoState,
+    scope: pb::StorageScope,
+    entries: &[pb::StorageEntry],
+) -> Result<u32, EngineError> {
+    let pairs: Vec<(&str, &str)> = entries
+        .iter()
+        .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+        .collect();
+    let entries_json = serde_json::to_string(&pairs)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let body = format!(
+        r#"const entries = {entries_json};
+            for (const [key, value] of entries) {{
+                storage.setItem(key, value);
+            }}
+            return entries.length;"#,
+    );
+    let set = run_storage_script(state, scope, &body)?;
+    Ok(set.as_u64().unwrap_or_default() as u32)
 }
 
-fn handle_act(
+fn handle_get_storage(
     state: &mut ServoState,
-    action: &pb::Action,
-) -> Result<pb::ActionResult, EngineError> {
-    let webview = state
-        .webview
-        .as_ref()
-        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    scope: pb::StorageScope,
+    keys: &[String],
+) -> Result<pb::GetStorageResponse, EngineError> {
+    let keys_json = serde_json::to_string(keys)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let body = format!(
+        r#"const keys = {keys_json};
+            if (keys.length === 0) {{
+                for (let i = 0; i < storage.length; i++) {{
+                    keys.push(storage.key(i));
+                }}
+            }}
+            return [location.origin, keys
+                .map((key) => [key, storage.getItem(key)])
+                .filter(([, value]) => value !== null)];"#,
+    );
+    let value = run_storage_script(state, scope, &body)?;
+    let (origin, pairs): (String, Vec<(String, String)>) =
+        serde_json::from_value(value).map_err(|e| {
+            EngineError::new("script_error", format!("storage JSON parse error: {}", e))
+        })?;
+    let mut entries: Vec<pb::StorageEntry> = pairs
+        .into_iter()
+        .map(|(key, value)| pb::StorageEntry { key, value })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries.dedup_by(|a, b| a.key == b.key);
+    Ok(pb::GetStorageResponse { origin, entries })
+}
 
-    // Check state version if provided
-    if action.expected_state_version > 0 && action.expected_state_version != state.state_version {
-        return Err(EngineError::new(
-            "stale_state",
-            format!(
-                "expected state version {} but current is {}",
-                action.expected_state_version, state.state_version
-            ),
-        ));
-    }
+fn handle_clear_storage(
+    state: &mut ServoState,
+    scope: pb::StorageScope,
+) -> Result<u32, EngineError> {
+    let cleared = run_storage_script(
+        state,
+        scope,
+        "const count = storage.length; storage.clear(); return count;",
+    )?;
+    Ok(cleared.as_u64().unwrap_or_default() as u32)
+}
 
-    // Dispatch action based on type
-    let action_type =
-        pb::ActionType::try_from(action.r#type).unwrap_or(pb::ActionType::Unspecified);
-    match action_type {
-        pb::ActionType::Click => {
-            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
-                EngineError::new("invalid_target", "click requires a target point")
-            })?;
-            send_mouse_move(webview, point);
-            send_mouse_button(webview, point, MouseButtonAction::Down);
-            send_mouse_button(webview, point, MouseButtonAction::Up);
-        }
-        pb::ActionType::Type => {
-            if action.text.is_empty() {
-                return Err(EngineError::new(
-                    "invalid_request",
-                    "type action requires text",
-                ));
-            }
-            if let Some(point) = action_point(state, action.target.as_ref()) {
-                send_mouse_move(webview, point);
-                send_mouse_button(webview, point, MouseButtonAction::Down);
-                send_mouse_button(webview, point, MouseButtonAction::Up);
-            }
-            let modifiers = modifiers_from_action(action);
-            send_text(webview, &action.text, modifiers);
-        }
-        pb::ActionType::Scroll => {
-            let scroll = action.scroll.as_ref().ok_or_else(|| {
-                EngineError::new("invalid_request", "scroll action requires delta")
-            })?;
-            let point =
-                action_point(state, action.target.as_ref()).unwrap_or_else(|| default_point(state));
-            send_scroll(webview, point, scroll);
-        }
-        pb::ActionType::Hover => {
-            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
-                EngineError::new("invalid_target", "hover requires a target point")
-            })?;
-            send_mouse_move(webview, point);
-        }
-        pb::ActionType::Key => {
-            if action.key.is_empty() {
-                return Err(EngineError::new(
-                    "invalid_request",
-                    "key action requires key",
-                ));
-            }
-            let modifiers = modifiers_from_action(action);
-            send_key(webview, &action.key, modifiers);
-        }
-        pb::ActionType::Focus => {
-            let point = action_point(state, action.target.as_ref()).ok_or_else(|| {
-                EngineError::new("invalid_target", "focus requires a target point")
-            })?;
-            send_mouse_move(webview, point);
-            send_mouse_button(webview, point, MouseButtonAction::Down);
-            send_mouse_button(webview, point, MouseButtonAction::Up);
-        }
-        pb::ActionType::ClipboardRead => {
-            ensure_clipboard_read_allowed(state)?;
-            let bytes = state.clipboard_text.as_bytes().len();
-            if bytes > state.clipboard_max_bytes {
-                return Err(EngineError::new("clipboard_limit", "clipboard exceeds size limit"));
-            }
-            let observation = build_observation(state, &pb::ObserveOptions::default())?;
-            state.state_version += 1;
-            return Ok(pb::ActionResult {
-                state_version: state.state_version,
-                observation: Some(observation),
-                effects: vec![pb::Effect {
-                    kind: "clipboard_read".to_string(),
-                    summary: format!("clipboard read {} bytes", bytes),
-                    metadata: clipboard_metadata(
-                        Some(&state.clipboard_text),
-                        bytes,
-                        clipboard_mode_label(state.clipboard_mode),
-                        "virtual",
-                    ),
-                }],
-            });
-        }
-        pb::ActionType::ClipboardWrite => {
-            ensure_clipboard_write_allowed(state)?;
-            let bytes = action.text.as_bytes().len();
-            if bytes > state.clipboard_max_bytes {
-                return Err(EngineError::new("clipboard_limit", "clipboard exceeds size limit"));
-            }
-            state.clipboard_text = action.text.clone();
-            let observation = build_observation(state, &pb::ObserveOptions::default())?;
-            state.state_version += 1;
-            return Ok(pb::ActionResult {
-                state_version: state.state_version,
-                observation: Some(observation),
-                effects: vec![pb::Effect {
-                    kind: "clipboard_write".to_string(),
-                    summary: format!("clipboard wrote {} bytes", bytes),
-                    metadata: clipboard_metadata(
-                        None,
-                        bytes,
-                        clipboard_mode_label(state.clipboard_mode),
-                        "virtual",
-                    ),
-                }],
-            });
+/// Clear what the page context can reach: cookies and storage of the active
+/// page's origin, plus the virtual clipboard. Cookies and storage live in
+/// Servo's network and storage threads, which libservo gives no way to wipe,
+/// so HttpOnly cookies, other paths and other origins survive; both kinds are
+/// reported as partial. libservo has no hook for its HTTP cache either, so
+/// `cache` is never reported.
+fn handle_clear_browsing_data(
+    state: &mut ServoState,
+    kinds: &pb::ClearBrowsingDataRequest,
+) -> Result<pb::ClearBrowsingDataResponse, EngineError> {
+    let mut response = pb::ClearBrowsingDataResponse::default();
+    let has_page = state.active_webview().is_some();
+    if kinds.cookies {
+        if has_page {
+            handle_clear_cookies(state)?;
         }
-        pb::ActionType::Unspecified => {
-            return Err(EngineError::new(
-                "invalid_request",
-                "unsupported action type",
-            ));
+        response.cleared.push("cookies".to_string());
+        response.partial.push("cookies".to_string());
+    }
+    if kinds.storage {
+        if has_page && storage_origin(&state.current_url).is_ok() {
+            handle_clear_storage(state, pb::StorageScope::Local)?;
+            handle_clear_storage(state, pb::StorageScope::Session)?;
         }
+        response.cleared.push("storage".to_string());
+        response.partial.push("storage".to_string());
     }
+    if kinds.clipboard && state.clipboard_mode != pb::ClipboardMode::Host {
+        state.clipboard_text.clear();
+        response.cleared.push("clipboard".to_string());
+    }
+    Ok(response)
+}
 
-    // Pump events after action
-    state.servo.spin_event_loop();
-    state.state_version += 1;
+fn parse_cookie_header(raw: &str, domain: &str) -> Vec<pb::Cookie> {
+    raw.split(';')
+        .filter_map(|pair| {
+            let pair = pair.trim();
+            if pair.is_empty() {
+                return None;
+            }
+            let (name, value) = pair.split_once('=').unwrap_or(("", pair));
+            Some(pb::Cookie {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                domain: domain.to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
 
-    // Build observation for result
-    let observation = build_observation(state, &pb::ObserveOptions::default())?;
+fn cookie_string(cookie: &pb::Cookie) -> String {
+    let mut out = format!("{}={}", cookie.name, cookie.value);
+    if !cookie.domain.is_empty() {
+        out.push_str(&format!("; domain={}", cookie.domain));
+    }
+    let path = if cookie.path.is_empty() {
+        "/"
+    } else {
+        &cookie.path
+    };
+    out.push_str(&format!("; path={path}"));
+    if cookie.expires_unix > 0 {
+        out.push_str(&format!(
+            "; max-age={}",
+            cookie_max_age(cookie.expires_unix)
+        ));
+    }
+    if cookie.secure {
+        out.push_str("; secure");
+    }
+    out
+}
 
-    Ok(pb::ActionResult {
-        state_version: state.state_version,
-        observation: Some(observation),
-        effects: vec![],
-    })
+fn cookie_max_age(expires_unix: i64) -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    (expires_unix - now).max(0)
 }
 
 fn modifiers_from_action(action: &pb::Action) -> Modifiers {
@@ -554,20 +2902,384 @@ fn modifiers_from_action(action: &pb::Action) -> Modifiers {
     modifiers
 }
 
-fn action_point(state: &ServoState, target: Option<&pb::ActionTarget>) -> Option<WebViewPoint> {
-    let target = target?;
-    if let Some(point) = target.point.as_ref() {
-        return Some(webview_point(state, point.x, point.y));
-    }
-    if target.node_id != 0 {
-        let rect = rect_for_node_id(state, target.node_id)?;
-        let half_width = rect.width.max(0) / 2;
-        let half_height = rect.height.max(0) / 2;
-        let center_x = rect.x.saturating_add(half_width);
-        let center_y = rect.y.saturating_add(half_height);
-        return Some(webview_point(state, center_x, center_y));
-    }
-    None
+fn action_point(state: &ServoState, target: Option<&pb::ActionTarget>) -> Option<WebViewPoint> {
+    let (x, y) = action_coords(state, target)?;
+    Some(webview_point(state, x, y))
+}
+
+/// Viewport coordinates of a target: its explicit point, or the centre of the
+/// node's hit-test bounds.
+fn action_coords(state: &ServoState, target: Option<&pb::ActionTarget>) -> Option<(i32, i32)> {
+    let target = target?;
+    if let Some(point) = target.point.as_ref() {
+        return Some((point.x, point.y));
+    }
+    if target.node_id != 0 {
+        let rect = rect_for_node_id(state, target.node_id)?;
+        let half_width = rect.width.max(0) / 2;
+        let half_height = rect.height.max(0) / 2;
+        let center_x = rect.x.saturating_add(half_width);
+        let center_y = rect.y.saturating_add(half_height);
+        return Some((center_x, center_y));
+    }
+    None
+}
+
+/// The node and point an action target resolves to. Point targets are matched
+/// against the most specific region of the last hit-test map, so the node is
+/// 0 until a hit test has been observed.
+fn resolved_target(
+    state: &ServoState,
+    target: Option<&pb::ActionTarget>,
+) -> (u64, Option<pb::Point>) {
+    let point = action_coords(state, target).map(|(x, y)| pb::Point { x, y });
+    let node_id = match target {
+        Some(target) if target.node_id != 0 => target.node_id,
+        _ => point
+            .as_ref()
+            .map(|point| node_at_point(state, point))
+            .unwrap_or(0),
+    };
+    (node_id, point)
+}
+
+/// Smallest cached hit-test region containing `point`.
+fn node_at_point(state: &ServoState, point: &pb::Point) -> u64 {
+    let Some(map) = state.last_hit_test.as_ref() else {
+        return 0;
+    };
+    map.regions
+        .iter()
+        .filter_map(|region| {
+            let bounds = region.bounds.as_ref()?;
+            let inside = point.x >= bounds.x
+                && point.y >= bounds.y
+                && point.x < bounds.x.saturating_add(bounds.width)
+                && point.y < bounds.y.saturating_add(bounds.height);
+            inside.then(|| {
+                (
+                    i64::from(bounds.width) * i64::from(bounds.height),
+                    region.node_id,
+                )
+            })
+        })
+        .min_by_key(|(area, _)| *area)
+        .map(|(_, node_id)| node_id)
+        .unwrap_or(0)
+}
+
+/// Replace selector targets with the centre point of the matched element so
+/// the rest of the action handling only deals with points and node ids.
+fn resolve_selector_targets(
+    state: &mut ServoState,
+    action: &pb::Action,
+) -> Result<pb::Action, EngineError> {
+    let mut action = action.clone();
+    for target in [action.target.as_mut(), action.drag_to.as_mut()]
+        .into_iter()
+        .flatten()
+    {
+        if target.selector.trim().is_empty() {
+            continue;
+        }
+        target.point = Some(selector_point(state, target)?);
+        target.node_id = 0;
+    }
+    Ok(action)
+}
+
+fn selector_point(
+    state: &mut ServoState,
+    target: &pb::ActionTarget,
+) -> Result<pb::Point, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let selector_json = serde_json::to_string(target.selector.trim())
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let xpath = target.selector_type == pb::SelectorType::Xpath as i32;
+    let script = selector_center_script(&selector_json, xpath);
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct SelectorJson {
+        valid: bool,
+        found: bool,
+        #[serde(default)]
+        x: f64,
+        #[serde(default)]
+        y: f64,
+    }
+
+    let result: SelectorJson = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new("script_error", format!("selector JSON parse error: {}", e))
+    })?;
+    if !result.valid {
+        return Err(EngineError::new("invalid_request", "invalid selector"));
+    }
+    if !result.found {
+        return Err(EngineError::new(
+            "invalid_target",
+            "selector matched no element",
+        ));
+    }
+    Ok(device_point(state, result.x, result.y))
+}
+
+/// Convert a point from getBoundingClientRect, which is in CSS pixels, to
+/// device pixels like the hit-test map and action points.
+fn device_point(state: &ServoState, x: f64, y: f64) -> pb::Point {
+    let scale = if state.device_scale_factor > 0.0 {
+        f64::from(state.device_scale_factor)
+    } else {
+        1.0
+    };
+    pb::Point {
+        x: (x * scale).round() as i32,
+        y: (y * scale).round() as i32,
+    }
+}
+
+/// Scroll a node target into view and pin the action to the node's centre
+/// after scrolling. Hit-test bounds only cover the viewport, so without this
+/// a node below the fold has no usable point. Nodes the page no longer has
+/// fall back to the cached bounds.
+fn scroll_target_into_view(
+    state: &mut ServoState,
+    action_type: pb::ActionType,
+    action: &pb::Action,
+) -> Result<pb::Action, EngineError> {
+    let mut action = action.clone();
+    let Some(target) = action.target.as_mut() else {
+        return Ok(action);
+    };
+    if !scrolls_into_view(action_type, target) {
+        return Ok(action);
+    }
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let script = scroll_into_view_script(target.node_id);
+    let value = evaluate_javascript_sync(state, &webview, &script)?;
+    let json = js_value_to_string(value)?;
+
+    #[derive(serde::Deserialize)]
+    struct ScrolledJson {
+        found: bool,
+        #[serde(default)]
+        x: f64,
+        #[serde(default)]
+        y: f64,
+    }
+
+    let result: ScrolledJson = serde_json::from_str(&json).map_err(|e| {
+        EngineError::new(
+            "script_error",
+            format!("scroll into view JSON parse error: {}", e),
+        )
+    })?;
+    if result.found {
+        target.point = Some(device_point(state, result.x, result.y));
+        // The page moved, so cached bounds no longer match the viewport.
+        state.last_hit_test = None;
+        state.servo.spin_event_loop();
+    }
+    Ok(action)
+}
+
+fn scroll_into_view_script(node_id: u64) -> String {
+    format!(
+        r#"(function() {{
+            const id = {node_id};
+            let el = null;
+            for (const candidate of document.getElementsByTagName("*")) {{
+                if (candidate.__buckleyId === id) {{
+                    el = candidate;
+                    break;
+                }}
+            }}
+            if (!el || !el.scrollIntoView) {{
+                return JSON.stringify({{ found: false }});
+            }}
+            el.scrollIntoView({{ block: "center", inline: "center" }});
+            const rect = el.getBoundingClientRect();
+            return JSON.stringify({{
+                found: true,
+                x: rect.left + rect.width / 2,
+                y: rect.top + rect.height / 2
+            }});
+        }})()"#,
+        node_id = node_id,
+    )
+}
+
+/// JavaScript expression for the element `target` names: the first match of
+/// its selector, the node with its Buckley id, or the element at its point.
+/// Evaluates to null when nothing matches or there is no target.
+fn target_element_js(
+    state: &ServoState,
+    target: Option<&pb::ActionTarget>,
+) -> Result<String, EngineError> {
+    let Some(target) = target else {
+        return Ok("null".to_string());
+    };
+    if !target.selector.trim().is_empty() {
+        let selector_json = serde_json::to_string(target.selector.trim())
+            .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+        return Ok(if target.selector_type == pb::SelectorType::Xpath as i32 {
+            format!(
+                "document.evaluate({selector_json}, document, null, XPathResult.FIRST_ORDERED_NODE_TYPE, null).singleNodeValue"
+            )
+        } else {
+            format!("document.querySelector({selector_json})")
+        });
+    }
+    if target.node_id != 0 {
+        return Ok(format!(
+            r#"Array.from(document.getElementsByTagName("*")).find((el) => el.__buckleyId === {}) || null"#,
+            target.node_id
+        ));
+    }
+    if let Some(point) = target.point.as_ref() {
+        // Action points are in device pixels; elementFromPoint wants CSS pixels.
+        let scale = if state.device_scale_factor > 0.0 {
+            f64::from(state.device_scale_factor)
+        } else {
+            1.0
+        };
+        return Ok(format!(
+            "document.elementFromPoint({}, {})",
+            f64::from(point.x) / scale,
+            f64::from(point.y) / scale
+        ));
+    }
+    Ok("null".to_string())
+}
+
+fn select_option_script(
+    state: &ServoState,
+    target: Option<&pb::ActionTarget>,
+    wanted: &str,
+) -> Result<String, EngineError> {
+    let element = target_element_js(state, target)?;
+    let wanted_json = serde_json::to_string(wanted)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    Ok(format!(
+        r#"(function() {{
+            const el = {element};
+            if (!el || el.tagName !== "SELECT") {{
+                return JSON.stringify({{ error: "not_select" }});
+            }}
+            const wanted = {wanted_json};
+            const options = Array.from(el.options);
+            const option = options.find((o) => o.value === wanted)
+                || options.find((o) => o.text.trim() === wanted.trim());
+            if (!option) {{
+                return JSON.stringify({{ error: "no_option" }});
+            }}
+            option.selected = true;
+            el.dispatchEvent(new Event("input", {{ bubbles: true }}));
+            el.dispatchEvent(new Event("change", {{ bubbles: true }}));
+            return JSON.stringify({{ value: option.value, label: option.text.trim() }});
+        }})()"#,
+    ))
+}
+
+/// Clicks through `el.click()` so the page sees the same click, input and
+/// change events as a user toggle, but only when the state has to change.
+fn set_checked_script(
+    state: &ServoState,
+    target: Option<&pb::ActionTarget>,
+    checked: bool,
+) -> Result<String, EngineError> {
+    let element = target_element_js(state, target)?;
+    Ok(format!(
+        r#"(function() {{
+            const el = {element};
+            if (!el || el.tagName !== "INPUT" || (el.type !== "checkbox" && el.type !== "radio")) {{
+                return JSON.stringify({{ error: "not_checkable" }});
+            }}
+            const wanted = {checked};
+            if (el.checked === wanted) {{
+                return JSON.stringify({{ checked: el.checked, changed: false }});
+            }}
+            if (el.type === "radio" && !wanted) {{
+                return JSON.stringify({{ error: "radio_uncheck" }});
+            }}
+            el.click();
+            return JSON.stringify({{ checked: el.checked, changed: true }});
+        }})()"#,
+    ))
+}
+
+/// Reports "ok" when the target takes typed text: a text-like input, a
+/// textarea or a contenteditable element, not disabled or read-only.
+fn editable_check_script(
+    state: &ServoState,
+    target: Option<&pb::ActionTarget>,
+) -> Result<String, EngineError> {
+    let element = target_element_js(state, target)?;
+    Ok(format!(
+        r#"(function() {{
+            const el = {element};
+            if (!el) return "missing";
+            if (el.isContentEditable) return "ok";
+            const textTypes = ["text", "search", "email", "url", "tel", "password", "number"];
+            const editable = el.tagName === "TEXTAREA"
+                || (el.tagName === "INPUT" && textTypes.includes(el.type));
+            if (!editable || el.disabled || el.readOnly) return "not_editable";
+            return "ok";
+        }})()"#,
+    ))
+}
+
+fn selector_center_script(selector_json: &str, xpath: bool) -> String {
+    format!(
+        r#"(function() {{
+            const selector = {selector_json};
+            let el = null;
+            try {{
+                if ({xpath}) {{
+                    el = document.evaluate(
+                        selector, document, null, XPathResult.FIRST_ORDERED_NODE_TYPE, null
+                    ).singleNodeValue;
+                }} else {{
+                    el = document.querySelector(selector);
+                }}
+            }} catch (e) {{
+                return JSON.stringify({{ valid: false, found: false }});
+            }}
+            if (!el || !el.getBoundingClientRect) {{
+                return JSON.stringify({{ valid: true, found: false }});
+            }}
+            const rect = el.getBoundingClientRect();
+            return JSON.stringify({{
+                valid: true,
+                found: true,
+                x: rect.left + rect.width / 2,
+                y: rect.top + rect.height / 2
+            }});
+        }})()"#,
+        selector_json = selector_json,
+        xpath = xpath,
+    )
+}
+
+/// Points visited by a drag after the start point: evenly spaced
+/// intermediate moves followed by the end point itself.
+fn drag_path(start: (i32, i32), end: (i32, i32), steps: u32) -> Vec<(i32, i32)> {
+    let segments = drag_steps(steps) + 1;
+    (1..=segments)
+        .map(|i| {
+            let t = f64::from(i) / f64::from(segments);
+            let x = f64::from(start.0) + f64::from(end.0 - start.0) * t;
+            let y = f64::from(start.1) + f64::from(end.1 - start.1) * t;
+            (x.round() as i32, y.round() as i32)
+        })
+        .collect()
 }
 
 fn rect_for_node_id(state: &ServoState, node_id: u64) -> Option<&pb::Rect> {
@@ -610,11 +3322,14 @@ fn send_mouse_move(webview: &WebView, point: WebViewPoint) {
     webview.notify_input_event(InputEvent::MouseMove(MouseMoveEvent::new(point)));
 }
 
-fn send_mouse_button(webview: &WebView, point: WebViewPoint, action: MouseButtonAction) {
+fn send_mouse_button(
+    webview: &WebView,
+    point: WebViewPoint,
+    action: MouseButtonAction,
+    button: MouseButton,
+) {
     webview.notify_input_event(InputEvent::MouseButton(MouseButtonEvent::new(
-        action,
-        MouseButton::Left,
-        point,
+        action, button, point,
     )));
 }
 
@@ -634,23 +3349,161 @@ fn send_scroll(webview: &WebView, point: WebViewPoint, delta: &pb::ScrollDelta)
 
 fn send_key(webview: &WebView, key: &str, modifiers: Modifiers) {
     let (key, code) = key_from_string(key);
-    send_keyboard_event(webview, key.clone(), code, modifiers, KeyState::Down);
-    send_keyboard_event(webview, key, code, modifiers, KeyState::Up);
+    for (key, code, modifiers, state) in chord_events(key, code, modifiers) {
+        send_keyboard_event(webview, key, code, modifiers, state);
+    }
+}
+
+/// One key press as a keyboard produces it: each held modifier goes down,
+/// then the key goes down and up, then the modifiers come up in reverse
+/// order. Every event carries the modifier state at that moment, so a
+/// modifier's own keydown already reports it as held.
+fn chord_events(
+    key: Key,
+    code: Code,
+    modifiers: Modifiers,
+) -> Vec<(Key, Code, Modifiers, KeyState)> {
+    let held: Vec<(Modifiers, NamedKey, Code)> = CHORD_MODIFIERS
+        .into_iter()
+        .filter(|(flag, named, _)| modifiers.contains(*flag) && key != Key::Named(*named))
+        .collect();
+    let mut state = Modifiers::empty();
+    let mut events = Vec::with_capacity(held.len() * 2 + 2);
+    for (flag, named, modifier_code) in &held {
+        state.insert(*flag);
+        events.push((Key::Named(*named), *modifier_code, state, KeyState::Down));
+    }
+    events.push((key.clone(), code, state, KeyState::Down));
+    events.push((key, code, state, KeyState::Up));
+    for (flag, named, modifier_code) in held.iter().rev() {
+        state.remove(*flag);
+        events.push((Key::Named(*named), *modifier_code, state, KeyState::Up));
+    }
+    events
 }
 
 fn send_text(webview: &WebView, text: &str, modifiers: Modifiers) {
-    for ch in text.chars() {
-        let (key, code) = match ch {
-            '\n' => (Key::Named(NamedKey::Enter), Code::Enter),
-            '\t' => (Key::Named(NamedKey::Tab), Code::Tab),
-            _ => (
-                Key::Character(ch.to_string()),
-                code_for_char(ch).unwrap_or(Code::Unidentified),
-            ),
-        };
-        send_keyboard_event(webview, key.clone(), code, modifiers, KeyState::Down);
-        send_keyboard_event(webview, key, code, modifiers, KeyState::Up);
+    for input in text_inputs(text) {
+        match input {
+            TextInput::Keys(run) => {
+                for ch in run.chars() {
+                    for (key, code, modifiers, state) in char_events(ch, modifiers) {
+                        send_keyboard_event(webview, key, code, modifiers, state);
+                    }
+                }
+            }
+            TextInput::Composed(run) => {
+                for event in composition_events(run) {
+                    webview.notify_input_event(InputEvent::Ime(ImeEvent::Composition(event)));
+                }
+            }
+        }
+    }
+}
+
+/// How `send_text` delivers one run of text.
+#[derive(Debug, PartialEq)]
+enum TextInput<'a> {
+    /// ASCII, typed key by key.
+    Keys(&'a str),
+    /// Anything else (CJK, accents, emoji), which no key on a US layout
+    /// produces. It is committed through one IME composition, the way an
+    /// input method delivers it, so multi-codepoint graphemes stay whole.
+    Composed(&'a str),
+}
+
+/// Split `text` into alternating runs of ASCII and non-ASCII graphemes.
+fn text_inputs(text: &str) -> Vec<TextInput<'_>> {
+    let mut inputs = Vec::new();
+    let mut run_start = 0;
+    let mut run_ascii = true;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        let ascii = grapheme.is_ascii();
+        if ascii != run_ascii && index > run_start {
+            let run = &text[run_start..index];
+            inputs.push(if run_ascii {
+                TextInput::Keys(run)
+            } else {
+                TextInput::Composed(run)
+            });
+            run_start = index;
+        }
+        run_ascii = ascii;
+    }
+    if run_start < text.len() {
+        let run = &text[run_start..];
+        inputs.push(if run_ascii {
+            TextInput::Keys(run)
+        } else {
+            TextInput::Composed(run)
+        });
     }
+    inputs
+}
+
+/// A composition that starts, shows `text` and commits it in one go.
+fn composition_events(text: &str) -> [CompositionEvent; 3] {
+    let event = |state: CompositionState, data: &str| CompositionEvent {
+        state,
+        data: data.to_string(),
+    };
+    [
+        event(CompositionState::Start, ""),
+        event(CompositionState::Update, text),
+        event(CompositionState::End, text),
+    ]
+}
+
+/// Key events that type `ch`. A character that needs Shift on a US layout
+/// (`A`, `!`) is typed with Shift held around it and the code of its base
+/// key, while the key itself stays the character, as a real keyboard
+/// reports it.
+fn char_events(ch: char, modifiers: Modifiers) -> Vec<(Key, Code, Modifiers, KeyState)> {
+    let (key, code) = match ch {
+        '\n' => (Key::Named(NamedKey::Enter), Code::Enter),
+        '\t' => (Key::Named(NamedKey::Tab), Code::Tab),
+        _ => (
+            Key::Character(ch.to_string()),
+            code_for_char(ch).unwrap_or(Code::Unidentified),
+        ),
+    };
+    let modifiers = if shifted_base(ch).is_some() {
+        modifiers | Modifiers::SHIFT
+    } else {
+        modifiers
+    };
+    chord_events(key, code, modifiers)
+}
+
+/// The unshifted character on the same US-layout key, for characters typed
+/// with Shift.
+fn shifted_base(ch: char) -> Option<char> {
+    let base = match ch {
+        'A'..='Z' => ch.to_ascii_lowercase(),
+        '~' => '`',
+        '!' => '1',
+        '@' => '2',
+        '#' => '3',
+        '$' => '4',
+        '%' => '5',
+        '^' => '6',
+        '&' => '7',
+        '*' => '8',
+        '(' => '9',
+        ')' => '0',
+        '_' => '-',
+        '+' => '=',
+        '{' => '[',
+        '}' => ']',
+        '|' => '\\',
+        ':' => ';',
+        '"' => '\'',
+        '<' => ',',
+        '>' => '.',
+        '?' => '/',
+        _ => return None,
+    };
+    Some(base)
 }
 
 fn send_keyboard_event(
@@ -750,8 +3603,7 @@ fn named_function_key(normalized: &str) -> Option<(NamedKey, Code)> {
 }
 
 fn code_for_char(ch: char) -> Option<Code> {
-    let lower = ch.to_ascii_lowercase();
-    let code = match lower {
+    let code = match shifted_base(ch).unwrap_or(ch) {
         'a' => Code::KeyA,
         'b' => Code::KeyB,
         'c' => Code::KeyC,
@@ -808,6 +3660,8 @@ fn code_for_char(ch: char) -> Option<Code> {
 fn handle_stream_event(
     state: &mut ServoState,
     event_type: pb::StreamEventType,
+    hit_test: &HitTestQuery,
+    base: &mut DeltaBase,
 ) -> Result<pb::StreamEvent, EngineError> {
     state.servo.spin_event_loop();
 
@@ -819,29 +3673,41 @@ fn handle_stream_event(
         dom_diff: vec![],
         accessibility_diff: vec![],
         hit_test: None,
+        stats: None,
+        console_messages: vec![],
+        sequence: 0,
+        content_hash: String::new(),
     };
 
     match event_type {
         pb::StreamEventType::Frame => {
-            event.frame = capture_frame(state);
+            event.frame = capture_stream_frame(state, base);
         }
         pb::StreamEventType::DomDiff => {
             if let Some(snapshot) = dom_snapshot_bytes(state) {
+                event.content_hash = content_hash(&snapshot);
                 event.dom_diff = wrap_diff_json(state.state_version, &snapshot);
             }
         }
         pb::StreamEventType::AccessibilityDiff => {
             if let Some(snapshot) = accessibility_snapshot_bytes(state) {
+                event.content_hash = content_hash(&snapshot);
                 event.accessibility_diff = wrap_diff_json(state.state_version, &snapshot);
             }
         }
         pb::StreamEventType::HitTest => {
-            if let Some(map) = build_hit_test_map(state) {
+            if let Some(map) = build_hit_test_map(state, &hit_test.for_cache()) {
                 state.last_hit_test = Some(map.clone());
-                event.hit_test = Some(map);
+                event.hit_test = Some(hit_test.truncate(map));
             }
         }
-        pb::StreamEventType::Unspecified => {}
+        pb::StreamEventType::Console => {
+            event.console_messages = drain_console_messages(state);
+        }
+        // Built by the daemon from the stream's own counters and timers.
+        pb::StreamEventType::Stats
+        | pb::StreamEventType::Heartbeat
+        | pb::StreamEventType::Unspecified => {}
     }
 
     Ok(event)
@@ -851,9 +3717,10 @@ fn build_observation(
     state: &mut ServoState,
     opts: &pb::ObserveOptions,
 ) -> Result<pb::Observation, EngineError> {
-    if let Some(webview) = state.webview.clone() {
+    if let Some(webview) = state.active_webview().cloned() {
         refresh_page_metadata(state, &webview);
     }
+    let render_complete = settle_paint(state);
 
     let mut obs = pb::Observation {
         state_version: state.state_version,
@@ -864,53 +3731,363 @@ fn build_observation(
         dom_snapshot: vec![],
         accessibility_tree: vec![],
         hit_test: None,
+        text_runs: vec![],
+        engine_info: None,
+        http_status: state.http_status,
+        focused_node_id: 0,
+        render_complete,
+        paint_epoch: state.paint_epoch,
+        computed_styles: None,
+        navigation_timing: None,
+        console_messages: vec![],
+        page_errors: vec![],
+        network_idle: false,
+        html: String::new(),
+        html_truncated: false,
+        page_metadata: None,
+        dom_snapshot_hash: String::new(),
+        accessibility_tree_hash: String::new(),
     };
 
     // Capture frame if requested
     if opts.include_frame {
-        if let Some(frame) = capture_frame(state) {
+        if opts.clip_node_id != 0 {
+            // Element bounds move with layout and scrolling, so refresh them.
+            state.last_hit_test = build_hit_test_map(state, &HitTestQuery::default());
+        }
+        let clip = resolve_clip_rect(
+            opts,
+            |node_id| rect_for_node_id(state, node_id).cloned(),
+            state.viewport_width,
+            state.viewport_height,
+        )?;
+        if let Some(frame) = capture_frame(state, clip.as_ref()) {
             obs.frame = Some(frame);
         }
     }
 
+    // Everything else comes from page scripts, run as one batch. Hit-test
+    // ids are assigned before `focused` reads them.
+    let mut batch = ObservationBuilder::default();
     if opts.include_dom_snapshot {
-        if let Some(snapshot) = dom_snapshot_bytes(state) {
-            obs.dom_snapshot = snapshot;
-        }
+        batch.add("dom", dom_snapshot_script(opts.dom_flat));
     }
-
     if opts.include_accessibility {
-        if let Some(snapshot) = accessibility_snapshot_bytes(state) {
-            obs.accessibility_tree = snapshot;
-        }
+        batch.add("accessibility", accessibility_snapshot_script());
     }
-
     if opts.include_hit_test {
-        if let Some(map) = build_hit_test_map(state) {
-            state.last_hit_test = Some(map.clone());
-            obs.hit_test = Some(map);
+        if let Ok(script) = hit_test_script(&HitTestQuery::from_observe(opts).for_cache()) {
+            batch.add("hit_test", script);
+        }
+    }
+    batch.add("focused", FOCUSED_NODE_SCRIPT.to_string());
+    if opts.include_text_runs {
+        batch.add("text_runs", text_runs_script());
+    }
+    if !opts.style_node_ids.is_empty() {
+        let (node_ids, properties) = style_query(opts);
+        if let Ok(script) = computed_styles_script(node_ids, &properties) {
+            batch.add("styles", script);
         }
     }
+    if opts.include_timing {
+        batch.add("timing", NAVIGATION_TIMING_SCRIPT.to_string());
+    }
+    if opts.include_metadata {
+        batch.add("metadata", PAGE_METADATA_SCRIPT.to_string());
+    }
+    if opts.include_html {
+        batch.add("html", document_html_script(html_byte_cap(opts)));
+    }
+    let batched = batch.run(state);
+
+    // Console and page error reads empty the page's buffers, so they run on
+    // their own, and only once the snapshot batch got through: entries
+    // drained by a batch that then timed out would be lost for good.
+    let mut drains = ObservationBuilder::default();
+    if opts.include_console {
+        drains.add("console", page_buffer_script("__buckleyConsole"));
+    }
+    if opts.include_page_errors {
+        drains.add("page_errors", page_buffer_script("__buckleyPageErrors"));
+    }
+    let drained = batched.as_ref().and_then(|_| drains.run(state));
+    let mut results = batched.unwrap_or_default();
+    results.extend(drained.unwrap_or_default());
+
+    if let Some(json) = results.remove("dom") {
+        obs.dom_snapshot = json.into_bytes();
+    }
+    if let Some(json) = results.remove("accessibility") {
+        obs.accessibility_tree = json.into_bytes();
+    }
+    if let Some(map) = results
+        .remove("hit_test")
+        .and_then(|json| hit_test_map_from_json(state, &json))
+    {
+        state.last_hit_test = Some(map.clone());
+        obs.hit_test = Some(HitTestQuery::from_observe(opts).truncate(map));
+    }
+    obs.focused_node_id = results
+        .remove("focused")
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(0);
+    if let Some(runs) = results
+        .remove("text_runs")
+        .and_then(|json| text_runs_from_json(&json))
+    {
+        obs.text_runs = runs;
+    }
+    obs.computed_styles = results
+        .remove("styles")
+        .and_then(|json| computed_styles_from_json(&json));
+    obs.navigation_timing = results
+        .remove("timing")
+        .and_then(|json| navigation_timing_from_json(&json));
+    if let Some(json) = results.remove("console") {
+        obs.console_messages = console_messages_from_json(&json);
+    }
+    if let Some(json) = results.remove("page_errors") {
+        obs.page_errors = page_errors_from_json(&json);
+    }
+    obs.page_metadata = results
+        .remove("metadata")
+        .and_then(|json| page_metadata_from_json(&json));
+    if let Some(html) = results.remove("html") {
+        set_observation_html(&mut obs, html, opts);
+    }
+    set_content_hashes(&mut obs);
 
     Ok(obs)
 }
 
+/// Collects the page scripts one observation needs and evaluates them in a
+/// single round trip. Every `evaluate_javascript_sync` spins Servo's event
+/// loop until the result comes back, which costs at least one poll (1ms,
+/// backing off to `SPIN_POLL_INTERVAL_MS`) plus a trip to the script thread
+/// and back. An observation asking for DOM, accessibility and hit test used
+/// to pay that four times, once per part and once for the focused node;
+/// batched it pays once, whatever the page. Each batch's time is logged at
+/// debug level. The JS budget covers the whole batch. Parts run in the order
+/// they were added, and one that throws only loses its own result.
+#[derive(Default)]
+struct ObservationBuilder {
+    parts: Vec<(&'static str, String)>,
+}
+
+impl ObservationBuilder {
+    /// Queue `script`, an expression evaluating to a string, under `key`.
+    fn add(&mut self, key: &'static str, script: String) {
+        self.parts.push((key, script));
+    }
+
+    fn script(&self) -> String {
+        let calls: String = self
+            .parts
+            .iter()
+            .map(|(key, script)| {
+                format!(
+                    "    run({key:?}, () => {});\n",
+                    script.trim().trim_end_matches(';')
+                )
+            })
+            .collect();
+        format!(
+            r#"(function() {{
+    const results = {{}};
+    const run = (key, part) => {{
+        try {{
+            const value = part();
+            results[key] = typeof value === "string" ? value : null;
+        }} catch (e) {{
+            results[key] = null;
+        }}
+    }};
+{calls}    return JSON.stringify(results);
+}})()"#
+        )
+    }
+
+    /// Evaluate the queued scripts, returning each part's result by key, or
+    /// `None` when the batch as a whole failed. Parts that threw or returned
+    /// a non-string are missing.
+    fn run(self, state: &mut ServoState) -> Option<HashMap<&'static str, String>> {
+        let mut results = HashMap::new();
+        let webview = state.active_webview().cloned()?;
+        if self.parts.is_empty() {
+            return Some(results);
+        }
+        let started = Instant::now();
+        let json = match evaluate_javascript_sync(state, &webview, &self.script())
+            .and_then(js_value_to_string)
+        {
+            Ok(json) => json,
+            Err(err) => {
+                log::warn!("observation scripts failed: {}", err.message);
+                return None;
+            }
+        };
+        log::debug!(
+            "observation scripts ({} parts) took {:?}",
+            self.parts.len(),
+            started.elapsed()
+        );
+        let mut values: HashMap<String, Option<String>> = match serde_json::from_str(&json) {
+            Ok(values) => values,
+            Err(err) => {
+                log::warn!("observation scripts JSON parse error: {}", err);
+                return None;
+            }
+        };
+        for (key, _) in self.parts {
+            if let Some(Some(value)) = values.remove(key) {
+                results.insert(key, value);
+            }
+        }
+        Some(results)
+    }
+}
+
+/// Paint the active tab if Servo has a new frame ready. Returns whether a
+/// frame was painted.
+fn paint_ready_frame(state: &mut ServoState) -> bool {
+    if !state.frame_ready.replace(false) {
+        return false;
+    }
+    let Some(webview) = state.active_webview() else {
+        return false;
+    };
+    webview.paint();
+    state.paint_epoch += 1;
+    true
+}
+
+/// Paint the frame Servo has ready, if any. When nothing has been painted
+/// since the state version last moved, wait up to `RENDER_QUIET_MS` for the
+/// first frame showing the change, returning as soon as it is painted.
+/// Returns false if another frame was already queued behind it, i.e. the
+/// page is still painting.
+fn settle_paint(state: &mut ServoState) -> bool {
+    if state.active_webview().is_none() {
+        return true;
+    }
+    let deadline = Instant::now() + Duration::from_millis(RENDER_QUIET_MS);
+    loop {
+        state.servo.spin_event_loop();
+        if paint_ready_frame(state) {
+            state.painted_version = state.state_version;
+            return !state.frame_ready.get();
+        }
+        if state.painted_version == state.state_version || Instant::now() >= deadline {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(SPIN_POLL_INTERVAL_MS));
+    }
+}
+
+/// Buckley node id of `document.activeElement`, or 0 when nothing is focused.
+/// Id of the focused element as a string, "0" when nothing is focused.
+const FOCUSED_NODE_SCRIPT: &str = r#"(function() {
+    const NEXT_ID_KEY = "__buckleyNextId";
+    const el = document.activeElement;
+    if (!el || el === document.body || el === document.documentElement) return "0";
+    if (!el.__buckleyId) {
+        const next = (window[NEXT_ID_KEY] || 1);
+        el.__buckleyId = next;
+        window[NEXT_ID_KEY] = next + 1;
+    }
+    return String(el.__buckleyId);
+})()"#;
+
 fn wait_for_load(
     state: &mut ServoState,
     webview: &WebView,
     timeout: Duration,
 ) -> Result<(), EngineError> {
     let deadline = Instant::now() + timeout;
+    poll_until(state, deadline, || {
+        (webview.load_status() == LoadStatus::Complete).then_some(())
+    })
+    .ok_or_else(|| EngineError::new("load_timeout", "navigation timed out"))
+}
+
+/// Like `wait_for_load`, but gives up early when the session's stop signal is
+/// raised. Returns whether it was.
+fn wait_for_load_or_stop(
+    state: &mut ServoState,
+    webview: &WebView,
+    timeout: Duration,
+) -> Result<bool, EngineError> {
+    let deadline = Instant::now() + timeout;
+    let stop = state.stop.clone();
+    poll_until(state, deadline, || {
+        if stop.take() {
+            return Some(true);
+        }
+        (webview.load_status() == LoadStatus::Complete).then_some(false)
+    })
+    .ok_or_else(|| EngineError::new("load_timeout", "navigation timed out"))
+}
+
+/// Spin the event loop until `predicate` yields a value or `deadline`
+/// passes, returning `None` on timeout. Checks start `SPIN_POLL_MIN_INTERVAL_MS`
+/// apart and back off to `SPIN_POLL_INTERVAL_MS`, so quick operations return
+/// promptly while slow ones don't spin hot.
+fn poll_until<T>(
+    state: &mut ServoState,
+    deadline: Instant,
+    mut predicate: impl FnMut() -> Option<T>,
+) -> Option<T> {
+    let mut attempt = 0;
     loop {
         state.servo.spin_event_loop();
-        if webview.load_status() == LoadStatus::Complete {
-            return Ok(());
+        if let Some(value) = predicate() {
+            return Some(value);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        thread::sleep(poll_interval(attempt).min(deadline - now));
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+/// Delay before check `attempt + 1` of `poll_until`: doubling from
+/// `SPIN_POLL_MIN_INTERVAL_MS`, capped at `SPIN_POLL_INTERVAL_MS`.
+fn poll_interval(attempt: u32) -> Duration {
+    let ms = SPIN_POLL_MIN_INTERVAL_MS
+        .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX))
+        .min(SPIN_POLL_INTERVAL_MS);
+    Duration::from_millis(ms)
+}
+
+fn handle_wait_for(
+    state: &mut ServoState,
+    selector: &str,
+    timeout: Duration,
+) -> Result<pb::Observation, EngineError> {
+    let webview = state
+        .active_webview()
+        .cloned()
+        .ok_or_else(|| EngineError::new("no_webview", "no webview active - navigate first"))?;
+    let selector_json = serde_json::to_string(selector)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let script = format!("document.querySelector({selector_json}) !== null");
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let JSValue::Boolean(true) = evaluate_javascript_sync(state, &webview, &script)? {
+            break;
         }
         if Instant::now() >= deadline {
-            return Err(EngineError::new("load_timeout", "navigation timed out"));
+            return Err(EngineError::new(
+                "wait_timeout",
+                format!("selector not found within {}ms", timeout.as_millis()),
+            ));
         }
         thread::sleep(Duration::from_millis(SPIN_POLL_INTERVAL_MS));
     }
+    build_observation(state, &pb::ObserveOptions::default())
 }
 
 fn refresh_page_metadata(state: &mut ServoState, webview: &WebView) {
@@ -923,8 +4100,8 @@ fn refresh_page_metadata(state: &mut ServoState, webview: &WebView) {
 }
 
 fn dom_snapshot_bytes(state: &mut ServoState) -> Option<Vec<u8>> {
-    let webview = state.webview.clone()?;
-    let script = dom_snapshot_script();
+    let webview = state.active_webview().cloned()?;
+    let script = dom_snapshot_script(false);
     match evaluate_javascript_sync(state, &webview, &script) {
         Ok(value) => match js_value_to_string(value) {
             Ok(json) => Some(json.into_bytes()),
@@ -941,7 +4118,7 @@ fn dom_snapshot_bytes(state: &mut ServoState) -> Option<Vec<u8>> {
 }
 
 fn accessibility_snapshot_bytes(state: &mut ServoState) -> Option<Vec<u8>> {
-    let webview = state.webview.clone()?;
+    let webview = state.active_webview().cloned()?;
     let script = accessibility_snapshot_script();
     match evaluate_javascript_sync(state, &webview, &script) {
         Ok(value) => match js_value_to_string(value) {
@@ -958,51 +4135,469 @@ fn accessibility_snapshot_bytes(state: &mut ServoState) -> Option<Vec<u8>> {
     }
 }
 
-fn build_hit_test_map(state: &mut ServoState) -> Option<pb::HitTestMap> {
-    let webview = state.webview.clone()?;
-    let script = hit_test_script();
-    let value = evaluate_javascript_sync(state, &webview, &script).ok()?;
-    let json = js_value_to_string(value).ok()?;
+fn build_hit_test_map(state: &mut ServoState, query: &HitTestQuery) -> Option<pb::HitTestMap> {
+    let webview = state.active_webview().cloned()?;
+    let script = hit_test_script(query).ok()?;
+    let value = evaluate_javascript_sync(state, &webview, &script).ok()?;
+    let json = js_value_to_string(value).ok()?;
+    hit_test_map_from_json(state, &json)
+}
+
+fn hit_test_map_from_json(state: &ServoState, json: &str) -> Option<pb::HitTestMap> {
+    #[derive(serde::Deserialize)]
+    struct HitRegionJson {
+        id: u64,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        tag: String,
+        role: String,
+        label: String,
+        occluded: bool,
+    }
+
+    let regions: Vec<HitRegionJson> = match serde_json::from_str(json) {
+        Ok(regions) => regions,
+        Err(err) => {
+            log::warn!("hit test JSON parse error: {}", err);
+            return None;
+        }
+    };
+
+    let mut map = pb::HitTestMap {
+        width: state.viewport_width,
+        height: state.viewport_height,
+        regions: Vec::new(),
+    };
+
+    for region in regions {
+        if region.width <= 0.0 || region.height <= 0.0 {
+            continue;
+        }
+        map.regions.push(pb::HitRegion {
+            node_id: region.id,
+            bounds: Some(pb::Rect {
+                x: region.x.round() as i32,
+                y: region.y.round() as i32,
+                width: region.width.round() as i32,
+                height: region.height.round() as i32,
+            }),
+            tag: region.tag,
+            role: region.role,
+            label: region.label,
+            occluded: region.occluded,
+        });
+    }
+
+    Some(map)
+}
+
+fn text_runs_from_json(json: &str) -> Option<Vec<pb::TextRun>> {
+    #[derive(serde::Deserialize)]
+    struct TextRunJson {
+        id: u64,
+        text: String,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    }
+
+    let runs: Vec<TextRunJson> = match serde_json::from_str(json) {
+        Ok(runs) => runs,
+        Err(err) => {
+            log::warn!("text runs JSON parse error: {}", err);
+            return None;
+        }
+    };
+
+    Some(
+        runs.into_iter()
+            .filter(|run| run.width > 0.0 && run.height > 0.0)
+            .map(|run| pb::TextRun {
+                text: run.text,
+                node_id: run.id,
+                bounds: Some(pb::Rect {
+                    x: run.x.round() as i32,
+                    y: run.y.round() as i32,
+                    width: run.width.round() as i32,
+                    height: run.height.round() as i32,
+                }),
+            })
+            .collect(),
+    )
+}
+
+fn computed_styles_from_json(json: &str) -> Option<pb::ComputedStyles> {
+    let nodes: HashMap<String, HashMap<String, String>> = match serde_json::from_str(json) {
+        Ok(nodes) => nodes,
+        Err(err) => {
+            log::warn!("computed styles JSON parse error: {}", err);
+            return None;
+        }
+    };
+    Some(pb::ComputedStyles {
+        nodes: nodes
+            .into_iter()
+            .filter_map(|(id, properties)| Some((id.parse().ok()?, pb::NodeStyle { properties })))
+            .collect(),
+    })
+}
+
+/// Empty the array a document-start script keeps at `window[global]` in the
+/// active tab. The buffer lives in the page, so entries recorded by a page
+/// that navigates away before they are read are lost.
+fn drain_page_buffer(state: &mut ServoState, global: &str) -> Option<String> {
+    let webview = state.active_webview().cloned()?;
+    let script = page_buffer_script(global);
+    match evaluate_javascript_sync(state, &webview, &script).and_then(js_value_to_string) {
+        Ok(json) => Some(json),
+        Err(err) => {
+            log::warn!("{global} drain error: {}", err.message);
+            None
+        }
+    }
+}
+
+/// Script that empties `window[global]` and returns its entries as JSON.
+fn page_buffer_script(global: &str) -> String {
+    format!("JSON.stringify(window.{global} ? window.{global}.splice(0) : [])")
+}
+
+fn page_buffer_from_json<T: serde::de::DeserializeOwned>(global: &str, json: &str) -> Vec<T> {
+    serde_json::from_str(json).unwrap_or_else(|err| {
+        log::warn!("{global} JSON parse error: {}", err);
+        Vec::new()
+    })
+}
+
+/// Take the messages buffered by `console_capture_script`.
+fn drain_console_messages(state: &mut ServoState) -> Vec<pb::ConsoleMessage> {
+    drain_page_buffer(state, "__buckleyConsole")
+        .map(|json| console_messages_from_json(&json))
+        .unwrap_or_default()
+}
+
+fn console_messages_from_json(json: &str) -> Vec<pb::ConsoleMessage> {
+    #[derive(serde::Deserialize)]
+    struct ConsoleJson {
+        level: String,
+        text: String,
+        ts: f64,
+    }
+
+    page_buffer_from_json::<ConsoleJson>("__buckleyConsole", json)
+        .into_iter()
+        .map(|message| {
+            let ms = message.ts.max(0.0) as i64;
+            pb::ConsoleMessage {
+                level: message.level,
+                text: message.text,
+                timestamp: Some(prost_types::Timestamp {
+                    seconds: ms / 1000,
+                    nanos: ((ms % 1000) * 1_000_000) as i32,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Wraps the console methods at document start to copy each call into
+/// `window.__buckleyConsole`, keeping the newest `MAX_CONSOLE_MESSAGES`.
+fn console_capture_script() -> String {
+    format!(
+        r#"(function() {{
+    if (window.__buckleyConsole) {{
+        return;
+    }}
+    const buffer = [];
+    window.__buckleyConsole = buffer;
+    const format = (value) => {{
+        if (typeof value === "string" || value instanceof Error) {{
+            return String(value);
+        }}
+        try {{
+            const json = JSON.stringify(value);
+            return json === undefined ? String(value) : json;
+        }} catch (e) {{
+            return String(value);
+        }}
+    }};
+    for (const level of ["log", "info", "warn", "error", "debug"]) {{
+        const original = console[level];
+        console[level] = function(...args) {{
+            const text = args.map(format).join(" ").slice(0, {MAX_CONSOLE_MESSAGE_CHARS});
+            buffer.push({{ level: level, text: text, ts: Date.now() }});
+            if (buffer.length > {MAX_CONSOLE_MESSAGES}) {{
+                buffer.splice(0, buffer.length - {MAX_CONSOLE_MESSAGES});
+            }}
+            if (original) {{
+                return original.apply(console, args);
+            }}
+        }};
+    }}
+}})();"#
+    )
+}
+
+/// Script returning the active page's `documentElement.outerHTML`, cut to
+/// just over `max_bytes` UTF-16 units. That is never less than `max_bytes`
+/// bytes, so `set_observation_html` still sees that it was truncated and
+/// makes the final byte cut.
+fn document_html_script(max_bytes: usize) -> String {
+    let limit = max_bytes + 1;
+    format!(
+        r#"(function() {{
+            const root = document.documentElement;
+            return root ? root.outerHTML.slice(0, {limit}) : "";
+        }})()"#,
+    )
+}
+
+/// Parse the errors buffered by `page_error_capture_script`.
+fn page_errors_from_json(json: &str) -> Vec<pb::PageError> {
+    #[derive(serde::Deserialize)]
+    struct PageErrorJson {
+        #[serde(default)]
+        message: String,
+        #[serde(default)]
+        source: String,
+        #[serde(default)]
+        line: u32,
+        #[serde(default)]
+        column: u32,
+    }
+
+    page_buffer_from_json::<PageErrorJson>("__buckleyPageErrors", json)
+        .into_iter()
+        .map(|error| pb::PageError {
+            message: error.message,
+            source: error.source,
+            line: error.line,
+            column: error.column,
+        })
+        .collect()
+}
+
+/// Counts the page's in-flight fetch and XHR requests in
+/// `window.__buckleyInflight`. The delegate only sees requests start, so
+/// completion has to come from the page.
+const NETWORK_TRACKING_SCRIPT: &str = r#"(function() {
+    if (window.__buckleyInflight !== undefined) {
+        return;
+    }
+    window.__buckleyInflight = 0;
+    const done = () => {
+        window.__buckleyInflight = Math.max(0, window.__buckleyInflight - 1);
+    };
+    if (window.fetch) {
+        const fetch = window.fetch;
+        window.fetch = function(...args) {
+            window.__buckleyInflight++;
+            let pending;
+            try {
+                pending = fetch.apply(this, args);
+            } catch (e) {
+                done();
+                throw e;
+            }
+            pending.then(done, done);
+            return pending;
+        };
+    }
+    const send = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.send = function(...args) {
+        window.__buckleyInflight++;
+        this.addEventListener("loadend", done, { once: true });
+        try {
+            return send.apply(this, args);
+        } catch (e) {
+            done();
+            throw e;
+        }
+    };
+})();"#;
+
+/// Records uncaught errors and unhandled rejections in
+/// `window.__buckleyPageErrors`, keeping the newest `MAX_PAGE_ERRORS`.
+/// Listeners are used rather than `window.onerror` so the page can still set
+/// its own handler.
+fn page_error_capture_script() -> String {
+    format!(
+        r#"(function() {{
+    if (window.__buckleyPageErrors) {{
+        return;
+    }}
+    const errors = [];
+    window.__buckleyPageErrors = errors;
+    const record = (entry) => {{
+        errors.push(entry);
+        if (errors.length > {MAX_PAGE_ERRORS}) {{
+            errors.splice(0, errors.length - {MAX_PAGE_ERRORS});
+        }}
+    }};
+    window.addEventListener("error", (event) => {{
+        // Resource load failures also fire "error", on the element.
+        if (event.target !== window) {{
+            return;
+        }}
+        record({{
+            message: String(event.message || event.error || "error"),
+            source: String(event.filename || ""),
+            line: event.lineno || 0,
+            column: event.colno || 0,
+        }});
+    }});
+    window.addEventListener("unhandledrejection", (event) => {{
+        const reason = event.reason;
+        let text = String(reason);
+        if (!(reason instanceof Error)) {{
+            try {{
+                text = JSON.stringify(reason) ?? text;
+            }} catch (e) {{}}
+        }}
+        record({{ message: "Unhandled rejection: " + text, source: "", line: 0, column: 0 }});
+    }});
+}})();"#
+    )
+}
 
+fn navigation_timing_from_json(json: &str) -> Option<pb::NavigationTiming> {
     #[derive(serde::Deserialize)]
-    struct HitRegionJson {
-        id: u64,
-        x: f32,
-        y: f32,
-        width: f32,
-        height: f32,
+    struct TimingJson {
+        dns: f64,
+        connect: f64,
+        ttfb: f64,
+        dom_content_loaded: f64,
+        load: f64,
     }
 
-    let regions: Vec<HitRegionJson> = match serde_json::from_str(&json) {
-        Ok(regions) => regions,
+    let timing: TimingJson = match serde_json::from_str(json) {
+        Ok(timing) => timing,
         Err(err) => {
-            log::warn!("hit test JSON parse error: {}", err);
+            log::warn!("navigation timing JSON parse error: {}", err);
             return None;
         }
     };
+    // Unfinished phases report an end of 0, which makes the span negative.
+    let phase = |ms: f64| if ms.is_finite() { ms.max(0.0) } else { 0.0 };
+    Some(pb::NavigationTiming {
+        dns_ms: phase(timing.dns),
+        connect_ms: phase(timing.connect),
+        ttfb_ms: phase(timing.ttfb),
+        dom_content_loaded_ms: phase(timing.dom_content_loaded),
+        load_ms: phase(timing.load),
+    })
+}
 
-    let mut map = pb::HitTestMap {
-        width: state.viewport_width,
-        height: state.viewport_height,
-        regions: Vec::new(),
+fn page_metadata_from_json(json: &str) -> Option<pb::PageMetadata> {
+    #[derive(serde::Deserialize, Default)]
+    #[serde(default)]
+    struct MetadataJson {
+        favicon_url: String,
+        description: String,
+        og_title: String,
+        og_image: String,
+        canonical_url: String,
+    }
+
+    let metadata: MetadataJson = match serde_json::from_str(json) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            log::warn!("page metadata JSON parse error: {}", err);
+            return None;
+        }
     };
+    Some(pb::PageMetadata {
+        favicon_url: metadata.favicon_url,
+        description: metadata.description,
+        og_title: metadata.og_title,
+        og_image: metadata.og_image,
+        canonical_url: metadata.canonical_url,
+    })
+}
 
-    for region in regions {
-        if region.width <= 0.0 || region.height <= 0.0 {
-            continue;
+/// Each field is read on its own so a missing or odd tag only blanks that
+/// field. Values are capped at 2048 characters.
+const PAGE_METADATA_SCRIPT: &str = r#"(function() {
+    const read = (selector, prop, isUrl) => {
+        try {
+            const el = document.querySelector(selector);
+            let value = el ? String(el.getAttribute(prop) || "").trim() : "";
+            if (value && isUrl) {
+                value = new URL(value, document.baseURI).href;
+            }
+            return value.slice(0, 2048);
+        } catch (e) {
+            return "";
         }
-        map.regions.push(pb::HitRegion {
-            node_id: region.id,
-            bounds: Some(pb::Rect {
-                x: region.x.round() as i32,
-                y: region.y.round() as i32,
-                width: region.width.round() as i32,
-                height: region.height.round() as i32,
-            }),
-        });
+    };
+    return JSON.stringify({
+        favicon_url: read('link[rel~="icon"]', "href", true),
+        description: read('meta[name="description"]', "content", false),
+        og_title: read('meta[property="og:title"]', "content", false),
+        og_image: read('meta[property="og:image"]', "content", true),
+        canonical_url: read('link[rel~="canonical"]', "href", true)
+    });
+})()"#;
+
+/// Prefers the Navigation Timing Level 2 entry and falls back to the
+/// deprecated `performance.timing`, rebased onto navigationStart.
+const NAVIGATION_TIMING_SCRIPT: &str = r#"(function() {
+    const entries = performance.getEntriesByType ? performance.getEntriesByType("navigation") : [];
+    let t = entries.length ? entries[0] : null;
+    if (!t && performance.timing) {
+        const raw = performance.timing;
+        const base = raw.navigationStart;
+        const rel = (value) => (value ? value - base : 0);
+        t = {
+            domainLookupStart: rel(raw.domainLookupStart),
+            domainLookupEnd: rel(raw.domainLookupEnd),
+            connectStart: rel(raw.connectStart),
+            connectEnd: rel(raw.connectEnd),
+            requestStart: rel(raw.requestStart),
+            responseStart: rel(raw.responseStart),
+            domContentLoadedEventEnd: rel(raw.domContentLoadedEventEnd),
+            loadEventEnd: rel(raw.loadEventEnd)
+        };
     }
-
-    Some(map)
+    t = t || {};
+    const span = (start, end) => (end || 0) - (start || 0);
+    return JSON.stringify({
+        dns: span(t.domainLookupStart, t.domainLookupEnd),
+        connect: span(t.connectStart, t.connectEnd),
+        ttfb: span(t.requestStart, t.responseStart),
+        dom_content_loaded: t.domContentLoadedEventEnd || 0,
+        load: t.loadEventEnd || 0
+    });
+})()"#;
+
+fn computed_styles_script(node_ids: &[u64], properties: &[String]) -> Result<String, EngineError> {
+    let ids_json = serde_json::to_string(node_ids)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    let properties_json = serde_json::to_string(properties)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    Ok(format!(
+        r#"(function() {{
+            const ids = new Set({ids_json});
+            const properties = {properties_json};
+            const found = {{}};
+            for (const el of document.getElementsByTagName("*")) {{
+                const id = el.__buckleyId;
+                if (!id || !ids.has(id) || found[id]) {{
+                    continue;
+                }}
+                const style = window.getComputedStyle(el);
+                const values = {{}};
+                for (const name of properties) {{
+                    values[name] = style.getPropertyValue(name);
+                }}
+                found[id] = values;
+            }}
+            return JSON.stringify(found);
+        }})()"#,
+    ))
 }
 
 fn wrap_diff_json(state_version: u64, snapshot: &[u8]) -> Vec<u8> {
@@ -1026,25 +4621,35 @@ fn evaluate_javascript_sync(
         *callback_cell.borrow_mut() = Some(result);
     });
 
-    let deadline = Instant::now() + Duration::from_millis(JS_EVALUATION_TIMEOUT_MS);
-    loop {
-        state.servo.spin_event_loop();
-        if let Some(result) = result_cell.borrow_mut().take() {
-            return result.map_err(|err| {
-                EngineError::new(
-                    "script_error",
-                    format!("javascript evaluation failed: {:?}", err),
-                )
-            });
-        }
-        if Instant::now() >= deadline {
-            return Err(EngineError::new(
-                "script_timeout",
-                "javascript evaluation timed out",
-            ));
-        }
-        thread::sleep(Duration::from_millis(SPIN_POLL_INTERVAL_MS));
+    // A configured budget replaces the default deadline and is reported
+    // distinctly so callers can tell policy limits from a stuck engine.
+    let (timeout, timeout_error) = match state.js_budget {
+        Some(budget) => (
+            budget,
+            EngineError::new(
+                "script_budget_exceeded",
+                format!("javascript exceeded budget of {}ms", budget.as_millis()),
+            ),
+        ),
+        None => (
+            Duration::from_millis(JS_EVALUATION_TIMEOUT_MS),
+            EngineError::new("script_timeout", "javascript evaluation timed out"),
+        ),
+    };
+    let mut deadline = Instant::now() + timeout;
+    let mut timeout_error = timeout_error;
+    if let Some(action_deadline) = state.action_deadline.filter(|d| *d < deadline) {
+        deadline = action_deadline;
+        timeout_error = EngineError::new("action_timeout", "action exceeded its timeout");
     }
+    let result =
+        poll_until(state, deadline, || result_cell.borrow_mut().take()).ok_or(timeout_error)?;
+    result.map_err(|err| {
+        EngineError::new(
+            "script_error",
+            format!("javascript evaluation failed: {:?}", err),
+        )
+    })
 }
 
 fn js_value_to_string(value: JSValue) -> Result<String, EngineError> {
@@ -1058,9 +4663,13 @@ fn js_value_to_string(value: JSValue) -> Result<String, EngineError> {
     }
 }
 
-fn dom_snapshot_script() -> String {
+/// The DOM snapshot script: a nested tree under `root`, or with `flat` a
+/// `nodes` array of elements carrying parent ids. Both forms share the depth,
+/// child and text caps.
+fn dom_snapshot_script(flat: bool) -> String {
     format!(
         r#"(function() {{
+            const FLAT = {flat};
             const MAX_DEPTH = {max_depth};
             const MAX_CHILDREN = {max_children};
             const MAX_TEXT = {max_text};
@@ -1083,16 +4692,28 @@ fn dom_snapshot_script() -> String {
                 return value.slice(0, 200);
             }}
 
+            function elementAttrs(el) {{
+                const attrs = {{}};
+                const names = ["id","class","name","type","value","href","src","role","aria-label","title","alt"];
+                for (const name of names) {{
+                    const value = attrValue(el, name);
+                    if (value) attrs[name] = value;
+                }}
+                // A select's choice lives in its options and a checkbox's
+                // state in a property, not in attributes.
+                if (el.tagName === "SELECT" && el.value) {{
+                    attrs.value = el.value.slice(0, 200);
+                }}
+                if (el.type === "checkbox" || el.type === "radio") {{
+                    attrs.checked = el.checked ? "true" : "false";
+                }}
+                return attrs;
+            }}
+
             function serializeNode(node, depth) {{
                 if (!node || depth > MAX_DEPTH) return null;
                 if (node.nodeType === Node.ELEMENT_NODE) {{
                     const el = node;
-                    const attrs = {{}};
-                    const names = ["id","class","name","type","value","href","src","role","aria-label","title","alt"];
-                    for (const name of names) {{
-                        const value = attrValue(el, name);
-                        if (value) attrs[name] = value;
-                    }}
                     const children = [];
                     let count = 0;
                     for (const child of el.childNodes) {{
@@ -1106,7 +4727,7 @@ fn dom_snapshot_script() -> String {
                     return {{
                         node_id: ensureId(el),
                         tag: el.tagName.toLowerCase(),
-                        attrs: attrs,
+                        attrs: elementAttrs(el),
                         children: children
                     }};
                 }}
@@ -1119,12 +4740,47 @@ fn dom_snapshot_script() -> String {
                 return null;
             }}
 
+            // Elements in document order, each pointing at its parent. Text
+            // nodes fold into their element's `text`.
+            function flattenNodes(root) {{
+                const nodes = [];
+                function visit(el, parentId, depth) {{
+                    if (depth > MAX_DEPTH) return;
+                    const id = ensureId(el);
+                    const texts = [];
+                    for (const child of el.childNodes) {{
+                        if (child.nodeType !== Node.TEXT_NODE) continue;
+                        const trimmed = (child.textContent || "").trim();
+                        if (trimmed) texts.push(trimmed);
+                    }}
+                    nodes.push({{
+                        node_id: id,
+                        parent_id: parentId,
+                        tag: el.tagName.toLowerCase(),
+                        text: texts.join(" ").slice(0, MAX_TEXT),
+                        attrs: elementAttrs(el)
+                    }});
+                    let count = 0;
+                    for (const child of el.children) {{
+                        if (count >= MAX_CHILDREN) break;
+                        visit(child, id, depth + 1);
+                        count += 1;
+                    }}
+                }}
+                if (root) visit(root, 0, 0);
+                return nodes;
+            }}
+
             const root = document.documentElement || document.body;
             const snapshot = {{
                 url: document.URL,
-                title: document.title || "",
-                root: root ? serializeNode(root, 0) : null
+                title: document.title || ""
             }};
+            if (FLAT) {{
+                snapshot.nodes = flattenNodes(root);
+            }} else {{
+                snapshot.root = root ? serializeNode(root, 0) : null;
+            }}
             return JSON.stringify(snapshot);
         }})()"#,
         max_depth = DOM_MAX_DEPTH,
@@ -1266,10 +4922,13 @@ fn accessibility_snapshot_script() -> String {
     )
 }
 
-fn hit_test_script() -> String {
-    format!(
+fn hit_test_script(query: &HitTestQuery) -> Result<String, EngineError> {
+    let extra_selectors_json = serde_json::to_string(&query.selectors)
+        .map_err(|e| EngineError::new("invalid_request", e.to_string()))?;
+    Ok(format!(
         r#"(function() {{
             const MAX_REGIONS = {max_regions};
+            const MAX_LABEL = {max_label};
             const NEXT_ID_KEY = "__buckleyNextId";
 
             function ensureId(el) {{
@@ -1291,6 +4950,46 @@ fn hit_test_script() -> String {
                 return rect.right > 0 && rect.bottom > 0 && rect.left < vw && rect.top < vh;
             }}
 
+            // Same implicit roles as the accessibility snapshot.
+            function roleFor(el) {{
+                const role = el.getAttribute && el.getAttribute("role");
+                if (role) return role.toLowerCase();
+                const tag = el.tagName.toLowerCase();
+                if (tag === "a") return "link";
+                if (tag === "button") return "button";
+                if (tag === "input") {{
+                    const type = (el.getAttribute("type") || "text").toLowerCase();
+                    if (type === "checkbox") return "checkbox";
+                    if (type === "radio") return "radio";
+                    if (type === "submit" || type === "button") return "button";
+                    return "textbox";
+                }}
+                if (tag === "textarea") return "textbox";
+                if (tag === "select") return "combobox";
+                if (tag === "option") return "option";
+                return "generic";
+            }}
+
+            function labelFor(el) {{
+                const aria = el.getAttribute && el.getAttribute("aria-label");
+                const text = aria || el.textContent || "";
+                return text.replace(/\s+/g, " ").trim().slice(0, MAX_LABEL);
+            }}
+
+            // Probe the centre of the part of the element inside the
+            // viewport; anything else on top (an overlay, a modal) that
+            // isn't the element or one of its children covers it.
+            function isOccluded(el, rect) {{
+                const vw = window.innerWidth || document.documentElement.clientWidth;
+                const vh = window.innerHeight || document.documentElement.clientHeight;
+                const left = Math.max(rect.left, 0);
+                const top = Math.max(rect.top, 0);
+                const right = Math.min(rect.right, vw);
+                const bottom = Math.min(rect.bottom, vh);
+                const topmost = document.elementFromPoint((left + right) / 2, (top + bottom) / 2);
+                return !!topmost && topmost !== el && !el.contains(topmost);
+            }}
+
             const selectors = [
                 "a[href]",
                 "button",
@@ -1302,6 +5001,13 @@ fn hit_test_script() -> String {
                 "[onclick]",
                 "[tabindex]"
             ];
+            // A selector that doesn't parse would make the whole query throw.
+            for (const extra of {extra_selectors_json}) {{
+                try {{
+                    document.querySelector(extra);
+                    selectors.push(extra);
+                }} catch (e) {{}}
+            }}
 
             const regions = [];
             const root = document.documentElement || document.body;
@@ -1312,7 +5018,11 @@ fn hit_test_script() -> String {
                     x: Math.max(0, Math.round(rect.left)),
                     y: Math.max(0, Math.round(rect.top)),
                     width: Math.round(rect.width),
-                    height: Math.round(rect.height)
+                    height: Math.round(rect.height),
+                    tag: root.tagName.toLowerCase(),
+                    role: "document",
+                    label: "",
+                    occluded: false
                 }});
             }}
 
@@ -1328,52 +5038,426 @@ fn hit_test_script() -> String {
                     x: Math.round(rect.left),
                     y: Math.round(rect.top),
                     width: Math.round(rect.width),
-                    height: Math.round(rect.height)
+                    height: Math.round(rect.height),
+                    tag: el.tagName.toLowerCase(),
+                    role: roleFor(el),
+                    label: labelFor(el),
+                    occluded: isOccluded(el, rect)
                 }});
             }}
             return JSON.stringify(regions);
         }})()"#,
-        max_regions = HIT_TEST_MAX_REGIONS,
+        max_regions = query.max_regions,
+        max_label = HIT_TEST_MAX_LABEL_CHARS,
+    ))
+}
+
+fn text_runs_script() -> String {
+    format!(
+        r#"(function() {{
+            const MAX_RUNS = {max_runs};
+            const MAX_RUN_CHARS = {max_run_chars};
+            const MAX_TOTAL_CHARS = {max_total_chars};
+            const NEXT_ID_KEY = "__buckleyNextId";
+
+            function ensureId(el) {{
+                if (!el) return 0;
+                if (!el.__buckleyId) {{
+                    const next = (window[NEXT_ID_KEY] || 1);
+                    el.__buckleyId = next;
+                    window[NEXT_ID_KEY] = next + 1;
+                }}
+                return el.__buckleyId;
+            }}
+
+            const vw = window.innerWidth || document.documentElement.clientWidth;
+            const vh = window.innerHeight || document.documentElement.clientHeight;
+            const root = document.body || document.documentElement;
+            const runs = [];
+            if (!root) return JSON.stringify(runs);
+
+            const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT);
+            let total = 0;
+            let node;
+            while ((node = walker.nextNode())) {{
+                if (runs.length >= MAX_RUNS || total >= MAX_TOTAL_CHARS) break;
+                const parent = node.parentElement;
+                if (!parent) continue;
+                const text = (node.textContent || "").replace(/\s+/g, " ").trim();
+                if (!text) continue;
+                const style = window.getComputedStyle(parent);
+                if (style.display === "none" || style.visibility === "hidden") continue;
+
+                const range = document.createRange();
+                range.selectNodeContents(node);
+                let left = Infinity, top = Infinity, right = -Infinity, bottom = -Infinity;
+                for (const rect of range.getClientRects()) {{
+                    if (rect.width <= 0 || rect.height <= 0) continue;
+                    if (rect.right <= 0 || rect.bottom <= 0 || rect.left >= vw || rect.top >= vh) continue;
+                    left = Math.min(left, rect.left);
+                    top = Math.min(top, rect.top);
+                    right = Math.max(right, rect.right);
+                    bottom = Math.max(bottom, rect.bottom);
+                }}
+                if (!Number.isFinite(left)) continue;
+
+                const clipped = text.slice(0, Math.min(MAX_RUN_CHARS, MAX_TOTAL_CHARS - total));
+                total += clipped.length;
+                runs.push({{
+                    id: ensureId(parent),
+                    text: clipped,
+                    x: left,
+                    y: top,
+                    width: right - left,
+                    height: bottom - top
+                }});
+            }}
+            return JSON.stringify(runs);
+        }})()"#,
+        max_runs = TEXT_RUNS_MAX_RUNS,
+        max_run_chars = TEXT_RUNS_MAX_RUN_CHARS,
+        max_total_chars = TEXT_RUNS_MAX_TOTAL_CHARS,
+    )
+}
+
+fn contrast_script(node_ids_json: &str) -> String {
+    format!(
+        r#"(function() {{
+            const TARGET_IDS = new Set({node_ids});
+            const MAX_NODES = {max_nodes};
+            const MAX_TEXT = {max_text};
+            const NEXT_ID_KEY = "__buckleyNextId";
+
+            function ensureId(el) {{
+                if (!el) return 0;
+                if (!el.__buckleyId) {{
+                    const next = (window[NEXT_ID_KEY] || 1);
+                    el.__buckleyId = next;
+                    window[NEXT_ID_KEY] = next + 1;
+                }}
+                return el.__buckleyId;
+            }}
+
+            function parseColor(value) {{
+                const match = /rgba?\(([^)]+)\)/.exec(value || "");
+                if (!match) return null;
+                const parts = match[1].split(/[\s,\/]+/).filter(Boolean).map(Number);
+                if (parts.length < 3 || parts.some(Number.isNaN)) return null;
+                return [parts[0], parts[1], parts[2], parts.length > 3 ? parts[3] : 1];
+            }}
+
+            function effectiveBackground(el) {{
+                for (let node = el; node; node = node.parentElement) {{
+                    const bg = parseColor(window.getComputedStyle(node).backgroundColor);
+                    if (bg && bg[3] > 0) return bg;
+                }}
+                return [255, 255, 255, 1];
+            }}
+
+            function ownText(el) {{
+                let text = "";
+                for (const child of el.childNodes) {{
+                    if (child.nodeType === Node.TEXT_NODE) text += child.textContent || "";
+                }}
+                return text.replace(/\s+/g, " ").trim();
+            }}
+
+            function isVisible(el, style) {{
+                if (style.display === "none" || style.visibility === "hidden") return false;
+                const rect = el.getBoundingClientRect();
+                return rect.width > 0 && rect.height > 0;
+            }}
+
+            const results = [];
+            for (const el of document.querySelectorAll("*")) {{
+                if (results.length >= MAX_NODES) break;
+                if (TARGET_IDS.size > 0) {{
+                    if (!el.__buckleyId || !TARGET_IDS.has(el.__buckleyId)) continue;
+                }}
+                const style = window.getComputedStyle(el);
+                const text = ownText(el);
+                if (TARGET_IDS.size === 0 && (!text || !isVisible(el, style))) continue;
+                const color = parseColor(style.color);
+                if (!color) continue;
+                results.push({{
+                    id: ensureId(el),
+                    text: text.slice(0, MAX_TEXT),
+                    color: color,
+                    background: effectiveBackground(el),
+                    font_size: parseFloat(style.fontSize) || 16,
+                    font_weight: parseInt(style.fontWeight, 10) || 400
+                }});
+            }}
+            return JSON.stringify(results);
+        }})()"#,
+        node_ids = node_ids_json,
+        max_nodes = CONTRAST_MAX_NODES,
+        max_text = CONTRAST_MAX_TEXT_CHARS,
+    )
+}
+
+fn capture_frame(state: &ServoState, clip: Option<&pb::Rect>) -> Option<pb::Frame> {
+    let image = match clip {
+        Some(clip) => read_image_rect(state, clip.x, clip.y, clip.width, clip.height)?,
+        None => read_viewport_image(state)?,
+    };
+    let (image, scaled) = fit_frame(image, max_frame_pixels());
+    let data = encode_png(&image)?;
+    let mut frame = frame_message(state.state_version, &image, data);
+    frame.scaled = scaled;
+    Some(frame)
+}
+
+/// Downscale `image` to at most `max_pixels`, returning whether it was
+/// resized. The readback has already allocated the full-size buffer; this
+/// bounds the PNG encode and the message, at the cost of detail.
+fn fit_frame(image: image::RgbaImage, max_pixels: u64) -> (image::RgbaImage, bool) {
+    match scaled_frame_size(image.width(), image.height(), max_pixels) {
+        Some((width, height)) => (
+            image::imageops::resize(&image, width, height, image::imageops::FilterType::Triangle),
+            true,
+        ),
+        None => (image, false),
+    }
+}
+
+/// Capture a frame for the stream, sending only the changed region when delta
+/// encoding is enabled and `base` holds a previous frame of the same size.
+fn capture_stream_frame(state: &mut ServoState, base: &mut DeltaBase) -> Option<pb::Frame> {
+    let (image, scaled) = fit_frame(read_viewport_image(state)?, max_frame_pixels());
+    if !state.frame_delta_encoding {
+        let data = encode_png(&image)?;
+        let mut frame = frame_message(state.state_version, &image, data);
+        frame.scaled = scaled;
+        return Some(frame);
+    }
+
+    let previous = base.frame.take();
+    let delta = previous
+        .as_ref()
+        .filter(|prev| prev.dimensions() == image.dimensions())
+        .map(|prev| dirty_rect(prev.as_raw(), image.as_raw(), image.width(), image.height()));
+
+    let mut frame = match delta {
+        Some(None) => {
+            // Nothing changed: an empty delta tells the client to keep its frame.
+            let mut frame = frame_message(state.state_version, &image, Vec::new());
+            frame.is_delta = true;
+            frame.width = 0;
+            frame.height = 0;
+            frame
+        }
+        Some(Some(rect)) if !rect.exceeds_ratio(image.width(), image.height()) => {
+            let region = image::imageops::crop_imm(&image, rect.x, rect.y, rect.width, rect.height)
+                .to_image();
+            let data = encode_png(&region)?;
+            let mut frame = frame_message(state.state_version, &region, data);
+            frame.is_delta = true;
+            frame.dirty_x = rect.x;
+            frame.dirty_y = rect.y;
+            frame
+        }
+        _ => {
+            let data = encode_png(&image)?;
+            frame_message(state.state_version, &image, data)
+        }
+    };
+
+    frame.scaled = scaled;
+    base.frame = Some(image);
+    Some(frame)
+}
+
+fn read_viewport_image(state: &ServoState) -> Option<image::RgbaImage> {
+    read_image_rect(
+        state,
+        0,
+        0,
+        state.viewport_width as i32,
+        state.viewport_height as i32,
     )
 }
 
-fn capture_frame(state: &ServoState) -> Option<pb::Frame> {
+fn read_image_rect(
+    state: &ServoState,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> Option<image::RgbaImage> {
     use servo::{DeviceIntPoint, DeviceIntRect, DeviceIntSize};
 
     let rect = DeviceIntRect::from_origin_and_size(
-        DeviceIntPoint::new(0, 0),
-        DeviceIntSize::new(state.viewport_width as i32, state.viewport_height as i32),
+        DeviceIntPoint::new(x, y),
+        DeviceIntSize::new(width, height),
     );
 
-    let image = state.rendering_context.read_to_image(rect)?;
+    state.rendering_context.read_to_image(rect)
+}
 
-    // Encode as PNG using image crate
+fn encode_png(image: &image::RgbaImage) -> Option<Vec<u8>> {
     use std::io::Cursor;
     let mut png_data = Vec::new();
     let mut cursor = Cursor::new(&mut png_data);
 
     image.write_to(&mut cursor, image::ImageFormat::Png).ok()?;
+    Some(png_data)
+}
 
-    Some(pb::Frame {
-        state_version: state.state_version,
+fn frame_message(state_version: u64, image: &image::RgbaImage, data: Vec<u8>) -> pb::Frame {
+    pb::Frame {
+        state_version,
         format: pb::FrameFormat::Png as i32,
-        data: png_data,
+        data,
         width: image.width(),
         height: image.height(),
         timestamp: Some(timestamp_now()),
+        presentation_timestamp: None,
+        capture_duration_us: 0,
+        is_delta: false,
+        dirty_x: 0,
+        dirty_y: 0,
+        scaled: false,
+    }
+}
+
+/// Bounding rectangle of the pixels that differ between two frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DirtyRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    changed_pixels: u64,
+}
+
+impl DirtyRect {
+    fn exceeds_ratio(&self, width: u32, height: u32) -> bool {
+        let total = u64::from(width) * u64::from(height);
+        total == 0 || self.changed_pixels as f64 > total as f64 * FRAME_DELTA_MAX_CHANGED_RATIO
+    }
+}
+
+/// Compare two RGBA buffers of `width` x `height` pixels and return the
+/// bounding rectangle of changed pixels, or `None` when they are identical.
+fn dirty_rect(previous: &[u8], current: &[u8], width: u32, height: u32) -> Option<DirtyRect> {
+    let stride = width as usize * 4;
+    if previous.len() != current.len() || current.len() < stride * height as usize {
+        return Some(DirtyRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+            changed_pixels: u64::from(width) * u64::from(height),
+        });
+    }
+
+    let mut min_x = u32::MAX;
+    let mut min_y = u32::MAX;
+    let mut max_x = 0;
+    let mut max_y = 0;
+    let mut changed_pixels = 0u64;
+    for y in 0..height {
+        let row_start = y as usize * stride;
+        let prev_row = &previous[row_start..row_start + stride];
+        let cur_row = &current[row_start..row_start + stride];
+        if prev_row == cur_row {
+            continue;
+        }
+        for x in 0..width {
+            let offset = x as usize * 4;
+            if prev_row[offset..offset + 4] != cur_row[offset..offset + 4] {
+                changed_pixels += 1;
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+    }
+
+    if changed_pixels == 0 {
+        return None;
+    }
+    Some(DirtyRect {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+        changed_pixels,
+    })
+}
+
+fn timestamp_now() -> prost_types::Timestamp {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    prost_types::Timestamp {
+        seconds: now.as_secs() as i64,
+        nanos: now.subsec_nanos() as i32,
+    }
+}
+
+fn handle_clipboard_get(state: &mut ServoState) -> Result<pb::ClipboardData, EngineError> {
+    ensure_clipboard_read_allowed(state)?;
+    let text = match state.host_clipboard.as_mut() {
+        Some(clipboard) => match clipboard.get_text() {
+            Ok(text) => text,
+            // Empty, or holding an image or other non-text content.
+            Err(arboard::Error::ContentNotAvailable) => String::new(),
+            Err(err) => return Err(host_clipboard_error(err)),
+        },
+        None => state.clipboard_text.clone(),
+    };
+    let bytes = text.len();
+    if bytes > state.clipboard_max_bytes {
+        return Err(EngineError::new(
+            "clipboard_limit",
+            "clipboard exceeds size limit",
+        ));
+    }
+    Ok(pb::ClipboardData {
+        text,
+        bytes: bytes as u32,
+        mode: state.clipboard_mode as i32,
+    })
+}
+
+fn handle_clipboard_set(
+    state: &mut ServoState,
+    text: &str,
+) -> Result<pb::ClipboardData, EngineError> {
+    ensure_clipboard_write_allowed(state)?;
+    let bytes = text.len();
+    if bytes > state.clipboard_max_bytes {
+        return Err(EngineError::new(
+            "clipboard_limit",
+            "clipboard exceeds size limit",
+        ));
+    }
+    match state.host_clipboard.as_mut() {
+        Some(clipboard) => clipboard.set_text(text).map_err(host_clipboard_error)?,
+        None => state.clipboard_text = text.to_string(),
+    }
+    Ok(pb::ClipboardData {
+        text: String::new(),
+        bytes: bytes as u32,
+        mode: state.clipboard_mode as i32,
     })
 }
 
-fn timestamp_now() -> prost_types::Timestamp {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default();
-    prost_types::Timestamp {
-        seconds: now.as_secs() as i64,
-        nanos: now.subsec_nanos() as i32,
+fn clipboard_source(state: &ServoState) -> &'static str {
+    if state.host_clipboard.is_some() {
+        "host"
+    } else {
+        "virtual"
     }
 }
 
+fn host_clipboard_error(err: arboard::Error) -> EngineError {
+    EngineError::new("unavailable", format!("host clipboard: {err}"))
+}
+
 fn ensure_clipboard_read_allowed(state: &ServoState) -> Result<(), EngineError> {
     if !state.clipboard_allow_read {
         return Err(EngineError::new("clipboard_denied", "clipboard read not allowed"));
@@ -1381,12 +5465,17 @@ fn ensure_clipboard_read_allowed(state: &ServoState) -> Result<(), EngineError>
     if state.clipboard_read_allowlist.is_empty() {
         return Ok(());
     }
-    let parsed = Url::parse(&state.current_url)
-        .map_err(|_| EngineError::new("clipboard_denied", "clipboard read requires allowed domain"))?;
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| EngineError::new("clipboard_denied", "clipboard read requires allowed domain"))?;
-    if allowlist_allows(host, parsed.port_or_known_default(), &state.clipboard_read_allowlist) {
+    let parsed = Url::parse(&state.current_url).map_err(|_| {
+        EngineError::new("clipboard_denied", "clipboard read requires allowed domain")
+    })?;
+    let host = parsed.host_str().ok_or_else(|| {
+        EngineError::new("clipboard_denied", "clipboard read requires allowed domain")
+    })?;
+    if host_list_matches(
+        host,
+        parsed.port_or_known_default(),
+        &state.clipboard_read_allowlist,
+    ) {
         Ok(())
     } else {
         Err(EngineError::new(
@@ -1411,6 +5500,121 @@ fn clipboard_mode_label(mode: pb::ClipboardMode) -> &'static str {
     }
 }
 
+fn action_type_label(action_type: pb::ActionType) -> &'static str {
+    match action_type {
+        pb::ActionType::Click => "click",
+        pb::ActionType::Type => "type",
+        pb::ActionType::Scroll => "scroll",
+        pb::ActionType::Hover => "hover",
+        pb::ActionType::Drag => "drag",
+        pb::ActionType::Tab => "tab",
+        pb::ActionType::Key => "key",
+        pb::ActionType::Focus => "focus",
+        pb::ActionType::ClipboardRead => "clipboard_read",
+        pb::ActionType::ClipboardWrite => "clipboard_write",
+        pb::ActionType::SelectOption => "select_option",
+        pb::ActionType::SetChecked => "set_checked",
+        pb::ActionType::TypeInto => "type_into",
+        pb::ActionType::Unspecified => "unspecified",
+    }
+}
+
+fn click_verb(button: pb::MouseButton, count: u32) -> String {
+    let repeat = match count {
+        1 => "",
+        2 => "double-",
+        _ => "triple-",
+    };
+    let button = match button {
+        pb::MouseButton::Right => "right-",
+        pb::MouseButton::Middle => "middle-",
+        pb::MouseButton::Left | pb::MouseButton::Unspecified => "",
+    };
+    format!("{repeat}{button}clicked")
+}
+
+fn scroll_unit_label(unit: i32) -> &'static str {
+    match pb::ScrollUnit::try_from(unit).unwrap_or(pb::ScrollUnit::Unspecified) {
+        pb::ScrollUnit::Pixels => "pixels",
+        pb::ScrollUnit::Lines => "lines",
+        pb::ScrollUnit::Unspecified => "units",
+    }
+}
+
+fn action_point_summary(action: &str, node_id: u64, point: Option<&pb::Point>) -> String {
+    if let Some(point) = point {
+        format!("{action} node {node_id} at {},{}", point.x, point.y)
+    } else {
+        format!("{action} node {node_id}")
+    }
+}
+
+/// Metadata for an input action's effect: where it landed, plus the same
+/// `scrolled_into_view` and `action_timeout_ms` flags the stub reports.
+fn action_metadata(
+    action_type: pb::ActionType,
+    action: &pb::Action,
+    point: Option<&pb::Point>,
+) -> Option<Struct> {
+    let mut fields = BTreeMap::new();
+    let mut insert = |key: &str, kind: value::Kind| {
+        fields.insert(key.to_string(), Value { kind: Some(kind) });
+    };
+    if let Some(point) = point {
+        insert("x", value::Kind::NumberValue(point.x as f64));
+        insert("y", value::Kind::NumberValue(point.y as f64));
+    }
+    if action
+        .target
+        .as_ref()
+        .is_some_and(|target| scrolls_into_view(action_type, target))
+    {
+        insert("scrolled_into_view", value::Kind::BoolValue(true));
+    }
+    if let Some(timeout) = action_timeout(action) {
+        insert(
+            "action_timeout_ms",
+            value::Kind::NumberValue(timeout.as_millis() as f64),
+        );
+    }
+    (!fields.is_empty()).then_some(Struct { fields })
+}
+
+fn checked_summary(checked: bool, changed: bool) -> String {
+    let state = if checked { "checked" } else { "unchecked" };
+    if changed {
+        state.to_string()
+    } else {
+        format!("already {state}")
+    }
+}
+
+fn checked_metadata(checked: bool, changed: bool) -> Option<Struct> {
+    let mut fields = BTreeMap::new();
+    for (key, flag) in [("checked", checked), ("changed", changed)] {
+        fields.insert(
+            key.to_string(),
+            Value {
+                kind: Some(value::Kind::BoolValue(flag)),
+            },
+        );
+    }
+    Some(Struct { fields })
+}
+
+fn selection_metadata(value: &str, label: &str) -> Option<Struct> {
+    let mut fields = BTreeMap::new();
+    for (key, text) in [("value", value), ("label", label)] {
+        fields.insert(
+            key.to_string(),
+            Value {
+                kind: Some(value::Kind::StringValue(text.to_string())),
+            },
+        );
+    }
+    Some(Struct { fields })
+}
+
 fn clipboard_metadata(text: Option<&str>, bytes: usize, mode: &str, source: &str) -> Option<Struct> {
     let mut fields = BTreeMap::new();
     fields.insert(
@@ -1473,35 +5677,694 @@ mod tests {
             frame_rate: 12,
             network_allowlist: Vec::new(),
             clipboard: None,
+            frame_delta_encoding: false,
+            navigation_timeout_ms: 0,
+            js_budget_ms: 0,
+            request_headers: HashMap::new(),
+            network_denylist: Vec::new(),
+            post_load_settle_ms: None,
+            downloads_enabled: true,
+            max_download_bytes: 0,
+            // Fixtures are loaded from disk.
+            allowed_schemes: vec!["file".to_string(), "about".to_string()],
         }
     }
 
+    #[test]
+    fn test_poll_interval_backs_off() {
+        let schedule: Vec<u128> = (0..6)
+            .map(|attempt| poll_interval(attempt).as_millis())
+            .collect();
+        assert_eq!(schedule, vec![1, 2, 4, 8, 10, 10]);
+        assert_eq!(
+            poll_interval(u32::MAX),
+            Duration::from_millis(SPIN_POLL_INTERVAL_MS)
+        );
+    }
+
     #[test]
     fn test_navigate_and_dom_snapshot() {
         let mut engine = ServoEngine::new(&test_config()).expect("engine init");
         let url = fixture_url("simple.html");
-        let obs = engine.navigate(&url).expect("navigate");
+        let obs = engine.navigate(&url, 0).expect("navigate");
         assert!(obs.url.contains("simple.html"));
         assert!(obs.title.contains("Test Page"));
 
-        let obs = engine
-            .observe(&pb::ObserveOptions {
-                include_frame: false,
-                include_dom_snapshot: true,
-                include_accessibility: false,
-                include_hit_test: false,
-            })
-            .expect("observe");
-        assert!(!obs.dom_snapshot.is_empty());
-        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
-        assert_eq!(dom["title"], "Test Page");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_frame: false,
+                include_dom_snapshot: true,
+                include_accessibility: false,
+                include_hit_test: false,
+                include_text_runs: false,
+                include_engine_info: false,
+                clip_node_id: 0,
+                clip_rect: None,
+                style_node_ids: Vec::new(),
+                style_properties: Vec::new(),
+                include_timing: false,
+                max_hit_regions: 0,
+                hit_test_selectors: Vec::new(),
+                include_console: false,
+                include_page_errors: false,
+                until_network_idle_ms: 0,
+                include_html: false,
+                max_html_bytes: 0,
+                include_metadata: false,
+                dom_flat: false,
+            })
+            .expect("observe");
+        assert!(!obs.dom_snapshot.is_empty());
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(dom["title"], "Test Page");
+    }
+
+    #[test]
+    fn test_flat_dom_parent_ids_match_tree() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let observe = |engine: &mut ServoEngine, dom_flat: bool| -> Value {
+            let obs = engine
+                .observe(&pb::ObserveOptions {
+                    include_dom_snapshot: true,
+                    dom_flat,
+                    ..Default::default()
+                })
+                .expect("observe");
+            serde_json::from_slice(&obs.dom_snapshot).expect("dom json")
+        };
+        let tree = observe(&mut engine, false);
+        let flat = observe(&mut engine, true);
+        assert!(flat.get("root").is_none());
+
+        // Collect (node_id, parent_id) from the nested tree; text nodes have
+        // no node_id and aren't listed in the flat form.
+        fn walk(node: &Value, parent: u64, edges: &mut Vec<(u64, u64)>) {
+            let Some(id) = node["node_id"].as_u64() else {
+                return;
+            };
+            edges.push((id, parent));
+            for child in node["children"].as_array().into_iter().flatten() {
+                walk(child, id, edges);
+            }
+        }
+        let mut expected = Vec::new();
+        walk(&tree["root"], 0, &mut expected);
+
+        let nodes = flat["nodes"].as_array().expect("nodes");
+        let edges: Vec<(u64, u64)> = nodes
+            .iter()
+            .map(|node| {
+                (
+                    node["node_id"].as_u64().unwrap(),
+                    node["parent_id"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(edges, expected);
+        assert_eq!(nodes[0]["tag"], "html");
+        assert_eq!(nodes[0]["parent_id"], 0);
+        let tags: Vec<&str> = nodes
+            .iter()
+            .filter_map(|node| node["tag"].as_str())
+            .collect();
+        assert!(tags.contains(&"button"));
+        let heading = nodes.iter().find(|node| node["tag"] == "h1").expect("h1");
+        assert_eq!(heading["text"], "Hello Servo");
+    }
+
+    #[test]
+    fn test_extract_text_from_fixture() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let text = engine.extract_text(64 * 1024).expect("extract text");
+        assert!(text.text.contains("Hello Servo"));
+        assert!(text.text.contains("simple test page"));
+        assert!(!text.truncated);
+        assert!(text.word_count >= 10);
+    }
+
+    #[test]
+    fn test_virtual_clipboard_round_trip() {
+        let mut config = test_config();
+        config.clipboard = Some(pb::ClipboardPolicy {
+            mode: pb::ClipboardMode::Virtual as i32,
+            allow_read: true,
+            allow_write: true,
+            max_bytes: 16,
+            read_allowlist: Vec::new(),
+        });
+        let mut engine = ServoEngine::new(&config).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+
+        let write = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::ClipboardWrite as i32,
+                text: "from action".to_string(),
+                ..Default::default()
+            })
+            .expect("clipboard write");
+        assert_eq!(write.effects[0].kind, "clipboard_write");
+        assert_eq!(
+            engine.clipboard_get().expect("clipboard get").text,
+            "from action"
+        );
+
+        engine.clipboard_set("from request").expect("clipboard set");
+        let read = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::ClipboardRead as i32,
+                ..Default::default()
+            })
+            .expect("clipboard read");
+        let metadata = read.effects[0].metadata.as_ref().expect("metadata");
+        let text = metadata
+            .fields
+            .get("text")
+            .and_then(|value| value.kind.as_ref());
+        assert_eq!(
+            text,
+            Some(&value::Kind::StringValue("from request".to_string()))
+        );
+        let source = metadata
+            .fields
+            .get("source")
+            .and_then(|value| value.kind.as_ref());
+        assert_eq!(
+            source,
+            Some(&value::Kind::StringValue("virtual".to_string()))
+        );
+
+        let err = match engine.clipboard_set("seventeen bytes!!") {
+            Ok(_) => panic!("expected clipboard limit"),
+            Err(err) => err,
+        };
+        assert_eq!(err.code, "clipboard_limit");
+    }
+
+    #[test]
+    fn test_computed_styles_for_node() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("styled.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        let node_id = find_node_by_attr_id(&dom["root"], "banner").expect("banner in snapshot");
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                style_node_ids: vec![node_id, 999_999],
+                style_properties: vec!["background-color".to_string(), "display".to_string()],
+                ..Default::default()
+            })
+            .expect("observe styles");
+        let styles = obs.computed_styles.expect("computed styles");
+        assert_eq!(styles.nodes.len(), 1, "unknown ids are left out");
+        let banner = &styles.nodes[&node_id].properties;
+        assert_eq!(banner["background-color"], "rgb(255, 0, 0)");
+        assert_eq!(banner["display"], "flex");
+    }
+
+    #[test]
+    fn test_fill_form_sets_values_and_fires_change() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("form.html"), 0)
+            .expect("navigate");
+        let field = |selector: &str, value: &str| pb::FormField {
+            selector: selector.to_string(),
+            value: value.to_string(),
+        };
+        let response = engine
+            .fill_form(&[
+                field("#first", "Ada"),
+                field("#last", "Lovelace"),
+                field("#missing", "x"),
+            ])
+            .expect("fill form");
+        let filled: Vec<bool> = response
+            .results
+            .iter()
+            .map(|result| result.filled)
+            .collect();
+        assert_eq!(filled, vec![true, true, false]);
+        assert_eq!(response.results[2].error, "not_found");
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(
+            find_attr_by_id(&dom["root"], "first", "title").as_deref(),
+            Some("Ada")
+        );
+        assert_eq!(
+            find_attr_by_id(&dom["root"], "last", "title").as_deref(),
+            Some("Lovelace")
+        );
+    }
+
+    #[test]
+    fn test_type_into_selector() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("type_into.html"), 0)
+            .expect("navigate");
+        let type_into = |selector: &str, text: &str| pb::Action {
+            r#type: pb::ActionType::TypeInto as i32,
+            target: Some(pb::ActionTarget {
+                selector: selector.to_string(),
+                ..Default::default()
+            }),
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let before = engine.state_version();
+        let result = engine
+            .act(&type_into("input[name=email]", "hello"))
+            .expect("type into");
+        assert_eq!(result.state_version, before + 1);
+        assert!(result.observation.expect("observation").focused_node_id != 0);
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(
+            find_attr_by_id(&dom["root"], "email", "title").as_deref(),
+            Some("5")
+        );
+
+        for selector in ["#agree", "#send"] {
+            let err = engine
+                .act(&type_into(selector, "x"))
+                .expect_err("not editable");
+            assert_eq!(err.code, "invalid_target");
+        }
+        let err = engine
+            .act(&type_into("#missing", "x"))
+            .expect_err("no match");
+        assert_eq!(err.code, "invalid_target");
+    }
+
+    #[test]
+    fn test_chord_events_wrap_key_in_modifiers() {
+        let enter = Key::Named(NamedKey::Enter);
+        let events = chord_events(
+            enter.clone(),
+            Code::Enter,
+            Modifiers::CONTROL | Modifiers::SHIFT,
+        );
+        let control = Key::Named(NamedKey::Control);
+        let shift = Key::Named(NamedKey::Shift);
+        let both = Modifiers::CONTROL | Modifiers::SHIFT;
+        assert_eq!(
+            events,
+            vec![
+                (
+                    control.clone(),
+                    Code::ControlLeft,
+                    Modifiers::CONTROL,
+                    KeyState::Down
+                ),
+                (shift.clone(), Code::ShiftLeft, both, KeyState::Down),
+                (enter.clone(), Code::Enter, both, KeyState::Down),
+                (enter.clone(), Code::Enter, both, KeyState::Up),
+                (shift, Code::ShiftLeft, Modifiers::CONTROL, KeyState::Up),
+                (control, Code::ControlLeft, Modifiers::empty(), KeyState::Up),
+            ]
+        );
+
+        let plain = chord_events(enter.clone(), Code::Enter, Modifiers::empty());
+        assert_eq!(plain.len(), 2);
+    }
+
+    #[test]
+    fn test_shifted_chars_hold_shift() {
+        let shift = Key::Named(NamedKey::Shift);
+        for (ch, code) in [('A', Code::KeyA), ('!', Code::Digit1)] {
+            let key = Key::Character(ch.to_string());
+            assert_eq!(
+                char_events(ch, Modifiers::empty()),
+                vec![
+                    (
+                        shift.clone(),
+                        Code::ShiftLeft,
+                        Modifiers::SHIFT,
+                        KeyState::Down
+                    ),
+                    (key.clone(), code, Modifiers::SHIFT, KeyState::Down),
+                    (key, code, Modifiers::SHIFT, KeyState::Up),
+                    (
+                        shift.clone(),
+                        Code::ShiftLeft,
+                        Modifiers::empty(),
+                        KeyState::Up
+                    ),
+                ]
+            );
+        }
+        let lower = char_events('a', Modifiers::empty());
+        assert_eq!(
+            lower,
+            vec![
+                (
+                    Key::Character("a".to_string()),
+                    Code::KeyA,
+                    Modifiers::empty(),
+                    KeyState::Down
+                ),
+                (
+                    Key::Character("a".to_string()),
+                    Code::KeyA,
+                    Modifiers::empty(),
+                    KeyState::Up
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_text_is_composed() {
+        assert_eq!(
+            text_inputs("ok \u{4f60}\u{597d}!\u{1f44d}\u{1f3fd}"),
+            vec![
+                TextInput::Keys("ok "),
+                TextInput::Composed("\u{4f60}\u{597d}"),
+                TextInput::Keys("!"),
+                TextInput::Composed("\u{1f44d}\u{1f3fd}"),
+            ]
+        );
+        assert_eq!(text_inputs("plain"), vec![TextInput::Keys("plain")]);
+        assert!(text_inputs("").is_empty());
+        let states: Vec<CompositionState> = composition_events("\u{e9}")
+            .iter()
+            .map(|event| event.state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                CompositionState::Start,
+                CompositionState::Update,
+                CompositionState::End
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_into_commits_composed_text() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("type_into.html"), 0)
+            .expect("navigate");
+        let text = "ok \u{1f44d}\u{1f3fd} \u{4f60}\u{597d}";
+        let action = pb::Action {
+            r#type: pb::ActionType::TypeInto as i32,
+            target: Some(pb::ActionTarget {
+                selector: "#note".to_string(),
+                ..Default::default()
+            }),
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let result = engine.act(&action).expect("type into");
+        assert_eq!(result.effects[0].summary, "typed 7 chars into #note");
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(
+            find_attr_by_id(&dom["root"], "note", "title").as_deref(),
+            Some(text)
+        );
+    }
+
+    #[test]
+    fn test_ctrl_enter_chord_fires() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("chord.html"), 0)
+            .expect("navigate");
+        engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Key as i32,
+                key: "Enter".to_string(),
+                modifiers: vec![pb::KeyModifier::Ctrl as i32],
+                ..Default::default()
+            })
+            .expect("key");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        assert_eq!(
+            find_attr_by_id(&dom["root"], "log", "title").as_deref(),
+            Some("Control+ctrl,Enter+ctrl")
+        );
+    }
+
+    #[test]
+    fn test_query_element_reads_link_href() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("query.html"), 0)
+            .expect("navigate");
+        let query = |selector: &str, properties: &[&str]| pb::QueryElementRequest {
+            selector: selector.to_string(),
+            properties: properties.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let link = engine
+            .query_element(&query(
+                "a#docs",
+                &["href", "data-section", "textContent", "nothing"],
+            ))
+            .expect("query link");
+        assert!(link.found);
+        // The property is the resolved URL, not the attribute as written.
+        assert!(link.properties["href"].ends_with("/fixtures/docs/intro.html"));
+        assert_eq!(link.properties["data-section"], "guide");
+        assert_eq!(link.properties["textContent"], "Docs");
+        assert!(!link.properties.contains_key("nothing"));
+
+        let checkbox = engine
+            .query_element(&query("#agree", &["checked"]))
+            .expect("query checkbox");
+        assert_eq!(checkbox.properties["checked"], "true");
+
+        let missing = engine
+            .query_element(&query("#missing", &["href"]))
+            .expect("query missing");
+        assert!(!missing.found);
+        let err = engine
+            .query_element(&query("a[", &["href"]))
+            .expect_err("invalid selector");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    fn find_attr_by_id(node: &Value, id: &str, attr: &str) -> Option<String> {
+        if node["attrs"]["id"] == id {
+            return node["attrs"][attr].as_str().map(str::to_string);
+        }
+        node["children"]
+            .as_array()?
+            .iter()
+            .find_map(|child| find_attr_by_id(child, id, attr))
+    }
+
+    #[test]
+    fn test_navigation_timing_after_load() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_timing: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let timing = obs.navigation_timing.expect("navigation timing");
+        for ms in [
+            timing.dns_ms,
+            timing.connect_ms,
+            timing.ttfb_ms,
+            timing.dom_content_loaded_ms,
+            timing.load_ms,
+        ] {
+            assert!(
+                ms.is_finite() && ms >= 0.0,
+                "timing {timing:?} should be non-negative"
+            );
+        }
+        assert!(timing.load_ms >= timing.dom_content_loaded_ms);
+
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .expect("observe");
+        assert!(obs.navigation_timing.is_none());
+    }
+
+    #[test]
+    fn test_navigation_reports_paint_state() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let obs = engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        assert!(obs.paint_epoch > 0, "the loaded page should have painted");
+
+        let again = engine
+            .observe(&pb::ObserveOptions::default())
+            .expect("observe");
+        assert!(again.render_complete);
+        assert!(again.paint_epoch >= obs.paint_epoch);
+    }
+
+    #[test]
+    fn test_missing_local_file_is_load_failed() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let url = fixture_url("does-not-exist.html");
+        let err = match engine.navigate(&url, 0) {
+            Ok(_) => panic!("navigation to a missing file should fail"),
+            Err(err) => err,
+        };
+        assert_eq!(err.code, "load_failed");
+    }
+
+    #[test]
+    fn test_file_scheme_needs_opt_in() {
+        let config = pb::SessionConfig {
+            allowed_schemes: Vec::new(),
+            ..test_config()
+        };
+        let mut engine = ServoEngine::new(&config).expect("engine init");
+        let url = fixture_url("simple.html");
+        let err = engine.navigate(&url, 0).expect_err("file scheme blocked");
+        assert_eq!(err.code, "invalid_request");
+        assert_eq!(err.message, "blocked scheme: file");
+        let err = engine.open_tab(&url).expect_err("file scheme blocked");
+        assert_eq!(err.message, "blocked scheme: file");
+    }
+
+    #[test]
+    fn test_check_http_status() {
+        let http = Url::parse("http://example.com/").expect("url");
+        let file = Url::parse("file:///tmp/page.html").expect("url");
+        assert!(check_http_status(&file, 0).is_ok());
+        assert!(check_http_status(&http, 200).is_ok());
+        assert!(check_http_status(&http, 304).is_ok());
+        let err = check_http_status(&http, 0).expect_err("no response");
+        assert_eq!(err.code, "load_failed");
+        for status in [404, 500] {
+            let err = check_http_status(&http, status).expect_err("error status");
+            assert_eq!(err.code, "load_failed");
+            assert!(err.message.contains(&status.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_user_agent_override_reaches_page() {
+        let config = pb::SessionConfig {
+            user_agent: "BuckleyTest/1.0".to_string(),
+            ..test_config()
+        };
+        let mut engine = ServoEngine::new(&config).expect("engine init");
+        let url = fixture_url("navigator.html");
+        let _ = engine.navigate(&url, 0).expect("navigate");
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom = String::from_utf8(obs.dom_snapshot).expect("dom utf8");
+        assert!(dom.contains("BuckleyTest/1.0"), "{dom}");
+    }
+
+    #[test]
+    fn test_request_headers_are_unsupported() {
+        let mut config = test_config();
+        config
+            .request_headers
+            .insert("X-Trace-Id".to_string(), "1".to_string());
+        let err = check_supported_config(&config).expect_err("request_headers");
+        assert_eq!(err.code, "unsupported");
+        assert!(ServoEngine::new(&config).is_err());
+        assert!(check_supported_config(&test_config()).is_ok());
+
+        // Forbidden names are invalid before they are unsupported.
+        config
+            .request_headers
+            .insert("Host".to_string(), "evil.test".to_string());
+        let err = ServoEngine::new(&config).err().expect("host header");
+        assert_eq!(err.code, "invalid_request");
+    }
+
+    #[test]
+    fn test_session_request_headers_add_accept_language() {
+        let mut config = pb::SessionConfig {
+            locale: "fr-FR".to_string(),
+            ..test_config()
+        };
+        let headers = session_request_headers(&config);
+        assert_eq!(
+            headers.get("Accept-Language").map(String::as_str),
+            Some("fr-FR")
+        );
+
+        config
+            .request_headers
+            .insert("accept-language".to_string(), "de".to_string());
+        let headers = session_request_headers(&config);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(
+            headers.get("accept-language").map(String::as_str),
+            Some("de")
+        );
+    }
+
+    #[test]
+    fn test_locale_and_timezone_are_accepted() {
+        let config = pb::SessionConfig {
+            locale: "fr-FR".to_string(),
+            timezone: "Europe/Paris".to_string(),
+            ..test_config()
+        };
+        assert!(check_supported_config(&config).is_ok());
     }
 
     #[test]
     fn test_accessibility_and_hit_test() {
         let mut engine = ServoEngine::new(&test_config()).expect("engine init");
         let url = fixture_url("simple.html");
-        let _ = engine.navigate(&url).expect("navigate");
+        let _ = engine.navigate(&url, 0).expect("navigate");
 
         let obs = engine
             .observe(&pb::ObserveOptions {
@@ -1509,6 +6372,22 @@ mod tests {
                 include_dom_snapshot: false,
                 include_accessibility: true,
                 include_hit_test: true,
+                include_text_runs: false,
+                include_engine_info: false,
+                clip_node_id: 0,
+                clip_rect: None,
+                style_node_ids: Vec::new(),
+                style_properties: Vec::new(),
+                include_timing: false,
+                max_hit_regions: 0,
+                hit_test_selectors: Vec::new(),
+                include_console: false,
+                include_page_errors: false,
+                until_network_idle_ms: 0,
+                include_html: false,
+                max_html_bytes: 0,
+                include_metadata: false,
+                dom_flat: false,
             })
             .expect("observe");
 
@@ -1522,11 +6401,130 @@ mod tests {
         assert!(!hit_test.regions.is_empty());
     }
 
+    #[test]
+    fn test_stop_signal_cuts_navigate_short() {
+        // A server that accepts the connection and never answers keeps the
+        // load going until the navigation timeout.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/", listener.local_addr().expect("addr"));
+        let config = pb::SessionConfig {
+            allowed_schemes: vec!["http".to_string(), "about".to_string()],
+            ..test_config()
+        };
+        let mut engine = ServoEngine::new(&config).expect("engine init");
+        let signal = engine.stop_signal();
+        let raiser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(500));
+            signal.raise();
+        });
+
+        let started = Instant::now();
+        engine.navigate(&url, 20_000).expect("stopped navigate");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        raiser.join().expect("raiser");
+        drop(listener);
+    }
+
+    #[test]
+    fn test_batched_observe_fills_every_part() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let only = |dom: bool, accessibility: bool, hit_test: bool| pb::ObserveOptions {
+            include_dom_snapshot: dom,
+            include_accessibility: accessibility,
+            include_hit_test: hit_test,
+            ..Default::default()
+        };
+        let obs = engine.observe(&only(true, true, true)).expect("observe");
+        assert!(!obs.dom_snapshot.is_empty());
+        assert!(!obs.accessibility_tree.is_empty());
+        assert!(obs.hit_test.is_some());
+    }
+
+    #[test]
+    fn test_hit_test_region_cap() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("links.html"), 0)
+            .expect("navigate");
+        let mut regions = |max_hit_regions, hit_test_selectors: Vec<String>| {
+            engine
+                .observe(&pb::ObserveOptions {
+                    include_hit_test: true,
+                    max_hit_regions,
+                    hit_test_selectors,
+                    ..Default::default()
+                })
+                .expect("observe")
+                .hit_test
+                .expect("hit test map")
+                .regions
+                .len()
+        };
+        let capped = regions(10, Vec::new());
+        assert_eq!(capped, 10);
+        let raised = regions(500, Vec::new());
+        assert!(raised > capped, "{raised} regions");
+
+        // The cards have no built-in interactive selector; an unparseable
+        // extra selector is skipped rather than failing the map.
+        let with_cards = regions(500, vec![".card".to_string(), "[[".to_string()]);
+        assert!(with_cards > raised, "{with_cards} regions");
+    }
+
+    #[test]
+    fn test_hit_regions_describe_elements() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("links.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_hit_test: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let regions = obs.hit_test.expect("hit test map").regions;
+        let link = regions
+            .iter()
+            .find(|region| region.tag == "a")
+            .expect("link region");
+        assert_eq!(link.role, "link");
+        assert!(link.label.starts_with("Link "), "{}", link.label);
+        assert!(regions.iter().any(|region| region.role == "document"));
+    }
+
+    #[test]
+    fn test_hit_test_marks_occluded_regions() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("overlay.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_hit_test: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let regions = obs.hit_test.expect("hit test map").regions;
+        let button = |label: &str| {
+            regions
+                .iter()
+                .find(|region| region.tag == "button" && region.label == label)
+                .unwrap_or_else(|| panic!("{label} region"))
+        };
+        assert!(button("Covered").occluded);
+        assert!(!button("Open").occluded);
+        assert!(!button("Dismiss").occluded);
+    }
+
     #[test]
     fn test_actions_increment_state_version() {
         let mut engine = ServoEngine::new(&test_config()).expect("engine init");
         let url = fixture_url("simple.html");
-        let _ = engine.navigate(&url).expect("navigate");
+        let _ = engine.navigate(&url, 0).expect("navigate");
 
         let initial = engine.state_version();
         let result = engine
@@ -1536,13 +6534,624 @@ mod tests {
                 target: Some(pb::ActionTarget {
                     node_id: 0,
                     point: Some(pb::Point { x: 10, y: 10 }),
+                    selector: String::new(),
+                    selector_type: 0,
+                    scroll_into_view: None,
                 }),
                 text: "".to_string(),
                 key: "".to_string(),
                 scroll: None,
                 modifiers: vec![],
+                drag_to: None,
+                drag_steps: 0,
+                button: 0,
+                click_count: 0,
+                repeat: 0,
+                action_timeout_ms: 0,
             })
             .expect("click");
         assert!(result.state_version > initial);
     }
+
+    fn find_node_by_attr_id(node: &Value, id: &str) -> Option<u64> {
+        if node["attrs"]["id"] == id {
+            return node["node_id"].as_u64();
+        }
+        node["children"]
+            .as_array()?
+            .iter()
+            .find_map(|child| find_node_by_attr_id(child, id))
+    }
+
+    #[test]
+    fn test_select_option_by_visible_text() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("select.html"), 0)
+            .expect("navigate");
+        let result = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::SelectOption as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "#size".to_string(),
+                    ..Default::default()
+                }),
+                text: "Medium".to_string(),
+                ..Default::default()
+            })
+            .expect("select option");
+        assert_eq!(result.effects[0].kind, "select_option");
+        let metadata = result.effects[0].metadata.as_ref().expect("metadata");
+        assert_eq!(
+            metadata.fields["value"].kind,
+            Some(prost_types::value::Kind::StringValue("m".to_string()))
+        );
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert_eq!(obs.title, "size m");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        let select = find_element_by_attr_id(&dom["root"], "size").expect("select in snapshot");
+        assert_eq!(select["attrs"]["value"], "m");
+
+        let err = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::SelectOption as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "#size".to_string(),
+                    ..Default::default()
+                }),
+                text: "Huge".to_string(),
+                ..Default::default()
+            })
+            .expect_err("unknown option");
+        assert_eq!(err.code, "invalid_target");
+    }
+
+    #[test]
+    fn test_set_checked_is_idempotent() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("checkbox.html"), 0)
+            .expect("navigate");
+        let mut set_checked = |checked: bool| {
+            let result = engine
+                .act(&pb::Action {
+                    r#type: pb::ActionType::SetChecked as i32,
+                    target: Some(pb::ActionTarget {
+                        selector: "#subscribe".to_string(),
+                        ..Default::default()
+                    }),
+                    checked,
+                    ..Default::default()
+                })
+                .expect("set checked");
+            let metadata = result.effects[0].metadata.clone().expect("metadata");
+            let flag = |key: &str| metadata.fields[key].kind.clone();
+            (flag("checked"), flag("changed"))
+        };
+        let on = Some(prost_types::value::Kind::BoolValue(true));
+        let off = Some(prost_types::value::Kind::BoolValue(false));
+        assert_eq!(set_checked(true), (on.clone(), on.clone()));
+        assert_eq!(set_checked(true), (on.clone(), off.clone()));
+        assert_eq!(set_checked(false), (off.clone(), on));
+    }
+
+    #[test]
+    fn test_set_file_input_uploads_file() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("upload.html"), 0)
+            .expect("navigate");
+        let mut upload = pb::SetFileInputRequest {
+            selector: "#name".to_string(),
+            filename: "notes.txt".to_string(),
+            data: b"hello".to_vec(),
+        };
+        let err = engine
+            .set_file_input(&upload)
+            .expect_err("not a file input");
+        assert_eq!(err.code, "invalid_target");
+
+        upload.selector = "#attachment".to_string();
+        let resp = engine.set_file_input(&upload).expect("upload");
+        assert_eq!(resp.bytes, 5);
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .expect("observe");
+        assert_eq!(obs.title, "notes.txt 5");
+    }
+
+    #[test]
+    fn test_link_click_reports_navigation_effect() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let start = fixture_url("nav_link.html");
+        engine.navigate(&start, 0).expect("navigate");
+        let result = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Click as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "#next".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .expect("click");
+        assert_eq!(result.effects[0].kind, "click");
+        assert!(
+            result.effects[0].summary.starts_with("clicked node"),
+            "{}",
+            result.effects[0].summary
+        );
+        let navigation = result
+            .effects
+            .iter()
+            .find(|effect| effect.kind == "navigation")
+            .expect("navigation effect");
+        let fields = &navigation.metadata.as_ref().expect("metadata").fields;
+        let text = |key: &str| match fields.get(key).and_then(|value| value.kind.as_ref()) {
+            Some(value::Kind::StringValue(text)) => text.clone(),
+            other => panic!("unexpected {key}: {other:?}"),
+        };
+        assert_eq!(text("from"), start);
+        assert_eq!(text("to"), fixture_url("simple.html"));
+        assert!(result.caused_navigation);
+        let observation = result.observation.expect("observation");
+        assert_eq!(observation.url, fixture_url("simple.html"));
+        assert_eq!(observation.title, "Test Page");
+
+        // Hovering never navigates, so only the action's own effect is reported.
+        let hover = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Hover as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "h1".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .expect("hover");
+        assert_eq!(hover.effects.len(), 1);
+        assert_eq!(hover.effects[0].kind, "hover");
+        assert!(!hover.caused_navigation);
+    }
+
+    #[test]
+    fn test_console_messages_are_captured() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("console.html"), 0)
+            .expect("navigate");
+        let console = pb::ObserveOptions {
+            include_console: true,
+            ..Default::default()
+        };
+        let obs = engine.observe(&console).expect("observe");
+        let messages: Vec<(&str, &str)> = obs
+            .console_messages
+            .iter()
+            .map(|message| (message.level.as_str(), message.text.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("log", r#"hello 42 {"ok":true}"#),
+                ("warn", "careful"),
+                ("error", "Error: broken"),
+            ]
+        );
+        assert!(obs.console_messages[0].timestamp.is_some());
+
+        let obs = engine.observe(&console).expect("observe");
+        assert!(obs.console_messages.is_empty());
+    }
+
+    #[test]
+    fn test_page_errors_are_reported() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let url = fixture_url("throws.html");
+        engine.navigate(&url, 0).expect("navigate");
+        let errors = pb::ObserveOptions {
+            include_page_errors: true,
+            ..Default::default()
+        };
+        let obs = engine.observe(&errors).expect("observe");
+        // The broken image's load error isn't a script error.
+        assert_eq!(obs.page_errors.len(), 1, "{:?}", obs.page_errors);
+        let error = &obs.page_errors[0];
+        assert!(
+            error.message.contains("config is not defined"),
+            "{}",
+            error.message
+        );
+        assert_eq!(error.source, url);
+        assert_eq!(error.line, 8);
+
+        let obs = engine.observe(&errors).expect("observe");
+        assert!(obs.page_errors.is_empty());
+    }
+
+    #[test]
+    fn test_observe_returns_html() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_html: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert!(obs.html.starts_with("<html"), "{}", obs.html);
+        assert!(obs.html.contains(r#"id="name""#), "{}", obs.html);
+        assert!(!obs.html_truncated);
+
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_html: true,
+                max_html_bytes: 16,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert_eq!(obs.html.len(), 16);
+        assert!(obs.html_truncated);
+    }
+
+    #[test]
+    fn test_observe_batches_dom_accessibility_and_hit_test() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                include_accessibility: true,
+                include_hit_test: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert!(!obs.dom_snapshot.is_empty());
+        assert!(!obs.accessibility_tree.is_empty());
+        let hit_test = obs.hit_test.expect("hit test");
+        assert!(!hit_test.regions.is_empty());
+    }
+
+    #[test]
+    fn test_observe_page_metadata() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let url = fixture_url("metadata.html");
+        engine.navigate(&url, 0).expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_metadata: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let metadata = obs.page_metadata.expect("page metadata");
+        let base = Url::parse(&url).expect("fixture url");
+        assert_eq!(
+            metadata.favicon_url,
+            base.join("icons/favicon.png").expect("join").as_str()
+        );
+        assert_eq!(metadata.description, "A page that describes itself.");
+        assert_eq!(metadata.og_title, "Metadata Fixture");
+        assert_eq!(metadata.og_image, "https://example.com/card.png");
+        assert_eq!(metadata.canonical_url, "https://example.com/metadata");
+
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_metadata: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert_eq!(obs.page_metadata, Some(pb::PageMetadata::default()));
+    }
+
+    #[test]
+    fn test_storage_survives_reload() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let url = fixture_url("storage.html");
+        let obs = engine.navigate(&url, 0).expect("navigate");
+        assert_eq!(obs.title, "Empty");
+
+        let entry = |key: &str, value: &str| pb::StorageEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        let set = engine
+            .set_storage(
+                pb::StorageScope::Local,
+                &[entry("greeting", "Hello"), entry("count", "2")],
+            )
+            .expect("set storage");
+        assert_eq!(set, 2);
+        engine
+            .set_storage(pb::StorageScope::Session, &[entry("tab", "one")])
+            .expect("set session storage");
+
+        let obs = engine.navigate(&url, 0).expect("reload");
+        assert_eq!(obs.title, "Hello");
+        let local = engine
+            .get_storage(pb::StorageScope::Local, &[])
+            .expect("get storage");
+        assert_eq!(
+            local.entries,
+            vec![entry("count", "2"), entry("greeting", "Hello")]
+        );
+        let session = engine
+            .get_storage(
+                pb::StorageScope::Session,
+                &["tab".to_string(), "missing".to_string()],
+            )
+            .expect("get session storage");
+        assert_eq!(session.entries, vec![entry("tab", "one")]);
+
+        assert_eq!(
+            engine
+                .clear_storage(pb::StorageScope::Local)
+                .expect("clear"),
+            2
+        );
+        let local = engine
+            .get_storage(pb::StorageScope::Local, &[])
+            .expect("get storage");
+        assert!(local.entries.is_empty());
+    }
+
+    #[test]
+    fn test_clear_browsing_data_reports_partial_kinds() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("storage.html"), 0)
+            .expect("navigate");
+        engine
+            .set_storage(
+                pb::StorageScope::Local,
+                &[pb::StorageEntry {
+                    key: "token".to_string(),
+                    value: "abc".to_string(),
+                }],
+            )
+            .expect("set storage");
+
+        let response = engine
+            .clear_browsing_data(&pb::ClearBrowsingDataRequest {
+                cookies: true,
+                storage: true,
+                cache: true,
+                clipboard: true,
+            })
+            .expect("clear");
+        assert_eq!(response.cleared, vec!["cookies", "storage", "clipboard"]);
+        assert_eq!(response.partial, vec!["cookies", "storage"]);
+        let local = engine
+            .get_storage(pb::StorageScope::Local, &[])
+            .expect("get storage");
+        assert!(local.entries.is_empty());
+    }
+
+    #[test]
+    fn test_observe_waits_for_network_idle() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let obs = engine
+            .navigate(&fixture_url("delayed_fetch.html"), 0)
+            .expect("navigate");
+        assert_eq!(obs.title, "Waiting");
+
+        // The fetch starts 300ms after load, inside the quiet period, so the
+        // wait covers it.
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                until_network_idle_ms: 500,
+                ..Default::default()
+            })
+            .expect("observe");
+        assert!(obs.network_idle);
+        assert_eq!(obs.title, "Fetched");
+
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .expect("observe");
+        assert!(!obs.network_idle);
+    }
+
+    #[test]
+    fn test_download_link_is_captured() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("download.html"), 0)
+            .expect("navigate");
+        let result = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Click as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "#export".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .expect("click");
+        let kinds: Vec<&str> = result
+            .effects
+            .iter()
+            .map(|effect| effect.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["click", "download"]);
+
+        let list = engine.list_downloads().expect("list");
+        assert_eq!(list.downloads.len(), 1);
+        assert_eq!(list.downloads[0].filename, "people.csv");
+        let download = engine.get_download(&list.downloads[0].id).expect("get");
+        assert_eq!(download.data, b"id,name\n1,Ada\n");
+    }
+
+    fn find_element_by_attr_id<'a>(node: &'a Value, id: &str) -> Option<&'a Value> {
+        if node["attrs"]["id"] == id {
+            return Some(node);
+        }
+        node["children"]
+            .as_array()?
+            .iter()
+            .find_map(|child| find_element_by_attr_id(child, id))
+    }
+
+    #[test]
+    fn test_stale_click_retries_with_policy() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        engine
+            .navigate(&fixture_url("simple.html"), 0)
+            .expect("navigate");
+        let stale = engine.state_version();
+        let click = |on_stale: pb::StalePolicy| pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            expected_state_version: stale,
+            target: Some(pb::ActionTarget {
+                selector: "#name".to_string(),
+                ..Default::default()
+            }),
+            on_stale: on_stale as i32,
+            ..Default::default()
+        };
+        engine
+            .act(&click(pb::StalePolicy::Fail))
+            .ok()
+            .expect("current click");
+
+        let err = engine
+            .act(&click(pb::StalePolicy::Fail))
+            .expect_err("stale click fails");
+        assert_eq!(err.code, "stale_state");
+        let result = engine
+            .act(&click(pb::StalePolicy::Retry))
+            .ok()
+            .expect("stale click retries");
+        assert!(result.state_version > stale + 1);
+        assert_eq!(result.state_version, engine.state_version());
+    }
+
+    #[test]
+    fn test_click_scrolls_offscreen_node_into_view() {
+        let mut engine = ServoEngine::new(&test_config()).expect("engine init");
+        let url = fixture_url("offscreen.html");
+        let _ = engine.navigate(&url, 0).expect("navigate");
+        let obs = engine
+            .observe(&pb::ObserveOptions {
+                include_dom_snapshot: true,
+                ..Default::default()
+            })
+            .expect("observe");
+        let dom: Value = serde_json::from_slice(&obs.dom_snapshot).expect("dom json");
+        let node_id = find_node_by_attr_id(&dom["root"], "far").expect("far button in snapshot");
+
+        let result = engine
+            .act(&pb::Action {
+                r#type: pb::ActionType::Click as i32,
+                target: Some(pb::ActionTarget {
+                    node_id,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .expect("click");
+        let point = result.resolved_point.expect("resolved point");
+        assert!(
+            point.y >= 0 && point.y < 600,
+            "point {point:?} should be in the viewport"
+        );
+
+        let obs = engine
+            .observe(&pb::ObserveOptions::default())
+            .expect("observe");
+        assert_eq!(obs.title, "clicked");
+    }
+
+    #[test]
+    fn test_js_budget_exceeded_on_slow_script() {
+        let config = pb::SessionConfig {
+            js_budget_ms: 100,
+            ..test_config()
+        };
+        let mut engine = ServoEngine::new(&config).expect("engine init");
+        let url = fixture_url("slow_script.html");
+        let _ = engine.navigate(&url, 0).expect("navigate");
+
+        let err = match engine.wait_for("body", Duration::from_millis(500)) {
+            Ok(_) => panic!("evaluation should exceed the budget"),
+            Err(err) => err,
+        };
+        assert_eq!(err.code, "script_budget_exceeded");
+    }
+
+    #[test]
+    fn test_drag_path_interpolates_to_end() {
+        let path = drag_path((0, 0), (100, 50), 4);
+        assert_eq!(
+            path,
+            vec![(20, 10), (40, 20), (60, 30), (80, 40), (100, 50)]
+        );
+        assert_eq!(drag_path((5, 5), (5, 5), 0).last(), Some(&(5, 5)));
+    }
+
+    #[test]
+    fn test_gl_backend_falls_back_to_software() {
+        let size = PhysicalSize::new(320, 240);
+        assert!(gl_rendering_context(size).is_err());
+        assert!(create_rendering_context(RenderBackend::Gl, size).is_ok());
+    }
+
+    #[test]
+    fn test_oversized_frame_is_downscaled() {
+        let image = image::RgbaImage::from_pixel(4000, 2000, image::Rgba([10, 20, 30, 255]));
+        let (image, scaled) = fit_frame(image, 2_000_000);
+        assert!(scaled);
+        assert_eq!(image.dimensions(), (2000, 1000));
+        assert_eq!(image.get_pixel(1999, 999), &image::Rgba([10, 20, 30, 255]));
+
+        let (image, scaled) = fit_frame(image, 2_000_000);
+        assert!(!scaled);
+        assert_eq!(image.dimensions(), (2000, 1000));
+    }
+
+    #[test]
+    fn test_dirty_rect_bounds_changed_pixels() {
+        let width = 4;
+        let height = 3;
+        let previous = vec![0u8; (width * height * 4) as usize];
+        assert_eq!(dirty_rect(&previous, &previous, width, height), None);
+
+        let mut current = previous.clone();
+        for (x, y) in [(1u32, 0u32), (2, 1)] {
+            let offset = ((y * width + x) * 4) as usize;
+            current[offset] = 255;
+        }
+        let rect = dirty_rect(&previous, &current, width, height).expect("dirty rect");
+        assert_eq!(
+            rect,
+            DirtyRect {
+                x: 1,
+                y: 0,
+                width: 2,
+                height: 2,
+                changed_pixels: 2,
+            }
+        );
+        assert!(!rect.exceeds_ratio(width, height));
+
+        let full = vec![255u8; previous.len()];
+        let rect = dirty_rect(&previous, &full, width, height).expect("dirty rect");
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (0, 0, width, height)
+        );
+        assert!(rect.exceeds_ratio(width, height));
+    }
 }
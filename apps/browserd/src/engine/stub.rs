@@ -1,17 +1,60 @@
+use super::contrast::{contrast_result, Rgba};
+use super::downloads::{download_effect, DownloadStore};
+use super::pdf::single_page_pdf;
+use super::profiles::apply_device_profile;
+use super::{
+    action_timeout, check_type_length, click_params, compiled_features, content_hash,
+    default_clipboard_policy, drag_steps, dry_run_result, grapheme_count, has_shift,
+    host_list_matches, is_stale, key_repeat, max_frame_pixels, max_type_chars, navigation_timeout,
+    page_text, parse_action_type, pdf_page_size, post_load_settle, resolve_clip_rect,
+    retries_on_stale, scaled_frame_size, scrolls_into_view, session_clipboard_policy,
+    session_viewport, set_content_hashes, set_observation_html, stale_state_error, storage_origin,
+    style_query, validate_request_headers, BrowserEngine, DeltaBase, EngineError, HitTestQuery,
+    NetworkIdleWait, StopSignal, DEFAULT_NAVIGATION_TIMEOUT_MS, MAX_CONSOLE_MESSAGES,
+    MAX_CONSOLE_MESSAGE_CHARS,
+};
 use crate::proto as pb;
-use super::{allowlist_allows, BrowserEngine, EngineError};
+use crate::url_policy::check_scheme;
 use prost_types::{value, Struct, Value};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use url::Url;
 
-const DEFAULT_VIEWPORT_WIDTH: u32 = 1280;
-const DEFAULT_VIEWPORT_HEIGHT: u32 = 720;
 const DEFAULT_FRAME_RATE: u32 = 12;
 const ROOT_NODE_ID: u64 = 1;
 const BUTTON_NODE_ID: u64 = 2;
 const INPUT_NODE_ID: u64 = 3;
+/// A download link that is only reachable by selector. Clicking it
+/// downloads `STUB_DOWNLOAD_NAME` so download handling can be tested without
+/// a browser.
+const DOWNLOAD_NODE_ID: u64 = 4;
+const STUB_DOWNLOAD_NAME: &str = "stub-download.txt";
+const STUB_DOWNLOAD_DATA: &[u8] = b"stub download\n";
+/// Focusable nodes in document (Tab) order.
+const FOCUS_ORDER: [u64; 2] = [BUTTON_NODE_ID, INPUT_NODE_ID];
 const DEFAULT_CLIPBOARD_MAX_BYTES: usize = 64 * 1024;
+/// Selector the stub treats as always present so `wait_for` can be tested
+/// without a real DOM. Every other selector times out immediately.
+const STUB_READY_SELECTOR: &str = "[data-buckley-ready]";
+/// Simulated page load time; navigations with a shorter timeout fail with
+/// `load_timeout` so the timeout path can be exercised without a browser.
+const STUB_LOAD_DURATION: Duration = Duration::from_millis(20);
+/// Every stub navigation "succeeds" with this status.
+const STUB_HTTP_STATUS: u32 = 200;
+/// Id of the tab a session starts on. The stub also opens a second, blank tab
+/// so tab requests can be tested without a browser.
+const FIRST_TAB_ID: u64 = 1;
+const STUB_TAB_TITLE: &str = "Stub Page";
+/// Placeholder page metadata; the stub has no head to read it from.
+const STUB_FAVICON_URL: &str = "about:blank#stub-favicon";
+const STUB_DESCRIPTION: &str = "Stub page for tests";
+
+/// A background tab. The active tab's URL and title live on the engine itself.
+struct StubTab {
+    id: u64,
+    url: String,
+    title: String,
+}
 
 pub struct StubEngine {
     url: String,
@@ -34,38 +77,72 @@ pub struct StubEngine {
     clipboard_max_bytes: usize,
     clipboard_read_allowlist: Vec<String>,
     clipboard_text: String,
+    cookies: Vec<pb::Cookie>,
+    // Web storage keyed by scope and origin. Session storage is shared across
+    // tabs here, unlike a real browser.
+    storage: BTreeMap<(pb::StorageScope, String), BTreeMap<String, String>>,
+    navigation_timeout: Duration,
+    post_load_settle: Duration,
+    // The stub makes no network requests; it only reports how many custom
+    // headers the session configured.
+    request_header_count: usize,
+    // Never watched, since stub loads finish inside `navigate`; `stop`
+    // clears it as the servo engine does.
+    stop: StopSignal,
+    // Running total of FillForm fields; the stub has no real inputs.
+    fields_filled: usize,
+    // Option chosen by the last select_option action; any node accepts one.
+    selected_option: String,
+    // Nodes a set_checked action left checked; every node is checkable.
+    checked_nodes: BTreeSet<u64>,
+    // Name and size of the last SetFileInput upload; nothing is written.
+    uploaded_file: Option<(String, usize)>,
+    downloads: DownloadStore,
+    allowed_schemes: Vec<String>,
+    http_status: u32,
+    tabs: Vec<StubTab>,
+    active_tab: usize,
+    next_tab_id: u64,
+    // Synthetic console output, one line per navigation.
+    console: VecDeque<pb::ConsoleMessage>,
 }
 
 impl StubEngine {
     pub fn new(config: &pb::SessionConfig) -> Result<Self, EngineError> {
+        Self::with_clipboard_default(config, default_clipboard_policy())
+    }
+
+    /// Like `new`, with `clipboard_default` standing in for the daemon-wide
+    /// clipboard policy when the config has none.
+    pub(crate) fn with_clipboard_default(
+        config: &pb::SessionConfig,
+        clipboard_default: &pb::ClipboardPolicy,
+    ) -> Result<Self, EngineError> {
         if config.session_id.trim().is_empty() {
             return Err(EngineError::new("invalid_request", "session_id is required"));
         }
-        let mut clipboard_mode = pb::ClipboardMode::Virtual;
-        let mut clipboard_allow_read = false;
-        let mut clipboard_allow_write = true;
-        let mut clipboard_max_bytes = DEFAULT_CLIPBOARD_MAX_BYTES;
-        let mut clipboard_read_allowlist = Vec::new();
-        if let Some(policy) = config.clipboard.as_ref() {
-            let mode = pb::ClipboardMode::try_from(policy.mode).unwrap_or(pb::ClipboardMode::Unspecified);
-            if mode != pb::ClipboardMode::Unspecified {
-                clipboard_mode = mode;
-            }
-            clipboard_allow_read = policy.allow_read;
-            clipboard_allow_write = policy.allow_write;
-            if policy.max_bytes > 0 {
-                clipboard_max_bytes = policy.max_bytes as usize;
-            }
-            if !policy.read_allowlist.is_empty() {
-                clipboard_read_allowlist = policy.read_allowlist.clone();
-            }
-        }
+        validate_request_headers(&config.request_headers)?;
+        let config = &apply_device_profile(config)?;
+        let policy = session_clipboard_policy(config, clipboard_default);
+        let clipboard_mode = match pb::ClipboardMode::try_from(policy.mode) {
+            Ok(pb::ClipboardMode::Unspecified) | Err(_) => pb::ClipboardMode::Virtual,
+            Ok(mode) => mode,
+        };
+        let clipboard_allow_read = policy.allow_read;
+        let clipboard_allow_write = policy.allow_write;
+        let clipboard_max_bytes = if policy.max_bytes > 0 {
+            policy.max_bytes as usize
+        } else {
+            DEFAULT_CLIPBOARD_MAX_BYTES
+        };
+        let clipboard_read_allowlist = policy.read_allowlist;
+        let viewport = session_viewport(config);
         let mut engine = StubEngine {
             url: "about:blank".to_string(),
-            title: "Stub Page".to_string(),
+            title: STUB_TAB_TITLE.to_string(),
             state_version: 1,
-            viewport_width: DEFAULT_VIEWPORT_WIDTH,
-            viewport_height: DEFAULT_VIEWPORT_HEIGHT,
+            viewport_width: viewport.width,
+            viewport_height: viewport.height,
             frame_rate: DEFAULT_FRAME_RATE,
             last_action: "idle".to_string(),
             last_action_detail: "ready".to_string(),
@@ -81,20 +158,44 @@ impl StubEngine {
             clipboard_max_bytes,
             clipboard_read_allowlist,
             clipboard_text: String::new(),
+            cookies: Vec::new(),
+            storage: BTreeMap::new(),
+            navigation_timeout: navigation_timeout(
+                config.navigation_timeout_ms,
+                Duration::from_millis(u64::from(DEFAULT_NAVIGATION_TIMEOUT_MS)),
+            ),
+            post_load_settle: post_load_settle(config),
+            request_header_count: config.request_headers.len(),
+            stop: StopSignal::default(),
+            fields_filled: 0,
+            selected_option: String::new(),
+            checked_nodes: BTreeSet::new(),
+            uploaded_file: None,
+            downloads: DownloadStore::new(config),
+            allowed_schemes: config.allowed_schemes.clone(),
+            http_status: 0,
+            tabs: vec![
+                StubTab {
+                    id: FIRST_TAB_ID,
+                    url: String::new(),
+                    title: String::new(),
+                },
+                StubTab {
+                    id: FIRST_TAB_ID + 1,
+                    url: "about:blank".to_string(),
+                    title: STUB_TAB_TITLE.to_string(),
+                },
+            ],
+            active_tab: 0,
+            next_tab_id: FIRST_TAB_ID + 2,
+            console: VecDeque::new(),
         };
-        if let Some(viewport) = &config.viewport {
-            if viewport.width > 0 {
-                engine.viewport_width = viewport.width;
-            }
-            if viewport.height > 0 {
-                engine.viewport_height = viewport.height;
-            }
-        }
         if config.frame_rate > 0 {
             engine.frame_rate = config.frame_rate;
         }
         if !config.initial_url.is_empty() {
             engine.url = config.initial_url.clone();
+            engine.http_status = STUB_HTTP_STATUS;
         }
         Ok(engine)
     }
@@ -103,42 +204,165 @@ impl StubEngine {
         self.state_version = self.state_version.saturating_add(1);
     }
 
-    fn build_observation(
+    fn tab_index(&self, tab_id: u64) -> Result<usize, EngineError> {
+        self.tabs
+            .iter()
+            .position(|tab| tab.id == tab_id)
+            .ok_or_else(|| EngineError::new("tab_not_found", format!("no tab with id {tab_id}")))
+    }
+
+    fn tab_info(&self, index: usize) -> pb::Tab {
+        let tab = &self.tabs[index];
+        let active = index == self.active_tab;
+        pb::Tab {
+            tab_id: tab.id,
+            url: if active {
+                self.url.clone()
+            } else {
+                tab.url.clone()
+            },
+            title: if active {
+                self.title.clone()
+            } else {
+                tab.title.clone()
+            },
+            active,
+        }
+    }
+
+    /// Key of the storage area `scope` names for the current page.
+    fn storage_area(
         &self,
-        include_dom: bool,
-        include_accessibility: bool,
-        include_frame: bool,
-        include_hit_test: bool,
-    ) -> pb::Observation {
-        let dom = if include_dom {
+        scope: pb::StorageScope,
+    ) -> Result<(pb::StorageScope, String), EngineError> {
+        let scope = match scope {
+            pb::StorageScope::Session => pb::StorageScope::Session,
+            _ => pb::StorageScope::Local,
+        };
+        Ok((scope, storage_origin(&self.url)?))
+    }
+
+    /// Park the active tab's page and load the page of the tab at `index`.
+    fn activate_tab(&mut self, index: usize) {
+        let current = &mut self.tabs[self.active_tab];
+        current.url = std::mem::take(&mut self.url);
+        current.title = std::mem::take(&mut self.title);
+        let next = &mut self.tabs[index];
+        self.url = std::mem::take(&mut next.url);
+        self.title = std::mem::take(&mut next.title);
+        self.active_tab = index;
+        self.scroll_x = 0;
+        self.scroll_y = 0;
+        self.bump_state();
+    }
+
+    fn build_observation(&self, opts: &pb::ObserveOptions) -> pb::Observation {
+        let dom = if opts.include_dom_snapshot && opts.dom_flat {
+            self.dom_flat_json().into_bytes()
+        } else if opts.include_dom_snapshot {
             self.dom_snapshot_json().into_bytes()
         } else {
             Vec::new()
         };
-        let a11y = if include_accessibility {
+        let a11y = if opts.include_accessibility {
             self.accessibility_snapshot_json().into_bytes()
         } else {
             Vec::new()
         };
-        pb::Observation {
+        let mut obs = pb::Observation {
             state_version: self.state_version,
             url: self.url.clone(),
             title: self.title.clone(),
-            frame: if include_frame { Some(self.build_frame()) } else { None },
+            frame: if opts.include_frame {
+                Some(self.build_frame())
+            } else {
+                None
+            },
             dom_snapshot: dom,
             accessibility_tree: a11y,
-            hit_test: if include_hit_test {
-                Some(self.build_hit_test_map())
+            hit_test: if opts.include_hit_test {
+                Some(self.build_hit_test_map(&HitTestQuery::from_observe(opts)))
             } else {
                 None
             },
             timestamp: Some(timestamp_now()),
+            text_runs: if opts.include_text_runs {
+                self.build_text_runs()
+            } else {
+                Vec::new()
+            },
+            engine_info: None,
+            http_status: self.http_status,
+            focused_node_id: self.focused_node,
+            // The stub "paints" synchronously on every state change.
+            render_complete: true,
+            paint_epoch: self.state_version,
+            computed_styles: (!opts.style_node_ids.is_empty())
+                .then(|| self.build_computed_styles(opts)),
+            // Nothing is fetched, so every phase takes no time.
+            navigation_timing: opts.include_timing.then(pb::NavigationTiming::default),
+            // Filled in by `observe`, which can drain the buffer.
+            console_messages: Vec::new(),
+            // Stub pages never throw.
+            page_errors: Vec::new(),
+            // Nothing is fetched, so the network is always idle.
+            network_idle: opts.until_network_idle_ms > 0,
+            html: String::new(),
+            html_truncated: false,
+            page_metadata: opts.include_metadata.then(|| pb::PageMetadata {
+                favicon_url: STUB_FAVICON_URL.to_string(),
+                description: STUB_DESCRIPTION.to_string(),
+                og_title: self.title.clone(),
+                og_image: String::new(),
+                canonical_url: self.url.clone(),
+            }),
+            dom_snapshot_hash: String::new(),
+            accessibility_tree_hash: String::new(),
+        };
+        set_content_hashes(&mut obs);
+        obs
+    }
+
+    fn log_console(&mut self, level: &str, text: String) {
+        if self.console.len() == MAX_CONSOLE_MESSAGES {
+            self.console.pop_front();
         }
+        self.console.push_back(pb::ConsoleMessage {
+            level: level.to_string(),
+            text: text.chars().take(MAX_CONSOLE_MESSAGE_CHARS).collect(),
+            timestamp: Some(timestamp_now()),
+        });
+    }
+
+    /// Fixed styles for the stub's three nodes; other ids are left out.
+    fn build_computed_styles(&self, opts: &pb::ObserveOptions) -> pb::ComputedStyles {
+        let (node_ids, properties) = style_query(opts);
+        let nodes = node_ids
+            .iter()
+            .filter(|id| [ROOT_NODE_ID, BUTTON_NODE_ID, INPUT_NODE_ID].contains(id))
+            .map(|&id| {
+                let properties = properties
+                    .iter()
+                    .map(|name| (name.clone(), stub_style_value(id, name).to_string()))
+                    .collect();
+                (id, pb::NodeStyle { properties })
+            })
+            .collect();
+        pb::ComputedStyles { nodes }
+    }
+
+    fn build_text_runs(&self) -> Vec<pb::TextRun> {
+        let (button_rect, _) = self.control_regions();
+        vec![pb::TextRun {
+            text: "Stub Button".to_string(),
+            bounds: Some(button_rect),
+            node_id: BUTTON_NODE_ID,
+        }]
     }
 
     fn dom_snapshot_json(&self) -> String {
         format!(
-            "{{\"url\":\"{}\",\"title\":\"{}\",\"state_version\":{},\"last_action\":\"{}\",\"last_action_detail\":\"{}\",\"last_text_len\":{},\"last_key\":\"{}\",\"scroll\":{{\"x\":{},\"y\":{}}},\"focused_node\":{},\"hovered_node\":{}}}",
+            "{{\"url\":\"{}\",\"title\":\"{}\",\"state_version\":{},\"last_action\":\"{}\",\"last_action_detail\":\"{}\",\"last_text_len\":{},\"last_key\":\"{}\",\"scroll\":{{\"x\":{},\"y\":{}}},\"focused_node\":{},\"hovered_node\":{},\"request_headers\":{},\"fields_filled\":{},\"selected_option\":\"{}\",\"uploaded_file\":\"{}\",\"uploaded_bytes\":{}}}",
             escape_json_string(&self.url),
             escape_json_string(&self.title),
             self.state_version,
@@ -149,7 +373,36 @@ impl StubEngine {
             self.scroll_x,
             self.scroll_y,
             self.focused_node,
-            self.hovered_node
+            self.hovered_node,
+            self.request_header_count,
+            self.fields_filled,
+            escape_json_string(&self.selected_option),
+            escape_json_string(self.uploaded_file.as_ref().map_or("", |(name, _)| name.as_str())),
+            self.uploaded_file.as_ref().map_or(0, |(_, bytes)| *bytes)
+        )
+    }
+
+    /// The stub page's elements as a flat `dom_flat` node list. The button
+    /// and input hang off the document root, matching the hit-test regions.
+    fn dom_flat_json(&self) -> String {
+        format!(
+            "{{\"url\":\"{}\",\"title\":\"{}\",\"nodes\":[{{\"node_id\":{},\"parent_id\":0,\"tag\":\"html\",\"text\":\"\",\"attrs\":{{}}}},{{\"node_id\":{},\"parent_id\":{},\"tag\":\"button\",\"text\":\"Stub Button\",\"attrs\":{{\"id\":\"stub-button\"}}}},{{\"node_id\":{},\"parent_id\":{},\"tag\":\"input\",\"text\":\"\",\"attrs\":{{\"id\":\"stub-input\",\"aria-label\":\"Stub Input\"}}}}]}}",
+            escape_json_string(&self.url),
+            escape_json_string(&self.title),
+            ROOT_NODE_ID,
+            BUTTON_NODE_ID,
+            ROOT_NODE_ID,
+            INPUT_NODE_ID,
+            ROOT_NODE_ID
+        )
+    }
+
+    /// The stub page as markup: the same button and input the other snapshots
+    /// describe.
+    fn html_document(&self) -> String {
+        format!(
+            "<html><head><title>{}</title></head><body><button id=\"stub-button\">Stub Button</button><input id=\"stub-input\" aria-label=\"Stub Input\"></body></html>",
+            escape_html(&self.title)
         )
     }
 
@@ -164,23 +417,25 @@ impl StubEngine {
         )
     }
 
-    fn dom_diff_json(&self) -> String {
-        let snapshot = self.dom_snapshot_json();
+    fn dom_diff_json(&self, snapshot: &str) -> String {
         format!(
             "{{\"type\":\"replace\",\"state_version\":{},\"snapshot\":{}}}",
             self.state_version, snapshot
         )
     }
 
-    fn accessibility_diff_json(&self) -> String {
-        let snapshot = self.accessibility_snapshot_json();
+    fn accessibility_diff_json(&self, snapshot: &str) -> String {
         format!(
             "{{\"type\":\"replace\",\"state_version\":{},\"snapshot\":{}}}",
             self.state_version, snapshot
         )
     }
 
-    fn build_stream_event(&self, event_type: pb::StreamEventType) -> pb::StreamEvent {
+    fn build_stream_event(
+        &self,
+        event_type: pb::StreamEventType,
+        hit_test: &HitTestQuery,
+    ) -> pb::StreamEvent {
         let mut event = pb::StreamEvent {
             r#type: event_type as i32,
             state_version: self.state_version,
@@ -189,6 +444,10 @@ impl StubEngine {
             accessibility_diff: Vec::new(),
             hit_test: None,
             timestamp: Some(timestamp_now()),
+            stats: None,
+            console_messages: Vec::new(),
+            sequence: 0,
+            content_hash: String::new(),
         };
 
         match event_type {
@@ -196,51 +455,87 @@ impl StubEngine {
                 event.frame = Some(self.build_frame());
             }
             pb::StreamEventType::DomDiff => {
-                event.dom_diff = self.dom_diff_json().into_bytes();
+                let snapshot = self.dom_snapshot_json();
+                event.content_hash = content_hash(snapshot.as_bytes());
+                event.dom_diff = self.dom_diff_json(&snapshot).into_bytes();
             }
             pb::StreamEventType::AccessibilityDiff => {
-                event.accessibility_diff = self.accessibility_diff_json().into_bytes();
+                let snapshot = self.accessibility_snapshot_json();
+                event.content_hash = content_hash(snapshot.as_bytes());
+                event.accessibility_diff = self.accessibility_diff_json(&snapshot).into_bytes();
             }
             pb::StreamEventType::HitTest => {
-                event.hit_test = Some(self.build_hit_test_map());
+                event.hit_test = Some(self.build_hit_test_map(hit_test));
             }
-            pb::StreamEventType::Unspecified => {}
+            // Drained in `stream_event`, which has the buffer mutably.
+            pb::StreamEventType::Console
+            | pb::StreamEventType::Stats
+            | pb::StreamEventType::Heartbeat
+            | pb::StreamEventType::Unspecified => {}
         }
 
         event
     }
 
     fn build_frame(&self) -> pb::Frame {
+        let scaled = scaled_frame_size(
+            self.viewport_width,
+            self.viewport_height,
+            max_frame_pixels(),
+        );
+        let (width, height) = scaled.unwrap_or((self.viewport_width, self.viewport_height));
         pb::Frame {
             state_version: self.state_version,
-            width: self.viewport_width,
-            height: self.viewport_height,
+            width,
+            height,
             format: pb::FrameFormat::Png as i32,
             data: Vec::new(),
             timestamp: Some(timestamp_now()),
+            presentation_timestamp: None,
+            capture_duration_us: 0,
+            is_delta: false,
+            dirty_x: 0,
+            dirty_y: 0,
+            scaled: scaled.is_some(),
         }
     }
 
-    fn build_hit_test_map(&self) -> pb::HitTestMap {
+    /// The stub's extra selectors never match anything, so only the region
+    /// cap applies.
+    fn build_hit_test_map(&self, query: &HitTestQuery) -> pb::HitTestMap {
         let (button_rect, input_rect) = self.control_regions();
         let root_rect = self.viewport_rect();
+        let mut regions = vec![
+            pb::HitRegion {
+                node_id: BUTTON_NODE_ID,
+                bounds: Some(button_rect),
+                tag: "button".to_string(),
+                role: "button".to_string(),
+                label: "Stub Button".to_string(),
+                occluded: false,
+            },
+            pb::HitRegion {
+                node_id: INPUT_NODE_ID,
+                bounds: Some(input_rect),
+                tag: "input".to_string(),
+                role: "textbox".to_string(),
+                label: "Stub Input".to_string(),
+                occluded: false,
+            },
+            pb::HitRegion {
+                node_id: ROOT_NODE_ID,
+                bounds: Some(root_rect),
+                tag: "html".to_string(),
+                role: "document".to_string(),
+                label: String::new(),
+                occluded: false,
+            },
+        ];
+        regions.truncate(query.max_regions);
         pb::HitTestMap {
             width: self.viewport_width,
             height: self.viewport_height,
-            regions: vec![
-                pb::HitRegion {
-                    node_id: BUTTON_NODE_ID,
-                    bounds: Some(button_rect),
-                },
-                pb::HitRegion {
-                    node_id: INPUT_NODE_ID,
-                    bounds: Some(input_rect),
-                },
-                pb::HitRegion {
-                    node_id: ROOT_NODE_ID,
-                    bounds: Some(root_rect),
-                },
-            ],
+            regions,
         }
     }
 
@@ -293,13 +588,42 @@ impl StubEngine {
         }
     }
 
-    fn resolve_target(&self, target: Option<&pb::ActionTarget>) -> (u64, Option<pb::Point>) {
+    /// Centre of a control's region, for targets given by node or selector.
+    fn node_center(&self, node_id: u64) -> Option<pb::Point> {
+        let (button_rect, input_rect) = self.control_regions();
+        let rect = match node_id {
+            BUTTON_NODE_ID => button_rect,
+            INPUT_NODE_ID => input_rect,
+            _ => return None,
+        };
+        Some(pb::Point {
+            x: rect.x + rect.width / 2,
+            y: rect.y + rect.height / 2,
+        })
+    }
+
+    fn check_url(&self, url: &str) -> Result<(), EngineError> {
+        let parsed =
+            Url::parse(url).map_err(|_| EngineError::new("invalid_request", "invalid url"))?;
+        check_scheme(&parsed, &self.allowed_schemes)
+    }
+
+    fn resolve_target(
+        &self,
+        target: Option<&pb::ActionTarget>,
+    ) -> Result<(u64, Option<pb::Point>), EngineError> {
         if let Some(target) = target {
+            if !target.selector.trim().is_empty() {
+                let node_id = stub_selector_node(target).ok_or_else(|| {
+                    EngineError::new("invalid_target", "selector matched no element")
+                })?;
+                return Ok((node_id, None));
+            }
             if target.node_id != 0 {
-                return (target.node_id, None);
+                return Ok((target.node_id, None));
             }
             if let Some(point) = target.point.as_ref() {
-                return (self.hit_test_node_id(point), Some(point.clone()));
+                return Ok((self.hit_test_node_id(point), Some(point.clone())));
             }
         }
         let fallback = if self.focused_node != 0 {
@@ -307,7 +631,7 @@ impl StubEngine {
         } else {
             ROOT_NODE_ID
         };
-        (fallback, None)
+        Ok((fallback, None))
     }
 
     fn ensure_clipboard_read_allowed(&self) -> Result<(), EngineError> {
@@ -317,12 +641,17 @@ impl StubEngine {
         if self.clipboard_read_allowlist.is_empty() {
             return Ok(());
         }
-        let parsed = Url::parse(&self.url)
-            .map_err(|_| EngineError::new("clipboard_denied", "clipboard read requires allowed domain"))?;
-        let host = parsed
-            .host_str()
-            .ok_or_else(|| EngineError::new("clipboard_denied", "clipboard read requires allowed domain"))?;
-        if allowlist_allows(host, parsed.port_or_known_default(), &self.clipboard_read_allowlist) {
+        let parsed = Url::parse(&self.url).map_err(|_| {
+            EngineError::new("clipboard_denied", "clipboard read requires allowed domain")
+        })?;
+        let host = parsed.host_str().ok_or_else(|| {
+            EngineError::new("clipboard_denied", "clipboard read requires allowed domain")
+        })?;
+        if host_list_matches(
+            host,
+            parsed.port_or_known_default(),
+            &self.clipboard_read_allowlist,
+        ) {
             Ok(())
         } else {
             Err(EngineError::new(
@@ -349,40 +678,101 @@ impl BrowserEngine for StubEngine {
         self.frame_rate
     }
 
-    fn navigate(&mut self, url: &str) -> Result<pb::Observation, EngineError> {
+    fn is_alive(&self) -> bool {
+        // Runs on the caller's thread, so there is nothing to die.
+        true
+    }
+
+    fn navigate(&mut self, url: &str, timeout_ms: u32) -> Result<pb::Observation, EngineError> {
         if url.trim().is_empty() {
             return Err(EngineError::new("invalid_request", "url is required"));
         }
+        self.check_url(url)?;
+        let timeout = navigation_timeout(timeout_ms, self.navigation_timeout);
+        if timeout < STUB_LOAD_DURATION {
+            return Err(EngineError::new("load_timeout", "navigation timed out"));
+        }
+        // Loading is simulated, but the settle really waits so callers can
+        // observe its cost.
+        std::thread::sleep(self.post_load_settle.min(timeout - STUB_LOAD_DURATION));
         self.url = url.to_string();
-        self.title = "Stub Page".to_string();
+        self.title = STUB_TAB_TITLE.to_string();
+        self.http_status = STUB_HTTP_STATUS;
         self.last_action = "navigate".to_string();
         self.last_action_detail = format!("navigate to {}", url);
         self.scroll_x = 0;
         self.scroll_y = 0;
+        self.log_console("info", format!("stub page loaded: {url}"));
         self.bump_state();
-        Ok(self.build_observation(true, true, false, false))
+        Ok(self.build_observation(&snapshot_options()))
     }
 
     fn observe(&mut self, opts: &pb::ObserveOptions) -> Result<pb::Observation, EngineError> {
-        Ok(self.build_observation(
-            opts.include_dom_snapshot,
-            opts.include_accessibility,
-            opts.include_frame,
-            opts.include_hit_test,
-        ))
+        let mut observation = self.build_observation(opts);
+        if opts.include_console {
+            observation.console_messages = self.console.drain(..).collect();
+        }
+        if opts.include_html {
+            set_observation_html(&mut observation, self.html_document(), opts);
+        }
+        if let Some(frame) = observation.frame.as_mut() {
+            let regions = self.build_hit_test_map(&HitTestQuery::default()).regions;
+            let clip = resolve_clip_rect(
+                opts,
+                |node_id| {
+                    regions
+                        .into_iter()
+                        .find(|region| region.node_id == node_id)
+                        .and_then(|region| region.bounds)
+                },
+                self.viewport_width,
+                self.viewport_height,
+            )?;
+            if let Some(clip) = clip {
+                frame.width = clip.width as u32;
+                frame.height = clip.height as u32;
+            }
+        }
+        Ok(observation)
+    }
+
+    fn network_idle_wait(&self, _quiet: Duration) -> NetworkIdleWait {
+        Box::new(|| true)
+    }
+
+    fn stop(&mut self) -> Result<pb::Observation, EngineError> {
+        // Stub loads finish inside `navigate`, so there is never one to abort.
+        self.stop.take();
+        self.last_action = "stop".to_string();
+        self.last_action_detail = format!("stop loading {}", self.url);
+        self.bump_state();
+        Ok(self.build_observation(&snapshot_options()))
+    }
+
+    fn stop_signal(&self) -> StopSignal {
+        self.stop.clone()
     }
 
     fn act(&mut self, action: &pb::Action) -> Result<pb::ActionResult, EngineError> {
-        let action_type = pb::ActionType::try_from(action.r#type)
-            .unwrap_or(pb::ActionType::Unspecified);
-        if action_type == pb::ActionType::Unspecified {
-            return Err(EngineError::new("invalid_request", "unsupported action type"));
+        let action_type = parse_action_type(action.r#type)?;
+        check_type_length(action_type, action, max_type_chars())?;
+        // Stub nodes never move, so retrying a stale action is resolving its
+        // target again, which happens below either way.
+        if is_stale(action, self.state_version) && !retries_on_stale(action) {
+            return Err(stale_state_error(
+                action.expected_state_version,
+                self.state_version,
+            ));
         }
 
-        let (mut target_node, target_point) = self.resolve_target(action.target.as_ref());
+        let (mut target_node, target_point) = self.resolve_target(action.target.as_ref())?;
         if action_type == pb::ActionType::Type && target_node == ROOT_NODE_ID {
             target_node = INPUT_NODE_ID;
         }
+        if action.dry_run {
+            let point = target_point.or_else(|| self.node_center(target_node));
+            return Ok(dry_run_result(self.state_version, target_node, point));
+        }
 
         let mut summary = String::new();
         let mut metadata = None;
@@ -390,12 +780,50 @@ impl BrowserEngine for StubEngine {
             pb::ActionType::Click => {
                 self.focused_node = target_node;
                 self.hovered_node = target_node;
-                summary = action_point_summary("clicked", target_node, target_point.as_ref());
+                let (button, count) = click_params(action);
+                summary = action_point_summary(
+                    &click_verb(button, count),
+                    target_node,
+                    target_point.as_ref(),
+                );
             }
             pb::ActionType::Type => {
                 self.focused_node = target_node;
-                self.last_text_len = action.text.chars().count();
-                summary = format!("typed {} chars into node {}", self.last_text_len, target_node);
+                self.last_text_len = grapheme_count(&action.text);
+                summary = format!(
+                    "typed {} chars into node {}",
+                    self.last_text_len, target_node
+                );
+            }
+            pb::ActionType::TypeInto => {
+                if action.text.is_empty() {
+                    return Err(EngineError::new(
+                        "invalid_request",
+                        "type into action requires text",
+                    ));
+                }
+                if action
+                    .target
+                    .as_ref()
+                    .is_none_or(|target| target.selector.trim().is_empty())
+                {
+                    return Err(EngineError::new(
+                        "invalid_request",
+                        "type into action requires a target selector",
+                    ));
+                }
+                if target_node != INPUT_NODE_ID {
+                    return Err(EngineError::new(
+                        "invalid_target",
+                        "target is not an editable element",
+                    ));
+                }
+                self.focused_node = target_node;
+                self.last_text_len = grapheme_count(&action.text);
+                summary = format!(
+                    "typed {} chars into node {}",
+                    self.last_text_len, target_node
+                );
             }
             pb::ActionType::Scroll => {
                 if let Some(scroll) = action.scroll.as_ref() {
@@ -414,6 +842,19 @@ impl BrowserEngine for StubEngine {
                 self.hovered_node = target_node;
                 summary = action_point_summary("hovered", target_node, target_point.as_ref());
             }
+            pb::ActionType::Drag => {
+                let drag_to = action.drag_to.as_ref().ok_or_else(|| {
+                    EngineError::new("invalid_target", "drag requires an end target")
+                })?;
+                let (end_node, _) = self.resolve_target(Some(drag_to))?;
+                self.hovered_node = end_node;
+                summary = format!(
+                    "dragged node {} to node {} in {} moves",
+                    target_node,
+                    end_node,
+                    drag_steps(action.drag_steps) + 1
+                );
+            }
             pb::ActionType::Key => {
                 self.last_key = action.key.clone();
                 if self.last_key.is_empty() {
@@ -421,61 +862,476 @@ impl BrowserEngine for StubEngine {
                 } else {
                     summary = format!("pressed key {}", self.last_key);
                 }
+                let repeat = key_repeat(action);
+                if repeat > 1 {
+                    summary.push_str(&format!(" {repeat} times"));
+                }
+            }
+            pb::ActionType::Tab => {
+                let reverse = has_shift(action);
+                for _ in 0..key_repeat(action) {
+                    self.focused_node = next_focus(self.focused_node, reverse);
+                }
+                self.last_key = if reverse { "Shift+Tab" } else { "Tab" }.to_string();
+                summary = format!("focused node {} via {}", self.focused_node, self.last_key);
             }
             pb::ActionType::Focus => {
                 self.focused_node = target_node;
                 summary = format!("focused node {}", target_node);
             }
             pb::ActionType::ClipboardRead => {
-                self.ensure_clipboard_read_allowed()?;
-                let bytes = self.clipboard_text.as_bytes().len();
-                if bytes > self.clipboard_max_bytes {
-                    return Err(EngineError::new("clipboard_limit", "clipboard exceeds size limit"));
-                }
-                summary = format!("clipboard read {} bytes", bytes);
+                let data = self.clipboard_get()?;
+                summary = format!("clipboard read {} bytes", data.bytes);
                 metadata = clipboard_metadata(
-                    Some(&self.clipboard_text),
-                    bytes,
+                    Some(&data.text),
+                    data.bytes as usize,
                     clipboard_mode_label(self.clipboard_mode),
                     "virtual",
                 );
             }
-            pb::ActionType::ClipboardWrite => {
-                self.ensure_clipboard_write_allowed()?;
-                let bytes = action.text.as_bytes().len();
-                if bytes > self.clipboard_max_bytes {
-                    return Err(EngineError::new("clipboard_limit", "clipboard exceeds size limit"));
+            pb::ActionType::SelectOption => {
+                if action.text.is_empty() {
+                    return Err(EngineError::new(
+                        "invalid_request",
+                        "select option action requires text",
+                    ));
                 }
-                self.clipboard_text = action.text.clone();
+                self.selected_option = action.text.clone();
+                summary = format!("selected option {:?}", self.selected_option);
+                metadata = selection_metadata(&self.selected_option, &self.selected_option);
+            }
+            pb::ActionType::SetChecked => {
+                let changed = if action.checked {
+                    self.checked_nodes.insert(target_node)
+                } else {
+                    self.checked_nodes.remove(&target_node)
+                };
+                let state = if action.checked {
+                    "checked"
+                } else {
+                    "unchecked"
+                };
+                summary = if changed {
+                    format!("{state} node {target_node}")
+                } else {
+                    format!("node {target_node} already {state}")
+                };
+                metadata = checked_metadata(action.checked, changed);
+            }
+            pb::ActionType::ClipboardWrite => {
+                let data = self.clipboard_set(&action.text)?;
                 self.last_text_len = action.text.chars().count();
-                summary = format!("clipboard wrote {} bytes", bytes);
+                summary = format!("clipboard wrote {} bytes", data.bytes);
                 metadata = clipboard_metadata(
                     None,
-                    bytes,
+                    data.bytes as usize,
                     clipboard_mode_label(self.clipboard_mode),
                     "virtual",
                 );
             }
             pb::ActionType::Unspecified => {}
         }
+        if action
+            .target
+            .as_ref()
+            .is_some_and(|target| scrolls_into_view(action_type, target))
+        {
+            // Stub nodes always fit the viewport; record that a real engine
+            // would have scrolled first.
+            metadata.get_or_insert_with(Struct::default).fields.insert(
+                "scrolled_into_view".to_string(),
+                Value {
+                    kind: Some(value::Kind::BoolValue(true)),
+                },
+            );
+        }
+        if let Some(timeout) = action_timeout(action) {
+            // The stub never blocks, so the bound is only echoed back.
+            metadata.get_or_insert_with(Struct::default).fields.insert(
+                "action_timeout_ms".to_string(),
+                Value {
+                    kind: Some(value::Kind::NumberValue(timeout.as_millis() as f64)),
+                },
+            );
+        }
+
+        let mut effects = vec![pb::Effect {
+            kind: action_type_label(action_type).to_string(),
+            summary: summary.clone(),
+            metadata,
+        }];
+        if action_type == pb::ActionType::Click && target_node == DOWNLOAD_NODE_ID {
+            let outcome = self.downloads.save(
+                &self.url,
+                STUB_DOWNLOAD_NAME,
+                "text/plain",
+                STUB_DOWNLOAD_DATA,
+            );
+            effects.push(download_effect(STUB_DOWNLOAD_NAME, &outcome));
+        }
 
         self.last_action = action_type_label(action_type).to_string();
-        self.last_action_detail = summary.clone();
+        self.last_action_detail = summary;
         self.bump_state();
         let result = pb::ActionResult {
             state_version: self.state_version,
-            observation: Some(self.build_observation(true, true, false, false)),
-            effects: vec![pb::Effect {
-                kind: action_type_label(action_type).to_string(),
-                summary,
-                metadata,
-            }],
+            observation: Some(self.build_observation(&snapshot_options())),
+            effects,
+            resolved_node_id: target_node,
+            resolved_point: target_point.or_else(|| self.node_center(target_node)),
+            caused_navigation: false,
         };
         Ok(result)
     }
 
-    fn stream_event(&mut self, event_type: pb::StreamEventType) -> Result<pb::StreamEvent, EngineError> {
-        Ok(self.build_stream_event(event_type))
+    fn stream_event(
+        &mut self,
+        event_type: pb::StreamEventType,
+        hit_test: &HitTestQuery,
+        _base: &mut DeltaBase,
+    ) -> Result<pb::StreamEvent, EngineError> {
+        let mut event = self.build_stream_event(event_type, hit_test);
+        if event_type == pb::StreamEventType::Console {
+            event.console_messages = self.console.drain(..).collect();
+        }
+        Ok(event)
+    }
+
+    fn check_contrast(&mut self, node_ids: &[u64]) -> Result<Vec<pb::ContrastResult>, EngineError> {
+        let text = Rgba::opaque(0x21, 0x21, 0x21);
+        let background = Rgba::opaque(0xff, 0xff, 0xff);
+        let targets = if node_ids.is_empty() {
+            vec![BUTTON_NODE_ID, INPUT_NODE_ID]
+        } else {
+            node_ids.to_vec()
+        };
+        Ok(targets
+            .into_iter()
+            .filter_map(|node_id| {
+                let label = match node_id {
+                    BUTTON_NODE_ID => "Stub Button",
+                    INPUT_NODE_ID => "Stub Input",
+                    _ => return None,
+                };
+                Some(contrast_result(
+                    node_id,
+                    label.to_string(),
+                    text,
+                    background,
+                    16.0,
+                    400,
+                ))
+            })
+            .collect())
+    }
+
+    fn export_pdf(&mut self, page_size: Option<&pb::Viewport>) -> Result<Vec<u8>, EngineError> {
+        let (width, height) = pdf_page_size(page_size, self.viewport_width, self.viewport_height);
+        Ok(single_page_pdf(width, height, None))
+    }
+
+    fn get_cookies(&mut self) -> Result<Vec<pb::Cookie>, EngineError> {
+        Ok(self.cookies.clone())
+    }
+
+    fn set_cookie(&mut self, cookie: &pb::Cookie) -> Result<(), EngineError> {
+        let mut cookie = cookie.clone();
+        if cookie.domain.is_empty() {
+            cookie.domain = Url::parse(&self.url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .unwrap_or_default();
+        }
+        if cookie.path.is_empty() {
+            cookie.path = "/".to_string();
+        }
+        self.cookies.retain(|existing| {
+            !(existing.name == cookie.name
+                && existing.domain == cookie.domain
+                && existing.path == cookie.path)
+        });
+        self.cookies.push(cookie);
+        Ok(())
+    }
+
+    fn clear_cookies(&mut self) -> Result<u32, EngineError> {
+        let cleared = self.cookies.len() as u32;
+        self.cookies.clear();
+        Ok(cleared)
+    }
+
+    fn set_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        entries: &[pb::StorageEntry],
+    ) -> Result<u32, EngineError> {
+        let key = self.storage_area(scope)?;
+        let area = self.storage.entry(key).or_default();
+        for entry in entries {
+            area.insert(entry.key.clone(), entry.value.clone());
+        }
+        Ok(entries.len() as u32)
+    }
+
+    fn get_storage(
+        &mut self,
+        scope: pb::StorageScope,
+        keys: &[String],
+    ) -> Result<pb::GetStorageResponse, EngineError> {
+        let (scope, origin) = self.storage_area(scope)?;
+        let entries = self
+            .storage
+            .get(&(scope, origin.clone()))
+            .map(|area| {
+                area.iter()
+                    .filter(|(key, _)| keys.is_empty() || keys.contains(key))
+                    .map(|(key, value)| pb::StorageEntry {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(pb::GetStorageResponse { origin, entries })
+    }
+
+    fn clear_storage(&mut self, scope: pb::StorageScope) -> Result<u32, EngineError> {
+        let area = self.storage_area(scope)?;
+        Ok(self
+            .storage
+            .remove(&area)
+            .map_or(0, |area| area.len() as u32))
+    }
+
+    fn clear_browsing_data(
+        &mut self,
+        kinds: &pb::ClearBrowsingDataRequest,
+    ) -> Result<pb::ClearBrowsingDataResponse, EngineError> {
+        let mut cleared = Vec::new();
+        if kinds.cookies {
+            self.cookies.clear();
+            cleared.push("cookies".to_string());
+        }
+        if kinds.storage {
+            self.storage.clear();
+            cleared.push("storage".to_string());
+        }
+        if kinds.cache {
+            // Nothing is fetched, so there is never anything cached.
+            cleared.push("cache".to_string());
+        }
+        if kinds.clipboard && self.clipboard_mode != pb::ClipboardMode::Host {
+            self.clipboard_text.clear();
+            cleared.push("clipboard".to_string());
+        }
+        Ok(pb::ClearBrowsingDataResponse {
+            cleared,
+            partial: Vec::new(),
+        })
+    }
+
+    fn wait_for(
+        &mut self,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<pb::Observation, EngineError> {
+        if selector.trim() != STUB_READY_SELECTOR {
+            return Err(EngineError::new(
+                "wait_timeout",
+                format!("selector not found within {}ms", timeout.as_millis()),
+            ));
+        }
+        Ok(self.build_observation(&snapshot_options()))
+    }
+
+    fn open_tab(&mut self, url: &str) -> Result<pb::Tab, EngineError> {
+        let url = match url.trim() {
+            "" => "about:blank",
+            url => url,
+        };
+        self.check_url(url)?;
+        self.tabs.push(StubTab {
+            id: self.next_tab_id,
+            url: url.to_string(),
+            title: STUB_TAB_TITLE.to_string(),
+        });
+        self.next_tab_id += 1;
+        self.activate_tab(self.tabs.len() - 1);
+        self.http_status = STUB_HTTP_STATUS;
+        Ok(self.tab_info(self.active_tab))
+    }
+
+    fn close_tab(&mut self, tab_id: u64) -> Result<u64, EngineError> {
+        let index = self.tab_index(tab_id)?;
+        if self.tabs.len() == 1 {
+            return Err(EngineError::new(
+                "invalid_request",
+                "cannot close the last tab",
+            ));
+        }
+        if index == self.active_tab {
+            let next = if index + 1 < self.tabs.len() {
+                index + 1
+            } else {
+                index - 1
+            };
+            self.activate_tab(next);
+        }
+        self.tabs.remove(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+        Ok(self.tabs[self.active_tab].id)
+    }
+
+    fn switch_tab(&mut self, tab_id: u64) -> Result<pb::Observation, EngineError> {
+        let index = self.tab_index(tab_id)?;
+        if index != self.active_tab {
+            self.activate_tab(index);
+        }
+        Ok(self.build_observation(&snapshot_options()))
+    }
+
+    fn list_tabs(&mut self) -> Result<Vec<pb::Tab>, EngineError> {
+        Ok((0..self.tabs.len())
+            .map(|index| self.tab_info(index))
+            .collect())
+    }
+
+    fn clipboard_get(&mut self) -> Result<pb::ClipboardData, EngineError> {
+        self.ensure_clipboard_read_allowed()?;
+        let bytes = self.clipboard_text.len();
+        if bytes > self.clipboard_max_bytes {
+            return Err(EngineError::new(
+                "clipboard_limit",
+                "clipboard exceeds size limit",
+            ));
+        }
+        Ok(pb::ClipboardData {
+            text: self.clipboard_text.clone(),
+            bytes: bytes as u32,
+            mode: self.clipboard_mode as i32,
+        })
+    }
+
+    fn clipboard_set(&mut self, text: &str) -> Result<pb::ClipboardData, EngineError> {
+        self.ensure_clipboard_write_allowed()?;
+        let bytes = text.len();
+        if bytes > self.clipboard_max_bytes {
+            return Err(EngineError::new(
+                "clipboard_limit",
+                "clipboard exceeds size limit",
+            ));
+        }
+        self.clipboard_text = text.to_string();
+        Ok(pb::ClipboardData {
+            text: String::new(),
+            bytes: bytes as u32,
+            mode: self.clipboard_mode as i32,
+        })
+    }
+
+    fn query_element(
+        &mut self,
+        query: &pb::QueryElementRequest,
+    ) -> Result<pb::QueryElementResponse, EngineError> {
+        let target = pb::ActionTarget {
+            selector: query.selector.clone(),
+            selector_type: query.selector_type,
+            ..Default::default()
+        };
+        let Some(node_id) = stub_selector_node(&target) else {
+            return Ok(pb::QueryElementResponse::default());
+        };
+        let known = stub_element_properties(node_id);
+        let properties = query
+            .properties
+            .iter()
+            .filter_map(|name| {
+                let (_, value) = known.iter().find(|(key, _)| key == name)?;
+                Some((name.clone(), value.to_string()))
+            })
+            .collect();
+        Ok(pb::QueryElementResponse {
+            found: true,
+            properties,
+        })
+    }
+
+    fn fill_form(&mut self, fields: &[pb::FormField]) -> Result<pb::FillFormResponse, EngineError> {
+        self.fields_filled += fields.len();
+        self.state_version += 1;
+        Ok(pb::FillFormResponse {
+            results: fields
+                .iter()
+                .map(|field| pb::FieldResult {
+                    selector: field.selector.clone(),
+                    filled: true,
+                    error: String::new(),
+                })
+                .collect(),
+            state_version: self.state_version,
+        })
+    }
+
+    fn set_file_input(
+        &mut self,
+        upload: &pb::SetFileInputRequest,
+    ) -> Result<pb::SetFileInputResponse, EngineError> {
+        self.uploaded_file = Some((upload.filename.clone(), upload.data.len()));
+        self.bump_state();
+        Ok(pb::SetFileInputResponse {
+            filename: upload.filename.clone(),
+            bytes: upload.data.len() as u64,
+            state_version: self.state_version,
+        })
+    }
+
+    fn list_downloads(&mut self) -> Result<pb::ListDownloadsResponse, EngineError> {
+        Ok(self.downloads.list())
+    }
+
+    fn get_download(&mut self, download_id: &str) -> Result<pb::GetDownloadResponse, EngineError> {
+        self.downloads.get(download_id)
+    }
+
+    fn scroll_position(&mut self) -> Result<pb::Point, EngineError> {
+        Ok(pb::Point {
+            x: self.scroll_x,
+            y: self.scroll_y,
+        })
+    }
+
+    fn set_viewport(&mut self, viewport: &pb::Viewport) -> Result<pb::Observation, EngineError> {
+        // Hit regions are derived from the viewport, so they follow on the
+        // next observation.
+        self.viewport_width = viewport.width;
+        self.viewport_height = viewport.height;
+        self.bump_state();
+        Ok(self.build_observation(&snapshot_options()))
+    }
+
+    fn extract_text(&mut self, max_bytes: usize) -> Result<pb::ExtractTextResponse, EngineError> {
+        let raw = format!("{}\n{}", self.title, self.last_action_detail);
+        Ok(page_text(&raw, max_bytes))
+    }
+}
+
+pub fn engine_info() -> pb::EngineInfo {
+    pb::EngineInfo {
+        name: "stub".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        commit: String::new(),
+        features: compiled_features(),
+    }
+}
+
+/// Observation contents returned alongside navigations and actions.
+fn snapshot_options() -> pb::ObserveOptions {
+    pb::ObserveOptions {
+        include_dom_snapshot: true,
+        include_accessibility: true,
+        ..Default::default()
     }
 }
 
@@ -491,14 +1347,86 @@ fn action_type_label(action_type: pb::ActionType) -> &'static str {
         pb::ActionType::Type => "type",
         pb::ActionType::Scroll => "scroll",
         pb::ActionType::Hover => "hover",
+        pb::ActionType::Drag => "drag",
+        pb::ActionType::Tab => "tab",
         pb::ActionType::Key => "key",
         pb::ActionType::Focus => "focus",
         pb::ActionType::ClipboardRead => "clipboard_read",
         pb::ActionType::ClipboardWrite => "clipboard_write",
+        pb::ActionType::SelectOption => "select_option",
+        pb::ActionType::SetChecked => "set_checked",
+        pb::ActionType::TypeInto => "type_into",
         pb::ActionType::Unspecified => "unspecified",
     }
 }
 
+/// The focusable node after (or before, when `reverse`) `current`, wrapping
+/// around. Focus outside the focus order moves to the first or last node.
+fn next_focus(current: u64, reverse: bool) -> u64 {
+    let len = FOCUS_ORDER.len();
+    let next = match FOCUS_ORDER.iter().position(|node| *node == current) {
+        Some(index) if reverse => (index + len - 1) % len,
+        Some(index) => (index + 1) % len,
+        None if reverse => len - 1,
+        None => 0,
+    };
+    FOCUS_ORDER[next]
+}
+
+/// Fixed selector mapping for the stub page's two controls.
+fn stub_selector_node(target: &pb::ActionTarget) -> Option<u64> {
+    let selector = target.selector.trim();
+    let xpath = target.selector_type == pb::SelectorType::Xpath as i32;
+    match (xpath, selector) {
+        (false, "#stub-button") | (false, "button") | (true, "//button") => Some(BUTTON_NODE_ID),
+        (false, "#stub-input") | (false, "input") | (true, "//input") => Some(INPUT_NODE_ID),
+        (false, "#stub-download") | (false, "a[download]") | (true, "//a[@download]") => {
+            Some(DOWNLOAD_NODE_ID)
+        }
+        _ => None,
+    }
+}
+
+/// Fixed properties of the stub page's controls for QueryElement.
+fn stub_element_properties(node_id: u64) -> Vec<(&'static str, &'static str)> {
+    match node_id {
+        BUTTON_NODE_ID => vec![
+            ("id", "stub-button"),
+            ("tagName", "BUTTON"),
+            ("type", "submit"),
+            ("textContent", "Stub Button"),
+        ],
+        INPUT_NODE_ID => vec![
+            ("id", "stub-input"),
+            ("tagName", "INPUT"),
+            ("type", "text"),
+            ("value", ""),
+            ("aria-label", "Stub Input"),
+        ],
+        DOWNLOAD_NODE_ID => vec![
+            ("id", "stub-download"),
+            ("tagName", "A"),
+            ("href", STUB_DOWNLOAD_NAME),
+            ("download", STUB_DOWNLOAD_NAME),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn click_verb(button: pb::MouseButton, count: u32) -> String {
+    let repeat = match count {
+        1 => "",
+        2 => "double-",
+        _ => "triple-",
+    };
+    let button = match button {
+        pb::MouseButton::Right => "right-",
+        pb::MouseButton::Middle => "middle-",
+        pb::MouseButton::Left | pb::MouseButton::Unspecified => "",
+    };
+    format!("{repeat}{button}clicked")
+}
+
 fn scroll_unit_label(unit: i32) -> &'static str {
     match pb::ScrollUnit::try_from(unit).unwrap_or(pb::ScrollUnit::Unspecified) {
         pb::ScrollUnit::Pixels => "pixels",
@@ -515,6 +1443,20 @@ fn action_point_summary(action: &str, node_id: u64, point: Option<&pb::Point>) -
     }
 }
 
+fn stub_style_value(node_id: u64, property: &str) -> &'static str {
+    match property {
+        "display" if node_id == ROOT_NODE_ID => "block",
+        "display" => "inline-block",
+        "visibility" => "visible",
+        "position" => "static",
+        "color" => "rgb(0, 0, 0)",
+        "background-color" => "rgba(0, 0, 0, 0)",
+        "font-size" => "16px",
+        "font-family" => "sans-serif",
+        _ => "",
+    }
+}
+
 fn clipboard_mode_label(mode: pb::ClipboardMode) -> &'static str {
     match mode {
         pb::ClipboardMode::Virtual => "virtual",
@@ -523,6 +1465,32 @@ fn clipboard_mode_label(mode: pb::ClipboardMode) -> &'static str {
     }
 }
 
+fn checked_metadata(checked: bool, changed: bool) -> Option<Struct> {
+    let mut fields = BTreeMap::new();
+    for (key, flag) in [("checked", checked), ("changed", changed)] {
+        fields.insert(
+            key.to_string(),
+            Value {
+                kind: Some(value::Kind::BoolValue(flag)),
+            },
+        );
+    }
+    Some(Struct { fields })
+}
+
+fn selection_metadata(value: &str, label: &str) -> Option<Struct> {
+    let mut fields = BTreeMap::new();
+    for (key, text) in [("value", value), ("label", label)] {
+        fields.insert(
+            key.to_string(),
+            Value {
+                kind: Some(value::Kind::StringValue(text.to_string())),
+            },
+        );
+    }
+    Some(Struct { fields })
+}
+
 fn clipboard_metadata(text: Option<&str>, bytes: usize, mode: &str, source: &str) -> Option<Struct> {
     let mut fields = BTreeMap::new();
     fields.insert(
@@ -563,6 +1531,14 @@ fn escape_json_string(value: &str) -> String {
         .replace('\t', "\\t")
 }
 
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 fn timestamp_now() -> prost_types::Timestamp {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -0,0 +1,79 @@
+//! Daemon logging with a level that can change while it runs.
+//!
+//! At startup `RUST_LOG` filters as usual (info when unset). Once a level is
+//! set, by `--log-level` or a `SetLogLevel` request, that single level
+//! applies to every module and `RUST_LOG`'s per-module directives stop
+//! applying.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once a level has replaced the `RUST_LOG` filter.
+static LEVEL_OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+
+struct ReloadableLogger {
+    /// Filters with `RUST_LOG`.
+    env: env_logger::Logger,
+    /// Lets everything through; `log::max_level()` does the filtering.
+    all: env_logger::Logger,
+}
+
+impl ReloadableLogger {
+    fn active(&self) -> &env_logger::Logger {
+        if LEVEL_OVERRIDDEN.load(Ordering::Relaxed) {
+            &self.all
+        } else {
+            &self.env
+        }
+    }
+}
+
+impl Log for ReloadableLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && self.active().enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.active().log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.active().flush();
+    }
+}
+
+/// Install the daemon logger. `level` is the `--log-level` flag, which
+/// overrides `RUST_LOG`.
+pub(crate) fn init(level: Option<LevelFilter>) {
+    let env =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).build();
+    let all = env_logger::Builder::new()
+        .filter_level(LevelFilter::Trace)
+        .build();
+    let env_level = env.filter();
+    if log::set_boxed_logger(Box::new(ReloadableLogger { env, all })).is_err() {
+        return;
+    }
+    log::set_max_level(env_level);
+    if let Some(level) = level {
+        set_level(level);
+    }
+}
+
+/// Apply `level` to every module from now on, returning the level that was
+/// in effect before.
+pub(crate) fn set_level(level: LevelFilter) -> LevelFilter {
+    let previous = log::max_level();
+    LEVEL_OVERRIDDEN.store(true, Ordering::Relaxed);
+    log::set_max_level(level);
+    previous
+}
+
+/// Parse a level name as accepted by `--log-level`.
+pub(crate) fn parse_level(value: &str) -> Result<LevelFilter, String> {
+    value.trim().parse().map_err(|_| {
+        format!("invalid log level: {value} (expected off, error, warn, info, debug or trace)")
+    })
+}
@@ -1,41 +1,127 @@
 use prost::Message;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
 use std::io;
-use std::io::Read;
 use std::io::Write;
+use std::io::{BufReader, Read};
+use std::net::{IpAddr, ToSocketAddrs, UdpSocket};
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use url::Url;
 
+mod codec;
 mod engine;
+mod logging;
+mod persist;
+mod url_policy;
 
+#[allow(clippy::large_enum_variant)]
 mod proto {
     include!(concat!(env!("OUT_DIR"), "/buckley.browserd.v1.rs"));
 }
 
-use engine::{allowlist_allows, BrowserEngine, EngineError};
+use codec::Protocol;
+use engine::{
+    has_mixed_script_label, host_list_matches, BrowserEngine, DeltaBase, EngineError, HitTestQuery,
+    StopSignal,
+};
 use proto as pb;
 
 const DEFAULT_SOCKET: &str = "/tmp/buckley/browserd.sock";
 const DEFAULT_FRAME_RATE: u32 = 12;
+const DEFAULT_MAX_SESSIONS: usize = 16;
+const DEFAULT_MAX_CONNECTIONS: usize = 64;
+const MAX_REAPER_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_WAIT_FOR_TIMEOUT_MS: u32 = 5_000;
+const MAX_WAIT_FOR_TIMEOUT_MS: u32 = 60_000;
+const DEFAULT_EXTRACT_TEXT_MAX_BYTES: u32 = 64 * 1024;
+const MAX_EXTRACT_TEXT_MAX_BYTES: u32 = 1024 * 1024;
+/// A batch runs under the sessions lock, so its length is capped to keep one
+/// request from starving every other session.
+const MAX_BATCH_ACTIONS: usize = 100;
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// A stream write blocked this long drops the event rather than stall.
+const STREAM_WRITE_TIMEOUT: Duration = Duration::from_millis(250);
+/// How long to keep retrying a frame that was partly written before giving up
+/// on the stream; a partial frame can't be dropped without breaking framing.
+const STREAM_STALL_LIMIT: Duration = Duration::from_secs(10);
+const STREAM_STATS_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_STREAM_HEARTBEAT_SECS: u32 = 3600;
+const DEFAULT_AUDIT_LOG_BACKUPS: usize = 5;
+/// Bytes of the SHA-256 digest kept in redacted audit values.
+const REDACT_HASH_BYTES: usize = 8;
+const DEFAULT_MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024; // 16 MB
+/// Ceiling for BROWSERD_MAX_MESSAGE_BYTES. Keeping lengths below 2^31 leaves
+/// the top bit of the length prefix free for the codec tags below.
+const MAX_MESSAGE_BYTES_CEILING: usize = 1 << 30;
+// Codec tags precede the length of a compressed frame. Uncompressed frames
+// start with the high byte of a length capped at MAX_MESSAGE_BYTES_CEILING, which is
+// never 0x80 or above, so the first byte tells the two apart.
+const FRAME_TAG_GZIP: u8 = 0x81;
+const FRAME_TAG_ZSTD: u8 = 0x82;
+const ZSTD_LEVEL: i32 = 3;
+
+/// Set by SIGTERM/SIGINT or an accepted `Shutdown` request; the accept loop
+/// polls it and drains sessions before exiting.
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Largest frame body accepted or sent, set once at startup from
+/// `BROWSERD_MAX_MESSAGE_BYTES`.
+static MAX_MESSAGE_BYTES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_MESSAGE_BYTES);
 
 struct Args {
     socket: PathBuf,
     session_id: Option<String>,
+    protocol: Protocol,
+    log_level: Option<log::LevelFilter>,
 }
 
 struct SessionEntry {
     session_id: String,
+    /// Config the session was created with, after daemon defaults, and with
+    /// later viewport changes applied; kept for SaveSession.
+    config: pb::SessionConfig,
+    url: String,
     allowlist: Vec<String>,
+    denylist: Vec<String>,
     engine: Box<dyn BrowserEngine>,
+    created_at: Instant,
+    last_activity: Instant,
+}
+
+/// Live sessions, plus each one's stop signal behind a lock of its own: a
+/// navigate holds the sessions lock while it waits for its page, and a Stop
+/// from another connection must still reach it.
+#[derive(Default)]
+struct Sessions {
+    entries: Mutex<HashMap<String, SessionEntry>>,
+    stop_signals: Mutex<HashMap<String, StopSignal>>,
+}
+
+impl Sessions {
+    fn lock(&self) -> LockResult<MutexGuard<'_, HashMap<String, SessionEntry>>> {
+        self.entries.lock()
+    }
+
+    /// Raise `session_id`'s stop signal without waiting for the sessions lock.
+    fn raise_stop(&self, session_id: &str) {
+        let signals = self.stop_signals.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(signal) = signals.get(session_id) {
+            signal.raise();
+        }
+    }
 }
 
-type SharedSessions = Arc<Mutex<HashMap<String, SessionEntry>>>;
+type SharedSessions = Arc<Sessions>;
 
 fn main() -> io::Result<()> {
     let args = match parse_args() {
@@ -47,6 +133,9 @@ fn main() -> io::Result<()> {
         }
     };
 
+    // Default to info so operational messages still reach stderr when
+    // RUST_LOG is unset.
+    logging::init(args.log_level);
     run(args)
 }
 
@@ -54,49 +143,141 @@ fn run(args: Args) -> io::Result<()> {
     let socket_path = args.socket;
     ensure_socket_dir(&socket_path)?;
     remove_existing_socket(&socket_path)?;
-    apply_security_config(&SecurityConfig::from_env())?;
+    let security = Arc::new(SecurityConfig::from_env());
+    apply_security_config(&security)?;
+    MAX_MESSAGE_BYTES.store(security.max_message_bytes, Ordering::Relaxed);
+    let audit_logger = AuditLogger::from_env()?;
 
     let _guard = SocketGuard::new(socket_path.clone());
     let listener = UnixListener::bind(&socket_path)?;
-    eprintln!("browserd listening on {}", socket_path.display());
+    // Non-blocking accepts let the loop notice a shutdown request promptly.
+    listener.set_nonblocking(true)?;
+    install_shutdown_handlers();
+    log::info!(
+        "browserd listening on {} ({:?} protocol)",
+        socket_path.display(),
+        args.protocol
+    );
+
+    let sessions: SharedSessions = SharedSessions::default();
 
-    let sessions: SharedSessions = Arc::new(Mutex::new(HashMap::new()));
-    let audit_logger = AuditLogger::from_env();
+    if let Some(idle_secs) = security.session_idle_secs.filter(|secs| *secs > 0) {
+        spawn_session_reaper(
+            Arc::clone(&sessions),
+            audit_logger.clone(),
+            Duration::from_secs(idle_secs),
+        );
+    }
 
-    for stream in listener.incoming() {
-        match stream {
-            Ok(stream) => {
+    let connection_slots = ConnectionSlots::new(security.max_connections);
+
+    while !SHUTDOWN_REQUESTED.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _addr)) => {
+                if let Err(err) = stream.set_nonblocking(false) {
+                    log::error!("accept error: {err}");
+                    continue;
+                }
+                let Some(permit) = connection_slots.try_acquire() else {
+                    log::warn!(
+                        "connection rejected: limit of {} reached",
+                        security.max_connections
+                    );
+                    drop(stream);
+                    continue;
+                };
                 let sessions = Arc::clone(&sessions);
                 let session_id = args.session_id.clone();
                 let audit_logger = audit_logger.clone();
+                let security = Arc::clone(&security);
+                let protocol = args.protocol;
                 thread::spawn(move || {
+                    let _permit = permit;
+                    let mut current_session = session_id.clone().unwrap_or_default();
                     if let Err(err) = handle_connection(
                         stream,
                         session_id.as_deref(),
                         sessions,
                         audit_logger.as_ref(),
+                        &security,
+                        protocol,
+                        &mut current_session,
                     ) {
-                        eprintln!("connection error: {err}");
+                        log::error!("connection error: {err}");
+                        log_audit_connection_error(audit_logger.as_ref(), &current_session, &err);
                     }
                 });
             }
-            Err(err) => eprintln!("accept error: {err}"),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(err) => log::error!("accept error: {err}"),
         }
     }
 
+    let closed = drain_sessions(&sessions, audit_logger.as_ref());
+    log::info!("browserd shutting down, closed {closed} session(s)");
     Ok(())
 }
 
+extern "C" fn handle_shutdown_signal(_signal: libc::c_int) {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+fn install_shutdown_handlers() {
+    let handler = handle_shutdown_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    for signal in [libc::SIGTERM, libc::SIGINT] {
+        if unsafe { libc::signal(signal, handler) } == libc::SIG_ERR {
+            log::warn!("failed to install handler for signal {signal}");
+        }
+    }
+}
+
+/// Close every session, shutting down its engine and recording the closure
+/// in the audit log. Returns how many sessions were closed.
+fn drain_sessions(sessions: &SharedSessions, audit_logger: Option<&AuditLogger>) -> usize {
+    let drained: Vec<SessionEntry> = {
+        let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+        map.drain().map(|(_, entry)| entry).collect()
+    };
+    for entry in &drained {
+        log_audit_event(
+            audit_logger,
+            &entry.session_id,
+            "session_closed",
+            serde_json::json!({ "reason": "shutdown" }),
+        );
+        close_audit_session(audit_logger, &entry.session_id);
+    }
+    // Dropping the entries drops the engines, which shuts down their runtimes.
+    drained.len()
+}
+
+/// Serve requests on one connection until it closes. `current_session` is
+/// kept set to the session the latest request addressed, so a connection
+/// error can be attributed to it.
 fn handle_connection(
     mut stream: UnixStream,
     session_id: Option<&str>,
     sessions: SharedSessions,
     audit_logger: Option<&AuditLogger>,
+    security: &SecurityConfig,
+    protocol: Protocol,
+    current_session: &mut String,
 ) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut sequence = Sequencer::default();
+    let reply = |stream: &mut UnixStream, sequence: &mut Sequencer, resp| {
+        write_response(stream, protocol, sequence.stamp(resp))
+    };
     let default_session_id = session_id.unwrap_or_default().to_string();
+    let mut limiter = security
+        .max_rps
+        .map(|rps| TokenBucket::new(rps as f64, Instant::now()));
+    let mut authenticated = security.auth_token.is_none();
 
     loop {
-        let envelope = match read_envelope(&mut stream)? {
+        let envelope = match protocol.read_envelope(&mut reader)? {
             Some(env) => env,
             None => return Ok(()),
         };
@@ -105,27 +286,199 @@ fn handle_connection(
             Some(pb::envelope::Message::Request(req)) => req,
             _ => {
                 let resp = error_response("", "", "invalid_request", "expected request");
-                write_envelope(&mut stream, resp)?;
+                reply(&mut stream, &mut sequence, resp)?;
                 continue;
             }
         };
+        if !req.session_id.is_empty() {
+            current_session.clone_from(&req.session_id);
+        }
+
+        if let Some(limiter) = limiter.as_mut() {
+            if !limiter.try_acquire(Instant::now()) {
+                let resp = error_response(
+                    &req.request_id,
+                    &req.session_id,
+                    "rate_limited",
+                    "request rate limit exceeded",
+                );
+                reply(&mut stream, &mut sequence, resp)?;
+                continue;
+            }
+        }
 
-        match handle_request(req, &default_session_id, &sessions, audit_logger) {
+        if let Some(resp) =
+            check_authentication(&req, security.auth_token.as_deref(), &mut authenticated)
+        {
+            reply(&mut stream, &mut sequence, resp)?;
+            continue;
+        }
+
+        // Requests on a connection are handled one at a time and each is
+        // answered before the next is read, so no two can be in flight with
+        // the same request_id; a reused id always refers to a new request.
+        match handle_request(req, &default_session_id, &sessions, audit_logger, security) {
             RequestOutcome::Response(resp, should_close) => {
-                write_envelope(&mut stream, resp)?;
+                reply(&mut stream, &mut sequence, resp)?;
                 if should_close {
                     return Ok(());
                 }
             }
             RequestOutcome::Stream(plan) => {
-                write_envelope(&mut stream, plan.response)?;
-                stream_events(&mut stream, &plan.session_id, &sessions, &plan.options)?;
+                reply(&mut stream, &mut sequence, plan.response)?;
+                stream_events(
+                    &mut stream,
+                    &plan.session_id,
+                    &sessions,
+                    audit_logger,
+                    &plan.options,
+                    protocol,
+                    &mut sequence,
+                )?;
                 return Ok(());
             }
         }
     }
 }
 
+/// Gate a connection on `BROWSERD_AUTH_TOKEN`. Answers `Authenticate`
+/// requests and anything sent before a successful one; returns `None` when
+/// the request may proceed to `handle_request`.
+fn check_authentication(
+    req: &pb::Request,
+    expected: Option<&str>,
+    authenticated: &mut bool,
+) -> Option<pb::Envelope> {
+    if let Some(pb::request::Payload::Authenticate(auth)) = &req.payload {
+        let valid =
+            expected.is_none_or(|token| constant_time_eq(token.as_bytes(), auth.token.as_bytes()));
+        if !valid {
+            return Some(error_response(
+                &req.request_id,
+                &req.session_id,
+                "unauthenticated",
+                "invalid auth token",
+            ));
+        }
+        *authenticated = true;
+        return Some(wrap_response(
+            req.request_id.clone(),
+            req.session_id.clone(),
+            pb::response::Payload::Authenticate(pb::AuthenticateResponse {
+                authenticated: true,
+            }),
+        ));
+    }
+    if *authenticated {
+        return None;
+    }
+    Some(error_response(
+        &req.request_id,
+        &req.session_id,
+        "unauthenticated",
+        "authenticate before sending requests",
+    ))
+}
+
+/// Compare secrets in time that depends only on the expected length.
+fn constant_time_eq(expected: &[u8], provided: &[u8]) -> bool {
+    let mut diff = expected.len() ^ provided.len();
+    for (i, byte) in expected.iter().enumerate() {
+        diff |= (byte ^ provided.get(i).copied().unwrap_or(0)) as usize;
+    }
+    diff == 0
+}
+
+/// Counts live connection threads, handing out at most `max` permits at once.
+/// A `max` of 0 means unlimited.
+struct ConnectionSlots {
+    live: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl ConnectionSlots {
+    fn new(max: usize) -> Self {
+        Self {
+            live: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    fn try_acquire(&self) -> Option<ConnectionPermit> {
+        let acquired = self
+            .live
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |live| {
+                (self.max == 0 || live < self.max).then_some(live + 1)
+            })
+            .is_ok();
+        acquired.then(|| ConnectionPermit {
+            live: Arc::clone(&self.live),
+        })
+    }
+}
+
+/// Releases its connection slot when the connection thread finishes.
+struct ConnectionPermit {
+    live: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.live.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Per-connection token bucket allowing `rate` requests per second with
+/// bursts of up to `rate` requests.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate,
+            last_refill: now,
+        }
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Numbers the responses and stream events written on one connection, from 1,
+/// so clients can order and dedupe them whatever request ids they send.
+#[derive(Default)]
+struct Sequencer {
+    last: u64,
+}
+
+impl Sequencer {
+    fn stamp(&mut self, mut envelope: pb::Envelope) -> pb::Envelope {
+        self.last += 1;
+        match envelope.message.as_mut() {
+            Some(pb::envelope::Message::Response(resp)) => resp.sequence = self.last,
+            Some(pb::envelope::Message::Event(event)) => event.sequence = self.last,
+            _ => {}
+        }
+        envelope
+    }
+}
+
 enum RequestOutcome {
     Response(pb::Envelope, bool),
     Stream(StreamPlan),
@@ -142,16 +495,249 @@ struct StreamSettings {
     include_dom_diffs: bool,
     include_accessibility_diffs: bool,
     include_hit_test: bool,
+    include_console: bool,
+    include_frame_timing: bool,
     target_fps: u32,
+    compression: pb::StreamCompression,
+    change_only: bool,
+    hit_test: HitTestQuery,
+    heartbeat: Option<Duration>,
+}
+
+/// Remembers the last diff event sent per type so `change_only` streams can
+/// skip repeats.
+#[derive(Default)]
+struct ChangeFilter {
+    last_sent: HashMap<i32, (u64, u64)>,
+}
+
+/// What `ChangeFilter` remembers of a delivered event: its type, state
+/// version and a hash of its payload.
+type ChangeKey = (i32, u64, u64);
+
+impl ChangeFilter {
+    /// The key to hand to `mark_sent` once `event` is delivered, or `None`
+    /// when it matches the last delivered event of its type. Frames always
+    /// pass so they keep their fps cadence.
+    fn unsent_change(&self, event: &pb::StreamEvent) -> Option<ChangeKey> {
+        if event.r#type == pb::StreamEventType::Frame as i32 {
+            return Some((event.r#type, event.state_version, 0));
+        }
+        let mut hasher = DefaultHasher::new();
+        event.dom_diff.hash(&mut hasher);
+        event.accessibility_diff.hash(&mut hasher);
+        if let Some(hit_test) = event.hit_test.as_ref() {
+            hit_test.encode_to_vec().hash(&mut hasher);
+        }
+        for message in &event.console_messages {
+            message.encode_to_vec().hash(&mut hasher);
+        }
+        let key = (event.state_version, hasher.finish());
+        (self.last_sent.get(&event.r#type) != Some(&key)).then_some((event.r#type, key.0, key.1))
+    }
+
+    /// Record a change as delivered. Called only after the write succeeds:
+    /// an event dropped for a slow client must not suppress its change.
+    fn mark_sent(&mut self, (event_type, state_version, hash): ChangeKey) {
+        self.last_sent.insert(event_type, (state_version, hash));
+    }
+}
+
+/// Line format of the audit log, from `BROWSERD_AUDIT_FORMAT`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AuditFormat {
+    /// One JSON object per line in `<session>.jsonl`.
+    Jsonl,
+    /// `<session>.csv`, starting with `AUDIT_CSV_HEADER`. The `details`
+    /// column holds the event's own fields as a JSON object.
+    Csv,
+}
+
+const AUDIT_CSV_HEADER: &str = "ts_ms,event,session_id,details\n";
+
+impl AuditFormat {
+    fn from_env() -> Self {
+        let Ok(raw) = env::var("BROWSERD_AUDIT_FORMAT") else {
+            return Self::Jsonl;
+        };
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "jsonl" => Self::Jsonl,
+            "csv" => Self::Csv,
+            _ => {
+                log::warn!("ignoring invalid BROWSERD_AUDIT_FORMAT={raw:?}, using jsonl");
+                Self::Jsonl
+            }
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// One audit log entry: the fields every event has, then the event's own.
+#[derive(serde::Serialize)]
+struct AuditRecord {
+    ts_ms: u64,
+    event: String,
+    session_id: String,
+    #[serde(flatten)]
+    details: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AuditRecord {
+    /// `details` is a JSON object of event fields; anything else is dropped.
+    fn new(event: &str, session_id: &str, details: serde_json::Value) -> Self {
+        let details = match details {
+            serde_json::Value::Object(fields) => fields,
+            _ => serde_json::Map::new(),
+        };
+        Self {
+            ts_ms: current_millis() as u64,
+            event: event.to_string(),
+            session_id: session_id.to_string(),
+            details,
+        }
+    }
+
+    fn to_line(&self, format: AuditFormat) -> String {
+        match format {
+            AuditFormat::Jsonl => {
+                let mut line = serde_json::to_string(self).unwrap_or_default();
+                line.push('\n');
+                line
+            }
+            AuditFormat::Csv => {
+                let details = serde_json::to_string(&self.details).unwrap_or_default();
+                format!(
+                    "{},{},{},{}\n",
+                    self.ts_ms,
+                    csv_field(&self.event),
+                    csv_field(&self.session_id),
+                    csv_field(&details)
+                )
+            }
+        }
+    }
+}
+
+/// Quote a CSV field that holds a comma, quote or line break, doubling any
+/// quotes inside, as RFC 4180 specifies.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 #[derive(Clone)]
 struct AuditLogger {
-    dir: PathBuf,
+    sink: Arc<dyn AuditSink>,
+    format: AuditFormat,
+    /// Hash every string-valued event field instead of logging it.
+    redact: bool,
 }
 
 impl AuditLogger {
-    fn from_env() -> Option<Self> {
+    /// Build the logger `BROWSERD_AUDIT_SINK` asks for: `file` (the default),
+    /// `stderr`, or `udp://host:port`. A UDP target that can't be set up is
+    /// an error rather than a silently disabled audit log.
+    fn from_env() -> io::Result<Option<Self>> {
+        let format = AuditFormat::from_env();
+        let spec = env::var("BROWSERD_AUDIT_SINK").unwrap_or_default();
+        let spec = spec.trim();
+        let sink: Arc<dyn AuditSink> = if spec.eq_ignore_ascii_case("stderr") {
+            Arc::new(StderrSink)
+        } else if let Some(target) = spec.strip_prefix("udp://") {
+            let sink = UdpSink::connect(target).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("audit log: can't reach udp://{target}: {err}"),
+                )
+            })?;
+            Arc::new(sink)
+        } else {
+            if !spec.is_empty() && !spec.eq_ignore_ascii_case("file") {
+                log::warn!("ignoring invalid BROWSERD_AUDIT_SINK={spec:?}, using file");
+            }
+            let Some(sink) = FileSink::from_env(format) else {
+                return Ok(None);
+            };
+            Arc::new(sink)
+        };
+        Ok(Some(
+            Self::new(sink, format).with_redaction(env_bool("BROWSERD_AUDIT_REDACT")),
+        ))
+    }
+
+    fn new(sink: Arc<dyn AuditSink>, format: AuditFormat) -> Self {
+        Self {
+            sink,
+            format,
+            redact: false,
+        }
+    }
+
+    /// A logger writing per-session files under `dir`.
+    #[cfg(test)]
+    fn file(dir: PathBuf, max_bytes: Option<u64>, backups: usize, format: AuditFormat) -> Self {
+        Self::new(
+            Arc::new(FileSink::new(dir, max_bytes, backups, format)),
+            format,
+        )
+    }
+
+    fn with_redaction(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// An event's fields as they should appear in the log. Every string field
+    /// goes through `redact` when redaction is on, so new audit events are
+    /// covered without each one opting in.
+    fn details(&self, details: serde_json::Value) -> serde_json::Value {
+        match details {
+            serde_json::Value::Object(fields) if self.redact => fields
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(text) => (key, redact(&text).into()),
+                    other => (key, other),
+                })
+                .collect(),
+            other => other,
+        }
+    }
+}
+
+/// Where audit lines go.
+trait AuditSink: Send + Sync {
+    /// Deliver one formatted line, newline included. Failures are logged,
+    /// never returned: auditing must not fail the request being audited.
+    fn write_line(&self, session_id: &str, line: &str);
+
+    /// Drop any per-session state once the session has closed.
+    fn close_session(&self, _session_id: &str) {}
+}
+
+/// One file per session, `<session>.<format extension>`, under `dir`.
+struct FileSink {
+    dir: PathBuf,
+    format: AuditFormat,
+    /// Rotate the session's log once an append would push it past this size.
+    max_bytes: Option<u64>,
+    /// Number of rotated files (`.1` newest .. `.N` oldest) to keep.
+    backups: usize,
+    /// One lock per log path so rotation and appends from concurrent
+    /// connections to the same session don't interleave.
+    locks: Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>,
+}
+
+impl FileSink {
+    fn from_env(format: AuditFormat) -> Option<Self> {
         let dir = env::var("BROWSERD_AUDIT_LOG_DIR")
             .unwrap_or_else(|_| "/tmp/buckley/browserd/audit".to_string());
         let trimmed = dir.trim();
@@ -161,25 +747,141 @@ impl AuditLogger {
         {
             return None;
         }
-        Some(Self {
-            dir: PathBuf::from(trimmed),
-        })
+        Some(Self::new(
+            PathBuf::from(trimmed),
+            env_u64("BROWSERD_AUDIT_LOG_MAX_BYTES").filter(|bytes| *bytes > 0),
+            env_u64("BROWSERD_AUDIT_LOG_BACKUPS")
+                .map(|value| value as usize)
+                .unwrap_or(DEFAULT_AUDIT_LOG_BACKUPS),
+            format,
+        ))
+    }
+
+    fn new(dir: PathBuf, max_bytes: Option<u64>, backups: usize, format: AuditFormat) -> Self {
+        Self {
+            dir,
+            format,
+            max_bytes,
+            backups,
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn path(&self, session_id: &str) -> PathBuf {
+        let file_name = format!(
+            "{}.{}",
+            sanitize_session_id(session_id),
+            self.format.extension()
+        );
+        self.dir.join(file_name)
+    }
+
+    fn path_lock(&self, path: &Path) -> Arc<Mutex<()>> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(locks.entry(path.to_path_buf()).or_default())
+    }
+
+    /// Shift `path.N-1` -> `path.N` down to `path` -> `path.1`, dropping the
+    /// oldest backup. With no backups configured the log is simply restarted.
+    fn rotate(&self, path: &Path) -> io::Result<()> {
+        let backup = |index: usize| {
+            let mut name = path.as_os_str().to_owned();
+            name.push(format!(".{index}"));
+            PathBuf::from(name)
+        };
+        if self.backups == 0 {
+            return fs::remove_file(path);
+        }
+        for index in (1..self.backups).rev() {
+            let from = backup(index);
+            if from.exists() {
+                fs::rename(&from, backup(index + 1))?;
+            }
+        }
+        fs::rename(path, backup(1))
     }
+}
 
+impl AuditSink for FileSink {
     fn write_line(&self, session_id: &str, line: &str) {
         if let Err(err) = fs::create_dir_all(&self.dir) {
-            eprintln!("audit log: {err}");
+            log::error!("audit log: {err}");
             return;
         }
-        let file_name = format!("{}.jsonl", sanitize_session_id(session_id));
-        let path = self.dir.join(file_name);
+        let path = self.path(session_id);
+        let lock = self.path_lock(&path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(max_bytes) = self.max_bytes {
+            let current = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+            if current > 0 && current + line.len() as u64 > max_bytes {
+                if let Err(err) = self.rotate(&path) {
+                    log::error!("audit log rotation: {err}");
+                }
+            }
+        }
+        let mut line = line.to_string();
+        if self.format == AuditFormat::Csv
+            && fs::metadata(&path).map_or(true, |meta| meta.len() == 0)
+        {
+            line.insert_str(0, AUDIT_CSV_HEADER);
+        }
         match OpenOptions::new().create(true).append(true).open(path) {
             Ok(mut file) => {
                 if let Err(err) = file.write_all(line.as_bytes()) {
-                    eprintln!("audit log: {err}");
+                    log::error!("audit log: {err}");
                 }
             }
-            Err(err) => eprintln!("audit log: {err}"),
+            Err(err) => log::error!("audit log: {err}"),
+        }
+    }
+
+    fn close_session(&self, session_id: &str) {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        locks.remove(&self.path(session_id));
+    }
+}
+
+/// Writes lines to the daemon's stderr, for supervisors that collect it.
+/// CSV output gets no header row here.
+struct StderrSink;
+
+impl AuditSink for StderrSink {
+    fn write_line(&self, _session_id: &str, line: &str) {
+        if let Err(err) = io::stderr().lock().write_all(line.as_bytes()) {
+            log::error!("audit log: {err}");
+        }
+    }
+}
+
+/// Sends each line as one datagram to a collector such as a syslog relay.
+/// Delivery is best effort: UDP drops lines silently when the collector is
+/// down or the network is congested. CSV output gets no header row here.
+struct UdpSink {
+    socket: UdpSocket,
+}
+
+impl UdpSink {
+    /// Resolve `target` (`host:port`) once and connect a socket to it.
+    fn connect(target: &str) -> io::Result<Self> {
+        let addr = target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no address for audit sink"))?;
+        let local = if addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(addr)?;
+        Ok(Self { socket })
+    }
+}
+
+impl AuditSink for UdpSink {
+    fn write_line(&self, _session_id: &str, line: &str) {
+        if let Err(err) = self.socket.send(line.as_bytes()) {
+            log::error!("audit log: {err}");
         }
     }
 }
@@ -193,8 +895,34 @@ struct SecurityConfig {
     assume_external: bool,
     strict: bool,
     downloads_enabled: bool,
+    /// Per-session cap on kept downloads (`BROWSERD_SECURITY_MAX_DOWNLOAD_BYTES`).
+    max_download_bytes: u64,
+    /// Let sessions use `ClipboardMode::Host` (`BROWSERD_SECURITY_HOST_CLIPBOARD`).
+    host_clipboard: bool,
+    /// Accept SetFileInput requests (`BROWSERD_SECURITY_UPLOADS_ENABLED`).
+    uploads_enabled: bool,
     js_budget_ms: Option<u64>,
     dom_mutation_limit: Option<u64>,
+    max_sessions: usize,
+    session_idle_secs: Option<u64>,
+    max_rps: Option<u64>,
+    max_connections: usize,
+    allow_remote_shutdown: bool,
+    /// Honor SetLogLevel requests (`BROWSERD_ALLOW_LOG_LEVEL_CHANGE`).
+    allow_log_level_change: bool,
+    auth_token: Option<String>,
+    max_message_bytes: usize,
+    /// Skip the SSRF guard (`BROWSERD_ALLOW_PRIVATE_HOSTS`).
+    allow_private_hosts: bool,
+    /// URL schemes sessions may load (`BROWSERD_ALLOWED_SCHEMES`,
+    /// comma-separated); empty uses the defaults in `url_policy`.
+    allowed_schemes: Vec<String>,
+    /// Denylist entries added to every session (`BROWSERD_GLOBAL_DENYLIST`,
+    /// comma-separated).
+    global_denylist: Vec<String>,
+    /// Where SaveSession writes snapshots (`BROWSERD_SESSION_DIR`); persistence
+    /// is disabled when unset.
+    session_dir: Option<PathBuf>,
 }
 
 impl SecurityConfig {
@@ -208,8 +936,49 @@ impl SecurityConfig {
             assume_external: env_bool("BROWSERD_SECURITY_ASSUME_EXTERNAL"),
             strict: env_bool("BROWSERD_SECURITY_STRICT"),
             downloads_enabled: env_bool("BROWSERD_SECURITY_DOWNLOADS_ENABLED"),
+            max_download_bytes: env_u64("BROWSERD_SECURITY_MAX_DOWNLOAD_BYTES")
+                .filter(|bytes| *bytes > 0)
+                .unwrap_or(engine::DEFAULT_MAX_DOWNLOAD_BYTES),
+            host_clipboard: env_bool("BROWSERD_SECURITY_HOST_CLIPBOARD"),
+            uploads_enabled: env_bool("BROWSERD_SECURITY_UPLOADS_ENABLED"),
             js_budget_ms: env_u64("BROWSERD_SECURITY_JS_BUDGET_MS"),
             dom_mutation_limit: env_u64("BROWSERD_SECURITY_DOM_MUTATION_LIMIT"),
+            max_sessions: env_u64("BROWSERD_MAX_SESSIONS")
+                .map(|value| value as usize)
+                .unwrap_or(DEFAULT_MAX_SESSIONS),
+            session_idle_secs: env_u64("BROWSERD_SESSION_IDLE_SECS"),
+            max_rps: env_u64("BROWSERD_MAX_RPS").filter(|rps| *rps > 0),
+            max_connections: env_u64("BROWSERD_MAX_CONNECTIONS")
+                .map(|value| value as usize)
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS),
+            allow_remote_shutdown: env_bool("BROWSERD_ALLOW_REMOTE_SHUTDOWN"),
+            allow_log_level_change: env_bool("BROWSERD_ALLOW_LOG_LEVEL_CHANGE"),
+            auth_token: env::var("BROWSERD_AUTH_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+            max_message_bytes: env_u64("BROWSERD_MAX_MESSAGE_BYTES")
+                .filter(|bytes| *bytes > 0)
+                .map(|bytes| (bytes as usize).min(MAX_MESSAGE_BYTES_CEILING))
+                .unwrap_or(DEFAULT_MAX_MESSAGE_BYTES),
+            allow_private_hosts: env_bool("BROWSERD_ALLOW_PRIVATE_HOSTS"),
+            allowed_schemes: env::var("BROWSERD_ALLOWED_SCHEMES")
+                .map(|value| url_policy::parse_allowed_schemes(&value))
+                .unwrap_or_default(),
+            global_denylist: env::var("BROWSERD_GLOBAL_DENYLIST")
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|entry| !entry.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            session_dir: env::var("BROWSERD_SESSION_DIR")
+                .ok()
+                .map(|dir| dir.trim().to_string())
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
         }
     }
 }
@@ -219,6 +988,7 @@ fn handle_request(
     default_session_id: &str,
     sessions: &SharedSessions,
     audit_logger: Option<&AuditLogger>,
+    security: &SecurityConfig,
 ) -> RequestOutcome {
     let request_id = req.request_id.clone();
     let session_id = resolve_session_id(&req.session_id, default_session_id);
@@ -238,14 +1008,19 @@ fn handle_request(
                 );
             }
             config.session_id = requested_id.clone();
-            if !config.initial_url.is_empty() {
-                if let Err(message) = validate_url(&config.initial_url, &config.network_allowlist)
-                {
-                    return RequestOutcome::Response(
-                        error_response(&request_id, &requested_id, "invalid_request", &message),
-                        false,
-                    );
-                }
+            // Cheap pre-check so a full daemon doesn't spin up an engine only
+            // to discard it; insert_session re-checks under the lock.
+            if !session_capacity_available(sessions, &requested_id, security.max_sessions) {
+                return RequestOutcome::Response(
+                    session_limit_response(&request_id, &requested_id, security.max_sessions),
+                    false,
+                );
+            }
+            if let Err(err) = prepare_session_config(&mut config, security) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &requested_id, err),
+                    false,
+                );
             }
             let engine = match engine::new_engine(&config) {
                 Ok(engine) => engine,
@@ -258,14 +1033,35 @@ fn handle_request(
             };
             let mut entry = SessionEntry {
                 session_id: requested_id.clone(),
+                config: config.clone(),
+                url: config.initial_url.clone(),
                 allowlist: config.network_allowlist.clone(),
+                denylist: config.network_denylist.clone(),
                 engine,
+                created_at: Instant::now(),
+                last_activity: Instant::now(),
             };
             let observe_opts = pb::ObserveOptions {
                 include_frame: false,
                 include_dom_snapshot: true,
                 include_accessibility: true,
                 include_hit_test: false,
+                include_text_runs: false,
+                include_engine_info: false,
+                clip_node_id: 0,
+                clip_rect: None,
+                style_node_ids: Vec::new(),
+                style_properties: Vec::new(),
+                include_timing: false,
+                max_hit_regions: 0,
+                hit_test_selectors: Vec::new(),
+                include_console: false,
+                include_page_errors: false,
+                until_network_idle_ms: 0,
+                include_html: false,
+                max_html_bytes: 0,
+                include_metadata: false,
+                dom_flat: false,
             };
             let observation = match entry.engine.observe(&observe_opts) {
                 Ok(obs) => obs,
@@ -276,6 +1072,7 @@ fn handle_request(
                     );
                 }
             };
+            entry.url = observation.url.clone();
             let response = pb::CreateSessionResponse {
                 session: Some(pb::SessionInfo {
                     session_id: entry.session_id.clone(),
@@ -284,7 +1081,13 @@ fn handle_request(
                 }),
                 observation: Some(observation),
             };
-            insert_session(sessions, entry);
+            if !insert_session(sessions, entry, security.max_sessions) {
+                return RequestOutcome::Response(
+                    session_limit_response(&request_id, &requested_id, security.max_sessions),
+                    false,
+                );
+            }
+            log_audit_session_created(audit_logger, &requested_id, &config);
             RequestOutcome::Response(
                 wrap_response(
                     request_id,
@@ -301,11 +1104,24 @@ fn handle_request(
                     false,
                 );
             }
-            let result = with_session(sessions, &session_id, |entry| {
-                if let Err(message) = validate_url(&navigate.url, &entry.allowlist) {
-                    return Err(EngineError::new("invalid_request", message));
-                }
-                entry.engine.navigate(&navigate.url)
+            if let Err(err) = engine::validate_navigation_timeout(navigate.timeout_ms) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            if let Err(err) =
+                validate_session_url(sessions, audit_logger, &session_id, &navigate.url, security)
+            {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let observation = entry.engine.navigate(&navigate.url, navigate.timeout_ms)?;
+                entry.url = observation.url.clone();
+                Ok(observation)
             });
             let observation = match result {
                 Some(Ok(obs)) => obs,
@@ -336,10 +1152,54 @@ fn handle_request(
             )
         }
         Some(pb::request::Payload::Observe(observe)) => {
-            let opts = observe.options.unwrap_or_default();
-            let result = with_session(sessions, &session_id, |entry| entry.engine.observe(&opts));
-            let observation = match result {
-                Some(Ok(obs)) => obs,
+            let mut opts = observe.options.unwrap_or_default();
+            if let Err(err) = engine::validate_style_request(&opts) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let volatile = requests_volatile_outputs(&opts);
+            // The network-idle wait can last the whole navigation timeout, so
+            // it runs outside the sessions lock and the observe after it
+            // doesn't wait again.
+            let network_idle = opts.until_network_idle_ms > 0 && {
+                let quiet = Duration::from_millis(u64::from(opts.until_network_idle_ms));
+                let wait = with_session(sessions, audit_logger, &session_id, |entry| {
+                    entry.engine.network_idle_wait(quiet)
+                });
+                wait.is_some_and(|wait| wait())
+            };
+            opts.until_network_idle_ms = 0;
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                // Checked before observing so an unchanged page costs no
+                // page scripts.
+                if !volatile
+                    && observe.if_state_version_changed == Some(entry.engine.state_version())
+                {
+                    return Ok(None);
+                }
+                let mut observation = entry.engine.observe(&opts)?;
+                observation.network_idle = network_idle;
+                entry.url = observation.url.clone();
+                Ok(Some(observation))
+            });
+            let mut observation = match result {
+                Some(Ok(Some(obs))) => obs,
+                Some(Ok(None)) => {
+                    let response = pb::ObserveResponse {
+                        observation: None,
+                        not_modified: true,
+                    };
+                    return RequestOutcome::Response(
+                        wrap_response(
+                            request_id,
+                            session_id,
+                            pb::response::Payload::Observe(response),
+                        ),
+                        false,
+                    );
+                }
                 Some(Err(err)) => {
                     return RequestOutcome::Response(
                         engine_error_response(&request_id, &session_id, err),
@@ -353,8 +1213,12 @@ fn handle_request(
                     );
                 }
             };
+            if opts.include_engine_info {
+                observation.engine_info = Some(engine::engine_info());
+            }
             let response = pb::ObserveResponse {
                 observation: Some(observation),
+                not_modified: false,
             };
             RequestOutcome::Response(
                 wrap_response(
@@ -375,9 +1239,10 @@ fn handle_request(
                     );
                 }
             };
-            let expected_state = action.expected_state_version;
-            let result = with_session(sessions, &session_id, |entry| {
-                if expected_state != 0 && expected_state != entry.engine.state_version() {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                if engine::is_stale(&action, entry.engine.state_version())
+                    && !engine::retries_on_stale(&action)
+                {
                     return Err(EngineError::new("stale_state", "stale state version"));
                 }
                 entry.engine.act(&action)
@@ -410,34 +1275,145 @@ fn handle_request(
                 false,
             )
         }
-        Some(pb::request::Payload::CloseSession(_close)) => {
-            if !remove_session(sessions, &session_id) {
+        Some(pb::request::Payload::BatchActions(batch)) => {
+            if batch.actions.is_empty() {
                 return RequestOutcome::Response(
-                    error_response(&request_id, &session_id, "invalid_session", "session not initialized"),
-                    true,
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "actions are required",
+                    ),
+                    false,
                 );
             }
-            let response = pb::CloseSessionResponse { closed: true };
-            RequestOutcome::Response(
-                wrap_response(
-                    request_id,
-                    session_id.clone(),
-                    pb::response::Payload::CloseSession(response),
-                ),
-                true,
-            )
-        }
-        Some(pb::request::Payload::StreamSubscribe(stream)) => {
-            let default_fps = match with_session(sessions, &session_id, |entry| entry.engine.frame_rate()) {
-                Some(rate) => rate,
-                None => {
-                    return RequestOutcome::Response(
-                        error_response(&request_id, &session_id, "invalid_session", "session not initialized"),
-                        false,
+            if batch.actions.len() > MAX_BATCH_ACTIONS {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        &format!("actions is limited to {MAX_BATCH_ACTIONS} entries"),
+                    ),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let mut results: Vec<pb::ActionResult> = Vec::new();
+                let mut failure = None;
+                let mut expected_state = batch.actions[0].expected_state_version;
+                for (index, action) in batch.actions.iter().enumerate() {
+                    let action = pb::Action {
+                        expected_state_version: expected_state,
+                        ..action.clone()
+                    };
+                    if engine::is_stale(&action, entry.engine.state_version())
+                        && !engine::retries_on_stale(&action)
+                    {
+                        failure = Some((
+                            index,
+                            EngineError::new("stale_state", "stale state version"),
+                        ));
+                        break;
+                    }
+                    match entry.engine.act(&action) {
+                        Ok(res) => {
+                            expected_state = res.state_version;
+                            results.push(res);
+                        }
+                        Err(err) => {
+                            failure = Some((index, err));
+                            break;
+                        }
+                    }
+                }
+                (results, failure)
+            });
+            let (results, failure) = match result {
+                Some(outcome) => outcome,
+                None => {
+                    return RequestOutcome::Response(
+                        error_response(
+                            &request_id,
+                            &session_id,
+                            "invalid_session",
+                            "session not initialized",
+                        ),
+                        false,
+                    );
+                }
+            };
+            for (action, res) in batch.actions.iter().zip(&results) {
+                log_audit_action(audit_logger, &session_id, action, res.state_version);
+            }
+            let (failed_index, error) = match failure {
+                Some((index, err)) => (
+                    index as i32,
+                    Some(pb::Error {
+                        code: err.code.to_string(),
+                        message: err.message,
+                        code_enum: pb::ErrorCode::from(err.code) as i32,
+                    }),
+                ),
+                None => (-1, None),
+            };
+            let response = pb::BatchActionsResponse {
+                results,
+                failed_index,
+                error,
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    session_id,
+                    pb::response::Payload::BatchActions(response),
+                ),
+                false,
+            )
+        }
+        Some(pb::request::Payload::CloseSession(_close)) => {
+            if !remove_session(sessions, &session_id) {
+                return RequestOutcome::Response(
+                    error_response(&request_id, &session_id, "invalid_session", "session not initialized"),
+                    true,
+                );
+            }
+            log_audit_event(
+                audit_logger,
+                &session_id,
+                "session_closed",
+                serde_json::json!({ "reason": "client" }),
+            );
+            close_audit_session(audit_logger, &session_id);
+            let response = pb::CloseSessionResponse { closed: true };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    session_id.clone(),
+                    pb::response::Payload::CloseSession(response),
+                ),
+                true,
+            )
+        }
+        Some(pb::request::Payload::StreamSubscribe(stream)) => {
+            let default_fps = match with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.frame_rate()
+            }) {
+                Some(rate) => rate,
+                None => {
+                    return RequestOutcome::Response(
+                        error_response(
+                            &request_id,
+                            &session_id,
+                            "invalid_session",
+                            "session not initialized",
+                        ),
+                        false,
                     );
                 }
             };
             let options = normalize_stream_options(stream.options, default_fps);
+            log_audit_stream_subscribe(audit_logger, &session_id, &options);
             let response = wrap_response(
                 request_id,
                 session_id.clone(),
@@ -449,484 +1425,4537 @@ fn handle_request(
                 options,
             })
         }
-        None => RequestOutcome::Response(
-            error_response(&request_id, &session_id, "invalid_request", "missing payload"),
-            false,
-        ),
-    }
-}
-
-fn engine_error_response(request_id: &str, session_id: &str, err: EngineError) -> pb::Envelope {
-    error_response(request_id, session_id, err.code, &err.message)
-}
-
-fn resolve_session_id(requested: &str, default_session_id: &str) -> String {
-    if !requested.is_empty() {
-        requested.to_string()
-    } else {
-        default_session_id.to_string()
-    }
-}
-
-fn insert_session(sessions: &SharedSessions, entry: SessionEntry) {
-    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
-    map.insert(entry.session_id.clone(), entry);
-}
-
-fn with_session<T, F>(sessions: &SharedSessions, session_id: &str, op: F) -> Option<T>
-where
-    F: FnOnce(&mut SessionEntry) -> T,
-{
-    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
-    let entry = map.get_mut(session_id)?;
-    Some(op(entry))
-}
-
-fn remove_session(sessions: &SharedSessions, session_id: &str) -> bool {
-    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
-    map.remove(session_id).is_some()
-}
-
-fn normalize_stream_options(
-    options: Option<pb::StreamOptions>,
-    default_fps: u32,
-) -> StreamSettings {
-    let mut settings = StreamSettings {
-        include_frames: false,
-        include_dom_diffs: false,
-        include_accessibility_diffs: false,
-        include_hit_test: false,
-        target_fps: default_fps,
-    };
-    if let Some(opts) = options {
-        settings.include_frames = opts.include_frames;
-        settings.include_dom_diffs = opts.include_dom_diffs;
-        settings.include_accessibility_diffs = opts.include_accessibility_diffs;
-        settings.include_hit_test = opts.include_hit_test;
-        if opts.target_fps > 0 {
-            settings.target_fps = opts.target_fps;
+        Some(pb::request::Payload::CheckContrast(check)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.check_contrast(&check.node_ids)
+            });
+            session_response(request_id, session_id, result, |results| {
+                pb::response::Payload::CheckContrast(pb::CheckContrastResponse { results })
+            })
         }
+        Some(pb::request::Payload::ExportPdf(export)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.export_pdf(export.page_size.as_ref())
+            });
+            session_response(request_id, session_id, result, |data| {
+                pb::response::Payload::ExportPdf(pb::ExportPdfResponse { data })
+            })
+        }
+        Some(pb::request::Payload::GetCookies(_get)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.get_cookies()
+            });
+            session_response(request_id, session_id, result, |cookies| {
+                pb::response::Payload::GetCookies(pb::GetCookiesResponse { cookies })
+            })
+        }
+        Some(pb::request::Payload::SetCookie(set)) => {
+            let Some(cookie) = set.cookie else {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "cookie is required",
+                    ),
+                    false,
+                );
+            };
+            if cookie.name.trim().is_empty() {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "cookie name is required",
+                    ),
+                    false,
+                );
+            }
+            if let Err(err) = engine::validate_cookie(&cookie) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                if !cookie_domain_allowed(&cookie.domain, &entry.allowlist) {
+                    return Err(EngineError::new(
+                        "cookie_denied",
+                        "cookie domain not in allowlist",
+                    ));
+                }
+                entry.engine.set_cookie(&cookie)
+            });
+            session_response(request_id, session_id, result, |()| {
+                pb::response::Payload::SetCookie(pb::SetCookieResponse { set: true })
+            })
+        }
+        Some(pb::request::Payload::ClearCookies(_clear)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.clear_cookies()
+            });
+            session_response(request_id, session_id, result, |cleared| {
+                pb::response::Payload::ClearCookies(pb::ClearCookiesResponse { cleared })
+            })
+        }
+        Some(pb::request::Payload::SetStorage(set)) => {
+            if let Err(err) = engine::validate_storage_entries(&set.entries) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let scope = storage_scope(set.scope);
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                check_storage_origin(entry)?;
+                entry.engine.set_storage(scope, &set.entries)
+            });
+            session_response(request_id, session_id, result, |set| {
+                pb::response::Payload::SetStorage(pb::SetStorageResponse { set })
+            })
+        }
+        Some(pb::request::Payload::GetStorage(get)) => {
+            let scope = storage_scope(get.scope);
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.get_storage(scope, &get.keys)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::GetStorage,
+            )
+        }
+        Some(pb::request::Payload::ClearStorage(clear)) => {
+            let scope = storage_scope(clear.scope);
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                check_storage_origin(entry)?;
+                entry.engine.clear_storage(scope)
+            });
+            session_response(request_id, session_id, result, |cleared| {
+                pb::response::Payload::ClearStorage(pb::ClearStorageResponse { cleared })
+            })
+        }
+        Some(pb::request::Payload::ClearBrowsingData(clear)) => {
+            let kinds = engine::browsing_data_kinds(&clear);
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.clear_browsing_data(&kinds)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::ClearBrowsingData,
+            )
+        }
+        Some(pb::request::Payload::WaitFor(wait)) => {
+            let selector = wait.selector.trim().to_string();
+            if selector.is_empty() {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "selector is required",
+                    ),
+                    false,
+                );
+            }
+            let timeout_ms = match wait.timeout_ms {
+                0 => DEFAULT_WAIT_FOR_TIMEOUT_MS,
+                ms => ms.min(MAX_WAIT_FOR_TIMEOUT_MS),
+            };
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let observation = entry
+                    .engine
+                    .wait_for(&selector, Duration::from_millis(u64::from(timeout_ms)))?;
+                entry.url = observation.url.clone();
+                Ok(observation)
+            });
+            session_response(request_id, session_id, result, |observation| {
+                pb::response::Payload::WaitFor(pb::WaitForResponse {
+                    observation: Some(observation),
+                })
+            })
+        }
+        Some(pb::request::Payload::OpenTab(open)) => {
+            let url = open.url.trim().to_string();
+            if !url.is_empty() {
+                if let Err(err) =
+                    validate_session_url(sessions, audit_logger, &session_id, &url, security)
+                {
+                    return RequestOutcome::Response(
+                        engine_error_response(&request_id, &session_id, err),
+                        false,
+                    );
+                }
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let tab = entry.engine.open_tab(&url)?;
+                entry.url = tab.url.clone();
+                Ok(tab)
+            });
+            session_response(request_id, session_id, result, |tab| {
+                pb::response::Payload::OpenTab(pb::OpenTabResponse { tab: Some(tab) })
+            })
+        }
+        Some(pb::request::Payload::CloseTab(close)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let active_tab_id = entry.engine.close_tab(close.tab_id)?;
+                entry.url = active_tab_url(entry.engine.list_tabs()?);
+                Ok(active_tab_id)
+            });
+            session_response(request_id, session_id, result, |active_tab_id| {
+                pb::response::Payload::CloseTab(pb::CloseTabResponse { active_tab_id })
+            })
+        }
+        Some(pb::request::Payload::SwitchTab(switch)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let observation = entry.engine.switch_tab(switch.tab_id)?;
+                entry.url = observation.url.clone();
+                Ok(observation)
+            });
+            session_response(request_id, session_id, result, |observation| {
+                pb::response::Payload::SwitchTab(pb::SwitchTabResponse {
+                    observation: Some(observation),
+                })
+            })
+        }
+        Some(pb::request::Payload::ListTabs(_list)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.list_tabs()
+            });
+            session_response(request_id, session_id, result, |tabs| {
+                pb::response::Payload::ListTabs(pb::ListTabsResponse { tabs })
+            })
+        }
+        Some(pb::request::Payload::SaveSession(_save)) => {
+            let Some(dir) = security.session_dir.as_deref() else {
+                return RequestOutcome::Response(
+                    persistence_disabled_response(&request_id, &session_id),
+                    false,
+                );
+            };
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                if entry.config.ephemeral {
+                    return Err(EngineError::new(
+                        "invalid_request",
+                        "ephemeral sessions cannot be saved",
+                    ));
+                }
+                let cookies = entry.engine.get_cookies()?;
+                let scroll = entry.engine.scroll_position()?;
+                Ok(persist::SessionSnapshot {
+                    version: persist::SNAPSHOT_VERSION,
+                    config: entry.config.clone(),
+                    url: entry.url.clone(),
+                    cookies,
+                    scroll_x: scroll.x,
+                    scroll_y: scroll.y,
+                })
+            });
+            // Write outside the sessions lock.
+            let result = result.map(|snapshot| {
+                snapshot.and_then(|snapshot| {
+                    persist::write_snapshot(dir, &snapshot).map_err(snapshot_error)
+                })
+            });
+            session_response(request_id, session_id, result, |path| {
+                pb::response::Payload::SaveSession(pb::SaveSessionResponse {
+                    path: path.display().to_string(),
+                })
+            })
+        }
+        Some(pb::request::Payload::RestoreSession(_restore)) => {
+            let Some(dir) = security.session_dir.as_deref() else {
+                return RequestOutcome::Response(
+                    persistence_disabled_response(&request_id, &session_id),
+                    false,
+                );
+            };
+            if session_id.is_empty() {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "session_id is required",
+                    ),
+                    false,
+                );
+            }
+            if !session_capacity_available(sessions, &session_id, security.max_sessions) {
+                return RequestOutcome::Response(
+                    session_limit_response(&request_id, &session_id, security.max_sessions),
+                    false,
+                );
+            }
+            let restored = persist::read_snapshot(dir, &session_id)
+                .map_err(snapshot_error)
+                .and_then(|snapshot| restore_session(snapshot, security));
+            let (entry, observation, cookies_restored) = match restored {
+                Ok(restored) => restored,
+                Err(err) => {
+                    return RequestOutcome::Response(
+                        engine_error_response(&request_id, &session_id, err),
+                        false,
+                    );
+                }
+            };
+            let config = entry.config.clone();
+            if !insert_session(sessions, entry, security.max_sessions) {
+                return RequestOutcome::Response(
+                    session_limit_response(&request_id, &session_id, security.max_sessions),
+                    false,
+                );
+            }
+            log_audit_session_created(audit_logger, &session_id, &config);
+            let response = pb::RestoreSessionResponse {
+                session: Some(pb::SessionInfo {
+                    session_id: session_id.clone(),
+                    state_version: observation.state_version,
+                    url: observation.url.clone(),
+                }),
+                observation: Some(observation),
+                cookies_restored,
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    session_id,
+                    pb::response::Payload::RestoreSession(response),
+                ),
+                false,
+            )
+        }
+        Some(pb::request::Payload::Stop(_stop)) => {
+            // A navigate still waiting for its page holds the sessions lock
+            // until it returns, so signal it first; the stop below then runs
+            // on whatever it left loading.
+            sessions.raise_stop(&session_id);
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.stop()
+            });
+            session_response(request_id, session_id, result, |observation| {
+                pb::response::Payload::Stop(pb::StopResponse {
+                    observation: Some(observation),
+                })
+            })
+        }
+        Some(pb::request::Payload::SetViewport(set)) => {
+            let Some(viewport) = set.viewport else {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "viewport is required",
+                    ),
+                    false,
+                );
+            };
+            if let Err(err) = engine::validate_viewport(&viewport) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                let observation = entry.engine.set_viewport(&viewport)?;
+                // Keep the config current so SaveSession records the new size.
+                let saved = entry.config.viewport.get_or_insert_with(Default::default);
+                saved.width = viewport.width;
+                saved.height = viewport.height;
+                if viewport.device_scale_factor > 0.0 {
+                    saved.device_scale_factor = viewport.device_scale_factor;
+                }
+                Ok(observation)
+            });
+            session_response(request_id, session_id, result, |observation| {
+                pb::response::Payload::SetViewport(pb::SetViewportResponse {
+                    observation: Some(observation),
+                })
+            })
+        }
+        Some(pb::request::Payload::ExtractText(extract)) => {
+            let max_bytes = match extract.max_bytes {
+                0 => DEFAULT_EXTRACT_TEXT_MAX_BYTES,
+                bytes => bytes.min(MAX_EXTRACT_TEXT_MAX_BYTES),
+            };
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.extract_text(max_bytes as usize)
+            });
+            session_response(request_id, session_id, result, |text| {
+                pb::response::Payload::ExtractText(text)
+            })
+        }
+        Some(pb::request::Payload::ClipboardGet(_get)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.clipboard_get()
+            });
+            session_response(request_id, session_id, result, |data| {
+                pb::response::Payload::ClipboardGet(pb::ClipboardGetResponse { data: Some(data) })
+            })
+        }
+        Some(pb::request::Payload::ClipboardSet(set)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.clipboard_set(&set.text)
+            });
+            session_response(request_id, session_id, result, |data| {
+                pb::response::Payload::ClipboardSet(pb::ClipboardSetResponse { data: Some(data) })
+            })
+        }
+        Some(pb::request::Payload::FillForm(fill)) => {
+            if let Err(err) = engine::validate_form_fields(&fill.fields) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.fill_form(&fill.fields)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::FillForm,
+            )
+        }
+        Some(pb::request::Payload::QueryElement(query)) => {
+            if let Err(err) = engine::validate_query_element(&query) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.query_element(&query)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::QueryElement,
+            )
+        }
+        Some(pb::request::Payload::SetFileInput(upload)) => {
+            if !security.uploads_enabled {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "uploads_disabled",
+                        "uploads are disabled; set BROWSERD_SECURITY_UPLOADS_ENABLED",
+                    ),
+                    false,
+                );
+            }
+            if let Err(err) = engine::validate_file_upload(&upload) {
+                return RequestOutcome::Response(
+                    engine_error_response(&request_id, &session_id, err),
+                    false,
+                );
+            }
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.set_file_input(&upload)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::SetFileInput,
+            )
+        }
+        Some(pb::request::Payload::SessionStats(_stats)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                Ok(pb::SessionStatsResponse {
+                    rss_bytes: process_rss_bytes().unwrap_or(0),
+                    state_version: entry.engine.state_version(),
+                    uptime_ms: entry.created_at.elapsed().as_millis() as u64,
+                })
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::SessionStats,
+            )
+        }
+        Some(pb::request::Payload::ListDownloads(_list)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.list_downloads()
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::ListDownloads,
+            )
+        }
+        Some(pb::request::Payload::GetDownload(get)) => {
+            let result = with_session(sessions, audit_logger, &session_id, |entry| {
+                entry.engine.get_download(&get.download_id)
+            });
+            session_response(
+                request_id,
+                session_id,
+                result,
+                pb::response::Payload::GetDownload,
+            )
+        }
+        Some(pb::request::Payload::GetInfo(_info)) => {
+            let response = pb::GetInfoResponse {
+                daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+                engine: Some(engine::engine_info()),
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    session_id,
+                    pb::response::Payload::GetInfo(response),
+                ),
+                false,
+            )
+        }
+        Some(pb::request::Payload::ListSessions(_list)) => {
+            let response = pb::ListSessionsResponse {
+                sessions: list_sessions(sessions),
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    String::new(),
+                    pb::response::Payload::ListSessions(response),
+                ),
+                false,
+            )
+        }
+        Some(pb::request::Payload::Shutdown(_shutdown)) => {
+            if !security.allow_remote_shutdown {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "unsupported",
+                        "remote shutdown is disabled",
+                    ),
+                    false,
+                );
+            }
+            let closed = drain_sessions(sessions, audit_logger);
+            SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+            let response = pb::ShutdownResponse {
+                sessions_closed: closed as u32,
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    String::new(),
+                    pb::response::Payload::Shutdown(response),
+                ),
+                true,
+            )
+        }
+        Some(pb::request::Payload::SetLogLevel(set_level)) => {
+            if !security.allow_log_level_change {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "unsupported",
+                        "log level changes are disabled",
+                    ),
+                    false,
+                );
+            }
+            let Some(level) = level_filter(set_level.level) else {
+                return RequestOutcome::Response(
+                    error_response(
+                        &request_id,
+                        &session_id,
+                        "invalid_request",
+                        "level is required",
+                    ),
+                    false,
+                );
+            };
+            let previous = logging::set_level(level);
+            log::warn!("log level changed from {previous} to {level}");
+            log_audit_event(
+                audit_logger,
+                &session_id,
+                "log_level_changed",
+                serde_json::json!({ "previous": previous.to_string(), "level": level.to_string() }),
+            );
+            let response = pb::SetLogLevelResponse {
+                previous: log_level(previous) as i32,
+                level: log_level(level) as i32,
+            };
+            RequestOutcome::Response(
+                wrap_response(
+                    request_id,
+                    String::new(),
+                    pb::response::Payload::SetLogLevel(response),
+                ),
+                false,
+            )
+        }
+        // Answered by check_authentication before requests get here.
+        Some(pb::request::Payload::Authenticate(_)) => RequestOutcome::Response(
+            error_response(
+                &request_id,
+                &session_id,
+                "invalid_request",
+                "authenticate must be sent as a connection handshake",
+            ),
+            false,
+        ),
+        None => RequestOutcome::Response(
+            error_response(&request_id, &session_id, "invalid_request", "missing payload"),
+            false,
+        ),
+    }
+}
+
+/// Build the response for a request run against a session via
+/// `with_session`, mapping a missing session and engine errors to error
+/// responses and wrapping a successful value with `payload`.
+fn session_response<T, F>(
+    request_id: String,
+    session_id: String,
+    result: Option<Result<T, EngineError>>,
+    payload: F,
+) -> RequestOutcome
+where
+    F: FnOnce(T) -> pb::response::Payload,
+{
+    let envelope = match result {
+        Some(Ok(value)) => wrap_response(request_id, session_id, payload(value)),
+        Some(Err(err)) => engine_error_response(&request_id, &session_id, err),
+        None => error_response(
+            &request_id,
+            &session_id,
+            "invalid_session",
+            "session not initialized",
+        ),
+    };
+    RequestOutcome::Response(envelope, false)
+}
+
+fn engine_error_response(request_id: &str, session_id: &str, err: EngineError) -> pb::Envelope {
+    error_response(request_id, session_id, err.code, &err.message)
+}
+
+fn resolve_session_id(requested: &str, default_session_id: &str) -> String {
+    if !requested.is_empty() {
+        requested.to_string()
+    } else {
+        default_session_id.to_string()
+    }
+}
+
+fn session_limit_response(request_id: &str, session_id: &str, max_sessions: usize) -> pb::Envelope {
+    error_response(
+        request_id,
+        session_id,
+        "session_limit",
+        &format!("session limit reached (max {max_sessions})"),
+    )
+}
+
+/// Report whether a session with `session_id` could be added without
+/// exceeding `max_sessions`. Replacing an existing session never counts
+/// against the limit, and a limit of zero disables it.
+fn session_capacity_available(
+    sessions: &SharedSessions,
+    session_id: &str,
+    max_sessions: usize,
+) -> bool {
+    let map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    has_capacity(&map, session_id, max_sessions)
+}
+
+fn has_capacity(
+    map: &HashMap<String, SessionEntry>,
+    session_id: &str,
+    max_sessions: usize,
+) -> bool {
+    max_sessions == 0 || map.contains_key(session_id) || map.len() < max_sessions
+}
+
+fn insert_session(sessions: &SharedSessions, entry: SessionEntry, max_sessions: usize) -> bool {
+    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    if !has_capacity(&map, &entry.session_id, max_sessions) {
+        return false;
+    }
+    // Drop the signals of sessions that have since gone away.
+    let mut signals = sessions
+        .stop_signals
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    signals.retain(|session_id, _| map.contains_key(session_id));
+    signals.insert(entry.session_id.clone(), entry.engine.stop_signal());
+    map.insert(entry.session_id.clone(), entry);
+    true
+}
+
+fn with_session<T, F>(
+    sessions: &SharedSessions,
+    audit_logger: Option<&AuditLogger>,
+    session_id: &str,
+    op: F,
+) -> Option<T>
+where
+    F: FnOnce(&mut SessionEntry) -> T,
+{
+    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = map.get_mut(session_id)?;
+    if !entry.engine.is_alive() {
+        // The engine can't recover, so drop the session and let the caller
+        // answer `invalid_session`; clients then know to recreate it.
+        let dead = map.remove(session_id);
+        drop(map);
+        if let Some(dead) = dead {
+            log::warn!("removing session {session_id}: engine runtime has exited");
+            audit_session_eviction(audit_logger, &dead, "engine_exited");
+        }
+        return None;
+    }
+    entry.last_activity = Instant::now();
+    Some(op(entry))
+}
+
+/// Resident set size of the daemon process from `/proc/self/statm`. Servo
+/// runs each session on a thread in this process, so there is no per-session
+/// figure to report.
+#[cfg(target_os = "linux")]
+fn process_rss_bytes() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(page_size)
+        .ok()
+        .map(|size| resident_pages * size)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_rss_bytes() -> Option<u64> {
+    None
+}
+
+fn list_sessions(sessions: &SharedSessions) -> Vec<pb::SessionInfo> {
+    let map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    let mut infos: Vec<pb::SessionInfo> = map
+        .values()
+        .map(|entry| pb::SessionInfo {
+            session_id: entry.session_id.clone(),
+            state_version: entry.engine.state_version(),
+            url: entry.url.clone(),
+        })
+        .collect();
+    infos.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+    infos
+}
+
+fn remove_session(sessions: &SharedSessions, session_id: &str) -> bool {
+    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    map.remove(session_id).is_some()
+}
+
+/// Remove sessions that have been idle for at least `idle_timeout` or whose
+/// engine has died. The entries are returned so callers can drop them
+/// (shutting down their engines) outside the session lock.
+fn evict_idle_sessions(sessions: &SharedSessions, idle_timeout: Duration) -> Vec<SessionEntry> {
+    let mut map = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    let idle_ids: Vec<String> = map
+        .values()
+        .filter(|entry| entry.last_activity.elapsed() >= idle_timeout || !entry.engine.is_alive())
+        .map(|entry| entry.session_id.clone())
+        .collect();
+    idle_ids
+        .iter()
+        .filter_map(|session_id| map.remove(session_id))
+        .collect()
+}
+
+/// Record that `entry` was taken out of the session map for `reason`
+/// without a CloseSession, and release its audit log.
+fn audit_session_eviction(audit_logger: Option<&AuditLogger>, entry: &SessionEntry, reason: &str) {
+    log_audit_event(
+        audit_logger,
+        &entry.session_id,
+        "session_evicted",
+        serde_json::json!({
+            "reason": reason,
+            "idle_secs": entry.last_activity.elapsed().as_secs(),
+        }),
+    );
+    close_audit_session(audit_logger, &entry.session_id);
+}
+
+fn spawn_session_reaper(
+    sessions: SharedSessions,
+    audit_logger: Option<AuditLogger>,
+    idle_timeout: Duration,
+) {
+    let interval = (idle_timeout / 2).clamp(Duration::from_secs(1), MAX_REAPER_INTERVAL);
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        for entry in evict_idle_sessions(&sessions, idle_timeout) {
+            let reason = if entry.engine.is_alive() {
+                log::info!(
+                    "evicting session {} after {}s idle",
+                    entry.session_id,
+                    entry.last_activity.elapsed().as_secs()
+                );
+                "idle"
+            } else {
+                log::warn!(
+                    "removing session {}: engine runtime has exited",
+                    entry.session_id
+                );
+                "engine_exited"
+            };
+            audit_session_eviction(audit_logger.as_ref(), &entry, reason);
+            // Dropping the entry drops the engine, which shuts down its runtime.
+            drop(entry);
+        }
+    });
+}
+
+fn normalize_stream_options(
+    options: Option<pb::StreamOptions>,
+    default_fps: u32,
+) -> StreamSettings {
+    let mut settings = StreamSettings {
+        include_frames: false,
+        include_dom_diffs: false,
+        include_accessibility_diffs: false,
+        include_hit_test: false,
+        include_console: false,
+        include_frame_timing: false,
+        target_fps: default_fps,
+        compression: pb::StreamCompression::None,
+        change_only: false,
+        hit_test: HitTestQuery::default(),
+        heartbeat: None,
+    };
+    if let Some(opts) = options {
+        settings.include_frames = opts.include_frames;
+        settings.include_dom_diffs = opts.include_dom_diffs;
+        settings.include_accessibility_diffs = opts.include_accessibility_diffs;
+        settings.include_hit_test = opts.include_hit_test;
+        settings.include_console = opts.include_console;
+        settings.include_frame_timing = opts.include_frame_timing;
+        settings.change_only = opts.change_only;
+        settings.hit_test = HitTestQuery::new(opts.max_hit_regions, &opts.hit_test_selectors);
+        settings.heartbeat = (opts.heartbeat_secs > 0).then(|| {
+            Duration::from_secs(u64::from(
+                opts.heartbeat_secs.min(MAX_STREAM_HEARTBEAT_SECS),
+            ))
+        });
+        settings.compression = pb::StreamCompression::try_from(opts.compression)
+            .unwrap_or(pb::StreamCompression::None);
+        if opts.target_fps > 0 {
+            settings.target_fps = opts.target_fps;
+        }
+    }
+    if !(settings.include_frames
+        || settings.include_dom_diffs
+        || settings.include_accessibility_diffs
+        || settings.include_hit_test
+        || settings.include_console)
+    {
+        settings.include_frames = true;
+    }
+    if settings.target_fps == 0 {
+        settings.target_fps = DEFAULT_FRAME_RATE;
+    }
+    settings
+}
+
+fn stream_events(
+    stream: &mut UnixStream,
+    session_id: &str,
+    sessions: &SharedSessions,
+    audit_logger: Option<&AuditLogger>,
+    options: &StreamSettings,
+    protocol: Protocol,
+    sequence: &mut Sequencer,
+) -> io::Result<()> {
+    let mut fps = options.target_fps;
+    if fps == 0 {
+        fps = DEFAULT_FRAME_RATE;
+    }
+    let interval = Duration::from_millis(std::cmp::max(1, 1000 / fps) as u64);
+    let start = Instant::now();
+    let start_wall = SystemTime::now();
+    let mut tick: u32 = 0;
+    stream.set_write_timeout(Some(STREAM_WRITE_TIMEOUT))?;
+    let mut stats = pb::StreamStats::default();
+    let mut last_stats = start;
+    let mut last_heartbeat = start;
+    let mut changes = ChangeFilter::default();
+    let mut delta_base = DeltaBase::default();
+    // Console messages are drained from the page when an event is built, so
+    // the ones a dropped event carried wait here for the next one.
+    let mut unsent_console = Vec::new();
+
+    loop {
+        // Frames are scheduled against a fixed grid from the start of the
+        // stream so slow captures don't accumulate drift.
+        let scheduled_offset = interval.saturating_mul(tick);
+        let presentation_time = start_wall + scheduled_offset;
+
+        let mut send_event = |event_type: pb::StreamEventType| -> io::Result<bool> {
+            let capture_start = Instant::now();
+            let result = with_session(sessions, audit_logger, session_id, |entry| {
+                entry
+                    .engine
+                    .stream_event(event_type, &options.hit_test, &mut delta_base)
+            });
+            let mut event = match result {
+                Some(Ok(event)) => event,
+                Some(Err(_)) => return Ok(false),
+                None => return Ok(false),
+            };
+            if event_type == pb::StreamEventType::Console {
+                requeue_console(std::mem::take(&mut unsent_console), &mut event);
+                if event.console_messages.is_empty() {
+                    return Ok(true);
+                }
+            }
+            let change = if options.change_only {
+                match changes.unsent_change(&event) {
+                    Some(change) => Some(change),
+                    None => return Ok(true),
+                }
+            } else {
+                None
+            };
+            if options.include_frame_timing {
+                if let Some(frame) = event.frame.as_mut() {
+                    stamp_frame_timing(frame, presentation_time, capture_start);
+                }
+            }
+            let console = if event_type == pb::StreamEventType::Console {
+                event.console_messages.clone()
+            } else {
+                Vec::new()
+            };
+            let frame =
+                protocol.encode_envelope(sequence.stamp(wrap_event(event)), options.compression)?;
+            if write_or_drop(stream, &frame)? {
+                stats.events_sent += 1;
+                if let Some(change) = change {
+                    changes.mark_sent(change);
+                }
+            } else {
+                stats.events_dropped += 1;
+                // The base already moved on to the dropped frame, which the
+                // client never saw; deltas against it would corrupt its view.
+                delta_base.reset();
+                unsent_console = console;
+            }
+            Ok(true)
+        };
+
+        if options.include_frames && !send_event(pb::StreamEventType::Frame)? {
+            return Ok(());
+        }
+        if options.include_dom_diffs && !send_event(pb::StreamEventType::DomDiff)? {
+            return Ok(());
+        }
+        if options.include_accessibility_diffs
+            && !send_event(pb::StreamEventType::AccessibilityDiff)?
+        {
+            return Ok(());
+        }
+        if options.include_hit_test && !send_event(pb::StreamEventType::HitTest)? {
+            return Ok(());
+        }
+        if options.include_console && !send_event(pb::StreamEventType::Console)? {
+            return Ok(());
+        }
+        if last_stats.elapsed() >= STREAM_STATS_INTERVAL {
+            last_stats = Instant::now();
+            let event = pb::StreamEvent {
+                r#type: pb::StreamEventType::Stats as i32,
+                timestamp: Some(timestamp_from_system_time(SystemTime::now())),
+                stats: Some(stats.clone()),
+                ..Default::default()
+            };
+            let frame =
+                protocol.encode_envelope(sequence.stamp(wrap_event(event)), options.compression)?;
+            write_or_drop(stream, &frame)?;
+        }
+        // Timed on its own clock so it keeps going when change_only skips
+        // everything else.
+        if options
+            .heartbeat
+            .is_some_and(|every| last_heartbeat.elapsed() >= every)
+        {
+            last_heartbeat = Instant::now();
+            let Some(state_version) = with_session(sessions, audit_logger, session_id, |entry| {
+                entry.engine.state_version()
+            }) else {
+                return Ok(());
+            };
+            let event = pb::StreamEvent {
+                r#type: pb::StreamEventType::Heartbeat as i32,
+                state_version,
+                timestamp: Some(timestamp_from_system_time(SystemTime::now())),
+                ..Default::default()
+            };
+            let frame =
+                protocol.encode_envelope(sequence.stamp(wrap_event(event)), options.compression)?;
+            write_or_drop(stream, &frame)?;
+        }
+
+        tick = tick.saturating_add(1);
+        let next_tick = start + interval.saturating_mul(tick);
+        let now = Instant::now();
+        if next_tick > now {
+            thread::sleep(next_tick - now);
+        }
+    }
+}
+
+/// Write a whole frame unless the client isn't keeping up. Returns
+/// `Ok(false)` when the frame was dropped because the first write timed out.
+/// Once part of a frame is out it has to be finished to keep the framing
+/// intact, so that case retries until `STREAM_STALL_LIMIT`.
+fn write_or_drop<W: Write>(stream: &mut W, frame: &[u8]) -> io::Result<bool> {
+    let mut written = 0;
+    let mut stalled_since: Option<Instant> = None;
+    while written < frame.len() {
+        match stream.write(&frame[written..]) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => {
+                written += n;
+                stalled_since = None;
+            }
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                if written == 0 {
+                    return Ok(false);
+                }
+                let since = *stalled_since.get_or_insert_with(Instant::now);
+                if since.elapsed() >= STREAM_STALL_LIMIT {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "stream client stalled mid-frame",
+                    ));
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    stream.flush()?;
+    Ok(true)
+}
+
+/// Put console messages from a dropped event back in front of `event`'s,
+/// keeping the newest `MAX_CONSOLE_MESSAGES` like the page buffer does.
+fn requeue_console(mut unsent: Vec<pb::ConsoleMessage>, event: &mut pb::StreamEvent) {
+    if unsent.is_empty() {
+        return;
+    }
+    unsent.append(&mut event.console_messages);
+    let excess = unsent.len().saturating_sub(engine::MAX_CONSOLE_MESSAGES);
+    unsent.drain(..excess);
+    event.console_messages = unsent;
+}
+
+fn wrap_event(event: pb::StreamEvent) -> pb::Envelope {
+    pb::Envelope {
+        message: Some(pb::envelope::Message::Event(event)),
+    }
+}
+
+/// Cookies may only be set for domains the session is allowed to reach. An
+/// empty domain scopes the cookie to the current page and is always allowed.
+fn cookie_domain_allowed(domain: &str, allowlist: &[String]) -> bool {
+    let domain = domain.trim().trim_start_matches('.');
+    if domain.is_empty() || allowlist.is_empty() {
+        return true;
+    }
+    host_list_matches(domain, None, allowlist)
+}
+
+/// Storage may only be written for the active page's origin when the session
+/// is allowed to reach it. As in `validate_url`, host lists only apply to
+/// http(s) pages.
+fn check_storage_origin(entry: &mut SessionEntry) -> Result<(), EngineError> {
+    let url = active_tab_url(entry.engine.list_tabs()?);
+    let Ok(parsed) = Url::parse(&url) else {
+        return Err(EngineError::new(
+            "invalid_request",
+            "the active page has no origin",
+        ));
+    };
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Ok(());
+    }
+    let host = parsed.host_str().unwrap_or_default();
+    let port = parsed.port_or_known_default();
+    let allowed = !host_list_matches(host, port, &entry.denylist)
+        && (entry.allowlist.is_empty() || host_list_matches(host, port, &entry.allowlist));
+    if !allowed {
+        return Err(EngineError::new(
+            "storage_denied",
+            "page origin not in allowlist",
+        ));
+    }
+    Ok(())
+}
+
+fn storage_scope(scope: i32) -> pb::StorageScope {
+    match pb::StorageScope::try_from(scope) {
+        Ok(pb::StorageScope::Session) => pb::StorageScope::Session,
+        _ => pb::StorageScope::Local,
+    }
+}
+
+/// Apply daemon defaults to a new session's config and validate it. Shared
+/// by CreateSession and RestoreSession so a snapshot can't skip the checks.
+fn prepare_session_config(
+    config: &mut pb::SessionConfig,
+    security: &SecurityConfig,
+) -> Result<(), EngineError> {
+    if config.js_budget_ms == 0 {
+        if let Some(budget) = security.js_budget_ms {
+            config.js_budget_ms = budget.min(u64::from(u32::MAX)) as u32;
+        }
+    }
+    // Download policy is the daemon's call; a session may only lower the cap.
+    config.downloads_enabled = security.downloads_enabled;
+    config.allowed_schemes = security.allowed_schemes.clone();
+    if config.max_download_bytes == 0 || config.max_download_bytes > security.max_download_bytes {
+        config.max_download_bytes = security.max_download_bytes;
+    }
+    engine::validate_navigation_timeout(config.navigation_timeout_ms)?;
+    engine::validate_request_headers(&config.request_headers)?;
+    let host_clipboard =
+        engine::session_clipboard_policy(config, engine::default_clipboard_policy()).mode
+            == pb::ClipboardMode::Host as i32;
+    if host_clipboard && !security.host_clipboard {
+        return Err(EngineError::new(
+            "clipboard_denied",
+            "host clipboard is disabled; set BROWSERD_SECURITY_HOST_CLIPBOARD",
+        ));
+    }
+    // Restored configs already carry the global entries.
+    for entry in &security.global_denylist {
+        if !config.network_denylist.contains(entry) {
+            config.network_denylist.push(entry.clone());
+        }
+    }
+    if !config.initial_url.is_empty() {
+        validate_url(
+            &config.initial_url,
+            &config.allowed_schemes,
+            &config.network_allowlist,
+            &config.network_denylist,
+            security.allow_private_hosts,
+            security.strict,
+        )?;
+    }
+    Ok(())
+}
+
+/// Build a session from a snapshot: a fresh engine navigated to the saved
+/// URL, then cookies and scroll offset replayed. Returns the entry, its
+/// observation and how many cookies were accepted.
+fn restore_session(
+    snapshot: persist::SessionSnapshot,
+    security: &SecurityConfig,
+) -> Result<(SessionEntry, pb::Observation, u32), EngineError> {
+    let mut config = snapshot.config;
+    prepare_session_config(&mut config, security)?;
+    let mut engine = engine::new_engine(&config)?;
+    if !snapshot.url.is_empty() {
+        validate_url(
+            &snapshot.url,
+            &config.allowed_schemes,
+            &config.network_allowlist,
+            &config.network_denylist,
+            security.allow_private_hosts,
+            security.strict,
+        )?;
+        engine.navigate(&snapshot.url, 0)?;
+    }
+    let cookies_restored = snapshot
+        .cookies
+        .iter()
+        .filter(|cookie| cookie_domain_allowed(&cookie.domain, &config.network_allowlist))
+        .filter(|cookie| engine.set_cookie(cookie).is_ok())
+        .count() as u32;
+    if snapshot.scroll_x != 0 || snapshot.scroll_y != 0 {
+        engine.act(&pb::Action {
+            r#type: pb::ActionType::Scroll as i32,
+            scroll: Some(pb::ScrollDelta {
+                x: snapshot.scroll_x,
+                y: snapshot.scroll_y,
+                unit: pb::ScrollUnit::Pixels as i32,
+            }),
+            ..Default::default()
+        })?;
+    }
+    let observation = engine.observe(&pb::ObserveOptions {
+        include_dom_snapshot: true,
+        include_accessibility: true,
+        ..Default::default()
+    })?;
+    let entry = SessionEntry {
+        session_id: config.session_id.clone(),
+        url: observation.url.clone(),
+        allowlist: config.network_allowlist.clone(),
+        denylist: config.network_denylist.clone(),
+        config,
+        engine,
+        created_at: Instant::now(),
+        last_activity: Instant::now(),
+    };
+    Ok((entry, observation, cookies_restored))
+}
+
+fn snapshot_error(err: io::Error) -> EngineError {
+    match err.kind() {
+        io::ErrorKind::NotFound => {
+            EngineError::new("invalid_session", "no saved snapshot for session")
+        }
+        io::ErrorKind::InvalidData => EngineError::new("invalid_request", err.to_string()),
+        _ => EngineError::new("unavailable", format!("session snapshot: {err}")),
+    }
+}
+
+fn persistence_disabled_response(request_id: &str, session_id: &str) -> pb::Envelope {
+    error_response(
+        request_id,
+        session_id,
+        "unsupported",
+        "session persistence is disabled; set BROWSERD_SESSION_DIR",
+    )
+}
+
+/// Check `url` against the session's host lists. Runs outside the sessions
+/// lock because the SSRF guard may resolve DNS. Unknown sessions pass; the
+/// request fails later with `invalid_session`.
+fn validate_session_url(
+    sessions: &SharedSessions,
+    audit_logger: Option<&AuditLogger>,
+    session_id: &str,
+    url: &str,
+    security: &SecurityConfig,
+) -> Result<(), EngineError> {
+    let lists = with_session(sessions, audit_logger, session_id, |entry| {
+        (
+            entry.config.allowed_schemes.clone(),
+            entry.allowlist.clone(),
+            entry.denylist.clone(),
+        )
+    });
+    match lists {
+        Some((schemes, allowlist, denylist)) => validate_url(
+            url,
+            &schemes,
+            &allowlist,
+            &denylist,
+            security.allow_private_hosts,
+            security.strict,
+        ),
+        None => Ok(()),
+    }
+}
+
+fn active_tab_url(tabs: Vec<pb::Tab>) -> String {
+    tabs.into_iter()
+        .find(|tab| tab.active)
+        .map(|tab| tab.url)
+        .unwrap_or_default()
+}
+
+/// Check a navigation target against the session's allowed `schemes` and its
+/// host lists. The denylist is checked first and wins over the allowlist.
+/// Unless `allow_private` is set or the host is explicitly allowlisted, hosts
+/// that resolve to private addresses are rejected (SSRF guard). Redirects are
+/// followed by the engine and are not re-checked here. In `strict` mode,
+/// hosts with a label mixing Latin, Greek and Cyrillic letters are rejected as
+/// likely homographs.
+fn validate_url(
+    url: &str,
+    schemes: &[String],
+    allowlist: &[String],
+    denylist: &[String],
+    allow_private: bool,
+    strict: bool,
+) -> Result<(), EngineError> {
+    let invalid = |message: String| EngineError::new("invalid_request", message);
+    let parsed = Url::parse(url).map_err(|_| invalid("invalid url".to_string()))?;
+    url_policy::check_scheme(&parsed, schemes)?;
+    // Host lists only apply to network schemes; anything else got here by
+    // being allowed outright.
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Ok(());
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| invalid("missing host".to_string()))?;
+    let port = parsed.port_or_known_default();
+    if strict && has_mixed_script_label(host) {
+        return Err(EngineError::new(
+            "host_denied",
+            format!("host mixes scripts and may be a homograph: {host}"),
+        ));
+    }
+    if host_list_matches(host, port, denylist) {
+        return Err(EngineError::new(
+            "host_denied",
+            format!("host is denied: {host}"),
+        ));
+    }
+    if !allowlist.is_empty() {
+        if host_list_matches(host, port, allowlist) {
+            return Ok(());
+        }
+        return Err(invalid("host not in allowlist".to_string()));
+    }
+    if !allow_private && resolves_to_private_address(host, port.unwrap_or(80)) {
+        return Err(EngineError::new(
+            "host_denied",
+            format!("host resolves to a private address: {host}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `host` is, or resolves to, an address in a private range.
+/// Hosts that fail to resolve are let through; the load itself will fail.
+fn resolves_to_private_address(host: &str, port: u16) -> bool {
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = literal.parse::<IpAddr>() {
+        return is_private_address(ip);
+    }
+    match (host, port).to_socket_addrs() {
+        Ok(mut addrs) => addrs.any(|addr| is_private_address(addr.ip())),
+        Err(_) => false,
+    }
+}
+
+/// Loopback, RFC 1918, link-local (including cloud metadata at
+/// 169.254.169.254), unique-local and unspecified addresses.
+fn is_private_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified()
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_private_address(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+fn apply_security_config(cfg: &SecurityConfig) -> io::Result<()> {
+    if cfg.enforce_non_root && unsafe { libc::geteuid() } == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "browserd must not run as root",
+        ));
+    }
+
+    let mut unmet = Vec::new();
+    if cfg.require_seccomp {
+        unmet.push("seccomp");
+    }
+    if cfg.require_cgroup {
+        unmet.push("cgroup");
+    }
+    if cfg.require_readonly_root {
+        unmet.push("read_only_root");
+    }
+    if cfg.require_netns {
+        unmet.push("netns");
+    }
+    if !unmet.is_empty() && !cfg.assume_external {
+        let message = format!(
+            "security requirements requested but not enforced: {}",
+            unmet.join(", ")
+        );
+        if cfg.strict {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, message));
+        }
+        log::warn!("{message}");
+    }
+
+    if cfg.downloads_enabled {
+        log::info!(
+            "security: downloads enabled, capped at {} bytes per session",
+            cfg.max_download_bytes
+        );
+    }
+    if cfg.host_clipboard && !cfg!(feature = "servo") {
+        log::warn!(
+            "security: host clipboard enabled but stub runtime only has a virtual clipboard"
+        );
+    }
+    if cfg.js_budget_ms.is_some() && !cfg!(feature = "servo") {
+        log::warn!("security: js budget configured but not enforced by stub runtime");
+    }
+    if cfg.dom_mutation_limit.is_some() {
+        log::warn!("security: dom mutation limit configured but not enforced by stub runtime");
+    }
+
+    Ok(())
+}
+
+fn env_bool(key: &str) -> bool {
+    match env::var(key) {
+        Ok(value) => matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        ),
+        Err(_) => false,
+    }
+}
+
+fn env_u64(key: &str) -> Option<u64> {
+    let value = env::var(key).ok()?;
+    if value.trim().is_empty() {
+        return None;
+    }
+    value.trim().parse::<u64>().ok()
+}
+
+fn log_audit_navigation(logger: Option<&AuditLogger>, session_id: &str, url: &str) {
+    let details = serde_json::json!({ "url": url });
+    log_audit_event(logger, session_id, "navigate", details);
+}
+
+/// Replace a value with a SHA-256 prefix and its length, so repeated inputs
+/// still correlate across log lines without exposing their content.
+fn redact(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    let hash: String = digest[..REDACT_HASH_BYTES]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256:{hash}:len={}", value.chars().count())
+}
+
+/// Record a new session's configuration. Request headers can carry
+/// credentials, so only their count is logged.
+fn log_audit_session_created(
+    logger: Option<&AuditLogger>,
+    session_id: &str,
+    config: &pb::SessionConfig,
+) {
+    let mut fields = serde_json::Map::new();
+    if let Some(viewport) = config.viewport.as_ref() {
+        fields.insert("viewport_width".into(), viewport.width.into());
+        fields.insert("viewport_height".into(), viewport.height.into());
+        fields.insert(
+            "device_scale_factor".into(),
+            viewport.device_scale_factor.into(),
+        );
+    }
+    if !config.device_profile.is_empty() {
+        fields.insert(
+            "device_profile".into(),
+            config.device_profile.clone().into(),
+        );
+    }
+    if config.ephemeral {
+        fields.insert("ephemeral".into(), true.into());
+    }
+    fields.insert(
+        "allowlist_len".into(),
+        config.network_allowlist.len().into(),
+    );
+    fields.insert("denylist_len".into(), config.network_denylist.len().into());
+    if let Some(clipboard) = config.clipboard.as_ref() {
+        fields.insert(
+            "clipboard_mode".into(),
+            clipboard_mode_name(clipboard.mode).into(),
+        );
+        fields.insert("clipboard_allow_read".into(), clipboard.allow_read.into());
+        fields.insert("clipboard_allow_write".into(), clipboard.allow_write.into());
+        fields.insert("clipboard_max_bytes".into(), clipboard.max_bytes.into());
+        fields.insert(
+            "clipboard_read_allowlist_len".into(),
+            clipboard.read_allowlist.len().into(),
+        );
+    }
+    if !config.request_headers.is_empty() {
+        fields.insert(
+            "request_header_count".into(),
+            config.request_headers.len().into(),
+        );
+    }
+    log_audit_event(logger, session_id, "session_created", fields.into());
+}
+
+fn log_audit_connection_error(logger: Option<&AuditLogger>, session_id: &str, err: &io::Error) {
+    let details = serde_json::json!({
+        "error_kind": format!("{:?}", err.kind()),
+        "error": err.to_string(),
+    });
+    log_audit_event(logger, session_id, "connection_error", details);
+}
+
+fn log_audit_stream_subscribe(
+    logger: Option<&AuditLogger>,
+    session_id: &str,
+    options: &StreamSettings,
+) {
+    let details = serde_json::json!({
+        "frames": options.include_frames,
+        "dom_diffs": options.include_dom_diffs,
+        "accessibility_diffs": options.include_accessibility_diffs,
+        "hit_test": options.include_hit_test,
+        "console": options.include_console,
+        "target_fps": options.target_fps,
+        "change_only": options.change_only,
+    });
+    log_audit_event(logger, session_id, "stream_subscribe", details);
+}
+
+fn log_audit_action(
+    logger: Option<&AuditLogger>,
+    session_id: &str,
+    action: &pb::Action,
+    state_version: u64,
+) {
+    let mut fields = serde_json::Map::new();
+    fields.insert("action".into(), action_type_name(action.r#type).into());
+    fields.insert("state_version".into(), state_version.into());
+    if action.expected_state_version != 0 {
+        fields.insert(
+            "expected_state_version".into(),
+            action.expected_state_version.into(),
+        );
+    }
+    if action.dry_run {
+        fields.insert("dry_run".into(), true.into());
+    }
+    if !action.text.is_empty() {
+        fields.insert("text_len".into(), action.text.chars().count().into());
+    }
+    if !action.key.is_empty() {
+        fields.insert("key_len".into(), action.key.chars().count().into());
+    }
+    if let Some(scroll) = action.scroll.as_ref() {
+        fields.insert("scroll_x".into(), scroll.x.into());
+        fields.insert("scroll_y".into(), scroll.y.into());
+        fields.insert("scroll_unit".into(), scroll_unit_name(scroll.unit).into());
+    }
+    if let Some(target) = action.target.as_ref() {
+        if target.node_id != 0 {
+            fields.insert("target_node_id".into(), target.node_id.into());
+        }
+        if let Some(point) = target.point.as_ref() {
+            fields.insert("target_x".into(), point.x.into());
+            fields.insert("target_y".into(), point.y.into());
+        }
+        if !target.selector.is_empty() {
+            fields.insert("target_selector".into(), target.selector.clone().into());
+        }
+    }
+    if action.repeat > 1 {
+        fields.insert("repeat".into(), engine::key_repeat(action).into());
+    }
+    if action.r#type == pb::ActionType::Click as i32 {
+        fields.insert("button".into(), mouse_button_name(action.button).into());
+        let (_, click_count) = engine::click_params(action);
+        fields.insert("click_count".into(), click_count.into());
+    }
+    if let Some(drag_to) = action.drag_to.as_ref() {
+        if drag_to.node_id != 0 {
+            fields.insert("drag_to_node_id".into(), drag_to.node_id.into());
+        }
+        if let Some(point) = drag_to.point.as_ref() {
+            fields.insert("drag_to_x".into(), point.x.into());
+            fields.insert("drag_to_y".into(), point.y.into());
+        }
+    }
+    log_audit_event(logger, session_id, "action", fields.into());
+}
+
+/// Append an event to the session's audit log. `details` is a JSON object of
+/// the event's own fields.
+fn log_audit_event(
+    logger: Option<&AuditLogger>,
+    session_id: &str,
+    event: &str,
+    details: serde_json::Value,
+) {
+    let Some(logger) = logger else {
+        return;
+    };
+    let record = AuditRecord::new(event, session_id, logger.details(details));
+    logger
+        .sink
+        .write_line(session_id, &record.to_line(logger.format));
+}
+
+/// Release the audit sink's state for a session that has closed.
+fn close_audit_session(logger: Option<&AuditLogger>, session_id: &str) {
+    if let Some(logger) = logger {
+        logger.sink.close_session(session_id);
+    }
+}
+
+/// Fills in the timing fields a stream only sends when `include_frame_timing`
+/// is set: the frame's slot on the schedule and how long the capture took.
+fn stamp_frame_timing(
+    frame: &mut pb::Frame,
+    presentation_time: SystemTime,
+    capture_start: Instant,
+) {
+    frame.presentation_timestamp = Some(timestamp_from_system_time(presentation_time));
+    frame.capture_duration_us = capture_start.elapsed().as_micros() as u64;
+}
+
+fn timestamp_from_system_time(time: SystemTime) -> prost_types::Timestamp {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    prost_types::Timestamp {
+        seconds: since_epoch.as_secs() as i64,
+        nanos: since_epoch.subsec_nanos() as i32,
+    }
+}
+
+fn current_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+fn action_type_name(action_type: i32) -> &'static str {
+    match pb::ActionType::try_from(action_type).unwrap_or(pb::ActionType::Unspecified) {
+        pb::ActionType::Click => "click",
+        pb::ActionType::Type => "type",
+        pb::ActionType::Scroll => "scroll",
+        pb::ActionType::Hover => "hover",
+        pb::ActionType::Drag => "drag",
+        pb::ActionType::Tab => "tab",
+        pb::ActionType::Key => "key",
+        pb::ActionType::Focus => "focus",
+        pb::ActionType::ClipboardRead => "clipboard_read",
+        pb::ActionType::ClipboardWrite => "clipboard_write",
+        pb::ActionType::SelectOption => "select_option",
+        pb::ActionType::SetChecked => "set_checked",
+        pb::ActionType::TypeInto => "type_into",
+        pb::ActionType::Unspecified => "unspecified",
+    }
+}
+
+fn mouse_button_name(button: i32) -> &'static str {
+    match pb::MouseButton::try_from(button).unwrap_or(pb::MouseButton::Unspecified) {
+        pb::MouseButton::Middle => "middle",
+        pb::MouseButton::Right => "right",
+        pb::MouseButton::Left | pb::MouseButton::Unspecified => "left",
+    }
+}
+
+fn clipboard_mode_name(mode: i32) -> &'static str {
+    match pb::ClipboardMode::try_from(mode).unwrap_or(pb::ClipboardMode::Unspecified) {
+        pb::ClipboardMode::Virtual => "virtual",
+        pb::ClipboardMode::Host => "host",
+        pb::ClipboardMode::Unspecified => "unspecified",
+    }
+}
+
+fn level_filter(level: i32) -> Option<log::LevelFilter> {
+    match pb::LogLevel::try_from(level).ok()? {
+        pb::LogLevel::Unspecified => None,
+        pb::LogLevel::Off => Some(log::LevelFilter::Off),
+        pb::LogLevel::Error => Some(log::LevelFilter::Error),
+        pb::LogLevel::Warn => Some(log::LevelFilter::Warn),
+        pb::LogLevel::Info => Some(log::LevelFilter::Info),
+        pb::LogLevel::Debug => Some(log::LevelFilter::Debug),
+        pb::LogLevel::Trace => Some(log::LevelFilter::Trace),
+    }
+}
+
+fn log_level(level: log::LevelFilter) -> pb::LogLevel {
+    match level {
+        log::LevelFilter::Off => pb::LogLevel::Off,
+        log::LevelFilter::Error => pb::LogLevel::Error,
+        log::LevelFilter::Warn => pb::LogLevel::Warn,
+        log::LevelFilter::Info => pb::LogLevel::Info,
+        log::LevelFilter::Debug => pb::LogLevel::Debug,
+        log::LevelFilter::Trace => pb::LogLevel::Trace,
+    }
+}
+
+fn scroll_unit_name(unit: i32) -> &'static str {
+    match pb::ScrollUnit::try_from(unit).unwrap_or(pb::ScrollUnit::Unspecified) {
+        pb::ScrollUnit::Pixels => "pixels",
+        pb::ScrollUnit::Lines => "lines",
+        pb::ScrollUnit::Unspecified => "units",
+    }
+}
+
+fn sanitize_session_id(session_id: &str) -> String {
+    let mut out = String::new();
+    for ch in session_id.chars() {
+        if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+            out.push(ch);
+        } else {
+            out.push('_');
+        }
+    }
+    if out.is_empty() {
+        "browser".to_string()
+    } else {
+        out
+    }
+}
+
+/// Whether `opts` asks for output that can change without the state version
+/// moving: buffered console messages and page errors, the network-idle wait
+/// and the frame. Such observes always run, even with a matching
+/// `if_state_version_changed`.
+fn requests_volatile_outputs(opts: &pb::ObserveOptions) -> bool {
+    opts.include_console
+        || opts.include_page_errors
+        || opts.until_network_idle_ms > 0
+        || opts.include_frame
+}
+
+fn wrap_response(
+    request_id: String,
+    session_id: String,
+    payload: pb::response::Payload,
+) -> pb::Envelope {
+    pb::Envelope {
+        message: Some(pb::envelope::Message::Response(pb::Response {
+            request_id,
+            session_id,
+            error: None,
+            payload: Some(payload),
+            sequence: 0,
+        })),
+    }
+}
+
+fn error_response(request_id: &str, session_id: &str, code: &str, message: &str) -> pb::Envelope {
+    pb::Envelope {
+        message: Some(pb::envelope::Message::Response(pb::Response {
+            request_id: request_id.to_string(),
+            session_id: session_id.to_string(),
+            error: Some(pb::Error {
+                code: code.to_string(),
+                message: message.to_string(),
+                code_enum: pb::ErrorCode::from(code) as i32,
+            }),
+            payload: None,
+            sequence: 0,
+        })),
+    }
+}
+
+fn read_envelope<R: Read>(stream: &mut R) -> io::Result<Option<pb::Envelope>> {
+    let mut first = [0u8; 1];
+    if let Err(err) = stream.read_exact(&mut first) {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            return Ok(None);
+        }
+        return Err(err);
+    }
+    let mut len_buf = [0u8; 4];
+    let codec = match first[0] {
+        FRAME_TAG_GZIP => {
+            stream.read_exact(&mut len_buf)?;
+            pb::StreamCompression::Gzip
+        }
+        FRAME_TAG_ZSTD => {
+            stream.read_exact(&mut len_buf)?;
+            pb::StreamCompression::Zstd
+        }
+        byte => {
+            len_buf[0] = byte;
+            stream.read_exact(&mut len_buf[1..])?;
+            pb::StreamCompression::None
+        }
+    };
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len == 0 {
+        return Ok(None);
+    }
+    // Checked before allocating so a hostile length prefix can't force a
+    // multi-gigabyte buffer.
+    check_message_size(len, max_message_bytes())?;
+    let mut buf = vec![0u8; len];
+    stream.read_exact(&mut buf)?;
+    let buf = decompress(codec, buf)?;
+    let envelope = pb::Envelope::decode(&*buf)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(Some(envelope))
+}
+
+fn max_message_bytes() -> usize {
+    MAX_MESSAGE_BYTES.load(Ordering::Relaxed)
+}
+
+/// Error payload for a message over the size limit, so writers can tell it
+/// apart from other encode failures.
+#[derive(Debug)]
+struct MessageTooLarge {
+    len: usize,
+    max: usize,
+}
+
+impl fmt::Display for MessageTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "message too large: {} bytes (max {})",
+            self.len, self.max
+        )
+    }
+}
+
+impl std::error::Error for MessageTooLarge {}
+
+fn check_message_size(len: usize, max: usize) -> io::Result<()> {
+    if len > max {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            MessageTooLarge { len, max },
+        ));
+    }
+    Ok(())
+}
+
+/// Write one response. A response over the message limit is replaced by a
+/// `response_too_large` error carrying the same request id and sequence, so
+/// the client gets an answer instead of a dropped connection.
+fn write_response<W: Write>(
+    stream: &mut W,
+    protocol: Protocol,
+    envelope: pb::Envelope,
+) -> io::Result<()> {
+    let (request_id, session_id, sequence) = match envelope.message.as_ref() {
+        Some(pb::envelope::Message::Response(resp)) => (
+            resp.request_id.clone(),
+            resp.session_id.clone(),
+            resp.sequence,
+        ),
+        _ => Default::default(),
+    };
+    // The envelope is encoded before anything is written, so a rejected
+    // response leaves the stream untouched.
+    match protocol.write_envelope(stream, envelope, pb::StreamCompression::None) {
+        Err(err)
+            if err
+                .get_ref()
+                .is_some_and(|inner| inner.is::<MessageTooLarge>()) =>
+        {
+            log::warn!("session {session_id}: response to {request_id:?} not sent: {err}");
+            let mut too_large = error_response(
+                &request_id,
+                &session_id,
+                "response_too_large",
+                &err.to_string(),
+            );
+            if let Some(pb::envelope::Message::Response(resp)) = too_large.message.as_mut() {
+                resp.sequence = sequence;
+            }
+            protocol.write_envelope(stream, too_large, pb::StreamCompression::None)
+        }
+        result => result,
+    }
+}
+
+/// Inflate a frame body, refusing output larger than the message limit.
+fn decompress(codec: pb::StreamCompression, buf: Vec<u8>) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let max = max_message_bytes();
+    let limit = max as u64 + 1;
+    match codec {
+        pb::StreamCompression::None => return Ok(buf),
+        pb::StreamCompression::Gzip => {
+            flate2::read::GzDecoder::new(&*buf)
+                .take(limit)
+                .read_to_end(&mut out)?;
+        }
+        pb::StreamCompression::Zstd => {
+            zstd::stream::read::Decoder::new(&*buf)?
+                .take(limit)
+                .read_to_end(&mut out)?;
+        }
+    }
+    if out.len() > max {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decompressed message exceeds {max} bytes"),
+        ));
+    }
+    Ok(out)
+}
+
+/// Encode an envelope as one wire frame: optional codec tag, big-endian
+/// length, then the body compressed with `codec`. Only stream events are
+/// compressed; requests and responses always use `None` so the handshake
+/// stays readable by clients that don't opt in.
+fn encode_frame(envelope: pb::Envelope, codec: pb::StreamCompression) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    envelope
+        .encode(&mut buf)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let (tag, buf) = match codec {
+        pb::StreamCompression::None => (None, buf),
+        pb::StreamCompression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+            encoder.write_all(&buf)?;
+            (Some(FRAME_TAG_GZIP), encoder.finish()?)
+        }
+        pb::StreamCompression::Zstd => (
+            Some(FRAME_TAG_ZSTD),
+            zstd::stream::encode_all(&*buf, ZSTD_LEVEL)?,
+        ),
+    };
+    check_message_size(buf.len(), max_message_bytes())?;
+    let mut frame = Vec::with_capacity(buf.len() + 5);
+    if let Some(tag) = tag {
+        frame.push(tag);
+    }
+    frame.extend_from_slice(&(buf.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&buf);
+    Ok(frame)
+}
+
+fn ensure_socket_dir(path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_existing_socket(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut socket = env::var("BROWSERD_SOCKET").unwrap_or_else(|_| DEFAULT_SOCKET.to_string());
+    let mut session_id = env::var("BROWSERD_SESSION_ID").ok();
+    let mut protocol = match env::var("BROWSERD_PROTOCOL") {
+        Ok(value) => Protocol::parse(&value)?,
+        Err(_) => Protocol::default(),
+    };
+    let mut log_level = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--socket" => {
+                socket = args
+                    .next()
+                    .ok_or_else(|| "missing value for --socket".to_string())?;
+            }
+            "--session-id" => {
+                session_id = Some(
+                    args.next()
+                        .ok_or_else(|| "missing value for --session-id".to_string())?,
+                );
+            }
+            "--protocol" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "missing value for --protocol".to_string())?;
+                protocol = Protocol::parse(&value)?;
+            }
+            "--log-level" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "missing value for --log-level".to_string())?;
+                log_level = Some(logging::parse_level(&value)?);
+            }
+            "-h" | "--help" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            "--version" => {
+                print_version();
+                std::process::exit(0);
+            }
+            _ => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+
+    Ok(Args {
+        socket: PathBuf::from(socket),
+        session_id,
+        protocol,
+        log_level,
+    })
+}
+
+fn print_usage() {
+    eprintln!(
+        "Usage: browserd [--socket <path>] [--session-id <id>] [--protocol <protobuf|json>] [--log-level <level>]\n\nOptions:\n  --socket <path>       Unix socket path (env: BROWSERD_SOCKET)\n  --session-id <id>     Optional session identifier (env: BROWSERD_SESSION_ID)\n  --protocol <name>     Wire framing: protobuf (default) or json lines (env: BROWSERD_PROTOCOL)\n  --log-level <level>   off, error, warn, info, debug or trace; overrides RUST_LOG\n  -h, --help            Show this help message\n  --version             Show version"
+    );
+}
+
+fn print_version() {
+    println!("browserd {}", env!("CARGO_PKG_VERSION"));
+}
+
+struct SocketGuard {
+    path: PathBuf,
+}
+
+impl SocketGuard {
+    fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Drop for SocketGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_security() -> SecurityConfig {
+        SecurityConfig {
+            enforce_non_root: false,
+            require_seccomp: false,
+            require_cgroup: false,
+            require_readonly_root: false,
+            require_netns: false,
+            assume_external: false,
+            strict: false,
+            downloads_enabled: false,
+            max_download_bytes: engine::DEFAULT_MAX_DOWNLOAD_BYTES,
+            host_clipboard: false,
+            uploads_enabled: false,
+            js_budget_ms: None,
+            dom_mutation_limit: None,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            session_idle_secs: None,
+            max_rps: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            allow_remote_shutdown: false,
+            allow_log_level_change: false,
+            auth_token: None,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            allow_private_hosts: false,
+            allowed_schemes: Vec::new(),
+            global_denylist: Vec::new(),
+            session_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_compressed_envelope_round_trip() {
+        let event = || {
+            wrap_event(pb::StreamEvent {
+                r#type: pb::StreamEventType::DomDiff as i32,
+                state_version: 7,
+                dom_diff: vec![b'x'; 4096],
+                ..Default::default()
+            })
+        };
+        for codec in [
+            pb::StreamCompression::None,
+            pb::StreamCompression::Gzip,
+            pb::StreamCompression::Zstd,
+        ] {
+            let mut pipe = Vec::new();
+            pipe.extend(encode_frame(event(), codec).expect("encode"));
+            pipe.extend(encode_frame(event(), pb::StreamCompression::None).expect("encode plain"));
+            let expected_tag = match codec {
+                pb::StreamCompression::None => None,
+                pb::StreamCompression::Gzip => Some(FRAME_TAG_GZIP),
+                pb::StreamCompression::Zstd => Some(FRAME_TAG_ZSTD),
+            };
+            assert_eq!(expected_tag.is_some(), pipe[0] >= 0x80);
+
+            let mut reader = &pipe[..];
+            for _ in 0..2 {
+                let envelope = read_envelope(&mut reader).expect("read").expect("envelope");
+                assert_eq!(envelope, event());
+            }
+            assert!(read_envelope(&mut reader).expect("eof").is_none());
+        }
+    }
+
+    /// An engine whose runtime has exited: it reports itself dead and every
+    /// call fails the way a `ServoRuntime` with a finished thread does.
+    struct DeadEngine;
+
+    fn unavailable<T>() -> Result<T, EngineError> {
+        Err(EngineError::new("unavailable", "runtime unavailable"))
+    }
+
+    impl BrowserEngine for DeadEngine {
+        fn state_version(&self) -> u64 {
+            0
+        }
+        fn frame_rate(&self) -> u32 {
+            DEFAULT_FRAME_RATE
+        }
+        fn is_alive(&self) -> bool {
+            false
+        }
+        fn navigate(&mut self, _: &str, _: u32) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+        fn observe(&mut self, _: &pb::ObserveOptions) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+        fn network_idle_wait(&self, _: Duration) -> engine::NetworkIdleWait {
+            Box::new(|| false)
+        }
+        fn stop(&mut self) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+        fn act(&mut self, _: &pb::Action) -> Result<pb::ActionResult, EngineError> {
+            unavailable()
+        }
+        fn stream_event(
+            &mut self,
+            _: pb::StreamEventType,
+            _: &HitTestQuery,
+            _: &mut DeltaBase,
+        ) -> Result<pb::StreamEvent, EngineError> {
+            unavailable()
+        }
+        fn check_contrast(&mut self, _: &[u64]) -> Result<Vec<pb::ContrastResult>, EngineError> {
+            unavailable()
+        }
+        fn export_pdf(&mut self, _: Option<&pb::Viewport>) -> Result<Vec<u8>, EngineError> {
+            unavailable()
+        }
+        fn get_cookies(&mut self) -> Result<Vec<pb::Cookie>, EngineError> {
+            unavailable()
+        }
+        fn set_cookie(&mut self, _: &pb::Cookie) -> Result<(), EngineError> {
+            unavailable()
+        }
+        fn clear_cookies(&mut self) -> Result<u32, EngineError> {
+            unavailable()
+        }
+        fn set_storage(
+            &mut self,
+            _: pb::StorageScope,
+            _: &[pb::StorageEntry],
+        ) -> Result<u32, EngineError> {
+            unavailable()
+        }
+        fn get_storage(
+            &mut self,
+            _: pb::StorageScope,
+            _: &[String],
+        ) -> Result<pb::GetStorageResponse, EngineError> {
+            unavailable()
+        }
+        fn clear_storage(&mut self, _: pb::StorageScope) -> Result<u32, EngineError> {
+            unavailable()
+        }
+        fn clear_browsing_data(
+            &mut self,
+            _: &pb::ClearBrowsingDataRequest,
+        ) -> Result<pb::ClearBrowsingDataResponse, EngineError> {
+            unavailable()
+        }
+        fn wait_for(&mut self, _: &str, _: Duration) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+        fn open_tab(&mut self, _: &str) -> Result<pb::Tab, EngineError> {
+            unavailable()
+        }
+        fn close_tab(&mut self, _: u64) -> Result<u64, EngineError> {
+            unavailable()
+        }
+        fn switch_tab(&mut self, _: u64) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+        fn list_tabs(&mut self) -> Result<Vec<pb::Tab>, EngineError> {
+            unavailable()
+        }
+        fn extract_text(&mut self, _: usize) -> Result<pb::ExtractTextResponse, EngineError> {
+            unavailable()
+        }
+        fn clipboard_get(&mut self) -> Result<pb::ClipboardData, EngineError> {
+            unavailable()
+        }
+        fn clipboard_set(&mut self, _: &str) -> Result<pb::ClipboardData, EngineError> {
+            unavailable()
+        }
+        fn fill_form(&mut self, _: &[pb::FormField]) -> Result<pb::FillFormResponse, EngineError> {
+            unavailable()
+        }
+        fn query_element(
+            &mut self,
+            _: &pb::QueryElementRequest,
+        ) -> Result<pb::QueryElementResponse, EngineError> {
+            unavailable()
+        }
+        fn set_file_input(
+            &mut self,
+            _: &pb::SetFileInputRequest,
+        ) -> Result<pb::SetFileInputResponse, EngineError> {
+            unavailable()
+        }
+        fn list_downloads(&mut self) -> Result<pb::ListDownloadsResponse, EngineError> {
+            unavailable()
+        }
+        fn get_download(&mut self, _: &str) -> Result<pb::GetDownloadResponse, EngineError> {
+            unavailable()
+        }
+        fn scroll_position(&mut self) -> Result<pb::Point, EngineError> {
+            unavailable()
+        }
+        fn set_viewport(&mut self, _: &pb::Viewport) -> Result<pb::Observation, EngineError> {
+            unavailable()
+        }
+    }
+
+    /// A writer that replays scripted outcomes: `None` times out, `Some(n)`
+    /// accepts up to `n` bytes. Once the script runs out it accepts everything.
+    struct ThrottledPipe {
+        script: std::collections::VecDeque<Option<usize>>,
+        out: Vec<u8>,
+    }
+
+    impl Write for ThrottledPipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let accept = match self.script.pop_front() {
+                Some(None) => return Err(io::ErrorKind::WouldBlock.into()),
+                Some(Some(n)) => n.min(buf.len()),
+                None => buf.len(),
+            };
+            self.out.extend_from_slice(&buf[..accept]);
+            Ok(accept)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream_heartbeat_on_quiet_stream() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("quiet"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        // A console-only stream on a page that logs nothing sends no events
+        // of its own.
+        let options = normalize_stream_options(
+            Some(pb::StreamOptions {
+                include_console: true,
+                heartbeat_secs: 1,
+                ..Default::default()
+            }),
+            DEFAULT_FRAME_RATE,
+        );
+        let (mut server, client) = UnixStream::pair().expect("socket pair");
+        let streamer = {
+            let sessions = sessions.clone();
+            thread::spawn(move || {
+                stream_events(
+                    &mut server,
+                    "quiet",
+                    &sessions,
+                    None,
+                    &options,
+                    Protocol::Protobuf,
+                    &mut Sequencer::default(),
+                )
+            })
+        };
+
+        client
+            .set_read_timeout(Some(Duration::from_secs(3)))
+            .expect("read timeout");
+        let start = Instant::now();
+        let envelope = Protocol::Protobuf
+            .read_envelope(&mut BufReader::new(&client))
+            .expect("read")
+            .expect("heartbeat");
+        assert!(start.elapsed() < Duration::from_secs(2));
+        match envelope.message {
+            Some(pb::envelope::Message::Event(event)) => {
+                assert_eq!(event.r#type, pb::StreamEventType::Heartbeat as i32);
+                assert_eq!(event.state_version, 1);
+                assert!(event.timestamp.is_some());
+            }
+            other => panic!("expected heartbeat event, got {other:?}"),
+        }
+
+        // Closing the session ends the stream.
+        sessions.lock().unwrap().remove("quiet");
+        streamer
+            .join()
+            .expect("stream thread")
+            .expect("stream result");
+    }
+
+    fn read_stream_frames(
+        session_id: &str,
+        include_frame_timing: bool,
+        count: usize,
+    ) -> Vec<pb::Frame> {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request(session_id),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let options = normalize_stream_options(
+            Some(pb::StreamOptions {
+                include_frames: true,
+                include_frame_timing,
+                target_fps: 20,
+                ..Default::default()
+            }),
+            DEFAULT_FRAME_RATE,
+        );
+        let (mut server, client) = UnixStream::pair().expect("socket pair");
+        let streamer = {
+            let sessions = sessions.clone();
+            let session_id = session_id.to_string();
+            thread::spawn(move || {
+                stream_events(
+                    &mut server,
+                    &session_id,
+                    &sessions,
+                    None,
+                    &options,
+                    Protocol::Protobuf,
+                    &mut Sequencer::default(),
+                )
+            })
+        };
+
+        client
+            .set_read_timeout(Some(Duration::from_secs(3)))
+            .expect("read timeout");
+        let mut reader = BufReader::new(&client);
+        let mut frames = Vec::new();
+        while frames.len() < count {
+            let envelope = Protocol::Protobuf
+                .read_envelope(&mut reader)
+                .expect("read")
+                .expect("event");
+            if let Some(pb::envelope::Message::Event(event)) = envelope.message {
+                if let Some(frame) = event.frame {
+                    frames.push(frame);
+                }
+            }
+        }
+
+        sessions.lock().unwrap().remove(session_id);
+        drop(reader);
+        drop(client);
+        let _ = streamer.join().expect("stream thread");
+        frames
+    }
+
+    fn timestamp_nanos(timestamp: &prost_types::Timestamp) -> i128 {
+        timestamp.seconds as i128 * 1_000_000_000 + timestamp.nanos as i128
+    }
+
+    #[test]
+    fn test_stream_frame_timing_follows_the_schedule() {
+        let frames = read_stream_frames("timed", true, 3);
+        let first = timestamp_nanos(frames[0].presentation_timestamp.as_ref().unwrap());
+        // 20fps puts frames on a 50ms grid from the start of the stream,
+        // however long each capture took.
+        for (n, frame) in frames.iter().enumerate() {
+            let presented = timestamp_nanos(frame.presentation_timestamp.as_ref().unwrap());
+            assert_eq!(presented - first, n as i128 * 50_000_000);
+        }
+    }
+
+    #[test]
+    fn test_stream_frame_timing_only_when_requested() {
+        for frame in read_stream_frames("untimed", false, 2) {
+            assert!(frame.presentation_timestamp.is_none());
+            assert_eq!(frame.capture_duration_us, 0);
+        }
+    }
+
+    #[test]
+    fn test_stamp_frame_timing() {
+        let presentation_time = UNIX_EPOCH + Duration::from_millis(1_500);
+        let capture_start = Instant::now() - Duration::from_millis(5);
+        let mut frame = pb::Frame::default();
+        stamp_frame_timing(&mut frame, presentation_time, capture_start);
+        let presented = frame
+            .presentation_timestamp
+            .expect("presentation timestamp");
+        assert_eq!((presented.seconds, presented.nanos), (1, 500_000_000));
+        assert!(frame.capture_duration_us >= 5_000);
+    }
+
+    #[test]
+    fn test_stream_write_drops_frames_for_slow_reader() {
+        let frame = encode_frame(
+            wrap_event(pb::StreamEvent {
+                r#type: pb::StreamEventType::Frame as i32,
+                state_version: 3,
+                ..Default::default()
+            }),
+            pb::StreamCompression::None,
+        )
+        .expect("encode");
+
+        let mut blocked = ThrottledPipe {
+            script: [None].into_iter().collect(),
+            out: Vec::new(),
+        };
+        assert!(!write_or_drop(&mut blocked, &frame).expect("dropped"));
+        assert!(blocked.out.is_empty());
+        assert!(write_or_drop(&mut blocked, &frame).expect("sent"));
+        assert_eq!(blocked.out, frame);
+
+        // A frame that is partly written is finished rather than dropped.
+        let mut trickle = ThrottledPipe {
+            script: [Some(2), None, None, Some(1)].into_iter().collect(),
+            out: Vec::new(),
+        };
+        assert!(write_or_drop(&mut trickle, &frame).expect("completed"));
+        assert_eq!(trickle.out, frame);
+    }
+
+    #[test]
+    fn test_requeue_console_keeps_dropped_messages_first() {
+        let message = |text: &str| pb::ConsoleMessage {
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let mut event = pb::StreamEvent {
+            console_messages: vec![message("new")],
+            ..Default::default()
+        };
+        requeue_console(vec![message("old")], &mut event);
+        let texts: Vec<&str> = event
+            .console_messages
+            .iter()
+            .map(|m| m.text.as_str())
+            .collect();
+        assert_eq!(texts, ["old", "new"]);
+
+        let mut event = pb::StreamEvent {
+            console_messages: vec![message("new")],
+            ..Default::default()
+        };
+        requeue_console(
+            vec![message("old"); engine::MAX_CONSOLE_MESSAGES],
+            &mut event,
+        );
+        assert_eq!(event.console_messages.len(), engine::MAX_CONSOLE_MESSAGES);
+        assert_eq!(
+            event.console_messages.last().map(|m| m.text.as_str()),
+            Some("new")
+        );
+    }
+
+    #[test]
+    fn test_change_filter_skips_unchanged_diffs() {
+        let config = pb::SessionConfig {
+            session_id: "changes".to_string(),
+            ..Default::default()
+        };
+        let mut engine = engine::new_engine(&config).ok().expect("engine");
+        let mut filter = ChangeFilter::default();
+        let mut next = |engine: &mut Box<dyn BrowserEngine>, event_type| {
+            let event = engine
+                .stream_event(
+                    event_type,
+                    &HitTestQuery::default(),
+                    &mut DeltaBase::default(),
+                )
+                .ok()
+                .expect("event");
+            let change = filter.unsent_change(&event);
+            if let Some(change) = change {
+                filter.mark_sent(change);
+            }
+            change.is_some()
+        };
+
+        assert!(next(&mut engine, pb::StreamEventType::DomDiff));
+        assert!(!next(&mut engine, pb::StreamEventType::DomDiff));
+        assert!(next(&mut engine, pb::StreamEventType::AccessibilityDiff));
+        assert!(!next(&mut engine, pb::StreamEventType::AccessibilityDiff));
+        assert!(next(&mut engine, pb::StreamEventType::Frame));
+        assert!(next(&mut engine, pb::StreamEventType::Frame));
+
+        let hover = pb::Action {
+            r#type: pb::ActionType::Hover as i32,
+            ..Default::default()
+        };
+        engine.act(&hover).ok().expect("act");
+        assert!(next(&mut engine, pb::StreamEventType::DomDiff));
+        assert!(!next(&mut engine, pb::StreamEventType::DomDiff));
+
+        // A change that was never delivered keeps passing the filter.
+        engine.act(&hover).ok().expect("act");
+        let event = engine
+            .stream_event(
+                pb::StreamEventType::DomDiff,
+                &HitTestQuery::default(),
+                &mut DeltaBase::default(),
+            )
+            .ok()
+            .expect("event");
+        assert!(filter.unsent_change(&event).is_some());
+        assert!(filter.unsent_change(&event).is_some());
+    }
+
+    #[test]
+    fn test_oversized_length_prefix_rejected() {
+        let mut pipe = Vec::new();
+        pipe.extend_from_slice(&0x7fff_ffffu32.to_be_bytes());
+        pipe.extend_from_slice(b"short body");
+        let err = read_envelope(&mut &pipe[..]).expect_err("oversized");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        assert!(check_message_size(64, 64).is_ok());
+        let err = check_message_size(65, 64).expect_err("over limit");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_oversized_response_becomes_error() {
+        let mut resp = wrap_response(
+            "big".to_string(),
+            "s1".to_string(),
+            pb::response::Payload::ExtractText(pb::ExtractTextResponse {
+                text: "x".repeat(DEFAULT_MAX_MESSAGE_BYTES + 1),
+                ..Default::default()
+            }),
+        );
+        if let Some(pb::envelope::Message::Response(inner)) = resp.message.as_mut() {
+            inner.sequence = 7;
+        }
+        let mut pipe = Vec::new();
+        write_response(&mut pipe, Protocol::Protobuf, resp).expect("write");
+
+        let resp = response_of(RequestOutcome::Response(
+            read_envelope(&mut &pipe[..])
+                .expect("read")
+                .expect("envelope"),
+            false,
+        ));
+        assert_eq!(resp.request_id, "big");
+        assert_eq!(resp.sequence, 7);
+        let err = resp.error.expect("error");
+        assert_eq!(err.code, "response_too_large");
+        assert_eq!(err.code_enum, pb::ErrorCode::ResponseTooLarge as i32);
+    }
+
+    #[test]
+    fn test_shutdown_request_drains_sessions() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        for id in ["drain-a", "drain-b"] {
+            let _ = handle_request(create_session_request(id), "", &sessions, None, &security);
+        }
+        let shutdown =
+            || session_request("", pb::request::Payload::Shutdown(pb::ShutdownRequest {}));
+
+        let resp = response_of(handle_request(shutdown(), "", &sessions, None, &security));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("unsupported")
+        );
+        assert_eq!(sessions.lock().unwrap().len(), 2);
+
+        security.allow_remote_shutdown = true;
+        let resp = response_of(handle_request(shutdown(), "", &sessions, None, &security));
+        match resp.payload {
+            Some(pb::response::Payload::Shutdown(shutdown)) => {
+                assert_eq!(shutdown.sessions_closed, 2)
+            }
+            other => panic!("expected shutdown response, got {other:?}"),
+        }
+        assert!(sessions.lock().unwrap().is_empty());
+        SHUTDOWN_REQUESTED.store(false, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_set_log_level() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let set_level = |level: pb::LogLevel| {
+            session_request(
+                "",
+                pb::request::Payload::SetLogLevel(pb::SetLogLevelRequest {
+                    level: level as i32,
+                }),
+            )
+        };
+
+        let resp = response_of(handle_request(
+            set_level(pb::LogLevel::Debug),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("unsupported")
+        );
+
+        security.allow_log_level_change = true;
+        let resp = response_of(handle_request(
+            set_level(pb::LogLevel::Unspecified),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
+
+        let before = log::max_level();
+        let resp = response_of(handle_request(
+            set_level(pb::LogLevel::Trace),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::SetLogLevel(resp)) => {
+                assert_eq!(resp.previous, log_level(before) as i32);
+                assert_eq!(resp.level, pb::LogLevel::Trace as i32);
+            }
+            other => panic!("expected set_log_level response, got {other:?}"),
+        }
+        assert_eq!(log::max_level(), log::LevelFilter::Trace);
+        logging::set_level(before);
+
+        assert_eq!(logging::parse_level(" Debug "), Ok(log::LevelFilter::Debug));
+        assert!(logging::parse_level("loud").is_err());
+    }
+
+    #[test]
+    fn test_authentication_gate() {
+        let observe = session_request(
+            "s",
+            pb::request::Payload::ListSessions(pb::ListSessionsRequest {}),
+        );
+        let authenticate = |token: &str| {
+            session_request(
+                "s",
+                pb::request::Payload::Authenticate(pb::AuthenticateRequest {
+                    token: token.to_string(),
+                }),
+            )
+        };
+        let error_code = |resp: Option<pb::Envelope>| match resp.and_then(|env| env.message) {
+            Some(pb::envelope::Message::Response(resp)) => resp.error.map(|err| err.code),
+            other => panic!("unexpected message: {other:?}"),
+        };
+
+        let mut authenticated = false;
+        let token = Some("s3cret");
+        assert_eq!(
+            error_code(check_authentication(&observe, token, &mut authenticated)).as_deref(),
+            Some("unauthenticated")
+        );
+        assert_eq!(
+            error_code(check_authentication(
+                &authenticate("s3cre"),
+                token,
+                &mut authenticated
+            ))
+            .as_deref(),
+            Some("unauthenticated")
+        );
+        assert!(!authenticated);
+
+        assert_eq!(
+            error_code(check_authentication(
+                &authenticate("s3cret"),
+                token,
+                &mut authenticated
+            )),
+            None
+        );
+        assert!(authenticated);
+        assert!(check_authentication(&observe, token, &mut authenticated).is_none());
+
+        let mut open = true;
+        assert!(check_authentication(&observe, None, &mut open).is_none());
+    }
+
+    #[test]
+    fn test_audit_log_rotation() {
+        let dir = env::temp_dir().join(format!("browserd-audit-rotate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), Some(64), 2, AuditFormat::Jsonl);
+        let line = format!("{}\n", "x".repeat(39));
+        for _ in 0..5 {
+            logger.sink.write_line("rotate", &line);
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap_or_default();
+        assert_eq!(read("rotate.jsonl"), line);
+        assert_eq!(read("rotate.jsonl.1"), line);
+        assert_eq!(read("rotate.jsonl.2"), line);
+        assert!(!dir.join("rotate.jsonl.3").exists());
+
+        // Closing the session releases its path lock.
+        let sink = FileSink::new(dir.clone(), None, 0, AuditFormat::Jsonl);
+        sink.write_line("rotate", &line);
+        assert_eq!(sink.locks.lock().unwrap().len(), 1);
+        sink.close_session("rotate");
+        assert!(sink.locks.lock().unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_create_session_writes_audit_event() {
+        let dir = env::temp_dir().join(format!("browserd-audit-create-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), None, 0, AuditFormat::Jsonl);
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut create = create_session_request("audited");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            let config = req.config.as_mut().expect("config");
+            config.network_allowlist = vec!["example.com".to_string()];
+            config
+                .request_headers
+                .insert("Authorization".to_string(), "Bearer secret".to_string());
+        }
+        let resp = response_of(handle_request(
+            create,
+            "",
+            &sessions,
+            Some(&logger),
+            &test_security(),
+        ));
+        assert!(resp.error.is_none());
+
+        let log = fs::read_to_string(dir.join("audited.jsonl")).expect("audit log");
+        let line = log.lines().next().expect("create event");
+        assert!(line.contains("\"event\":\"session_created\""));
+        assert!(line.contains("\"allowlist_len\":1"));
+        assert!(line.contains("\"request_header_count\":1"));
+        assert!(!line.contains("secret"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_connection_error_is_audited() {
+        let dir = env::temp_dir().join(format!("browserd-audit-conn-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), None, 0, AuditFormat::Jsonl);
+        let sessions: SharedSessions = SharedSessions::default();
+        let (server, mut client) = UnixStream::pair().expect("socket pair");
+        client.write_all(b"not json\n").expect("write");
+
+        let mut current_session = "decode".to_string();
+        let err = handle_connection(
+            server,
+            Some("decode"),
+            sessions,
+            Some(&logger),
+            &test_security(),
+            Protocol::Json,
+            &mut current_session,
+        )
+        .expect_err("decode error");
+        log_audit_connection_error(Some(&logger), &current_session, &err);
+
+        let log = fs::read_to_string(dir.join("decode.jsonl")).expect("audit log");
+        assert!(log.contains("\"event\":\"connection_error\""));
+        assert!(log.contains("\"session_id\":\"decode\""));
+        assert!(log.contains("\"error_kind\":\"InvalidData\""));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_connection_sequences_responses() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let (server, mut client) = UnixStream::pair().expect("socket pair");
+        for request_id in ["first", "second", "first", ""] {
+            let req = pb::Request {
+                request_id: request_id.to_string(),
+                ..session_request(
+                    "seq",
+                    pb::request::Payload::ListSessions(pb::ListSessionsRequest {}),
+                )
+            };
+            let envelope = pb::Envelope {
+                message: Some(pb::envelope::Message::Request(req)),
+            };
+            Protocol::Protobuf
+                .write_envelope(&mut client, envelope, pb::StreamCompression::None)
+                .expect("write");
+        }
+        client
+            .shutdown(std::net::Shutdown::Write)
+            .expect("shutdown");
+
+        let mut current_session = String::new();
+        handle_connection(
+            server,
+            None,
+            sessions,
+            None,
+            &test_security(),
+            Protocol::Protobuf,
+            &mut current_session,
+        )
+        .expect("connection");
+
+        let mut reader = BufReader::new(&client);
+        let responses: Vec<pb::Response> = (0..4)
+            .map(
+                |_| match Protocol::Protobuf.read_envelope(&mut reader).expect("read") {
+                    Some(pb::Envelope {
+                        message: Some(pb::envelope::Message::Response(resp)),
+                    }) => resp,
+                    other => panic!("expected response, got {other:?}"),
+                },
+            )
+            .collect();
+        let sequences: Vec<u64> = responses.iter().map(|resp| resp.sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3, 4]);
+        // "first" was answered before it came back, so reusing it is fine.
+        assert!(responses.iter().all(|resp| resp.error.is_none()));
+        assert_eq!(responses[2].request_id, "first");
+    }
+
+    #[test]
+    fn test_action_audit_logs_executed_counts() {
+        let dir = env::temp_dir().join(format!("browserd-audit-action-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), None, 0, AuditFormat::Jsonl);
+        let action = pb::Action {
+            r#type: pb::ActionType::Click as i32,
+            click_count: 50,
+            ..Default::default()
+        };
+        log_audit_action(Some(&logger), "clamped", &action, 1);
+        let key = pb::Action {
+            r#type: pb::ActionType::Key as i32,
+            key: "Tab".to_string(),
+            repeat: 5000,
+            ..Default::default()
+        };
+        log_audit_action(Some(&logger), "clamped", &key, 2);
+
+        let log = fs::read_to_string(dir.join("clamped.jsonl")).expect("audit log");
+        let mut lines = log.lines();
+        let line = lines.next().expect("click event");
+        assert!(line.contains("\"click_count\":3"), "{line}");
+        let line = lines.next().expect("key event");
+        assert!(line.contains("\"repeat\":100"), "{line}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_audit_record_escapes_values() {
+        let awkward = "say \"hi\",\nthen\tleave\u{1}";
+        let record = AuditRecord::new(
+            "navigate",
+            "quote\"d",
+            serde_json::json!({ "url": awkward }),
+        );
+
+        let line = record.to_line(AuditFormat::Jsonl);
+        assert!(line.ends_with('\n'));
+        assert_eq!(line.matches('\n').count(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(line.trim_end()).expect("valid json");
+        assert_eq!(parsed["event"], "navigate");
+        assert_eq!(parsed["session_id"], "quote\"d");
+        assert_eq!(parsed["url"], awkward);
+        assert_eq!(parsed["ts_ms"], record.ts_ms);
+
+        let line = record.to_line(AuditFormat::Csv);
+        let details =
+            serde_json::to_string(&serde_json::json!({ "url": awkward })).expect("details");
+        assert_eq!(
+            line,
+            format!(
+                "{},navigate,\"quote\"\"d\",\"{}\"\n",
+                record.ts_ms,
+                details.replace('"', "\"\"")
+            )
+        );
+        // The details JSON escapes the newline, so the record stays on one line.
+        assert_eq!(line.matches('\n').count(), 1);
+    }
+
+    #[test]
+    fn test_audit_csv_log_has_header() {
+        let dir = env::temp_dir().join(format!("browserd-audit-csv-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), None, 0, AuditFormat::Csv);
+        log_audit_event(
+            Some(&logger),
+            "csv",
+            "session_closed",
+            serde_json::json!({ "reason": "client" }),
+        );
+        log_audit_event(
+            Some(&logger),
+            "csv",
+            "session_closed",
+            serde_json::json!({ "reason": "shutdown" }),
+        );
+
+        let log = fs::read_to_string(dir.join("csv.csv")).expect("audit log");
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], AUDIT_CSV_HEADER.trim_end());
+        assert!(
+            lines[1].ends_with(r#",session_closed,csv,"{""reason"":""client""}""#),
+            "{}",
+            lines[1]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_udp_audit_sink_delivers_lines() {
+        let collector = UdpSocket::bind("127.0.0.1:0").expect("bind collector");
+        collector
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .expect("read timeout");
+        let target = collector.local_addr().expect("collector addr").to_string();
+        let sink = UdpSink::connect(&target).expect("connect sink");
+        let logger = AuditLogger::new(Arc::new(sink), AuditFormat::Jsonl);
+
+        log_audit_navigation(Some(&logger), "udp", "https://example.com/");
+
+        let mut buf = [0u8; 2048];
+        let len = collector.recv(&mut buf).expect("datagram");
+        let line = std::str::from_utf8(&buf[..len]).expect("utf-8");
+        assert!(line.ends_with('\n'));
+        let record: serde_json::Value = serde_json::from_str(line.trim_end()).expect("json line");
+        assert_eq!(record["event"], "navigate");
+        assert_eq!(record["session_id"], "udp");
+        assert_eq!(record["url"], "https://example.com/");
+    }
+
+    #[test]
+    fn test_audit_redaction() {
+        let dir = env::temp_dir().join(format!("browserd-audit-redact-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger =
+            AuditLogger::file(dir.clone(), None, 0, AuditFormat::Jsonl).with_redaction(true);
+        let url = "https://example.com/?q=hunter2";
+        log_audit_navigation(Some(&logger), "redact", url);
+        log_audit_navigation(Some(&logger), "redact", url);
+        let err = io::Error::new(io::ErrorKind::InvalidData, "token=hunter2");
+        log_audit_connection_error(Some(&logger), "redact", &err);
+        log_audit_event(
+            Some(&logger),
+            "redact",
+            "clipboard",
+            serde_json::json!({ "origin": "https://hunter2.example/", "bytes": 6 }),
+        );
+
+        let log = fs::read_to_string(dir.join("redact.jsonl")).expect("audit log");
+        assert!(!log.contains("hunter2"));
+        let records: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        // Redacted values still correlate across lines.
+        assert_eq!(records[0]["url"], records[1]["url"]);
+        assert_eq!(records[0]["url"], redact(url));
+        assert_eq!(records[2]["error"], redact("token=hunter2"));
+        assert_eq!(records[2]["error_kind"], redact("InvalidData"));
+        // Only event fields are hashed; the envelope and numbers are kept.
+        assert_eq!(records[3]["event"], "clipboard");
+        assert_eq!(records[3]["session_id"], "redact");
+        assert_eq!(records[3]["bytes"], 6);
+        assert_eq!(redact("hello"), "sha256:2cf24dba5fb0a30e:len=5");
+        let _ = fs::remove_dir_all(&dir);
+
+        let plain = AuditLogger::file(dir, None, 0, AuditFormat::Jsonl);
+        assert_eq!(
+            plain.details(serde_json::json!({ "url": url })),
+            serde_json::json!({ "url": url })
+        );
+    }
+
+    #[test]
+    fn test_denylist_overrides_allowlist() {
+        let allow = vec!["*.example.com".to_string()];
+        let deny = vec!["admin.example.com".to_string(), "10.0.0.0/8".to_string()];
+        let code = |url: &str, allow: &[String]| {
+            validate_url(url, &[], allow, &deny, false, false)
+                .err()
+                .map(|err| err.code)
+        };
+
+        assert_eq!(
+            code("https://admin.example.com/", &allow),
+            Some("host_denied")
+        );
+        assert_eq!(code("https://www.example.com/", &allow), None);
+        assert_eq!(code("http://10.1.2.3/", &[]), Some("host_denied"));
+        assert_eq!(code("https://public.test/", &[]), None);
+        assert_eq!(
+            code("https://public.test/", &allow),
+            Some("invalid_request")
+        );
     }
-    if !(settings.include_frames
-        || settings.include_dom_diffs
-        || settings.include_accessibility_diffs
-        || settings.include_hit_test)
-    {
-        settings.include_frames = true;
-    }
-    if settings.target_fps == 0 {
-        settings.target_fps = DEFAULT_FRAME_RATE;
+
+    #[test]
+    fn test_strict_mode_rejects_mixed_script_hosts() {
+        let allow = vec!["xn--pypal-4ve.com".to_string()];
+        let code = |url: &str, strict: bool| {
+            validate_url(url, &[], &allow, &[], false, strict)
+                .err()
+                .map(|err| err.code)
+        };
+        assert_eq!(code("https://p\u{0430}ypal.com/", false), None);
+        assert_eq!(
+            code("https://xn--pypal-4ve.com/", true),
+            Some("host_denied")
+        );
+        assert_eq!(
+            code("https://p\u{0430}ypal.com/", true),
+            Some("host_denied")
+        );
     }
-    settings
-}
 
-fn stream_events(
-    stream: &mut UnixStream,
-    session_id: &str,
-    sessions: &SharedSessions,
-    options: &StreamSettings,
-) -> io::Result<()> {
-    let mut fps = options.target_fps;
-    if fps == 0 {
-        fps = DEFAULT_FRAME_RATE;
+    #[test]
+    fn test_private_address_classification() {
+        let private = |ip: &str| is_private_address(ip.parse().expect("ip"));
+        assert!(private("127.0.0.1"));
+        assert!(private("10.20.30.40"));
+        assert!(private("192.168.1.1"));
+        assert!(private("169.254.169.254"));
+        assert!(private("::1"));
+        assert!(private("fd00::1"));
+        assert!(private("::ffff:10.0.0.1"));
+        assert!(!private("93.184.216.34"));
+        assert!(!private("2606:4700::1111"));
     }
-    let interval_ms = std::cmp::max(1, 1000 / fps) as u64;
 
-    loop {
-        let mut send_event = |event_type| -> io::Result<bool> {
-            let result = with_session(sessions, session_id, |entry| entry.engine.stream_event(event_type));
-            let event = match result {
-                Some(Ok(event)) => event,
-                Some(Err(_)) => return Ok(false),
-                None => return Ok(false),
-            };
-            write_envelope(stream, wrap_event(event))?;
-            Ok(true)
+    #[test]
+    fn test_ssrf_guard() {
+        let metadata = "http://169.254.169.254/latest/meta-data/";
+        let code = |allow: &[String], allow_private: bool| {
+            validate_url(metadata, &[], allow, &[], allow_private, false)
+                .err()
+                .map(|err| err.code)
         };
+        assert_eq!(code(&[], false), Some("host_denied"));
+        assert_eq!(code(&[], true), None);
+        assert_eq!(code(&["169.254.169.254".to_string()], false), None);
+        assert_eq!(
+            validate_url("http://[::1]:8080/", &[], &[], &[], false, false)
+                .err()
+                .map(|err| err.code),
+            Some("host_denied")
+        );
+        assert!(validate_url("http://93.184.216.34/", &[], &[], &[], false, false).is_ok());
+    }
 
-        if options.include_frames && !send_event(pb::StreamEventType::Frame)? {
-            return Ok(());
+    #[test]
+    fn test_global_denylist_applies_to_new_sessions() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        security.global_denylist = vec!["blocked.test".to_string()];
+        let mut create = create_session_request("denied");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            req.config.as_mut().expect("config").initial_url = "https://blocked.test/".to_string();
         }
-        if options.include_dom_diffs && !send_event(pb::StreamEventType::DomDiff)? {
-            return Ok(());
-        }
-        if options.include_accessibility_diffs
-            && !send_event(pb::StreamEventType::AccessibilityDiff)?
-        {
-            return Ok(());
-        }
-        if options.include_hit_test && !send_event(pb::StreamEventType::HitTest)? {
-            return Ok(());
-        }
-
-        thread::sleep(Duration::from_millis(interval_ms));
+        let resp = response_of(handle_request(create, "", &sessions, None, &security));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("host_denied")
+        );
     }
-}
 
-fn wrap_event(event: pb::StreamEvent) -> pb::Envelope {
-    pb::Envelope {
-        message: Some(pb::envelope::Message::Event(event)),
+    #[test]
+    fn test_file_scheme_rejected_on_create_and_navigate() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let create = |session_id: &str| {
+            let mut create = create_session_request(session_id);
+            if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+                req.config.as_mut().expect("config").initial_url = "file:///etc/passwd".to_string();
+            }
+            create
+        };
+        let navigate = |session_id: &str| {
+            session_request(
+                session_id,
+                pb::request::Payload::Navigate(pb::NavigateRequest {
+                    url: "file:///etc/passwd".to_string(),
+                    timeout_ms: 0,
+                }),
+            )
+        };
+
+        let resp = response_of(handle_request(
+            create("files"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.message).as_deref(),
+            Some("blocked scheme: file")
+        );
+        let _ = handle_request(
+            create_session_request("files"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let resp = response_of(handle_request(
+            navigate("files"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.message).as_deref(),
+            Some("blocked scheme: file")
+        );
+
+        security.allowed_schemes = url_policy::parse_allowed_schemes("https,file");
+        let resp = response_of(handle_request(
+            create("files-allowed"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert!(resp.error.is_none());
+        let resp = response_of(handle_request(
+            navigate("files-allowed"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert!(resp.error.is_none());
     }
-}
 
-fn validate_url(url: &str, allowlist: &[String]) -> Result<(), String> {
-    let parsed = Url::parse(url).map_err(|_| "invalid url".to_string())?;
-    let scheme = parsed.scheme().to_ascii_lowercase();
-    if scheme == "file" || scheme == "data" || scheme == "javascript" {
-        return Err(format!("blocked scheme: {scheme}"));
+    #[test]
+    fn test_connection_slots_release_on_drop() {
+        let slots = ConnectionSlots::new(2);
+        let first = slots.try_acquire().expect("first slot");
+        let _second = slots.try_acquire().expect("second slot");
+        assert!(slots.try_acquire().is_none());
+        drop(first);
+        assert!(slots.try_acquire().is_some());
+
+        let unlimited = ConnectionSlots::new(0);
+        let permits: Vec<_> = (0..100).filter_map(|_| unlimited.try_acquire()).collect();
+        assert_eq!(permits.len(), 100);
     }
-    if scheme == "about" {
-        return Ok(());
+
+    #[test]
+    fn test_token_bucket_refill() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, start);
+        assert!(bucket.try_acquire(start));
+        assert!(bucket.try_acquire(start));
+        assert!(!bucket.try_acquire(start));
+
+        // Half a second at 2 rps refills exactly one token.
+        let later = start + Duration::from_millis(500);
+        assert!(bucket.try_acquire(later));
+        assert!(!bucket.try_acquire(later));
+
+        // A long idle period refills only up to the burst size.
+        let much_later = later + Duration::from_secs(60);
+        assert!(bucket.try_acquire(much_later));
+        assert!(bucket.try_acquire(much_later));
+        assert!(!bucket.try_acquire(much_later));
     }
-    if scheme != "http" && scheme != "https" {
-        return Err(format!("unsupported scheme: {scheme}"));
+
+    fn create_session_request(session_id: &str) -> pb::Request {
+        pb::Request {
+            request_id: format!("create-{session_id}"),
+            session_id: String::new(),
+            payload: Some(pb::request::Payload::CreateSession(
+                pb::CreateSessionRequest {
+                    config: Some(pb::SessionConfig {
+                        session_id: session_id.to_string(),
+                        ..Default::default()
+                    }),
+                },
+            )),
+        }
     }
-    let host = parsed.host_str().ok_or_else(|| "missing host".to_string())?;
-    if allowlist.is_empty() {
-        return Ok(());
+
+    fn session_request(session_id: &str, payload: pb::request::Payload) -> pb::Request {
+        pb::Request {
+            request_id: "req".to_string(),
+            session_id: session_id.to_string(),
+            payload: Some(payload),
+        }
     }
-    let port = parsed.port_or_known_default();
-    if allowlist_allows(host, port, allowlist) {
-        Ok(())
-    } else {
-        Err("host not in allowlist".to_string())
+
+    fn response_of(outcome: RequestOutcome) -> pb::Response {
+        match outcome {
+            RequestOutcome::Response(envelope, _) => match envelope.message {
+                Some(pb::envelope::Message::Response(resp)) => resp,
+                other => panic!("expected response, got {other:?}"),
+            },
+            RequestOutcome::Stream(_) => panic!("expected response, got stream"),
+        }
     }
-}
 
-fn apply_security_config(cfg: &SecurityConfig) -> io::Result<()> {
-    if cfg.enforce_non_root && unsafe { libc::geteuid() } == 0 {
-        return Err(io::Error::new(
-            io::ErrorKind::PermissionDenied,
-            "browserd must not run as root",
+    #[test]
+    #[cfg(not(feature = "servo"))]
+    fn test_get_info_reports_the_stub_engine() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let response = response_of(handle_request(
+            session_request("", pb::request::Payload::GetInfo(pb::GetInfoRequest {})),
+            "",
+            &sessions,
+            None,
+            &security,
         ));
+        let info = match response.payload {
+            Some(pb::response::Payload::GetInfo(info)) => info,
+            other => panic!("expected info, got {other:?}"),
+        };
+        assert_eq!(info.daemon_version, env!("CARGO_PKG_VERSION"));
+        let engine = info.engine.expect("engine info");
+        assert_eq!(engine.name, "stub");
+        assert_eq!(engine.version, env!("CARGO_PKG_VERSION"));
+        assert!(engine.features.is_empty());
     }
 
-    let mut unmet = Vec::new();
-    if cfg.require_seccomp {
-        unmet.push("seccomp");
+    #[test]
+    fn test_create_session_rejected_at_limit() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        security.max_sessions = 2;
+
+        for id in ["a", "b"] {
+            let resp = response_of(handle_request(
+                create_session_request(id),
+                "",
+                &sessions,
+                None,
+                &security,
+            ));
+            assert!(resp.error.is_none(), "session {id} should be created");
+        }
+
+        let resp = response_of(handle_request(
+            create_session_request("c"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("session_limit")
+        );
+        assert_eq!(sessions.lock().unwrap().len(), 2);
+
+        // Recreating an existing session replaces it rather than counting twice.
+        let resp = response_of(handle_request(
+            create_session_request("a"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert!(resp.error.is_none());
     }
-    if cfg.require_cgroup {
-        unmet.push("cgroup");
+
+    #[test]
+    fn test_evict_idle_sessions() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        for id in ["idle", "active"] {
+            let _ = handle_request(create_session_request(id), "", &sessions, None, &security);
+        }
+        with_session(&sessions, None, "idle", |entry| {
+            entry.last_activity = Instant::now() - Duration::from_secs(120);
+        });
+
+        let evicted = evict_idle_sessions(&sessions, Duration::from_secs(60));
+        let evicted_ids: Vec<&str> = evicted
+            .iter()
+            .map(|entry| entry.session_id.as_str())
+            .collect();
+        assert_eq!(evicted_ids, vec!["idle"]);
+        let map = sessions.lock().unwrap();
+        assert!(map.contains_key("active"));
+        assert!(!map.contains_key("idle"));
     }
-    if cfg.require_readonly_root {
-        unmet.push("read_only_root");
+
+    #[test]
+    fn test_stop_raises_the_session_signal() {
+        let sessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("halt"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let signal = with_session(&sessions, None, "halt", |entry| entry.engine.stop_signal())
+            .expect("session");
+
+        // Raising needs only the signal lock, so it reaches a navigate that
+        // holds the sessions lock.
+        let held = sessions.lock().unwrap();
+        sessions.raise_stop("halt");
+        sessions.raise_stop("missing");
+        drop(held);
+        assert!(signal.take());
+
+        // Stop raises it too, and the engine clears it once stopped.
+        let resp = response_of(handle_request(
+            session_request("halt", pb::request::Payload::Stop(pb::StopRequest {})),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert!(resp.error.is_none());
+        assert!(!signal.take());
     }
-    if cfg.require_netns {
-        unmet.push("netns");
+
+    #[test]
+    fn test_dead_engine_session_is_removed() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        for id in ["dead", "reaped"] {
+            let _ = handle_request(create_session_request(id), "", &sessions, None, &security);
+            let mut map = sessions.lock().unwrap();
+            map.get_mut(id).expect("session").engine = Box::new(DeadEngine);
+        }
+        let err = DeadEngine
+            .observe(&pb::ObserveOptions::default())
+            .expect_err("dead engine fails");
+        assert_eq!(err.code, "unavailable");
+
+        let dir = env::temp_dir().join(format!("browserd-audit-dead-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let logger = AuditLogger::file(dir.clone(), None, 0, AuditFormat::Jsonl);
+        let observe = pb::request::Payload::Observe(pb::ObserveRequest::default());
+        let resp = response_of(handle_request(
+            session_request("dead", observe),
+            "",
+            &sessions,
+            Some(&logger),
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_session")
+        );
+        assert!(!sessions.lock().unwrap().contains_key("dead"));
+        let log = fs::read_to_string(dir.join("dead.jsonl")).expect("audit log");
+        let line = log.lines().next().expect("eviction event");
+        assert!(line.contains("\"event\":\"session_evicted\""));
+        assert!(line.contains("\"reason\":\"engine_exited\""));
+        let _ = fs::remove_dir_all(&dir);
+
+        // The reaper also evicts dead sessions that nobody touches.
+        let evicted = evict_idle_sessions(&sessions, Duration::from_secs(3600));
+        let evicted_ids: Vec<&str> = evicted
+            .iter()
+            .map(|entry| entry.session_id.as_str())
+            .collect();
+        assert_eq!(evicted_ids, vec!["reaped"]);
     }
-    if !unmet.is_empty() && !cfg.assume_external {
-        let message = format!(
-            "security requirements requested but not enforced: {}",
-            unmet.join(", ")
+
+    #[test]
+    fn test_observe_if_state_version_changed() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("etag"),
+            "",
+            &sessions,
+            None,
+            &security,
         );
-        if cfg.strict {
-            return Err(io::Error::new(io::ErrorKind::PermissionDenied, message));
+        let current = with_session(&sessions, None, "etag", |entry| {
+            entry.engine.state_version()
+        })
+        .expect("session");
+
+        let observe = |version: Option<u64>| {
+            let request = pb::ObserveRequest {
+                options: Some(pb::ObserveOptions {
+                    include_dom_snapshot: true,
+                    ..Default::default()
+                }),
+                if_state_version_changed: version,
+            };
+            let resp = response_of(handle_request(
+                session_request("etag", pb::request::Payload::Observe(request)),
+                "",
+                &sessions,
+                None,
+                &security,
+            ));
+            match resp.payload {
+                Some(pb::response::Payload::Observe(observe)) => observe,
+                other => panic!("expected observe response, got {other:?}"),
+            }
+        };
+
+        let unchanged = observe(Some(current));
+        assert!(unchanged.not_modified);
+        assert!(unchanged.observation.is_none());
+
+        for version in [Some(current - 1), None] {
+            let changed = observe(version);
+            assert!(!changed.not_modified);
+            assert_eq!(
+                changed.observation.expect("observation").state_version,
+                current
+            );
         }
-        eprintln!("{message}");
-    }
 
-    if cfg.downloads_enabled {
-        eprintln!("security: downloads enabled (not enforced by stub runtime)");
-    }
-    if cfg.js_budget_ms.is_some() {
-        eprintln!("security: js budget configured but not enforced by stub runtime");
-    }
-    if cfg.dom_mutation_limit.is_some() {
-        eprintln!("security: dom mutation limit configured but not enforced by stub runtime");
+        // Console messages are not tracked by the state version, so asking
+        // for them always observes.
+        let request = pb::ObserveRequest {
+            options: Some(pb::ObserveOptions {
+                include_console: true,
+                ..Default::default()
+            }),
+            if_state_version_changed: Some(current),
+        };
+        let resp = response_of(handle_request(
+            session_request("etag", pb::request::Payload::Observe(request)),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::Observe(observe)) => {
+                assert!(!observe.not_modified);
+                assert!(observe.observation.is_some());
+            }
+            other => panic!("expected observe response, got {other:?}"),
+        }
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_observe_waits_for_network_idle() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("idle"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
 
-fn env_bool(key: &str) -> bool {
-    match env::var(key) {
-        Ok(value) => matches!(
-            value.trim().to_ascii_lowercase().as_str(),
-            "1" | "true" | "yes" | "on"
-        ),
-        Err(_) => false,
+        let observe = |until_network_idle_ms: u32| {
+            let request = pb::ObserveRequest {
+                options: Some(pb::ObserveOptions {
+                    until_network_idle_ms,
+                    ..Default::default()
+                }),
+                if_state_version_changed: None,
+            };
+            let resp = response_of(handle_request(
+                session_request("idle", pb::request::Payload::Observe(request)),
+                "",
+                &sessions,
+                None,
+                &security,
+            ));
+            match resp.payload {
+                Some(pb::response::Payload::Observe(observe)) => {
+                    observe.observation.expect("observation")
+                }
+                other => panic!("expected observe response, got {other:?}"),
+            }
+        };
+
+        assert!(observe(100).network_idle);
+        assert!(!observe(0).network_idle);
     }
-}
 
-fn env_u64(key: &str) -> Option<u64> {
-    let value = env::var(key).ok()?;
-    if value.trim().is_empty() {
-        return None;
+    #[test]
+    fn test_set_cookie_rejects_domain_outside_allowlist() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let mut create = create_session_request("cookies");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            req.config.as_mut().unwrap().network_allowlist = vec!["example.com".to_string()];
+        }
+        let _ = handle_request(create, "", &sessions, None, &security);
+
+        let set_cookie = |domain: &str| {
+            session_request(
+                "cookies",
+                pb::request::Payload::SetCookie(pb::SetCookieRequest {
+                    cookie: Some(pb::Cookie {
+                        name: "sid".to_string(),
+                        value: "abc".to_string(),
+                        domain: domain.to_string(),
+                        ..Default::default()
+                    }),
+                }),
+            )
+        };
+        let resp = response_of(handle_request(
+            set_cookie("evil.test"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        let err = resp.error.expect("cookie should be denied");
+        assert_eq!(err.code, "cookie_denied");
+        assert_eq!(err.code_enum, pb::ErrorCode::CookieDenied as i32);
+
+        let resp = response_of(handle_request(
+            set_cookie("example.com; path=/admin"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(resp.error.expect("error").code, "invalid_request");
+
+        let resp = response_of(handle_request(
+            set_cookie(".example.com"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert!(resp.error.is_none());
+
+        let resp = response_of(handle_request(
+            session_request(
+                "cookies",
+                pb::request::Payload::GetCookies(pb::GetCookiesRequest {}),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::GetCookies(get)) => {
+                assert_eq!(get.cookies.len(), 1);
+                assert_eq!(get.cookies[0].name, "sid");
+            }
+            other => panic!("expected get_cookies response, got {other:?}"),
+        }
     }
-    value.trim().parse::<u64>().ok()
-}
 
-fn log_audit_navigation(logger: Option<&AuditLogger>, session_id: &str, url: &str) {
-    let details = format!("\"url\":\"{}\"", escape_json_string(url));
-    log_audit_event(logger, session_id, "navigate", &details);
-}
+    #[test]
+    fn test_storage_requests_check_origin() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let mut create = create_session_request("storage");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            let config = req.config.as_mut().unwrap();
+            config.network_allowlist = vec!["example.com".to_string()];
+            config.initial_url = "https://example.com/".to_string();
+        }
+        let _ = handle_request(create, "", &sessions, None, &security);
+        let send = |payload| {
+            response_of(handle_request(
+                session_request("storage", payload),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        let set_storage = |key: &str| {
+            pb::request::Payload::SetStorage(pb::SetStorageRequest {
+                scope: pb::StorageScope::Local as i32,
+                entries: vec![pb::StorageEntry {
+                    key: key.to_string(),
+                    value: "1".to_string(),
+                }],
+            })
+        };
 
-fn log_audit_action(
-    logger: Option<&AuditLogger>,
-    session_id: &str,
-    action: &pb::Action,
-    state_version: u64,
-) {
-    let mut fields = Vec::new();
-    fields.push(format!(
-        "\"action\":\"{}\"",
-        escape_json_string(action_type_name(action.r#type))
-    ));
-    fields.push(format!("\"state_version\":{state_version}"));
-    if action.expected_state_version != 0 {
-        fields.push(format!(
-            "\"expected_state_version\":{}",
-            action.expected_state_version
+        let resp = send(set_storage("seen"));
+        match resp.payload {
+            Some(pb::response::Payload::SetStorage(set)) => assert_eq!(set.set, 1),
+            other => panic!("expected set_storage response, got {other:?}"),
+        }
+        let resp = send(set_storage(""));
+        assert_eq!(
+            resp.error.expect("empty key should be rejected").code,
+            "invalid_request"
+        );
+
+        let resp = send(pb::request::Payload::GetStorage(
+            pb::GetStorageRequest::default(),
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::GetStorage(get)) => {
+                assert_eq!(get.origin, "https://example.com");
+                assert_eq!(get.entries.len(), 1);
+                assert_eq!(get.entries[0].key, "seen");
+            }
+            other => panic!("expected get_storage response, got {other:?}"),
+        }
+        let resp = send(pb::request::Payload::GetStorage(pb::GetStorageRequest {
+            scope: pb::StorageScope::Session as i32,
+            keys: Vec::new(),
+        }));
+        match resp.payload {
+            Some(pb::response::Payload::GetStorage(get)) => assert!(get.entries.is_empty()),
+            other => panic!("expected get_storage response, got {other:?}"),
+        }
+
+        // Narrow the allowlist so the current page falls outside it.
+        with_session(&sessions, None, "storage", |entry| {
+            entry.allowlist = vec!["other.test".to_string()]
+        });
+        let resp = send(set_storage("seen"));
+        let err = resp.error.expect("storage should be denied");
+        assert_eq!(err.code, "storage_denied");
+        assert_eq!(err.code_enum, pb::ErrorCode::StorageDenied as i32);
+        let resp = send(pb::request::Payload::ClearStorage(
+            pb::ClearStorageRequest::default(),
         ));
+        assert_eq!(
+            resp.error.expect("clear should be denied").code,
+            "storage_denied"
+        );
     }
-    if !action.text.is_empty() {
-        fields.push(format!("\"text_len\":{}", action.text.chars().count()));
+
+    #[test]
+    fn test_clear_browsing_data() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let mut create = create_session_request("wipe");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            let config = req.config.as_mut().unwrap();
+            config.network_allowlist = vec!["example.com".to_string()];
+            config.initial_url = "https://example.com/".to_string();
+            config.clipboard = Some(pb::ClipboardPolicy {
+                allow_read: true,
+                allow_write: true,
+                ..Default::default()
+            });
+        }
+        let _ = handle_request(create, "", &sessions, None, &security);
+        let send = |payload| {
+            response_of(handle_request(
+                session_request("wipe", payload),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        let fill = || {
+            send(pb::request::Payload::ClipboardSet(
+                pb::ClipboardSetRequest {
+                    text: "secret".to_string(),
+                },
+            ));
+            send(pb::request::Payload::SetStorage(pb::SetStorageRequest {
+                scope: pb::StorageScope::Local as i32,
+                entries: vec![pb::StorageEntry {
+                    key: "token".to_string(),
+                    value: "abc".to_string(),
+                }],
+            }));
+        };
+        let stored = || {
+            with_session(&sessions, None, "wipe", |entry| {
+                let clipboard = entry.engine.clipboard_get().ok().expect("clipboard").text;
+                let storage = entry
+                    .engine
+                    .get_storage(pb::StorageScope::Local, &[])
+                    .ok()
+                    .expect("storage")
+                    .entries
+                    .len();
+                (clipboard, storage)
+            })
+            .expect("session")
+        };
+        let clear = |kinds| match send(pb::request::Payload::ClearBrowsingData(kinds)).payload {
+            Some(pb::response::Payload::ClearBrowsingData(clear)) => clear,
+            other => panic!("expected clear_browsing_data response, got {other:?}"),
+        };
+
+        fill();
+        assert_eq!(stored(), ("secret".to_string(), 1));
+        let cleared = clear(pb::ClearBrowsingDataRequest {
+            storage: true,
+            ..Default::default()
+        });
+        assert_eq!(cleared.cleared, vec!["storage"]);
+        assert!(cleared.partial.is_empty());
+        assert_eq!(stored(), ("secret".to_string(), 0));
+
+        fill();
+        let cleared = clear(pb::ClearBrowsingDataRequest::default());
+        assert_eq!(
+            cleared.cleared,
+            vec!["cookies", "storage", "cache", "clipboard"]
+        );
+        assert!(cleared.partial.is_empty());
+        assert_eq!(stored(), (String::new(), 0));
     }
-    if !action.key.is_empty() {
-        fields.push(format!("\"key_len\":{}", action.key.chars().count()));
+
+    #[test]
+    fn test_tab_requests() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("tabs"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let send = |payload| {
+            response_of(handle_request(
+                session_request("tabs", payload),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        let list_tabs =
+            || match send(pb::request::Payload::ListTabs(pb::ListTabsRequest {})).payload {
+                Some(pb::response::Payload::ListTabs(list)) => list.tabs,
+                other => panic!("expected list_tabs response, got {other:?}"),
+            };
+
+        let tabs = list_tabs();
+        assert_eq!(tabs.len(), 2);
+        assert!(tabs[0].active && !tabs[1].active);
+
+        let resp = send(pb::request::Payload::SwitchTab(pb::SwitchTabRequest {
+            tab_id: tabs[1].tab_id,
+        }));
+        match resp.payload {
+            Some(pb::response::Payload::SwitchTab(switch)) => {
+                assert_eq!(switch.observation.expect("observation").url, "about:blank");
+            }
+            other => panic!("expected switch_tab response, got {other:?}"),
+        }
+
+        let resp = send(pb::request::Payload::OpenTab(pb::OpenTabRequest {
+            url: String::new(),
+        }));
+        let opened = match resp.payload {
+            Some(pb::response::Payload::OpenTab(open)) => open.tab.expect("tab"),
+            other => panic!("expected open_tab response, got {other:?}"),
+        };
+        assert!(opened.active);
+        let tabs = list_tabs();
+        assert_eq!(tabs.len(), 3);
+        assert_eq!(tabs.iter().filter(|tab| tab.active).count(), 1);
+
+        let resp = send(pb::request::Payload::CloseTab(pb::CloseTabRequest {
+            tab_id: opened.tab_id,
+        }));
+        match resp.payload {
+            Some(pb::response::Payload::CloseTab(close)) => {
+                assert_eq!(close.active_tab_id, tabs[1].tab_id)
+            }
+            other => panic!("expected close_tab response, got {other:?}"),
+        }
+        assert_eq!(list_tabs().len(), 2);
+
+        let resp = send(pb::request::Payload::SwitchTab(pb::SwitchTabRequest {
+            tab_id: 99,
+        }));
+        let err = resp.error.expect("unknown tab");
+        assert_eq!(err.code_enum, pb::ErrorCode::TabNotFound as i32);
+
+        let _ = send(pb::request::Payload::CloseTab(pb::CloseTabRequest {
+            tab_id: tabs[0].tab_id,
+        }));
+        let resp = send(pb::request::Payload::CloseTab(pb::CloseTabRequest {
+            tab_id: tabs[1].tab_id,
+        }));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
     }
-    if let Some(scroll) = action.scroll.as_ref() {
-        fields.push(format!("\"scroll_x\":{}", scroll.x));
-        fields.push(format!("\"scroll_y\":{}", scroll.y));
-        fields.push(format!(
-            "\"scroll_unit\":\"{}\"",
-            escape_json_string(scroll_unit_name(scroll.unit))
-        ));
+
+    #[test]
+    fn test_ephemeral_session_cannot_be_saved() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let dir = env::temp_dir().join(format!("browserd-ephemeral-{}", std::process::id()));
+        security.session_dir = Some(dir.clone());
+        let mut create = create_session_request("private");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            req.config.as_mut().unwrap().ephemeral = true;
+        }
+        let _ = handle_request(create, "", &sessions, None, &security);
+
+        let save = session_request(
+            "private",
+            pb::request::Payload::SaveSession(pb::SaveSessionRequest {}),
+        );
+        let resp = response_of(handle_request(save, "", &sessions, None, &security));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
+        assert!(!dir.join("private.json").exists());
     }
-    if let Some(target) = action.target.as_ref() {
-        if target.node_id != 0 {
-            fields.push(format!("\"target_node_id\":{}", target.node_id));
+
+    #[test]
+    fn test_save_and_restore_session() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let save = || {
+            session_request(
+                "saved",
+                pb::request::Payload::SaveSession(pb::SaveSessionRequest {}),
+            )
+        };
+        let restore = || {
+            session_request(
+                "saved",
+                pb::request::Payload::RestoreSession(pb::RestoreSessionRequest {}),
+            )
+        };
+
+        let resp = response_of(handle_request(save(), "", &sessions, None, &security));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("unsupported")
+        );
+
+        let dir = env::temp_dir().join(format!("browserd-sessions-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        security.session_dir = Some(dir.clone());
+        let mut create = create_session_request("saved");
+        if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+            let config = req.config.as_mut().unwrap();
+            config.network_allowlist = vec!["example.com".to_string()];
+            config.initial_url = "https://example.com/cart".to_string();
+        }
+        let _ = handle_request(create, "", &sessions, None, &security);
+        let _ = handle_request(
+            session_request(
+                "saved",
+                pb::request::Payload::SetCookie(pb::SetCookieRequest {
+                    cookie: Some(pb::Cookie {
+                        name: "sid".to_string(),
+                        value: "abc".to_string(),
+                        domain: "example.com".to_string(),
+                        ..Default::default()
+                    }),
+                }),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let _ = handle_request(
+            session_request(
+                "saved",
+                pb::request::Payload::SetViewport(pb::SetViewportRequest {
+                    viewport: Some(pb::Viewport {
+                        width: 640,
+                        height: 480,
+                        device_scale_factor: 0.0,
+                    }),
+                }),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+
+        let resp = response_of(handle_request(save(), "", &sessions, None, &security));
+        match resp.payload {
+            Some(pb::response::Payload::SaveSession(saved)) => {
+                assert_eq!(PathBuf::from(saved.path), dir.join("saved.json"));
+            }
+            other => panic!("expected save_session response, got {other:?}"),
         }
-        if let Some(point) = target.point.as_ref() {
-            fields.push(format!("\"target_x\":{}", point.x));
-            fields.push(format!("\"target_y\":{}", point.y));
+        let _ = handle_request(
+            session_request(
+                "saved",
+                pb::request::Payload::CloseSession(pb::CloseSessionRequest {}),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        assert!(sessions.lock().unwrap().is_empty());
+
+        let resp = response_of(handle_request(restore(), "", &sessions, None, &security));
+        match resp.payload {
+            Some(pb::response::Payload::RestoreSession(restored)) => {
+                assert_eq!(restored.cookies_restored, 1);
+                assert_eq!(
+                    restored.observation.expect("observation").url,
+                    "https://example.com/cart"
+                );
+            }
+            other => panic!("expected restore_session response, got {other:?}"),
         }
+        let map = sessions.lock().unwrap();
+        let entry = map.get("saved").expect("restored session");
+        assert_eq!(
+            entry.config.network_allowlist,
+            vec!["example.com".to_string()]
+        );
+        let viewport = entry.config.viewport.clone().expect("viewport");
+        assert_eq!((viewport.width, viewport.height), (640, 480));
+        drop(map);
+
+        let resp = response_of(handle_request(
+            session_request(
+                "unknown",
+                pb::request::Payload::RestoreSession(pb::RestoreSessionRequest {}),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_session")
+        );
+        let _ = fs::remove_dir_all(&dir);
     }
-    log_audit_event(logger, session_id, "action", &fields.join(","));
-}
 
-fn log_audit_event(logger: Option<&AuditLogger>, session_id: &str, event: &str, details: &str) {
-    let Some(logger) = logger else {
-        return;
-    };
-    let mut line = String::new();
-    line.push_str("{\"ts_ms\":");
-    line.push_str(&current_millis().to_string());
-    line.push_str(",\"event\":\"");
-    line.push_str(&escape_json_string(event));
-    line.push_str("\",\"session_id\":\"");
-    line.push_str(&escape_json_string(session_id));
-    line.push_str("\"");
-    if !details.trim().is_empty() {
-        line.push(',');
-        line.push_str(details);
-    }
-    line.push_str("}\n");
-    logger.write_line(session_id, &line);
-}
+    #[test]
+    fn test_clipboard_requests() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let create = |session_id: &str, policy: pb::ClipboardPolicy| {
+            let mut create = create_session_request(session_id);
+            if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+                req.config.as_mut().unwrap().clipboard = Some(policy);
+            }
+            let _ = handle_request(create, "", &sessions, None, &security);
+        };
+        let get = |session_id: &str| {
+            response_of(handle_request(
+                session_request(
+                    session_id,
+                    pb::request::Payload::ClipboardGet(pb::ClipboardGetRequest {}),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        let set = |session_id: &str, text: &str| {
+            response_of(handle_request(
+                session_request(
+                    session_id,
+                    pb::request::Payload::ClipboardSet(pb::ClipboardSetRequest {
+                        text: text.to_string(),
+                    }),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        let error_code = |resp: pb::Response| resp.error.map(|err| err.code);
 
-fn current_millis() -> u128 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis()
-}
+        create(
+            "clip",
+            pb::ClipboardPolicy {
+                allow_read: true,
+                allow_write: true,
+                max_bytes: 8,
+                ..Default::default()
+            },
+        );
+        match set("clip", "copied").payload {
+            Some(pb::response::Payload::ClipboardSet(resp)) => {
+                let data = resp.data.expect("data");
+                assert_eq!(data.bytes, 6);
+                assert_eq!(data.mode, pb::ClipboardMode::Virtual as i32);
+            }
+            other => panic!("expected clipboard_set response, got {other:?}"),
+        }
+        match get("clip").payload {
+            Some(pb::response::Payload::ClipboardGet(resp)) => {
+                assert_eq!(resp.data.expect("data").text, "copied");
+            }
+            other => panic!("expected clipboard_get response, got {other:?}"),
+        }
+        assert_eq!(
+            error_code(set("clip", "too long for it")).as_deref(),
+            Some("clipboard_limit")
+        );
 
-fn action_type_name(action_type: i32) -> &'static str {
-    match pb::ActionType::try_from(action_type).unwrap_or(pb::ActionType::Unspecified) {
-        pb::ActionType::Click => "click",
-        pb::ActionType::Type => "type",
-        pb::ActionType::Scroll => "scroll",
-        pb::ActionType::Hover => "hover",
-        pb::ActionType::Key => "key",
-        pb::ActionType::Focus => "focus",
-        pb::ActionType::ClipboardRead => "clipboard_read",
-        pb::ActionType::ClipboardWrite => "clipboard_write",
-        pb::ActionType::Unspecified => "unspecified",
-    }
-}
+        create(
+            "locked",
+            pb::ClipboardPolicy {
+                allow_read: false,
+                allow_write: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            error_code(get("locked")).as_deref(),
+            Some("clipboard_denied")
+        );
+        assert_eq!(
+            error_code(set("locked", "x")).as_deref(),
+            Some("clipboard_denied")
+        );
 
-fn scroll_unit_name(unit: i32) -> &'static str {
-    match pb::ScrollUnit::try_from(unit).unwrap_or(pb::ScrollUnit::Unspecified) {
-        pb::ScrollUnit::Pixels => "pixels",
-        pb::ScrollUnit::Lines => "lines",
-        pb::ScrollUnit::Unspecified => "units",
+        create(
+            "allowlisted",
+            pb::ClipboardPolicy {
+                allow_read: true,
+                allow_write: true,
+                read_allowlist: vec!["example.com".to_string()],
+                ..Default::default()
+            },
+        );
+        let resp = get("allowlisted");
+        let err = resp.error.expect("page not on read allowlist");
+        assert_eq!(err.code_enum, pb::ErrorCode::ClipboardDenied as i32);
     }
-}
 
-fn sanitize_session_id(session_id: &str) -> String {
-    let mut out = String::new();
-    for ch in session_id.chars() {
-        if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
-            out.push(ch);
-        } else {
-            out.push('_');
+    #[test]
+    fn test_fill_form_request() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("form"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let fill = |selectors: &[&str]| {
+            let fields = selectors
+                .iter()
+                .map(|selector| pb::FormField {
+                    selector: selector.to_string(),
+                    value: "value".to_string(),
+                })
+                .collect();
+            response_of(handle_request(
+                session_request(
+                    "form",
+                    pb::request::Payload::FillForm(pb::FillFormRequest { fields }),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+
+        match fill(&["#first", "#last"]).payload {
+            Some(pb::response::Payload::FillForm(resp)) => {
+                assert_eq!(resp.results.len(), 2);
+                assert!(resp.results.iter().all(|result| result.filled));
+                assert_eq!(resp.results[1].selector, "#last");
+            }
+            other => panic!("expected fill_form response, got {other:?}"),
         }
+
+        let resp = fill(&[]);
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
+        let resp = fill(&["#first", " "]);
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
+        let resp = fill(&["input"; 101]);
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
     }
-    if out.is_empty() {
-        "browser".to_string()
-    } else {
-        out
-    }
-}
 
-fn escape_json_string(value: &str) -> String {
-    value
-        .replace('\\', "\\\\")
-        .replace('"', "\\\"")
-        .replace('\n', "\\n")
-        .replace('\r', "\\r")
-        .replace('\t', "\\t")
-}
+    #[test]
+    fn test_host_clipboard_requires_opt_in() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let create = || {
+            let mut create = create_session_request("host-clip");
+            if let Some(pb::request::Payload::CreateSession(ref mut req)) = create.payload {
+                req.config.as_mut().unwrap().clipboard = Some(pb::ClipboardPolicy {
+                    mode: pb::ClipboardMode::Host as i32,
+                    ..Default::default()
+                });
+            }
+            create
+        };
 
-fn wrap_response(
-    request_id: String,
-    session_id: String,
-    payload: pb::response::Payload,
-) -> pb::Envelope {
-    pb::Envelope {
-        message: Some(pb::envelope::Message::Response(pb::Response {
-            request_id,
-            session_id,
-            error: None,
-            payload: Some(payload),
-        })),
-    }
-}
+        let resp = response_of(handle_request(create(), "", &sessions, None, &security));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("clipboard_denied")
+        );
+        assert!(sessions.lock().unwrap().is_empty());
 
-fn error_response(request_id: &str, session_id: &str, code: &str, message: &str) -> pb::Envelope {
-    pb::Envelope {
-        message: Some(pb::envelope::Message::Response(pb::Response {
-            request_id: request_id.to_string(),
-            session_id: session_id.to_string(),
-            error: Some(pb::Error {
-                code: code.to_string(),
-                message: message.to_string(),
-            }),
-            payload: None,
-        })),
+        security.host_clipboard = true;
+        let resp = response_of(handle_request(create(), "", &sessions, None, &security));
+        assert!(resp.error.is_none());
     }
-}
 
-fn read_envelope(stream: &mut UnixStream) -> io::Result<Option<pb::Envelope>> {
-    const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+    #[test]
+    fn test_set_file_input_requires_opt_in() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        let _ = handle_request(
+            create_session_request("upload"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let upload = || {
+            session_request(
+                "upload",
+                pb::request::Payload::SetFileInput(pb::SetFileInputRequest {
+                    selector: "#file".to_string(),
+                    filename: "report.csv".to_string(),
+                    data: b"a,b\n".to_vec(),
+                }),
+            )
+        };
+
+        let resp = response_of(handle_request(upload(), "", &sessions, None, &security));
+        let err = resp.error.expect("uploads disabled");
+        assert_eq!(err.code, "uploads_disabled");
+        assert_eq!(err.code_enum, pb::ErrorCode::UploadsDisabled as i32);
 
-    let mut len_buf = [0u8; 4];
-    if let Err(err) = stream.read_exact(&mut len_buf) {
-        if err.kind() == io::ErrorKind::UnexpectedEof {
-            return Ok(None);
+        security.uploads_enabled = true;
+        match response_of(handle_request(upload(), "", &sessions, None, &security)).payload {
+            Some(pb::response::Payload::SetFileInput(resp)) => {
+                assert_eq!(resp.filename, "report.csv");
+                assert_eq!(resp.bytes, 4);
+            }
+            other => panic!("unexpected payload: {other:?}"),
         }
-        return Err(err);
-    }
-    let len = u32::from_be_bytes(len_buf) as usize;
-    if len == 0 {
-        return Ok(None);
-    }
-    if len > MAX_MESSAGE_SIZE {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("message too large: {} bytes (max {})", len, MAX_MESSAGE_SIZE),
-        ));
     }
-    let mut buf = vec![0u8; len];
-    stream.read_exact(&mut buf)?;
-    let envelope = pb::Envelope::decode(&*buf)
-        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
-    Ok(Some(envelope))
-}
 
-fn write_envelope(stream: &mut UnixStream, envelope: pb::Envelope) -> io::Result<()> {
-    let mut buf = Vec::new();
-    envelope
-        .encode(&mut buf)
-        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
-    if buf.len() > u32::MAX as usize {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "message too large",
-        ));
-    }
-    let len = (buf.len() as u32).to_be_bytes();
-    stream.write_all(&len)?;
-    stream.write_all(&buf)?;
-    stream.flush()?;
-    Ok(())
-}
+    #[test]
+    fn test_session_stats_request() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("stats"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let stats = || {
+            response_of(handle_request(
+                session_request(
+                    "stats",
+                    pb::request::Payload::SessionStats(pb::SessionStatsRequest {}),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
 
-fn ensure_socket_dir(path: &Path) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
+        thread::sleep(Duration::from_millis(5));
+        match stats().payload {
+            Some(pb::response::Payload::SessionStats(resp)) => {
+                let engine_version = with_session(&sessions, None, "stats", |entry| {
+                    entry.engine.state_version()
+                });
+                assert_eq!(Some(resp.state_version), engine_version);
+                assert!(resp.uptime_ms >= 5);
+                if cfg!(target_os = "linux") {
+                    assert!(resp.rss_bytes > 0);
+                }
+            }
+            other => panic!("unexpected payload: {other:?}"),
         }
-    }
-    Ok(())
-}
 
-fn remove_existing_socket(path: &Path) -> io::Result<()> {
-    if path.exists() {
-        fs::remove_file(path)?;
+        let missing = response_of(handle_request(
+            session_request(
+                "nope",
+                pb::request::Payload::SessionStats(pb::SessionStatsRequest {}),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            missing.error.map(|err| err.code).as_deref(),
+            Some("invalid_session")
+        );
     }
-    Ok(())
-}
 
-fn parse_args() -> Result<Args, String> {
-    let mut socket = env::var("BROWSERD_SOCKET").unwrap_or_else(|_| DEFAULT_SOCKET.to_string());
-    let mut session_id = env::var("BROWSERD_SESSION_ID").ok();
+    #[test]
+    fn test_batch_actions_run_in_order() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("batch"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let start = with_session(&sessions, None, "batch", |entry| {
+            entry.engine.state_version()
+        })
+        .expect("session");
+        let action = |action_type: pb::ActionType, selector: &str| pb::Action {
+            r#type: action_type as i32,
+            target: Some(pb::ActionTarget {
+                selector: selector.to_string(),
+                ..Default::default()
+            }),
+            text: if action_type == pb::ActionType::Type {
+                "hello".to_string()
+            } else {
+                String::new()
+            },
+            key: if action_type == pb::ActionType::Key {
+                "Enter".to_string()
+            } else {
+                String::new()
+            },
+            ..Default::default()
+        };
+        let batch = |actions: Vec<pb::Action>| {
+            response_of(handle_request(
+                session_request(
+                    "batch",
+                    pb::request::Payload::BatchActions(pb::BatchActionsRequest { actions }),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
 
-    let mut args = env::args().skip(1);
-    while let Some(arg) = args.next() {
-        match arg.as_str() {
-            "--socket" => {
-                socket = args
-                    .next()
-                    .ok_or_else(|| "missing value for --socket".to_string())?;
+        let mut first = action(pb::ActionType::Focus, "input");
+        first.expected_state_version = start;
+        let resp = batch(vec![
+            first,
+            action(pb::ActionType::Type, "input"),
+            action(pb::ActionType::Key, "input"),
+        ]);
+        match resp.payload {
+            Some(pb::response::Payload::BatchActions(resp)) => {
+                assert_eq!(resp.failed_index, -1);
+                assert!(resp.error.is_none());
+                assert_eq!(resp.results.len(), 3);
+                assert_eq!(resp.results[2].state_version, start + 3);
             }
-            "--session-id" => {
-                session_id = Some(
-                    args.next()
-                        .ok_or_else(|| "missing value for --session-id".to_string())?,
+            other => panic!("unexpected payload: {other:?}"),
+        }
+
+        let resp = batch(vec![
+            action(pb::ActionType::Click, "#stub-button"),
+            action(pb::ActionType::Click, "#missing"),
+            action(pb::ActionType::Click, "#stub-button"),
+        ]);
+        match resp.payload {
+            Some(pb::response::Payload::BatchActions(resp)) => {
+                assert_eq!(resp.failed_index, 1);
+                assert_eq!(resp.results.len(), 1);
+                assert_eq!(
+                    resp.error.map(|err| err.code).as_deref(),
+                    Some("invalid_target")
                 );
             }
-            "-h" | "--help" => {
-                print_usage();
-                std::process::exit(0);
-            }
-            "--version" => {
-                print_version();
-                std::process::exit(0);
+            other => panic!("unexpected payload: {other:?}"),
+        }
+        let end = with_session(&sessions, None, "batch", |entry| {
+            entry.engine.state_version()
+        });
+        assert_eq!(end, Some(start + 4));
+
+        let resp = batch(vec![
+            action(pb::ActionType::Hover, "#stub-button");
+            MAX_BATCH_ACTIONS + 1
+        ]);
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("invalid_request")
+        );
+        let after = with_session(&sessions, None, "batch", |entry| {
+            entry.engine.state_version()
+        });
+        assert_eq!(after, end);
+    }
+
+    #[test]
+    fn test_stale_action_policy() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("stale"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let start = with_session(&sessions, None, "stale", |entry| {
+            entry.engine.state_version()
+        })
+        .expect("session");
+        let click = |on_stale: pb::StalePolicy| {
+            response_of(handle_request(
+                session_request(
+                    "stale",
+                    pb::request::Payload::Act(pb::ActRequest {
+                        action: Some(pb::Action {
+                            r#type: pb::ActionType::Click as i32,
+                            expected_state_version: start,
+                            target: Some(pb::ActionTarget {
+                                selector: "#stub-button".to_string(),
+                                ..Default::default()
+                            }),
+                            on_stale: on_stale as i32,
+                            ..Default::default()
+                        }),
+                    }),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        // The first click is current; the rest were built against `start`.
+        let _ = click(pb::StalePolicy::Fail);
+
+        let resp = click(pb::StalePolicy::Fail);
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("stale_state")
+        );
+        match click(pb::StalePolicy::Retry).payload {
+            Some(pb::response::Payload::Act(resp)) => {
+                let result = resp.result.expect("result");
+                assert_eq!(result.state_version, start + 2);
             }
-            _ => return Err(format!("unknown argument: {arg}")),
+            other => panic!("unexpected payload: {other:?}"),
         }
     }
 
-    Ok(Args {
-        socket: PathBuf::from(socket),
-        session_id,
-    })
-}
+    #[test]
+    fn test_download_requests() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let mut security = test_security();
+        security.downloads_enabled = true;
+        let _ = handle_request(
+            create_session_request("downloads"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+        let click = pb::request::Payload::Act(pb::ActRequest {
+            action: Some(pb::Action {
+                r#type: pb::ActionType::Click as i32,
+                target: Some(pb::ActionTarget {
+                    selector: "#stub-download".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        });
+        let _ = handle_request(
+            session_request("downloads", click),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
 
-fn print_usage() {
-    eprintln!(
-        "Usage: browserd [--socket <path>] [--session-id <id>]\n\nOptions:\n  --socket <path>       Unix socket path (env: BROWSERD_SOCKET)\n  --session-id <id>     Optional session identifier (env: BROWSERD_SESSION_ID)\n  -h, --help            Show this help message\n  --version             Show version"
-    );
-}
+        let list = response_of(handle_request(
+            session_request(
+                "downloads",
+                pb::request::Payload::ListDownloads(pb::ListDownloadsRequest {}),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        let download = match list.payload {
+            Some(pb::response::Payload::ListDownloads(resp)) => {
+                assert!(resp.enabled);
+                assert_eq!(resp.max_bytes, engine::DEFAULT_MAX_DOWNLOAD_BYTES);
+                resp.downloads.into_iter().next().expect("download listed")
+            }
+            other => panic!("unexpected payload: {other:?}"),
+        };
 
-fn print_version() {
-    println!("browserd {}", env!("CARGO_PKG_VERSION"));
-}
+        let get = |download_id: &str| {
+            response_of(handle_request(
+                session_request(
+                    "downloads",
+                    pb::request::Payload::GetDownload(pb::GetDownloadRequest {
+                        download_id: download_id.to_string(),
+                    }),
+                ),
+                "",
+                &sessions,
+                None,
+                &security,
+            ))
+        };
+        match get(&download.id).payload {
+            Some(pb::response::Payload::GetDownload(resp)) => {
+                assert_eq!(resp.data.len() as u64, download.bytes);
+            }
+            other => panic!("unexpected payload: {other:?}"),
+        }
+        let err = get("missing").error.expect("unknown download");
+        assert_eq!(err.code_enum, pb::ErrorCode::DownloadNotFound as i32);
+    }
 
-struct SocketGuard {
-    path: PathBuf,
-}
+    #[test]
+    fn test_wait_for_request() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("wait"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
 
-impl SocketGuard {
-    fn new(path: PathBuf) -> Self {
-        Self { path }
+        let wait_for = |selector: &str| {
+            session_request(
+                "wait",
+                pb::request::Payload::WaitFor(pb::WaitForRequest {
+                    selector: selector.to_string(),
+                    timeout_ms: 50,
+                }),
+            )
+        };
+        let resp = response_of(handle_request(
+            wait_for("[data-buckley-ready]"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::WaitFor(wait)) => assert!(wait.observation.is_some()),
+            other => panic!("expected wait_for response, got {other:?}"),
+        }
+
+        let resp = response_of(handle_request(
+            wait_for("#missing"),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        assert_eq!(
+            resp.error.map(|err| err.code).as_deref(),
+            Some("wait_timeout")
+        );
     }
-}
 
-impl Drop for SocketGuard {
-    fn drop(&mut self) {
-        let _ = fs::remove_file(&self.path);
+    #[test]
+    fn test_export_pdf_request() {
+        let sessions: SharedSessions = SharedSessions::default();
+        let security = test_security();
+        let _ = handle_request(
+            create_session_request("pdf"),
+            "",
+            &sessions,
+            None,
+            &security,
+        );
+
+        let resp = response_of(handle_request(
+            session_request(
+                "pdf",
+                pb::request::Payload::ExportPdf(pb::ExportPdfRequest { page_size: None }),
+            ),
+            "",
+            &sessions,
+            None,
+            &security,
+        ));
+        match resp.payload {
+            Some(pb::response::Payload::ExportPdf(export)) => {
+                assert!(export.data.starts_with(b"%PDF-"));
+            }
+            other => panic!("expected export_pdf response, got {other:?}"),
+        }
     }
 }
@@ -0,0 +1,162 @@
+//! Session snapshots on disk.
+//!
+//! `SaveSession` writes a session's config, URL, cookies and scroll offset to
+//! `<BROWSERD_SESSION_DIR>/<session>.json`, and `RestoreSession` rebuilds the
+//! session on a fresh engine from that file, e.g. after a daemon restart.
+//! Snapshots hold live cookies, so they are only readable by the daemon's
+//! user, and credential request headers are never written.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::proto as pb;
+
+/// Bumped whenever the snapshot layout changes incompatibly; older files are
+/// rejected rather than half-restored.
+pub(crate) const SNAPSHOT_VERSION: u32 = 1;
+
+/// Request headers left out of snapshots, compared case-insensitively. A
+/// restored session that needs them has to be given them again.
+const CREDENTIAL_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SessionSnapshot {
+    pub(crate) version: u32,
+    pub(crate) config: pb::SessionConfig,
+    pub(crate) url: String,
+    pub(crate) cookies: Vec<pb::Cookie>,
+    pub(crate) scroll_x: i32,
+    pub(crate) scroll_y: i32,
+}
+
+fn snapshot_path(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", crate::sanitize_session_id(session_id)))
+}
+
+/// Write `snapshot` with mode 0600, replacing any earlier snapshot of the
+/// same session. `CREDENTIAL_HEADERS` are dropped from its config.
+pub(crate) fn write_snapshot(dir: &Path, snapshot: &SessionSnapshot) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = snapshot_path(dir, &snapshot.config.session_id);
+    let mut snapshot = snapshot.clone();
+    snapshot
+        .config
+        .request_headers
+        .retain(|name, _| !CREDENTIAL_HEADERS.contains(&name.to_ascii_lowercase().as_str()));
+    let data = serde_json::to_vec_pretty(&snapshot)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    // Write then rename so a crash mid-save never leaves a truncated file. A
+    // temp file left by such a crash is removed first, since the mode only
+    // applies to newly created files.
+    let tmp = path.with_extension("json.tmp");
+    let _ = fs::remove_file(&tmp);
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&tmp)?;
+    file.write_all(&data)?;
+    fs::rename(&tmp, &path)?;
+    Ok(path)
+}
+
+pub(crate) fn read_snapshot(dir: &Path, session_id: &str) -> io::Result<SessionSnapshot> {
+    let data = fs::read(snapshot_path(dir, session_id))?;
+    let snapshot: SessionSnapshot = serde_json::from_slice(&data)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported snapshot version {}", snapshot.version),
+        ));
+    }
+    // File names are sanitized, so distinct ids can share a file.
+    if snapshot.config.session_id != session_id {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("snapshot belongs to session {}", snapshot.config.session_id),
+        ));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn snapshot(session_id: &str) -> SessionSnapshot {
+        SessionSnapshot {
+            version: SNAPSHOT_VERSION,
+            config: pb::SessionConfig {
+                session_id: session_id.to_string(),
+                network_allowlist: vec!["example.com".to_string()],
+                ..Default::default()
+            },
+            url: "https://example.com/cart".to_string(),
+            cookies: vec![pb::Cookie {
+                name: "sid".to_string(),
+                value: "abc".to_string(),
+                domain: "example.com".to_string(),
+                path: "/".to_string(),
+                ..Default::default()
+            }],
+            scroll_x: 0,
+            scroll_y: 640,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = env::temp_dir().join(format!("browserd-persist-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let saved = snapshot("warm.restart");
+        let path = write_snapshot(&dir, &saved).expect("write");
+        assert_eq!(path, dir.join("warm_restart.json"));
+        assert_eq!(read_snapshot(&dir, "warm.restart").expect("read"), saved);
+
+        // "warm_restart" sanitizes to the same file name.
+        let err = read_snapshot(&dir, "warm_restart").expect_err("id mismatch");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = read_snapshot(&dir, "missing").expect_err("no snapshot");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_snapshot_is_private_and_drops_credentials() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = env::temp_dir().join(format!("browserd-persist-private-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut saved = snapshot("private");
+        saved.config.request_headers = [
+            ("Authorization".to_string(), "Bearer secret".to_string()),
+            ("cookie".to_string(), "sid=abc".to_string()),
+            ("X-Trace".to_string(), "1".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let path = write_snapshot(&dir, &saved).expect("write");
+        let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let restored = read_snapshot(&dir, "private").expect("read");
+        let names: Vec<&str> = restored
+            .config
+            .request_headers
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(names, ["X-Trace"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,100 @@
+//! Which URL schemes sessions may load.
+//!
+//! The daemon checks request URLs against this policy and the engines check
+//! again before loading, so a URL that reaches an engine some other way (a
+//! restored snapshot, a new tab) is held to the same rules.
+
+use url::Url;
+
+use crate::engine::EngineError;
+
+/// Schemes allowed when `BROWSERD_ALLOWED_SCHEMES` is unset.
+pub(crate) const DEFAULT_ALLOWED_SCHEMES: &[&str] = &["http", "https", "about"];
+/// Refused unless explicitly allowed; reported as blocked rather than
+/// unsupported since they read local data or run script.
+const SENSITIVE_SCHEMES: &[&str] = &["file", "data", "javascript"];
+/// Never allowed: a `javascript:` URL runs in whatever page is loaded.
+const NEVER_ALLOWED_SCHEMES: &[&str] = &["javascript"];
+/// Always allowed, even when a configured list leaves them out: engines open
+/// new tabs on about:blank.
+const ALWAYS_ALLOWED_SCHEMES: &[&str] = &["about"];
+
+/// Parse a comma-separated scheme list such as `BROWSERD_ALLOWED_SCHEMES`.
+/// Blank entries and schemes that can never be allowed are dropped; an
+/// empty result means the defaults.
+pub(crate) fn parse_allowed_schemes(value: &str) -> Vec<String> {
+    let mut schemes: Vec<String> = Vec::new();
+    for scheme in value.split(',') {
+        let scheme = scheme.trim().trim_end_matches(':').to_ascii_lowercase();
+        if scheme.is_empty() || NEVER_ALLOWED_SCHEMES.contains(&scheme.as_str()) {
+            continue;
+        }
+        if !schemes.contains(&scheme) {
+            schemes.push(scheme);
+        }
+    }
+    schemes
+}
+
+/// Whether `url`'s scheme may be loaded. `allowed` is a list from
+/// `parse_allowed_schemes`; empty uses `DEFAULT_ALLOWED_SCHEMES`. `about`
+/// is allowed either way.
+pub(crate) fn check_scheme(url: &Url, allowed: &[String]) -> Result<(), EngineError> {
+    let scheme = url.scheme();
+    let permitted = if allowed.is_empty() {
+        DEFAULT_ALLOWED_SCHEMES.contains(&scheme)
+    } else {
+        ALWAYS_ALLOWED_SCHEMES.contains(&scheme) || allowed.iter().any(|entry| entry == scheme)
+    };
+    if permitted && !NEVER_ALLOWED_SCHEMES.contains(&scheme) {
+        return Ok(());
+    }
+    if SENSITIVE_SCHEMES.contains(&scheme) {
+        Err(EngineError::new(
+            "invalid_request",
+            format!("blocked scheme: {scheme}"),
+        ))
+    } else {
+        Err(EngineError::new(
+            "invalid_request",
+            format!("unsupported scheme: {scheme}"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(url: &str, allowed: &[String]) -> Result<(), String> {
+        check_scheme(&Url::parse(url).expect("url"), allowed).map_err(|err| err.message)
+    }
+
+    #[test]
+    fn test_default_scheme_policy() {
+        assert!(check("https://example.com/", &[]).is_ok());
+        assert!(check("about:blank", &[]).is_ok());
+        assert_eq!(
+            check("file:///etc/passwd", &[]),
+            Err("blocked scheme: file".to_string())
+        );
+        assert_eq!(
+            check("data:text/html,hi", &[]),
+            Err("blocked scheme: data".to_string())
+        );
+        assert_eq!(
+            check("ftp://example.com/", &[]),
+            Err("unsupported scheme: ftp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_configured_schemes_replace_defaults() {
+        let allowed = parse_allowed_schemes(" HTTPS, file:, javascript ,,https");
+        assert_eq!(allowed, vec!["https".to_string(), "file".to_string()]);
+        assert!(check("file:///tmp/page.html", &allowed).is_ok());
+        assert!(check("about:blank", &allowed).is_ok());
+        assert!(check("http://example.com/", &allowed).is_err());
+        assert!(check("javascript:alert(1)", &["javascript".to_string()]).is_err());
+    }
+}
@@ -23,6 +23,251 @@ const (
 	_ = protoimpl.EnforceVersion(protoimpl.MaxVersion - 20)
 )
 
+type ErrorCode int32
+
+const (
+	ErrorCode_ERROR_CODE_UNSPECIFIED            ErrorCode = 0
+	ErrorCode_ERROR_CODE_INVALID_REQUEST        ErrorCode = 1
+	ErrorCode_ERROR_CODE_INVALID_SESSION        ErrorCode = 2
+	ErrorCode_ERROR_CODE_STALE_STATE            ErrorCode = 3
+	ErrorCode_ERROR_CODE_LOAD_TIMEOUT           ErrorCode = 4
+	ErrorCode_ERROR_CODE_SCRIPT_ERROR           ErrorCode = 5
+	ErrorCode_ERROR_CODE_SCRIPT_TIMEOUT         ErrorCode = 6
+	ErrorCode_ERROR_CODE_UNAVAILABLE            ErrorCode = 7
+	ErrorCode_ERROR_CODE_INVALID_URL            ErrorCode = 8
+	ErrorCode_ERROR_CODE_INVALID_TARGET         ErrorCode = 9
+	ErrorCode_ERROR_CODE_NO_WEBVIEW             ErrorCode = 10
+	ErrorCode_ERROR_CODE_RENDER_FAILED          ErrorCode = 11
+	ErrorCode_ERROR_CODE_RENDERING_INIT         ErrorCode = 12
+	ErrorCode_ERROR_CODE_CLIPBOARD_DENIED       ErrorCode = 13
+	ErrorCode_ERROR_CODE_CLIPBOARD_LIMIT        ErrorCode = 14
+	ErrorCode_ERROR_CODE_COOKIE_DENIED          ErrorCode = 15
+	ErrorCode_ERROR_CODE_WAIT_TIMEOUT           ErrorCode = 16
+	ErrorCode_ERROR_CODE_SESSION_LIMIT          ErrorCode = 17
+	ErrorCode_ERROR_CODE_UNSUPPORTED            ErrorCode = 18
+	ErrorCode_ERROR_CODE_RATE_LIMITED           ErrorCode = 19
+	ErrorCode_ERROR_CODE_SCRIPT_BUDGET_EXCEEDED ErrorCode = 20
+	ErrorCode_ERROR_CODE_LOAD_FAILED            ErrorCode = 21
+	ErrorCode_ERROR_CODE_UNAUTHENTICATED        ErrorCode = 22
+	ErrorCode_ERROR_CODE_HOST_DENIED            ErrorCode = 23
+	ErrorCode_ERROR_CODE_ACTION_TIMEOUT         ErrorCode = 24
+	ErrorCode_ERROR_CODE_TAB_NOT_FOUND          ErrorCode = 25
+	ErrorCode_ERROR_CODE_UPLOADS_DISABLED       ErrorCode = 26
+	ErrorCode_ERROR_CODE_DOWNLOAD_NOT_FOUND     ErrorCode = 27
+	ErrorCode_ERROR_CODE_STORAGE_DENIED         ErrorCode = 28
+	ErrorCode_ERROR_CODE_ENGINE_PANIC           ErrorCode = 29
+	ErrorCode_ERROR_CODE_RESPONSE_TOO_LARGE     ErrorCode = 30
+)
+
+// Enum value maps for ErrorCode.
+var (
+	ErrorCode_name = map[int32]string{
+		0:  "ERROR_CODE_UNSPECIFIED",
+		1:  "ERROR_CODE_INVALID_REQUEST",
+		2:  "ERROR_CODE_INVALID_SESSION",
+		3:  "ERROR_CODE_STALE_STATE",
+		4:  "ERROR_CODE_LOAD_TIMEOUT",
+		5:  "ERROR_CODE_SCRIPT_ERROR",
+		6:  "ERROR_CODE_SCRIPT_TIMEOUT",
+		7:  "ERROR_CODE_UNAVAILABLE",
+		8:  "ERROR_CODE_INVALID_URL",
+		9:  "ERROR_CODE_INVALID_TARGET",
+		10: "ERROR_CODE_NO_WEBVIEW",
+		11: "ERROR_CODE_RENDER_FAILED",
+		12: "ERROR_CODE_RENDERING_INIT",
+		13: "ERROR_CODE_CLIPBOARD_DENIED",
+		14: "ERROR_CODE_CLIPBOARD_LIMIT",
+		15: "ERROR_CODE_COOKIE_DENIED",
+		16: "ERROR_CODE_WAIT_TIMEOUT",
+		17: "ERROR_CODE_SESSION_LIMIT",
+		18: "ERROR_CODE_UNSUPPORTED",
+		19: "ERROR_CODE_RATE_LIMITED",
+		20: "ERROR_CODE_SCRIPT_BUDGET_EXCEEDED",
+		21: "ERROR_CODE_LOAD_FAILED",
+		22: "ERROR_CODE_UNAUTHENTICATED",
+		23: "ERROR_CODE_HOST_DENIED",
+		24: "ERROR_CODE_ACTION_TIMEOUT",
+		25: "ERROR_CODE_TAB_NOT_FOUND",
+		26: "ERROR_CODE_UPLOADS_DISABLED",
+		27: "ERROR_CODE_DOWNLOAD_NOT_FOUND",
+		28: "ERROR_CODE_STORAGE_DENIED",
+		29: "ERROR_CODE_ENGINE_PANIC",
+		30: "ERROR_CODE_RESPONSE_TOO_LARGE",
+	}
+	ErrorCode_value = map[string]int32{
+		"ERROR_CODE_UNSPECIFIED":            0,
+		"ERROR_CODE_INVALID_REQUEST":        1,
+		"ERROR_CODE_INVALID_SESSION":        2,
+		"ERROR_CODE_STALE_STATE":            3,
+		"ERROR_CODE_LOAD_TIMEOUT":           4,
+		"ERROR_CODE_SCRIPT_ERROR":           5,
+		"ERROR_CODE_SCRIPT_TIMEOUT":         6,
+		"ERROR_CODE_UNAVAILABLE":            7,
+		"ERROR_CODE_INVALID_URL":            8,
+		"ERROR_CODE_INVALID_TARGET":         9,
+		"ERROR_CODE_NO_WEBVIEW":             10,
+		"ERROR_CODE_RENDER_FAILED":          11,
+		"ERROR_CODE_RENDERING_INIT":         12,
+		"ERROR_CODE_CLIPBOARD_DENIED":       13,
+		"ERROR_CODE_CLIPBOARD_LIMIT":        14,
+		"ERROR_CODE_COOKIE_DENIED":          15,
+		"ERROR_CODE_WAIT_TIMEOUT":           16,
+		"ERROR_CODE_SESSION_LIMIT":          17,
+		"ERROR_CODE_UNSUPPORTED":            18,
+		"ERROR_CODE_RATE_LIMITED":           19,
+		"ERROR_CODE_SCRIPT_BUDGET_EXCEEDED": 20,
+		"ERROR_CODE_LOAD_FAILED":            21,
+		"ERROR_CODE_UNAUTHENTICATED":        22,
+		"ERROR_CODE_HOST_DENIED":            23,
+		"ERROR_CODE_ACTION_TIMEOUT":         24,
+		"ERROR_CODE_TAB_NOT_FOUND":          25,
+		"ERROR_CODE_UPLOADS_DISABLED":       26,
+		"ERROR_CODE_DOWNLOAD_NOT_FOUND":     27,
+		"ERROR_CODE_STORAGE_DENIED":         28,
+		"ERROR_CODE_ENGINE_PANIC":           29,
+		"ERROR_CODE_RESPONSE_TOO_LARGE":     30,
+	}
+)
+
+func (x ErrorCode) Enum() *ErrorCode {
+	p := new(ErrorCode)
+	*p = x
+	return p
+}
+
+func (x ErrorCode) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (ErrorCode) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[0].Descriptor()
+}
+
+func (ErrorCode) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[0]
+}
+
+func (x ErrorCode) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use ErrorCode.Descriptor instead.
+func (ErrorCode) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{0}
+}
+
+// Which Web Storage area of the active page's origin a request targets.
+type StorageScope int32
+
+const (
+	// Treated as local.
+	StorageScope_STORAGE_SCOPE_UNSPECIFIED StorageScope = 0
+	StorageScope_STORAGE_SCOPE_LOCAL       StorageScope = 1
+	StorageScope_STORAGE_SCOPE_SESSION     StorageScope = 2
+)
+
+// Enum value maps for StorageScope.
+var (
+	StorageScope_name = map[int32]string{
+		0: "STORAGE_SCOPE_UNSPECIFIED",
+		1: "STORAGE_SCOPE_LOCAL",
+		2: "STORAGE_SCOPE_SESSION",
+	}
+	StorageScope_value = map[string]int32{
+		"STORAGE_SCOPE_UNSPECIFIED": 0,
+		"STORAGE_SCOPE_LOCAL":       1,
+		"STORAGE_SCOPE_SESSION":     2,
+	}
+)
+
+func (x StorageScope) Enum() *StorageScope {
+	p := new(StorageScope)
+	*p = x
+	return p
+}
+
+func (x StorageScope) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (StorageScope) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[1].Descriptor()
+}
+
+func (StorageScope) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[1]
+}
+
+func (x StorageScope) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use StorageScope.Descriptor instead.
+func (StorageScope) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{1}
+}
+
+type LogLevel int32
+
+const (
+	LogLevel_LOG_LEVEL_UNSPECIFIED LogLevel = 0
+	LogLevel_LOG_LEVEL_OFF         LogLevel = 1
+	LogLevel_LOG_LEVEL_ERROR       LogLevel = 2
+	LogLevel_LOG_LEVEL_WARN        LogLevel = 3
+	LogLevel_LOG_LEVEL_INFO        LogLevel = 4
+	LogLevel_LOG_LEVEL_DEBUG       LogLevel = 5
+	LogLevel_LOG_LEVEL_TRACE       LogLevel = 6
+)
+
+// Enum value maps for LogLevel.
+var (
+	LogLevel_name = map[int32]string{
+		0: "LOG_LEVEL_UNSPECIFIED",
+		1: "LOG_LEVEL_OFF",
+		2: "LOG_LEVEL_ERROR",
+		3: "LOG_LEVEL_WARN",
+		4: "LOG_LEVEL_INFO",
+		5: "LOG_LEVEL_DEBUG",
+		6: "LOG_LEVEL_TRACE",
+	}
+	LogLevel_value = map[string]int32{
+		"LOG_LEVEL_UNSPECIFIED": 0,
+		"LOG_LEVEL_OFF":         1,
+		"LOG_LEVEL_ERROR":       2,
+		"LOG_LEVEL_WARN":        3,
+		"LOG_LEVEL_INFO":        4,
+		"LOG_LEVEL_DEBUG":       5,
+		"LOG_LEVEL_TRACE":       6,
+	}
+)
+
+func (x LogLevel) Enum() *LogLevel {
+	p := new(LogLevel)
+	*p = x
+	return p
+}
+
+func (x LogLevel) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (LogLevel) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[2].Descriptor()
+}
+
+func (LogLevel) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[2]
+}
+
+func (x LogLevel) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use LogLevel.Descriptor instead.
+func (LogLevel) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{2}
+}
+
 type ClipboardMode int32
 
 const (
@@ -56,11 +301,11 @@ func (x ClipboardMode) String() string {
 }
 
 func (ClipboardMode) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[0].Descriptor()
+	return file_browserd_proto_enumTypes[3].Descriptor()
 }
 
 func (ClipboardMode) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[0]
+	return &file_browserd_proto_enumTypes[3]
 }
 
 func (x ClipboardMode) Number() protoreflect.EnumNumber {
@@ -69,7 +314,56 @@ func (x ClipboardMode) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use ClipboardMode.Descriptor instead.
 func (ClipboardMode) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{0}
+	return file_browserd_proto_rawDescGZIP(), []int{3}
+}
+
+type StreamCompression int32
+
+const (
+	StreamCompression_STREAM_COMPRESSION_NONE StreamCompression = 0
+	StreamCompression_STREAM_COMPRESSION_GZIP StreamCompression = 1
+	StreamCompression_STREAM_COMPRESSION_ZSTD StreamCompression = 2
+)
+
+// Enum value maps for StreamCompression.
+var (
+	StreamCompression_name = map[int32]string{
+		0: "STREAM_COMPRESSION_NONE",
+		1: "STREAM_COMPRESSION_GZIP",
+		2: "STREAM_COMPRESSION_ZSTD",
+	}
+	StreamCompression_value = map[string]int32{
+		"STREAM_COMPRESSION_NONE": 0,
+		"STREAM_COMPRESSION_GZIP": 1,
+		"STREAM_COMPRESSION_ZSTD": 2,
+	}
+)
+
+func (x StreamCompression) Enum() *StreamCompression {
+	p := new(StreamCompression)
+	*p = x
+	return p
+}
+
+func (x StreamCompression) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (StreamCompression) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[4].Descriptor()
+}
+
+func (StreamCompression) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[4]
+}
+
+func (x StreamCompression) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use StreamCompression.Descriptor instead.
+func (StreamCompression) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{4}
 }
 
 type FrameFormat int32
@@ -108,11 +402,11 @@ func (x FrameFormat) String() string {
 }
 
 func (FrameFormat) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[1].Descriptor()
+	return file_browserd_proto_enumTypes[5].Descriptor()
 }
 
 func (FrameFormat) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[1]
+	return &file_browserd_proto_enumTypes[5]
 }
 
 func (x FrameFormat) Number() protoreflect.EnumNumber {
@@ -121,7 +415,158 @@ func (x FrameFormat) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use FrameFormat.Descriptor instead.
 func (FrameFormat) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{1}
+	return file_browserd_proto_rawDescGZIP(), []int{5}
+}
+
+type StalePolicy int32
+
+const (
+	// Same as STALE_POLICY_FAIL.
+	StalePolicy_STALE_POLICY_UNSPECIFIED StalePolicy = 0
+	StalePolicy_STALE_POLICY_FAIL        StalePolicy = 1
+	StalePolicy_STALE_POLICY_RETRY       StalePolicy = 2
+)
+
+// Enum value maps for StalePolicy.
+var (
+	StalePolicy_name = map[int32]string{
+		0: "STALE_POLICY_UNSPECIFIED",
+		1: "STALE_POLICY_FAIL",
+		2: "STALE_POLICY_RETRY",
+	}
+	StalePolicy_value = map[string]int32{
+		"STALE_POLICY_UNSPECIFIED": 0,
+		"STALE_POLICY_FAIL":        1,
+		"STALE_POLICY_RETRY":       2,
+	}
+)
+
+func (x StalePolicy) Enum() *StalePolicy {
+	p := new(StalePolicy)
+	*p = x
+	return p
+}
+
+func (x StalePolicy) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (StalePolicy) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[6].Descriptor()
+}
+
+func (StalePolicy) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[6]
+}
+
+func (x StalePolicy) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use StalePolicy.Descriptor instead.
+func (StalePolicy) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{6}
+}
+
+type MouseButton int32
+
+const (
+	MouseButton_MOUSE_BUTTON_UNSPECIFIED MouseButton = 0
+	MouseButton_MOUSE_BUTTON_LEFT        MouseButton = 1
+	MouseButton_MOUSE_BUTTON_MIDDLE      MouseButton = 2
+	MouseButton_MOUSE_BUTTON_RIGHT       MouseButton = 3
+)
+
+// Enum value maps for MouseButton.
+var (
+	MouseButton_name = map[int32]string{
+		0: "MOUSE_BUTTON_UNSPECIFIED",
+		1: "MOUSE_BUTTON_LEFT",
+		2: "MOUSE_BUTTON_MIDDLE",
+		3: "MOUSE_BUTTON_RIGHT",
+	}
+	MouseButton_value = map[string]int32{
+		"MOUSE_BUTTON_UNSPECIFIED": 0,
+		"MOUSE_BUTTON_LEFT":        1,
+		"MOUSE_BUTTON_MIDDLE":      2,
+		"MOUSE_BUTTON_RIGHT":       3,
+	}
+)
+
+func (x MouseButton) Enum() *MouseButton {
+	p := new(MouseButton)
+	*p = x
+	return p
+}
+
+func (x MouseButton) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (MouseButton) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[7].Descriptor()
+}
+
+func (MouseButton) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[7]
+}
+
+func (x MouseButton) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use MouseButton.Descriptor instead.
+func (MouseButton) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{7}
+}
+
+type SelectorType int32
+
+const (
+	SelectorType_SELECTOR_TYPE_UNSPECIFIED SelectorType = 0
+	SelectorType_SELECTOR_TYPE_CSS         SelectorType = 1
+	SelectorType_SELECTOR_TYPE_XPATH       SelectorType = 2
+)
+
+// Enum value maps for SelectorType.
+var (
+	SelectorType_name = map[int32]string{
+		0: "SELECTOR_TYPE_UNSPECIFIED",
+		1: "SELECTOR_TYPE_CSS",
+		2: "SELECTOR_TYPE_XPATH",
+	}
+	SelectorType_value = map[string]int32{
+		"SELECTOR_TYPE_UNSPECIFIED": 0,
+		"SELECTOR_TYPE_CSS":         1,
+		"SELECTOR_TYPE_XPATH":       2,
+	}
+)
+
+func (x SelectorType) Enum() *SelectorType {
+	p := new(SelectorType)
+	*p = x
+	return p
+}
+
+func (x SelectorType) String() string {
+	return protoimpl.X.EnumStringOf(x.Descriptor(), protoreflect.EnumNumber(x))
+}
+
+func (SelectorType) Descriptor() protoreflect.EnumDescriptor {
+	return file_browserd_proto_enumTypes[8].Descriptor()
+}
+
+func (SelectorType) Type() protoreflect.EnumType {
+	return &file_browserd_proto_enumTypes[8]
+}
+
+func (x SelectorType) Number() protoreflect.EnumNumber {
+	return protoreflect.EnumNumber(x)
+}
+
+// Deprecated: Use SelectorType.Descriptor instead.
+func (SelectorType) EnumDescriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{8}
 }
 
 type ScrollUnit int32
@@ -157,11 +602,11 @@ func (x ScrollUnit) String() string {
 }
 
 func (ScrollUnit) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[2].Descriptor()
+	return file_browserd_proto_enumTypes[9].Descriptor()
 }
 
 func (ScrollUnit) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[2]
+	return &file_browserd_proto_enumTypes[9]
 }
 
 func (x ScrollUnit) Number() protoreflect.EnumNumber {
@@ -170,7 +615,7 @@ func (x ScrollUnit) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use ScrollUnit.Descriptor instead.
 func (ScrollUnit) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{2}
+	return file_browserd_proto_rawDescGZIP(), []int{9}
 }
 
 type ActionType int32
@@ -185,20 +630,38 @@ const (
 	ActionType_ACTION_TYPE_FOCUS           ActionType = 6
 	ActionType_ACTION_TYPE_CLIPBOARD_READ  ActionType = 7
 	ActionType_ACTION_TYPE_CLIPBOARD_WRITE ActionType = 8
+	ActionType_ACTION_TYPE_DRAG            ActionType = 9
+	// Move keyboard focus with Tab, or Shift+Tab when the Shift modifier is set.
+	ActionType_ACTION_TYPE_TAB ActionType = 10
+	// Choose the option of the target <select> whose value, or failing that
+	// whose visible text, equals `text`, firing input and change events.
+	ActionType_ACTION_TYPE_SELECT_OPTION ActionType = 11
+	// Bring the target checkbox or radio to `checked`, clicking it only when
+	// its current state differs.
+	ActionType_ACTION_TYPE_SET_CHECKED ActionType = 12
+	// Focus the element matching target.selector, then type `text` into it,
+	// as one action. The element must be a text input, textarea or
+	// contenteditable that isn't disabled or read-only.
+	ActionType_ACTION_TYPE_TYPE_INTO ActionType = 13
 )
 
 // Enum value maps for ActionType.
 var (
 	ActionType_name = map[int32]string{
-		0: "ACTION_TYPE_UNSPECIFIED",
-		1: "ACTION_TYPE_CLICK",
-		2: "ACTION_TYPE_TYPE",
-		3: "ACTION_TYPE_SCROLL",
-		4: "ACTION_TYPE_HOVER",
-		5: "ACTION_TYPE_KEY",
-		6: "ACTION_TYPE_FOCUS",
-		7: "ACTION_TYPE_CLIPBOARD_READ",
-		8: "ACTION_TYPE_CLIPBOARD_WRITE",
+		0:  "ACTION_TYPE_UNSPECIFIED",
+		1:  "ACTION_TYPE_CLICK",
+		2:  "ACTION_TYPE_TYPE",
+		3:  "ACTION_TYPE_SCROLL",
+		4:  "ACTION_TYPE_HOVER",
+		5:  "ACTION_TYPE_KEY",
+		6:  "ACTION_TYPE_FOCUS",
+		7:  "ACTION_TYPE_CLIPBOARD_READ",
+		8:  "ACTION_TYPE_CLIPBOARD_WRITE",
+		9:  "ACTION_TYPE_DRAG",
+		10: "ACTION_TYPE_TAB",
+		11: "ACTION_TYPE_SELECT_OPTION",
+		12: "ACTION_TYPE_SET_CHECKED",
+		13: "ACTION_TYPE_TYPE_INTO",
 	}
 	ActionType_value = map[string]int32{
 		"ACTION_TYPE_UNSPECIFIED":     0,
@@ -210,6 +673,11 @@ var (
 		"ACTION_TYPE_FOCUS":           6,
 		"ACTION_TYPE_CLIPBOARD_READ":  7,
 		"ACTION_TYPE_CLIPBOARD_WRITE": 8,
+		"ACTION_TYPE_DRAG":            9,
+		"ACTION_TYPE_TAB":             10,
+		"ACTION_TYPE_SELECT_OPTION":   11,
+		"ACTION_TYPE_SET_CHECKED":     12,
+		"ACTION_TYPE_TYPE_INTO":       13,
 	}
 )
 
@@ -224,11 +692,11 @@ func (x ActionType) String() string {
 }
 
 func (ActionType) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[3].Descriptor()
+	return file_browserd_proto_enumTypes[10].Descriptor()
 }
 
 func (ActionType) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[3]
+	return &file_browserd_proto_enumTypes[10]
 }
 
 func (x ActionType) Number() protoreflect.EnumNumber {
@@ -237,7 +705,7 @@ func (x ActionType) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use ActionType.Descriptor instead.
 func (ActionType) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{3}
+	return file_browserd_proto_rawDescGZIP(), []int{10}
 }
 
 type KeyModifier int32
@@ -279,11 +747,11 @@ func (x KeyModifier) String() string {
 }
 
 func (KeyModifier) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[4].Descriptor()
+	return file_browserd_proto_enumTypes[11].Descriptor()
 }
 
 func (KeyModifier) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[4]
+	return &file_browserd_proto_enumTypes[11]
 }
 
 func (x KeyModifier) Number() protoreflect.EnumNumber {
@@ -292,7 +760,7 @@ func (x KeyModifier) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use KeyModifier.Descriptor instead.
 func (KeyModifier) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{4}
+	return file_browserd_proto_rawDescGZIP(), []int{11}
 }
 
 type StreamEventType int32
@@ -303,6 +771,9 @@ const (
 	StreamEventType_STREAM_EVENT_TYPE_DOM_DIFF           StreamEventType = 2
 	StreamEventType_STREAM_EVENT_TYPE_ACCESSIBILITY_DIFF StreamEventType = 3
 	StreamEventType_STREAM_EVENT_TYPE_HIT_TEST           StreamEventType = 4
+	StreamEventType_STREAM_EVENT_TYPE_STATS              StreamEventType = 5
+	StreamEventType_STREAM_EVENT_TYPE_CONSOLE            StreamEventType = 6
+	StreamEventType_STREAM_EVENT_TYPE_HEARTBEAT          StreamEventType = 7
 )
 
 // Enum value maps for StreamEventType.
@@ -313,6 +784,9 @@ var (
 		2: "STREAM_EVENT_TYPE_DOM_DIFF",
 		3: "STREAM_EVENT_TYPE_ACCESSIBILITY_DIFF",
 		4: "STREAM_EVENT_TYPE_HIT_TEST",
+		5: "STREAM_EVENT_TYPE_STATS",
+		6: "STREAM_EVENT_TYPE_CONSOLE",
+		7: "STREAM_EVENT_TYPE_HEARTBEAT",
 	}
 	StreamEventType_value = map[string]int32{
 		"STREAM_EVENT_TYPE_UNSPECIFIED":        0,
@@ -320,6 +794,9 @@ var (
 		"STREAM_EVENT_TYPE_DOM_DIFF":           2,
 		"STREAM_EVENT_TYPE_ACCESSIBILITY_DIFF": 3,
 		"STREAM_EVENT_TYPE_HIT_TEST":           4,
+		"STREAM_EVENT_TYPE_STATS":              5,
+		"STREAM_EVENT_TYPE_CONSOLE":            6,
+		"STREAM_EVENT_TYPE_HEARTBEAT":          7,
 	}
 )
 
@@ -334,11 +811,11 @@ func (x StreamEventType) String() string {
 }
 
 func (StreamEventType) Descriptor() protoreflect.EnumDescriptor {
-	return file_browserd_proto_enumTypes[5].Descriptor()
+	return file_browserd_proto_enumTypes[12].Descriptor()
 }
 
 func (StreamEventType) Type() protoreflect.EnumType {
-	return &file_browserd_proto_enumTypes[5]
+	return &file_browserd_proto_enumTypes[12]
 }
 
 func (x StreamEventType) Number() protoreflect.EnumNumber {
@@ -347,7 +824,7 @@ func (x StreamEventType) Number() protoreflect.EnumNumber {
 
 // Deprecated: Use StreamEventType.Descriptor instead.
 func (StreamEventType) EnumDescriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{5}
+	return file_browserd_proto_rawDescGZIP(), []int{12}
 }
 
 type Envelope struct {
@@ -452,6 +929,7 @@ type Error struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
 	Code          string                 `protobuf:"bytes,1,opt,name=code,proto3" json:"code,omitempty"`
 	Message       string                 `protobuf:"bytes,2,opt,name=message,proto3" json:"message,omitempty"`
+	CodeEnum      ErrorCode              `protobuf:"varint,3,opt,name=code_enum,json=codeEnum,proto3,enum=buckley.browserd.v1.ErrorCode" json:"code_enum,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
@@ -500,6 +978,13 @@ func (x *Error) GetMessage() string {
 	return ""
 }
 
+func (x *Error) GetCodeEnum() ErrorCode {
+	if x != nil {
+		return x.CodeEnum
+	}
+	return ErrorCode_ERROR_CODE_UNSPECIFIED
+}
+
 type Request struct {
 	state     protoimpl.MessageState `protogen:"open.v1"`
 	RequestId string                 `protobuf:"bytes,1,opt,name=request_id,json=requestId,proto3" json:"request_id,omitempty"`
@@ -512,6 +997,39 @@ type Request struct {
 	//	*Request_Act
 	//	*Request_CloseSession
 	//	*Request_StreamSubscribe
+	//	*Request_ListSessions
+	//	*Request_CheckContrast
+	//	*Request_GetInfo
+	//	*Request_ExportPdf
+	//	*Request_GetCookies
+	//	*Request_SetCookie
+	//	*Request_ClearCookies
+	//	*Request_WaitFor
+	//	*Request_Shutdown
+	//	*Request_Authenticate
+	//	*Request_OpenTab
+	//	*Request_CloseTab
+	//	*Request_SwitchTab
+	//	*Request_ListTabs
+	//	*Request_ExtractText
+	//	*Request_SetViewport
+	//	*Request_SaveSession
+	//	*Request_RestoreSession
+	//	*Request_ClipboardGet
+	//	*Request_ClipboardSet
+	//	*Request_FillForm
+	//	*Request_SetFileInput
+	//	*Request_ListDownloads
+	//	*Request_GetDownload
+	//	*Request_SessionStats
+	//	*Request_BatchActions
+	//	*Request_SetStorage
+	//	*Request_GetStorage
+	//	*Request_ClearStorage
+	//	*Request_ClearBrowsingData
+	//	*Request_Stop
+	//	*Request_SetLogLevel
+	//	*Request_QueryElement
 	Payload       isRequest_Payload `protobuf_oneof:"payload"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
@@ -622,34 +1140,463 @@ func (x *Request) GetStreamSubscribe() *StreamSubscribeRequest {
 	return nil
 }
 
-type isRequest_Payload interface {
-	isRequest_Payload()
+func (x *Request) GetListSessions() *ListSessionsRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ListSessions); ok {
+			return x.ListSessions
+		}
+	}
+	return nil
 }
 
-type Request_CreateSession struct {
-	CreateSession *CreateSessionRequest `protobuf:"bytes,3,opt,name=create_session,json=createSession,proto3,oneof"`
+func (x *Request) GetCheckContrast() *CheckContrastRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_CheckContrast); ok {
+			return x.CheckContrast
+		}
+	}
+	return nil
 }
 
-type Request_Navigate struct {
-	Navigate *NavigateRequest `protobuf:"bytes,4,opt,name=navigate,proto3,oneof"`
+func (x *Request) GetGetInfo() *GetInfoRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_GetInfo); ok {
+			return x.GetInfo
+		}
+	}
+	return nil
 }
 
-type Request_Observe struct {
-	Observe *ObserveRequest `protobuf:"bytes,5,opt,name=observe,proto3,oneof"`
+func (x *Request) GetExportPdf() *ExportPdfRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ExportPdf); ok {
+			return x.ExportPdf
+		}
+	}
+	return nil
 }
 
-type Request_Act struct {
-	Act *ActRequest `protobuf:"bytes,6,opt,name=act,proto3,oneof"`
+func (x *Request) GetGetCookies() *GetCookiesRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_GetCookies); ok {
+			return x.GetCookies
+		}
+	}
+	return nil
 }
 
-type Request_CloseSession struct {
-	CloseSession *CloseSessionRequest `protobuf:"bytes,7,opt,name=close_session,json=closeSession,proto3,oneof"`
+func (x *Request) GetSetCookie() *SetCookieRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SetCookie); ok {
+			return x.SetCookie
+		}
+	}
+	return nil
 }
 
-type Request_StreamSubscribe struct {
+func (x *Request) GetClearCookies() *ClearCookiesRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ClearCookies); ok {
+			return x.ClearCookies
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetWaitFor() *WaitForRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_WaitFor); ok {
+			return x.WaitFor
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetShutdown() *ShutdownRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_Shutdown); ok {
+			return x.Shutdown
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetAuthenticate() *AuthenticateRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_Authenticate); ok {
+			return x.Authenticate
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetOpenTab() *OpenTabRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_OpenTab); ok {
+			return x.OpenTab
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetCloseTab() *CloseTabRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_CloseTab); ok {
+			return x.CloseTab
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSwitchTab() *SwitchTabRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SwitchTab); ok {
+			return x.SwitchTab
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetListTabs() *ListTabsRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ListTabs); ok {
+			return x.ListTabs
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetExtractText() *ExtractTextRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ExtractText); ok {
+			return x.ExtractText
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSetViewport() *SetViewportRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SetViewport); ok {
+			return x.SetViewport
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSaveSession() *SaveSessionRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SaveSession); ok {
+			return x.SaveSession
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetRestoreSession() *RestoreSessionRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_RestoreSession); ok {
+			return x.RestoreSession
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetClipboardGet() *ClipboardGetRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ClipboardGet); ok {
+			return x.ClipboardGet
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetClipboardSet() *ClipboardSetRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ClipboardSet); ok {
+			return x.ClipboardSet
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetFillForm() *FillFormRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_FillForm); ok {
+			return x.FillForm
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSetFileInput() *SetFileInputRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SetFileInput); ok {
+			return x.SetFileInput
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetListDownloads() *ListDownloadsRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ListDownloads); ok {
+			return x.ListDownloads
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetGetDownload() *GetDownloadRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_GetDownload); ok {
+			return x.GetDownload
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSessionStats() *SessionStatsRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SessionStats); ok {
+			return x.SessionStats
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetBatchActions() *BatchActionsRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_BatchActions); ok {
+			return x.BatchActions
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSetStorage() *SetStorageRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SetStorage); ok {
+			return x.SetStorage
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetGetStorage() *GetStorageRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_GetStorage); ok {
+			return x.GetStorage
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetClearStorage() *ClearStorageRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ClearStorage); ok {
+			return x.ClearStorage
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetClearBrowsingData() *ClearBrowsingDataRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_ClearBrowsingData); ok {
+			return x.ClearBrowsingData
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetStop() *StopRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_Stop); ok {
+			return x.Stop
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetSetLogLevel() *SetLogLevelRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_SetLogLevel); ok {
+			return x.SetLogLevel
+		}
+	}
+	return nil
+}
+
+func (x *Request) GetQueryElement() *QueryElementRequest {
+	if x != nil {
+		if x, ok := x.Payload.(*Request_QueryElement); ok {
+			return x.QueryElement
+		}
+	}
+	return nil
+}
+
+type isRequest_Payload interface {
+	isRequest_Payload()
+}
+
+type Request_CreateSession struct {
+	CreateSession *CreateSessionRequest `protobuf:"bytes,3,opt,name=create_session,json=createSession,proto3,oneof"`
+}
+
+type Request_Navigate struct {
+	Navigate *NavigateRequest `protobuf:"bytes,4,opt,name=navigate,proto3,oneof"`
+}
+
+type Request_Observe struct {
+	Observe *ObserveRequest `protobuf:"bytes,5,opt,name=observe,proto3,oneof"`
+}
+
+type Request_Act struct {
+	Act *ActRequest `protobuf:"bytes,6,opt,name=act,proto3,oneof"`
+}
+
+type Request_CloseSession struct {
+	CloseSession *CloseSessionRequest `protobuf:"bytes,7,opt,name=close_session,json=closeSession,proto3,oneof"`
+}
+
+type Request_StreamSubscribe struct {
 	StreamSubscribe *StreamSubscribeRequest `protobuf:"bytes,8,opt,name=stream_subscribe,json=streamSubscribe,proto3,oneof"`
 }
 
+type Request_ListSessions struct {
+	ListSessions *ListSessionsRequest `protobuf:"bytes,9,opt,name=list_sessions,json=listSessions,proto3,oneof"`
+}
+
+type Request_CheckContrast struct {
+	CheckContrast *CheckContrastRequest `protobuf:"bytes,10,opt,name=check_contrast,json=checkContrast,proto3,oneof"`
+}
+
+type Request_GetInfo struct {
+	GetInfo *GetInfoRequest `protobuf:"bytes,11,opt,name=get_info,json=getInfo,proto3,oneof"`
+}
+
+type Request_ExportPdf struct {
+	ExportPdf *ExportPdfRequest `protobuf:"bytes,12,opt,name=export_pdf,json=exportPdf,proto3,oneof"`
+}
+
+type Request_GetCookies struct {
+	GetCookies *GetCookiesRequest `protobuf:"bytes,13,opt,name=get_cookies,json=getCookies,proto3,oneof"`
+}
+
+type Request_SetCookie struct {
+	SetCookie *SetCookieRequest `protobuf:"bytes,14,opt,name=set_cookie,json=setCookie,proto3,oneof"`
+}
+
+type Request_ClearCookies struct {
+	ClearCookies *ClearCookiesRequest `protobuf:"bytes,15,opt,name=clear_cookies,json=clearCookies,proto3,oneof"`
+}
+
+type Request_WaitFor struct {
+	WaitFor *WaitForRequest `protobuf:"bytes,16,opt,name=wait_for,json=waitFor,proto3,oneof"`
+}
+
+type Request_Shutdown struct {
+	Shutdown *ShutdownRequest `protobuf:"bytes,17,opt,name=shutdown,proto3,oneof"`
+}
+
+type Request_Authenticate struct {
+	Authenticate *AuthenticateRequest `protobuf:"bytes,18,opt,name=authenticate,proto3,oneof"`
+}
+
+type Request_OpenTab struct {
+	OpenTab *OpenTabRequest `protobuf:"bytes,19,opt,name=open_tab,json=openTab,proto3,oneof"`
+}
+
+type Request_CloseTab struct {
+	CloseTab *CloseTabRequest `protobuf:"bytes,20,opt,name=close_tab,json=closeTab,proto3,oneof"`
+}
+
+type Request_SwitchTab struct {
+	SwitchTab *SwitchTabRequest `protobuf:"bytes,21,opt,name=switch_tab,json=switchTab,proto3,oneof"`
+}
+
+type Request_ListTabs struct {
+	ListTabs *ListTabsRequest `protobuf:"bytes,22,opt,name=list_tabs,json=listTabs,proto3,oneof"`
+}
+
+type Request_ExtractText struct {
+	ExtractText *ExtractTextRequest `protobuf:"bytes,23,opt,name=extract_text,json=extractText,proto3,oneof"`
+}
+
+type Request_SetViewport struct {
+	SetViewport *SetViewportRequest `protobuf:"bytes,24,opt,name=set_viewport,json=setViewport,proto3,oneof"`
+}
+
+type Request_SaveSession struct {
+	SaveSession *SaveSessionRequest `protobuf:"bytes,25,opt,name=save_session,json=saveSession,proto3,oneof"`
+}
+
+type Request_RestoreSession struct {
+	RestoreSession *RestoreSessionRequest `protobuf:"bytes,26,opt,name=restore_session,json=restoreSession,proto3,oneof"`
+}
+
+type Request_ClipboardGet struct {
+	ClipboardGet *ClipboardGetRequest `protobuf:"bytes,27,opt,name=clipboard_get,json=clipboardGet,proto3,oneof"`
+}
+
+type Request_ClipboardSet struct {
+	ClipboardSet *ClipboardSetRequest `protobuf:"bytes,28,opt,name=clipboard_set,json=clipboardSet,proto3,oneof"`
+}
+
+type Request_FillForm struct {
+	FillForm *FillFormRequest `protobuf:"bytes,29,opt,name=fill_form,json=fillForm,proto3,oneof"`
+}
+
+type Request_SetFileInput struct {
+	SetFileInput *SetFileInputRequest `protobuf:"bytes,30,opt,name=set_file_input,json=setFileInput,proto3,oneof"`
+}
+
+type Request_ListDownloads struct {
+	ListDownloads *ListDownloadsRequest `protobuf:"bytes,31,opt,name=list_downloads,json=listDownloads,proto3,oneof"`
+}
+
+type Request_GetDownload struct {
+	GetDownload *GetDownloadRequest `protobuf:"bytes,32,opt,name=get_download,json=getDownload,proto3,oneof"`
+}
+
+type Request_SessionStats struct {
+	SessionStats *SessionStatsRequest `protobuf:"bytes,33,opt,name=session_stats,json=sessionStats,proto3,oneof"`
+}
+
+type Request_BatchActions struct {
+	BatchActions *BatchActionsRequest `protobuf:"bytes,34,opt,name=batch_actions,json=batchActions,proto3,oneof"`
+}
+
+type Request_SetStorage struct {
+	SetStorage *SetStorageRequest `protobuf:"bytes,35,opt,name=set_storage,json=setStorage,proto3,oneof"`
+}
+
+type Request_GetStorage struct {
+	GetStorage *GetStorageRequest `protobuf:"bytes,36,opt,name=get_storage,json=getStorage,proto3,oneof"`
+}
+
+type Request_ClearStorage struct {
+	ClearStorage *ClearStorageRequest `protobuf:"bytes,37,opt,name=clear_storage,json=clearStorage,proto3,oneof"`
+}
+
+type Request_ClearBrowsingData struct {
+	ClearBrowsingData *ClearBrowsingDataRequest `protobuf:"bytes,38,opt,name=clear_browsing_data,json=clearBrowsingData,proto3,oneof"`
+}
+
+type Request_Stop struct {
+	Stop *StopRequest `protobuf:"bytes,39,opt,name=stop,proto3,oneof"`
+}
+
+type Request_SetLogLevel struct {
+	SetLogLevel *SetLogLevelRequest `protobuf:"bytes,40,opt,name=set_log_level,json=setLogLevel,proto3,oneof"`
+}
+
+type Request_QueryElement struct {
+	QueryElement *QueryElementRequest `protobuf:"bytes,41,opt,name=query_element,json=queryElement,proto3,oneof"`
+}
+
 func (*Request_CreateSession) isRequest_Payload() {}
 
 func (*Request_Navigate) isRequest_Payload() {}
@@ -662,9 +1609,75 @@ func (*Request_CloseSession) isRequest_Payload() {}
 
 func (*Request_StreamSubscribe) isRequest_Payload() {}
 
-type Response struct {
-	state     protoimpl.MessageState `protogen:"open.v1"`
-	RequestId string                 `protobuf:"bytes,1,opt,name=request_id,json=requestId,proto3" json:"request_id,omitempty"`
+func (*Request_ListSessions) isRequest_Payload() {}
+
+func (*Request_CheckContrast) isRequest_Payload() {}
+
+func (*Request_GetInfo) isRequest_Payload() {}
+
+func (*Request_ExportPdf) isRequest_Payload() {}
+
+func (*Request_GetCookies) isRequest_Payload() {}
+
+func (*Request_SetCookie) isRequest_Payload() {}
+
+func (*Request_ClearCookies) isRequest_Payload() {}
+
+func (*Request_WaitFor) isRequest_Payload() {}
+
+func (*Request_Shutdown) isRequest_Payload() {}
+
+func (*Request_Authenticate) isRequest_Payload() {}
+
+func (*Request_OpenTab) isRequest_Payload() {}
+
+func (*Request_CloseTab) isRequest_Payload() {}
+
+func (*Request_SwitchTab) isRequest_Payload() {}
+
+func (*Request_ListTabs) isRequest_Payload() {}
+
+func (*Request_ExtractText) isRequest_Payload() {}
+
+func (*Request_SetViewport) isRequest_Payload() {}
+
+func (*Request_SaveSession) isRequest_Payload() {}
+
+func (*Request_RestoreSession) isRequest_Payload() {}
+
+func (*Request_ClipboardGet) isRequest_Payload() {}
+
+func (*Request_ClipboardSet) isRequest_Payload() {}
+
+func (*Request_FillForm) isRequest_Payload() {}
+
+func (*Request_SetFileInput) isRequest_Payload() {}
+
+func (*Request_ListDownloads) isRequest_Payload() {}
+
+func (*Request_GetDownload) isRequest_Payload() {}
+
+func (*Request_SessionStats) isRequest_Payload() {}
+
+func (*Request_BatchActions) isRequest_Payload() {}
+
+func (*Request_SetStorage) isRequest_Payload() {}
+
+func (*Request_GetStorage) isRequest_Payload() {}
+
+func (*Request_ClearStorage) isRequest_Payload() {}
+
+func (*Request_ClearBrowsingData) isRequest_Payload() {}
+
+func (*Request_Stop) isRequest_Payload() {}
+
+func (*Request_SetLogLevel) isRequest_Payload() {}
+
+func (*Request_QueryElement) isRequest_Payload() {}
+
+type Response struct {
+	state     protoimpl.MessageState `protogen:"open.v1"`
+	RequestId string                 `protobuf:"bytes,1,opt,name=request_id,json=requestId,proto3" json:"request_id,omitempty"`
 	SessionId string                 `protobuf:"bytes,2,opt,name=session_id,json=sessionId,proto3" json:"session_id,omitempty"`
 	Error     *Error                 `protobuf:"bytes,3,opt,name=error,proto3" json:"error,omitempty"`
 	// Types that are valid to be assigned to Payload:
@@ -675,26 +1688,4907 @@ type Response struct {
 	//	*Response_Act
 	//	*Response_CloseSession
 	//	*Response_StreamSubscribe
-	Payload       isResponse_Payload `protobuf_oneof:"payload"`
+	//	*Response_ListSessions
+	//	*Response_CheckContrast
+	//	*Response_GetInfo
+	//	*Response_ExportPdf
+	//	*Response_GetCookies
+	//	*Response_SetCookie
+	//	*Response_ClearCookies
+	//	*Response_WaitFor
+	//	*Response_Shutdown
+	//	*Response_Authenticate
+	//	*Response_OpenTab
+	//	*Response_CloseTab
+	//	*Response_SwitchTab
+	//	*Response_ListTabs
+	//	*Response_ExtractText
+	//	*Response_SetViewport
+	//	*Response_SaveSession
+	//	*Response_RestoreSession
+	//	*Response_ClipboardGet
+	//	*Response_ClipboardSet
+	//	*Response_FillForm
+	//	*Response_SetFileInput
+	//	*Response_ListDownloads
+	//	*Response_GetDownload
+	//	*Response_SessionStats
+	//	*Response_BatchActions
+	//	*Response_SetStorage
+	//	*Response_GetStorage
+	//	*Response_ClearStorage
+	//	*Response_ClearBrowsingData
+	//	*Response_Stop
+	//	*Response_SetLogLevel
+	//	*Response_QueryElement
+	Payload isResponse_Payload `protobuf_oneof:"payload"`
+	// Position of this message among everything the daemon has written on
+	// the connection, responses and stream events alike, counting from 1.
+	Sequence      uint64 `protobuf:"varint,41,opt,name=sequence,proto3" json:"sequence,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Response) Reset() {
+	*x = Response{}
+	mi := &file_browserd_proto_msgTypes[3]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Response) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Response) ProtoMessage() {}
+
+func (x *Response) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[3]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Response.ProtoReflect.Descriptor instead.
+func (*Response) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{3}
+}
+
+func (x *Response) GetRequestId() string {
+	if x != nil {
+		return x.RequestId
+	}
+	return ""
+}
+
+func (x *Response) GetSessionId() string {
+	if x != nil {
+		return x.SessionId
+	}
+	return ""
+}
+
+func (x *Response) GetError() *Error {
+	if x != nil {
+		return x.Error
+	}
+	return nil
+}
+
+func (x *Response) GetPayload() isResponse_Payload {
+	if x != nil {
+		return x.Payload
+	}
+	return nil
+}
+
+func (x *Response) GetCreateSession() *CreateSessionResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_CreateSession); ok {
+			return x.CreateSession
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetNavigate() *NavigateResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Navigate); ok {
+			return x.Navigate
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetObserve() *ObserveResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Observe); ok {
+			return x.Observe
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetAct() *ActResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Act); ok {
+			return x.Act
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetCloseSession() *CloseSessionResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_CloseSession); ok {
+			return x.CloseSession
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetStreamSubscribe() *StreamSubscribeResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_StreamSubscribe); ok {
+			return x.StreamSubscribe
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetListSessions() *ListSessionsResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ListSessions); ok {
+			return x.ListSessions
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetCheckContrast() *CheckContrastResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_CheckContrast); ok {
+			return x.CheckContrast
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetGetInfo() *GetInfoResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_GetInfo); ok {
+			return x.GetInfo
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetExportPdf() *ExportPdfResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ExportPdf); ok {
+			return x.ExportPdf
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetGetCookies() *GetCookiesResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_GetCookies); ok {
+			return x.GetCookies
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSetCookie() *SetCookieResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SetCookie); ok {
+			return x.SetCookie
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetClearCookies() *ClearCookiesResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ClearCookies); ok {
+			return x.ClearCookies
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetWaitFor() *WaitForResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_WaitFor); ok {
+			return x.WaitFor
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetShutdown() *ShutdownResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Shutdown); ok {
+			return x.Shutdown
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetAuthenticate() *AuthenticateResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Authenticate); ok {
+			return x.Authenticate
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetOpenTab() *OpenTabResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_OpenTab); ok {
+			return x.OpenTab
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetCloseTab() *CloseTabResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_CloseTab); ok {
+			return x.CloseTab
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSwitchTab() *SwitchTabResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SwitchTab); ok {
+			return x.SwitchTab
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetListTabs() *ListTabsResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ListTabs); ok {
+			return x.ListTabs
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetExtractText() *ExtractTextResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ExtractText); ok {
+			return x.ExtractText
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSetViewport() *SetViewportResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SetViewport); ok {
+			return x.SetViewport
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSaveSession() *SaveSessionResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SaveSession); ok {
+			return x.SaveSession
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetRestoreSession() *RestoreSessionResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_RestoreSession); ok {
+			return x.RestoreSession
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetClipboardGet() *ClipboardGetResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ClipboardGet); ok {
+			return x.ClipboardGet
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetClipboardSet() *ClipboardSetResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ClipboardSet); ok {
+			return x.ClipboardSet
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetFillForm() *FillFormResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_FillForm); ok {
+			return x.FillForm
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSetFileInput() *SetFileInputResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SetFileInput); ok {
+			return x.SetFileInput
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetListDownloads() *ListDownloadsResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ListDownloads); ok {
+			return x.ListDownloads
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetGetDownload() *GetDownloadResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_GetDownload); ok {
+			return x.GetDownload
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSessionStats() *SessionStatsResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SessionStats); ok {
+			return x.SessionStats
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetBatchActions() *BatchActionsResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_BatchActions); ok {
+			return x.BatchActions
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSetStorage() *SetStorageResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SetStorage); ok {
+			return x.SetStorage
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetGetStorage() *GetStorageResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_GetStorage); ok {
+			return x.GetStorage
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetClearStorage() *ClearStorageResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ClearStorage); ok {
+			return x.ClearStorage
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetClearBrowsingData() *ClearBrowsingDataResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_ClearBrowsingData); ok {
+			return x.ClearBrowsingData
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetStop() *StopResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_Stop); ok {
+			return x.Stop
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSetLogLevel() *SetLogLevelResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_SetLogLevel); ok {
+			return x.SetLogLevel
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetQueryElement() *QueryElementResponse {
+	if x != nil {
+		if x, ok := x.Payload.(*Response_QueryElement); ok {
+			return x.QueryElement
+		}
+	}
+	return nil
+}
+
+func (x *Response) GetSequence() uint64 {
+	if x != nil {
+		return x.Sequence
+	}
+	return 0
+}
+
+type isResponse_Payload interface {
+	isResponse_Payload()
+}
+
+type Response_CreateSession struct {
+	CreateSession *CreateSessionResponse `protobuf:"bytes,4,opt,name=create_session,json=createSession,proto3,oneof"`
+}
+
+type Response_Navigate struct {
+	Navigate *NavigateResponse `protobuf:"bytes,5,opt,name=navigate,proto3,oneof"`
+}
+
+type Response_Observe struct {
+	Observe *ObserveResponse `protobuf:"bytes,6,opt,name=observe,proto3,oneof"`
+}
+
+type Response_Act struct {
+	Act *ActResponse `protobuf:"bytes,7,opt,name=act,proto3,oneof"`
+}
+
+type Response_CloseSession struct {
+	CloseSession *CloseSessionResponse `protobuf:"bytes,8,opt,name=close_session,json=closeSession,proto3,oneof"`
+}
+
+type Response_StreamSubscribe struct {
+	StreamSubscribe *StreamSubscribeResponse `protobuf:"bytes,9,opt,name=stream_subscribe,json=streamSubscribe,proto3,oneof"`
+}
+
+type Response_ListSessions struct {
+	ListSessions *ListSessionsResponse `protobuf:"bytes,10,opt,name=list_sessions,json=listSessions,proto3,oneof"`
+}
+
+type Response_CheckContrast struct {
+	CheckContrast *CheckContrastResponse `protobuf:"bytes,11,opt,name=check_contrast,json=checkContrast,proto3,oneof"`
+}
+
+type Response_GetInfo struct {
+	GetInfo *GetInfoResponse `protobuf:"bytes,12,opt,name=get_info,json=getInfo,proto3,oneof"`
+}
+
+type Response_ExportPdf struct {
+	ExportPdf *ExportPdfResponse `protobuf:"bytes,13,opt,name=export_pdf,json=exportPdf,proto3,oneof"`
+}
+
+type Response_GetCookies struct {
+	GetCookies *GetCookiesResponse `protobuf:"bytes,14,opt,name=get_cookies,json=getCookies,proto3,oneof"`
+}
+
+type Response_SetCookie struct {
+	SetCookie *SetCookieResponse `protobuf:"bytes,15,opt,name=set_cookie,json=setCookie,proto3,oneof"`
+}
+
+type Response_ClearCookies struct {
+	ClearCookies *ClearCookiesResponse `protobuf:"bytes,16,opt,name=clear_cookies,json=clearCookies,proto3,oneof"`
+}
+
+type Response_WaitFor struct {
+	WaitFor *WaitForResponse `protobuf:"bytes,17,opt,name=wait_for,json=waitFor,proto3,oneof"`
+}
+
+type Response_Shutdown struct {
+	Shutdown *ShutdownResponse `protobuf:"bytes,18,opt,name=shutdown,proto3,oneof"`
+}
+
+type Response_Authenticate struct {
+	Authenticate *AuthenticateResponse `protobuf:"bytes,19,opt,name=authenticate,proto3,oneof"`
+}
+
+type Response_OpenTab struct {
+	OpenTab *OpenTabResponse `protobuf:"bytes,20,opt,name=open_tab,json=openTab,proto3,oneof"`
+}
+
+type Response_CloseTab struct {
+	CloseTab *CloseTabResponse `protobuf:"bytes,21,opt,name=close_tab,json=closeTab,proto3,oneof"`
+}
+
+type Response_SwitchTab struct {
+	SwitchTab *SwitchTabResponse `protobuf:"bytes,22,opt,name=switch_tab,json=switchTab,proto3,oneof"`
+}
+
+type Response_ListTabs struct {
+	ListTabs *ListTabsResponse `protobuf:"bytes,23,opt,name=list_tabs,json=listTabs,proto3,oneof"`
+}
+
+type Response_ExtractText struct {
+	ExtractText *ExtractTextResponse `protobuf:"bytes,24,opt,name=extract_text,json=extractText,proto3,oneof"`
+}
+
+type Response_SetViewport struct {
+	SetViewport *SetViewportResponse `protobuf:"bytes,25,opt,name=set_viewport,json=setViewport,proto3,oneof"`
+}
+
+type Response_SaveSession struct {
+	SaveSession *SaveSessionResponse `protobuf:"bytes,26,opt,name=save_session,json=saveSession,proto3,oneof"`
+}
+
+type Response_RestoreSession struct {
+	RestoreSession *RestoreSessionResponse `protobuf:"bytes,27,opt,name=restore_session,json=restoreSession,proto3,oneof"`
+}
+
+type Response_ClipboardGet struct {
+	ClipboardGet *ClipboardGetResponse `protobuf:"bytes,28,opt,name=clipboard_get,json=clipboardGet,proto3,oneof"`
+}
+
+type Response_ClipboardSet struct {
+	ClipboardSet *ClipboardSetResponse `protobuf:"bytes,29,opt,name=clipboard_set,json=clipboardSet,proto3,oneof"`
+}
+
+type Response_FillForm struct {
+	FillForm *FillFormResponse `protobuf:"bytes,30,opt,name=fill_form,json=fillForm,proto3,oneof"`
+}
+
+type Response_SetFileInput struct {
+	SetFileInput *SetFileInputResponse `protobuf:"bytes,31,opt,name=set_file_input,json=setFileInput,proto3,oneof"`
+}
+
+type Response_ListDownloads struct {
+	ListDownloads *ListDownloadsResponse `protobuf:"bytes,32,opt,name=list_downloads,json=listDownloads,proto3,oneof"`
+}
+
+type Response_GetDownload struct {
+	GetDownload *GetDownloadResponse `protobuf:"bytes,33,opt,name=get_download,json=getDownload,proto3,oneof"`
+}
+
+type Response_SessionStats struct {
+	SessionStats *SessionStatsResponse `protobuf:"bytes,34,opt,name=session_stats,json=sessionStats,proto3,oneof"`
+}
+
+type Response_BatchActions struct {
+	BatchActions *BatchActionsResponse `protobuf:"bytes,35,opt,name=batch_actions,json=batchActions,proto3,oneof"`
+}
+
+type Response_SetStorage struct {
+	SetStorage *SetStorageResponse `protobuf:"bytes,36,opt,name=set_storage,json=setStorage,proto3,oneof"`
+}
+
+type Response_GetStorage struct {
+	GetStorage *GetStorageResponse `protobuf:"bytes,37,opt,name=get_storage,json=getStorage,proto3,oneof"`
+}
+
+type Response_ClearStorage struct {
+	ClearStorage *ClearStorageResponse `protobuf:"bytes,38,opt,name=clear_storage,json=clearStorage,proto3,oneof"`
+}
+
+type Response_ClearBrowsingData struct {
+	ClearBrowsingData *ClearBrowsingDataResponse `protobuf:"bytes,39,opt,name=clear_browsing_data,json=clearBrowsingData,proto3,oneof"`
+}
+
+type Response_Stop struct {
+	Stop *StopResponse `protobuf:"bytes,40,opt,name=stop,proto3,oneof"`
+}
+
+type Response_SetLogLevel struct {
+	SetLogLevel *SetLogLevelResponse `protobuf:"bytes,42,opt,name=set_log_level,json=setLogLevel,proto3,oneof"`
+}
+
+type Response_QueryElement struct {
+	QueryElement *QueryElementResponse `protobuf:"bytes,43,opt,name=query_element,json=queryElement,proto3,oneof"`
+}
+
+func (*Response_CreateSession) isResponse_Payload() {}
+
+func (*Response_Navigate) isResponse_Payload() {}
+
+func (*Response_Observe) isResponse_Payload() {}
+
+func (*Response_Act) isResponse_Payload() {}
+
+func (*Response_CloseSession) isResponse_Payload() {}
+
+func (*Response_StreamSubscribe) isResponse_Payload() {}
+
+func (*Response_ListSessions) isResponse_Payload() {}
+
+func (*Response_CheckContrast) isResponse_Payload() {}
+
+func (*Response_GetInfo) isResponse_Payload() {}
+
+func (*Response_ExportPdf) isResponse_Payload() {}
+
+func (*Response_GetCookies) isResponse_Payload() {}
+
+func (*Response_SetCookie) isResponse_Payload() {}
+
+func (*Response_ClearCookies) isResponse_Payload() {}
+
+func (*Response_WaitFor) isResponse_Payload() {}
+
+func (*Response_Shutdown) isResponse_Payload() {}
+
+func (*Response_Authenticate) isResponse_Payload() {}
+
+func (*Response_OpenTab) isResponse_Payload() {}
+
+func (*Response_CloseTab) isResponse_Payload() {}
+
+func (*Response_SwitchTab) isResponse_Payload() {}
+
+func (*Response_ListTabs) isResponse_Payload() {}
+
+func (*Response_ExtractText) isResponse_Payload() {}
+
+func (*Response_SetViewport) isResponse_Payload() {}
+
+func (*Response_SaveSession) isResponse_Payload() {}
+
+func (*Response_RestoreSession) isResponse_Payload() {}
+
+func (*Response_ClipboardGet) isResponse_Payload() {}
+
+func (*Response_ClipboardSet) isResponse_Payload() {}
+
+func (*Response_FillForm) isResponse_Payload() {}
+
+func (*Response_SetFileInput) isResponse_Payload() {}
+
+func (*Response_ListDownloads) isResponse_Payload() {}
+
+func (*Response_GetDownload) isResponse_Payload() {}
+
+func (*Response_SessionStats) isResponse_Payload() {}
+
+func (*Response_BatchActions) isResponse_Payload() {}
+
+func (*Response_SetStorage) isResponse_Payload() {}
+
+func (*Response_GetStorage) isResponse_Payload() {}
+
+func (*Response_ClearStorage) isResponse_Payload() {}
+
+func (*Response_ClearBrowsingData) isResponse_Payload() {}
+
+func (*Response_Stop) isResponse_Payload() {}
+
+func (*Response_SetLogLevel) isResponse_Payload() {}
+
+func (*Response_QueryElement) isResponse_Payload() {}
+
+type CreateSessionRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Config        *SessionConfig         `protobuf:"bytes,1,opt,name=config,proto3" json:"config,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CreateSessionRequest) Reset() {
+	*x = CreateSessionRequest{}
+	mi := &file_browserd_proto_msgTypes[4]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CreateSessionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CreateSessionRequest) ProtoMessage() {}
+
+func (x *CreateSessionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[4]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CreateSessionRequest.ProtoReflect.Descriptor instead.
+func (*CreateSessionRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{4}
+}
+
+func (x *CreateSessionRequest) GetConfig() *SessionConfig {
+	if x != nil {
+		return x.Config
+	}
+	return nil
+}
+
+type CreateSessionResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Session       *SessionInfo           `protobuf:"bytes,1,opt,name=session,proto3" json:"session,omitempty"`
+	Observation   *Observation           `protobuf:"bytes,2,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CreateSessionResponse) Reset() {
+	*x = CreateSessionResponse{}
+	mi := &file_browserd_proto_msgTypes[5]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CreateSessionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CreateSessionResponse) ProtoMessage() {}
+
+func (x *CreateSessionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[5]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CreateSessionResponse.ProtoReflect.Descriptor instead.
+func (*CreateSessionResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{5}
+}
+
+func (x *CreateSessionResponse) GetSession() *SessionInfo {
+	if x != nil {
+		return x.Session
+	}
+	return nil
+}
+
+func (x *CreateSessionResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+type NavigateRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Url           string                 `protobuf:"bytes,1,opt,name=url,proto3" json:"url,omitempty"`
+	TimeoutMs     uint32                 `protobuf:"varint,2,opt,name=timeout_ms,json=timeoutMs,proto3" json:"timeout_ms,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *NavigateRequest) Reset() {
+	*x = NavigateRequest{}
+	mi := &file_browserd_proto_msgTypes[6]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *NavigateRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*NavigateRequest) ProtoMessage() {}
+
+func (x *NavigateRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[6]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use NavigateRequest.ProtoReflect.Descriptor instead.
+func (*NavigateRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{6}
+}
+
+func (x *NavigateRequest) GetUrl() string {
+	if x != nil {
+		return x.Url
+	}
+	return ""
+}
+
+func (x *NavigateRequest) GetTimeoutMs() uint32 {
+	if x != nil {
+		return x.TimeoutMs
+	}
+	return 0
+}
+
+type NavigateResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *NavigateResponse) Reset() {
+	*x = NavigateResponse{}
+	mi := &file_browserd_proto_msgTypes[7]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *NavigateResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*NavigateResponse) ProtoMessage() {}
+
+func (x *NavigateResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[7]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use NavigateResponse.ProtoReflect.Descriptor instead.
+func (*NavigateResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{7}
+}
+
+func (x *NavigateResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+// Aborts the active tab's page load, keeping whatever has loaded so far. A
+// Navigate on another connection that is still waiting for the page returns
+// early with that partial page; the Stop then answers once it has. Loads a
+// timed-out Navigate left running are stopped too.
+type StopRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StopRequest) Reset() {
+	*x = StopRequest{}
+	mi := &file_browserd_proto_msgTypes[8]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StopRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StopRequest) ProtoMessage() {}
+
+func (x *StopRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[8]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StopRequest.ProtoReflect.Descriptor instead.
+func (*StopRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{8}
+}
+
+type StopResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StopResponse) Reset() {
+	*x = StopResponse{}
+	mi := &file_browserd_proto_msgTypes[9]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StopResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StopResponse) ProtoMessage() {}
+
+func (x *StopResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[9]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StopResponse.ProtoReflect.Descriptor instead.
+func (*StopResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{9}
+}
+
+func (x *StopResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+type ObserveRequest struct {
+	state   protoimpl.MessageState `protogen:"open.v1"`
+	Options *ObserveOptions        `protobuf:"bytes,1,opt,name=options,proto3" json:"options,omitempty"`
+	// When the session's state_version still equals this, skip the snapshot
+	// and answer with ObserveResponse.not_modified. state_version only moves
+	// on navigations and actions, so changes the page makes by itself
+	// (timers, fetches) don't count as modified. Ignored when the options ask
+	// for console messages, page errors, a network-idle wait or a frame.
+	IfStateVersionChanged *uint64 `protobuf:"varint,2,opt,name=if_state_version_changed,json=ifStateVersionChanged,proto3,oneof" json:"if_state_version_changed,omitempty"`
+	unknownFields         protoimpl.UnknownFields
+	sizeCache             protoimpl.SizeCache
+}
+
+func (x *ObserveRequest) Reset() {
+	*x = ObserveRequest{}
+	mi := &file_browserd_proto_msgTypes[10]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ObserveRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ObserveRequest) ProtoMessage() {}
+
+func (x *ObserveRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[10]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ObserveRequest.ProtoReflect.Descriptor instead.
+func (*ObserveRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{10}
+}
+
+func (x *ObserveRequest) GetOptions() *ObserveOptions {
+	if x != nil {
+		return x.Options
+	}
+	return nil
+}
+
+func (x *ObserveRequest) GetIfStateVersionChanged() uint64 {
+	if x != nil && x.IfStateVersionChanged != nil {
+		return *x.IfStateVersionChanged
+	}
+	return 0
+}
+
+type ObserveResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Unset when not_modified is.
+	Observation   *Observation `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	NotModified   bool         `protobuf:"varint,2,opt,name=not_modified,json=notModified,proto3" json:"not_modified,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ObserveResponse) Reset() {
+	*x = ObserveResponse{}
+	mi := &file_browserd_proto_msgTypes[11]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ObserveResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ObserveResponse) ProtoMessage() {}
+
+func (x *ObserveResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[11]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ObserveResponse.ProtoReflect.Descriptor instead.
+func (*ObserveResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{11}
+}
+
+func (x *ObserveResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+func (x *ObserveResponse) GetNotModified() bool {
+	if x != nil {
+		return x.NotModified
+	}
+	return false
+}
+
+type ActRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Action        *Action                `protobuf:"bytes,1,opt,name=action,proto3" json:"action,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ActRequest) Reset() {
+	*x = ActRequest{}
+	mi := &file_browserd_proto_msgTypes[12]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ActRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ActRequest) ProtoMessage() {}
+
+func (x *ActRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[12]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ActRequest.ProtoReflect.Descriptor instead.
+func (*ActRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{12}
+}
+
+func (x *ActRequest) GetAction() *Action {
+	if x != nil {
+		return x.Action
+	}
+	return nil
+}
+
+type ActResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Result        *ActionResult          `protobuf:"bytes,1,opt,name=result,proto3" json:"result,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ActResponse) Reset() {
+	*x = ActResponse{}
+	mi := &file_browserd_proto_msgTypes[13]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ActResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ActResponse) ProtoMessage() {}
+
+func (x *ActResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[13]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ActResponse.ProtoReflect.Descriptor instead.
+func (*ActResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{13}
+}
+
+func (x *ActResponse) GetResult() *ActionResult {
+	if x != nil {
+		return x.Result
+	}
+	return nil
+}
+
+// Actions run in order under one session lock. Only the first action's
+// expected_state_version is checked against the session; later ones are
+// checked against the version the previous action returned.
+type BatchActionsRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Actions       []*Action              `protobuf:"bytes,1,rep,name=actions,proto3" json:"actions,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *BatchActionsRequest) Reset() {
+	*x = BatchActionsRequest{}
+	mi := &file_browserd_proto_msgTypes[14]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *BatchActionsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*BatchActionsRequest) ProtoMessage() {}
+
+func (x *BatchActionsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[14]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use BatchActionsRequest.ProtoReflect.Descriptor instead.
+func (*BatchActionsRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{14}
+}
+
+func (x *BatchActionsRequest) GetActions() []*Action {
+	if x != nil {
+		return x.Actions
+	}
+	return nil
+}
+
+type BatchActionsResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Results of the actions that ran, in order.
+	Results []*ActionResult `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	// Index of the action that failed, or -1 when all succeeded. Actions after
+	// it were not run.
+	FailedIndex   int32  `protobuf:"varint,2,opt,name=failed_index,json=failedIndex,proto3" json:"failed_index,omitempty"`
+	Error         *Error `protobuf:"bytes,3,opt,name=error,proto3" json:"error,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *BatchActionsResponse) Reset() {
+	*x = BatchActionsResponse{}
+	mi := &file_browserd_proto_msgTypes[15]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *BatchActionsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*BatchActionsResponse) ProtoMessage() {}
+
+func (x *BatchActionsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[15]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use BatchActionsResponse.ProtoReflect.Descriptor instead.
+func (*BatchActionsResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{15}
+}
+
+func (x *BatchActionsResponse) GetResults() []*ActionResult {
+	if x != nil {
+		return x.Results
+	}
+	return nil
+}
+
+func (x *BatchActionsResponse) GetFailedIndex() int32 {
+	if x != nil {
+		return x.FailedIndex
+	}
+	return 0
+}
+
+func (x *BatchActionsResponse) GetError() *Error {
+	if x != nil {
+		return x.Error
+	}
+	return nil
+}
+
+type CloseSessionRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CloseSessionRequest) Reset() {
+	*x = CloseSessionRequest{}
+	mi := &file_browserd_proto_msgTypes[16]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CloseSessionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CloseSessionRequest) ProtoMessage() {}
+
+func (x *CloseSessionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[16]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CloseSessionRequest.ProtoReflect.Descriptor instead.
+func (*CloseSessionRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{16}
+}
+
+type CloseSessionResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Closed        bool                   `protobuf:"varint,1,opt,name=closed,proto3" json:"closed,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CloseSessionResponse) Reset() {
+	*x = CloseSessionResponse{}
+	mi := &file_browserd_proto_msgTypes[17]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CloseSessionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CloseSessionResponse) ProtoMessage() {}
+
+func (x *CloseSessionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[17]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CloseSessionResponse.ProtoReflect.Descriptor instead.
+func (*CloseSessionResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{17}
+}
+
+func (x *CloseSessionResponse) GetClosed() bool {
+	if x != nil {
+		return x.Closed
+	}
+	return false
+}
+
+type StreamSubscribeRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Options       *StreamOptions         `protobuf:"bytes,1,opt,name=options,proto3" json:"options,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StreamSubscribeRequest) Reset() {
+	*x = StreamSubscribeRequest{}
+	mi := &file_browserd_proto_msgTypes[18]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StreamSubscribeRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StreamSubscribeRequest) ProtoMessage() {}
+
+func (x *StreamSubscribeRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[18]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StreamSubscribeRequest.ProtoReflect.Descriptor instead.
+func (*StreamSubscribeRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{18}
+}
+
+func (x *StreamSubscribeRequest) GetOptions() *StreamOptions {
+	if x != nil {
+		return x.Options
+	}
+	return nil
+}
+
+type StreamSubscribeResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Subscribed    bool                   `protobuf:"varint,1,opt,name=subscribed,proto3" json:"subscribed,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StreamSubscribeResponse) Reset() {
+	*x = StreamSubscribeResponse{}
+	mi := &file_browserd_proto_msgTypes[19]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StreamSubscribeResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StreamSubscribeResponse) ProtoMessage() {}
+
+func (x *StreamSubscribeResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[19]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StreamSubscribeResponse.ProtoReflect.Descriptor instead.
+func (*StreamSubscribeResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{19}
+}
+
+func (x *StreamSubscribeResponse) GetSubscribed() bool {
+	if x != nil {
+		return x.Subscribed
+	}
+	return false
+}
+
+type SessionStatsRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SessionStatsRequest) Reset() {
+	*x = SessionStatsRequest{}
+	mi := &file_browserd_proto_msgTypes[20]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SessionStatsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SessionStatsRequest) ProtoMessage() {}
+
+func (x *SessionStatsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[20]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SessionStatsRequest.ProtoReflect.Descriptor instead.
+func (*SessionStatsRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{20}
+}
+
+type SessionStatsResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Resident memory of the daemon process. Sessions share the process, so
+	// this is the same for every session; 0 where it can't be read.
+	RssBytes     uint64 `protobuf:"varint,1,opt,name=rss_bytes,json=rssBytes,proto3" json:"rss_bytes,omitempty"`
+	StateVersion uint64 `protobuf:"varint,2,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	// Time since the session was created or restored.
+	UptimeMs      uint64 `protobuf:"varint,3,opt,name=uptime_ms,json=uptimeMs,proto3" json:"uptime_ms,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SessionStatsResponse) Reset() {
+	*x = SessionStatsResponse{}
+	mi := &file_browserd_proto_msgTypes[21]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SessionStatsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SessionStatsResponse) ProtoMessage() {}
+
+func (x *SessionStatsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[21]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SessionStatsResponse.ProtoReflect.Descriptor instead.
+func (*SessionStatsResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{21}
+}
+
+func (x *SessionStatsResponse) GetRssBytes() uint64 {
+	if x != nil {
+		return x.RssBytes
+	}
+	return 0
+}
+
+func (x *SessionStatsResponse) GetStateVersion() uint64 {
+	if x != nil {
+		return x.StateVersion
+	}
+	return 0
+}
+
+func (x *SessionStatsResponse) GetUptimeMs() uint64 {
+	if x != nil {
+		return x.UptimeMs
+	}
+	return 0
+}
+
+type ListSessionsRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListSessionsRequest) Reset() {
+	*x = ListSessionsRequest{}
+	mi := &file_browserd_proto_msgTypes[22]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListSessionsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListSessionsRequest) ProtoMessage() {}
+
+func (x *ListSessionsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[22]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListSessionsRequest.ProtoReflect.Descriptor instead.
+func (*ListSessionsRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{22}
+}
+
+type ListSessionsResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Sessions      []*SessionInfo         `protobuf:"bytes,1,rep,name=sessions,proto3" json:"sessions,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListSessionsResponse) Reset() {
+	*x = ListSessionsResponse{}
+	mi := &file_browserd_proto_msgTypes[23]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListSessionsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListSessionsResponse) ProtoMessage() {}
+
+func (x *ListSessionsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[23]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListSessionsResponse.ProtoReflect.Descriptor instead.
+func (*ListSessionsResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{23}
+}
+
+func (x *ListSessionsResponse) GetSessions() []*SessionInfo {
+	if x != nil {
+		return x.Sessions
+	}
+	return nil
+}
+
+type CheckContrastRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	NodeIds       []uint64               `protobuf:"varint,1,rep,packed,name=node_ids,json=nodeIds,proto3" json:"node_ids,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CheckContrastRequest) Reset() {
+	*x = CheckContrastRequest{}
+	mi := &file_browserd_proto_msgTypes[24]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CheckContrastRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CheckContrastRequest) ProtoMessage() {}
+
+func (x *CheckContrastRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[24]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CheckContrastRequest.ProtoReflect.Descriptor instead.
+func (*CheckContrastRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{24}
+}
+
+func (x *CheckContrastRequest) GetNodeIds() []uint64 {
+	if x != nil {
+		return x.NodeIds
+	}
+	return nil
+}
+
+type CheckContrastResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Results       []*ContrastResult      `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CheckContrastResponse) Reset() {
+	*x = CheckContrastResponse{}
+	mi := &file_browserd_proto_msgTypes[25]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CheckContrastResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CheckContrastResponse) ProtoMessage() {}
+
+func (x *CheckContrastResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[25]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CheckContrastResponse.ProtoReflect.Descriptor instead.
+func (*CheckContrastResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{25}
+}
+
+func (x *CheckContrastResponse) GetResults() []*ContrastResult {
+	if x != nil {
+		return x.Results
+	}
+	return nil
+}
+
+type ContrastResult struct {
+	state           protoimpl.MessageState `protogen:"open.v1"`
+	NodeId          uint64                 `protobuf:"varint,1,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
+	Text            string                 `protobuf:"bytes,2,opt,name=text,proto3" json:"text,omitempty"`
+	Color           string                 `protobuf:"bytes,3,opt,name=color,proto3" json:"color,omitempty"`
+	BackgroundColor string                 `protobuf:"bytes,4,opt,name=background_color,json=backgroundColor,proto3" json:"background_color,omitempty"`
+	ContrastRatio   float64                `protobuf:"fixed64,5,opt,name=contrast_ratio,json=contrastRatio,proto3" json:"contrast_ratio,omitempty"`
+	LargeText       bool                   `protobuf:"varint,6,opt,name=large_text,json=largeText,proto3" json:"large_text,omitempty"`
+	PassesAa        bool                   `protobuf:"varint,7,opt,name=passes_aa,json=passesAa,proto3" json:"passes_aa,omitempty"`
+	PassesAaa       bool                   `protobuf:"varint,8,opt,name=passes_aaa,json=passesAaa,proto3" json:"passes_aaa,omitempty"`
+	unknownFields   protoimpl.UnknownFields
+	sizeCache       protoimpl.SizeCache
+}
+
+func (x *ContrastResult) Reset() {
+	*x = ContrastResult{}
+	mi := &file_browserd_proto_msgTypes[26]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ContrastResult) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ContrastResult) ProtoMessage() {}
+
+func (x *ContrastResult) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[26]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ContrastResult.ProtoReflect.Descriptor instead.
+func (*ContrastResult) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{26}
+}
+
+func (x *ContrastResult) GetNodeId() uint64 {
+	if x != nil {
+		return x.NodeId
+	}
+	return 0
+}
+
+func (x *ContrastResult) GetText() string {
+	if x != nil {
+		return x.Text
+	}
+	return ""
+}
+
+func (x *ContrastResult) GetColor() string {
+	if x != nil {
+		return x.Color
+	}
+	return ""
+}
+
+func (x *ContrastResult) GetBackgroundColor() string {
+	if x != nil {
+		return x.BackgroundColor
+	}
+	return ""
+}
+
+func (x *ContrastResult) GetContrastRatio() float64 {
+	if x != nil {
+		return x.ContrastRatio
+	}
+	return 0
+}
+
+func (x *ContrastResult) GetLargeText() bool {
+	if x != nil {
+		return x.LargeText
+	}
+	return false
+}
+
+func (x *ContrastResult) GetPassesAa() bool {
+	if x != nil {
+		return x.PassesAa
+	}
+	return false
+}
+
+func (x *ContrastResult) GetPassesAaa() bool {
+	if x != nil {
+		return x.PassesAaa
+	}
+	return false
+}
+
+type GetInfoRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetInfoRequest) Reset() {
+	*x = GetInfoRequest{}
+	mi := &file_browserd_proto_msgTypes[27]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetInfoRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetInfoRequest) ProtoMessage() {}
+
+func (x *GetInfoRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[27]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetInfoRequest.ProtoReflect.Descriptor instead.
+func (*GetInfoRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{27}
+}
+
+type GetInfoResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	DaemonVersion string                 `protobuf:"bytes,1,opt,name=daemon_version,json=daemonVersion,proto3" json:"daemon_version,omitempty"`
+	Engine        *EngineInfo            `protobuf:"bytes,2,opt,name=engine,proto3" json:"engine,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetInfoResponse) Reset() {
+	*x = GetInfoResponse{}
+	mi := &file_browserd_proto_msgTypes[28]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetInfoResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetInfoResponse) ProtoMessage() {}
+
+func (x *GetInfoResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[28]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetInfoResponse.ProtoReflect.Descriptor instead.
+func (*GetInfoResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{28}
+}
+
+func (x *GetInfoResponse) GetDaemonVersion() string {
+	if x != nil {
+		return x.DaemonVersion
+	}
+	return ""
+}
+
+func (x *GetInfoResponse) GetEngine() *EngineInfo {
+	if x != nil {
+		return x.Engine
+	}
+	return nil
+}
+
+type EngineInfo struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Name          string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Version       string                 `protobuf:"bytes,2,opt,name=version,proto3" json:"version,omitempty"`
+	Commit        string                 `protobuf:"bytes,3,opt,name=commit,proto3" json:"commit,omitempty"`
+	Features      []string               `protobuf:"bytes,4,rep,name=features,proto3" json:"features,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *EngineInfo) Reset() {
+	*x = EngineInfo{}
+	mi := &file_browserd_proto_msgTypes[29]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *EngineInfo) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*EngineInfo) ProtoMessage() {}
+
+func (x *EngineInfo) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[29]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use EngineInfo.ProtoReflect.Descriptor instead.
+func (*EngineInfo) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{29}
+}
+
+func (x *EngineInfo) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *EngineInfo) GetVersion() string {
+	if x != nil {
+		return x.Version
+	}
+	return ""
+}
+
+func (x *EngineInfo) GetCommit() string {
+	if x != nil {
+		return x.Commit
+	}
+	return ""
+}
+
+func (x *EngineInfo) GetFeatures() []string {
+	if x != nil {
+		return x.Features
+	}
+	return nil
+}
+
+type ExportPdfRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	PageSize      *Viewport              `protobuf:"bytes,1,opt,name=page_size,json=pageSize,proto3" json:"page_size,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ExportPdfRequest) Reset() {
+	*x = ExportPdfRequest{}
+	mi := &file_browserd_proto_msgTypes[30]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ExportPdfRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExportPdfRequest) ProtoMessage() {}
+
+func (x *ExportPdfRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[30]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExportPdfRequest.ProtoReflect.Descriptor instead.
+func (*ExportPdfRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{30}
+}
+
+func (x *ExportPdfRequest) GetPageSize() *Viewport {
+	if x != nil {
+		return x.PageSize
+	}
+	return nil
+}
+
+type ExportPdfResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Data          []byte                 `protobuf:"bytes,1,opt,name=data,proto3" json:"data,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ExportPdfResponse) Reset() {
+	*x = ExportPdfResponse{}
+	mi := &file_browserd_proto_msgTypes[31]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ExportPdfResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExportPdfResponse) ProtoMessage() {}
+
+func (x *ExportPdfResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[31]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExportPdfResponse.ProtoReflect.Descriptor instead.
+func (*ExportPdfResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{31}
+}
+
+func (x *ExportPdfResponse) GetData() []byte {
+	if x != nil {
+		return x.Data
+	}
+	return nil
+}
+
+type Cookie struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Name          string                 `protobuf:"bytes,1,opt,name=name,proto3" json:"name,omitempty"`
+	Value         string                 `protobuf:"bytes,2,opt,name=value,proto3" json:"value,omitempty"`
+	Domain        string                 `protobuf:"bytes,3,opt,name=domain,proto3" json:"domain,omitempty"`
+	Path          string                 `protobuf:"bytes,4,opt,name=path,proto3" json:"path,omitempty"`
+	Secure        bool                   `protobuf:"varint,5,opt,name=secure,proto3" json:"secure,omitempty"`
+	HttpOnly      bool                   `protobuf:"varint,6,opt,name=http_only,json=httpOnly,proto3" json:"http_only,omitempty"`
+	ExpiresUnix   int64                  `protobuf:"varint,7,opt,name=expires_unix,json=expiresUnix,proto3" json:"expires_unix,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Cookie) Reset() {
+	*x = Cookie{}
+	mi := &file_browserd_proto_msgTypes[32]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Cookie) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Cookie) ProtoMessage() {}
+
+func (x *Cookie) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[32]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Cookie.ProtoReflect.Descriptor instead.
+func (*Cookie) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{32}
+}
+
+func (x *Cookie) GetName() string {
+	if x != nil {
+		return x.Name
+	}
+	return ""
+}
+
+func (x *Cookie) GetValue() string {
+	if x != nil {
+		return x.Value
+	}
+	return ""
+}
+
+func (x *Cookie) GetDomain() string {
+	if x != nil {
+		return x.Domain
+	}
+	return ""
+}
+
+func (x *Cookie) GetPath() string {
+	if x != nil {
+		return x.Path
+	}
+	return ""
+}
+
+func (x *Cookie) GetSecure() bool {
+	if x != nil {
+		return x.Secure
+	}
+	return false
+}
+
+func (x *Cookie) GetHttpOnly() bool {
+	if x != nil {
+		return x.HttpOnly
+	}
+	return false
+}
+
+func (x *Cookie) GetExpiresUnix() int64 {
+	if x != nil {
+		return x.ExpiresUnix
+	}
+	return 0
+}
+
+type GetCookiesRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetCookiesRequest) Reset() {
+	*x = GetCookiesRequest{}
+	mi := &file_browserd_proto_msgTypes[33]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetCookiesRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetCookiesRequest) ProtoMessage() {}
+
+func (x *GetCookiesRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[33]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetCookiesRequest.ProtoReflect.Descriptor instead.
+func (*GetCookiesRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{33}
+}
+
+type GetCookiesResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Cookies       []*Cookie              `protobuf:"bytes,1,rep,name=cookies,proto3" json:"cookies,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetCookiesResponse) Reset() {
+	*x = GetCookiesResponse{}
+	mi := &file_browserd_proto_msgTypes[34]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetCookiesResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetCookiesResponse) ProtoMessage() {}
+
+func (x *GetCookiesResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[34]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetCookiesResponse.ProtoReflect.Descriptor instead.
+func (*GetCookiesResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{34}
+}
+
+func (x *GetCookiesResponse) GetCookies() []*Cookie {
+	if x != nil {
+		return x.Cookies
+	}
+	return nil
+}
+
+type SetCookieRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Cookie        *Cookie                `protobuf:"bytes,1,opt,name=cookie,proto3" json:"cookie,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetCookieRequest) Reset() {
+	*x = SetCookieRequest{}
+	mi := &file_browserd_proto_msgTypes[35]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetCookieRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetCookieRequest) ProtoMessage() {}
+
+func (x *SetCookieRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[35]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetCookieRequest.ProtoReflect.Descriptor instead.
+func (*SetCookieRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{35}
+}
+
+func (x *SetCookieRequest) GetCookie() *Cookie {
+	if x != nil {
+		return x.Cookie
+	}
+	return nil
+}
+
+type SetCookieResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Set           bool                   `protobuf:"varint,1,opt,name=set,proto3" json:"set,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetCookieResponse) Reset() {
+	*x = SetCookieResponse{}
+	mi := &file_browserd_proto_msgTypes[36]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetCookieResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetCookieResponse) ProtoMessage() {}
+
+func (x *SetCookieResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[36]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetCookieResponse.ProtoReflect.Descriptor instead.
+func (*SetCookieResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{36}
+}
+
+func (x *SetCookieResponse) GetSet() bool {
+	if x != nil {
+		return x.Set
+	}
+	return false
+}
+
+type ClearCookiesRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearCookiesRequest) Reset() {
+	*x = ClearCookiesRequest{}
+	mi := &file_browserd_proto_msgTypes[37]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearCookiesRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearCookiesRequest) ProtoMessage() {}
+
+func (x *ClearCookiesRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[37]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearCookiesRequest.ProtoReflect.Descriptor instead.
+func (*ClearCookiesRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{37}
+}
+
+type ClearCookiesResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Cleared       uint32                 `protobuf:"varint,1,opt,name=cleared,proto3" json:"cleared,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearCookiesResponse) Reset() {
+	*x = ClearCookiesResponse{}
+	mi := &file_browserd_proto_msgTypes[38]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearCookiesResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearCookiesResponse) ProtoMessage() {}
+
+func (x *ClearCookiesResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[38]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearCookiesResponse.ProtoReflect.Descriptor instead.
+func (*ClearCookiesResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{38}
+}
+
+func (x *ClearCookiesResponse) GetCleared() uint32 {
+	if x != nil {
+		return x.Cleared
+	}
+	return 0
+}
+
+type StorageEntry struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Key           string                 `protobuf:"bytes,1,opt,name=key,proto3" json:"key,omitempty"`
+	Value         string                 `protobuf:"bytes,2,opt,name=value,proto3" json:"value,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StorageEntry) Reset() {
+	*x = StorageEntry{}
+	mi := &file_browserd_proto_msgTypes[39]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StorageEntry) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StorageEntry) ProtoMessage() {}
+
+func (x *StorageEntry) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[39]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StorageEntry.ProtoReflect.Descriptor instead.
+func (*StorageEntry) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{39}
+}
+
+func (x *StorageEntry) GetKey() string {
+	if x != nil {
+		return x.Key
+	}
+	return ""
+}
+
+func (x *StorageEntry) GetValue() string {
+	if x != nil {
+		return x.Value
+	}
+	return ""
+}
+
+// Writes `entries` into the active page's storage. The page's origin must be
+// reachable under the session's host lists.
+type SetStorageRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Scope         StorageScope           `protobuf:"varint,1,opt,name=scope,proto3,enum=buckley.browserd.v1.StorageScope" json:"scope,omitempty"`
+	Entries       []*StorageEntry        `protobuf:"bytes,2,rep,name=entries,proto3" json:"entries,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetStorageRequest) Reset() {
+	*x = SetStorageRequest{}
+	mi := &file_browserd_proto_msgTypes[40]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetStorageRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetStorageRequest) ProtoMessage() {}
+
+func (x *SetStorageRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[40]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetStorageRequest.ProtoReflect.Descriptor instead.
+func (*SetStorageRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{40}
+}
+
+func (x *SetStorageRequest) GetScope() StorageScope {
+	if x != nil {
+		return x.Scope
+	}
+	return StorageScope_STORAGE_SCOPE_UNSPECIFIED
+}
+
+func (x *SetStorageRequest) GetEntries() []*StorageEntry {
+	if x != nil {
+		return x.Entries
+	}
+	return nil
+}
+
+type SetStorageResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Set           uint32                 `protobuf:"varint,1,opt,name=set,proto3" json:"set,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetStorageResponse) Reset() {
+	*x = SetStorageResponse{}
+	mi := &file_browserd_proto_msgTypes[41]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetStorageResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetStorageResponse) ProtoMessage() {}
+
+func (x *SetStorageResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[41]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetStorageResponse.ProtoReflect.Descriptor instead.
+func (*SetStorageResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{41}
+}
+
+func (x *SetStorageResponse) GetSet() uint32 {
+	if x != nil {
+		return x.Set
+	}
+	return 0
+}
+
+type GetStorageRequest struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Scope StorageScope           `protobuf:"varint,1,opt,name=scope,proto3,enum=buckley.browserd.v1.StorageScope" json:"scope,omitempty"`
+	// Keys to read; empty reads every key. Missing keys are omitted.
+	Keys          []string `protobuf:"bytes,2,rep,name=keys,proto3" json:"keys,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetStorageRequest) Reset() {
+	*x = GetStorageRequest{}
+	mi := &file_browserd_proto_msgTypes[42]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetStorageRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetStorageRequest) ProtoMessage() {}
+
+func (x *GetStorageRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[42]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetStorageRequest.ProtoReflect.Descriptor instead.
+func (*GetStorageRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{42}
+}
+
+func (x *GetStorageRequest) GetScope() StorageScope {
+	if x != nil {
+		return x.Scope
+	}
+	return StorageScope_STORAGE_SCOPE_UNSPECIFIED
+}
+
+func (x *GetStorageRequest) GetKeys() []string {
+	if x != nil {
+		return x.Keys
+	}
+	return nil
+}
+
+type GetStorageResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Origin the entries belong to, e.g. "https://example.com".
+	Origin string `protobuf:"bytes,1,opt,name=origin,proto3" json:"origin,omitempty"`
+	// Sorted by key.
+	Entries       []*StorageEntry `protobuf:"bytes,2,rep,name=entries,proto3" json:"entries,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetStorageResponse) Reset() {
+	*x = GetStorageResponse{}
+	mi := &file_browserd_proto_msgTypes[43]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetStorageResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetStorageResponse) ProtoMessage() {}
+
+func (x *GetStorageResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[43]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetStorageResponse.ProtoReflect.Descriptor instead.
+func (*GetStorageResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{43}
+}
+
+func (x *GetStorageResponse) GetOrigin() string {
+	if x != nil {
+		return x.Origin
+	}
+	return ""
+}
+
+func (x *GetStorageResponse) GetEntries() []*StorageEntry {
+	if x != nil {
+		return x.Entries
+	}
+	return nil
+}
+
+type ClearStorageRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Scope         StorageScope           `protobuf:"varint,1,opt,name=scope,proto3,enum=buckley.browserd.v1.StorageScope" json:"scope,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearStorageRequest) Reset() {
+	*x = ClearStorageRequest{}
+	mi := &file_browserd_proto_msgTypes[44]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearStorageRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearStorageRequest) ProtoMessage() {}
+
+func (x *ClearStorageRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[44]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearStorageRequest.ProtoReflect.Descriptor instead.
+func (*ClearStorageRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{44}
+}
+
+func (x *ClearStorageRequest) GetScope() StorageScope {
+	if x != nil {
+		return x.Scope
+	}
+	return StorageScope_STORAGE_SCOPE_UNSPECIFIED
+}
+
+type ClearStorageResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Cleared       uint32                 `protobuf:"varint,1,opt,name=cleared,proto3" json:"cleared,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearStorageResponse) Reset() {
+	*x = ClearStorageResponse{}
+	mi := &file_browserd_proto_msgTypes[45]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearStorageResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearStorageResponse) ProtoMessage() {}
+
+func (x *ClearStorageResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[45]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearStorageResponse.ProtoReflect.Descriptor instead.
+func (*ClearStorageResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{45}
+}
+
+func (x *ClearStorageResponse) GetCleared() uint32 {
+	if x != nil {
+		return x.Cleared
+	}
+	return 0
+}
+
+// Drops the session's browsing data without closing it. Leaving every flag
+// unset clears everything.
+type ClearBrowsingDataRequest struct {
+	state   protoimpl.MessageState `protogen:"open.v1"`
+	Cookies bool                   `protobuf:"varint,1,opt,name=cookies,proto3" json:"cookies,omitempty"`
+	// Local and session storage.
+	Storage bool `protobuf:"varint,2,opt,name=storage,proto3" json:"storage,omitempty"`
+	// The HTTP cache.
+	Cache bool `protobuf:"varint,3,opt,name=cache,proto3" json:"cache,omitempty"`
+	// The virtual clipboard; a host clipboard is never touched.
+	Clipboard     bool `protobuf:"varint,4,opt,name=clipboard,proto3" json:"clipboard,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearBrowsingDataRequest) Reset() {
+	*x = ClearBrowsingDataRequest{}
+	mi := &file_browserd_proto_msgTypes[46]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearBrowsingDataRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearBrowsingDataRequest) ProtoMessage() {}
+
+func (x *ClearBrowsingDataRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[46]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearBrowsingDataRequest.ProtoReflect.Descriptor instead.
+func (*ClearBrowsingDataRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{46}
+}
+
+func (x *ClearBrowsingDataRequest) GetCookies() bool {
+	if x != nil {
+		return x.Cookies
+	}
+	return false
+}
+
+func (x *ClearBrowsingDataRequest) GetStorage() bool {
+	if x != nil {
+		return x.Storage
+	}
+	return false
+}
+
+func (x *ClearBrowsingDataRequest) GetCache() bool {
+	if x != nil {
+		return x.Cache
+	}
+	return false
+}
+
+func (x *ClearBrowsingDataRequest) GetClipboard() bool {
+	if x != nil {
+		return x.Clipboard
+	}
+	return false
+}
+
+type ClearBrowsingDataResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Kinds that were cleared ("cookies", "storage", "cache", "clipboard").
+	// Requested kinds the engine can't clear are left out.
+	Cleared []string `protobuf:"bytes,1,rep,name=cleared,proto3" json:"cleared,omitempty"`
+	// Kinds in `cleared` that were only cleared in part. Servo can only reach
+	// the active page: script-visible cookies and the page origin's storage.
+	Partial       []string `protobuf:"bytes,2,rep,name=partial,proto3" json:"partial,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClearBrowsingDataResponse) Reset() {
+	*x = ClearBrowsingDataResponse{}
+	mi := &file_browserd_proto_msgTypes[47]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClearBrowsingDataResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClearBrowsingDataResponse) ProtoMessage() {}
+
+func (x *ClearBrowsingDataResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[47]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClearBrowsingDataResponse.ProtoReflect.Descriptor instead.
+func (*ClearBrowsingDataResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{47}
+}
+
+func (x *ClearBrowsingDataResponse) GetCleared() []string {
+	if x != nil {
+		return x.Cleared
+	}
+	return nil
+}
+
+func (x *ClearBrowsingDataResponse) GetPartial() []string {
+	if x != nil {
+		return x.Partial
+	}
+	return nil
+}
+
+type WaitForRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Selector      string                 `protobuf:"bytes,1,opt,name=selector,proto3" json:"selector,omitempty"`
+	TimeoutMs     uint32                 `protobuf:"varint,2,opt,name=timeout_ms,json=timeoutMs,proto3" json:"timeout_ms,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WaitForRequest) Reset() {
+	*x = WaitForRequest{}
+	mi := &file_browserd_proto_msgTypes[48]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WaitForRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WaitForRequest) ProtoMessage() {}
+
+func (x *WaitForRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[48]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WaitForRequest.ProtoReflect.Descriptor instead.
+func (*WaitForRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{48}
+}
+
+func (x *WaitForRequest) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *WaitForRequest) GetTimeoutMs() uint32 {
+	if x != nil {
+		return x.TimeoutMs
+	}
+	return 0
+}
+
+type WaitForResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WaitForResponse) Reset() {
+	*x = WaitForResponse{}
+	mi := &file_browserd_proto_msgTypes[49]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WaitForResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WaitForResponse) ProtoMessage() {}
+
+func (x *WaitForResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[49]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WaitForResponse.ProtoReflect.Descriptor instead.
+func (*WaitForResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{49}
+}
+
+func (x *WaitForResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+// Drains all sessions and stops the daemon. Only honored when the daemon runs
+// with BROWSERD_ALLOW_REMOTE_SHUTDOWN set.
+type ShutdownRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ShutdownRequest) Reset() {
+	*x = ShutdownRequest{}
+	mi := &file_browserd_proto_msgTypes[50]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ShutdownRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ShutdownRequest) ProtoMessage() {}
+
+func (x *ShutdownRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[50]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ShutdownRequest.ProtoReflect.Descriptor instead.
+func (*ShutdownRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{50}
+}
+
+type ShutdownResponse struct {
+	state          protoimpl.MessageState `protogen:"open.v1"`
+	SessionsClosed uint32                 `protobuf:"varint,1,opt,name=sessions_closed,json=sessionsClosed,proto3" json:"sessions_closed,omitempty"`
+	unknownFields  protoimpl.UnknownFields
+	sizeCache      protoimpl.SizeCache
+}
+
+func (x *ShutdownResponse) Reset() {
+	*x = ShutdownResponse{}
+	mi := &file_browserd_proto_msgTypes[51]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ShutdownResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ShutdownResponse) ProtoMessage() {}
+
+func (x *ShutdownResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[51]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ShutdownResponse.ProtoReflect.Descriptor instead.
+func (*ShutdownResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{51}
+}
+
+func (x *ShutdownResponse) GetSessionsClosed() uint32 {
+	if x != nil {
+		return x.SessionsClosed
+	}
+	return 0
+}
+
+// Reads a few properties of the first element matching a selector, without
+// taking a snapshot.
+type QueryElementRequest struct {
+	state        protoimpl.MessageState `protogen:"open.v1"`
+	Selector     string                 `protobuf:"bytes,1,opt,name=selector,proto3" json:"selector,omitempty"`
+	SelectorType SelectorType           `protobuf:"varint,2,opt,name=selector_type,json=selectorType,proto3,enum=buckley.browserd.v1.SelectorType" json:"selector_type,omitempty"`
+	// Names to read, at most 32. A DOM property of that name with a string,
+	// number or boolean value wins (so href is the resolved URL and checked
+	// the live state); otherwise the attribute of that name is read. Names
+	// that are neither are left out of the response.
+	Properties    []string `protobuf:"bytes,3,rep,name=properties,proto3" json:"properties,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *QueryElementRequest) Reset() {
+	*x = QueryElementRequest{}
+	mi := &file_browserd_proto_msgTypes[52]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *QueryElementRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*QueryElementRequest) ProtoMessage() {}
+
+func (x *QueryElementRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[52]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use QueryElementRequest.ProtoReflect.Descriptor instead.
+func (*QueryElementRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{52}
+}
+
+func (x *QueryElementRequest) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *QueryElementRequest) GetSelectorType() SelectorType {
+	if x != nil {
+		return x.SelectorType
+	}
+	return SelectorType_SELECTOR_TYPE_UNSPECIFIED
+}
+
+func (x *QueryElementRequest) GetProperties() []string {
+	if x != nil {
+		return x.Properties
+	}
+	return nil
+}
+
+type QueryElementResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Found         bool                   `protobuf:"varint,1,opt,name=found,proto3" json:"found,omitempty"`
+	Properties    map[string]string      `protobuf:"bytes,2,rep,name=properties,proto3" json:"properties,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *QueryElementResponse) Reset() {
+	*x = QueryElementResponse{}
+	mi := &file_browserd_proto_msgTypes[53]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *QueryElementResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*QueryElementResponse) ProtoMessage() {}
+
+func (x *QueryElementResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[53]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use QueryElementResponse.ProtoReflect.Descriptor instead.
+func (*QueryElementResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{53}
+}
+
+func (x *QueryElementResponse) GetFound() bool {
+	if x != nil {
+		return x.Found
+	}
+	return false
+}
+
+func (x *QueryElementResponse) GetProperties() map[string]string {
+	if x != nil {
+		return x.Properties
+	}
+	return nil
+}
+
+// Changes the daemon's log level until it exits or the level is set again.
+// The level applies to every module, replacing any RUST_LOG directives. Only
+// honored when the daemon runs with BROWSERD_ALLOW_LOG_LEVEL_CHANGE set.
+type SetLogLevelRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Level         LogLevel               `protobuf:"varint,1,opt,name=level,proto3,enum=buckley.browserd.v1.LogLevel" json:"level,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetLogLevelRequest) Reset() {
+	*x = SetLogLevelRequest{}
+	mi := &file_browserd_proto_msgTypes[54]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetLogLevelRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetLogLevelRequest) ProtoMessage() {}
+
+func (x *SetLogLevelRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[54]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetLogLevelRequest.ProtoReflect.Descriptor instead.
+func (*SetLogLevelRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{54}
+}
+
+func (x *SetLogLevelRequest) GetLevel() LogLevel {
+	if x != nil {
+		return x.Level
+	}
+	return LogLevel_LOG_LEVEL_UNSPECIFIED
+}
+
+type SetLogLevelResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Previous      LogLevel               `protobuf:"varint,1,opt,name=previous,proto3,enum=buckley.browserd.v1.LogLevel" json:"previous,omitempty"`
+	Level         LogLevel               `protobuf:"varint,2,opt,name=level,proto3,enum=buckley.browserd.v1.LogLevel" json:"level,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetLogLevelResponse) Reset() {
+	*x = SetLogLevelResponse{}
+	mi := &file_browserd_proto_msgTypes[55]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetLogLevelResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetLogLevelResponse) ProtoMessage() {}
+
+func (x *SetLogLevelResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[55]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetLogLevelResponse.ProtoReflect.Descriptor instead.
+func (*SetLogLevelResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{55}
+}
+
+func (x *SetLogLevelResponse) GetPrevious() LogLevel {
+	if x != nil {
+		return x.Previous
+	}
+	return LogLevel_LOG_LEVEL_UNSPECIFIED
+}
+
+func (x *SetLogLevelResponse) GetLevel() LogLevel {
+	if x != nil {
+		return x.Level
+	}
+	return LogLevel_LOG_LEVEL_UNSPECIFIED
+}
+
+// First message on a connection when the daemon runs with
+// BROWSERD_AUTH_TOKEN set; every other request is rejected until it matches.
+type AuthenticateRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Token         string                 `protobuf:"bytes,1,opt,name=token,proto3" json:"token,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *AuthenticateRequest) Reset() {
+	*x = AuthenticateRequest{}
+	mi := &file_browserd_proto_msgTypes[56]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *AuthenticateRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*AuthenticateRequest) ProtoMessage() {}
+
+func (x *AuthenticateRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[56]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use AuthenticateRequest.ProtoReflect.Descriptor instead.
+func (*AuthenticateRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{56}
+}
+
+func (x *AuthenticateRequest) GetToken() string {
+	if x != nil {
+		return x.Token
+	}
+	return ""
+}
+
+type AuthenticateResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Authenticated bool                   `protobuf:"varint,1,opt,name=authenticated,proto3" json:"authenticated,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *AuthenticateResponse) Reset() {
+	*x = AuthenticateResponse{}
+	mi := &file_browserd_proto_msgTypes[57]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *AuthenticateResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*AuthenticateResponse) ProtoMessage() {}
+
+func (x *AuthenticateResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[57]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use AuthenticateResponse.ProtoReflect.Descriptor instead.
+func (*AuthenticateResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{57}
+}
+
+func (x *AuthenticateResponse) GetAuthenticated() bool {
+	if x != nil {
+		return x.Authenticated
+	}
+	return false
+}
+
+// One webview within a session. Navigation, observation and actions always
+// target the active tab.
+type Tab struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	TabId         uint64                 `protobuf:"varint,1,opt,name=tab_id,json=tabId,proto3" json:"tab_id,omitempty"`
+	Url           string                 `protobuf:"bytes,2,opt,name=url,proto3" json:"url,omitempty"`
+	Title         string                 `protobuf:"bytes,3,opt,name=title,proto3" json:"title,omitempty"`
+	Active        bool                   `protobuf:"varint,4,opt,name=active,proto3" json:"active,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Tab) Reset() {
+	*x = Tab{}
+	mi := &file_browserd_proto_msgTypes[58]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Tab) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Tab) ProtoMessage() {}
+
+func (x *Tab) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[58]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Tab.ProtoReflect.Descriptor instead.
+func (*Tab) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{58}
+}
+
+func (x *Tab) GetTabId() uint64 {
+	if x != nil {
+		return x.TabId
+	}
+	return 0
+}
+
+func (x *Tab) GetUrl() string {
+	if x != nil {
+		return x.Url
+	}
+	return ""
+}
+
+func (x *Tab) GetTitle() string {
+	if x != nil {
+		return x.Title
+	}
+	return ""
+}
+
+func (x *Tab) GetActive() bool {
+	if x != nil {
+		return x.Active
+	}
+	return false
+}
+
+// Opens a tab at `url` (about:blank when empty) and makes it active.
+type OpenTabRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Url           string                 `protobuf:"bytes,1,opt,name=url,proto3" json:"url,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *OpenTabRequest) Reset() {
+	*x = OpenTabRequest{}
+	mi := &file_browserd_proto_msgTypes[59]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *OpenTabRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*OpenTabRequest) ProtoMessage() {}
+
+func (x *OpenTabRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[59]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use OpenTabRequest.ProtoReflect.Descriptor instead.
+func (*OpenTabRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{59}
+}
+
+func (x *OpenTabRequest) GetUrl() string {
+	if x != nil {
+		return x.Url
+	}
+	return ""
+}
+
+type OpenTabResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Tab           *Tab                   `protobuf:"bytes,1,opt,name=tab,proto3" json:"tab,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *OpenTabResponse) Reset() {
+	*x = OpenTabResponse{}
+	mi := &file_browserd_proto_msgTypes[60]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *OpenTabResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*OpenTabResponse) ProtoMessage() {}
+
+func (x *OpenTabResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[60]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use OpenTabResponse.ProtoReflect.Descriptor instead.
+func (*OpenTabResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{60}
+}
+
+func (x *OpenTabResponse) GetTab() *Tab {
+	if x != nil {
+		return x.Tab
+	}
+	return nil
+}
+
+// Closing the active tab activates its neighbour. The last tab can't be closed.
+type CloseTabRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	TabId         uint64                 `protobuf:"varint,1,opt,name=tab_id,json=tabId,proto3" json:"tab_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CloseTabRequest) Reset() {
+	*x = CloseTabRequest{}
+	mi := &file_browserd_proto_msgTypes[61]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CloseTabRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CloseTabRequest) ProtoMessage() {}
+
+func (x *CloseTabRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[61]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CloseTabRequest.ProtoReflect.Descriptor instead.
+func (*CloseTabRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{61}
+}
+
+func (x *CloseTabRequest) GetTabId() uint64 {
+	if x != nil {
+		return x.TabId
+	}
+	return 0
+}
+
+type CloseTabResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	ActiveTabId   uint64                 `protobuf:"varint,1,opt,name=active_tab_id,json=activeTabId,proto3" json:"active_tab_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *CloseTabResponse) Reset() {
+	*x = CloseTabResponse{}
+	mi := &file_browserd_proto_msgTypes[62]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *CloseTabResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*CloseTabResponse) ProtoMessage() {}
+
+func (x *CloseTabResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[62]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use CloseTabResponse.ProtoReflect.Descriptor instead.
+func (*CloseTabResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{62}
+}
+
+func (x *CloseTabResponse) GetActiveTabId() uint64 {
+	if x != nil {
+		return x.ActiveTabId
+	}
+	return 0
+}
+
+type SwitchTabRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	TabId         uint64                 `protobuf:"varint,1,opt,name=tab_id,json=tabId,proto3" json:"tab_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SwitchTabRequest) Reset() {
+	*x = SwitchTabRequest{}
+	mi := &file_browserd_proto_msgTypes[63]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SwitchTabRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SwitchTabRequest) ProtoMessage() {}
+
+func (x *SwitchTabRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[63]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SwitchTabRequest.ProtoReflect.Descriptor instead.
+func (*SwitchTabRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{63}
+}
+
+func (x *SwitchTabRequest) GetTabId() uint64 {
+	if x != nil {
+		return x.TabId
+	}
+	return 0
+}
+
+type SwitchTabResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SwitchTabResponse) Reset() {
+	*x = SwitchTabResponse{}
+	mi := &file_browserd_proto_msgTypes[64]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SwitchTabResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SwitchTabResponse) ProtoMessage() {}
+
+func (x *SwitchTabResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[64]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SwitchTabResponse.ProtoReflect.Descriptor instead.
+func (*SwitchTabResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{64}
+}
+
+func (x *SwitchTabResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+type ListTabsRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListTabsRequest) Reset() {
+	*x = ListTabsRequest{}
+	mi := &file_browserd_proto_msgTypes[65]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListTabsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListTabsRequest) ProtoMessage() {}
+
+func (x *ListTabsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[65]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListTabsRequest.ProtoReflect.Descriptor instead.
+func (*ListTabsRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{65}
+}
+
+type ListTabsResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Tabs          []*Tab                 `protobuf:"bytes,1,rep,name=tabs,proto3" json:"tabs,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListTabsResponse) Reset() {
+	*x = ListTabsResponse{}
+	mi := &file_browserd_proto_msgTypes[66]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListTabsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListTabsResponse) ProtoMessage() {}
+
+func (x *ListTabsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[66]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListTabsResponse.ProtoReflect.Descriptor instead.
+func (*ListTabsResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{66}
+}
+
+func (x *ListTabsResponse) GetTabs() []*Tab {
+	if x != nil {
+		return x.Tabs
+	}
+	return nil
+}
+
+// Visible text of the active tab with whitespace collapsed and blank lines
+// dropped. `max_bytes` caps the returned text; 0 uses the daemon default.
+type ExtractTextRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	MaxBytes      uint32                 `protobuf:"varint,1,opt,name=max_bytes,json=maxBytes,proto3" json:"max_bytes,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ExtractTextRequest) Reset() {
+	*x = ExtractTextRequest{}
+	mi := &file_browserd_proto_msgTypes[67]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ExtractTextRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExtractTextRequest) ProtoMessage() {}
+
+func (x *ExtractTextRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[67]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExtractTextRequest.ProtoReflect.Descriptor instead.
+func (*ExtractTextRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{67}
+}
+
+func (x *ExtractTextRequest) GetMaxBytes() uint32 {
+	if x != nil {
+		return x.MaxBytes
+	}
+	return 0
+}
+
+type ExtractTextResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	Text  string                 `protobuf:"bytes,1,opt,name=text,proto3" json:"text,omitempty"`
+	// Words in `text`, after truncation.
+	WordCount     uint32 `protobuf:"varint,2,opt,name=word_count,json=wordCount,proto3" json:"word_count,omitempty"`
+	Truncated     bool   `protobuf:"varint,3,opt,name=truncated,proto3" json:"truncated,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ExtractTextResponse) Reset() {
+	*x = ExtractTextResponse{}
+	mi := &file_browserd_proto_msgTypes[68]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ExtractTextResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ExtractTextResponse) ProtoMessage() {}
+
+func (x *ExtractTextResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[68]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ExtractTextResponse.ProtoReflect.Descriptor instead.
+func (*ExtractTextResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{68}
+}
+
+func (x *ExtractTextResponse) GetText() string {
+	if x != nil {
+		return x.Text
+	}
+	return ""
+}
+
+func (x *ExtractTextResponse) GetWordCount() uint32 {
+	if x != nil {
+		return x.WordCount
+	}
+	return 0
+}
+
+func (x *ExtractTextResponse) GetTruncated() bool {
+	if x != nil {
+		return x.Truncated
+	}
+	return false
+}
+
+// Sets the value of each field's element and fires `input` and `change` on
+// it, in one round trip. Checkboxes and radios are checked for "true", "on"
+// or "1". At most 100 fields; the session's JS budget covers the whole batch.
+type FillFormRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Fields        []*FormField           `protobuf:"bytes,1,rep,name=fields,proto3" json:"fields,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *FillFormRequest) Reset() {
+	*x = FillFormRequest{}
+	mi := &file_browserd_proto_msgTypes[69]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *FillFormRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*FillFormRequest) ProtoMessage() {}
+
+func (x *FillFormRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[69]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use FillFormRequest.ProtoReflect.Descriptor instead.
+func (*FillFormRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{69}
+}
+
+func (x *FillFormRequest) GetFields() []*FormField {
+	if x != nil {
+		return x.Fields
+	}
+	return nil
+}
+
+type FormField struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// CSS selector; the first match is filled.
+	Selector      string `protobuf:"bytes,1,opt,name=selector,proto3" json:"selector,omitempty"`
+	Value         string `protobuf:"bytes,2,opt,name=value,proto3" json:"value,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *FormField) Reset() {
+	*x = FormField{}
+	mi := &file_browserd_proto_msgTypes[70]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *FormField) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*FormField) ProtoMessage() {}
+
+func (x *FormField) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[70]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use FormField.ProtoReflect.Descriptor instead.
+func (*FormField) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{70}
+}
+
+func (x *FormField) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *FormField) GetValue() string {
+	if x != nil {
+		return x.Value
+	}
+	return ""
+}
+
+type FillFormResponse struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// One per request field, in order.
+	Results       []*FieldResult `protobuf:"bytes,1,rep,name=results,proto3" json:"results,omitempty"`
+	StateVersion  uint64         `protobuf:"varint,2,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *FillFormResponse) Reset() {
+	*x = FillFormResponse{}
+	mi := &file_browserd_proto_msgTypes[71]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *FillFormResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*FillFormResponse) ProtoMessage() {}
+
+func (x *FillFormResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[71]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use FillFormResponse.ProtoReflect.Descriptor instead.
+func (*FillFormResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{71}
+}
+
+func (x *FillFormResponse) GetResults() []*FieldResult {
+	if x != nil {
+		return x.Results
+	}
+	return nil
+}
+
+func (x *FillFormResponse) GetStateVersion() uint64 {
+	if x != nil {
+		return x.StateVersion
+	}
+	return 0
+}
+
+type FieldResult struct {
+	state    protoimpl.MessageState `protogen:"open.v1"`
+	Selector string                 `protobuf:"bytes,1,opt,name=selector,proto3" json:"selector,omitempty"`
+	Filled   bool                   `protobuf:"varint,2,opt,name=filled,proto3" json:"filled,omitempty"`
+	// Why the field wasn't filled: not_found, invalid_selector or
+	// not_fillable.
+	Error         string `protobuf:"bytes,3,opt,name=error,proto3" json:"error,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *FieldResult) Reset() {
+	*x = FieldResult{}
+	mi := &file_browserd_proto_msgTypes[72]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *FieldResult) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*FieldResult) ProtoMessage() {}
+
+func (x *FieldResult) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[72]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use FieldResult.ProtoReflect.Descriptor instead.
+func (*FieldResult) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{72}
+}
+
+func (x *FieldResult) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *FieldResult) GetFilled() bool {
+	if x != nil {
+		return x.Filled
+	}
+	return false
+}
+
+func (x *FieldResult) GetError() string {
+	if x != nil {
+		return x.Error
+	}
+	return ""
+}
+
+// Attaches a file to an <input type=file>, as if the user had picked it.
+// Fails with UPLOADS_DISABLED unless the daemon runs with
+// BROWSERD_SECURITY_UPLOADS_ENABLED.
+type SetFileInputRequest struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// CSS selector; the first match is used.
+	Selector string `protobuf:"bytes,1,opt,name=selector,proto3" json:"selector,omitempty"`
+	// Base name the page sees; must not contain path separators.
+	Filename      string `protobuf:"bytes,2,opt,name=filename,proto3" json:"filename,omitempty"`
+	Data          []byte `protobuf:"bytes,3,opt,name=data,proto3" json:"data,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetFileInputRequest) Reset() {
+	*x = SetFileInputRequest{}
+	mi := &file_browserd_proto_msgTypes[73]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetFileInputRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetFileInputRequest) ProtoMessage() {}
+
+func (x *SetFileInputRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[73]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetFileInputRequest.ProtoReflect.Descriptor instead.
+func (*SetFileInputRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{73}
+}
+
+func (x *SetFileInputRequest) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *SetFileInputRequest) GetFilename() string {
+	if x != nil {
+		return x.Filename
+	}
+	return ""
+}
+
+func (x *SetFileInputRequest) GetData() []byte {
+	if x != nil {
+		return x.Data
+	}
+	return nil
+}
+
+type SetFileInputResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Filename      string                 `protobuf:"bytes,1,opt,name=filename,proto3" json:"filename,omitempty"`
+	Bytes         uint64                 `protobuf:"varint,2,opt,name=bytes,proto3" json:"bytes,omitempty"`
+	StateVersion  uint64                 `protobuf:"varint,3,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetFileInputResponse) Reset() {
+	*x = SetFileInputResponse{}
+	mi := &file_browserd_proto_msgTypes[74]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetFileInputResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetFileInputResponse) ProtoMessage() {}
+
+func (x *SetFileInputResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[74]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetFileInputResponse.ProtoReflect.Descriptor instead.
+func (*SetFileInputResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{74}
+}
+
+func (x *SetFileInputResponse) GetFilename() string {
+	if x != nil {
+		return x.Filename
+	}
+	return ""
+}
+
+func (x *SetFileInputResponse) GetBytes() uint64 {
+	if x != nil {
+		return x.Bytes
+	}
+	return 0
+}
+
+func (x *SetFileInputResponse) GetStateVersion() uint64 {
+	if x != nil {
+		return x.StateVersion
+	}
+	return 0
+}
+
+// A file the page downloaded. Its bytes stay in a per-session temp dir until
+// the session closes.
+type Download struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Id            string                 `protobuf:"bytes,1,opt,name=id,proto3" json:"id,omitempty"`
+	Filename      string                 `protobuf:"bytes,2,opt,name=filename,proto3" json:"filename,omitempty"`
+	Url           string                 `protobuf:"bytes,3,opt,name=url,proto3" json:"url,omitempty"`
+	MimeType      string                 `protobuf:"bytes,4,opt,name=mime_type,json=mimeType,proto3" json:"mime_type,omitempty"`
+	Bytes         uint64                 `protobuf:"varint,5,opt,name=bytes,proto3" json:"bytes,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *Download) Reset() {
+	*x = Download{}
+	mi := &file_browserd_proto_msgTypes[75]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *Download) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*Download) ProtoMessage() {}
+
+func (x *Download) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[75]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use Download.ProtoReflect.Descriptor instead.
+func (*Download) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{75}
+}
+
+func (x *Download) GetId() string {
+	if x != nil {
+		return x.Id
+	}
+	return ""
+}
+
+func (x *Download) GetFilename() string {
+	if x != nil {
+		return x.Filename
+	}
+	return ""
+}
+
+func (x *Download) GetUrl() string {
+	if x != nil {
+		return x.Url
+	}
+	return ""
+}
+
+func (x *Download) GetMimeType() string {
+	if x != nil {
+		return x.MimeType
+	}
+	return ""
+}
+
+func (x *Download) GetBytes() uint64 {
+	if x != nil {
+		return x.Bytes
+	}
+	return 0
+}
+
+type ListDownloadsRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListDownloadsRequest) Reset() {
+	*x = ListDownloadsRequest{}
+	mi := &file_browserd_proto_msgTypes[76]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListDownloadsRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListDownloadsRequest) ProtoMessage() {}
+
+func (x *ListDownloadsRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[76]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListDownloadsRequest.ProtoReflect.Descriptor instead.
+func (*ListDownloadsRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{76}
+}
+
+type ListDownloadsResponse struct {
+	state     protoimpl.MessageState `protogen:"open.v1"`
+	Downloads []*Download            `protobuf:"bytes,1,rep,name=downloads,proto3" json:"downloads,omitempty"`
+	// Size of the kept downloads and the cap they count against.
+	TotalBytes    uint64 `protobuf:"varint,2,opt,name=total_bytes,json=totalBytes,proto3" json:"total_bytes,omitempty"`
+	MaxBytes      uint64 `protobuf:"varint,3,opt,name=max_bytes,json=maxBytes,proto3" json:"max_bytes,omitempty"`
+	Enabled       bool   `protobuf:"varint,4,opt,name=enabled,proto3" json:"enabled,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ListDownloadsResponse) Reset() {
+	*x = ListDownloadsResponse{}
+	mi := &file_browserd_proto_msgTypes[77]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ListDownloadsResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ListDownloadsResponse) ProtoMessage() {}
+
+func (x *ListDownloadsResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[77]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ListDownloadsResponse.ProtoReflect.Descriptor instead.
+func (*ListDownloadsResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{77}
+}
+
+func (x *ListDownloadsResponse) GetDownloads() []*Download {
+	if x != nil {
+		return x.Downloads
+	}
+	return nil
+}
+
+func (x *ListDownloadsResponse) GetTotalBytes() uint64 {
+	if x != nil {
+		return x.TotalBytes
+	}
+	return 0
+}
+
+func (x *ListDownloadsResponse) GetMaxBytes() uint64 {
+	if x != nil {
+		return x.MaxBytes
+	}
+	return 0
+}
+
+func (x *ListDownloadsResponse) GetEnabled() bool {
+	if x != nil {
+		return x.Enabled
+	}
+	return false
+}
+
+type GetDownloadRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	DownloadId    string                 `protobuf:"bytes,1,opt,name=download_id,json=downloadId,proto3" json:"download_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetDownloadRequest) Reset() {
+	*x = GetDownloadRequest{}
+	mi := &file_browserd_proto_msgTypes[78]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetDownloadRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetDownloadRequest) ProtoMessage() {}
+
+func (x *GetDownloadRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[78]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetDownloadRequest.ProtoReflect.Descriptor instead.
+func (*GetDownloadRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{78}
+}
+
+func (x *GetDownloadRequest) GetDownloadId() string {
+	if x != nil {
+		return x.DownloadId
+	}
+	return ""
+}
+
+type GetDownloadResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Download      *Download              `protobuf:"bytes,1,opt,name=download,proto3" json:"download,omitempty"`
+	Data          []byte                 `protobuf:"bytes,2,opt,name=data,proto3" json:"data,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *GetDownloadResponse) Reset() {
+	*x = GetDownloadResponse{}
+	mi := &file_browserd_proto_msgTypes[79]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *GetDownloadResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*GetDownloadResponse) ProtoMessage() {}
+
+func (x *GetDownloadResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[79]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use GetDownloadResponse.ProtoReflect.Descriptor instead.
+func (*GetDownloadResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{79}
+}
+
+func (x *GetDownloadResponse) GetDownload() *Download {
+	if x != nil {
+		return x.Download
+	}
+	return nil
+}
+
+func (x *GetDownloadResponse) GetData() []byte {
+	if x != nil {
+		return x.Data
+	}
+	return nil
+}
+
+// Resizes every tab in the session. Width and height must be 1..=16384; a
+// device_scale_factor of 0 keeps the current scale.
+type SetViewportRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Viewport      *Viewport              `protobuf:"bytes,1,opt,name=viewport,proto3" json:"viewport,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetViewportRequest) Reset() {
+	*x = SetViewportRequest{}
+	mi := &file_browserd_proto_msgTypes[80]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetViewportRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetViewportRequest) ProtoMessage() {}
+
+func (x *SetViewportRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[80]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetViewportRequest.ProtoReflect.Descriptor instead.
+func (*SetViewportRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{80}
+}
+
+func (x *SetViewportRequest) GetViewport() *Viewport {
+	if x != nil {
+		return x.Viewport
+	}
+	return nil
+}
+
+type SetViewportResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SetViewportResponse) Reset() {
+	*x = SetViewportResponse{}
+	mi := &file_browserd_proto_msgTypes[81]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SetViewportResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SetViewportResponse) ProtoMessage() {}
+
+func (x *SetViewportResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[81]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SetViewportResponse.ProtoReflect.Descriptor instead.
+func (*SetViewportResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{81}
+}
+
+func (x *SetViewportResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+// Writes the session's config, URL, cookies and scroll offset under
+// BROWSERD_SESSION_DIR. Fails with UNSUPPORTED when that isn't set.
+type SaveSessionRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SaveSessionRequest) Reset() {
+	*x = SaveSessionRequest{}
+	mi := &file_browserd_proto_msgTypes[82]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SaveSessionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SaveSessionRequest) ProtoMessage() {}
+
+func (x *SaveSessionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[82]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SaveSessionRequest.ProtoReflect.Descriptor instead.
+func (*SaveSessionRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{82}
+}
+
+type SaveSessionResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Path          string                 `protobuf:"bytes,1,opt,name=path,proto3" json:"path,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SaveSessionResponse) Reset() {
+	*x = SaveSessionResponse{}
+	mi := &file_browserd_proto_msgTypes[83]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SaveSessionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SaveSessionResponse) ProtoMessage() {}
+
+func (x *SaveSessionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[83]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SaveSessionResponse.ProtoReflect.Descriptor instead.
+func (*SaveSessionResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{83}
+}
+
+func (x *SaveSessionResponse) GetPath() string {
+	if x != nil {
+		return x.Path
+	}
+	return ""
+}
+
+// Recreates the request's session from its saved snapshot: a fresh engine,
+// navigated to the saved URL, with cookies and scroll offset replayed.
+type RestoreSessionRequest struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *RestoreSessionRequest) Reset() {
+	*x = RestoreSessionRequest{}
+	mi := &file_browserd_proto_msgTypes[84]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *RestoreSessionRequest) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*RestoreSessionRequest) ProtoMessage() {}
+
+func (x *RestoreSessionRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[84]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use RestoreSessionRequest.ProtoReflect.Descriptor instead.
+func (*RestoreSessionRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{84}
+}
+
+type RestoreSessionResponse struct {
+	state       protoimpl.MessageState `protogen:"open.v1"`
+	Session     *SessionInfo           `protobuf:"bytes,1,opt,name=session,proto3" json:"session,omitempty"`
+	Observation *Observation           `protobuf:"bytes,2,opt,name=observation,proto3" json:"observation,omitempty"`
+	// Cookies the engine accepted; the rest (e.g. http_only under Servo) are
+	// skipped.
+	CookiesRestored uint32 `protobuf:"varint,3,opt,name=cookies_restored,json=cookiesRestored,proto3" json:"cookies_restored,omitempty"`
+	unknownFields   protoimpl.UnknownFields
+	sizeCache       protoimpl.SizeCache
+}
+
+func (x *RestoreSessionResponse) Reset() {
+	*x = RestoreSessionResponse{}
+	mi := &file_browserd_proto_msgTypes[85]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *RestoreSessionResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*RestoreSessionResponse) ProtoMessage() {}
+
+func (x *RestoreSessionResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[85]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use RestoreSessionResponse.ProtoReflect.Descriptor instead.
+func (*RestoreSessionResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{85}
+}
+
+func (x *RestoreSessionResponse) GetSession() *SessionInfo {
+	if x != nil {
+		return x.Session
+	}
+	return nil
+}
+
+func (x *RestoreSessionResponse) GetObservation() *Observation {
+	if x != nil {
+		return x.Observation
+	}
+	return nil
+}
+
+func (x *RestoreSessionResponse) GetCookiesRestored() uint32 {
+	if x != nil {
+		return x.CookiesRestored
+	}
+	return 0
+}
+
+type SessionInfo struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	SessionId     string                 `protobuf:"bytes,1,opt,name=session_id,json=sessionId,proto3" json:"session_id,omitempty"`
+	StateVersion  uint64                 `protobuf:"varint,2,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	Url           string                 `protobuf:"bytes,3,opt,name=url,proto3" json:"url,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *SessionInfo) Reset() {
+	*x = SessionInfo{}
+	mi := &file_browserd_proto_msgTypes[86]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *SessionInfo) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*SessionInfo) ProtoMessage() {}
+
+func (x *SessionInfo) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[86]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use SessionInfo.ProtoReflect.Descriptor instead.
+func (*SessionInfo) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{86}
+}
+
+func (x *SessionInfo) GetSessionId() string {
+	if x != nil {
+		return x.SessionId
+	}
+	return ""
+}
+
+func (x *SessionInfo) GetStateVersion() uint64 {
+	if x != nil {
+		return x.StateVersion
+	}
+	return 0
+}
+
+func (x *SessionInfo) GetUrl() string {
+	if x != nil {
+		return x.Url
+	}
+	return ""
+}
+
+type SessionConfig struct {
+	state      protoimpl.MessageState `protogen:"open.v1"`
+	SessionId  string                 `protobuf:"bytes,1,opt,name=session_id,json=sessionId,proto3" json:"session_id,omitempty"`
+	InitialUrl string                 `protobuf:"bytes,2,opt,name=initial_url,json=initialUrl,proto3" json:"initial_url,omitempty"`
+	Viewport   *Viewport              `protobuf:"bytes,3,opt,name=viewport,proto3" json:"viewport,omitempty"`
+	UserAgent  string                 `protobuf:"bytes,4,opt,name=user_agent,json=userAgent,proto3" json:"user_agent,omitempty"`
+	// The servo engine accepts locale and timezone but can't apply them yet:
+	// it has no hook to send Accept-Language and reads the timezone from the
+	// daemon's process. Both are ignored with a warning.
+	Locale              string           `protobuf:"bytes,5,opt,name=locale,proto3" json:"locale,omitempty"`
+	Timezone            string           `protobuf:"bytes,6,opt,name=timezone,proto3" json:"timezone,omitempty"`
+	FrameRate           uint32           `protobuf:"varint,7,opt,name=frame_rate,json=frameRate,proto3" json:"frame_rate,omitempty"`
+	NetworkAllowlist    []string         `protobuf:"bytes,8,rep,name=network_allowlist,json=networkAllowlist,proto3" json:"network_allowlist,omitempty"`
+	Clipboard           *ClipboardPolicy `protobuf:"bytes,9,opt,name=clipboard,proto3" json:"clipboard,omitempty"`
+	FrameDeltaEncoding  bool             `protobuf:"varint,10,opt,name=frame_delta_encoding,json=frameDeltaEncoding,proto3" json:"frame_delta_encoding,omitempty"`
+	NavigationTimeoutMs uint32           `protobuf:"varint,11,opt,name=navigation_timeout_ms,json=navigationTimeoutMs,proto3" json:"navigation_timeout_ms,omitempty"`
+	// Per-evaluation JavaScript budget. 0 falls back to the daemon's
+	// BROWSERD_SECURITY_JS_BUDGET_MS, then to the engine default.
+	JsBudgetMs uint32 `protobuf:"varint,12,opt,name=js_budget_ms,json=jsBudgetMs,proto3" json:"js_budget_ms,omitempty"`
+	// Extra headers sent with every navigation in the session. Names the
+	// network stack owns (Host, Content-Length, hop-by-hop headers) are
+	// rejected with invalid_request. The servo engine can't send headers yet
+	// and rejects sessions that set any as unsupported; the stub sends no
+	// requests and only reports the header count in its DOM snapshot.
+	RequestHeaders map[string]string `protobuf:"bytes,13,rep,name=request_headers,json=requestHeaders,proto3" json:"request_headers,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
+	// Hosts that are always blocked, even when network_allowlist permits them.
+	// Entries use the allowlist syntax (host, host:port, *.suffix, CIDR).
+	NetworkDenylist []string `protobuf:"bytes,14,rep,name=network_denylist,json=networkDenylist,proto3" json:"network_denylist,omitempty"`
+	// Time to keep running the page after it reports loaded, so client-side
+	// rendering can finish before the navigation's observation. Unset uses
+	// 100ms; 0 disables. Never extends past the navigation timeout.
+	PostLoadSettleMs *uint32 `protobuf:"varint,15,opt,name=post_load_settle_ms,json=postLoadSettleMs,proto3,oneof" json:"post_load_settle_ms,omitempty"`
+	// Set by the daemon from BROWSERD_SECURITY_DOWNLOADS_ENABLED; a value sent
+	// by the client is overwritten. When false, downloads are cancelled.
+	DownloadsEnabled bool `protobuf:"varint,16,opt,name=downloads_enabled,json=downloadsEnabled,proto3" json:"downloads_enabled,omitempty"`
+	// Cap on the total size of the session's downloads. 0, or anything above
+	// BROWSERD_SECURITY_MAX_DOWNLOAD_BYTES, uses the daemon's cap.
+	MaxDownloadBytes uint64 `protobuf:"varint,17,opt,name=max_download_bytes,json=maxDownloadBytes,proto3" json:"max_download_bytes,omitempty"`
+	// URL schemes the session may load. Set by the daemon from
+	// BROWSERD_ALLOWED_SCHEMES; a value sent by the client is overwritten.
+	// Empty means http, https and about; about is allowed even when a
+	// configured list leaves it out.
+	AllowedSchemes []string `protobuf:"bytes,18,rep,name=allowed_schemes,json=allowedSchemes,proto3" json:"allowed_schemes,omitempty"`
+	// Named device preset ("desktop", "iphone", "pixel", "ipad") supplying the
+	// viewport, scale factor and user agent. Fields set explicitly above take
+	// precedence. Unknown names are rejected.
+	DeviceProfile string `protobuf:"bytes,19,opt,name=device_profile,json=deviceProfile,proto3" json:"device_profile,omitempty"`
+	// Keep nothing beyond the session: browsing data lives only in memory and
+	// SaveSession is refused. Servo sessions never write browsing data to
+	// disk, so there the flag's only effect is refusing SaveSession.
+	Ephemeral     bool `protobuf:"varint,20,opt,name=ephemeral,proto3" json:"ephemeral,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *Response) Reset() {
-	*x = Response{}
-	mi := &file_browserd_proto_msgTypes[3]
+func (x *SessionConfig) Reset() {
+	*x = SessionConfig{}
+	mi := &file_browserd_proto_msgTypes[87]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *Response) String() string {
+func (x *SessionConfig) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*Response) ProtoMessage() {}
+func (*SessionConfig) ProtoMessage() {}
 
-func (x *Response) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[3]
+func (x *SessionConfig) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[87]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -705,155 +6599,237 @@ func (x *Response) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use Response.ProtoReflect.Descriptor instead.
-func (*Response) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{3}
+// Deprecated: Use SessionConfig.ProtoReflect.Descriptor instead.
+func (*SessionConfig) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{87}
 }
 
-func (x *Response) GetRequestId() string {
+func (x *SessionConfig) GetSessionId() string {
 	if x != nil {
-		return x.RequestId
+		return x.SessionId
 	}
 	return ""
 }
 
-func (x *Response) GetSessionId() string {
+func (x *SessionConfig) GetInitialUrl() string {
 	if x != nil {
-		return x.SessionId
+		return x.InitialUrl
 	}
 	return ""
 }
 
-func (x *Response) GetError() *Error {
+func (x *SessionConfig) GetViewport() *Viewport {
 	if x != nil {
-		return x.Error
+		return x.Viewport
 	}
 	return nil
 }
 
-func (x *Response) GetPayload() isResponse_Payload {
+func (x *SessionConfig) GetUserAgent() string {
 	if x != nil {
-		return x.Payload
+		return x.UserAgent
 	}
-	return nil
+	return ""
 }
 
-func (x *Response) GetCreateSession() *CreateSessionResponse {
+func (x *SessionConfig) GetLocale() string {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_CreateSession); ok {
-			return x.CreateSession
-		}
+		return x.Locale
 	}
-	return nil
+	return ""
 }
 
-func (x *Response) GetNavigate() *NavigateResponse {
+func (x *SessionConfig) GetTimezone() string {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_Navigate); ok {
-			return x.Navigate
-		}
+		return x.Timezone
 	}
-	return nil
+	return ""
 }
 
-func (x *Response) GetObserve() *ObserveResponse {
+func (x *SessionConfig) GetFrameRate() uint32 {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_Observe); ok {
-			return x.Observe
-		}
+		return x.FrameRate
+	}
+	return 0
+}
+
+func (x *SessionConfig) GetNetworkAllowlist() []string {
+	if x != nil {
+		return x.NetworkAllowlist
 	}
 	return nil
 }
 
-func (x *Response) GetAct() *ActResponse {
+func (x *SessionConfig) GetClipboard() *ClipboardPolicy {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_Act); ok {
-			return x.Act
-		}
+		return x.Clipboard
 	}
 	return nil
 }
 
-func (x *Response) GetCloseSession() *CloseSessionResponse {
+func (x *SessionConfig) GetFrameDeltaEncoding() bool {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_CloseSession); ok {
-			return x.CloseSession
-		}
+		return x.FrameDeltaEncoding
+	}
+	return false
+}
+
+func (x *SessionConfig) GetNavigationTimeoutMs() uint32 {
+	if x != nil {
+		return x.NavigationTimeoutMs
+	}
+	return 0
+}
+
+func (x *SessionConfig) GetJsBudgetMs() uint32 {
+	if x != nil {
+		return x.JsBudgetMs
+	}
+	return 0
+}
+
+func (x *SessionConfig) GetRequestHeaders() map[string]string {
+	if x != nil {
+		return x.RequestHeaders
 	}
 	return nil
 }
 
-func (x *Response) GetStreamSubscribe() *StreamSubscribeResponse {
+func (x *SessionConfig) GetNetworkDenylist() []string {
 	if x != nil {
-		if x, ok := x.Payload.(*Response_StreamSubscribe); ok {
-			return x.StreamSubscribe
-		}
+		return x.NetworkDenylist
 	}
 	return nil
 }
 
-type isResponse_Payload interface {
-	isResponse_Payload()
+func (x *SessionConfig) GetPostLoadSettleMs() uint32 {
+	if x != nil && x.PostLoadSettleMs != nil {
+		return *x.PostLoadSettleMs
+	}
+	return 0
 }
 
-type Response_CreateSession struct {
-	CreateSession *CreateSessionResponse `protobuf:"bytes,4,opt,name=create_session,json=createSession,proto3,oneof"`
+func (x *SessionConfig) GetDownloadsEnabled() bool {
+	if x != nil {
+		return x.DownloadsEnabled
+	}
+	return false
 }
 
-type Response_Navigate struct {
-	Navigate *NavigateResponse `protobuf:"bytes,5,opt,name=navigate,proto3,oneof"`
+func (x *SessionConfig) GetMaxDownloadBytes() uint64 {
+	if x != nil {
+		return x.MaxDownloadBytes
+	}
+	return 0
 }
 
-type Response_Observe struct {
-	Observe *ObserveResponse `protobuf:"bytes,6,opt,name=observe,proto3,oneof"`
+func (x *SessionConfig) GetAllowedSchemes() []string {
+	if x != nil {
+		return x.AllowedSchemes
+	}
+	return nil
 }
 
-type Response_Act struct {
-	Act *ActResponse `protobuf:"bytes,7,opt,name=act,proto3,oneof"`
+func (x *SessionConfig) GetDeviceProfile() string {
+	if x != nil {
+		return x.DeviceProfile
+	}
+	return ""
 }
 
-type Response_CloseSession struct {
-	CloseSession *CloseSessionResponse `protobuf:"bytes,8,opt,name=close_session,json=closeSession,proto3,oneof"`
+func (x *SessionConfig) GetEphemeral() bool {
+	if x != nil {
+		return x.Ephemeral
+	}
+	return false
 }
 
-type Response_StreamSubscribe struct {
-	StreamSubscribe *StreamSubscribeResponse `protobuf:"bytes,9,opt,name=stream_subscribe,json=streamSubscribe,proto3,oneof"`
+type Viewport struct {
+	state             protoimpl.MessageState `protogen:"open.v1"`
+	Width             uint32                 `protobuf:"varint,1,opt,name=width,proto3" json:"width,omitempty"`
+	Height            uint32                 `protobuf:"varint,2,opt,name=height,proto3" json:"height,omitempty"`
+	DeviceScaleFactor float64                `protobuf:"fixed64,3,opt,name=device_scale_factor,json=deviceScaleFactor,proto3" json:"device_scale_factor,omitempty"`
+	unknownFields     protoimpl.UnknownFields
+	sizeCache         protoimpl.SizeCache
 }
 
-func (*Response_CreateSession) isResponse_Payload() {}
+func (x *Viewport) Reset() {
+	*x = Viewport{}
+	mi := &file_browserd_proto_msgTypes[88]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
 
-func (*Response_Navigate) isResponse_Payload() {}
+func (x *Viewport) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
 
-func (*Response_Observe) isResponse_Payload() {}
+func (*Viewport) ProtoMessage() {}
 
-func (*Response_Act) isResponse_Payload() {}
+func (x *Viewport) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[88]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
 
-func (*Response_CloseSession) isResponse_Payload() {}
+// Deprecated: Use Viewport.ProtoReflect.Descriptor instead.
+func (*Viewport) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{88}
+}
 
-func (*Response_StreamSubscribe) isResponse_Payload() {}
+func (x *Viewport) GetWidth() uint32 {
+	if x != nil {
+		return x.Width
+	}
+	return 0
+}
 
-type CreateSessionRequest struct {
+func (x *Viewport) GetHeight() uint32 {
+	if x != nil {
+		return x.Height
+	}
+	return 0
+}
+
+func (x *Viewport) GetDeviceScaleFactor() float64 {
+	if x != nil {
+		return x.DeviceScaleFactor
+	}
+	return 0
+}
+
+// Clipboard contents as seen by the session's ClipboardPolicy. `text` is
+// empty in ClipboardSet responses.
+type ClipboardData struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Config        *SessionConfig         `protobuf:"bytes,1,opt,name=config,proto3" json:"config,omitempty"`
+	Text          string                 `protobuf:"bytes,1,opt,name=text,proto3" json:"text,omitempty"`
+	Bytes         uint32                 `protobuf:"varint,2,opt,name=bytes,proto3" json:"bytes,omitempty"`
+	Mode          ClipboardMode          `protobuf:"varint,3,opt,name=mode,proto3,enum=buckley.browserd.v1.ClipboardMode" json:"mode,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *CreateSessionRequest) Reset() {
-	*x = CreateSessionRequest{}
-	mi := &file_browserd_proto_msgTypes[4]
+func (x *ClipboardData) Reset() {
+	*x = ClipboardData{}
+	mi := &file_browserd_proto_msgTypes[89]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *CreateSessionRequest) String() string {
+func (x *ClipboardData) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CreateSessionRequest) ProtoMessage() {}
+func (*ClipboardData) ProtoMessage() {}
 
-func (x *CreateSessionRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[4]
+func (x *ClipboardData) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[89]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -864,41 +6840,55 @@ func (x *CreateSessionRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CreateSessionRequest.ProtoReflect.Descriptor instead.
-func (*CreateSessionRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{4}
+// Deprecated: Use ClipboardData.ProtoReflect.Descriptor instead.
+func (*ClipboardData) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{89}
 }
 
-func (x *CreateSessionRequest) GetConfig() *SessionConfig {
+func (x *ClipboardData) GetText() string {
 	if x != nil {
-		return x.Config
+		return x.Text
 	}
-	return nil
+	return ""
 }
 
-type CreateSessionResponse struct {
+func (x *ClipboardData) GetBytes() uint32 {
+	if x != nil {
+		return x.Bytes
+	}
+	return 0
+}
+
+func (x *ClipboardData) GetMode() ClipboardMode {
+	if x != nil {
+		return x.Mode
+	}
+	return ClipboardMode_CLIPBOARD_MODE_UNSPECIFIED
+}
+
+// Reads the session clipboard. Requires allow_read and, when set, a page on
+// read_allowlist.
+type ClipboardGetRequest struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Session       *SessionInfo           `protobuf:"bytes,1,opt,name=session,proto3" json:"session,omitempty"`
-	Observation   *Observation           `protobuf:"bytes,2,opt,name=observation,proto3" json:"observation,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *CreateSessionResponse) Reset() {
-	*x = CreateSessionResponse{}
-	mi := &file_browserd_proto_msgTypes[5]
+func (x *ClipboardGetRequest) Reset() {
+	*x = ClipboardGetRequest{}
+	mi := &file_browserd_proto_msgTypes[90]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *CreateSessionResponse) String() string {
+func (x *ClipboardGetRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CreateSessionResponse) ProtoMessage() {}
+func (*ClipboardGetRequest) ProtoMessage() {}
 
-func (x *CreateSessionResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[5]
+func (x *ClipboardGetRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[90]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -909,47 +6899,79 @@ func (x *CreateSessionResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CreateSessionResponse.ProtoReflect.Descriptor instead.
-func (*CreateSessionResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{5}
+// Deprecated: Use ClipboardGetRequest.ProtoReflect.Descriptor instead.
+func (*ClipboardGetRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{90}
 }
 
-func (x *CreateSessionResponse) GetSession() *SessionInfo {
+type ClipboardGetResponse struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Data          *ClipboardData         `protobuf:"bytes,1,opt,name=data,proto3" json:"data,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ClipboardGetResponse) Reset() {
+	*x = ClipboardGetResponse{}
+	mi := &file_browserd_proto_msgTypes[91]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ClipboardGetResponse) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ClipboardGetResponse) ProtoMessage() {}
+
+func (x *ClipboardGetResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[91]
 	if x != nil {
-		return x.Session
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
 	}
-	return nil
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ClipboardGetResponse.ProtoReflect.Descriptor instead.
+func (*ClipboardGetResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{91}
 }
 
-func (x *CreateSessionResponse) GetObservation() *Observation {
+func (x *ClipboardGetResponse) GetData() *ClipboardData {
 	if x != nil {
-		return x.Observation
+		return x.Data
 	}
 	return nil
 }
 
-type NavigateRequest struct {
+// Replaces the session clipboard. Requires allow_write; text is capped at
+// max_bytes.
+type ClipboardSetRequest struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Url           string                 `protobuf:"bytes,1,opt,name=url,proto3" json:"url,omitempty"`
+	Text          string                 `protobuf:"bytes,1,opt,name=text,proto3" json:"text,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *NavigateRequest) Reset() {
-	*x = NavigateRequest{}
-	mi := &file_browserd_proto_msgTypes[6]
+func (x *ClipboardSetRequest) Reset() {
+	*x = ClipboardSetRequest{}
+	mi := &file_browserd_proto_msgTypes[92]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *NavigateRequest) String() string {
+func (x *ClipboardSetRequest) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*NavigateRequest) ProtoMessage() {}
+func (*ClipboardSetRequest) ProtoMessage() {}
 
-func (x *NavigateRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[6]
+func (x *ClipboardSetRequest) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[92]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -960,40 +6982,40 @@ func (x *NavigateRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use NavigateRequest.ProtoReflect.Descriptor instead.
-func (*NavigateRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{6}
+// Deprecated: Use ClipboardSetRequest.ProtoReflect.Descriptor instead.
+func (*ClipboardSetRequest) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{92}
 }
 
-func (x *NavigateRequest) GetUrl() string {
+func (x *ClipboardSetRequest) GetText() string {
 	if x != nil {
-		return x.Url
+		return x.Text
 	}
 	return ""
 }
 
-type NavigateResponse struct {
+type ClipboardSetResponse struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+	Data          *ClipboardData         `protobuf:"bytes,1,opt,name=data,proto3" json:"data,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *NavigateResponse) Reset() {
-	*x = NavigateResponse{}
-	mi := &file_browserd_proto_msgTypes[7]
+func (x *ClipboardSetResponse) Reset() {
+	*x = ClipboardSetResponse{}
+	mi := &file_browserd_proto_msgTypes[93]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *NavigateResponse) String() string {
+func (x *ClipboardSetResponse) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*NavigateResponse) ProtoMessage() {}
+func (*ClipboardSetResponse) ProtoMessage() {}
 
-func (x *NavigateResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[7]
+func (x *ClipboardSetResponse) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[93]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1004,40 +7026,44 @@ func (x *NavigateResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use NavigateResponse.ProtoReflect.Descriptor instead.
-func (*NavigateResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{7}
+// Deprecated: Use ClipboardSetResponse.ProtoReflect.Descriptor instead.
+func (*ClipboardSetResponse) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{93}
 }
 
-func (x *NavigateResponse) GetObservation() *Observation {
+func (x *ClipboardSetResponse) GetData() *ClipboardData {
 	if x != nil {
-		return x.Observation
+		return x.Data
 	}
 	return nil
 }
 
-type ObserveRequest struct {
+type ClipboardPolicy struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
-	Options       *ObserveOptions        `protobuf:"bytes,1,opt,name=options,proto3" json:"options,omitempty"`
+	Mode          ClipboardMode          `protobuf:"varint,1,opt,name=mode,proto3,enum=buckley.browserd.v1.ClipboardMode" json:"mode,omitempty"`
+	AllowRead     bool                   `protobuf:"varint,2,opt,name=allow_read,json=allowRead,proto3" json:"allow_read,omitempty"`
+	AllowWrite    bool                   `protobuf:"varint,3,opt,name=allow_write,json=allowWrite,proto3" json:"allow_write,omitempty"`
+	MaxBytes      uint32                 `protobuf:"varint,4,opt,name=max_bytes,json=maxBytes,proto3" json:"max_bytes,omitempty"`
+	ReadAllowlist []string               `protobuf:"bytes,5,rep,name=read_allowlist,json=readAllowlist,proto3" json:"read_allowlist,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ObserveRequest) Reset() {
-	*x = ObserveRequest{}
-	mi := &file_browserd_proto_msgTypes[8]
+func (x *ClipboardPolicy) Reset() {
+	*x = ClipboardPolicy{}
+	mi := &file_browserd_proto_msgTypes[94]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ObserveRequest) String() string {
+func (x *ClipboardPolicy) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ObserveRequest) ProtoMessage() {}
+func (*ClipboardPolicy) ProtoMessage() {}
 
-func (x *ObserveRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[8]
+func (x *ClipboardPolicy) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[94]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1048,40 +7074,118 @@ func (x *ObserveRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ObserveRequest.ProtoReflect.Descriptor instead.
-func (*ObserveRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{8}
+// Deprecated: Use ClipboardPolicy.ProtoReflect.Descriptor instead.
+func (*ClipboardPolicy) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{94}
 }
 
-func (x *ObserveRequest) GetOptions() *ObserveOptions {
+func (x *ClipboardPolicy) GetMode() ClipboardMode {
 	if x != nil {
-		return x.Options
+		return x.Mode
+	}
+	return ClipboardMode_CLIPBOARD_MODE_UNSPECIFIED
+}
+
+func (x *ClipboardPolicy) GetAllowRead() bool {
+	if x != nil {
+		return x.AllowRead
+	}
+	return false
+}
+
+func (x *ClipboardPolicy) GetAllowWrite() bool {
+	if x != nil {
+		return x.AllowWrite
+	}
+	return false
+}
+
+func (x *ClipboardPolicy) GetMaxBytes() uint32 {
+	if x != nil {
+		return x.MaxBytes
+	}
+	return 0
+}
+
+func (x *ClipboardPolicy) GetReadAllowlist() []string {
+	if x != nil {
+		return x.ReadAllowlist
 	}
 	return nil
 }
 
-type ObserveResponse struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Observation   *Observation           `protobuf:"bytes,1,opt,name=observation,proto3" json:"observation,omitempty"`
+type ObserveOptions struct {
+	state                protoimpl.MessageState `protogen:"open.v1"`
+	IncludeFrame         bool                   `protobuf:"varint,1,opt,name=include_frame,json=includeFrame,proto3" json:"include_frame,omitempty"`
+	IncludeDomSnapshot   bool                   `protobuf:"varint,2,opt,name=include_dom_snapshot,json=includeDomSnapshot,proto3" json:"include_dom_snapshot,omitempty"`
+	IncludeAccessibility bool                   `protobuf:"varint,3,opt,name=include_accessibility,json=includeAccessibility,proto3" json:"include_accessibility,omitempty"`
+	IncludeHitTest       bool                   `protobuf:"varint,4,opt,name=include_hit_test,json=includeHitTest,proto3" json:"include_hit_test,omitempty"`
+	IncludeTextRuns      bool                   `protobuf:"varint,5,opt,name=include_text_runs,json=includeTextRuns,proto3" json:"include_text_runs,omitempty"`
+	IncludeEngineInfo    bool                   `protobuf:"varint,6,opt,name=include_engine_info,json=includeEngineInfo,proto3" json:"include_engine_info,omitempty"`
+	// Crop the frame to one element's hit-test bounds, or to clip_rect when
+	// clip_node_id is unset. Only applies when include_frame is set.
+	ClipNodeId uint64 `protobuf:"varint,7,opt,name=clip_node_id,json=clipNodeId,proto3" json:"clip_node_id,omitempty"`
+	ClipRect   *Rect  `protobuf:"bytes,8,opt,name=clip_rect,json=clipRect,proto3" json:"clip_rect,omitempty"`
+	// Report computed CSS for these node ids (at most 32) in
+	// Observation.computed_styles.
+	StyleNodeIds []uint64 `protobuf:"varint,9,rep,packed,name=style_node_ids,json=styleNodeIds,proto3" json:"style_node_ids,omitempty"`
+	// CSS properties to read, at most 32. Empty reads a default layout set
+	// (display, visibility, position, width, height, color, background-color,
+	// font-size, font-family).
+	StyleProperties []string `protobuf:"bytes,10,rep,name=style_properties,json=styleProperties,proto3" json:"style_properties,omitempty"`
+	// Report the last navigation's timing in Observation.navigation_timing.
+	IncludeTiming bool `protobuf:"varint,11,opt,name=include_timing,json=includeTiming,proto3" json:"include_timing,omitempty"`
+	// Cap on hit-test regions, including the document root. 0 uses the
+	// default of 250; larger values are capped at 2000.
+	MaxHitRegions uint32 `protobuf:"varint,12,opt,name=max_hit_regions,json=maxHitRegions,proto3" json:"max_hit_regions,omitempty"`
+	// Extra CSS selectors (at most 32) whose visible matches get hit-test
+	// regions alongside the built-in interactive ones. Invalid selectors are
+	// ignored.
+	HitTestSelectors []string `protobuf:"bytes,13,rep,name=hit_test_selectors,json=hitTestSelectors,proto3" json:"hit_test_selectors,omitempty"`
+	// Return the console messages logged since the last observation or
+	// console stream event in Observation.console_messages.
+	IncludeConsole bool `protobuf:"varint,14,opt,name=include_console,json=includeConsole,proto3" json:"include_console,omitempty"`
+	// Return uncaught errors and unhandled promise rejections since the last
+	// observation that asked for them in Observation.page_errors.
+	IncludePageErrors bool `protobuf:"varint,15,opt,name=include_page_errors,json=includePageErrors,proto3" json:"include_page_errors,omitempty"`
+	// Before observing, wait for the page to finish loading and then for no
+	// network request to start or be in flight for this many milliseconds,
+	// giving up at the session's navigation timeout. 0 doesn't wait.
+	UntilNetworkIdleMs uint32 `protobuf:"varint,16,opt,name=until_network_idle_ms,json=untilNetworkIdleMs,proto3" json:"until_network_idle_ms,omitempty"`
+	// Return the serialized document (documentElement.outerHTML) in
+	// Observation.html.
+	IncludeHtml bool `protobuf:"varint,17,opt,name=include_html,json=includeHtml,proto3" json:"include_html,omitempty"`
+	// Cap on Observation.html in bytes. 0 uses the default of 256 KiB; larger
+	// values are capped at 4 MiB.
+	MaxHtmlBytes uint32 `protobuf:"varint,18,opt,name=max_html_bytes,json=maxHtmlBytes,proto3" json:"max_html_bytes,omitempty"`
+	// Return the favicon, description and Open Graph tags in
+	// Observation.page_metadata.
+	IncludeMetadata bool `protobuf:"varint,19,opt,name=include_metadata,json=includeMetadata,proto3" json:"include_metadata,omitempty"`
+	// Return Observation.dom_snapshot as {url, title, nodes}: a flat array of
+	// {node_id, parent_id, tag, text, attrs} elements in document order, with
+	// parent_id 0 for the root, instead of the nested tree. text is the
+	// element's own text, not its descendants'. Only applies with
+	// include_dom_snapshot.
+	DomFlat       bool `protobuf:"varint,20,opt,name=dom_flat,json=domFlat,proto3" json:"dom_flat,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ObserveResponse) Reset() {
-	*x = ObserveResponse{}
-	mi := &file_browserd_proto_msgTypes[9]
+func (x *ObserveOptions) Reset() {
+	*x = ObserveOptions{}
+	mi := &file_browserd_proto_msgTypes[95]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ObserveResponse) String() string {
+func (x *ObserveOptions) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ObserveResponse) ProtoMessage() {}
+func (*ObserveOptions) ProtoMessage() {}
 
-func (x *ObserveResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[9]
+func (x *ObserveOptions) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[95]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1092,40 +7196,182 @@ func (x *ObserveResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ObserveResponse.ProtoReflect.Descriptor instead.
-func (*ObserveResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{9}
+// Deprecated: Use ObserveOptions.ProtoReflect.Descriptor instead.
+func (*ObserveOptions) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{95}
 }
 
-func (x *ObserveResponse) GetObservation() *Observation {
+func (x *ObserveOptions) GetIncludeFrame() bool {
 	if x != nil {
-		return x.Observation
+		return x.IncludeFrame
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludeDomSnapshot() bool {
+	if x != nil {
+		return x.IncludeDomSnapshot
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludeAccessibility() bool {
+	if x != nil {
+		return x.IncludeAccessibility
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludeHitTest() bool {
+	if x != nil {
+		return x.IncludeHitTest
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludeTextRuns() bool {
+	if x != nil {
+		return x.IncludeTextRuns
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludeEngineInfo() bool {
+	if x != nil {
+		return x.IncludeEngineInfo
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetClipNodeId() uint64 {
+	if x != nil {
+		return x.ClipNodeId
+	}
+	return 0
+}
+
+func (x *ObserveOptions) GetClipRect() *Rect {
+	if x != nil {
+		return x.ClipRect
 	}
 	return nil
 }
 
-type ActRequest struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Action        *Action                `protobuf:"bytes,1,opt,name=action,proto3" json:"action,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+func (x *ObserveOptions) GetStyleNodeIds() []uint64 {
+	if x != nil {
+		return x.StyleNodeIds
+	}
+	return nil
 }
 
-func (x *ActRequest) Reset() {
-	*x = ActRequest{}
-	mi := &file_browserd_proto_msgTypes[10]
+func (x *ObserveOptions) GetStyleProperties() []string {
+	if x != nil {
+		return x.StyleProperties
+	}
+	return nil
+}
+
+func (x *ObserveOptions) GetIncludeTiming() bool {
+	if x != nil {
+		return x.IncludeTiming
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetMaxHitRegions() uint32 {
+	if x != nil {
+		return x.MaxHitRegions
+	}
+	return 0
+}
+
+func (x *ObserveOptions) GetHitTestSelectors() []string {
+	if x != nil {
+		return x.HitTestSelectors
+	}
+	return nil
+}
+
+func (x *ObserveOptions) GetIncludeConsole() bool {
+	if x != nil {
+		return x.IncludeConsole
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetIncludePageErrors() bool {
+	if x != nil {
+		return x.IncludePageErrors
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetUntilNetworkIdleMs() uint32 {
+	if x != nil {
+		return x.UntilNetworkIdleMs
+	}
+	return 0
+}
+
+func (x *ObserveOptions) GetIncludeHtml() bool {
+	if x != nil {
+		return x.IncludeHtml
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetMaxHtmlBytes() uint32 {
+	if x != nil {
+		return x.MaxHtmlBytes
+	}
+	return 0
+}
+
+func (x *ObserveOptions) GetIncludeMetadata() bool {
+	if x != nil {
+		return x.IncludeMetadata
+	}
+	return false
+}
+
+func (x *ObserveOptions) GetDomFlat() bool {
+	if x != nil {
+		return x.DomFlat
+	}
+	return false
+}
+
+// Phases of the last navigation in milliseconds, from the page's
+// Navigation Timing entry. Phases the engine didn't go through (e.g. DNS
+// for file:// URLs) are 0.
+type NavigationTiming struct {
+	state     protoimpl.MessageState `protogen:"open.v1"`
+	DnsMs     float64                `protobuf:"fixed64,1,opt,name=dns_ms,json=dnsMs,proto3" json:"dns_ms,omitempty"`
+	ConnectMs float64                `protobuf:"fixed64,2,opt,name=connect_ms,json=connectMs,proto3" json:"connect_ms,omitempty"`
+	// Request start to first response byte.
+	TtfbMs float64 `protobuf:"fixed64,3,opt,name=ttfb_ms,json=ttfbMs,proto3" json:"ttfb_ms,omitempty"`
+	// Navigation start to the end of DOMContentLoaded and load.
+	DomContentLoadedMs float64 `protobuf:"fixed64,4,opt,name=dom_content_loaded_ms,json=domContentLoadedMs,proto3" json:"dom_content_loaded_ms,omitempty"`
+	LoadMs             float64 `protobuf:"fixed64,5,opt,name=load_ms,json=loadMs,proto3" json:"load_ms,omitempty"`
+	unknownFields      protoimpl.UnknownFields
+	sizeCache          protoimpl.SizeCache
+}
+
+func (x *NavigationTiming) Reset() {
+	*x = NavigationTiming{}
+	mi := &file_browserd_proto_msgTypes[96]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ActRequest) String() string {
+func (x *NavigationTiming) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ActRequest) ProtoMessage() {}
+func (*NavigationTiming) ProtoMessage() {}
 
-func (x *ActRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[10]
+func (x *NavigationTiming) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[96]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1136,40 +7382,69 @@ func (x *ActRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ActRequest.ProtoReflect.Descriptor instead.
-func (*ActRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{10}
+// Deprecated: Use NavigationTiming.ProtoReflect.Descriptor instead.
+func (*NavigationTiming) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{96}
 }
 
-func (x *ActRequest) GetAction() *Action {
+func (x *NavigationTiming) GetDnsMs() float64 {
+	if x != nil {
+		return x.DnsMs
+	}
+	return 0
+}
+
+func (x *NavigationTiming) GetConnectMs() float64 {
+	if x != nil {
+		return x.ConnectMs
+	}
+	return 0
+}
+
+func (x *NavigationTiming) GetTtfbMs() float64 {
+	if x != nil {
+		return x.TtfbMs
+	}
+	return 0
+}
+
+func (x *NavigationTiming) GetDomContentLoadedMs() float64 {
+	if x != nil {
+		return x.DomContentLoadedMs
+	}
+	return 0
+}
+
+func (x *NavigationTiming) GetLoadMs() float64 {
 	if x != nil {
-		return x.Action
+		return x.LoadMs
 	}
-	return nil
+	return 0
 }
 
-type ActResponse struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Result        *ActionResult          `protobuf:"bytes,1,opt,name=result,proto3" json:"result,omitempty"`
+type ComputedStyles struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// Keyed by node id. Ids no longer in the page are left out.
+	Nodes         map[uint64]*NodeStyle `protobuf:"bytes,1,rep,name=nodes,proto3" json:"nodes,omitempty" protobuf_key:"varint,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ActResponse) Reset() {
-	*x = ActResponse{}
-	mi := &file_browserd_proto_msgTypes[11]
+func (x *ComputedStyles) Reset() {
+	*x = ComputedStyles{}
+	mi := &file_browserd_proto_msgTypes[97]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ActResponse) String() string {
+func (x *ComputedStyles) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ActResponse) ProtoMessage() {}
+func (*ComputedStyles) ProtoMessage() {}
 
-func (x *ActResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[11]
+func (x *ComputedStyles) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[97]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1180,39 +7455,40 @@ func (x *ActResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ActResponse.ProtoReflect.Descriptor instead.
-func (*ActResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{11}
+// Deprecated: Use ComputedStyles.ProtoReflect.Descriptor instead.
+func (*ComputedStyles) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{97}
 }
 
-func (x *ActResponse) GetResult() *ActionResult {
+func (x *ComputedStyles) GetNodes() map[uint64]*NodeStyle {
 	if x != nil {
-		return x.Result
+		return x.Nodes
 	}
 	return nil
 }
 
-type CloseSessionRequest struct {
+type NodeStyle struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
+	Properties    map[string]string      `protobuf:"bytes,1,rep,name=properties,proto3" json:"properties,omitempty" protobuf_key:"bytes,1,opt,name=key,proto3" protobuf_val:"bytes,2,opt,name=value,proto3"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *CloseSessionRequest) Reset() {
-	*x = CloseSessionRequest{}
-	mi := &file_browserd_proto_msgTypes[12]
+func (x *NodeStyle) Reset() {
+	*x = NodeStyle{}
+	mi := &file_browserd_proto_msgTypes[98]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *CloseSessionRequest) String() string {
+func (x *NodeStyle) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CloseSessionRequest) ProtoMessage() {}
+func (*NodeStyle) ProtoMessage() {}
 
-func (x *CloseSessionRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[12]
+func (x *NodeStyle) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[98]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1223,33 +7499,61 @@ func (x *CloseSessionRequest) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CloseSessionRequest.ProtoReflect.Descriptor instead.
-func (*CloseSessionRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{12}
+// Deprecated: Use NodeStyle.ProtoReflect.Descriptor instead.
+func (*NodeStyle) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{98}
 }
 
-type CloseSessionResponse struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Closed        bool                   `protobuf:"varint,1,opt,name=closed,proto3" json:"closed,omitempty"`
+func (x *NodeStyle) GetProperties() map[string]string {
+	if x != nil {
+		return x.Properties
+	}
+	return nil
+}
+
+type StreamOptions struct {
+	state                     protoimpl.MessageState `protogen:"open.v1"`
+	IncludeFrames             bool                   `protobuf:"varint,1,opt,name=include_frames,json=includeFrames,proto3" json:"include_frames,omitempty"`
+	IncludeDomDiffs           bool                   `protobuf:"varint,2,opt,name=include_dom_diffs,json=includeDomDiffs,proto3" json:"include_dom_diffs,omitempty"`
+	IncludeAccessibilityDiffs bool                   `protobuf:"varint,3,opt,name=include_accessibility_diffs,json=includeAccessibilityDiffs,proto3" json:"include_accessibility_diffs,omitempty"`
+	IncludeHitTest            bool                   `protobuf:"varint,4,opt,name=include_hit_test,json=includeHitTest,proto3" json:"include_hit_test,omitempty"`
+	TargetFps                 uint32                 `protobuf:"varint,5,opt,name=target_fps,json=targetFps,proto3" json:"target_fps,omitempty"`
+	IncludeFrameTiming        bool                   `protobuf:"varint,6,opt,name=include_frame_timing,json=includeFrameTiming,proto3" json:"include_frame_timing,omitempty"`
+	// Compress stream event envelopes. Compressed frames start with a codec
+	// tag byte (0x81 gzip, 0x82 zstd) ahead of the usual 4-byte length.
+	Compression StreamCompression `protobuf:"varint,7,opt,name=compression,proto3,enum=buckley.browserd.v1.StreamCompression" json:"compression,omitempty"`
+	// Skip DOM, accessibility and hit-test events whose state_version and
+	// content match the last one sent. Frames keep the target_fps cadence.
+	ChangeOnly bool `protobuf:"varint,8,opt,name=change_only,json=changeOnly,proto3" json:"change_only,omitempty"`
+	// Same as the ObserveOptions fields, for hit-test events.
+	MaxHitRegions    uint32   `protobuf:"varint,9,opt,name=max_hit_regions,json=maxHitRegions,proto3" json:"max_hit_regions,omitempty"`
+	HitTestSelectors []string `protobuf:"bytes,10,rep,name=hit_test_selectors,json=hitTestSelectors,proto3" json:"hit_test_selectors,omitempty"`
+	// Send console events carrying messages logged since the last read. Ticks
+	// with no new messages send nothing.
+	IncludeConsole bool `protobuf:"varint,11,opt,name=include_console,json=includeConsole,proto3" json:"include_console,omitempty"`
+	// Send a heartbeat event carrying the current state_version every this
+	// many seconds, whatever else the stream sends, so a quiet stream can be
+	// told apart from a dead one. 0 sends none; at most 3600.
+	HeartbeatSecs uint32 `protobuf:"varint,12,opt,name=heartbeat_secs,json=heartbeatSecs,proto3" json:"heartbeat_secs,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *CloseSessionResponse) Reset() {
-	*x = CloseSessionResponse{}
-	mi := &file_browserd_proto_msgTypes[13]
+func (x *StreamOptions) Reset() {
+	*x = StreamOptions{}
+	mi := &file_browserd_proto_msgTypes[99]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *CloseSessionResponse) String() string {
+func (x *StreamOptions) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*CloseSessionResponse) ProtoMessage() {}
+func (*StreamOptions) ProtoMessage() {}
 
-func (x *CloseSessionResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[13]
+func (x *StreamOptions) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[99]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1260,130 +7564,159 @@ func (x *CloseSessionResponse) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use CloseSessionResponse.ProtoReflect.Descriptor instead.
-func (*CloseSessionResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{13}
+// Deprecated: Use StreamOptions.ProtoReflect.Descriptor instead.
+func (*StreamOptions) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{99}
 }
 
-func (x *CloseSessionResponse) GetClosed() bool {
+func (x *StreamOptions) GetIncludeFrames() bool {
 	if x != nil {
-		return x.Closed
+		return x.IncludeFrames
 	}
 	return false
 }
 
-type StreamSubscribeRequest struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Options       *StreamOptions         `protobuf:"bytes,1,opt,name=options,proto3" json:"options,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
-}
-
-func (x *StreamSubscribeRequest) Reset() {
-	*x = StreamSubscribeRequest{}
-	mi := &file_browserd_proto_msgTypes[14]
-	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-	ms.StoreMessageInfo(mi)
+func (x *StreamOptions) GetIncludeDomDiffs() bool {
+	if x != nil {
+		return x.IncludeDomDiffs
+	}
+	return false
 }
 
-func (x *StreamSubscribeRequest) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *StreamOptions) GetIncludeAccessibilityDiffs() bool {
+	if x != nil {
+		return x.IncludeAccessibilityDiffs
+	}
+	return false
 }
 
-func (*StreamSubscribeRequest) ProtoMessage() {}
-
-func (x *StreamSubscribeRequest) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[14]
+func (x *StreamOptions) GetIncludeHitTest() bool {
 	if x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+		return x.IncludeHitTest
 	}
-	return mi.MessageOf(x)
+	return false
 }
 
-// Deprecated: Use StreamSubscribeRequest.ProtoReflect.Descriptor instead.
-func (*StreamSubscribeRequest) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{14}
+func (x *StreamOptions) GetTargetFps() uint32 {
+	if x != nil {
+		return x.TargetFps
+	}
+	return 0
 }
 
-func (x *StreamSubscribeRequest) GetOptions() *StreamOptions {
+func (x *StreamOptions) GetIncludeFrameTiming() bool {
 	if x != nil {
-		return x.Options
+		return x.IncludeFrameTiming
 	}
-	return nil
+	return false
 }
 
-type StreamSubscribeResponse struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Subscribed    bool                   `protobuf:"varint,1,opt,name=subscribed,proto3" json:"subscribed,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+func (x *StreamOptions) GetCompression() StreamCompression {
+	if x != nil {
+		return x.Compression
+	}
+	return StreamCompression_STREAM_COMPRESSION_NONE
 }
 
-func (x *StreamSubscribeResponse) Reset() {
-	*x = StreamSubscribeResponse{}
-	mi := &file_browserd_proto_msgTypes[15]
-	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-	ms.StoreMessageInfo(mi)
+func (x *StreamOptions) GetChangeOnly() bool {
+	if x != nil {
+		return x.ChangeOnly
+	}
+	return false
 }
 
-func (x *StreamSubscribeResponse) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *StreamOptions) GetMaxHitRegions() uint32 {
+	if x != nil {
+		return x.MaxHitRegions
+	}
+	return 0
 }
 
-func (*StreamSubscribeResponse) ProtoMessage() {}
-
-func (x *StreamSubscribeResponse) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[15]
+func (x *StreamOptions) GetHitTestSelectors() []string {
 	if x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+		return x.HitTestSelectors
 	}
-	return mi.MessageOf(x)
+	return nil
 }
 
-// Deprecated: Use StreamSubscribeResponse.ProtoReflect.Descriptor instead.
-func (*StreamSubscribeResponse) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{15}
+func (x *StreamOptions) GetIncludeConsole() bool {
+	if x != nil {
+		return x.IncludeConsole
+	}
+	return false
 }
 
-func (x *StreamSubscribeResponse) GetSubscribed() bool {
+func (x *StreamOptions) GetHeartbeatSecs() uint32 {
 	if x != nil {
-		return x.Subscribed
+		return x.HeartbeatSecs
 	}
-	return false
+	return 0
 }
 
-type SessionInfo struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	SessionId     string                 `protobuf:"bytes,1,opt,name=session_id,json=sessionId,proto3" json:"session_id,omitempty"`
-	StateVersion  uint64                 `protobuf:"varint,2,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
-	Url           string                 `protobuf:"bytes,3,opt,name=url,proto3" json:"url,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+type Observation struct {
+	state             protoimpl.MessageState `protogen:"open.v1"`
+	StateVersion      uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	Url               string                 `protobuf:"bytes,2,opt,name=url,proto3" json:"url,omitempty"`
+	Title             string                 `protobuf:"bytes,3,opt,name=title,proto3" json:"title,omitempty"`
+	Frame             *Frame                 `protobuf:"bytes,4,opt,name=frame,proto3" json:"frame,omitempty"`
+	DomSnapshot       []byte                 `protobuf:"bytes,5,opt,name=dom_snapshot,json=domSnapshot,proto3" json:"dom_snapshot,omitempty"`
+	AccessibilityTree []byte                 `protobuf:"bytes,6,opt,name=accessibility_tree,json=accessibilityTree,proto3" json:"accessibility_tree,omitempty"`
+	HitTest           *HitTestMap            `protobuf:"bytes,7,opt,name=hit_test,json=hitTest,proto3" json:"hit_test,omitempty"`
+	Timestamp         *timestamppb.Timestamp `protobuf:"bytes,8,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
+	TextRuns          []*TextRun             `protobuf:"bytes,9,rep,name=text_runs,json=textRuns,proto3" json:"text_runs,omitempty"`
+	EngineInfo        *EngineInfo            `protobuf:"bytes,10,opt,name=engine_info,json=engineInfo,proto3" json:"engine_info,omitempty"`
+	// HTTP status of the last navigation, or 0 when unknown or not HTTP.
+	HttpStatus    uint32 `protobuf:"varint,11,opt,name=http_status,json=httpStatus,proto3" json:"http_status,omitempty"`
+	FocusedNodeId uint64 `protobuf:"varint,12,opt,name=focused_node_id,json=focusedNodeId,proto3" json:"focused_node_id,omitempty"`
+	// False when another frame was already queued behind the one painted for
+	// this observation, so `frame` may lag behind layout; re-observe to get a
+	// settled frame.
+	RenderComplete bool `protobuf:"varint,13,opt,name=render_complete,json=renderComplete,proto3" json:"render_complete,omitempty"`
+	// Number of frames painted so far in the session. Unchanged between two
+	// observations means nothing was repainted in between.
+	PaintEpoch uint64 `protobuf:"varint,14,opt,name=paint_epoch,json=paintEpoch,proto3" json:"paint_epoch,omitempty"`
+	// Set when ObserveOptions.style_node_ids is non-empty.
+	ComputedStyles *ComputedStyles `protobuf:"bytes,15,opt,name=computed_styles,json=computedStyles,proto3" json:"computed_styles,omitempty"`
+	// Set when ObserveOptions.include_timing is.
+	NavigationTiming *NavigationTiming `protobuf:"bytes,16,opt,name=navigation_timing,json=navigationTiming,proto3" json:"navigation_timing,omitempty"`
+	// Set when ObserveOptions.include_console is.
+	ConsoleMessages []*ConsoleMessage `protobuf:"bytes,17,rep,name=console_messages,json=consoleMessages,proto3" json:"console_messages,omitempty"`
+	// Set when ObserveOptions.include_page_errors is.
+	PageErrors []*PageError `protobuf:"bytes,18,rep,name=page_errors,json=pageErrors,proto3" json:"page_errors,omitempty"`
+	// Set when ObserveOptions.until_network_idle_ms is: false means the
+	// navigation timeout passed before the network went quiet.
+	NetworkIdle bool `protobuf:"varint,19,opt,name=network_idle,json=networkIdle,proto3" json:"network_idle,omitempty"`
+	// Set when ObserveOptions.include_html is, cut at max_html_bytes on a
+	// character boundary.
+	Html          string `protobuf:"bytes,20,opt,name=html,proto3" json:"html,omitempty"`
+	HtmlTruncated bool   `protobuf:"varint,21,opt,name=html_truncated,json=htmlTruncated,proto3" json:"html_truncated,omitempty"`
+	// Set when ObserveOptions.include_metadata is.
+	PageMetadata *PageMetadata `protobuf:"bytes,22,opt,name=page_metadata,json=pageMetadata,proto3" json:"page_metadata,omitempty"`
+	// Hex SHA-256 prefix of dom_snapshot and accessibility_tree as sent, empty
+	// when the snapshot is. Equal hashes mean unchanged snapshots, so clients
+	// can skip diffing; a nested DOM snapshot hashes the same as the snapshot
+	// in a DOM_DIFF stream event of the same state.
+	DomSnapshotHash       string `protobuf:"bytes,23,opt,name=dom_snapshot_hash,json=domSnapshotHash,proto3" json:"dom_snapshot_hash,omitempty"`
+	AccessibilityTreeHash string `protobuf:"bytes,24,opt,name=accessibility_tree_hash,json=accessibilityTreeHash,proto3" json:"accessibility_tree_hash,omitempty"`
+	unknownFields         protoimpl.UnknownFields
+	sizeCache             protoimpl.SizeCache
 }
 
-func (x *SessionInfo) Reset() {
-	*x = SessionInfo{}
-	mi := &file_browserd_proto_msgTypes[16]
+func (x *Observation) Reset() {
+	*x = Observation{}
+	mi := &file_browserd_proto_msgTypes[100]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *SessionInfo) String() string {
+func (x *Observation) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*SessionInfo) ProtoMessage() {}
+func (*Observation) ProtoMessage() {}
 
-func (x *SessionInfo) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[16]
+func (x *Observation) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[100]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1394,226 +7727,212 @@ func (x *SessionInfo) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use SessionInfo.ProtoReflect.Descriptor instead.
-func (*SessionInfo) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{16}
-}
-
-func (x *SessionInfo) GetSessionId() string {
-	if x != nil {
-		return x.SessionId
-	}
-	return ""
+// Deprecated: Use Observation.ProtoReflect.Descriptor instead.
+func (*Observation) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{100}
 }
 
-func (x *SessionInfo) GetStateVersion() uint64 {
+func (x *Observation) GetStateVersion() uint64 {
 	if x != nil {
 		return x.StateVersion
 	}
 	return 0
 }
 
-func (x *SessionInfo) GetUrl() string {
+func (x *Observation) GetUrl() string {
 	if x != nil {
 		return x.Url
 	}
 	return ""
 }
 
-type SessionConfig struct {
-	state            protoimpl.MessageState `protogen:"open.v1"`
-	SessionId        string                 `protobuf:"bytes,1,opt,name=session_id,json=sessionId,proto3" json:"session_id,omitempty"`
-	InitialUrl       string                 `protobuf:"bytes,2,opt,name=initial_url,json=initialUrl,proto3" json:"initial_url,omitempty"`
-	Viewport         *Viewport              `protobuf:"bytes,3,opt,name=viewport,proto3" json:"viewport,omitempty"`
-	UserAgent        string                 `protobuf:"bytes,4,opt,name=user_agent,json=userAgent,proto3" json:"user_agent,omitempty"`
-	Locale           string                 `protobuf:"bytes,5,opt,name=locale,proto3" json:"locale,omitempty"`
-	Timezone         string                 `protobuf:"bytes,6,opt,name=timezone,proto3" json:"timezone,omitempty"`
-	FrameRate        uint32                 `protobuf:"varint,7,opt,name=frame_rate,json=frameRate,proto3" json:"frame_rate,omitempty"`
-	NetworkAllowlist []string               `protobuf:"bytes,8,rep,name=network_allowlist,json=networkAllowlist,proto3" json:"network_allowlist,omitempty"`
-	Clipboard        *ClipboardPolicy       `protobuf:"bytes,9,opt,name=clipboard,proto3" json:"clipboard,omitempty"`
-	unknownFields    protoimpl.UnknownFields
-	sizeCache        protoimpl.SizeCache
+func (x *Observation) GetTitle() string {
+	if x != nil {
+		return x.Title
+	}
+	return ""
 }
 
-func (x *SessionConfig) Reset() {
-	*x = SessionConfig{}
-	mi := &file_browserd_proto_msgTypes[17]
-	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-	ms.StoreMessageInfo(mi)
+func (x *Observation) GetFrame() *Frame {
+	if x != nil {
+		return x.Frame
+	}
+	return nil
 }
 
-func (x *SessionConfig) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *Observation) GetDomSnapshot() []byte {
+	if x != nil {
+		return x.DomSnapshot
+	}
+	return nil
 }
 
-func (*SessionConfig) ProtoMessage() {}
-
-func (x *SessionConfig) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[17]
+func (x *Observation) GetAccessibilityTree() []byte {
 	if x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+		return x.AccessibilityTree
 	}
-	return mi.MessageOf(x)
+	return nil
 }
 
-// Deprecated: Use SessionConfig.ProtoReflect.Descriptor instead.
-func (*SessionConfig) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{17}
+func (x *Observation) GetHitTest() *HitTestMap {
+	if x != nil {
+		return x.HitTest
+	}
+	return nil
 }
 
-func (x *SessionConfig) GetSessionId() string {
+func (x *Observation) GetTimestamp() *timestamppb.Timestamp {
 	if x != nil {
-		return x.SessionId
+		return x.Timestamp
 	}
-	return ""
+	return nil
 }
 
-func (x *SessionConfig) GetInitialUrl() string {
+func (x *Observation) GetTextRuns() []*TextRun {
 	if x != nil {
-		return x.InitialUrl
+		return x.TextRuns
 	}
-	return ""
+	return nil
 }
 
-func (x *SessionConfig) GetViewport() *Viewport {
+func (x *Observation) GetEngineInfo() *EngineInfo {
 	if x != nil {
-		return x.Viewport
+		return x.EngineInfo
 	}
 	return nil
 }
 
-func (x *SessionConfig) GetUserAgent() string {
+func (x *Observation) GetHttpStatus() uint32 {
 	if x != nil {
-		return x.UserAgent
+		return x.HttpStatus
 	}
-	return ""
+	return 0
 }
 
-func (x *SessionConfig) GetLocale() string {
+func (x *Observation) GetFocusedNodeId() uint64 {
 	if x != nil {
-		return x.Locale
+		return x.FocusedNodeId
 	}
-	return ""
+	return 0
 }
 
-func (x *SessionConfig) GetTimezone() string {
+func (x *Observation) GetRenderComplete() bool {
 	if x != nil {
-		return x.Timezone
+		return x.RenderComplete
 	}
-	return ""
+	return false
 }
 
-func (x *SessionConfig) GetFrameRate() uint32 {
+func (x *Observation) GetPaintEpoch() uint64 {
 	if x != nil {
-		return x.FrameRate
+		return x.PaintEpoch
 	}
 	return 0
 }
 
-func (x *SessionConfig) GetNetworkAllowlist() []string {
+func (x *Observation) GetComputedStyles() *ComputedStyles {
 	if x != nil {
-		return x.NetworkAllowlist
+		return x.ComputedStyles
 	}
 	return nil
 }
 
-func (x *SessionConfig) GetClipboard() *ClipboardPolicy {
+func (x *Observation) GetNavigationTiming() *NavigationTiming {
 	if x != nil {
-		return x.Clipboard
+		return x.NavigationTiming
 	}
 	return nil
 }
 
-type Viewport struct {
-	state             protoimpl.MessageState `protogen:"open.v1"`
-	Width             uint32                 `protobuf:"varint,1,opt,name=width,proto3" json:"width,omitempty"`
-	Height            uint32                 `protobuf:"varint,2,opt,name=height,proto3" json:"height,omitempty"`
-	DeviceScaleFactor float64                `protobuf:"fixed64,3,opt,name=device_scale_factor,json=deviceScaleFactor,proto3" json:"device_scale_factor,omitempty"`
-	unknownFields     protoimpl.UnknownFields
-	sizeCache         protoimpl.SizeCache
+func (x *Observation) GetConsoleMessages() []*ConsoleMessage {
+	if x != nil {
+		return x.ConsoleMessages
+	}
+	return nil
 }
 
-func (x *Viewport) Reset() {
-	*x = Viewport{}
-	mi := &file_browserd_proto_msgTypes[18]
-	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-	ms.StoreMessageInfo(mi)
+func (x *Observation) GetPageErrors() []*PageError {
+	if x != nil {
+		return x.PageErrors
+	}
+	return nil
 }
 
-func (x *Viewport) String() string {
-	return protoimpl.X.MessageStringOf(x)
+func (x *Observation) GetNetworkIdle() bool {
+	if x != nil {
+		return x.NetworkIdle
+	}
+	return false
 }
 
-func (*Viewport) ProtoMessage() {}
-
-func (x *Viewport) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[18]
+func (x *Observation) GetHtml() string {
 	if x != nil {
-		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
-		if ms.LoadMessageInfo() == nil {
-			ms.StoreMessageInfo(mi)
-		}
-		return ms
+		return x.Html
 	}
-	return mi.MessageOf(x)
+	return ""
 }
 
-// Deprecated: Use Viewport.ProtoReflect.Descriptor instead.
-func (*Viewport) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{18}
+func (x *Observation) GetHtmlTruncated() bool {
+	if x != nil {
+		return x.HtmlTruncated
+	}
+	return false
 }
 
-func (x *Viewport) GetWidth() uint32 {
+func (x *Observation) GetPageMetadata() *PageMetadata {
 	if x != nil {
-		return x.Width
+		return x.PageMetadata
 	}
-	return 0
+	return nil
 }
 
-func (x *Viewport) GetHeight() uint32 {
+func (x *Observation) GetDomSnapshotHash() string {
 	if x != nil {
-		return x.Height
+		return x.DomSnapshotHash
 	}
-	return 0
+	return ""
 }
 
-func (x *Viewport) GetDeviceScaleFactor() float64 {
+func (x *Observation) GetAccessibilityTreeHash() string {
 	if x != nil {
-		return x.DeviceScaleFactor
+		return x.AccessibilityTreeHash
 	}
-	return 0
+	return ""
 }
 
-type ClipboardPolicy struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	Mode          ClipboardMode          `protobuf:"varint,1,opt,name=mode,proto3,enum=buckley.browserd.v1.ClipboardMode" json:"mode,omitempty"`
-	AllowRead     bool                   `protobuf:"varint,2,opt,name=allow_read,json=allowRead,proto3" json:"allow_read,omitempty"`
-	AllowWrite    bool                   `protobuf:"varint,3,opt,name=allow_write,json=allowWrite,proto3" json:"allow_write,omitempty"`
-	MaxBytes      uint32                 `protobuf:"varint,4,opt,name=max_bytes,json=maxBytes,proto3" json:"max_bytes,omitempty"`
-	ReadAllowlist []string               `protobuf:"bytes,5,rep,name=read_allowlist,json=readAllowlist,proto3" json:"read_allowlist,omitempty"`
+// Metadata declared in the page's head. Fields whose tag is missing are
+// empty; no /favicon.ico fallback is guessed. URLs are absolute.
+type PageMetadata struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// First <link rel="icon"> (including "shortcut icon").
+	FaviconUrl string `protobuf:"bytes,1,opt,name=favicon_url,json=faviconUrl,proto3" json:"favicon_url,omitempty"`
+	// <meta name="description">.
+	Description string `protobuf:"bytes,2,opt,name=description,proto3" json:"description,omitempty"`
+	// <meta property="og:title">.
+	OgTitle string `protobuf:"bytes,3,opt,name=og_title,json=ogTitle,proto3" json:"og_title,omitempty"`
+	// <meta property="og:image">.
+	OgImage string `protobuf:"bytes,4,opt,name=og_image,json=ogImage,proto3" json:"og_image,omitempty"`
+	// <link rel="canonical">.
+	CanonicalUrl  string `protobuf:"bytes,5,opt,name=canonical_url,json=canonicalUrl,proto3" json:"canonical_url,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ClipboardPolicy) Reset() {
-	*x = ClipboardPolicy{}
-	mi := &file_browserd_proto_msgTypes[19]
+func (x *PageMetadata) Reset() {
+	*x = PageMetadata{}
+	mi := &file_browserd_proto_msgTypes[101]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ClipboardPolicy) String() string {
+func (x *PageMetadata) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ClipboardPolicy) ProtoMessage() {}
+func (*PageMetadata) ProtoMessage() {}
 
-func (x *ClipboardPolicy) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[19]
+func (x *PageMetadata) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[101]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1624,71 +7943,75 @@ func (x *ClipboardPolicy) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ClipboardPolicy.ProtoReflect.Descriptor instead.
-func (*ClipboardPolicy) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{19}
+// Deprecated: Use PageMetadata.ProtoReflect.Descriptor instead.
+func (*PageMetadata) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{101}
 }
 
-func (x *ClipboardPolicy) GetMode() ClipboardMode {
+func (x *PageMetadata) GetFaviconUrl() string {
 	if x != nil {
-		return x.Mode
+		return x.FaviconUrl
 	}
-	return ClipboardMode_CLIPBOARD_MODE_UNSPECIFIED
+	return ""
 }
 
-func (x *ClipboardPolicy) GetAllowRead() bool {
+func (x *PageMetadata) GetDescription() string {
 	if x != nil {
-		return x.AllowRead
+		return x.Description
 	}
-	return false
+	return ""
 }
 
-func (x *ClipboardPolicy) GetAllowWrite() bool {
+func (x *PageMetadata) GetOgTitle() string {
 	if x != nil {
-		return x.AllowWrite
+		return x.OgTitle
 	}
-	return false
+	return ""
 }
 
-func (x *ClipboardPolicy) GetMaxBytes() uint32 {
+func (x *PageMetadata) GetOgImage() string {
 	if x != nil {
-		return x.MaxBytes
+		return x.OgImage
 	}
-	return 0
+	return ""
 }
 
-func (x *ClipboardPolicy) GetReadAllowlist() []string {
+func (x *PageMetadata) GetCanonicalUrl() string {
 	if x != nil {
-		return x.ReadAllowlist
+		return x.CanonicalUrl
 	}
-	return nil
+	return ""
 }
 
-type ObserveOptions struct {
-	state                protoimpl.MessageState `protogen:"open.v1"`
-	IncludeFrame         bool                   `protobuf:"varint,1,opt,name=include_frame,json=includeFrame,proto3" json:"include_frame,omitempty"`
-	IncludeDomSnapshot   bool                   `protobuf:"varint,2,opt,name=include_dom_snapshot,json=includeDomSnapshot,proto3" json:"include_dom_snapshot,omitempty"`
-	IncludeAccessibility bool                   `protobuf:"varint,3,opt,name=include_accessibility,json=includeAccessibility,proto3" json:"include_accessibility,omitempty"`
-	IncludeHitTest       bool                   `protobuf:"varint,4,opt,name=include_hit_test,json=includeHitTest,proto3" json:"include_hit_test,omitempty"`
-	unknownFields        protoimpl.UnknownFields
-	sizeCache            protoimpl.SizeCache
+// A console.log/info/warn/error/debug call. Each message is reported once,
+// to whichever observation or stream event reads it first. A session keeps
+// at most 200 unread messages, dropping the oldest, and cuts each to 2000
+// characters.
+type ConsoleMessage struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// "log", "info", "warn", "error" or "debug".
+	Level         string                 `protobuf:"bytes,1,opt,name=level,proto3" json:"level,omitempty"`
+	Text          string                 `protobuf:"bytes,2,opt,name=text,proto3" json:"text,omitempty"`
+	Timestamp     *timestamppb.Timestamp `protobuf:"bytes,3,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
-func (x *ObserveOptions) Reset() {
-	*x = ObserveOptions{}
-	mi := &file_browserd_proto_msgTypes[20]
+func (x *ConsoleMessage) Reset() {
+	*x = ConsoleMessage{}
+	mi := &file_browserd_proto_msgTypes[102]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *ObserveOptions) String() string {
+func (x *ConsoleMessage) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*ObserveOptions) ProtoMessage() {}
+func (*ConsoleMessage) ProtoMessage() {}
 
-func (x *ObserveOptions) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[20]
+func (x *ConsoleMessage) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[102]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1699,65 +8022,61 @@ func (x *ObserveOptions) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use ObserveOptions.ProtoReflect.Descriptor instead.
-func (*ObserveOptions) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{20}
-}
-
-func (x *ObserveOptions) GetIncludeFrame() bool {
-	if x != nil {
-		return x.IncludeFrame
-	}
-	return false
+// Deprecated: Use ConsoleMessage.ProtoReflect.Descriptor instead.
+func (*ConsoleMessage) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{102}
 }
 
-func (x *ObserveOptions) GetIncludeDomSnapshot() bool {
+func (x *ConsoleMessage) GetLevel() string {
 	if x != nil {
-		return x.IncludeDomSnapshot
+		return x.Level
 	}
-	return false
+	return ""
 }
 
-func (x *ObserveOptions) GetIncludeAccessibility() bool {
+func (x *ConsoleMessage) GetText() string {
 	if x != nil {
-		return x.IncludeAccessibility
+		return x.Text
 	}
-	return false
+	return ""
 }
 
-func (x *ObserveOptions) GetIncludeHitTest() bool {
+func (x *ConsoleMessage) GetTimestamp() *timestamppb.Timestamp {
 	if x != nil {
-		return x.IncludeHitTest
+		return x.Timestamp
 	}
-	return false
+	return nil
 }
 
-type StreamOptions struct {
-	state                     protoimpl.MessageState `protogen:"open.v1"`
-	IncludeFrames             bool                   `protobuf:"varint,1,opt,name=include_frames,json=includeFrames,proto3" json:"include_frames,omitempty"`
-	IncludeDomDiffs           bool                   `protobuf:"varint,2,opt,name=include_dom_diffs,json=includeDomDiffs,proto3" json:"include_dom_diffs,omitempty"`
-	IncludeAccessibilityDiffs bool                   `protobuf:"varint,3,opt,name=include_accessibility_diffs,json=includeAccessibilityDiffs,proto3" json:"include_accessibility_diffs,omitempty"`
-	IncludeHitTest            bool                   `protobuf:"varint,4,opt,name=include_hit_test,json=includeHitTest,proto3" json:"include_hit_test,omitempty"`
-	TargetFps                 uint32                 `protobuf:"varint,5,opt,name=target_fps,json=targetFps,proto3" json:"target_fps,omitempty"`
-	unknownFields             protoimpl.UnknownFields
-	sizeCache                 protoimpl.SizeCache
+// An error the page threw and didn't catch, or a promise rejection nothing
+// handled. At most 50 are kept between observations, dropping the oldest.
+type PageError struct {
+	state   protoimpl.MessageState `protogen:"open.v1"`
+	Message string                 `protobuf:"bytes,1,opt,name=message,proto3" json:"message,omitempty"`
+	// URL of the script that threw; empty for promise rejections.
+	Source string `protobuf:"bytes,2,opt,name=source,proto3" json:"source,omitempty"`
+	// 1-based position in `source`, 0 when unknown.
+	Line          uint32 `protobuf:"varint,3,opt,name=line,proto3" json:"line,omitempty"`
+	Column        uint32 `protobuf:"varint,4,opt,name=column,proto3" json:"column,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
-func (x *StreamOptions) Reset() {
-	*x = StreamOptions{}
-	mi := &file_browserd_proto_msgTypes[21]
+func (x *PageError) Reset() {
+	*x = PageError{}
+	mi := &file_browserd_proto_msgTypes[103]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *StreamOptions) String() string {
+func (x *PageError) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*StreamOptions) ProtoMessage() {}
+func (*PageError) ProtoMessage() {}
 
-func (x *StreamOptions) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[21]
+func (x *PageError) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[103]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1768,75 +8087,63 @@ func (x *StreamOptions) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use StreamOptions.ProtoReflect.Descriptor instead.
-func (*StreamOptions) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{21}
-}
-
-func (x *StreamOptions) GetIncludeFrames() bool {
-	if x != nil {
-		return x.IncludeFrames
-	}
-	return false
+// Deprecated: Use PageError.ProtoReflect.Descriptor instead.
+func (*PageError) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{103}
 }
 
-func (x *StreamOptions) GetIncludeDomDiffs() bool {
+func (x *PageError) GetMessage() string {
 	if x != nil {
-		return x.IncludeDomDiffs
+		return x.Message
 	}
-	return false
+	return ""
 }
 
-func (x *StreamOptions) GetIncludeAccessibilityDiffs() bool {
+func (x *PageError) GetSource() string {
 	if x != nil {
-		return x.IncludeAccessibilityDiffs
+		return x.Source
 	}
-	return false
+	return ""
 }
 
-func (x *StreamOptions) GetIncludeHitTest() bool {
+func (x *PageError) GetLine() uint32 {
 	if x != nil {
-		return x.IncludeHitTest
+		return x.Line
 	}
-	return false
+	return 0
 }
 
-func (x *StreamOptions) GetTargetFps() uint32 {
+func (x *PageError) GetColumn() uint32 {
 	if x != nil {
-		return x.TargetFps
+		return x.Column
 	}
 	return 0
 }
 
-type Observation struct {
-	state             protoimpl.MessageState `protogen:"open.v1"`
-	StateVersion      uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
-	Url               string                 `protobuf:"bytes,2,opt,name=url,proto3" json:"url,omitempty"`
-	Title             string                 `protobuf:"bytes,3,opt,name=title,proto3" json:"title,omitempty"`
-	Frame             *Frame                 `protobuf:"bytes,4,opt,name=frame,proto3" json:"frame,omitempty"`
-	DomSnapshot       []byte                 `protobuf:"bytes,5,opt,name=dom_snapshot,json=domSnapshot,proto3" json:"dom_snapshot,omitempty"`
-	AccessibilityTree []byte                 `protobuf:"bytes,6,opt,name=accessibility_tree,json=accessibilityTree,proto3" json:"accessibility_tree,omitempty"`
-	HitTest           *HitTestMap            `protobuf:"bytes,7,opt,name=hit_test,json=hitTest,proto3" json:"hit_test,omitempty"`
-	Timestamp         *timestamppb.Timestamp `protobuf:"bytes,8,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
-	unknownFields     protoimpl.UnknownFields
-	sizeCache         protoimpl.SizeCache
+type TextRun struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	Text          string                 `protobuf:"bytes,1,opt,name=text,proto3" json:"text,omitempty"`
+	Bounds        *Rect                  `protobuf:"bytes,2,opt,name=bounds,proto3" json:"bounds,omitempty"`
+	NodeId        uint64                 `protobuf:"varint,3,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
-func (x *Observation) Reset() {
-	*x = Observation{}
-	mi := &file_browserd_proto_msgTypes[22]
+func (x *TextRun) Reset() {
+	*x = TextRun{}
+	mi := &file_browserd_proto_msgTypes[104]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
 
-func (x *Observation) String() string {
+func (x *TextRun) String() string {
 	return protoimpl.X.MessageStringOf(x)
 }
 
-func (*Observation) ProtoMessage() {}
+func (*TextRun) ProtoMessage() {}
 
-func (x *Observation) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[22]
+func (x *TextRun) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[104]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1847,82 +8154,56 @@ func (x *Observation) ProtoReflect() protoreflect.Message {
 	return mi.MessageOf(x)
 }
 
-// Deprecated: Use Observation.ProtoReflect.Descriptor instead.
-func (*Observation) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{22}
-}
-
-func (x *Observation) GetStateVersion() uint64 {
-	if x != nil {
-		return x.StateVersion
-	}
-	return 0
-}
-
-func (x *Observation) GetUrl() string {
-	if x != nil {
-		return x.Url
-	}
-	return ""
+// Deprecated: Use TextRun.ProtoReflect.Descriptor instead.
+func (*TextRun) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{104}
 }
 
-func (x *Observation) GetTitle() string {
+func (x *TextRun) GetText() string {
 	if x != nil {
-		return x.Title
+		return x.Text
 	}
 	return ""
 }
 
-func (x *Observation) GetFrame() *Frame {
-	if x != nil {
-		return x.Frame
-	}
-	return nil
-}
-
-func (x *Observation) GetDomSnapshot() []byte {
-	if x != nil {
-		return x.DomSnapshot
-	}
-	return nil
-}
-
-func (x *Observation) GetAccessibilityTree() []byte {
-	if x != nil {
-		return x.AccessibilityTree
-	}
-	return nil
-}
-
-func (x *Observation) GetHitTest() *HitTestMap {
+func (x *TextRun) GetBounds() *Rect {
 	if x != nil {
-		return x.HitTest
+		return x.Bounds
 	}
 	return nil
 }
 
-func (x *Observation) GetTimestamp() *timestamppb.Timestamp {
+func (x *TextRun) GetNodeId() uint64 {
 	if x != nil {
-		return x.Timestamp
+		return x.NodeId
 	}
-	return nil
+	return 0
 }
 
 type Frame struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	StateVersion  uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
-	Width         uint32                 `protobuf:"varint,2,opt,name=width,proto3" json:"width,omitempty"`
-	Height        uint32                 `protobuf:"varint,3,opt,name=height,proto3" json:"height,omitempty"`
-	Format        FrameFormat            `protobuf:"varint,4,opt,name=format,proto3,enum=buckley.browserd.v1.FrameFormat" json:"format,omitempty"`
-	Data          []byte                 `protobuf:"bytes,5,opt,name=data,proto3" json:"data,omitempty"`
-	Timestamp     *timestamppb.Timestamp `protobuf:"bytes,6,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
+	state                 protoimpl.MessageState `protogen:"open.v1"`
+	StateVersion          uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	Width                 uint32                 `protobuf:"varint,2,opt,name=width,proto3" json:"width,omitempty"`
+	Height                uint32                 `protobuf:"varint,3,opt,name=height,proto3" json:"height,omitempty"`
+	Format                FrameFormat            `protobuf:"varint,4,opt,name=format,proto3,enum=buckley.browserd.v1.FrameFormat" json:"format,omitempty"`
+	Data                  []byte                 `protobuf:"bytes,5,opt,name=data,proto3" json:"data,omitempty"`
+	Timestamp             *timestamppb.Timestamp `protobuf:"bytes,6,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
+	PresentationTimestamp *timestamppb.Timestamp `protobuf:"bytes,7,opt,name=presentation_timestamp,json=presentationTimestamp,proto3" json:"presentation_timestamp,omitempty"`
+	CaptureDurationUs     uint64                 `protobuf:"varint,8,opt,name=capture_duration_us,json=captureDurationUs,proto3" json:"capture_duration_us,omitempty"`
+	IsDelta               bool                   `protobuf:"varint,9,opt,name=is_delta,json=isDelta,proto3" json:"is_delta,omitempty"`
+	DirtyX                uint32                 `protobuf:"varint,10,opt,name=dirty_x,json=dirtyX,proto3" json:"dirty_x,omitempty"`
+	DirtyY                uint32                 `protobuf:"varint,11,opt,name=dirty_y,json=dirtyY,proto3" json:"dirty_y,omitempty"`
+	// The capture had more pixels than BROWSERD_MAX_FRAME_PIXELS and was
+	// downscaled to fit, keeping its aspect ratio. width and height are the
+	// scaled size; page coordinates map onto the frame by the ratio of the two.
+	Scaled        bool `protobuf:"varint,12,opt,name=scaled,proto3" json:"scaled,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
 func (x *Frame) Reset() {
 	*x = Frame{}
-	mi := &file_browserd_proto_msgTypes[23]
+	mi := &file_browserd_proto_msgTypes[105]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -1934,7 +8215,7 @@ func (x *Frame) String() string {
 func (*Frame) ProtoMessage() {}
 
 func (x *Frame) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[23]
+	mi := &file_browserd_proto_msgTypes[105]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -1947,7 +8228,7 @@ func (x *Frame) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Frame.ProtoReflect.Descriptor instead.
 func (*Frame) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{23}
+	return file_browserd_proto_rawDescGZIP(), []int{105}
 }
 
 func (x *Frame) GetStateVersion() uint64 {
@@ -1992,6 +8273,48 @@ func (x *Frame) GetTimestamp() *timestamppb.Timestamp {
 	return nil
 }
 
+func (x *Frame) GetPresentationTimestamp() *timestamppb.Timestamp {
+	if x != nil {
+		return x.PresentationTimestamp
+	}
+	return nil
+}
+
+func (x *Frame) GetCaptureDurationUs() uint64 {
+	if x != nil {
+		return x.CaptureDurationUs
+	}
+	return 0
+}
+
+func (x *Frame) GetIsDelta() bool {
+	if x != nil {
+		return x.IsDelta
+	}
+	return false
+}
+
+func (x *Frame) GetDirtyX() uint32 {
+	if x != nil {
+		return x.DirtyX
+	}
+	return 0
+}
+
+func (x *Frame) GetDirtyY() uint32 {
+	if x != nil {
+		return x.DirtyY
+	}
+	return 0
+}
+
+func (x *Frame) GetScaled() bool {
+	if x != nil {
+		return x.Scaled
+	}
+	return false
+}
+
 type HitTestMap struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
 	Width         uint32                 `protobuf:"varint,1,opt,name=width,proto3" json:"width,omitempty"`
@@ -2003,7 +8326,7 @@ type HitTestMap struct {
 
 func (x *HitTestMap) Reset() {
 	*x = HitTestMap{}
-	mi := &file_browserd_proto_msgTypes[24]
+	mi := &file_browserd_proto_msgTypes[106]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2015,7 +8338,7 @@ func (x *HitTestMap) String() string {
 func (*HitTestMap) ProtoMessage() {}
 
 func (x *HitTestMap) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[24]
+	mi := &file_browserd_proto_msgTypes[106]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2028,7 +8351,7 @@ func (x *HitTestMap) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use HitTestMap.ProtoReflect.Descriptor instead.
 func (*HitTestMap) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{24}
+	return file_browserd_proto_rawDescGZIP(), []int{106}
 }
 
 func (x *HitTestMap) GetWidth() uint32 {
@@ -2053,16 +8376,26 @@ func (x *HitTestMap) GetRegions() []*HitRegion {
 }
 
 type HitRegion struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	NodeId        uint64                 `protobuf:"varint,1,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
-	Bounds        *Rect                  `protobuf:"bytes,2,opt,name=bounds,proto3" json:"bounds,omitempty"`
+	state  protoimpl.MessageState `protogen:"open.v1"`
+	NodeId uint64                 `protobuf:"varint,1,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
+	Bounds *Rect                  `protobuf:"bytes,2,opt,name=bounds,proto3" json:"bounds,omitempty"`
+	// Lowercase tag name, e.g. "a" or "button".
+	Tag string `protobuf:"bytes,3,opt,name=tag,proto3" json:"tag,omitempty"`
+	// Explicit ARIA role, or the implicit one the accessibility tree uses.
+	Role string `protobuf:"bytes,4,opt,name=role,proto3" json:"role,omitempty"`
+	// aria-label, or the element's whitespace-collapsed text, cut at 120
+	// characters.
+	Label string `protobuf:"bytes,5,opt,name=label,proto3" json:"label,omitempty"`
+	// Another element is topmost at the centre of the region's visible part,
+	// e.g. a modal overlay, so a click there would not reach this element.
+	Occluded      bool `protobuf:"varint,6,opt,name=occluded,proto3" json:"occluded,omitempty"`
 	unknownFields protoimpl.UnknownFields
 	sizeCache     protoimpl.SizeCache
 }
 
 func (x *HitRegion) Reset() {
 	*x = HitRegion{}
-	mi := &file_browserd_proto_msgTypes[25]
+	mi := &file_browserd_proto_msgTypes[107]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2074,7 +8407,7 @@ func (x *HitRegion) String() string {
 func (*HitRegion) ProtoMessage() {}
 
 func (x *HitRegion) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[25]
+	mi := &file_browserd_proto_msgTypes[107]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2087,7 +8420,7 @@ func (x *HitRegion) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use HitRegion.ProtoReflect.Descriptor instead.
 func (*HitRegion) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{25}
+	return file_browserd_proto_rawDescGZIP(), []int{107}
 }
 
 func (x *HitRegion) GetNodeId() uint64 {
@@ -2104,6 +8437,34 @@ func (x *HitRegion) GetBounds() *Rect {
 	return nil
 }
 
+func (x *HitRegion) GetTag() string {
+	if x != nil {
+		return x.Tag
+	}
+	return ""
+}
+
+func (x *HitRegion) GetRole() string {
+	if x != nil {
+		return x.Role
+	}
+	return ""
+}
+
+func (x *HitRegion) GetLabel() string {
+	if x != nil {
+		return x.Label
+	}
+	return ""
+}
+
+func (x *HitRegion) GetOccluded() bool {
+	if x != nil {
+		return x.Occluded
+	}
+	return false
+}
+
 type Rect struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
 	X             int32                  `protobuf:"varint,1,opt,name=x,proto3" json:"x,omitempty"`
@@ -2116,7 +8477,7 @@ type Rect struct {
 
 func (x *Rect) Reset() {
 	*x = Rect{}
-	mi := &file_browserd_proto_msgTypes[26]
+	mi := &file_browserd_proto_msgTypes[108]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2128,7 +8489,7 @@ func (x *Rect) String() string {
 func (*Rect) ProtoMessage() {}
 
 func (x *Rect) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[26]
+	mi := &file_browserd_proto_msgTypes[108]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2141,7 +8502,7 @@ func (x *Rect) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Rect.ProtoReflect.Descriptor instead.
 func (*Rect) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{26}
+	return file_browserd_proto_rawDescGZIP(), []int{108}
 }
 
 func (x *Rect) GetX() int32 {
@@ -2182,7 +8543,7 @@ type Point struct {
 
 func (x *Point) Reset() {
 	*x = Point{}
-	mi := &file_browserd_proto_msgTypes[27]
+	mi := &file_browserd_proto_msgTypes[109]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2194,7 +8555,7 @@ func (x *Point) String() string {
 func (*Point) ProtoMessage() {}
 
 func (x *Point) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[27]
+	mi := &file_browserd_proto_msgTypes[109]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2207,7 +8568,7 @@ func (x *Point) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Point.ProtoReflect.Descriptor instead.
 func (*Point) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{27}
+	return file_browserd_proto_rawDescGZIP(), []int{109}
 }
 
 func (x *Point) GetX() int32 {
@@ -2232,14 +8593,42 @@ type Action struct {
 	Text                 string                 `protobuf:"bytes,4,opt,name=text,proto3" json:"text,omitempty"`
 	Key                  string                 `protobuf:"bytes,5,opt,name=key,proto3" json:"key,omitempty"`
 	Scroll               *ScrollDelta           `protobuf:"bytes,6,opt,name=scroll,proto3" json:"scroll,omitempty"`
-	Modifiers            []KeyModifier          `protobuf:"varint,7,rep,packed,name=modifiers,proto3,enum=buckley.browserd.v1.KeyModifier" json:"modifiers,omitempty"`
-	unknownFields        protoimpl.UnknownFields
-	sizeCache            protoimpl.SizeCache
+	// Held down for the duration of each key press, like a real chord: every
+	// modifier goes down before the key and comes back up after it, in
+	// reverse order.
+	Modifiers []KeyModifier `protobuf:"varint,7,rep,packed,name=modifiers,proto3,enum=buckley.browserd.v1.KeyModifier" json:"modifiers,omitempty"`
+	// End point for ACTION_TYPE_DRAG; the start point comes from target.
+	DragTo *ActionTarget `protobuf:"bytes,8,opt,name=drag_to,json=dragTo,proto3" json:"drag_to,omitempty"`
+	// Intermediate mouse moves between the drag start and end; 0 uses the
+	// default.
+	DragSteps uint32 `protobuf:"varint,9,opt,name=drag_steps,json=dragSteps,proto3" json:"drag_steps,omitempty"`
+	// Button and repeat count for ACTION_TYPE_CLICK; unset means a single
+	// left click.
+	Button     MouseButton `protobuf:"varint,10,opt,name=button,proto3,enum=buckley.browserd.v1.MouseButton" json:"button,omitempty"`
+	ClickCount uint32      `protobuf:"varint,11,opt,name=click_count,json=clickCount,proto3" json:"click_count,omitempty"`
+	// How many times to press the key for ACTION_TYPE_KEY and ACTION_TYPE_TAB;
+	// 0 means once.
+	Repeat uint32 `protobuf:"varint,12,opt,name=repeat,proto3" json:"repeat,omitempty"`
+	// Upper bound on the whole act call, including the JavaScript that builds
+	// the resulting observation. 0 leaves only the engine's script timeouts.
+	ActionTimeoutMs uint32 `protobuf:"varint,13,opt,name=action_timeout_ms,json=actionTimeoutMs,proto3" json:"action_timeout_ms,omitempty"`
+	// Desired state for ACTION_TYPE_SET_CHECKED.
+	Checked bool `protobuf:"varint,14,opt,name=checked,proto3" json:"checked,omitempty"`
+	// Only resolve the target and check expected_state_version. Nothing is
+	// dispatched: the result has the resolved node and point, an unchanged
+	// state_version, a "dry_run" effect and no observation.
+	DryRun bool `protobuf:"varint,15,opt,name=dry_run,json=dryRun,proto3" json:"dry_run,omitempty"`
+	// What to do when expected_state_version no longer matches. Retry
+	// re-resolves the target against the current state and goes ahead once;
+	// the result carries the new state_version.
+	OnStale       StalePolicy `protobuf:"varint,16,opt,name=on_stale,json=onStale,proto3,enum=buckley.browserd.v1.StalePolicy" json:"on_stale,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
 func (x *Action) Reset() {
 	*x = Action{}
-	mi := &file_browserd_proto_msgTypes[28]
+	mi := &file_browserd_proto_msgTypes[110]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2251,7 +8640,7 @@ func (x *Action) String() string {
 func (*Action) ProtoMessage() {}
 
 func (x *Action) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[28]
+	mi := &file_browserd_proto_msgTypes[110]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2264,7 +8653,7 @@ func (x *Action) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Action.ProtoReflect.Descriptor instead.
 func (*Action) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{28}
+	return file_browserd_proto_rawDescGZIP(), []int{110}
 }
 
 func (x *Action) GetType() ActionType {
@@ -2316,17 +8705,86 @@ func (x *Action) GetModifiers() []KeyModifier {
 	return nil
 }
 
+func (x *Action) GetDragTo() *ActionTarget {
+	if x != nil {
+		return x.DragTo
+	}
+	return nil
+}
+
+func (x *Action) GetDragSteps() uint32 {
+	if x != nil {
+		return x.DragSteps
+	}
+	return 0
+}
+
+func (x *Action) GetButton() MouseButton {
+	if x != nil {
+		return x.Button
+	}
+	return MouseButton_MOUSE_BUTTON_UNSPECIFIED
+}
+
+func (x *Action) GetClickCount() uint32 {
+	if x != nil {
+		return x.ClickCount
+	}
+	return 0
+}
+
+func (x *Action) GetRepeat() uint32 {
+	if x != nil {
+		return x.Repeat
+	}
+	return 0
+}
+
+func (x *Action) GetActionTimeoutMs() uint32 {
+	if x != nil {
+		return x.ActionTimeoutMs
+	}
+	return 0
+}
+
+func (x *Action) GetChecked() bool {
+	if x != nil {
+		return x.Checked
+	}
+	return false
+}
+
+func (x *Action) GetDryRun() bool {
+	if x != nil {
+		return x.DryRun
+	}
+	return false
+}
+
+func (x *Action) GetOnStale() StalePolicy {
+	if x != nil {
+		return x.OnStale
+	}
+	return StalePolicy_STALE_POLICY_UNSPECIFIED
+}
+
 type ActionTarget struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	NodeId        uint64                 `protobuf:"varint,1,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
-	Point         *Point                 `protobuf:"bytes,2,opt,name=point,proto3" json:"point,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+	state  protoimpl.MessageState `protogen:"open.v1"`
+	NodeId uint64                 `protobuf:"varint,1,opt,name=node_id,json=nodeId,proto3" json:"node_id,omitempty"`
+	Point  *Point                 `protobuf:"bytes,2,opt,name=point,proto3" json:"point,omitempty"`
+	// Resolved to the centre of the first matching element when set.
+	Selector     string       `protobuf:"bytes,3,opt,name=selector,proto3" json:"selector,omitempty"`
+	SelectorType SelectorType `protobuf:"varint,4,opt,name=selector_type,json=selectorType,proto3,enum=buckley.browserd.v1.SelectorType" json:"selector_type,omitempty"`
+	// Scroll a node_id target into view before clicking, hovering, focusing or
+	// typing into it. Unset means true.
+	ScrollIntoView *bool `protobuf:"varint,5,opt,name=scroll_into_view,json=scrollIntoView,proto3,oneof" json:"scroll_into_view,omitempty"`
+	unknownFields  protoimpl.UnknownFields
+	sizeCache      protoimpl.SizeCache
 }
 
 func (x *ActionTarget) Reset() {
 	*x = ActionTarget{}
-	mi := &file_browserd_proto_msgTypes[29]
+	mi := &file_browserd_proto_msgTypes[111]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2338,7 +8796,7 @@ func (x *ActionTarget) String() string {
 func (*ActionTarget) ProtoMessage() {}
 
 func (x *ActionTarget) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[29]
+	mi := &file_browserd_proto_msgTypes[111]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2351,7 +8809,7 @@ func (x *ActionTarget) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ActionTarget.ProtoReflect.Descriptor instead.
 func (*ActionTarget) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{29}
+	return file_browserd_proto_rawDescGZIP(), []int{111}
 }
 
 func (x *ActionTarget) GetNodeId() uint64 {
@@ -2368,6 +8826,27 @@ func (x *ActionTarget) GetPoint() *Point {
 	return nil
 }
 
+func (x *ActionTarget) GetSelector() string {
+	if x != nil {
+		return x.Selector
+	}
+	return ""
+}
+
+func (x *ActionTarget) GetSelectorType() SelectorType {
+	if x != nil {
+		return x.SelectorType
+	}
+	return SelectorType_SELECTOR_TYPE_UNSPECIFIED
+}
+
+func (x *ActionTarget) GetScrollIntoView() bool {
+	if x != nil && x.ScrollIntoView != nil {
+		return *x.ScrollIntoView
+	}
+	return false
+}
+
 type ScrollDelta struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
 	X             int32                  `protobuf:"varint,1,opt,name=x,proto3" json:"x,omitempty"`
@@ -2379,7 +8858,7 @@ type ScrollDelta struct {
 
 func (x *ScrollDelta) Reset() {
 	*x = ScrollDelta{}
-	mi := &file_browserd_proto_msgTypes[30]
+	mi := &file_browserd_proto_msgTypes[112]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2391,7 +8870,7 @@ func (x *ScrollDelta) String() string {
 func (*ScrollDelta) ProtoMessage() {}
 
 func (x *ScrollDelta) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[30]
+	mi := &file_browserd_proto_msgTypes[112]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2404,7 +8883,7 @@ func (x *ScrollDelta) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ScrollDelta.ProtoReflect.Descriptor instead.
 func (*ScrollDelta) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{30}
+	return file_browserd_proto_rawDescGZIP(), []int{112}
 }
 
 func (x *ScrollDelta) GetX() int32 {
@@ -2429,17 +8908,25 @@ func (x *ScrollDelta) GetUnit() ScrollUnit {
 }
 
 type ActionResult struct {
-	state         protoimpl.MessageState `protogen:"open.v1"`
-	StateVersion  uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
-	Observation   *Observation           `protobuf:"bytes,2,opt,name=observation,proto3" json:"observation,omitempty"`
-	Effects       []*Effect              `protobuf:"bytes,3,rep,name=effects,proto3" json:"effects,omitempty"`
-	unknownFields protoimpl.UnknownFields
-	sizeCache     protoimpl.SizeCache
+	state        protoimpl.MessageState `protogen:"open.v1"`
+	StateVersion uint64                 `protobuf:"varint,1,opt,name=state_version,json=stateVersion,proto3" json:"state_version,omitempty"`
+	Observation  *Observation           `protobuf:"bytes,2,opt,name=observation,proto3" json:"observation,omitempty"`
+	Effects      []*Effect              `protobuf:"bytes,3,rep,name=effects,proto3" json:"effects,omitempty"`
+	// The element and viewport point the action's target resolved to. 0 and
+	// unset when the engine couldn't tell.
+	ResolvedNodeId uint64 `protobuf:"varint,4,opt,name=resolved_node_id,json=resolvedNodeId,proto3" json:"resolved_node_id,omitempty"`
+	ResolvedPoint  *Point `protobuf:"bytes,5,opt,name=resolved_point,json=resolvedPoint,proto3" json:"resolved_point,omitempty"`
+	// The action started a page load. The observation is taken after the load
+	// finished or the navigation timeout passed, and effects include a
+	// "navigation" entry with the old and new URLs.
+	CausedNavigation bool `protobuf:"varint,6,opt,name=caused_navigation,json=causedNavigation,proto3" json:"caused_navigation,omitempty"`
+	unknownFields    protoimpl.UnknownFields
+	sizeCache        protoimpl.SizeCache
 }
 
 func (x *ActionResult) Reset() {
 	*x = ActionResult{}
-	mi := &file_browserd_proto_msgTypes[31]
+	mi := &file_browserd_proto_msgTypes[113]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2451,7 +8938,7 @@ func (x *ActionResult) String() string {
 func (*ActionResult) ProtoMessage() {}
 
 func (x *ActionResult) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[31]
+	mi := &file_browserd_proto_msgTypes[113]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2464,7 +8951,7 @@ func (x *ActionResult) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use ActionResult.ProtoReflect.Descriptor instead.
 func (*ActionResult) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{31}
+	return file_browserd_proto_rawDescGZIP(), []int{113}
 }
 
 func (x *ActionResult) GetStateVersion() uint64 {
@@ -2488,6 +8975,27 @@ func (x *ActionResult) GetEffects() []*Effect {
 	return nil
 }
 
+func (x *ActionResult) GetResolvedNodeId() uint64 {
+	if x != nil {
+		return x.ResolvedNodeId
+	}
+	return 0
+}
+
+func (x *ActionResult) GetResolvedPoint() *Point {
+	if x != nil {
+		return x.ResolvedPoint
+	}
+	return nil
+}
+
+func (x *ActionResult) GetCausedNavigation() bool {
+	if x != nil {
+		return x.CausedNavigation
+	}
+	return false
+}
+
 type Effect struct {
 	state         protoimpl.MessageState `protogen:"open.v1"`
 	Kind          string                 `protobuf:"bytes,1,opt,name=kind,proto3" json:"kind,omitempty"`
@@ -2499,7 +9007,7 @@ type Effect struct {
 
 func (x *Effect) Reset() {
 	*x = Effect{}
-	mi := &file_browserd_proto_msgTypes[32]
+	mi := &file_browserd_proto_msgTypes[114]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2511,7 +9019,7 @@ func (x *Effect) String() string {
 func (*Effect) ProtoMessage() {}
 
 func (x *Effect) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[32]
+	mi := &file_browserd_proto_msgTypes[114]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2524,7 +9032,7 @@ func (x *Effect) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use Effect.ProtoReflect.Descriptor instead.
 func (*Effect) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{32}
+	return file_browserd_proto_rawDescGZIP(), []int{114}
 }
 
 func (x *Effect) GetKind() string {
@@ -2557,13 +9065,22 @@ type StreamEvent struct {
 	AccessibilityDiff []byte                 `protobuf:"bytes,5,opt,name=accessibility_diff,json=accessibilityDiff,proto3" json:"accessibility_diff,omitempty"`
 	HitTest           *HitTestMap            `protobuf:"bytes,6,opt,name=hit_test,json=hitTest,proto3" json:"hit_test,omitempty"`
 	Timestamp         *timestamppb.Timestamp `protobuf:"bytes,7,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
-	unknownFields     protoimpl.UnknownFields
-	sizeCache         protoimpl.SizeCache
+	Stats             *StreamStats           `protobuf:"bytes,8,opt,name=stats,proto3" json:"stats,omitempty"`
+	ConsoleMessages   []*ConsoleMessage      `protobuf:"bytes,9,rep,name=console_messages,json=consoleMessages,proto3" json:"console_messages,omitempty"`
+	// Connection-wide message number, shared with Response.sequence. Dropped
+	// events still use up a number, so a gap means events were dropped.
+	Sequence uint64 `protobuf:"varint,10,opt,name=sequence,proto3" json:"sequence,omitempty"`
+	// For DOM_DIFF and ACCESSIBILITY_DIFF, the hash of the snapshot the diff
+	// produces, as in Observation.dom_snapshot_hash, so consumers can check
+	// they applied it to the right base.
+	ContentHash   string `protobuf:"bytes,11,opt,name=content_hash,json=contentHash,proto3" json:"content_hash,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
 }
 
 func (x *StreamEvent) Reset() {
 	*x = StreamEvent{}
-	mi := &file_browserd_proto_msgTypes[33]
+	mi := &file_browserd_proto_msgTypes[115]
 	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 	ms.StoreMessageInfo(mi)
 }
@@ -2575,7 +9092,7 @@ func (x *StreamEvent) String() string {
 func (*StreamEvent) ProtoMessage() {}
 
 func (x *StreamEvent) ProtoReflect() protoreflect.Message {
-	mi := &file_browserd_proto_msgTypes[33]
+	mi := &file_browserd_proto_msgTypes[115]
 	if x != nil {
 		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
 		if ms.LoadMessageInfo() == nil {
@@ -2588,7 +9105,7 @@ func (x *StreamEvent) ProtoReflect() protoreflect.Message {
 
 // Deprecated: Use StreamEvent.ProtoReflect.Descriptor instead.
 func (*StreamEvent) Descriptor() ([]byte, []int) {
-	return file_browserd_proto_rawDescGZIP(), []int{33}
+	return file_browserd_proto_rawDescGZIP(), []int{115}
 }
 
 func (x *StreamEvent) GetType() StreamEventType {
@@ -2640,6 +9157,89 @@ func (x *StreamEvent) GetTimestamp() *timestamppb.Timestamp {
 	return nil
 }
 
+func (x *StreamEvent) GetStats() *StreamStats {
+	if x != nil {
+		return x.Stats
+	}
+	return nil
+}
+
+func (x *StreamEvent) GetConsoleMessages() []*ConsoleMessage {
+	if x != nil {
+		return x.ConsoleMessages
+	}
+	return nil
+}
+
+func (x *StreamEvent) GetSequence() uint64 {
+	if x != nil {
+		return x.Sequence
+	}
+	return 0
+}
+
+func (x *StreamEvent) GetContentHash() string {
+	if x != nil {
+		return x.ContentHash
+	}
+	return ""
+}
+
+// Sent periodically on a stream. Events are dropped instead of queued when
+// the client reads too slowly, so a growing events_dropped means the
+// consumer is falling behind.
+type StreamStats struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	EventsSent    uint64                 `protobuf:"varint,1,opt,name=events_sent,json=eventsSent,proto3" json:"events_sent,omitempty"`
+	EventsDropped uint64                 `protobuf:"varint,2,opt,name=events_dropped,json=eventsDropped,proto3" json:"events_dropped,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *StreamStats) Reset() {
+	*x = StreamStats{}
+	mi := &file_browserd_proto_msgTypes[116]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *StreamStats) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*StreamStats) ProtoMessage() {}
+
+func (x *StreamStats) ProtoReflect() protoreflect.Message {
+	mi := &file_browserd_proto_msgTypes[116]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use StreamStats.ProtoReflect.Descriptor instead.
+func (*StreamStats) Descriptor() ([]byte, []int) {
+	return file_browserd_proto_rawDescGZIP(), []int{116}
+}
+
+func (x *StreamStats) GetEventsSent() uint64 {
+	if x != nil {
+		return x.EventsSent
+	}
+	return 0
+}
+
+func (x *StreamStats) GetEventsDropped() uint64 {
+	if x != nil {
+		return x.EventsDropped
+	}
+	return 0
+}
+
 var File_browserd_proto protoreflect.FileDescriptor
 
 const file_browserd_proto_rawDesc = "" +
@@ -2649,10 +9249,11 @@ const file_browserd_proto_rawDesc = "" +
 	"\arequest\x18\x01 \x01(\v2\x1c.buckley.browserd.v1.RequestH\x00R\arequest\x12;\n" +
 	"\bresponse\x18\x02 \x01(\v2\x1d.buckley.browserd.v1.ResponseH\x00R\bresponse\x128\n" +
 	"\x05event\x18\x03 \x01(\v2 .buckley.browserd.v1.StreamEventH\x00R\x05eventB\t\n" +
-	"\amessage\"5\n" +
+	"\amessage\"r\n" +
 	"\x05Error\x12\x12\n" +
 	"\x04code\x18\x01 \x01(\tR\x04code\x12\x18\n" +
-	"\amessage\x18\x02 \x01(\tR\amessage\"\x8b\x04\n" +
+	"\amessage\x18\x02 \x01(\tR\amessage\x12;\n" +
+	"\tcode_enum\x18\x03 \x01(\x0e2\x1e.buckley.browserd.v1.ErrorCodeR\bcodeEnum\"\xe6\x17\n" +
 	"\aRequest\x12\x1d\n" +
 	"\n" +
 	"request_id\x18\x01 \x01(\tR\trequestId\x12\x1d\n" +
@@ -2663,8 +9264,48 @@ const file_browserd_proto_rawDesc = "" +
 	"\aobserve\x18\x05 \x01(\v2#.buckley.browserd.v1.ObserveRequestH\x00R\aobserve\x123\n" +
 	"\x03act\x18\x06 \x01(\v2\x1f.buckley.browserd.v1.ActRequestH\x00R\x03act\x12O\n" +
 	"\rclose_session\x18\a \x01(\v2(.buckley.browserd.v1.CloseSessionRequestH\x00R\fcloseSession\x12X\n" +
-	"\x10stream_subscribe\x18\b \x01(\v2+.buckley.browserd.v1.StreamSubscribeRequestH\x00R\x0fstreamSubscribeB\t\n" +
-	"\apayload\"\xc4\x04\n" +
+	"\x10stream_subscribe\x18\b \x01(\v2+.buckley.browserd.v1.StreamSubscribeRequestH\x00R\x0fstreamSubscribe\x12O\n" +
+	"\rlist_sessions\x18\t \x01(\v2(.buckley.browserd.v1.ListSessionsRequestH\x00R\flistSessions\x12R\n" +
+	"\x0echeck_contrast\x18\n" +
+	" \x01(\v2).buckley.browserd.v1.CheckContrastRequestH\x00R\rcheckContrast\x12@\n" +
+	"\bget_info\x18\v \x01(\v2#.buckley.browserd.v1.GetInfoRequestH\x00R\agetInfo\x12F\n" +
+	"\n" +
+	"export_pdf\x18\f \x01(\v2%.buckley.browserd.v1.ExportPdfRequestH\x00R\texportPdf\x12I\n" +
+	"\vget_cookies\x18\r \x01(\v2&.buckley.browserd.v1.GetCookiesRequestH\x00R\n" +
+	"getCookies\x12F\n" +
+	"\n" +
+	"set_cookie\x18\x0e \x01(\v2%.buckley.browserd.v1.SetCookieRequestH\x00R\tsetCookie\x12O\n" +
+	"\rclear_cookies\x18\x0f \x01(\v2(.buckley.browserd.v1.ClearCookiesRequestH\x00R\fclearCookies\x12@\n" +
+	"\bwait_for\x18\x10 \x01(\v2#.buckley.browserd.v1.WaitForRequestH\x00R\awaitFor\x12B\n" +
+	"\bshutdown\x18\x11 \x01(\v2$.buckley.browserd.v1.ShutdownRequestH\x00R\bshutdown\x12N\n" +
+	"\fauthenticate\x18\x12 \x01(\v2(.buckley.browserd.v1.AuthenticateRequestH\x00R\fauthenticate\x12@\n" +
+	"\bopen_tab\x18\x13 \x01(\v2#.buckley.browserd.v1.OpenTabRequestH\x00R\aopenTab\x12C\n" +
+	"\tclose_tab\x18\x14 \x01(\v2$.buckley.browserd.v1.CloseTabRequestH\x00R\bcloseTab\x12F\n" +
+	"\n" +
+	"switch_tab\x18\x15 \x01(\v2%.buckley.browserd.v1.SwitchTabRequestH\x00R\tswitchTab\x12C\n" +
+	"\tlist_tabs\x18\x16 \x01(\v2$.buckley.browserd.v1.ListTabsRequestH\x00R\blistTabs\x12L\n" +
+	"\fextract_text\x18\x17 \x01(\v2'.buckley.browserd.v1.ExtractTextRequestH\x00R\vextractText\x12L\n" +
+	"\fset_viewport\x18\x18 \x01(\v2'.buckley.browserd.v1.SetViewportRequestH\x00R\vsetViewport\x12L\n" +
+	"\fsave_session\x18\x19 \x01(\v2'.buckley.browserd.v1.SaveSessionRequestH\x00R\vsaveSession\x12U\n" +
+	"\x0frestore_session\x18\x1a \x01(\v2*.buckley.browserd.v1.RestoreSessionRequestH\x00R\x0erestoreSession\x12O\n" +
+	"\rclipboard_get\x18\x1b \x01(\v2(.buckley.browserd.v1.ClipboardGetRequestH\x00R\fclipboardGet\x12O\n" +
+	"\rclipboard_set\x18\x1c \x01(\v2(.buckley.browserd.v1.ClipboardSetRequestH\x00R\fclipboardSet\x12C\n" +
+	"\tfill_form\x18\x1d \x01(\v2$.buckley.browserd.v1.FillFormRequestH\x00R\bfillForm\x12P\n" +
+	"\x0eset_file_input\x18\x1e \x01(\v2(.buckley.browserd.v1.SetFileInputRequestH\x00R\fsetFileInput\x12R\n" +
+	"\x0elist_downloads\x18\x1f \x01(\v2).buckley.browserd.v1.ListDownloadsRequestH\x00R\rlistDownloads\x12L\n" +
+	"\fget_download\x18  \x01(\v2'.buckley.browserd.v1.GetDownloadRequestH\x00R\vgetDownload\x12O\n" +
+	"\rsession_stats\x18! \x01(\v2(.buckley.browserd.v1.SessionStatsRequestH\x00R\fsessionStats\x12O\n" +
+	"\rbatch_actions\x18\" \x01(\v2(.buckley.browserd.v1.BatchActionsRequestH\x00R\fbatchActions\x12I\n" +
+	"\vset_storage\x18# \x01(\v2&.buckley.browserd.v1.SetStorageRequestH\x00R\n" +
+	"setStorage\x12I\n" +
+	"\vget_storage\x18$ \x01(\v2&.buckley.browserd.v1.GetStorageRequestH\x00R\n" +
+	"getStorage\x12O\n" +
+	"\rclear_storage\x18% \x01(\v2(.buckley.browserd.v1.ClearStorageRequestH\x00R\fclearStorage\x12_\n" +
+	"\x13clear_browsing_data\x18& \x01(\v2-.buckley.browserd.v1.ClearBrowsingDataRequestH\x00R\x11clearBrowsingData\x126\n" +
+	"\x04stop\x18' \x01(\v2 .buckley.browserd.v1.StopRequestH\x00R\x04stop\x12M\n" +
+	"\rset_log_level\x18( \x01(\v2'.buckley.browserd.v1.SetLogLevelRequestH\x00R\vsetLogLevel\x12O\n" +
+	"\rquery_element\x18) \x01(\v2(.buckley.browserd.v1.QueryElementRequestH\x00R\fqueryElementB\t\n" +
+	"\apayload\"\xdc\x18\n" +
 	"\bResponse\x12\x1d\n" +
 	"\n" +
 	"request_id\x18\x01 \x01(\tR\trequestId\x12\x1d\n" +
@@ -2676,26 +9317,81 @@ const file_browserd_proto_rawDesc = "" +
 	"\aobserve\x18\x06 \x01(\v2$.buckley.browserd.v1.ObserveResponseH\x00R\aobserve\x124\n" +
 	"\x03act\x18\a \x01(\v2 .buckley.browserd.v1.ActResponseH\x00R\x03act\x12P\n" +
 	"\rclose_session\x18\b \x01(\v2).buckley.browserd.v1.CloseSessionResponseH\x00R\fcloseSession\x12Y\n" +
-	"\x10stream_subscribe\x18\t \x01(\v2,.buckley.browserd.v1.StreamSubscribeResponseH\x00R\x0fstreamSubscribeB\t\n" +
+	"\x10stream_subscribe\x18\t \x01(\v2,.buckley.browserd.v1.StreamSubscribeResponseH\x00R\x0fstreamSubscribe\x12P\n" +
+	"\rlist_sessions\x18\n" +
+	" \x01(\v2).buckley.browserd.v1.ListSessionsResponseH\x00R\flistSessions\x12S\n" +
+	"\x0echeck_contrast\x18\v \x01(\v2*.buckley.browserd.v1.CheckContrastResponseH\x00R\rcheckContrast\x12A\n" +
+	"\bget_info\x18\f \x01(\v2$.buckley.browserd.v1.GetInfoResponseH\x00R\agetInfo\x12G\n" +
+	"\n" +
+	"export_pdf\x18\r \x01(\v2&.buckley.browserd.v1.ExportPdfResponseH\x00R\texportPdf\x12J\n" +
+	"\vget_cookies\x18\x0e \x01(\v2'.buckley.browserd.v1.GetCookiesResponseH\x00R\n" +
+	"getCookies\x12G\n" +
+	"\n" +
+	"set_cookie\x18\x0f \x01(\v2&.buckley.browserd.v1.SetCookieResponseH\x00R\tsetCookie\x12P\n" +
+	"\rclear_cookies\x18\x10 \x01(\v2).buckley.browserd.v1.ClearCookiesResponseH\x00R\fclearCookies\x12A\n" +
+	"\bwait_for\x18\x11 \x01(\v2$.buckley.browserd.v1.WaitForResponseH\x00R\awaitFor\x12C\n" +
+	"\bshutdown\x18\x12 \x01(\v2%.buckley.browserd.v1.ShutdownResponseH\x00R\bshutdown\x12O\n" +
+	"\fauthenticate\x18\x13 \x01(\v2).buckley.browserd.v1.AuthenticateResponseH\x00R\fauthenticate\x12A\n" +
+	"\bopen_tab\x18\x14 \x01(\v2$.buckley.browserd.v1.OpenTabResponseH\x00R\aopenTab\x12D\n" +
+	"\tclose_tab\x18\x15 \x01(\v2%.buckley.browserd.v1.CloseTabResponseH\x00R\bcloseTab\x12G\n" +
+	"\n" +
+	"switch_tab\x18\x16 \x01(\v2&.buckley.browserd.v1.SwitchTabResponseH\x00R\tswitchTab\x12D\n" +
+	"\tlist_tabs\x18\x17 \x01(\v2%.buckley.browserd.v1.ListTabsResponseH\x00R\blistTabs\x12M\n" +
+	"\fextract_text\x18\x18 \x01(\v2(.buckley.browserd.v1.ExtractTextResponseH\x00R\vextractText\x12M\n" +
+	"\fset_viewport\x18\x19 \x01(\v2(.buckley.browserd.v1.SetViewportResponseH\x00R\vsetViewport\x12M\n" +
+	"\fsave_session\x18\x1a \x01(\v2(.buckley.browserd.v1.SaveSessionResponseH\x00R\vsaveSession\x12V\n" +
+	"\x0frestore_session\x18\x1b \x01(\v2+.buckley.browserd.v1.RestoreSessionResponseH\x00R\x0erestoreSession\x12P\n" +
+	"\rclipboard_get\x18\x1c \x01(\v2).buckley.browserd.v1.ClipboardGetResponseH\x00R\fclipboardGet\x12P\n" +
+	"\rclipboard_set\x18\x1d \x01(\v2).buckley.browserd.v1.ClipboardSetResponseH\x00R\fclipboardSet\x12D\n" +
+	"\tfill_form\x18\x1e \x01(\v2%.buckley.browserd.v1.FillFormResponseH\x00R\bfillForm\x12Q\n" +
+	"\x0eset_file_input\x18\x1f \x01(\v2).buckley.browserd.v1.SetFileInputResponseH\x00R\fsetFileInput\x12S\n" +
+	"\x0elist_downloads\x18  \x01(\v2*.buckley.browserd.v1.ListDownloadsResponseH\x00R\rlistDownloads\x12M\n" +
+	"\fget_download\x18! \x01(\v2(.buckley.browserd.v1.GetDownloadResponseH\x00R\vgetDownload\x12P\n" +
+	"\rsession_stats\x18\" \x01(\v2).buckley.browserd.v1.SessionStatsResponseH\x00R\fsessionStats\x12P\n" +
+	"\rbatch_actions\x18# \x01(\v2).buckley.browserd.v1.BatchActionsResponseH\x00R\fbatchActions\x12J\n" +
+	"\vset_storage\x18$ \x01(\v2'.buckley.browserd.v1.SetStorageResponseH\x00R\n" +
+	"setStorage\x12J\n" +
+	"\vget_storage\x18% \x01(\v2'.buckley.browserd.v1.GetStorageResponseH\x00R\n" +
+	"getStorage\x12P\n" +
+	"\rclear_storage\x18& \x01(\v2).buckley.browserd.v1.ClearStorageResponseH\x00R\fclearStorage\x12`\n" +
+	"\x13clear_browsing_data\x18' \x01(\v2..buckley.browserd.v1.ClearBrowsingDataResponseH\x00R\x11clearBrowsingData\x127\n" +
+	"\x04stop\x18( \x01(\v2!.buckley.browserd.v1.StopResponseH\x00R\x04stop\x12N\n" +
+	"\rset_log_level\x18* \x01(\v2(.buckley.browserd.v1.SetLogLevelResponseH\x00R\vsetLogLevel\x12P\n" +
+	"\rquery_element\x18+ \x01(\v2).buckley.browserd.v1.QueryElementResponseH\x00R\fqueryElement\x12\x1a\n" +
+	"\bsequence\x18) \x01(\x04R\bsequenceB\t\n" +
 	"\apayload\"R\n" +
 	"\x14CreateSessionRequest\x12:\n" +
 	"\x06config\x18\x01 \x01(\v2\".buckley.browserd.v1.SessionConfigR\x06config\"\x97\x01\n" +
 	"\x15CreateSessionResponse\x12:\n" +
 	"\asession\x18\x01 \x01(\v2 .buckley.browserd.v1.SessionInfoR\asession\x12B\n" +
-	"\vobservation\x18\x02 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"#\n" +
+	"\vobservation\x18\x02 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"B\n" +
 	"\x0fNavigateRequest\x12\x10\n" +
-	"\x03url\x18\x01 \x01(\tR\x03url\"V\n" +
+	"\x03url\x18\x01 \x01(\tR\x03url\x12\x1d\n" +
+	"\n" +
+	"timeout_ms\x18\x02 \x01(\rR\ttimeoutMs\"V\n" +
 	"\x10NavigateResponse\x12B\n" +
-	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"O\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"\r\n" +
+	"\vStopRequest\"R\n" +
+	"\fStopResponse\x12B\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"\xaa\x01\n" +
 	"\x0eObserveRequest\x12=\n" +
-	"\aoptions\x18\x01 \x01(\v2#.buckley.browserd.v1.ObserveOptionsR\aoptions\"U\n" +
+	"\aoptions\x18\x01 \x01(\v2#.buckley.browserd.v1.ObserveOptionsR\aoptions\x12<\n" +
+	"\x18if_state_version_changed\x18\x02 \x01(\x04H\x00R\x15ifStateVersionChanged\x88\x01\x01B\x1b\n" +
+	"\x19_if_state_version_changed\"x\n" +
 	"\x0fObserveResponse\x12B\n" +
-	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"A\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\x12!\n" +
+	"\fnot_modified\x18\x02 \x01(\bR\vnotModified\"A\n" +
 	"\n" +
 	"ActRequest\x123\n" +
 	"\x06action\x18\x01 \x01(\v2\x1b.buckley.browserd.v1.ActionR\x06action\"H\n" +
 	"\vActResponse\x129\n" +
-	"\x06result\x18\x01 \x01(\v2!.buckley.browserd.v1.ActionResultR\x06result\"\x15\n" +
+	"\x06result\x18\x01 \x01(\v2!.buckley.browserd.v1.ActionResultR\x06result\"L\n" +
+	"\x13BatchActionsRequest\x125\n" +
+	"\aactions\x18\x01 \x03(\v2\x1b.buckley.browserd.v1.ActionR\aactions\"\xa8\x01\n" +
+	"\x14BatchActionsResponse\x12;\n" +
+	"\aresults\x18\x01 \x03(\v2!.buckley.browserd.v1.ActionResultR\aresults\x12!\n" +
+	"\ffailed_index\x18\x02 \x01(\x05R\vfailedIndex\x120\n" +
+	"\x05error\x18\x03 \x01(\v2\x1a.buckley.browserd.v1.ErrorR\x05error\"\x15\n" +
 	"\x13CloseSessionRequest\".\n" +
 	"\x14CloseSessionResponse\x12\x16\n" +
 	"\x06closed\x18\x01 \x01(\bR\x06closed\"V\n" +
@@ -2704,12 +9400,203 @@ const file_browserd_proto_rawDesc = "" +
 	"\x17StreamSubscribeResponse\x12\x1e\n" +
 	"\n" +
 	"subscribed\x18\x01 \x01(\bR\n" +
-	"subscribed\"c\n" +
+	"subscribed\"\x15\n" +
+	"\x13SessionStatsRequest\"u\n" +
+	"\x14SessionStatsResponse\x12\x1b\n" +
+	"\trss_bytes\x18\x01 \x01(\x04R\brssBytes\x12#\n" +
+	"\rstate_version\x18\x02 \x01(\x04R\fstateVersion\x12\x1b\n" +
+	"\tuptime_ms\x18\x03 \x01(\x04R\buptimeMs\"\x15\n" +
+	"\x13ListSessionsRequest\"T\n" +
+	"\x14ListSessionsResponse\x12<\n" +
+	"\bsessions\x18\x01 \x03(\v2 .buckley.browserd.v1.SessionInfoR\bsessions\"1\n" +
+	"\x14CheckContrastRequest\x12\x19\n" +
+	"\bnode_ids\x18\x01 \x03(\x04R\anodeIds\"V\n" +
+	"\x15CheckContrastResponse\x12=\n" +
+	"\aresults\x18\x01 \x03(\v2#.buckley.browserd.v1.ContrastResultR\aresults\"\x80\x02\n" +
+	"\x0eContrastResult\x12\x17\n" +
+	"\anode_id\x18\x01 \x01(\x04R\x06nodeId\x12\x12\n" +
+	"\x04text\x18\x02 \x01(\tR\x04text\x12\x14\n" +
+	"\x05color\x18\x03 \x01(\tR\x05color\x12)\n" +
+	"\x10background_color\x18\x04 \x01(\tR\x0fbackgroundColor\x12%\n" +
+	"\x0econtrast_ratio\x18\x05 \x01(\x01R\rcontrastRatio\x12\x1d\n" +
+	"\n" +
+	"large_text\x18\x06 \x01(\bR\tlargeText\x12\x1b\n" +
+	"\tpasses_aa\x18\a \x01(\bR\bpassesAa\x12\x1d\n" +
+	"\n" +
+	"passes_aaa\x18\b \x01(\bR\tpassesAaa\"\x10\n" +
+	"\x0eGetInfoRequest\"q\n" +
+	"\x0fGetInfoResponse\x12%\n" +
+	"\x0edaemon_version\x18\x01 \x01(\tR\rdaemonVersion\x127\n" +
+	"\x06engine\x18\x02 \x01(\v2\x1f.buckley.browserd.v1.EngineInfoR\x06engine\"n\n" +
+	"\n" +
+	"EngineInfo\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12\x18\n" +
+	"\aversion\x18\x02 \x01(\tR\aversion\x12\x16\n" +
+	"\x06commit\x18\x03 \x01(\tR\x06commit\x12\x1a\n" +
+	"\bfeatures\x18\x04 \x03(\tR\bfeatures\"N\n" +
+	"\x10ExportPdfRequest\x12:\n" +
+	"\tpage_size\x18\x01 \x01(\v2\x1d.buckley.browserd.v1.ViewportR\bpageSize\"'\n" +
+	"\x11ExportPdfResponse\x12\x12\n" +
+	"\x04data\x18\x01 \x01(\fR\x04data\"\xb6\x01\n" +
+	"\x06Cookie\x12\x12\n" +
+	"\x04name\x18\x01 \x01(\tR\x04name\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value\x12\x16\n" +
+	"\x06domain\x18\x03 \x01(\tR\x06domain\x12\x12\n" +
+	"\x04path\x18\x04 \x01(\tR\x04path\x12\x16\n" +
+	"\x06secure\x18\x05 \x01(\bR\x06secure\x12\x1b\n" +
+	"\thttp_only\x18\x06 \x01(\bR\bhttpOnly\x12!\n" +
+	"\fexpires_unix\x18\a \x01(\x03R\vexpiresUnix\"\x13\n" +
+	"\x11GetCookiesRequest\"K\n" +
+	"\x12GetCookiesResponse\x125\n" +
+	"\acookies\x18\x01 \x03(\v2\x1b.buckley.browserd.v1.CookieR\acookies\"G\n" +
+	"\x10SetCookieRequest\x123\n" +
+	"\x06cookie\x18\x01 \x01(\v2\x1b.buckley.browserd.v1.CookieR\x06cookie\"%\n" +
+	"\x11SetCookieResponse\x12\x10\n" +
+	"\x03set\x18\x01 \x01(\bR\x03set\"\x15\n" +
+	"\x13ClearCookiesRequest\"0\n" +
+	"\x14ClearCookiesResponse\x12\x18\n" +
+	"\acleared\x18\x01 \x01(\rR\acleared\"6\n" +
+	"\fStorageEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value\"\x89\x01\n" +
+	"\x11SetStorageRequest\x127\n" +
+	"\x05scope\x18\x01 \x01(\x0e2!.buckley.browserd.v1.StorageScopeR\x05scope\x12;\n" +
+	"\aentries\x18\x02 \x03(\v2!.buckley.browserd.v1.StorageEntryR\aentries\"&\n" +
+	"\x12SetStorageResponse\x12\x10\n" +
+	"\x03set\x18\x01 \x01(\rR\x03set\"`\n" +
+	"\x11GetStorageRequest\x127\n" +
+	"\x05scope\x18\x01 \x01(\x0e2!.buckley.browserd.v1.StorageScopeR\x05scope\x12\x12\n" +
+	"\x04keys\x18\x02 \x03(\tR\x04keys\"i\n" +
+	"\x12GetStorageResponse\x12\x16\n" +
+	"\x06origin\x18\x01 \x01(\tR\x06origin\x12;\n" +
+	"\aentries\x18\x02 \x03(\v2!.buckley.browserd.v1.StorageEntryR\aentries\"N\n" +
+	"\x13ClearStorageRequest\x127\n" +
+	"\x05scope\x18\x01 \x01(\x0e2!.buckley.browserd.v1.StorageScopeR\x05scope\"0\n" +
+	"\x14ClearStorageResponse\x12\x18\n" +
+	"\acleared\x18\x01 \x01(\rR\acleared\"\x82\x01\n" +
+	"\x18ClearBrowsingDataRequest\x12\x18\n" +
+	"\acookies\x18\x01 \x01(\bR\acookies\x12\x18\n" +
+	"\astorage\x18\x02 \x01(\bR\astorage\x12\x14\n" +
+	"\x05cache\x18\x03 \x01(\bR\x05cache\x12\x1c\n" +
+	"\tclipboard\x18\x04 \x01(\bR\tclipboard\"O\n" +
+	"\x19ClearBrowsingDataResponse\x12\x18\n" +
+	"\acleared\x18\x01 \x03(\tR\acleared\x12\x18\n" +
+	"\apartial\x18\x02 \x03(\tR\apartial\"K\n" +
+	"\x0eWaitForRequest\x12\x1a\n" +
+	"\bselector\x18\x01 \x01(\tR\bselector\x12\x1d\n" +
+	"\n" +
+	"timeout_ms\x18\x02 \x01(\rR\ttimeoutMs\"U\n" +
+	"\x0fWaitForResponse\x12B\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"\x11\n" +
+	"\x0fShutdownRequest\";\n" +
+	"\x10ShutdownResponse\x12'\n" +
+	"\x0fsessions_closed\x18\x01 \x01(\rR\x0esessionsClosed\"\x99\x01\n" +
+	"\x13QueryElementRequest\x12\x1a\n" +
+	"\bselector\x18\x01 \x01(\tR\bselector\x12F\n" +
+	"\rselector_type\x18\x02 \x01(\x0e2!.buckley.browserd.v1.SelectorTypeR\fselectorType\x12\x1e\n" +
+	"\n" +
+	"properties\x18\x03 \x03(\tR\n" +
+	"properties\"\xc6\x01\n" +
+	"\x14QueryElementResponse\x12\x14\n" +
+	"\x05found\x18\x01 \x01(\bR\x05found\x12Y\n" +
+	"\n" +
+	"properties\x18\x02 \x03(\v29.buckley.browserd.v1.QueryElementResponse.PropertiesEntryR\n" +
+	"properties\x1a=\n" +
+	"\x0fPropertiesEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value:\x028\x01\"I\n" +
+	"\x12SetLogLevelRequest\x123\n" +
+	"\x05level\x18\x01 \x01(\x0e2\x1d.buckley.browserd.v1.LogLevelR\x05level\"\x85\x01\n" +
+	"\x13SetLogLevelResponse\x129\n" +
+	"\bprevious\x18\x01 \x01(\x0e2\x1d.buckley.browserd.v1.LogLevelR\bprevious\x123\n" +
+	"\x05level\x18\x02 \x01(\x0e2\x1d.buckley.browserd.v1.LogLevelR\x05level\"+\n" +
+	"\x13AuthenticateRequest\x12\x14\n" +
+	"\x05token\x18\x01 \x01(\tR\x05token\"<\n" +
+	"\x14AuthenticateResponse\x12$\n" +
+	"\rauthenticated\x18\x01 \x01(\bR\rauthenticated\"\\\n" +
+	"\x03Tab\x12\x15\n" +
+	"\x06tab_id\x18\x01 \x01(\x04R\x05tabId\x12\x10\n" +
+	"\x03url\x18\x02 \x01(\tR\x03url\x12\x14\n" +
+	"\x05title\x18\x03 \x01(\tR\x05title\x12\x16\n" +
+	"\x06active\x18\x04 \x01(\bR\x06active\"\"\n" +
+	"\x0eOpenTabRequest\x12\x10\n" +
+	"\x03url\x18\x01 \x01(\tR\x03url\"=\n" +
+	"\x0fOpenTabResponse\x12*\n" +
+	"\x03tab\x18\x01 \x01(\v2\x18.buckley.browserd.v1.TabR\x03tab\"(\n" +
+	"\x0fCloseTabRequest\x12\x15\n" +
+	"\x06tab_id\x18\x01 \x01(\x04R\x05tabId\"6\n" +
+	"\x10CloseTabResponse\x12\"\n" +
+	"\ractive_tab_id\x18\x01 \x01(\x04R\vactiveTabId\")\n" +
+	"\x10SwitchTabRequest\x12\x15\n" +
+	"\x06tab_id\x18\x01 \x01(\x04R\x05tabId\"W\n" +
+	"\x11SwitchTabResponse\x12B\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"\x11\n" +
+	"\x0fListTabsRequest\"@\n" +
+	"\x10ListTabsResponse\x12,\n" +
+	"\x04tabs\x18\x01 \x03(\v2\x18.buckley.browserd.v1.TabR\x04tabs\"1\n" +
+	"\x12ExtractTextRequest\x12\x1b\n" +
+	"\tmax_bytes\x18\x01 \x01(\rR\bmaxBytes\"f\n" +
+	"\x13ExtractTextResponse\x12\x12\n" +
+	"\x04text\x18\x01 \x01(\tR\x04text\x12\x1d\n" +
+	"\n" +
+	"word_count\x18\x02 \x01(\rR\twordCount\x12\x1c\n" +
+	"\ttruncated\x18\x03 \x01(\bR\ttruncated\"I\n" +
+	"\x0fFillFormRequest\x126\n" +
+	"\x06fields\x18\x01 \x03(\v2\x1e.buckley.browserd.v1.FormFieldR\x06fields\"=\n" +
+	"\tFormField\x12\x1a\n" +
+	"\bselector\x18\x01 \x01(\tR\bselector\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value\"s\n" +
+	"\x10FillFormResponse\x12:\n" +
+	"\aresults\x18\x01 \x03(\v2 .buckley.browserd.v1.FieldResultR\aresults\x12#\n" +
+	"\rstate_version\x18\x02 \x01(\x04R\fstateVersion\"W\n" +
+	"\vFieldResult\x12\x1a\n" +
+	"\bselector\x18\x01 \x01(\tR\bselector\x12\x16\n" +
+	"\x06filled\x18\x02 \x01(\bR\x06filled\x12\x14\n" +
+	"\x05error\x18\x03 \x01(\tR\x05error\"a\n" +
+	"\x13SetFileInputRequest\x12\x1a\n" +
+	"\bselector\x18\x01 \x01(\tR\bselector\x12\x1a\n" +
+	"\bfilename\x18\x02 \x01(\tR\bfilename\x12\x12\n" +
+	"\x04data\x18\x03 \x01(\fR\x04data\"m\n" +
+	"\x14SetFileInputResponse\x12\x1a\n" +
+	"\bfilename\x18\x01 \x01(\tR\bfilename\x12\x14\n" +
+	"\x05bytes\x18\x02 \x01(\x04R\x05bytes\x12#\n" +
+	"\rstate_version\x18\x03 \x01(\x04R\fstateVersion\"{\n" +
+	"\bDownload\x12\x0e\n" +
+	"\x02id\x18\x01 \x01(\tR\x02id\x12\x1a\n" +
+	"\bfilename\x18\x02 \x01(\tR\bfilename\x12\x10\n" +
+	"\x03url\x18\x03 \x01(\tR\x03url\x12\x1b\n" +
+	"\tmime_type\x18\x04 \x01(\tR\bmimeType\x12\x14\n" +
+	"\x05bytes\x18\x05 \x01(\x04R\x05bytes\"\x16\n" +
+	"\x14ListDownloadsRequest\"\xac\x01\n" +
+	"\x15ListDownloadsResponse\x12;\n" +
+	"\tdownloads\x18\x01 \x03(\v2\x1d.buckley.browserd.v1.DownloadR\tdownloads\x12\x1f\n" +
+	"\vtotal_bytes\x18\x02 \x01(\x04R\n" +
+	"totalBytes\x12\x1b\n" +
+	"\tmax_bytes\x18\x03 \x01(\x04R\bmaxBytes\x12\x18\n" +
+	"\aenabled\x18\x04 \x01(\bR\aenabled\"5\n" +
+	"\x12GetDownloadRequest\x12\x1f\n" +
+	"\vdownload_id\x18\x01 \x01(\tR\n" +
+	"downloadId\"d\n" +
+	"\x13GetDownloadResponse\x129\n" +
+	"\bdownload\x18\x01 \x01(\v2\x1d.buckley.browserd.v1.DownloadR\bdownload\x12\x12\n" +
+	"\x04data\x18\x02 \x01(\fR\x04data\"O\n" +
+	"\x12SetViewportRequest\x129\n" +
+	"\bviewport\x18\x01 \x01(\v2\x1d.buckley.browserd.v1.ViewportR\bviewport\"Y\n" +
+	"\x13SetViewportResponse\x12B\n" +
+	"\vobservation\x18\x01 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\"\x14\n" +
+	"\x12SaveSessionRequest\")\n" +
+	"\x13SaveSessionResponse\x12\x12\n" +
+	"\x04path\x18\x01 \x01(\tR\x04path\"\x17\n" +
+	"\x15RestoreSessionRequest\"\xc3\x01\n" +
+	"\x16RestoreSessionResponse\x12:\n" +
+	"\asession\x18\x01 \x01(\v2 .buckley.browserd.v1.SessionInfoR\asession\x12B\n" +
+	"\vobservation\x18\x02 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\x12)\n" +
+	"\x10cookies_restored\x18\x03 \x01(\rR\x0fcookiesRestored\"c\n" +
 	"\vSessionInfo\x12\x1d\n" +
 	"\n" +
 	"session_id\x18\x01 \x01(\tR\tsessionId\x12#\n" +
 	"\rstate_version\x18\x02 \x01(\x04R\fstateVersion\x12\x10\n" +
-	"\x03url\x18\x03 \x01(\tR\x03url\"\xed\x02\n" +
+	"\x03url\x18\x03 \x01(\tR\x03url\"\xd9\a\n" +
 	"\rSessionConfig\x12\x1d\n" +
 	"\n" +
 	"session_id\x18\x01 \x01(\tR\tsessionId\x12\x1f\n" +
@@ -2723,11 +9610,39 @@ const file_browserd_proto_rawDesc = "" +
 	"\n" +
 	"frame_rate\x18\a \x01(\rR\tframeRate\x12+\n" +
 	"\x11network_allowlist\x18\b \x03(\tR\x10networkAllowlist\x12B\n" +
-	"\tclipboard\x18\t \x01(\v2$.buckley.browserd.v1.ClipboardPolicyR\tclipboard\"h\n" +
+	"\tclipboard\x18\t \x01(\v2$.buckley.browserd.v1.ClipboardPolicyR\tclipboard\x120\n" +
+	"\x14frame_delta_encoding\x18\n" +
+	" \x01(\bR\x12frameDeltaEncoding\x122\n" +
+	"\x15navigation_timeout_ms\x18\v \x01(\rR\x13navigationTimeoutMs\x12 \n" +
+	"\fjs_budget_ms\x18\f \x01(\rR\n" +
+	"jsBudgetMs\x12_\n" +
+	"\x0frequest_headers\x18\r \x03(\v26.buckley.browserd.v1.SessionConfig.RequestHeadersEntryR\x0erequestHeaders\x12)\n" +
+	"\x10network_denylist\x18\x0e \x03(\tR\x0fnetworkDenylist\x122\n" +
+	"\x13post_load_settle_ms\x18\x0f \x01(\rH\x00R\x10postLoadSettleMs\x88\x01\x01\x12+\n" +
+	"\x11downloads_enabled\x18\x10 \x01(\bR\x10downloadsEnabled\x12,\n" +
+	"\x12max_download_bytes\x18\x11 \x01(\x04R\x10maxDownloadBytes\x12'\n" +
+	"\x0fallowed_schemes\x18\x12 \x03(\tR\x0eallowedSchemes\x12%\n" +
+	"\x0edevice_profile\x18\x13 \x01(\tR\rdeviceProfile\x12\x1c\n" +
+	"\tephemeral\x18\x14 \x01(\bR\tephemeral\x1aA\n" +
+	"\x13RequestHeadersEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value:\x028\x01B\x16\n" +
+	"\x14_post_load_settle_ms\"h\n" +
 	"\bViewport\x12\x14\n" +
 	"\x05width\x18\x01 \x01(\rR\x05width\x12\x16\n" +
 	"\x06height\x18\x02 \x01(\rR\x06height\x12.\n" +
-	"\x13device_scale_factor\x18\x03 \x01(\x01R\x11deviceScaleFactor\"\xcd\x01\n" +
+	"\x13device_scale_factor\x18\x03 \x01(\x01R\x11deviceScaleFactor\"q\n" +
+	"\rClipboardData\x12\x12\n" +
+	"\x04text\x18\x01 \x01(\tR\x04text\x12\x14\n" +
+	"\x05bytes\x18\x02 \x01(\rR\x05bytes\x126\n" +
+	"\x04mode\x18\x03 \x01(\x0e2\".buckley.browserd.v1.ClipboardModeR\x04mode\"\x15\n" +
+	"\x13ClipboardGetRequest\"N\n" +
+	"\x14ClipboardGetResponse\x126\n" +
+	"\x04data\x18\x01 \x01(\v2\".buckley.browserd.v1.ClipboardDataR\x04data\")\n" +
+	"\x13ClipboardSetRequest\x12\x12\n" +
+	"\x04text\x18\x01 \x01(\tR\x04text\"N\n" +
+	"\x14ClipboardSetResponse\x126\n" +
+	"\x04data\x18\x01 \x01(\v2\".buckley.browserd.v1.ClipboardDataR\x04data\"\xcd\x01\n" +
 	"\x0fClipboardPolicy\x126\n" +
 	"\x04mode\x18\x01 \x01(\x0e2\".buckley.browserd.v1.ClipboardModeR\x04mode\x12\x1d\n" +
 	"\n" +
@@ -2735,19 +9650,66 @@ const file_browserd_proto_rawDesc = "" +
 	"\vallow_write\x18\x03 \x01(\bR\n" +
 	"allowWrite\x12\x1b\n" +
 	"\tmax_bytes\x18\x04 \x01(\rR\bmaxBytes\x12%\n" +
-	"\x0eread_allowlist\x18\x05 \x03(\tR\rreadAllowlist\"\xc6\x01\n" +
+	"\x0eread_allowlist\x18\x05 \x03(\tR\rreadAllowlist\"\xe5\x06\n" +
 	"\x0eObserveOptions\x12#\n" +
 	"\rinclude_frame\x18\x01 \x01(\bR\fincludeFrame\x120\n" +
 	"\x14include_dom_snapshot\x18\x02 \x01(\bR\x12includeDomSnapshot\x123\n" +
 	"\x15include_accessibility\x18\x03 \x01(\bR\x14includeAccessibility\x12(\n" +
-	"\x10include_hit_test\x18\x04 \x01(\bR\x0eincludeHitTest\"\xeb\x01\n" +
+	"\x10include_hit_test\x18\x04 \x01(\bR\x0eincludeHitTest\x12*\n" +
+	"\x11include_text_runs\x18\x05 \x01(\bR\x0fincludeTextRuns\x12.\n" +
+	"\x13include_engine_info\x18\x06 \x01(\bR\x11includeEngineInfo\x12 \n" +
+	"\fclip_node_id\x18\a \x01(\x04R\n" +
+	"clipNodeId\x126\n" +
+	"\tclip_rect\x18\b \x01(\v2\x19.buckley.browserd.v1.RectR\bclipRect\x12$\n" +
+	"\x0estyle_node_ids\x18\t \x03(\x04R\fstyleNodeIds\x12)\n" +
+	"\x10style_properties\x18\n" +
+	" \x03(\tR\x0fstyleProperties\x12%\n" +
+	"\x0einclude_timing\x18\v \x01(\bR\rincludeTiming\x12&\n" +
+	"\x0fmax_hit_regions\x18\f \x01(\rR\rmaxHitRegions\x12,\n" +
+	"\x12hit_test_selectors\x18\r \x03(\tR\x10hitTestSelectors\x12'\n" +
+	"\x0finclude_console\x18\x0e \x01(\bR\x0eincludeConsole\x12.\n" +
+	"\x13include_page_errors\x18\x0f \x01(\bR\x11includePageErrors\x121\n" +
+	"\x15until_network_idle_ms\x18\x10 \x01(\rR\x12untilNetworkIdleMs\x12!\n" +
+	"\finclude_html\x18\x11 \x01(\bR\vincludeHtml\x12$\n" +
+	"\x0emax_html_bytes\x18\x12 \x01(\rR\fmaxHtmlBytes\x12)\n" +
+	"\x10include_metadata\x18\x13 \x01(\bR\x0fincludeMetadata\x12\x19\n" +
+	"\bdom_flat\x18\x14 \x01(\bR\adomFlat\"\xad\x01\n" +
+	"\x10NavigationTiming\x12\x15\n" +
+	"\x06dns_ms\x18\x01 \x01(\x01R\x05dnsMs\x12\x1d\n" +
+	"\n" +
+	"connect_ms\x18\x02 \x01(\x01R\tconnectMs\x12\x17\n" +
+	"\attfb_ms\x18\x03 \x01(\x01R\x06ttfbMs\x121\n" +
+	"\x15dom_content_loaded_ms\x18\x04 \x01(\x01R\x12domContentLoadedMs\x12\x17\n" +
+	"\aload_ms\x18\x05 \x01(\x01R\x06loadMs\"\xb0\x01\n" +
+	"\x0eComputedStyles\x12D\n" +
+	"\x05nodes\x18\x01 \x03(\v2..buckley.browserd.v1.ComputedStyles.NodesEntryR\x05nodes\x1aX\n" +
+	"\n" +
+	"NodesEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\x04R\x03key\x124\n" +
+	"\x05value\x18\x02 \x01(\v2\x1e.buckley.browserd.v1.NodeStyleR\x05value:\x028\x01\"\x9a\x01\n" +
+	"\tNodeStyle\x12N\n" +
+	"\n" +
+	"properties\x18\x01 \x03(\v2..buckley.browserd.v1.NodeStyle.PropertiesEntryR\n" +
+	"properties\x1a=\n" +
+	"\x0fPropertiesEntry\x12\x10\n" +
+	"\x03key\x18\x01 \x01(\tR\x03key\x12\x14\n" +
+	"\x05value\x18\x02 \x01(\tR\x05value:\x028\x01\"\xae\x04\n" +
 	"\rStreamOptions\x12%\n" +
 	"\x0einclude_frames\x18\x01 \x01(\bR\rincludeFrames\x12*\n" +
 	"\x11include_dom_diffs\x18\x02 \x01(\bR\x0fincludeDomDiffs\x12>\n" +
 	"\x1binclude_accessibility_diffs\x18\x03 \x01(\bR\x19includeAccessibilityDiffs\x12(\n" +
 	"\x10include_hit_test\x18\x04 \x01(\bR\x0eincludeHitTest\x12\x1d\n" +
 	"\n" +
-	"target_fps\x18\x05 \x01(\rR\ttargetFps\"\xd4\x02\n" +
+	"target_fps\x18\x05 \x01(\rR\ttargetFps\x120\n" +
+	"\x14include_frame_timing\x18\x06 \x01(\bR\x12includeFrameTiming\x12H\n" +
+	"\vcompression\x18\a \x01(\x0e2&.buckley.browserd.v1.StreamCompressionR\vcompression\x12\x1f\n" +
+	"\vchange_only\x18\b \x01(\bR\n" +
+	"changeOnly\x12&\n" +
+	"\x0fmax_hit_regions\x18\t \x01(\rR\rmaxHitRegions\x12,\n" +
+	"\x12hit_test_selectors\x18\n" +
+	" \x03(\tR\x10hitTestSelectors\x12'\n" +
+	"\x0finclude_console\x18\v \x01(\bR\x0eincludeConsole\x12%\n" +
+	"\x0eheartbeat_secs\x18\f \x01(\rR\rheartbeatSecs\"\xa1\t\n" +
 	"\vObservation\x12#\n" +
 	"\rstate_version\x18\x01 \x01(\x04R\fstateVersion\x12\x10\n" +
 	"\x03url\x18\x02 \x01(\tR\x03url\x12\x14\n" +
@@ -2756,22 +9718,74 @@ const file_browserd_proto_rawDesc = "" +
 	"\fdom_snapshot\x18\x05 \x01(\fR\vdomSnapshot\x12-\n" +
 	"\x12accessibility_tree\x18\x06 \x01(\fR\x11accessibilityTree\x12:\n" +
 	"\bhit_test\x18\a \x01(\v2\x1f.buckley.browserd.v1.HitTestMapR\ahitTest\x128\n" +
-	"\ttimestamp\x18\b \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\"\xe2\x01\n" +
+	"\ttimestamp\x18\b \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\x129\n" +
+	"\ttext_runs\x18\t \x03(\v2\x1c.buckley.browserd.v1.TextRunR\btextRuns\x12@\n" +
+	"\vengine_info\x18\n" +
+	" \x01(\v2\x1f.buckley.browserd.v1.EngineInfoR\n" +
+	"engineInfo\x12\x1f\n" +
+	"\vhttp_status\x18\v \x01(\rR\n" +
+	"httpStatus\x12&\n" +
+	"\x0ffocused_node_id\x18\f \x01(\x04R\rfocusedNodeId\x12'\n" +
+	"\x0frender_complete\x18\r \x01(\bR\x0erenderComplete\x12\x1f\n" +
+	"\vpaint_epoch\x18\x0e \x01(\x04R\n" +
+	"paintEpoch\x12L\n" +
+	"\x0fcomputed_styles\x18\x0f \x01(\v2#.buckley.browserd.v1.ComputedStylesR\x0ecomputedStyles\x12R\n" +
+	"\x11navigation_timing\x18\x10 \x01(\v2%.buckley.browserd.v1.NavigationTimingR\x10navigationTiming\x12N\n" +
+	"\x10console_messages\x18\x11 \x03(\v2#.buckley.browserd.v1.ConsoleMessageR\x0fconsoleMessages\x12?\n" +
+	"\vpage_errors\x18\x12 \x03(\v2\x1e.buckley.browserd.v1.PageErrorR\n" +
+	"pageErrors\x12!\n" +
+	"\fnetwork_idle\x18\x13 \x01(\bR\vnetworkIdle\x12\x12\n" +
+	"\x04html\x18\x14 \x01(\tR\x04html\x12%\n" +
+	"\x0ehtml_truncated\x18\x15 \x01(\bR\rhtmlTruncated\x12F\n" +
+	"\rpage_metadata\x18\x16 \x01(\v2!.buckley.browserd.v1.PageMetadataR\fpageMetadata\x12*\n" +
+	"\x11dom_snapshot_hash\x18\x17 \x01(\tR\x0fdomSnapshotHash\x126\n" +
+	"\x17accessibility_tree_hash\x18\x18 \x01(\tR\x15accessibilityTreeHash\"\xac\x01\n" +
+	"\fPageMetadata\x12\x1f\n" +
+	"\vfavicon_url\x18\x01 \x01(\tR\n" +
+	"faviconUrl\x12 \n" +
+	"\vdescription\x18\x02 \x01(\tR\vdescription\x12\x19\n" +
+	"\bog_title\x18\x03 \x01(\tR\aogTitle\x12\x19\n" +
+	"\bog_image\x18\x04 \x01(\tR\aogImage\x12#\n" +
+	"\rcanonical_url\x18\x05 \x01(\tR\fcanonicalUrl\"t\n" +
+	"\x0eConsoleMessage\x12\x14\n" +
+	"\x05level\x18\x01 \x01(\tR\x05level\x12\x12\n" +
+	"\x04text\x18\x02 \x01(\tR\x04text\x128\n" +
+	"\ttimestamp\x18\x03 \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\"i\n" +
+	"\tPageError\x12\x18\n" +
+	"\amessage\x18\x01 \x01(\tR\amessage\x12\x16\n" +
+	"\x06source\x18\x02 \x01(\tR\x06source\x12\x12\n" +
+	"\x04line\x18\x03 \x01(\rR\x04line\x12\x16\n" +
+	"\x06column\x18\x04 \x01(\rR\x06column\"i\n" +
+	"\aTextRun\x12\x12\n" +
+	"\x04text\x18\x01 \x01(\tR\x04text\x121\n" +
+	"\x06bounds\x18\x02 \x01(\v2\x19.buckley.browserd.v1.RectR\x06bounds\x12\x17\n" +
+	"\anode_id\x18\x03 \x01(\x04R\x06nodeId\"\xca\x03\n" +
 	"\x05Frame\x12#\n" +
 	"\rstate_version\x18\x01 \x01(\x04R\fstateVersion\x12\x14\n" +
 	"\x05width\x18\x02 \x01(\rR\x05width\x12\x16\n" +
 	"\x06height\x18\x03 \x01(\rR\x06height\x128\n" +
 	"\x06format\x18\x04 \x01(\x0e2 .buckley.browserd.v1.FrameFormatR\x06format\x12\x12\n" +
 	"\x04data\x18\x05 \x01(\fR\x04data\x128\n" +
-	"\ttimestamp\x18\x06 \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\"t\n" +
+	"\ttimestamp\x18\x06 \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\x12Q\n" +
+	"\x16presentation_timestamp\x18\a \x01(\v2\x1a.google.protobuf.TimestampR\x15presentationTimestamp\x12.\n" +
+	"\x13capture_duration_us\x18\b \x01(\x04R\x11captureDurationUs\x12\x19\n" +
+	"\bis_delta\x18\t \x01(\bR\aisDelta\x12\x17\n" +
+	"\adirty_x\x18\n" +
+	" \x01(\rR\x06dirtyX\x12\x17\n" +
+	"\adirty_y\x18\v \x01(\rR\x06dirtyY\x12\x16\n" +
+	"\x06scaled\x18\f \x01(\bR\x06scaled\"t\n" +
 	"\n" +
 	"HitTestMap\x12\x14\n" +
 	"\x05width\x18\x01 \x01(\rR\x05width\x12\x16\n" +
 	"\x06height\x18\x02 \x01(\rR\x06height\x128\n" +
-	"\aregions\x18\x03 \x03(\v2\x1e.buckley.browserd.v1.HitRegionR\aregions\"W\n" +
+	"\aregions\x18\x03 \x03(\v2\x1e.buckley.browserd.v1.HitRegionR\aregions\"\xaf\x01\n" +
 	"\tHitRegion\x12\x17\n" +
 	"\anode_id\x18\x01 \x01(\x04R\x06nodeId\x121\n" +
-	"\x06bounds\x18\x02 \x01(\v2\x19.buckley.browserd.v1.RectR\x06bounds\"P\n" +
+	"\x06bounds\x18\x02 \x01(\v2\x19.buckley.browserd.v1.RectR\x06bounds\x12\x10\n" +
+	"\x03tag\x18\x03 \x01(\tR\x03tag\x12\x12\n" +
+	"\x04role\x18\x04 \x01(\tR\x04role\x12\x14\n" +
+	"\x05label\x18\x05 \x01(\tR\x05label\x12\x1a\n" +
+	"\boccluded\x18\x06 \x01(\bR\boccluded\"P\n" +
 	"\x04Rect\x12\f\n" +
 	"\x01x\x18\x01 \x01(\x05R\x01x\x12\f\n" +
 	"\x01y\x18\x02 \x01(\x05R\x01y\x12\x14\n" +
@@ -2779,7 +9793,7 @@ const file_browserd_proto_rawDesc = "" +
 	"\x06height\x18\x04 \x01(\x05R\x06height\"#\n" +
 	"\x05Point\x12\f\n" +
 	"\x01x\x18\x01 \x01(\x05R\x01x\x12\f\n" +
-	"\x01y\x18\x02 \x01(\x05R\x01y\"\xce\x02\n" +
+	"\x01y\x18\x02 \x01(\x05R\x01y\"\xb8\x05\n" +
 	"\x06Action\x123\n" +
 	"\x04type\x18\x01 \x01(\x0e2\x1f.buckley.browserd.v1.ActionTypeR\x04type\x124\n" +
 	"\x16expected_state_version\x18\x02 \x01(\x04R\x14expectedStateVersion\x129\n" +
@@ -2787,22 +9801,41 @@ const file_browserd_proto_rawDesc = "" +
 	"\x04text\x18\x04 \x01(\tR\x04text\x12\x10\n" +
 	"\x03key\x18\x05 \x01(\tR\x03key\x128\n" +
 	"\x06scroll\x18\x06 \x01(\v2 .buckley.browserd.v1.ScrollDeltaR\x06scroll\x12>\n" +
-	"\tmodifiers\x18\a \x03(\x0e2 .buckley.browserd.v1.KeyModifierR\tmodifiers\"Y\n" +
+	"\tmodifiers\x18\a \x03(\x0e2 .buckley.browserd.v1.KeyModifierR\tmodifiers\x12:\n" +
+	"\adrag_to\x18\b \x01(\v2!.buckley.browserd.v1.ActionTargetR\x06dragTo\x12\x1d\n" +
+	"\n" +
+	"drag_steps\x18\t \x01(\rR\tdragSteps\x128\n" +
+	"\x06button\x18\n" +
+	" \x01(\x0e2 .buckley.browserd.v1.MouseButtonR\x06button\x12\x1f\n" +
+	"\vclick_count\x18\v \x01(\rR\n" +
+	"clickCount\x12\x16\n" +
+	"\x06repeat\x18\f \x01(\rR\x06repeat\x12*\n" +
+	"\x11action_timeout_ms\x18\r \x01(\rR\x0factionTimeoutMs\x12\x18\n" +
+	"\achecked\x18\x0e \x01(\bR\achecked\x12\x17\n" +
+	"\adry_run\x18\x0f \x01(\bR\x06dryRun\x12;\n" +
+	"\bon_stale\x18\x10 \x01(\x0e2 .buckley.browserd.v1.StalePolicyR\aonStale\"\x81\x02\n" +
 	"\fActionTarget\x12\x17\n" +
 	"\anode_id\x18\x01 \x01(\x04R\x06nodeId\x120\n" +
-	"\x05point\x18\x02 \x01(\v2\x1a.buckley.browserd.v1.PointR\x05point\"^\n" +
+	"\x05point\x18\x02 \x01(\v2\x1a.buckley.browserd.v1.PointR\x05point\x12\x1a\n" +
+	"\bselector\x18\x03 \x01(\tR\bselector\x12F\n" +
+	"\rselector_type\x18\x04 \x01(\x0e2!.buckley.browserd.v1.SelectorTypeR\fselectorType\x12-\n" +
+	"\x10scroll_into_view\x18\x05 \x01(\bH\x00R\x0escrollIntoView\x88\x01\x01B\x13\n" +
+	"\x11_scroll_into_view\"^\n" +
 	"\vScrollDelta\x12\f\n" +
 	"\x01x\x18\x01 \x01(\x05R\x01x\x12\f\n" +
 	"\x01y\x18\x02 \x01(\x05R\x01y\x123\n" +
-	"\x04unit\x18\x03 \x01(\x0e2\x1f.buckley.browserd.v1.ScrollUnitR\x04unit\"\xae\x01\n" +
+	"\x04unit\x18\x03 \x01(\x0e2\x1f.buckley.browserd.v1.ScrollUnitR\x04unit\"\xc8\x02\n" +
 	"\fActionResult\x12#\n" +
 	"\rstate_version\x18\x01 \x01(\x04R\fstateVersion\x12B\n" +
 	"\vobservation\x18\x02 \x01(\v2 .buckley.browserd.v1.ObservationR\vobservation\x125\n" +
-	"\aeffects\x18\x03 \x03(\v2\x1b.buckley.browserd.v1.EffectR\aeffects\"k\n" +
+	"\aeffects\x18\x03 \x03(\v2\x1b.buckley.browserd.v1.EffectR\aeffects\x12(\n" +
+	"\x10resolved_node_id\x18\x04 \x01(\x04R\x0eresolvedNodeId\x12A\n" +
+	"\x0eresolved_point\x18\x05 \x01(\v2\x1a.buckley.browserd.v1.PointR\rresolvedPoint\x12+\n" +
+	"\x11caused_navigation\x18\x06 \x01(\bR\x10causedNavigation\"k\n" +
 	"\x06Effect\x12\x12\n" +
 	"\x04kind\x18\x01 \x01(\tR\x04kind\x12\x18\n" +
 	"\asummary\x18\x02 \x01(\tR\asummary\x123\n" +
-	"\bmetadata\x18\x03 \x01(\v2\x17.google.protobuf.StructR\bmetadata\"\xde\x02\n" +
+	"\bmetadata\x18\x03 \x01(\v2\x17.google.protobuf.StructR\bmetadata\"\xa5\x04\n" +
 	"\vStreamEvent\x128\n" +
 	"\x04type\x18\x01 \x01(\x0e2$.buckley.browserd.v1.StreamEventTypeR\x04type\x12#\n" +
 	"\rstate_version\x18\x02 \x01(\x04R\fstateVersion\x120\n" +
@@ -2810,21 +9843,92 @@ const file_browserd_proto_rawDesc = "" +
 	"\bdom_diff\x18\x04 \x01(\fR\adomDiff\x12-\n" +
 	"\x12accessibility_diff\x18\x05 \x01(\fR\x11accessibilityDiff\x12:\n" +
 	"\bhit_test\x18\x06 \x01(\v2\x1f.buckley.browserd.v1.HitTestMapR\ahitTest\x128\n" +
-	"\ttimestamp\x18\a \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp*d\n" +
+	"\ttimestamp\x18\a \x01(\v2\x1a.google.protobuf.TimestampR\ttimestamp\x126\n" +
+	"\x05stats\x18\b \x01(\v2 .buckley.browserd.v1.StreamStatsR\x05stats\x12N\n" +
+	"\x10console_messages\x18\t \x03(\v2#.buckley.browserd.v1.ConsoleMessageR\x0fconsoleMessages\x12\x1a\n" +
+	"\bsequence\x18\n" +
+	" \x01(\x04R\bsequence\x12!\n" +
+	"\fcontent_hash\x18\v \x01(\tR\vcontentHash\"U\n" +
+	"\vStreamStats\x12\x1f\n" +
+	"\vevents_sent\x18\x01 \x01(\x04R\n" +
+	"eventsSent\x12%\n" +
+	"\x0eevents_dropped\x18\x02 \x01(\x04R\reventsDropped*\xbd\a\n" +
+	"\tErrorCode\x12\x1a\n" +
+	"\x16ERROR_CODE_UNSPECIFIED\x10\x00\x12\x1e\n" +
+	"\x1aERROR_CODE_INVALID_REQUEST\x10\x01\x12\x1e\n" +
+	"\x1aERROR_CODE_INVALID_SESSION\x10\x02\x12\x1a\n" +
+	"\x16ERROR_CODE_STALE_STATE\x10\x03\x12\x1b\n" +
+	"\x17ERROR_CODE_LOAD_TIMEOUT\x10\x04\x12\x1b\n" +
+	"\x17ERROR_CODE_SCRIPT_ERROR\x10\x05\x12\x1d\n" +
+	"\x19ERROR_CODE_SCRIPT_TIMEOUT\x10\x06\x12\x1a\n" +
+	"\x16ERROR_CODE_UNAVAILABLE\x10\a\x12\x1a\n" +
+	"\x16ERROR_CODE_INVALID_URL\x10\b\x12\x1d\n" +
+	"\x19ERROR_CODE_INVALID_TARGET\x10\t\x12\x19\n" +
+	"\x15ERROR_CODE_NO_WEBVIEW\x10\n" +
+	"\x12\x1c\n" +
+	"\x18ERROR_CODE_RENDER_FAILED\x10\v\x12\x1d\n" +
+	"\x19ERROR_CODE_RENDERING_INIT\x10\f\x12\x1f\n" +
+	"\x1bERROR_CODE_CLIPBOARD_DENIED\x10\r\x12\x1e\n" +
+	"\x1aERROR_CODE_CLIPBOARD_LIMIT\x10\x0e\x12\x1c\n" +
+	"\x18ERROR_CODE_COOKIE_DENIED\x10\x0f\x12\x1b\n" +
+	"\x17ERROR_CODE_WAIT_TIMEOUT\x10\x10\x12\x1c\n" +
+	"\x18ERROR_CODE_SESSION_LIMIT\x10\x11\x12\x1a\n" +
+	"\x16ERROR_CODE_UNSUPPORTED\x10\x12\x12\x1b\n" +
+	"\x17ERROR_CODE_RATE_LIMITED\x10\x13\x12%\n" +
+	"!ERROR_CODE_SCRIPT_BUDGET_EXCEEDED\x10\x14\x12\x1a\n" +
+	"\x16ERROR_CODE_LOAD_FAILED\x10\x15\x12\x1e\n" +
+	"\x1aERROR_CODE_UNAUTHENTICATED\x10\x16\x12\x1a\n" +
+	"\x16ERROR_CODE_HOST_DENIED\x10\x17\x12\x1d\n" +
+	"\x19ERROR_CODE_ACTION_TIMEOUT\x10\x18\x12\x1c\n" +
+	"\x18ERROR_CODE_TAB_NOT_FOUND\x10\x19\x12\x1f\n" +
+	"\x1bERROR_CODE_UPLOADS_DISABLED\x10\x1a\x12!\n" +
+	"\x1dERROR_CODE_DOWNLOAD_NOT_FOUND\x10\x1b\x12\x1d\n" +
+	"\x19ERROR_CODE_STORAGE_DENIED\x10\x1c\x12\x1b\n" +
+	"\x17ERROR_CODE_ENGINE_PANIC\x10\x1d\x12!\n" +
+	"\x1dERROR_CODE_RESPONSE_TOO_LARGE\x10\x1e*a\n" +
+	"\fStorageScope\x12\x1d\n" +
+	"\x19STORAGE_SCOPE_UNSPECIFIED\x10\x00\x12\x17\n" +
+	"\x13STORAGE_SCOPE_LOCAL\x10\x01\x12\x19\n" +
+	"\x15STORAGE_SCOPE_SESSION\x10\x02*\x9f\x01\n" +
+	"\bLogLevel\x12\x19\n" +
+	"\x15LOG_LEVEL_UNSPECIFIED\x10\x00\x12\x11\n" +
+	"\rLOG_LEVEL_OFF\x10\x01\x12\x13\n" +
+	"\x0fLOG_LEVEL_ERROR\x10\x02\x12\x12\n" +
+	"\x0eLOG_LEVEL_WARN\x10\x03\x12\x12\n" +
+	"\x0eLOG_LEVEL_INFO\x10\x04\x12\x13\n" +
+	"\x0fLOG_LEVEL_DEBUG\x10\x05\x12\x13\n" +
+	"\x0fLOG_LEVEL_TRACE\x10\x06*d\n" +
 	"\rClipboardMode\x12\x1e\n" +
 	"\x1aCLIPBOARD_MODE_UNSPECIFIED\x10\x00\x12\x1a\n" +
 	"\x16CLIPBOARD_MODE_VIRTUAL\x10\x01\x12\x17\n" +
-	"\x13CLIPBOARD_MODE_HOST\x10\x02*o\n" +
+	"\x13CLIPBOARD_MODE_HOST\x10\x02*j\n" +
+	"\x11StreamCompression\x12\x1b\n" +
+	"\x17STREAM_COMPRESSION_NONE\x10\x00\x12\x1b\n" +
+	"\x17STREAM_COMPRESSION_GZIP\x10\x01\x12\x1b\n" +
+	"\x17STREAM_COMPRESSION_ZSTD\x10\x02*o\n" +
 	"\vFrameFormat\x12\x1c\n" +
 	"\x18FRAME_FORMAT_UNSPECIFIED\x10\x00\x12\x14\n" +
 	"\x10FRAME_FORMAT_PNG\x10\x01\x12\x15\n" +
 	"\x11FRAME_FORMAT_JPEG\x10\x02\x12\x15\n" +
-	"\x11FRAME_FORMAT_WEBP\x10\x03*X\n" +
+	"\x11FRAME_FORMAT_WEBP\x10\x03*Z\n" +
+	"\vStalePolicy\x12\x1c\n" +
+	"\x18STALE_POLICY_UNSPECIFIED\x10\x00\x12\x15\n" +
+	"\x11STALE_POLICY_FAIL\x10\x01\x12\x16\n" +
+	"\x12STALE_POLICY_RETRY\x10\x02*s\n" +
+	"\vMouseButton\x12\x1c\n" +
+	"\x18MOUSE_BUTTON_UNSPECIFIED\x10\x00\x12\x15\n" +
+	"\x11MOUSE_BUTTON_LEFT\x10\x01\x12\x17\n" +
+	"\x13MOUSE_BUTTON_MIDDLE\x10\x02\x12\x16\n" +
+	"\x12MOUSE_BUTTON_RIGHT\x10\x03*]\n" +
+	"\fSelectorType\x12\x1d\n" +
+	"\x19SELECTOR_TYPE_UNSPECIFIED\x10\x00\x12\x15\n" +
+	"\x11SELECTOR_TYPE_CSS\x10\x01\x12\x17\n" +
+	"\x13SELECTOR_TYPE_XPATH\x10\x02*X\n" +
 	"\n" +
 	"ScrollUnit\x12\x1b\n" +
 	"\x17SCROLL_UNIT_UNSPECIFIED\x10\x00\x12\x16\n" +
 	"\x12SCROLL_UNIT_PIXELS\x10\x01\x12\x15\n" +
-	"\x11SCROLL_UNIT_LINES\x10\x02*\xf2\x01\n" +
+	"\x11SCROLL_UNIT_LINES\x10\x02*\xf4\x02\n" +
 	"\n" +
 	"ActionType\x12\x1b\n" +
 	"\x17ACTION_TYPE_UNSPECIFIED\x10\x00\x12\x15\n" +
@@ -2835,19 +9939,28 @@ const file_browserd_proto_rawDesc = "" +
 	"\x0fACTION_TYPE_KEY\x10\x05\x12\x15\n" +
 	"\x11ACTION_TYPE_FOCUS\x10\x06\x12\x1e\n" +
 	"\x1aACTION_TYPE_CLIPBOARD_READ\x10\a\x12\x1f\n" +
-	"\x1bACTION_TYPE_CLIPBOARD_WRITE\x10\b*\x87\x01\n" +
+	"\x1bACTION_TYPE_CLIPBOARD_WRITE\x10\b\x12\x14\n" +
+	"\x10ACTION_TYPE_DRAG\x10\t\x12\x13\n" +
+	"\x0fACTION_TYPE_TAB\x10\n" +
+	"\x12\x1d\n" +
+	"\x19ACTION_TYPE_SELECT_OPTION\x10\v\x12\x1b\n" +
+	"\x17ACTION_TYPE_SET_CHECKED\x10\f\x12\x19\n" +
+	"\x15ACTION_TYPE_TYPE_INTO\x10\r*\x87\x01\n" +
 	"\vKeyModifier\x12\x1c\n" +
 	"\x18KEY_MODIFIER_UNSPECIFIED\x10\x00\x12\x16\n" +
 	"\x12KEY_MODIFIER_SHIFT\x10\x01\x12\x14\n" +
 	"\x10KEY_MODIFIER_ALT\x10\x02\x12\x15\n" +
 	"\x11KEY_MODIFIER_CTRL\x10\x03\x12\x15\n" +
-	"\x11KEY_MODIFIER_META\x10\x04*\xbb\x01\n" +
+	"\x11KEY_MODIFIER_META\x10\x04*\x98\x02\n" +
 	"\x0fStreamEventType\x12!\n" +
 	"\x1dSTREAM_EVENT_TYPE_UNSPECIFIED\x10\x00\x12\x1b\n" +
 	"\x17STREAM_EVENT_TYPE_FRAME\x10\x01\x12\x1e\n" +
 	"\x1aSTREAM_EVENT_TYPE_DOM_DIFF\x10\x02\x12(\n" +
 	"$STREAM_EVENT_TYPE_ACCESSIBILITY_DIFF\x10\x03\x12\x1e\n" +
-	"\x1aSTREAM_EVENT_TYPE_HIT_TEST\x10\x04BJZHgithub.com/odvcencio/buckley/pkg/browser/adapters/servo/proto;browserdpbb\x06proto3"
+	"\x1aSTREAM_EVENT_TYPE_HIT_TEST\x10\x04\x12\x1b\n" +
+	"\x17STREAM_EVENT_TYPE_STATS\x10\x05\x12\x1d\n" +
+	"\x19STREAM_EVENT_TYPE_CONSOLE\x10\x06\x12\x1f\n" +
+	"\x1bSTREAM_EVENT_TYPE_HEARTBEAT\x10\aBJZHgithub.com/odvcencio/buckley/pkg/browser/adapters/servo/proto;browserdpbb\x06proto3"
 
 var (
 	file_browserd_proto_rawDescOnce sync.Once
@@ -2861,106 +9974,325 @@ func file_browserd_proto_rawDescGZIP() []byte {
 	return file_browserd_proto_rawDescData
 }
 
-var file_browserd_proto_enumTypes = make([]protoimpl.EnumInfo, 6)
-var file_browserd_proto_msgTypes = make([]protoimpl.MessageInfo, 34)
+var file_browserd_proto_enumTypes = make([]protoimpl.EnumInfo, 13)
+var file_browserd_proto_msgTypes = make([]protoimpl.MessageInfo, 121)
 var file_browserd_proto_goTypes = []any{
-	(ClipboardMode)(0),              // 0: buckley.browserd.v1.ClipboardMode
-	(FrameFormat)(0),                // 1: buckley.browserd.v1.FrameFormat
-	(ScrollUnit)(0),                 // 2: buckley.browserd.v1.ScrollUnit
-	(ActionType)(0),                 // 3: buckley.browserd.v1.ActionType
-	(KeyModifier)(0),                // 4: buckley.browserd.v1.KeyModifier
-	(StreamEventType)(0),            // 5: buckley.browserd.v1.StreamEventType
-	(*Envelope)(nil),                // 6: buckley.browserd.v1.Envelope
-	(*Error)(nil),                   // 7: buckley.browserd.v1.Error
-	(*Request)(nil),                 // 8: buckley.browserd.v1.Request
-	(*Response)(nil),                // 9: buckley.browserd.v1.Response
-	(*CreateSessionRequest)(nil),    // 10: buckley.browserd.v1.CreateSessionRequest
-	(*CreateSessionResponse)(nil),   // 11: buckley.browserd.v1.CreateSessionResponse
-	(*NavigateRequest)(nil),         // 12: buckley.browserd.v1.NavigateRequest
-	(*NavigateResponse)(nil),        // 13: buckley.browserd.v1.NavigateResponse
-	(*ObserveRequest)(nil),          // 14: buckley.browserd.v1.ObserveRequest
-	(*ObserveResponse)(nil),         // 15: buckley.browserd.v1.ObserveResponse
-	(*ActRequest)(nil),              // 16: buckley.browserd.v1.ActRequest
-	(*ActResponse)(nil),             // 17: buckley.browserd.v1.ActResponse
-	(*CloseSessionRequest)(nil),     // 18: buckley.browserd.v1.CloseSessionRequest
-	(*CloseSessionResponse)(nil),    // 19: buckley.browserd.v1.CloseSessionResponse
-	(*StreamSubscribeRequest)(nil),  // 20: buckley.browserd.v1.StreamSubscribeRequest
-	(*StreamSubscribeResponse)(nil), // 21: buckley.browserd.v1.StreamSubscribeResponse
-	(*SessionInfo)(nil),             // 22: buckley.browserd.v1.SessionInfo
-	(*SessionConfig)(nil),           // 23: buckley.browserd.v1.SessionConfig
-	(*Viewport)(nil),                // 24: buckley.browserd.v1.Viewport
-	(*ClipboardPolicy)(nil),         // 25: buckley.browserd.v1.ClipboardPolicy
-	(*ObserveOptions)(nil),          // 26: buckley.browserd.v1.ObserveOptions
-	(*StreamOptions)(nil),           // 27: buckley.browserd.v1.StreamOptions
-	(*Observation)(nil),             // 28: buckley.browserd.v1.Observation
-	(*Frame)(nil),                   // 29: buckley.browserd.v1.Frame
-	(*HitTestMap)(nil),              // 30: buckley.browserd.v1.HitTestMap
-	(*HitRegion)(nil),               // 31: buckley.browserd.v1.HitRegion
-	(*Rect)(nil),                    // 32: buckley.browserd.v1.Rect
-	(*Point)(nil),                   // 33: buckley.browserd.v1.Point
-	(*Action)(nil),                  // 34: buckley.browserd.v1.Action
-	(*ActionTarget)(nil),            // 35: buckley.browserd.v1.ActionTarget
-	(*ScrollDelta)(nil),             // 36: buckley.browserd.v1.ScrollDelta
-	(*ActionResult)(nil),            // 37: buckley.browserd.v1.ActionResult
-	(*Effect)(nil),                  // 38: buckley.browserd.v1.Effect
-	(*StreamEvent)(nil),             // 39: buckley.browserd.v1.StreamEvent
-	(*timestamppb.Timestamp)(nil),   // 40: google.protobuf.Timestamp
-	(*structpb.Struct)(nil),         // 41: google.protobuf.Struct
+	(ErrorCode)(0),                    // 0: buckley.browserd.v1.ErrorCode
+	(StorageScope)(0),                 // 1: buckley.browserd.v1.StorageScope
+	(LogLevel)(0),                     // 2: buckley.browserd.v1.LogLevel
+	(ClipboardMode)(0),                // 3: buckley.browserd.v1.ClipboardMode
+	(StreamCompression)(0),            // 4: buckley.browserd.v1.StreamCompression
+	(FrameFormat)(0),                  // 5: buckley.browserd.v1.FrameFormat
+	(StalePolicy)(0),                  // 6: buckley.browserd.v1.StalePolicy
+	(MouseButton)(0),                  // 7: buckley.browserd.v1.MouseButton
+	(SelectorType)(0),                 // 8: buckley.browserd.v1.SelectorType
+	(ScrollUnit)(0),                   // 9: buckley.browserd.v1.ScrollUnit
+	(ActionType)(0),                   // 10: buckley.browserd.v1.ActionType
+	(KeyModifier)(0),                  // 11: buckley.browserd.v1.KeyModifier
+	(StreamEventType)(0),              // 12: buckley.browserd.v1.StreamEventType
+	(*Envelope)(nil),                  // 13: buckley.browserd.v1.Envelope
+	(*Error)(nil),                     // 14: buckley.browserd.v1.Error
+	(*Request)(nil),                   // 15: buckley.browserd.v1.Request
+	(*Response)(nil),                  // 16: buckley.browserd.v1.Response
+	(*CreateSessionRequest)(nil),      // 17: buckley.browserd.v1.CreateSessionRequest
+	(*CreateSessionResponse)(nil),     // 18: buckley.browserd.v1.CreateSessionResponse
+	(*NavigateRequest)(nil),           // 19: buckley.browserd.v1.NavigateRequest
+	(*NavigateResponse)(nil),          // 20: buckley.browserd.v1.NavigateResponse
+	(*StopRequest)(nil),               // 21: buckley.browserd.v1.StopRequest
+	(*StopResponse)(nil),              // 22: buckley.browserd.v1.StopResponse
+	(*ObserveRequest)(nil),            // 23: buckley.browserd.v1.ObserveRequest
+	(*ObserveResponse)(nil),           // 24: buckley.browserd.v1.ObserveResponse
+	(*ActRequest)(nil),                // 25: buckley.browserd.v1.ActRequest
+	(*ActResponse)(nil),               // 26: buckley.browserd.v1.ActResponse
+	(*BatchActionsRequest)(nil),       // 27: buckley.browserd.v1.BatchActionsRequest
+	(*BatchActionsResponse)(nil),      // 28: buckley.browserd.v1.BatchActionsResponse
+	(*CloseSessionRequest)(nil),       // 29: buckley.browserd.v1.CloseSessionRequest
+	(*CloseSessionResponse)(nil),      // 30: buckley.browserd.v1.CloseSessionResponse
+	(*StreamSubscribeRequest)(nil),    // 31: buckley.browserd.v1.StreamSubscribeRequest
+	(*StreamSubscribeResponse)(nil),   // 32: buckley.browserd.v1.StreamSubscribeResponse
+	(*SessionStatsRequest)(nil),       // 33: buckley.browserd.v1.SessionStatsRequest
+	(*SessionStatsResponse)(nil),      // 34: buckley.browserd.v1.SessionStatsResponse
+	(*ListSessionsRequest)(nil),       // 35: buckley.browserd.v1.ListSessionsRequest
+	(*ListSessionsResponse)(nil),      // 36: buckley.browserd.v1.ListSessionsResponse
+	(*CheckContrastRequest)(nil),      // 37: buckley.browserd.v1.CheckContrastRequest
+	(*CheckContrastResponse)(nil),     // 38: buckley.browserd.v1.CheckContrastResponse
+	(*ContrastResult)(nil),            // 39: buckley.browserd.v1.ContrastResult
+	(*GetInfoRequest)(nil),            // 40: buckley.browserd.v1.GetInfoRequest
+	(*GetInfoResponse)(nil),           // 41: buckley.browserd.v1.GetInfoResponse
+	(*EngineInfo)(nil),                // 42: buckley.browserd.v1.EngineInfo
+	(*ExportPdfRequest)(nil),          // 43: buckley.browserd.v1.ExportPdfRequest
+	(*ExportPdfResponse)(nil),         // 44: buckley.browserd.v1.ExportPdfResponse
+	(*Cookie)(nil),                    // 45: buckley.browserd.v1.Cookie
+	(*GetCookiesRequest)(nil),         // 46: buckley.browserd.v1.GetCookiesRequest
+	(*GetCookiesResponse)(nil),        // 47: buckley.browserd.v1.GetCookiesResponse
+	(*SetCookieRequest)(nil),          // 48: buckley.browserd.v1.SetCookieRequest
+	(*SetCookieResponse)(nil),         // 49: buckley.browserd.v1.SetCookieResponse
+	(*ClearCookiesRequest)(nil),       // 50: buckley.browserd.v1.ClearCookiesRequest
+	(*ClearCookiesResponse)(nil),      // 51: buckley.browserd.v1.ClearCookiesResponse
+	(*StorageEntry)(nil),              // 52: buckley.browserd.v1.StorageEntry
+	(*SetStorageRequest)(nil),         // 53: buckley.browserd.v1.SetStorageRequest
+	(*SetStorageResponse)(nil),        // 54: buckley.browserd.v1.SetStorageResponse
+	(*GetStorageRequest)(nil),         // 55: buckley.browserd.v1.GetStorageRequest
+	(*GetStorageResponse)(nil),        // 56: buckley.browserd.v1.GetStorageResponse
+	(*ClearStorageRequest)(nil),       // 57: buckley.browserd.v1.ClearStorageRequest
+	(*ClearStorageResponse)(nil),      // 58: buckley.browserd.v1.ClearStorageResponse
+	(*ClearBrowsingDataRequest)(nil),  // 59: buckley.browserd.v1.ClearBrowsingDataRequest
+	(*ClearBrowsingDataResponse)(nil), // 60: buckley.browserd.v1.ClearBrowsingDataResponse
+	(*WaitForRequest)(nil),            // 61: buckley.browserd.v1.WaitForRequest
+	(*WaitForResponse)(nil),           // 62: buckley.browserd.v1.WaitForResponse
+	(*ShutdownRequest)(nil),           // 63: buckley.browserd.v1.ShutdownRequest
+	(*ShutdownResponse)(nil),          // 64: buckley.browserd.v1.ShutdownResponse
+	(*QueryElementRequest)(nil),       // 65: buckley.browserd.v1.QueryElementRequest
+	(*QueryElementResponse)(nil),      // 66: buckley.browserd.v1.QueryElementResponse
+	(*SetLogLevelRequest)(nil),        // 67: buckley.browserd.v1.SetLogLevelRequest
+	(*SetLogLevelResponse)(nil),       // 68: buckley.browserd.v1.SetLogLevelResponse
+	(*AuthenticateRequest)(nil),       // 69: buckley.browserd.v1.AuthenticateRequest
+	(*AuthenticateResponse)(nil),      // 70: buckley.browserd.v1.AuthenticateResponse
+	(*Tab)(nil),                       // 71: buckley.browserd.v1.Tab
+	(*OpenTabRequest)(nil),            // 72: buckley.browserd.v1.OpenTabRequest
+	(*OpenTabResponse)(nil),           // 73: buckley.browserd.v1.OpenTabResponse
+	(*CloseTabRequest)(nil),           // 74: buckley.browserd.v1.CloseTabRequest
+	(*CloseTabResponse)(nil),          // 75: buckley.browserd.v1.CloseTabResponse
+	(*SwitchTabRequest)(nil),          // 76: buckley.browserd.v1.SwitchTabRequest
+	(*SwitchTabResponse)(nil),         // 77: buckley.browserd.v1.SwitchTabResponse
+	(*ListTabsRequest)(nil),           // 78: buckley.browserd.v1.ListTabsRequest
+	(*ListTabsResponse)(nil),          // 79: buckley.browserd.v1.ListTabsResponse
+	(*ExtractTextRequest)(nil),        // 80: buckley.browserd.v1.ExtractTextRequest
+	(*ExtractTextResponse)(nil),       // 81: buckley.browserd.v1.ExtractTextResponse
+	(*FillFormRequest)(nil),           // 82: buckley.browserd.v1.FillFormRequest
+	(*FormField)(nil),                 // 83: buckley.browserd.v1.FormField
+	(*FillFormResponse)(nil),          // 84: buckley.browserd.v1.FillFormResponse
+	(*FieldResult)(nil),               // 85: buckley.browserd.v1.FieldResult
+	(*SetFileInputRequest)(nil),       // 86: buckley.browserd.v1.SetFileInputRequest
+	(*SetFileInputResponse)(nil),      // 87: buckley.browserd.v1.SetFileInputResponse
+	(*Download)(nil),                  // 88: buckley.browserd.v1.Download
+	(*ListDownloadsRequest)(nil),      // 89: buckley.browserd.v1.ListDownloadsRequest
+	(*ListDownloadsResponse)(nil),     // 90: buckley.browserd.v1.ListDownloadsResponse
+	(*GetDownloadRequest)(nil),        // 91: buckley.browserd.v1.GetDownloadRequest
+	(*GetDownloadResponse)(nil),       // 92: buckley.browserd.v1.GetDownloadResponse
+	(*SetViewportRequest)(nil),        // 93: buckley.browserd.v1.SetViewportRequest
+	(*SetViewportResponse)(nil),       // 94: buckley.browserd.v1.SetViewportResponse
+	(*SaveSessionRequest)(nil),        // 95: buckley.browserd.v1.SaveSessionRequest
+	(*SaveSessionResponse)(nil),       // 96: buckley.browserd.v1.SaveSessionResponse
+	(*RestoreSessionRequest)(nil),     // 97: buckley.browserd.v1.RestoreSessionRequest
+	(*RestoreSessionResponse)(nil),    // 98: buckley.browserd.v1.RestoreSessionResponse
+	(*SessionInfo)(nil),               // 99: buckley.browserd.v1.SessionInfo
+	(*SessionConfig)(nil),             // 100: buckley.browserd.v1.SessionConfig
+	(*Viewport)(nil),                  // 101: buckley.browserd.v1.Viewport
+	(*ClipboardData)(nil),             // 102: buckley.browserd.v1.ClipboardData
+	(*ClipboardGetRequest)(nil),       // 103: buckley.browserd.v1.ClipboardGetRequest
+	(*ClipboardGetResponse)(nil),      // 104: buckley.browserd.v1.ClipboardGetResponse
+	(*ClipboardSetRequest)(nil),       // 105: buckley.browserd.v1.ClipboardSetRequest
+	(*ClipboardSetResponse)(nil),      // 106: buckley.browserd.v1.ClipboardSetResponse
+	(*ClipboardPolicy)(nil),           // 107: buckley.browserd.v1.ClipboardPolicy
+	(*ObserveOptions)(nil),            // 108: buckley.browserd.v1.ObserveOptions
+	(*NavigationTiming)(nil),          // 109: buckley.browserd.v1.NavigationTiming
+	(*ComputedStyles)(nil),            // 110: buckley.browserd.v1.ComputedStyles
+	(*NodeStyle)(nil),                 // 111: buckley.browserd.v1.NodeStyle
+	(*StreamOptions)(nil),             // 112: buckley.browserd.v1.StreamOptions
+	(*Observation)(nil),               // 113: buckley.browserd.v1.Observation
+	(*PageMetadata)(nil),              // 114: buckley.browserd.v1.PageMetadata
+	(*ConsoleMessage)(nil),            // 115: buckley.browserd.v1.ConsoleMessage
+	(*PageError)(nil),                 // 116: buckley.browserd.v1.PageError
+	(*TextRun)(nil),                   // 117: buckley.browserd.v1.TextRun
+	(*Frame)(nil),                     // 118: buckley.browserd.v1.Frame
+	(*HitTestMap)(nil),                // 119: buckley.browserd.v1.HitTestMap
+	(*HitRegion)(nil),                 // 120: buckley.browserd.v1.HitRegion
+	(*Rect)(nil),                      // 121: buckley.browserd.v1.Rect
+	(*Point)(nil),                     // 122: buckley.browserd.v1.Point
+	(*Action)(nil),                    // 123: buckley.browserd.v1.Action
+	(*ActionTarget)(nil),              // 124: buckley.browserd.v1.ActionTarget
+	(*ScrollDelta)(nil),               // 125: buckley.browserd.v1.ScrollDelta
+	(*ActionResult)(nil),              // 126: buckley.browserd.v1.ActionResult
+	(*Effect)(nil),                    // 127: buckley.browserd.v1.Effect
+	(*StreamEvent)(nil),               // 128: buckley.browserd.v1.StreamEvent
+	(*StreamStats)(nil),               // 129: buckley.browserd.v1.StreamStats
+	nil,                               // 130: buckley.browserd.v1.QueryElementResponse.PropertiesEntry
+	nil,                               // 131: buckley.browserd.v1.SessionConfig.RequestHeadersEntry
+	nil,                               // 132: buckley.browserd.v1.ComputedStyles.NodesEntry
+	nil,                               // 133: buckley.browserd.v1.NodeStyle.PropertiesEntry
+	(*timestamppb.Timestamp)(nil),     // 134: google.protobuf.Timestamp
+	(*structpb.Struct)(nil),           // 135: google.protobuf.Struct
 }
 var file_browserd_proto_depIdxs = []int32{
-	8,  // 0: buckley.browserd.v1.Envelope.request:type_name -> buckley.browserd.v1.Request
-	9,  // 1: buckley.browserd.v1.Envelope.response:type_name -> buckley.browserd.v1.Response
-	39, // 2: buckley.browserd.v1.Envelope.event:type_name -> buckley.browserd.v1.StreamEvent
-	10, // 3: buckley.browserd.v1.Request.create_session:type_name -> buckley.browserd.v1.CreateSessionRequest
-	12, // 4: buckley.browserd.v1.Request.navigate:type_name -> buckley.browserd.v1.NavigateRequest
-	14, // 5: buckley.browserd.v1.Request.observe:type_name -> buckley.browserd.v1.ObserveRequest
-	16, // 6: buckley.browserd.v1.Request.act:type_name -> buckley.browserd.v1.ActRequest
-	18, // 7: buckley.browserd.v1.Request.close_session:type_name -> buckley.browserd.v1.CloseSessionRequest
-	20, // 8: buckley.browserd.v1.Request.stream_subscribe:type_name -> buckley.browserd.v1.StreamSubscribeRequest
-	7,  // 9: buckley.browserd.v1.Response.error:type_name -> buckley.browserd.v1.Error
-	11, // 10: buckley.browserd.v1.Response.create_session:type_name -> buckley.browserd.v1.CreateSessionResponse
-	13, // 11: buckley.browserd.v1.Response.navigate:type_name -> buckley.browserd.v1.NavigateResponse
-	15, // 12: buckley.browserd.v1.Response.observe:type_name -> buckley.browserd.v1.ObserveResponse
-	17, // 13: buckley.browserd.v1.Response.act:type_name -> buckley.browserd.v1.ActResponse
-	19, // 14: buckley.browserd.v1.Response.close_session:type_name -> buckley.browserd.v1.CloseSessionResponse
-	21, // 15: buckley.browserd.v1.Response.stream_subscribe:type_name -> buckley.browserd.v1.StreamSubscribeResponse
-	23, // 16: buckley.browserd.v1.CreateSessionRequest.config:type_name -> buckley.browserd.v1.SessionConfig
-	22, // 17: buckley.browserd.v1.CreateSessionResponse.session:type_name -> buckley.browserd.v1.SessionInfo
-	28, // 18: buckley.browserd.v1.CreateSessionResponse.observation:type_name -> buckley.browserd.v1.Observation
-	28, // 19: buckley.browserd.v1.NavigateResponse.observation:type_name -> buckley.browserd.v1.Observation
-	26, // 20: buckley.browserd.v1.ObserveRequest.options:type_name -> buckley.browserd.v1.ObserveOptions
-	28, // 21: buckley.browserd.v1.ObserveResponse.observation:type_name -> buckley.browserd.v1.Observation
-	34, // 22: buckley.browserd.v1.ActRequest.action:type_name -> buckley.browserd.v1.Action
-	37, // 23: buckley.browserd.v1.ActResponse.result:type_name -> buckley.browserd.v1.ActionResult
-	27, // 24: buckley.browserd.v1.StreamSubscribeRequest.options:type_name -> buckley.browserd.v1.StreamOptions
-	24, // 25: buckley.browserd.v1.SessionConfig.viewport:type_name -> buckley.browserd.v1.Viewport
-	25, // 26: buckley.browserd.v1.SessionConfig.clipboard:type_name -> buckley.browserd.v1.ClipboardPolicy
-	0,  // 27: buckley.browserd.v1.ClipboardPolicy.mode:type_name -> buckley.browserd.v1.ClipboardMode
-	29, // 28: buckley.browserd.v1.Observation.frame:type_name -> buckley.browserd.v1.Frame
-	30, // 29: buckley.browserd.v1.Observation.hit_test:type_name -> buckley.browserd.v1.HitTestMap
-	40, // 30: buckley.browserd.v1.Observation.timestamp:type_name -> google.protobuf.Timestamp
-	1,  // 31: buckley.browserd.v1.Frame.format:type_name -> buckley.browserd.v1.FrameFormat
-	40, // 32: buckley.browserd.v1.Frame.timestamp:type_name -> google.protobuf.Timestamp
-	31, // 33: buckley.browserd.v1.HitTestMap.regions:type_name -> buckley.browserd.v1.HitRegion
-	32, // 34: buckley.browserd.v1.HitRegion.bounds:type_name -> buckley.browserd.v1.Rect
-	3,  // 35: buckley.browserd.v1.Action.type:type_name -> buckley.browserd.v1.ActionType
-	35, // 36: buckley.browserd.v1.Action.target:type_name -> buckley.browserd.v1.ActionTarget
-	36, // 37: buckley.browserd.v1.Action.scroll:type_name -> buckley.browserd.v1.ScrollDelta
-	4,  // 38: buckley.browserd.v1.Action.modifiers:type_name -> buckley.browserd.v1.KeyModifier
-	33, // 39: buckley.browserd.v1.ActionTarget.point:type_name -> buckley.browserd.v1.Point
-	2,  // 40: buckley.browserd.v1.ScrollDelta.unit:type_name -> buckley.browserd.v1.ScrollUnit
-	28, // 41: buckley.browserd.v1.ActionResult.observation:type_name -> buckley.browserd.v1.Observation
-	38, // 42: buckley.browserd.v1.ActionResult.effects:type_name -> buckley.browserd.v1.Effect
-	41, // 43: buckley.browserd.v1.Effect.metadata:type_name -> google.protobuf.Struct
-	5,  // 44: buckley.browserd.v1.StreamEvent.type:type_name -> buckley.browserd.v1.StreamEventType
-	29, // 45: buckley.browserd.v1.StreamEvent.frame:type_name -> buckley.browserd.v1.Frame
-	30, // 46: buckley.browserd.v1.StreamEvent.hit_test:type_name -> buckley.browserd.v1.HitTestMap
-	40, // 47: buckley.browserd.v1.StreamEvent.timestamp:type_name -> google.protobuf.Timestamp
-	48, // [48:48] is the sub-list for method output_type
-	48, // [48:48] is the sub-list for method input_type
-	48, // [48:48] is the sub-list for extension type_name
-	48, // [48:48] is the sub-list for extension extendee
-	0,  // [0:48] is the sub-list for field type_name
+	15,  // 0: buckley.browserd.v1.Envelope.request:type_name -> buckley.browserd.v1.Request
+	16,  // 1: buckley.browserd.v1.Envelope.response:type_name -> buckley.browserd.v1.Response
+	128, // 2: buckley.browserd.v1.Envelope.event:type_name -> buckley.browserd.v1.StreamEvent
+	0,   // 3: buckley.browserd.v1.Error.code_enum:type_name -> buckley.browserd.v1.ErrorCode
+	17,  // 4: buckley.browserd.v1.Request.create_session:type_name -> buckley.browserd.v1.CreateSessionRequest
+	19,  // 5: buckley.browserd.v1.Request.navigate:type_name -> buckley.browserd.v1.NavigateRequest
+	23,  // 6: buckley.browserd.v1.Request.observe:type_name -> buckley.browserd.v1.ObserveRequest
+	25,  // 7: buckley.browserd.v1.Request.act:type_name -> buckley.browserd.v1.ActRequest
+	29,  // 8: buckley.browserd.v1.Request.close_session:type_name -> buckley.browserd.v1.CloseSessionRequest
+	31,  // 9: buckley.browserd.v1.Request.stream_subscribe:type_name -> buckley.browserd.v1.StreamSubscribeRequest
+	35,  // 10: buckley.browserd.v1.Request.list_sessions:type_name -> buckley.browserd.v1.ListSessionsRequest
+	37,  // 11: buckley.browserd.v1.Request.check_contrast:type_name -> buckley.browserd.v1.CheckContrastRequest
+	40,  // 12: buckley.browserd.v1.Request.get_info:type_name -> buckley.browserd.v1.GetInfoRequest
+	43,  // 13: buckley.browserd.v1.Request.export_pdf:type_name -> buckley.browserd.v1.ExportPdfRequest
+	46,  // 14: buckley.browserd.v1.Request.get_cookies:type_name -> buckley.browserd.v1.GetCookiesRequest
+	48,  // 15: buckley.browserd.v1.Request.set_cookie:type_name -> buckley.browserd.v1.SetCookieRequest
+	50,  // 16: buckley.browserd.v1.Request.clear_cookies:type_name -> buckley.browserd.v1.ClearCookiesRequest
+	61,  // 17: buckley.browserd.v1.Request.wait_for:type_name -> buckley.browserd.v1.WaitForRequest
+	63,  // 18: buckley.browserd.v1.Request.shutdown:type_name -> buckley.browserd.v1.ShutdownRequest
+	69,  // 19: buckley.browserd.v1.Request.authenticate:type_name -> buckley.browserd.v1.AuthenticateRequest
+	72,  // 20: buckley.browserd.v1.Request.open_tab:type_name -> buckley.browserd.v1.OpenTabRequest
+	74,  // 21: buckley.browserd.v1.Request.close_tab:type_name -> buckley.browserd.v1.CloseTabRequest
+	76,  // 22: buckley.browserd.v1.Request.switch_tab:type_name -> buckley.browserd.v1.SwitchTabRequest
+	78,  // 23: buckley.browserd.v1.Request.list_tabs:type_name -> buckley.browserd.v1.ListTabsRequest
+	80,  // 24: buckley.browserd.v1.Request.extract_text:type_name -> buckley.browserd.v1.ExtractTextRequest
+	93,  // 25: buckley.browserd.v1.Request.set_viewport:type_name -> buckley.browserd.v1.SetViewportRequest
+	95,  // 26: buckley.browserd.v1.Request.save_session:type_name -> buckley.browserd.v1.SaveSessionRequest
+	97,  // 27: buckley.browserd.v1.Request.restore_session:type_name -> buckley.browserd.v1.RestoreSessionRequest
+	103, // 28: buckley.browserd.v1.Request.clipboard_get:type_name -> buckley.browserd.v1.ClipboardGetRequest
+	105, // 29: buckley.browserd.v1.Request.clipboard_set:type_name -> buckley.browserd.v1.ClipboardSetRequest
+	82,  // 30: buckley.browserd.v1.Request.fill_form:type_name -> buckley.browserd.v1.FillFormRequest
+	86,  // 31: buckley.browserd.v1.Request.set_file_input:type_name -> buckley.browserd.v1.SetFileInputRequest
+	89,  // 32: buckley.browserd.v1.Request.list_downloads:type_name -> buckley.browserd.v1.ListDownloadsRequest
+	91,  // 33: buckley.browserd.v1.Request.get_download:type_name -> buckley.browserd.v1.GetDownloadRequest
+	33,  // 34: buckley.browserd.v1.Request.session_stats:type_name -> buckley.browserd.v1.SessionStatsRequest
+	27,  // 35: buckley.browserd.v1.Request.batch_actions:type_name -> buckley.browserd.v1.BatchActionsRequest
+	53,  // 36: buckley.browserd.v1.Request.set_storage:type_name -> buckley.browserd.v1.SetStorageRequest
+	55,  // 37: buckley.browserd.v1.Request.get_storage:type_name -> buckley.browserd.v1.GetStorageRequest
+	57,  // 38: buckley.browserd.v1.Request.clear_storage:type_name -> buckley.browserd.v1.ClearStorageRequest
+	59,  // 39: buckley.browserd.v1.Request.clear_browsing_data:type_name -> buckley.browserd.v1.ClearBrowsingDataRequest
+	21,  // 40: buckley.browserd.v1.Request.stop:type_name -> buckley.browserd.v1.StopRequest
+	67,  // 41: buckley.browserd.v1.Request.set_log_level:type_name -> buckley.browserd.v1.SetLogLevelRequest
+	65,  // 42: buckley.browserd.v1.Request.query_element:type_name -> buckley.browserd.v1.QueryElementRequest
+	14,  // 43: buckley.browserd.v1.Response.error:type_name -> buckley.browserd.v1.Error
+	18,  // 44: buckley.browserd.v1.Response.create_session:type_name -> buckley.browserd.v1.CreateSessionResponse
+	20,  // 45: buckley.browserd.v1.Response.navigate:type_name -> buckley.browserd.v1.NavigateResponse
+	24,  // 46: buckley.browserd.v1.Response.observe:type_name -> buckley.browserd.v1.ObserveResponse
+	26,  // 47: buckley.browserd.v1.Response.act:type_name -> buckley.browserd.v1.ActResponse
+	30,  // 48: buckley.browserd.v1.Response.close_session:type_name -> buckley.browserd.v1.CloseSessionResponse
+	32,  // 49: buckley.browserd.v1.Response.stream_subscribe:type_name -> buckley.browserd.v1.StreamSubscribeResponse
+	36,  // 50: buckley.browserd.v1.Response.list_sessions:type_name -> buckley.browserd.v1.ListSessionsResponse
+	38,  // 51: buckley.browserd.v1.Response.check_contrast:type_name -> buckley.browserd.v1.CheckContrastResponse
+	41,  // 52: buckley.browserd.v1.Response.get_info:type_name -> buckley.browserd.v1.GetInfoResponse
+	44,  // 53: buckley.browserd.v1.Response.export_pdf:type_name -> buckley.browserd.v1.ExportPdfResponse
+	47,  // 54: buckley.browserd.v1.Response.get_cookies:type_name -> buckley.browserd.v1.GetCookiesResponse
+	49,  // 55: buckley.browserd.v1.Response.set_cookie:type_name -> buckley.browserd.v1.SetCookieResponse
+	51,  // 56: buckley.browserd.v1.Response.clear_cookies:type_name -> buckley.browserd.v1.ClearCookiesResponse
+	62,  // 57: buckley.browserd.v1.Response.wait_for:type_name -> buckley.browserd.v1.WaitForResponse
+	64,  // 58: buckley.browserd.v1.Response.shutdown:type_name -> buckley.browserd.v1.ShutdownResponse
+	70,  // 59: buckley.browserd.v1.Response.authenticate:type_name -> buckley.browserd.v1.AuthenticateResponse
+	73,  // 60: buckley.browserd.v1.Response.open_tab:type_name -> buckley.browserd.v1.OpenTabResponse
+	75,  // 61: buckley.browserd.v1.Response.close_tab:type_name -> buckley.browserd.v1.CloseTabResponse
+	77,  // 62: buckley.browserd.v1.Response.switch_tab:type_name -> buckley.browserd.v1.SwitchTabResponse
+	79,  // 63: buckley.browserd.v1.Response.list_tabs:type_name -> buckley.browserd.v1.ListTabsResponse
+	81,  // 64: buckley.browserd.v1.Response.extract_text:type_name -> buckley.browserd.v1.ExtractTextResponse
+	94,  // 65: buckley.browserd.v1.Response.set_viewport:type_name -> buckley.browserd.v1.SetViewportResponse
+	96,  // 66: buckley.browserd.v1.Response.save_session:type_name -> buckley.browserd.v1.SaveSessionResponse
+	98,  // 67: buckley.browserd.v1.Response.restore_session:type_name -> buckley.browserd.v1.RestoreSessionResponse
+	104, // 68: buckley.browserd.v1.Response.clipboard_get:type_name -> buckley.browserd.v1.ClipboardGetResponse
+	106, // 69: buckley.browserd.v1.Response.clipboard_set:type_name -> buckley.browserd.v1.ClipboardSetResponse
+	84,  // 70: buckley.browserd.v1.Response.fill_form:type_name -> buckley.browserd.v1.FillFormResponse
+	87,  // 71: buckley.browserd.v1.Response.set_file_input:type_name -> buckley.browserd.v1.SetFileInputResponse
+	90,  // 72: buckley.browserd.v1.Response.list_downloads:type_name -> buckley.browserd.v1.ListDownloadsResponse
+	92,  // 73: buckley.browserd.v1.Response.get_download:type_name -> buckley.browserd.v1.GetDownloadResponse
+	34,  // 74: buckley.browserd.v1.Response.session_stats:type_name -> buckley.browserd.v1.SessionStatsResponse
+	28,  // 75: buckley.browserd.v1.Response.batch_actions:type_name -> buckley.browserd.v1.BatchActionsResponse
+	54,  // 76: buckley.browserd.v1.Response.set_storage:type_name -> buckley.browserd.v1.SetStorageResponse
+	56,  // 77: buckley.browserd.v1.Response.get_storage:type_name -> buckley.browserd.v1.GetStorageResponse
+	58,  // 78: buckley.browserd.v1.Response.clear_storage:type_name -> buckley.browserd.v1.ClearStorageResponse
+	60,  // 79: buckley.browserd.v1.Response.clear_browsing_data:type_name -> buckley.browserd.v1.ClearBrowsingDataResponse
+	22,  // 80: buckley.browserd.v1.Response.stop:type_name -> buckley.browserd.v1.StopResponse
+	68,  // 81: buckley.browserd.v1.Response.set_log_level:type_name -> buckley.browserd.v1.SetLogLevelResponse
+	66,  // 82: buckley.browserd.v1.Response.query_element:type_name -> buckley.browserd.v1.QueryElementResponse
+	100, // 83: buckley.browserd.v1.CreateSessionRequest.config:type_name -> buckley.browserd.v1.SessionConfig
+	99,  // 84: buckley.browserd.v1.CreateSessionResponse.session:type_name -> buckley.browserd.v1.SessionInfo
+	113, // 85: buckley.browserd.v1.CreateSessionResponse.observation:type_name -> buckley.browserd.v1.Observation
+	113, // 86: buckley.browserd.v1.NavigateResponse.observation:type_name -> buckley.browserd.v1.Observation
+	113, // 87: buckley.browserd.v1.StopResponse.observation:type_name -> buckley.browserd.v1.Observation
+	108, // 88: buckley.browserd.v1.ObserveRequest.options:type_name -> buckley.browserd.v1.ObserveOptions
+	113, // 89: buckley.browserd.v1.ObserveResponse.observation:type_name -> buckley.browserd.v1.Observation
+	123, // 90: buckley.browserd.v1.ActRequest.action:type_name -> buckley.browserd.v1.Action
+	126, // 91: buckley.browserd.v1.ActResponse.result:type_name -> buckley.browserd.v1.ActionResult
+	123, // 92: buckley.browserd.v1.BatchActionsRequest.actions:type_name -> buckley.browserd.v1.Action
+	126, // 93: buckley.browserd.v1.BatchActionsResponse.results:type_name -> buckley.browserd.v1.ActionResult
+	14,  // 94: buckley.browserd.v1.BatchActionsResponse.error:type_name -> buckley.browserd.v1.Error
+	112, // 95: buckley.browserd.v1.StreamSubscribeRequest.options:type_name -> buckley.browserd.v1.StreamOptions
+	99,  // 96: buckley.browserd.v1.ListSessionsResponse.sessions:type_name -> buckley.browserd.v1.SessionInfo
+	39,  // 97: buckley.browserd.v1.CheckContrastResponse.results:type_name -> buckley.browserd.v1.ContrastResult
+	42,  // 98: buckley.browserd.v1.GetInfoResponse.engine:type_name -> buckley.browserd.v1.EngineInfo
+	101, // 99: buckley.browserd.v1.ExportPdfRequest.page_size:type_name -> buckley.browserd.v1.Viewport
+	45,  // 100: buckley.browserd.v1.GetCookiesResponse.cookies:type_name -> buckley.browserd.v1.Cookie
+	45,  // 101: buckley.browserd.v1.SetCookieRequest.cookie:type_name -> buckley.browserd.v1.Cookie
+	1,   // 102: buckley.browserd.v1.SetStorageRequest.scope:type_name -> buckley.browserd.v1.StorageScope
+	52,  // 103: buckley.browserd.v1.SetStorageRequest.entries:type_name -> buckley.browserd.v1.StorageEntry
+	1,   // 104: buckley.browserd.v1.GetStorageRequest.scope:type_name -> buckley.browserd.v1.StorageScope
+	52,  // 105: buckley.browserd.v1.GetStorageResponse.entries:type_name -> buckley.browserd.v1.StorageEntry
+	1,   // 106: buckley.browserd.v1.ClearStorageRequest.scope:type_name -> buckley.browserd.v1.StorageScope
+	113, // 107: buckley.browserd.v1.WaitForResponse.observation:type_name -> buckley.browserd.v1.Observation
+	8,   // 108: buckley.browserd.v1.QueryElementRequest.selector_type:type_name -> buckley.browserd.v1.SelectorType
+	130, // 109: buckley.browserd.v1.QueryElementResponse.properties:type_name -> buckley.browserd.v1.QueryElementResponse.PropertiesEntry
+	2,   // 110: buckley.browserd.v1.SetLogLevelRequest.level:type_name -> buckley.browserd.v1.LogLevel
+	2,   // 111: buckley.browserd.v1.SetLogLevelResponse.previous:type_name -> buckley.browserd.v1.LogLevel
+	2,   // 112: buckley.browserd.v1.SetLogLevelResponse.level:type_name -> buckley.browserd.v1.LogLevel
+	71,  // 113: buckley.browserd.v1.OpenTabResponse.tab:type_name -> buckley.browserd.v1.Tab
+	113, // 114: buckley.browserd.v1.SwitchTabResponse.observation:type_name -> buckley.browserd.v1.Observation
+	71,  // 115: buckley.browserd.v1.ListTabsResponse.tabs:type_name -> buckley.browserd.v1.Tab
+	83,  // 116: buckley.browserd.v1.FillFormRequest.fields:type_name -> buckley.browserd.v1.FormField
+	85,  // 117: buckley.browserd.v1.FillFormResponse.results:type_name -> buckley.browserd.v1.FieldResult
+	88,  // 118: buckley.browserd.v1.ListDownloadsResponse.downloads:type_name -> buckley.browserd.v1.Download
+	88,  // 119: buckley.browserd.v1.GetDownloadResponse.download:type_name -> buckley.browserd.v1.Download
+	101, // 120: buckley.browserd.v1.SetViewportRequest.viewport:type_name -> buckley.browserd.v1.Viewport
+	113, // 121: buckley.browserd.v1.SetViewportResponse.observation:type_name -> buckley.browserd.v1.Observation
+	99,  // 122: buckley.browserd.v1.RestoreSessionResponse.session:type_name -> buckley.browserd.v1.SessionInfo
+	113, // 123: buckley.browserd.v1.RestoreSessionResponse.observation:type_name -> buckley.browserd.v1.Observation
+	101, // 124: buckley.browserd.v1.SessionConfig.viewport:type_name -> buckley.browserd.v1.Viewport
+	107, // 125: buckley.browserd.v1.SessionConfig.clipboard:type_name -> buckley.browserd.v1.ClipboardPolicy
+	131, // 126: buckley.browserd.v1.SessionConfig.request_headers:type_name -> buckley.browserd.v1.SessionConfig.RequestHeadersEntry
+	3,   // 127: buckley.browserd.v1.ClipboardData.mode:type_name -> buckley.browserd.v1.ClipboardMode
+	102, // 128: buckley.browserd.v1.ClipboardGetResponse.data:type_name -> buckley.browserd.v1.ClipboardData
+	102, // 129: buckley.browserd.v1.ClipboardSetResponse.data:type_name -> buckley.browserd.v1.ClipboardData
+	3,   // 130: buckley.browserd.v1.ClipboardPolicy.mode:type_name -> buckley.browserd.v1.ClipboardMode
+	121, // 131: buckley.browserd.v1.ObserveOptions.clip_rect:type_name -> buckley.browserd.v1.Rect
+	132, // 132: buckley.browserd.v1.ComputedStyles.nodes:type_name -> buckley.browserd.v1.ComputedStyles.NodesEntry
+	133, // 133: buckley.browserd.v1.NodeStyle.properties:type_name -> buckley.browserd.v1.NodeStyle.PropertiesEntry
+	4,   // 134: buckley.browserd.v1.StreamOptions.compression:type_name -> buckley.browserd.v1.StreamCompression
+	118, // 135: buckley.browserd.v1.Observation.frame:type_name -> buckley.browserd.v1.Frame
+	119, // 136: buckley.browserd.v1.Observation.hit_test:type_name -> buckley.browserd.v1.HitTestMap
+	134, // 137: buckley.browserd.v1.Observation.timestamp:type_name -> google.protobuf.Timestamp
+	117, // 138: buckley.browserd.v1.Observation.text_runs:type_name -> buckley.browserd.v1.TextRun
+	42,  // 139: buckley.browserd.v1.Observation.engine_info:type_name -> buckley.browserd.v1.EngineInfo
+	110, // 140: buckley.browserd.v1.Observation.computed_styles:type_name -> buckley.browserd.v1.ComputedStyles
+	109, // 141: buckley.browserd.v1.Observation.navigation_timing:type_name -> buckley.browserd.v1.NavigationTiming
+	115, // 142: buckley.browserd.v1.Observation.console_messages:type_name -> buckley.browserd.v1.ConsoleMessage
+	116, // 143: buckley.browserd.v1.Observation.page_errors:type_name -> buckley.browserd.v1.PageError
+	114, // 144: buckley.browserd.v1.Observation.page_metadata:type_name -> buckley.browserd.v1.PageMetadata
+	134, // 145: buckley.browserd.v1.ConsoleMessage.timestamp:type_name -> google.protobuf.Timestamp
+	121, // 146: buckley.browserd.v1.TextRun.bounds:type_name -> buckley.browserd.v1.Rect
+	5,   // 147: buckley.browserd.v1.Frame.format:type_name -> buckley.browserd.v1.FrameFormat
+	134, // 148: buckley.browserd.v1.Frame.timestamp:type_name -> google.protobuf.Timestamp
+	134, // 149: buckley.browserd.v1.Frame.presentation_timestamp:type_name -> google.protobuf.Timestamp
+	120, // 150: buckley.browserd.v1.HitTestMap.regions:type_name -> buckley.browserd.v1.HitRegion
+	121, // 151: buckley.browserd.v1.HitRegion.bounds:type_name -> buckley.browserd.v1.Rect
+	10,  // 152: buckley.browserd.v1.Action.type:type_name -> buckley.browserd.v1.ActionType
+	124, // 153: buckley.browserd.v1.Action.target:type_name -> buckley.browserd.v1.ActionTarget
+	125, // 154: buckley.browserd.v1.Action.scroll:type_name -> buckley.browserd.v1.ScrollDelta
+	11,  // 155: buckley.browserd.v1.Action.modifiers:type_name -> buckley.browserd.v1.KeyModifier
+	124, // 156: buckley.browserd.v1.Action.drag_to:type_name -> buckley.browserd.v1.ActionTarget
+	7,   // 157: buckley.browserd.v1.Action.button:type_name -> buckley.browserd.v1.MouseButton
+	6,   // 158: buckley.browserd.v1.Action.on_stale:type_name -> buckley.browserd.v1.StalePolicy
+	122, // 159: buckley.browserd.v1.ActionTarget.point:type_name -> buckley.browserd.v1.Point
+	8,   // 160: buckley.browserd.v1.ActionTarget.selector_type:type_name -> buckley.browserd.v1.SelectorType
+	9,   // 161: buckley.browserd.v1.ScrollDelta.unit:type_name -> buckley.browserd.v1.ScrollUnit
+	113, // 162: buckley.browserd.v1.ActionResult.observation:type_name -> buckley.browserd.v1.Observation
+	127, // 163: buckley.browserd.v1.ActionResult.effects:type_name -> buckley.browserd.v1.Effect
+	122, // 164: buckley.browserd.v1.ActionResult.resolved_point:type_name -> buckley.browserd.v1.Point
+	135, // 165: buckley.browserd.v1.Effect.metadata:type_name -> google.protobuf.Struct
+	12,  // 166: buckley.browserd.v1.StreamEvent.type:type_name -> buckley.browserd.v1.StreamEventType
+	118, // 167: buckley.browserd.v1.StreamEvent.frame:type_name -> buckley.browserd.v1.Frame
+	119, // 168: buckley.browserd.v1.StreamEvent.hit_test:type_name -> buckley.browserd.v1.HitTestMap
+	134, // 169: buckley.browserd.v1.StreamEvent.timestamp:type_name -> google.protobuf.Timestamp
+	129, // 170: buckley.browserd.v1.StreamEvent.stats:type_name -> buckley.browserd.v1.StreamStats
+	115, // 171: buckley.browserd.v1.StreamEvent.console_messages:type_name -> buckley.browserd.v1.ConsoleMessage
+	111, // 172: buckley.browserd.v1.ComputedStyles.NodesEntry.value:type_name -> buckley.browserd.v1.NodeStyle
+	173, // [173:173] is the sub-list for method output_type
+	173, // [173:173] is the sub-list for method input_type
+	173, // [173:173] is the sub-list for extension type_name
+	173, // [173:173] is the sub-list for extension extendee
+	0,   // [0:173] is the sub-list for field type_name
 }
 
 func init() { file_browserd_proto_init() }
@@ -2980,6 +10312,39 @@ func file_browserd_proto_init() {
 		(*Request_Act)(nil),
 		(*Request_CloseSession)(nil),
 		(*Request_StreamSubscribe)(nil),
+		(*Request_ListSessions)(nil),
+		(*Request_CheckContrast)(nil),
+		(*Request_GetInfo)(nil),
+		(*Request_ExportPdf)(nil),
+		(*Request_GetCookies)(nil),
+		(*Request_SetCookie)(nil),
+		(*Request_ClearCookies)(nil),
+		(*Request_WaitFor)(nil),
+		(*Request_Shutdown)(nil),
+		(*Request_Authenticate)(nil),
+		(*Request_OpenTab)(nil),
+		(*Request_CloseTab)(nil),
+		(*Request_SwitchTab)(nil),
+		(*Request_ListTabs)(nil),
+		(*Request_ExtractText)(nil),
+		(*Request_SetViewport)(nil),
+		(*Request_SaveSession)(nil),
+		(*Request_RestoreSession)(nil),
+		(*Request_ClipboardGet)(nil),
+		(*Request_ClipboardSet)(nil),
+		(*Request_FillForm)(nil),
+		(*Request_SetFileInput)(nil),
+		(*Request_ListDownloads)(nil),
+		(*Request_GetDownload)(nil),
+		(*Request_SessionStats)(nil),
+		(*Request_BatchActions)(nil),
+		(*Request_SetStorage)(nil),
+		(*Request_GetStorage)(nil),
+		(*Request_ClearStorage)(nil),
+		(*Request_ClearBrowsingData)(nil),
+		(*Request_Stop)(nil),
+		(*Request_SetLogLevel)(nil),
+		(*Request_QueryElement)(nil),
 	}
 	file_browserd_proto_msgTypes[3].OneofWrappers = []any{
 		(*Response_CreateSession)(nil),
@@ -2988,14 +10353,50 @@ func file_browserd_proto_init() {
 		(*Response_Act)(nil),
 		(*Response_CloseSession)(nil),
 		(*Response_StreamSubscribe)(nil),
-	}
+		(*Response_ListSessions)(nil),
+		(*Response_CheckContrast)(nil),
+		(*Response_GetInfo)(nil),
+		(*Response_ExportPdf)(nil),
+		(*Response_GetCookies)(nil),
+		(*Response_SetCookie)(nil),
+		(*Response_ClearCookies)(nil),
+		(*Response_WaitFor)(nil),
+		(*Response_Shutdown)(nil),
+		(*Response_Authenticate)(nil),
+		(*Response_OpenTab)(nil),
+		(*Response_CloseTab)(nil),
+		(*Response_SwitchTab)(nil),
+		(*Response_ListTabs)(nil),
+		(*Response_ExtractText)(nil),
+		(*Response_SetViewport)(nil),
+		(*Response_SaveSession)(nil),
+		(*Response_RestoreSession)(nil),
+		(*Response_ClipboardGet)(nil),
+		(*Response_ClipboardSet)(nil),
+		(*Response_FillForm)(nil),
+		(*Response_SetFileInput)(nil),
+		(*Response_ListDownloads)(nil),
+		(*Response_GetDownload)(nil),
+		(*Response_SessionStats)(nil),
+		(*Response_BatchActions)(nil),
+		(*Response_SetStorage)(nil),
+		(*Response_GetStorage)(nil),
+		(*Response_ClearStorage)(nil),
+		(*Response_ClearBrowsingData)(nil),
+		(*Response_Stop)(nil),
+		(*Response_SetLogLevel)(nil),
+		(*Response_QueryElement)(nil),
+	}
+	file_browserd_proto_msgTypes[10].OneofWrappers = []any{}
+	file_browserd_proto_msgTypes[87].OneofWrappers = []any{}
+	file_browserd_proto_msgTypes[111].OneofWrappers = []any{}
 	type x struct{}
 	out := protoimpl.TypeBuilder{
 		File: protoimpl.DescBuilder{
 			GoPackagePath: reflect.TypeOf(x{}).PkgPath(),
 			RawDescriptor: unsafe.Slice(unsafe.StringData(file_browserd_proto_rawDesc), len(file_browserd_proto_rawDesc)),
-			NumEnums:      6,
-			NumMessages:   34,
+			NumEnums:      13,
+			NumMessages:   121,
 			NumExtensions: 0,
 			NumServices:   0,
 		},